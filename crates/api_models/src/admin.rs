@@ -504,6 +504,22 @@ pub struct WebhookDetails {
     /// If this property is true, a webhook message is posted whenever a payment fails
     #[schema(example = true)]
     pub payment_failed_enabled: Option<bool>,
+
+    /// Additional webhook endpoints, each of which is posted only the events it is subscribed to.
+    /// The `webhook_url`, if configured, continues to receive all events.
+    pub webhook_endpoints: Option<Vec<WebhookEndpoint>>,
+}
+
+#[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookEndpoint {
+    /// The url for the webhook endpoint
+    #[schema(value_type = String, example = "www.ekart.com/webhooks/refunds")]
+    pub url: Secret<String>,
+
+    /// The types of events posted to this endpoint
+    #[schema(value_type = Vec<EventType>, example = json!(["refund_succeeded", "refund_failed"]))]
+    pub enabled_events: Vec<api_enums::EventType>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -945,6 +961,46 @@ pub struct ToggleKVRequest {
     pub kv_enabled: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StorageSchemeMigrationRequest {
+    #[serde(skip_deserializing)]
+    pub merchant_id: String,
+    /// The storage scheme to migrate the merchant to
+    #[schema(value_type = MerchantStorageScheme, example = "redis_kv")]
+    pub target_storage_scheme: api_enums::MerchantStorageScheme,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct StorageSchemeMigrationResponse {
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 255, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+    /// The identifier of the process tracker task running the migration
+    pub migration_id: String,
+    /// The storage scheme the merchant is migrated from
+    #[schema(value_type = MerchantStorageScheme, example = "postgres_only")]
+    pub source_storage_scheme: api_enums::MerchantStorageScheme,
+    /// The storage scheme the merchant is migrated to
+    #[schema(value_type = MerchantStorageScheme, example = "redis_kv")]
+    pub target_storage_scheme: api_enums::MerchantStorageScheme,
+    /// The current stage of the migration
+    #[schema(value_type = StorageSchemeMigrationStage, example = "waiting_for_drainer")]
+    pub stage: api_enums::StorageSchemeMigrationStage,
+    /// The drainer streams that still hold entries queued before the current stage started
+    pub pending_drainer_streams: Vec<String>,
+    /// The number of the merchant's Redis keys removed once they were drained
+    pub flushed_keys: Option<usize>,
+    /// The time at which the migration was started
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+    /// The time at which the migration last progressed
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub updated_at: time::PrimitiveDateTime,
+}
+
 #[derive(Debug, Clone, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
 pub struct MerchantConnectorDetailsWrap {
     /// Creds Identifier is to uniquely identify the credentials. Do not send any sensitive info in this field. And do not send the string "null".
@@ -1164,3 +1220,51 @@ pub struct BusinessProfileUpdate {
     /// Verified applepay domains for a particular profile
     pub applepay_verified_domains: Option<Vec<String>>,
 }
+
+#[derive(Clone, Debug, Default, Deserialize, ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookSigningKeyRotateRequest {
+    /// The duration in seconds for which outgoing webhooks continue to be signed with the previous
+    /// key, in addition to the new key. Until then, the `X-Webhook-Signature-512` header is signed
+    /// with the previous key alone, the timestamped header carrying signatures with both keys.
+    /// Defaults to 24 hours, and can be at most 7 days.
+    #[schema(example = 86400, maximum = 604800)]
+    pub overlap_window_secs: Option<u32>,
+}
+
+#[derive(Clone, Debug, ToSchema, Serialize)]
+pub struct WebhookSigningKeyRotateResponse {
+    /// The identifier for business profile
+    #[schema(max_length = 64, example = "pro_abcdefghijklmnopqrstuvwxyz")]
+    pub profile_id: String,
+
+    /// The new key used to sign the outgoing webhooks
+    pub payment_response_hash_key: String,
+
+    /// The time until which outgoing webhooks are also signed with the previous key
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub previous_key_expires_at: Option<time::PrimitiveDateTime>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct MerchantConnectorCircuitBreakerResponse {
+    /// The identifier for the Merchant Connector Account
+    #[schema(example = "mca_5apGeP94tMts6rg3U3kR")]
+    pub merchant_connector_id: String,
+
+    /// Whether the merchant connector account is being routed to
+    #[schema(value_type = CircuitBreakerState)]
+    pub state: api_enums::CircuitBreakerState,
+
+    /// The number of consecutive 5xx responses or timeouts received from the connector
+    pub consecutive_failures: u32,
+
+    /// The number of successful probes since the circuit was half-opened
+    pub probe_successes: u32,
+
+    /// The time at which the circuit was last opened
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub opened_at: Option<time::PrimitiveDateTime>,
+}
@@ -0,0 +1,76 @@
+use common_utils::custom_serde;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::enums as api_enums;
+
+#[derive(Clone, Debug, Default, Deserialize, ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuditLogListConstraints {
+    /// Whether the changes were made by a dashboard user or with the admin API key
+    #[schema(value_type = Option<AuditActorType>)]
+    pub actor_type: Option<api_enums::AuditActorType>,
+    /// The user who made the changes
+    pub actor_id: Option<String>,
+    /// The type of the resources changed, for example `api_keys`, `merchant_connector`,
+    /// `routing` or `user_role`
+    pub resource_type: Option<String>,
+    /// The resource changed
+    pub resource_id: Option<String>,
+    /// List the changes made at or after this time
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(default, with = "custom_serde::iso8601::option")]
+    pub start_time: Option<PrimitiveDateTime>,
+    /// List the changes made at or before this time
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(default, with = "custom_serde::iso8601::option")]
+    pub end_time: Option<PrimitiveDateTime>,
+    /// The maximum number of entries to return
+    pub limit: Option<i64>,
+    /// The number of entries to skip
+    pub offset: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct AuditLogResponse {
+    /// The identifier of the entry
+    pub id: i64,
+    /// Whether the change was made by a dashboard user or with the admin API key
+    #[schema(value_type = AuditActorType)]
+    pub actor_type: api_enums::AuditActorType,
+    /// The user who made the change, not set for the admin API key
+    pub actor_id: Option<String>,
+    /// The merchant whose resource was changed
+    pub merchant_id: Option<String>,
+    /// The operation performed, for example `ApiKeyUpdate`
+    pub flow: String,
+    /// The type of the resource changed
+    pub resource_type: String,
+    /// The resource changed, if identified in the path of the request, or the user invited if
+    /// they already exist
+    pub resource_id: Option<String>,
+    /// The identifier of the request which made the change
+    pub request_id: Option<String>,
+    /// The request, with its PII masked
+    #[schema(value_type = Option<Object>)]
+    pub request: Option<serde_json::Value>,
+    /// The resource before the change, with its PII masked
+    #[schema(value_type = Option<Object>)]
+    pub snapshot_before: Option<serde_json::Value>,
+    /// The response to the change, with its PII masked
+    #[schema(value_type = Option<Object>)]
+    pub snapshot_after: Option<serde_json::Value>,
+    /// The time at which the change was made
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct AuditLogListResponse {
+    /// The number of entries returned
+    pub count: usize,
+    /// The entries matching the constraints, the latest first
+    pub data: Vec<AuditLogResponse>,
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct CacheStatisticsResponse {
+    /// The statistics of the in-memory caches of the instance which served the request
+    pub caches: Vec<CacheStatistics>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct CacheStatistics {
+    /// The name of the cache
+    #[schema(example = "ACCOUNTS_CACHE")]
+    pub name: String,
+    /// The number of lookups which found an entry since the instance started
+    pub hits: u64,
+    /// The number of lookups which found no entry since the instance started
+    pub misses: u64,
+    /// The number of entries held by the cache
+    pub entry_count: u64,
+    /// An estimate of the memory held by the entries of the cache, not counting the memory their
+    /// values point to
+    pub estimated_size_in_bytes: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CacheInvalidationRequest {
+    /// Invalidate the entries whose key starts with the prefix, in every cache
+    KeyPrefix { prefix: String },
+    /// Invalidate the entries of the merchant, in every cache
+    Merchant { merchant_id: String },
+}
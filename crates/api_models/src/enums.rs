@@ -501,6 +501,31 @@ pub enum RetryAction {
     Requeue,
 }
 
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CircuitBreakerState {
+    /// The merchant connector account is routed to
+    #[default]
+    Closed,
+    /// The merchant connector account has failed repeatedly and is not routed to
+    Open,
+    /// The merchant connector account is being probed with a few payments after its cool-down
+    HalfOpen,
+}
+
 #[derive(Clone, Copy)]
 pub enum LockerChoice {
     Basilisk,
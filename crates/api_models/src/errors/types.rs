@@ -89,6 +89,7 @@ pub enum ApiErrorResponse {
     Gone(ApiError),
     Unprocessable(ApiError),
     InternalServerError(ApiError),
+    ServiceUnavailable(ApiError),
     NotImplemented(ApiError),
     ConnectorError(ApiError, #[serde(skip_serializing)] StatusCode),
     NotFound(ApiError),
@@ -118,6 +119,7 @@ impl ApiErrorResponse {
             | Self::Gone(i)
             | Self::Unprocessable(i)
             | Self::InternalServerError(i)
+            | Self::ServiceUnavailable(i)
             | Self::NotImplemented(i)
             | Self::NotFound(i)
             | Self::MethodNotAllowed(i)
@@ -135,6 +137,7 @@ impl ApiErrorResponse {
             | Self::Gone(i)
             | Self::Unprocessable(i)
             | Self::InternalServerError(i)
+            | Self::ServiceUnavailable(i)
             | Self::NotImplemented(i)
             | Self::NotFound(i)
             | Self::MethodNotAllowed(i)
@@ -155,7 +158,7 @@ impl ApiErrorResponse {
             | Self::MethodNotAllowed(_)
             | Self::NotFound(_)
             | Self::BadRequest(_) => "invalid_request",
-            Self::InternalServerError(_) => "api",
+            Self::InternalServerError(_) | Self::ServiceUnavailable(_) => "api",
             Self::ConnectorError(_, _) => "connector",
         }
     }
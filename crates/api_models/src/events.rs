@@ -19,13 +19,17 @@ use crate::{
     admin::*,
     analytics::{api_event::*, sdk_events::*, *},
     api_keys::*,
+    audit_log::*,
+    cache::*,
     cards_info::*,
     disputes::*,
     files::*,
     mandates::*,
     payment_methods::*,
     payments::*,
+    process_tracker::*,
     verifications::*,
+    webhook_events::*,
 };
 
 impl ApiEventMetric for TimeRange {}
@@ -55,7 +59,11 @@ impl_misc_api_event_type!(
     BusinessProfileCreate,
     RevokeApiKeyResponse,
     ToggleKVResponse,
+    CacheStatisticsResponse,
+    CacheInvalidationRequest,
     ToggleKVRequest,
+    StorageSchemeMigrationRequest,
+    StorageSchemeMigrationResponse,
     MerchantAccountDeleteResponse,
     MerchantAccountUpdate,
     CardInfoResponse,
@@ -89,7 +97,23 @@ impl_misc_api_event_type!(
     ApiLogsRequest,
     GetApiEventMetricRequest,
     SdkEventsRequest,
-    ReportRequest
+    ReportRequest,
+    WebhookSigningKeyRotateRequest,
+    WebhookSigningKeyRotateResponse,
+    EventListConstraints,
+    EventListItemResponse,
+    EventDeliveryAttemptResponse,
+    EventId,
+    MerchantConnectorCircuitBreakerResponse,
+    ProcessTrackerListConstraints,
+    ProcessTrackerResponse,
+    ProcessTrackerListResponse,
+    ProcessTrackerId,
+    ProcessTrackerRescheduleRequest,
+    ProcessTrackerFinishRequest,
+    AuditLogListConstraints,
+    AuditLogResponse,
+    AuditLogListResponse
 );
 
 #[cfg(feature = "stripe")]
@@ -6,10 +6,16 @@ use crate::user::{
     dashboard_metadata::{
         GetMetaDataRequest, GetMetaDataResponse, GetMultipleMetaDataPayload, SetMetaDataRequest,
     },
-    AuthorizeResponse, ChangePasswordRequest, ConnectAccountRequest, CreateInternalUserRequest,
-    DashboardEntryResponse, ForgotPasswordRequest, GetUsersResponse, InviteUserRequest,
-    InviteUserResponse, ResetPasswordRequest, SendVerifyEmailRequest, SignUpRequest,
-    SignUpWithMerchantIdRequest, SwitchMerchantIdRequest, UserMerchantCreate, VerifyEmailRequest,
+    sso::{
+        OidcAuthorizeRequest, OidcAuthorizeResponse, OidcCallbackRequest, OidcConfigResponse,
+        SetOidcConfigRequest,
+    },
+    AuthorizeResponse, BeginTotpResponse, ChangePasswordRequest, ConnectAccountRequest,
+    CreateInternalUserRequest, DashboardEntryResponse, ForgotPasswordRequest, GetUsersResponse,
+    InviteUserRequest, InviteUserResponse, RecoveryCodesResponse, ResetPasswordRequest,
+    ResetTwoFactorAuthRequest, SendVerifyEmailRequest, SignInResponse, SignUpRequest,
+    SignUpWithMerchantIdRequest, SwitchMerchantIdRequest, TwoFactorAuthSignInRequest,
+    UserMerchantCreate, VerifyEmailRequest, VerifyTotpRequest,
 };
 
 impl ApiEventMetric for DashboardEntryResponse {
@@ -21,6 +27,15 @@ impl ApiEventMetric for DashboardEntryResponse {
     }
 }
 
+impl ApiEventMetric for SignInResponse {
+    fn get_api_event_type(&self) -> Option<ApiEventsType> {
+        match self {
+            Self::DashboardEntry(response) => response.get_api_event_type(),
+            Self::TwoFactorAuth(_) => Some(ApiEventsType::Miscellaneous),
+        }
+    }
+}
+
 common_utils::impl_misc_api_event_type!(
     SignUpRequest,
     SignUpWithMerchantIdRequest,
@@ -40,7 +55,17 @@ common_utils::impl_misc_api_event_type!(
     InviteUserRequest,
     InviteUserResponse,
     VerifyEmailRequest,
-    SendVerifyEmailRequest
+    SendVerifyEmailRequest,
+    TwoFactorAuthSignInRequest,
+    BeginTotpResponse,
+    VerifyTotpRequest,
+    RecoveryCodesResponse,
+    ResetTwoFactorAuthRequest,
+    SetOidcConfigRequest,
+    OidcConfigResponse,
+    OidcAuthorizeRequest,
+    OidcAuthorizeResponse,
+    OidcCallbackRequest
 );
 
 #[cfg(feature = "dummy_connector")]
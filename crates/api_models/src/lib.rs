@@ -2,7 +2,9 @@
 pub mod admin;
 pub mod analytics;
 pub mod api_keys;
+pub mod audit_log;
 pub mod bank_accounts;
+pub mod cache;
 pub mod cards_info;
 pub mod conditional_configs;
 pub mod connector_onboarding;
@@ -24,6 +26,7 @@ pub mod payments;
 #[cfg(feature = "payouts")]
 pub mod payouts;
 pub mod pm_auth;
+pub mod process_tracker;
 pub mod refunds;
 pub mod routing;
 pub mod surcharge_decision_configs;
@@ -31,4 +34,5 @@ pub mod user;
 pub mod user_role;
 pub mod verifications;
 pub mod verify_connector;
+pub mod webhook_events;
 pub mod webhooks;
@@ -0,0 +1,99 @@
+use common_utils::custom_serde;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::enums as api_enums;
+
+#[derive(Clone, Debug, Default, Deserialize, ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessTrackerListConstraints {
+    /// The runner of the tasks, for example `PAYMENTS_SYNC_WORKFLOW`
+    pub runner: Option<String>,
+    /// The status of the tasks
+    #[schema(value_type = Option<ProcessTrackerStatus>)]
+    pub status: Option<api_enums::ProcessTrackerStatus>,
+    /// The merchant the tasks were scheduled for
+    pub merchant_id: Option<String>,
+    /// List the tasks scheduled at or after this time
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(default, with = "custom_serde::iso8601::option")]
+    pub schedule_time_start: Option<PrimitiveDateTime>,
+    /// List the tasks scheduled at or before this time
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(default, with = "custom_serde::iso8601::option")]
+    pub schedule_time_end: Option<PrimitiveDateTime>,
+    /// The maximum number of tasks to return, 100 by default
+    pub limit: Option<i64>,
+    /// The number of tasks to skip
+    pub offset: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ProcessTrackerResponse {
+    /// The identifier of the task
+    pub id: String,
+    /// The name of the task
+    pub name: Option<String>,
+    /// The tags attached to the task
+    pub tag: Vec<String>,
+    /// The runner that executes the task
+    pub runner: Option<String>,
+    /// The number of times the task has been retried by its workflow
+    pub retry_count: i32,
+    /// The number of times the task was recovered after the consumer holding it crashed
+    pub crash_count: i32,
+    /// The time at which the task is scheduled to run
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "custom_serde::iso8601::option")]
+    pub schedule_time: Option<PrimitiveDateTime>,
+    /// The data the workflow needs to run the task
+    #[schema(value_type = Object)]
+    pub tracking_data: serde_json::Value,
+    /// The workflow specific status of the task
+    pub business_status: String,
+    /// The status of the task in the scheduler
+    #[schema(value_type = ProcessTrackerStatus)]
+    pub status: api_enums::ProcessTrackerStatus,
+    /// The time at which the task was created
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    /// The time at which the task was last updated
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "custom_serde::iso8601")]
+    pub updated_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct ProcessTrackerListResponse {
+    /// The number of tasks returned
+    pub count: usize,
+    /// The tasks matching the constraints
+    pub data: Vec<ProcessTrackerResponse>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProcessTrackerId {
+    pub process_tracker_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessTrackerRescheduleRequest {
+    #[serde(skip_deserializing)]
+    pub process_tracker_id: String,
+    /// The time at which the task should run
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "custom_serde::iso8601")]
+    pub schedule_time: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessTrackerFinishRequest {
+    #[serde(skip_deserializing)]
+    pub process_tracker_id: String,
+    /// The business status to record on the task, defaults to `CANCELLED_BY_ADMIN`
+    pub business_status: Option<String>,
+}
@@ -113,6 +113,8 @@ impl EuclidDirFilter for ConnectorSelection {
         DirKeyKind::VoucherType,
         DirKeyKind::CardRedirectType,
         DirKeyKind::BankTransferType,
+        DirKeyKind::DayOfWeek,
+        DirKeyKind::TimeOfDay,
     ];
 }
 
@@ -159,6 +161,39 @@ pub struct ConnectorVolumeSplit {
     pub split: u8,
 }
 
+/// The maximum length of the window over which the authorization rate of connectors is computed
+pub const SUCCESS_RATE_MAX_WINDOW_MINUTES: u16 = 360;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SuccessRateBasedAlgorithm {
+    /// The connectors to route between. Connectors without enough attempts in the window to
+    /// compute an authorization rate are tried in the order they are listed in.
+    pub connectors: Vec<RoutableConnectorChoice>,
+    /// The length of the sliding window over which the authorization rate is computed
+    #[serde(default = "default_success_rate_window_minutes")]
+    pub window_minutes: u16,
+    /// The minimum number of attempts a connector needs in the window for its authorization rate
+    /// to be considered
+    #[serde(default = "default_success_rate_min_attempts")]
+    pub min_attempts: u32,
+    /// The percentage of payments routed to a random connector rather than the best performing
+    /// one, so that the authorization rate of every connector stays current
+    #[serde(default = "default_success_rate_exploration_percent")]
+    pub exploration_percent: u8,
+}
+
+fn default_success_rate_window_minutes() -> u16 {
+    60
+}
+
+fn default_success_rate_min_attempts() -> u32 {
+    20
+}
+
+fn default_success_rate_exploration_percent() -> u8 {
+    10
+}
+
 #[cfg(feature = "connector_choice_bcompat")]
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub enum RoutableChoiceKind {
@@ -330,6 +365,7 @@ pub enum RoutingAlgorithmKind {
     Priority,
     VolumeSplit,
     Advanced,
+    SuccessRateBased,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -351,6 +387,7 @@ pub enum RoutingAlgorithm {
     Priority(Vec<RoutableConnectorChoice>),
     VolumeSplit(Vec<ConnectorVolumeSplit>),
     Advanced(euclid::frontend::ast::Program<ConnectorSelection>),
+    SuccessRateBased(SuccessRateBasedAlgorithm),
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -360,6 +397,7 @@ pub enum RoutingAlgorithmSerde {
     Priority(Vec<RoutableConnectorChoice>),
     VolumeSplit(Vec<ConnectorVolumeSplit>),
     Advanced(euclid::frontend::ast::Program<ConnectorSelection>),
+    SuccessRateBased(SuccessRateBasedAlgorithm),
 }
 
 impl TryFrom<RoutingAlgorithmSerde> for RoutingAlgorithm {
@@ -379,6 +417,26 @@ impl TryFrom<RoutingAlgorithmSerde> for RoutingAlgorithm {
                 ))
                 .into_report()?
             }
+            RoutingAlgorithmSerde::SuccessRateBased(i) if i.connectors.is_empty() => {
+                Err(ParsingError::StructParseFailure(
+                    "Connectors list can't be empty for Success rate based Algorithm",
+                ))
+                .into_report()?
+            }
+            RoutingAlgorithmSerde::SuccessRateBased(i)
+                if i.window_minutes == 0 || i.window_minutes > SUCCESS_RATE_MAX_WINDOW_MINUTES =>
+            {
+                Err(ParsingError::StructParseFailure(
+                    "Window for Success rate based Algorithm must be between 1 and 360 minutes",
+                ))
+                .into_report()?
+            }
+            RoutingAlgorithmSerde::SuccessRateBased(i) if i.exploration_percent > 100 => {
+                Err(ParsingError::StructParseFailure(
+                    "Exploration percentage for Success rate based Algorithm can't exceed 100",
+                ))
+                .into_report()?
+            }
             _ => {}
         };
         Ok(match value {
@@ -386,6 +444,7 @@ impl TryFrom<RoutingAlgorithmSerde> for RoutingAlgorithm {
             RoutingAlgorithmSerde::Priority(i) => Self::Priority(i),
             RoutingAlgorithmSerde::VolumeSplit(i) => Self::VolumeSplit(i),
             RoutingAlgorithmSerde::Advanced(i) => Self::Advanced(i),
+            RoutingAlgorithmSerde::SuccessRateBased(i) => Self::SuccessRateBased(i),
         })
     }
 }
@@ -487,6 +546,7 @@ impl RoutingAlgorithm {
             Self::Priority(_) => RoutingAlgorithmKind::Priority,
             Self::VolumeSplit(_) => RoutingAlgorithmKind::VolumeSplit,
             Self::Advanced(_) => RoutingAlgorithmKind::Advanced,
+            Self::SuccessRateBased(_) => RoutingAlgorithmKind::SuccessRateBased,
         }
     }
 }
@@ -547,3 +607,120 @@ pub enum RoutingKind {
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 #[serde(transparent)]
 pub struct RoutingAlgorithmId(pub String);
+
+/// The maximum number of historical payments that can be replayed in a single simulation
+pub const ROUTING_SIMULATION_MAX_PAYMENTS: u32 = 10000;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoutingSimulationRequest {
+    /// The start of the time range of the payments to be replayed
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub starting_at: time::PrimitiveDateTime,
+    /// The end of the time range of the payments to be replayed
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub ending_at: time::PrimitiveDateTime,
+    /// The maximum number of payments to be replayed, capped at `ROUTING_SIMULATION_MAX_PAYMENTS`
+    pub limit: Option<u32>,
+    /// The business profile the payments should belong to
+    pub profile_id: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct RoutingSimulationPayloadWrapper {
+    pub algorithm_id: String,
+    pub simulation_request: RoutingSimulationRequest,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RoutingSimulationResponse {
+    pub algorithm_id: String,
+    /// The number of payments that were replayed through the algorithm
+    pub total_payment_count: u64,
+    /// The number of payments the algorithm could not be evaluated for, or which were not routed
+    /// to any connector in the first place
+    pub skipped_payment_count: u64,
+    /// The number of payments the algorithm would route to a connector other than the one that
+    /// was actually chosen
+    pub rerouted_payment_count: u64,
+    pub connectors: Vec<ConnectorSimulationResult>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ConnectorSimulationResult {
+    pub connector: String,
+    /// The number of payments the simulated algorithm would have routed to the connector
+    pub simulated_payment_count: u64,
+    /// The total amount per currency of the payments the simulated algorithm would have routed to
+    /// the connector
+    pub simulated_volume: Vec<CurrencyVolume>,
+    /// The number of payments that were actually routed to the connector
+    pub actual_payment_count: u64,
+    /// The total amount per currency of the payments that were actually routed to the connector
+    pub actual_volume: Vec<CurrencyVolume>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct CurrencyVolume {
+    pub currency: enums::Currency,
+    /// The total amount of the payments in the currency, in its minor unit
+    pub amount: i64,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct RoutingActivationHistoryQuery {
+    pub profile_id: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RoutingActivationRecord {
+    pub activation_id: String,
+    pub profile_id: Option<String>,
+    /// The algorithm that became active, `None` if the active algorithm was deactivated
+    pub algorithm_id: Option<String>,
+    /// The algorithm that was active before this activation
+    pub previous_algorithm_id: Option<String>,
+    pub action: enums::RoutingActivationAction,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: time::PrimitiveDateTime,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct RoutingRollbackRequest {
+    pub profile_id: Option<String>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct RoutingDiffRequest {
+    pub base_algorithm_id: String,
+    pub compare_algorithm_id: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RoutingDiffResponse {
+    pub base_algorithm_id: String,
+    pub compare_algorithm_id: String,
+    pub base_kind: RoutingAlgorithmKind,
+    pub compare_kind: RoutingAlgorithmKind,
+    pub changes: Vec<RoutingConfigChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct RoutingConfigChange {
+    /// The part of the algorithm that changed, such as `default_selection` or `rules.<rule_name>`
+    pub path: String,
+    pub change_type: RoutingConfigChangeType,
+    /// The value in the base algorithm, `None` if it was added in the compared algorithm
+    pub base: Option<serde_json::Value>,
+    /// The value in the compared algorithm, `None` if it was removed from the base algorithm
+    pub compare: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoutingConfigChangeType {
+    Added,
+    Removed,
+    Modified,
+}
@@ -5,6 +5,7 @@ use crate::user_role::UserStatus;
 pub mod dashboard_metadata;
 #[cfg(feature = "dummy_connector")]
 pub mod sample_data;
+pub mod sso;
 
 #[derive(serde::Deserialize, Debug, Clone, serde::Serialize)]
 pub struct SignUpWithMerchantIdRequest {
@@ -39,7 +40,53 @@ pub struct DashboardEntryResponse {
 
 pub type SignInRequest = SignUpRequest;
 
-pub type SignInResponse = DashboardEntryResponse;
+#[derive(serde::Serialize, Debug, Clone)]
+#[serde(tag = "flow_type", rename_all = "snake_case")]
+pub enum SignInResponse {
+    DashboardEntry(DashboardEntryResponse),
+    TwoFactorAuth(TwoFactorAuthRequiredResponse),
+}
+
+/// Returned once the password has been verified, for the second factor to be verified
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct TwoFactorAuthRequiredResponse {
+    /// The short-lived token to be sent along with the second factor
+    pub token: Secret<String>,
+    pub email: pii::Email,
+}
+
+#[derive(serde::Deserialize, Debug, Clone, serde::Serialize)]
+pub struct TwoFactorAuthSignInRequest {
+    pub token: Secret<String>,
+    pub totp: Option<Secret<String>>,
+    pub recovery_code: Option<Secret<String>>,
+}
+
+pub type TwoFactorAuthSignInResponse = DashboardEntryResponse;
+
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct BeginTotpResponse {
+    /// The secret, for it to be entered manually in the authenticator app
+    pub secret: Secret<String>,
+    pub totp_url: Secret<String>,
+    /// The `totp_url` as a QR code image
+    pub qr_code: Secret<String>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone, serde::Serialize)]
+pub struct VerifyTotpRequest {
+    pub totp: Secret<String>,
+}
+
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct RecoveryCodesResponse {
+    pub recovery_codes: Vec<Secret<String>>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone, serde::Serialize)]
+pub struct ResetTwoFactorAuthRequest {
+    pub user_id: String,
+}
 
 #[derive(serde::Deserialize, Debug, Clone, serde::Serialize)]
 pub struct ConnectAccountRequest {
@@ -127,7 +174,7 @@ pub struct VerifyEmailRequest {
     pub token: Secret<String>,
 }
 
-pub type VerifyEmailResponse = DashboardEntryResponse;
+pub type VerifyEmailResponse = SignInResponse;
 
 #[derive(serde::Deserialize, Debug, serde::Serialize)]
 pub struct SendVerifyEmailRequest {
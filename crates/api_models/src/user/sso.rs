@@ -0,0 +1,62 @@
+use masking::Secret;
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+pub struct OidcRoleMapping {
+    /// The group of the user in the identity provider
+    pub group: String,
+    /// The role given to the members of the group
+    pub role_id: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+pub struct SetOidcConfigRequest {
+    /// The URL of the identity provider, from which its discovery document is fetched
+    pub issuer_url: String,
+    pub client_id: String,
+    /// Required when the config is created, the existing secret is kept if not sent afterwards
+    pub client_secret: Option<Secret<String>>,
+    /// The dashboard URL to which the identity provider redirects after the sign in
+    pub redirect_url: String,
+    /// The claim of the ID token listing the groups of the user, `groups` if not sent
+    pub groups_claim: Option<String>,
+    /// The first mapping whose group the user belongs to decides their role
+    #[serde(default)]
+    pub role_mappings: Vec<OidcRoleMapping>,
+    /// The role given to users belonging to none of the mapped groups, such users are refused
+    /// if not set
+    pub default_role_id: Option<String>,
+    pub is_enabled: Option<bool>,
+}
+
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct OidcConfigResponse {
+    pub org_id: String,
+    pub merchant_id: String,
+    pub issuer_url: String,
+    pub client_id: String,
+    pub redirect_url: String,
+    pub groups_claim: String,
+    pub role_mappings: Vec<OidcRoleMapping>,
+    pub default_role_id: Option<String>,
+    pub is_enabled: bool,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+pub struct OidcAuthorizeRequest {
+    /// The organization whose identity provider the user signs in with
+    pub org_id: String,
+}
+
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct OidcAuthorizeResponse {
+    /// The identity provider URL to which the user is to be redirected
+    pub authorization_url: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
+pub struct OidcCallbackRequest {
+    pub state: String,
+    pub code: Secret<String>,
+}
+
+pub type OidcCallbackResponse = super::SignInResponse;
@@ -3,9 +3,11 @@ pub struct ListRolesResponse(pub Vec<RoleInfoResponse>);
 
 #[derive(Debug, serde::Serialize)]
 pub struct RoleInfoResponse {
-    pub role_id: &'static str,
+    pub role_id: String,
     pub permissions: Vec<Permission>,
-    pub role_name: &'static str,
+    pub role_name: String,
+    /// Whether the role has been created by the merchant, as opposed to a predefined role
+    pub is_custom: bool,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
@@ -13,7 +15,19 @@ pub struct GetRoleRequest {
     pub role_id: String,
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct CreateRoleRequest {
+    pub role_name: String,
+    pub permissions: Vec<Permission>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct UpdateRoleRequest {
+    pub role_name: Option<String>,
+    pub permissions: Option<Vec<Permission>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 pub enum Permission {
     PaymentRead,
     PaymentWrite,
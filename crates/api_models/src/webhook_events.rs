@@ -0,0 +1,77 @@
+use common_utils::custom_serde;
+use masking::Secret;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::enums as api_enums;
+
+#[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventListConstraints {
+    /// The identifier of the payment, refund, dispute or mandate to list the events for
+    pub object_id: String,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct EventListItemResponse {
+    /// The identifier of the event
+    pub event_id: String,
+    /// The type of the event
+    #[schema(value_type = EventType)]
+    pub event_type: api_enums::EventType,
+    /// The class of the event
+    #[schema(value_type = EventClass)]
+    pub event_class: api_enums::EventClass,
+    /// The identifier of the object the event was raised for
+    pub object_id: String,
+    /// The type of the object the event was raised for
+    #[schema(value_type = EventObjectType)]
+    pub object_type: api_enums::EventObjectType,
+    /// Whether the merchant has acknowledged the webhook for this event
+    pub is_webhook_notified: bool,
+    /// The number of times the webhook for this event has been delivered
+    pub delivery_attempts: i32,
+    /// The time at which the event was created
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "custom_serde::iso8601")]
+    pub created: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct EventDeliveryAttemptResponse {
+    /// The identifier of the delivery attempt
+    pub attempt_id: String,
+    /// The identifier of the event that was delivered
+    pub event_id: String,
+    /// The number of the delivery attempt, starting at 1
+    pub attempt_number: i32,
+    /// What caused the delivery attempt
+    #[schema(value_type = WebhookDeliveryAttemptTrigger)]
+    pub delivery_trigger: api_enums::WebhookDeliveryAttemptTrigger,
+    /// The URL the webhook was delivered to
+    pub request_url: String,
+    /// The payload sent to the merchant
+    #[schema(value_type = String)]
+    pub request_body: Secret<String>,
+    /// The HTTP status code returned by the merchant, if a response was received
+    pub response_status_code: Option<u16>,
+    /// The body of the response returned by the merchant, truncated if too long
+    #[schema(value_type = Option<String>)]
+    pub response_body: Option<Secret<String>>,
+    /// The error encountered while calling the merchant, if no response was received
+    pub error_message: Option<String>,
+    /// The time taken for the merchant to respond, in milliseconds
+    pub latency_ms: i64,
+    /// Whether the merchant acknowledged the webhook with a successful status code
+    pub is_delivered: bool,
+    /// The time at which the delivery was attempted
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "custom_serde::iso8601")]
+    pub created: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EventId {
+    pub event_id: String,
+}
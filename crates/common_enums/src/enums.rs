@@ -939,6 +939,98 @@ pub enum EventType {
     MandateRevoked,
 }
 
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "db_enum")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum EventClass {
+    Payments,
+    Refunds,
+    Disputes,
+    Mandates,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "db_enum")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum EventObjectType {
+    PaymentDetails,
+    RefundDetails,
+    DisputeDetails,
+    MandateDetails,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum WebhookDeliveryAttemptTrigger {
+    /// The first delivery of the webhook, made right after the event was raised
+    InitialAttempt,
+    /// A delivery scheduled by the process tracker, after a failed delivery
+    AutomaticRetry,
+    /// A delivery explicitly requested by the merchant
+    ManualRetry,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum RoutingActivationAction {
+    /// A routing algorithm was activated
+    Activate,
+    /// The active routing algorithm was deactivated
+    Deactivate,
+    /// The previously active routing algorithm was restored
+    Rollback,
+}
+
 // TODO: This decision about using KV mode or not,
 // should be taken at a top level rather than pushing it down to individual functions via an enum.
 #[derive(
@@ -948,6 +1040,7 @@ pub enum EventType {
     Default,
     Eq,
     PartialEq,
+    ToSchema,
     serde::Deserialize,
     serde::Serialize,
     strum::Display,
@@ -962,6 +1055,37 @@ pub enum MerchantStorageScheme {
     RedisKv,
 }
 
+/// Stages of the online migration of a merchant between storage schemes
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    ToSchema,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum StorageSchemeMigrationStage {
+    /// Dual-read is enabled, waiting for the drainer to apply the entries queued before the
+    /// migration started
+    WaitingForDrainer,
+    /// The KV writes of a merchant migrated to `PostgresOnly` have been paused, waiting for the
+    /// writes in flight to be queued
+    PausingWrites,
+    /// The KV writes of a merchant migrated to `PostgresOnly` are paused, waiting for the drainer
+    /// to apply the entries queued before the pause
+    WaitingForDrainerWithWritesPaused,
+    /// The merchant has been switched and dual-read is disabled
+    Completed,
+    /// The migration was aborted before the merchant was switched
+    Aborted,
+}
+
 #[derive(
     Clone,
     Copy,
@@ -2030,3 +2154,53 @@ pub enum ConnectorStatus {
     Inactive,
     Active,
 }
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "db_enum")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ProcessTrackerStatus {
+    // Picked by the producer
+    Processing,
+    // State when the task is added
+    New,
+    // Send to retry
+    Pending,
+    // Picked by consumer
+    ProcessStarted,
+    // Finished by consumer
+    Finish,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "db_enum")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditActorType {
+    // A dashboard user, authenticated with their JWT
+    User,
+    // Whoever holds the admin API key
+    AdminApiKey,
+}
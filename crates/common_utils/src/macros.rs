@@ -76,6 +76,33 @@ macro_rules! fallback_reverse_lookup_not_found {
     };
 }
 
+/// Falls back to `$b` when `$a` failed with a not found error and dual-read is enabled for the
+/// merchant on `$store`, which is the case while the merchant is migrated between storage schemes
+#[macro_export]
+macro_rules! fallback_dual_read_not_found {
+    ($store:expr, $merchant_id:expr, $a:expr, $b:expr) => {
+        match $a {
+            Ok(res) => Ok(res),
+            Err(err) => {
+                let is_not_found = match err.current_context() {
+                    errors::StorageError::ValueNotFound(_) => true,
+                    errors::StorageError::DatabaseError(data_err) => matches!(
+                        data_err.current_context(),
+                        diesel_models::errors::DatabaseError::NotFound
+                    ),
+                    _ => false,
+                };
+                if is_not_found && $store.is_dual_read_enabled($merchant_id).await {
+                    router_env::logger::info!(dual_read_fallback = %err);
+                    $b
+                } else {
+                    Err(err)
+                }
+            }
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! collect_missing_value_keys {
     [$(($key:literal, $option:expr)),+] => {
@@ -0,0 +1,55 @@
+use diesel::{Identifiable, Insertable, Queryable};
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::audit_log};
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = audit_log)]
+pub struct AuditLogNew {
+    pub actor_type: storage_enums::AuditActorType,
+    pub actor_id: Option<String>,
+    pub merchant_id: Option<String>,
+    pub flow: String,
+    pub resource_type: String,
+    pub resource_id: Option<String>,
+    pub request_id: Option<String>,
+    pub request: Option<serde_json::Value>,
+    pub snapshot_before: Option<serde_json::Value>,
+    pub snapshot_after: Option<serde_json::Value>,
+    pub created_at: PrimitiveDateTime,
+}
+
+/// The entries are never updated nor deleted once written
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = audit_log)]
+pub struct AuditLog {
+    pub id: i64,
+    pub actor_type: storage_enums::AuditActorType,
+    /// The user who made the change, not set for the admin API key
+    pub actor_id: Option<String>,
+    pub merchant_id: Option<String>,
+    pub flow: String,
+    pub resource_type: String,
+    pub resource_id: Option<String>,
+    pub request_id: Option<String>,
+    /// The request, with its PII masked
+    pub request: Option<serde_json::Value>,
+    /// The resource before the change, with its PII masked
+    pub snapshot_before: Option<serde_json::Value>,
+    /// The response to the change, with its PII masked
+    pub snapshot_after: Option<serde_json::Value>,
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AuditLogListConstraints {
+    pub merchant_id: Option<String>,
+    pub actor_type: Option<storage_enums::AuditActorType>,
+    pub actor_id: Option<String>,
+    pub resource_type: Option<String>,
+    pub resource_id: Option<String>,
+    pub start_time: Option<PrimitiveDateTime>,
+    pub end_time: Option<PrimitiveDateTime>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
@@ -32,6 +32,8 @@ pub struct BusinessProfile {
     pub is_recon_enabled: bool,
     #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
     pub applepay_verified_domains: Option<Vec<String>>,
+    pub previous_payment_response_hash_key: Option<String>,
+    pub previous_payment_response_hash_key_expires_at: Option<time::PrimitiveDateTime>,
 }
 
 #[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
@@ -55,6 +57,8 @@ pub struct BusinessProfileNew {
     pub is_recon_enabled: bool,
     #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
     pub applepay_verified_domains: Option<Vec<String>>,
+    pub previous_payment_response_hash_key: Option<String>,
+    pub previous_payment_response_hash_key_expires_at: Option<time::PrimitiveDateTime>,
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -75,6 +79,8 @@ pub struct BusinessProfileUpdateInternal {
     pub is_recon_enabled: Option<bool>,
     #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
     pub applepay_verified_domains: Option<Vec<String>>,
+    pub previous_payment_response_hash_key: Option<String>,
+    pub previous_payment_response_hash_key_expires_at: Option<time::PrimitiveDateTime>,
 }
 
 impl From<BusinessProfileNew> for BusinessProfile {
@@ -97,6 +103,9 @@ impl From<BusinessProfileNew> for BusinessProfile {
             payout_routing_algorithm: new.payout_routing_algorithm,
             is_recon_enabled: new.is_recon_enabled,
             applepay_verified_domains: new.applepay_verified_domains,
+            previous_payment_response_hash_key: new.previous_payment_response_hash_key,
+            previous_payment_response_hash_key_expires_at: new
+                .previous_payment_response_hash_key_expires_at,
         }
     }
 }
@@ -118,6 +127,8 @@ impl BusinessProfileUpdateInternal {
             payout_routing_algorithm,
             is_recon_enabled,
             applepay_verified_domains,
+            previous_payment_response_hash_key,
+            previous_payment_response_hash_key_expires_at,
         } = self;
         BusinessProfile {
             profile_name: profile_name.unwrap_or(source.profile_name),
@@ -136,6 +147,11 @@ impl BusinessProfileUpdateInternal {
             payout_routing_algorithm,
             is_recon_enabled: is_recon_enabled.unwrap_or(source.is_recon_enabled),
             applepay_verified_domains,
+            previous_payment_response_hash_key: previous_payment_response_hash_key
+                .or(source.previous_payment_response_hash_key),
+            previous_payment_response_hash_key_expires_at:
+                previous_payment_response_hash_key_expires_at
+                    .or(source.previous_payment_response_hash_key_expires_at),
             ..source
         }
     }
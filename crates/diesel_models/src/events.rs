@@ -16,6 +16,8 @@ pub struct EventNew {
     pub intent_reference_id: Option<String>,
     pub primary_object_id: String,
     pub primary_object_type: storage_enums::EventObjectType,
+    pub merchant_id: Option<String>,
+    pub business_profile_id: Option<String>,
 }
 
 #[derive(Debug)]
@@ -43,6 +45,21 @@ pub struct Event {
     pub primary_object_type: storage_enums::EventObjectType,
     #[serde(with = "custom_serde::iso8601")]
     pub created_at: PrimitiveDateTime,
+    pub delivery_attempts: i32,
+    pub merchant_id: Option<String>,
+    pub business_profile_id: Option<String>,
+}
+
+// Tracking data by process_tracker
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OutgoingWebhookTrackingData {
+    pub event_id: String,
+    pub merchant_id: String,
+    pub business_profile_id: String,
+    pub event_type: storage_enums::EventType,
+    pub event_class: storage_enums::EventClass,
+    pub primary_object_id: String,
+    pub primary_object_type: storage_enums::EventObjectType,
 }
 
 impl From<EventUpdate> for EventUpdateInternal {
@@ -0,0 +1,32 @@
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use time::PrimitiveDateTime;
+
+use crate::schema::idempotency_key;
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = idempotency_key)]
+pub struct IdempotencyKeyNew {
+    pub merchant_id: String,
+    pub key: String,
+    pub request_fingerprint: String,
+    pub expires_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = idempotency_key, primary_key(merchant_id, key))]
+pub struct IdempotencyKey {
+    pub merchant_id: String,
+    pub key: String,
+    pub request_fingerprint: String,
+    /// The response of the request, set once the request has completed
+    pub response: Option<String>,
+    pub created_at: PrimitiveDateTime,
+    pub expires_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = idempotency_key)]
+pub struct IdempotencyKeyUpdate {
+    pub response: Option<String>,
+    pub expires_at: PrimitiveDateTime,
+}
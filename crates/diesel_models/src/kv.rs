@@ -3,9 +3,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     address::{Address, AddressNew, AddressUpdateInternal},
+    customers::{Customer, CustomerNew, CustomerUpdateInternal},
     errors,
+    mandate::{Mandate, MandateNew, MandateUpdate},
     payment_attempt::{PaymentAttempt, PaymentAttemptNew, PaymentAttemptUpdate},
     payment_intent::{PaymentIntentNew, PaymentIntentUpdate},
+    payment_method::{PaymentMethod, PaymentMethodNew, PaymentMethodUpdate},
     refund::{Refund, RefundNew, RefundUpdate},
     reverse_lookup::ReverseLookupNew,
     PaymentIntent,
@@ -16,7 +19,7 @@ use crate::{
 pub enum DBOperation {
     Insert { insertable: Insertable },
     Update { updatable: Updateable },
-    Delete,
+    Delete { deletable: Deletable },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -55,6 +58,9 @@ pub enum Insertable {
     Refund(RefundNew),
     Address(Box<AddressNew>),
     ReverseLookUp(ReverseLookupNew),
+    Customer(CustomerNew),
+    Mandate(MandateNew),
+    PaymentMethod(PaymentMethodNew),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -64,6 +70,24 @@ pub enum Updateable {
     PaymentAttemptUpdate(PaymentAttemptUpdateMems),
     RefundUpdate(RefundUpdateMems),
     AddressUpdate(Box<AddressUpdateMems>),
+    CustomerUpdate(CustomerUpdateMems),
+    MandateUpdate(MandateUpdateMems),
+    PaymentMethodUpdate(PaymentMethodUpdateMems),
+}
+
+/// Rows deleted from redis, which are deleted from the database once the operations queued
+/// before the deletion are drained
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "table", content = "data")]
+pub enum Deletable {
+    Customer {
+        customer_id: String,
+        merchant_id: String,
+    },
+    PaymentMethod {
+        merchant_id: String,
+        payment_method_id: String,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -89,3 +113,21 @@ pub struct RefundUpdateMems {
     pub orig: Refund,
     pub update_data: RefundUpdate,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CustomerUpdateMems {
+    pub orig: Customer,
+    pub update_data: CustomerUpdateInternal,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MandateUpdateMems {
+    pub orig: Mandate,
+    pub update_data: MandateUpdate,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaymentMethodUpdateMems {
+    pub orig: PaymentMethod,
+    pub update_data: PaymentMethodUpdate,
+}
@@ -1,5 +1,6 @@
 pub mod address;
 pub mod api_keys;
+pub mod audit_log;
 pub mod business_profile;
 pub mod capture;
 pub mod cards_info;
@@ -17,6 +18,7 @@ pub mod file;
 #[allow(unused)]
 pub mod fraud_check;
 pub mod gsm;
+pub mod idempotency_key;
 #[cfg(feature = "kv_store")]
 pub mod kv;
 pub mod locker_mock_up;
@@ -25,6 +27,7 @@ pub mod mandate;
 pub mod merchant_account;
 pub mod merchant_connector_account;
 pub mod merchant_key_store;
+pub mod oidc_config;
 pub mod organization;
 pub mod payment_attempt;
 pub mod payment_intent;
@@ -36,11 +39,15 @@ pub mod process_tracker;
 pub mod query;
 pub mod refund;
 pub mod reverse_lookup;
+pub mod role;
 pub mod routing_algorithm;
+pub mod routing_algorithm_activation;
 #[allow(unused_qualifications)]
 pub mod schema;
 pub mod user;
+pub mod user_key_store;
 pub mod user_role;
+pub mod webhook_delivery_attempt;
 
 use diesel_impl::{DieselArray, OptionalDieselArray};
 
@@ -1,11 +1,12 @@
 use common_utils::pii;
 use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
 use masking::Secret;
+use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 
 use crate::{enums as storage_enums, schema::mandate};
 
-#[derive(Clone, Debug, Identifiable, Queryable)]
+#[derive(Clone, Debug, Identifiable, Queryable, Serialize, Deserialize)]
 #[diesel(table_name = mandate)]
 pub struct Mandate {
     pub id: i32,
@@ -35,7 +36,14 @@ pub struct Mandate {
 }
 
 #[derive(
-    router_derive::Setter, Clone, Debug, Default, Insertable, router_derive::DebugAsDisplay,
+    router_derive::Setter,
+    Clone,
+    Debug,
+    Default,
+    Insertable,
+    router_derive::DebugAsDisplay,
+    Serialize,
+    Deserialize,
 )]
 #[diesel(table_name = mandate)]
 pub struct MandateNew {
@@ -64,7 +72,7 @@ pub struct MandateNew {
     pub merchant_connector_id: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum MandateUpdate {
     StatusUpdate {
         mandate_status: storage_enums::MandateStatus,
@@ -113,3 +121,19 @@ impl From<MandateUpdate> for MandateUpdateInternal {
         }
     }
 }
+
+impl MandateUpdate {
+    pub fn apply_changeset(self, source: Mandate) -> Mandate {
+        let MandateUpdateInternal {
+            mandate_status,
+            amount_captured,
+            connector_mandate_ids,
+        } = self.into();
+        Mandate {
+            mandate_status: mandate_status.unwrap_or(source.mandate_status),
+            amount_captured: amount_captured.or(source.amount_captured),
+            connector_mandate_ids: connector_mandate_ids.or(source.connector_mandate_ids),
+            ..source
+        }
+    }
+}
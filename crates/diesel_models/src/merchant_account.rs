@@ -101,3 +101,23 @@ pub struct MerchantAccountUpdateInternal {
     pub recon_status: storage_enums::ReconStatus,
     pub payment_link_config: Option<serde_json::Value>,
 }
+
+/// Tracking data of the process tracker task migrating a merchant between storage schemes
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct StorageSchemeMigrationTrackingData {
+    pub merchant_id: String,
+    pub source_storage_scheme: storage_enums::MerchantStorageScheme,
+    pub target_storage_scheme: storage_enums::MerchantStorageScheme,
+    pub stage: storage_enums::StorageSchemeMigrationStage,
+    /// Last entries of the drainer streams holding the merchant's entries, recorded when the
+    /// current stage started
+    pub watermarks: Vec<DrainerStreamWatermark>,
+    /// Number of the merchant's Redis keys removed once they were drained
+    pub flushed_keys: Option<usize>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct DrainerStreamWatermark {
+    pub stream_name: String,
+    pub entry_id: String,
+}
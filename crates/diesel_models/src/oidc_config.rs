@@ -0,0 +1,130 @@
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use time::PrimitiveDateTime;
+
+use crate::{
+    encryption::Encryption,
+    schema::{oidc_config, oidc_identity},
+};
+
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = oidc_config, primary_key(org_id))]
+pub struct OidcConfig {
+    pub org_id: String,
+    pub merchant_id: String,
+    pub issuer_url: String,
+    pub client_id: String,
+    pub client_secret: Encryption,
+    pub redirect_url: String,
+    pub groups_claim: String,
+    pub role_mappings: serde_json::Value,
+    pub default_role_id: Option<String>,
+    pub is_enabled: bool,
+    pub created_by: String,
+    pub created_at: PrimitiveDateTime,
+    pub last_modified_by: String,
+    pub last_modified_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = oidc_config)]
+pub struct OidcConfigNew {
+    pub org_id: String,
+    pub merchant_id: String,
+    pub issuer_url: String,
+    pub client_id: String,
+    pub client_secret: Encryption,
+    pub redirect_url: String,
+    pub groups_claim: String,
+    pub role_mappings: serde_json::Value,
+    pub default_role_id: Option<String>,
+    pub is_enabled: bool,
+    pub created_by: String,
+    pub created_at: PrimitiveDateTime,
+    pub last_modified_by: String,
+    pub last_modified_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = oidc_config, treat_none_as_null = true)]
+pub struct OidcConfigUpdateInternal {
+    merchant_id: String,
+    issuer_url: String,
+    client_id: String,
+    client_secret: Encryption,
+    redirect_url: String,
+    groups_claim: String,
+    role_mappings: serde_json::Value,
+    default_role_id: Option<String>,
+    is_enabled: bool,
+    last_modified_by: String,
+    last_modified_at: PrimitiveDateTime,
+}
+
+pub enum OidcConfigUpdate {
+    Update {
+        merchant_id: String,
+        issuer_url: String,
+        client_id: String,
+        client_secret: Encryption,
+        redirect_url: String,
+        groups_claim: String,
+        role_mappings: serde_json::Value,
+        default_role_id: Option<String>,
+        is_enabled: bool,
+        last_modified_by: String,
+    },
+}
+
+impl From<OidcConfigUpdate> for OidcConfigUpdateInternal {
+    fn from(value: OidcConfigUpdate) -> Self {
+        let last_modified_at = common_utils::date_time::now();
+        match value {
+            OidcConfigUpdate::Update {
+                merchant_id,
+                issuer_url,
+                client_id,
+                client_secret,
+                redirect_url,
+                groups_claim,
+                role_mappings,
+                default_role_id,
+                is_enabled,
+                last_modified_by,
+            } => Self {
+                merchant_id,
+                issuer_url,
+                client_id,
+                client_secret,
+                redirect_url,
+                groups_claim,
+                role_mappings,
+                default_role_id,
+                is_enabled,
+                last_modified_by,
+                last_modified_at,
+            },
+        }
+    }
+}
+
+/// Binds the subject of an identity provider to a user, within the organization using the provider
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = oidc_identity)]
+pub struct OidcIdentity {
+    pub id: i32,
+    pub org_id: String,
+    pub issuer: String,
+    pub subject: String,
+    pub user_id: String,
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = oidc_identity)]
+pub struct OidcIdentityNew {
+    pub org_id: String,
+    pub issuer: String,
+    pub subject: String,
+    pub user_id: String,
+    pub created_at: PrimitiveDateTime,
+}
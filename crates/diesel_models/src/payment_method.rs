@@ -6,7 +6,7 @@ use time::PrimitiveDateTime;
 
 use crate::{encryption::Encryption, enums as storage_enums, schema::payment_methods};
 
-#[derive(Clone, Debug, Eq, PartialEq, Identifiable, Queryable)]
+#[derive(Clone, Debug, Eq, PartialEq, Identifiable, Queryable, Serialize, Deserialize)]
 #[diesel(table_name = payment_methods)]
 pub struct PaymentMethod {
     pub id: i32,
@@ -35,7 +35,17 @@ pub struct PaymentMethod {
     pub payment_method_data: Option<Encryption>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Insertable, Queryable, router_derive::DebugAsDisplay)]
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Insertable,
+    Queryable,
+    router_derive::DebugAsDisplay,
+    Serialize,
+    Deserialize,
+)]
 #[diesel(table_name = payment_methods)]
 pub struct PaymentMethodNew {
     pub customer_id: String,
@@ -97,7 +107,7 @@ pub struct TokenizeCoreWorkflow {
     pub pm: storage_enums::PaymentMethod,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum PaymentMethodUpdate {
     MetadataUpdate {
         metadata: Option<serde_json::Value>,
@@ -116,9 +126,14 @@ pub struct PaymentMethodUpdateInternal {
 
 impl PaymentMethodUpdateInternal {
     pub fn create_payment_method(self, source: PaymentMethod) -> PaymentMethod {
-        let metadata = self.metadata.map(Secret::new);
+        let metadata = self.metadata.map(Secret::new).or(source.metadata);
+        let payment_method_data = self.payment_method_data.or(source.payment_method_data);
 
-        PaymentMethod { metadata, ..source }
+        PaymentMethod {
+            metadata,
+            payment_method_data,
+            ..source
+        }
     }
 }
 
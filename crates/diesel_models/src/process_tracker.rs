@@ -35,6 +35,9 @@ pub struct ProcessTracker {
     pub created_at: PrimitiveDateTime,
     #[serde(with = "common_utils::custom_serde::iso8601")]
     pub updated_at: PrimitiveDateTime,
+    /// Number of times the task was recovered by the cleaner after its lease expired
+    #[serde(default)]
+    pub crash_count: i32,
 }
 
 #[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
@@ -55,7 +58,19 @@ pub struct ProcessTrackerNew {
     pub updated_at: PrimitiveDateTime,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Default)]
+pub struct ProcessTrackerListConstraints {
+    pub runner: Option<String>,
+    pub status: Option<storage_enums::ProcessTrackerStatus>,
+    /// Matched against the `merchant_id` field of the tracking data
+    pub merchant_id: Option<String>,
+    pub schedule_time_start: Option<PrimitiveDateTime>,
+    pub schedule_time_end: Option<PrimitiveDateTime>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Clone, Debug)]
 pub enum ProcessTrackerUpdate {
     Update {
         name: Option<String>,
@@ -145,6 +160,30 @@ impl From<ProcessTrackerUpdate> for ProcessTrackerUpdateInternal {
     }
 }
 
+impl ProcessTrackerUpdateInternal {
+    pub fn apply_changeset(self, source: ProcessTracker) -> ProcessTracker {
+        let Self {
+            name,
+            retry_count,
+            schedule_time,
+            tracking_data,
+            business_status,
+            status,
+            updated_at,
+        } = self;
+        ProcessTracker {
+            name: name.or(source.name),
+            retry_count: retry_count.unwrap_or(source.retry_count),
+            schedule_time: schedule_time.or(source.schedule_time),
+            tracking_data: tracking_data.unwrap_or(source.tracking_data),
+            business_status: business_status.unwrap_or(source.business_status),
+            status: status.unwrap_or(source.status),
+            updated_at: updated_at.unwrap_or(source.updated_at),
+            ..source
+        }
+    }
+}
+
 #[allow(dead_code)]
 pub struct SchedulerOptions {
     looper_interval: common_utils::date_time::Milliseconds,
@@ -1,5 +1,6 @@
 pub mod address;
 pub mod api_keys;
+pub mod audit_log;
 pub mod business_profile;
 mod capture;
 pub mod cards_info;
@@ -14,11 +15,13 @@ pub mod file;
 pub mod fraud_check;
 pub mod generics;
 pub mod gsm;
+pub mod idempotency_key;
 pub mod locker_mock_up;
 pub mod mandate;
 pub mod merchant_account;
 pub mod merchant_connector_account;
 pub mod merchant_key_store;
+pub mod oidc_config;
 pub mod organization;
 pub mod payment_attempt;
 pub mod payment_intent;
@@ -29,6 +32,10 @@ pub mod payouts;
 pub mod process_tracker;
 pub mod refund;
 pub mod reverse_lookup;
+pub mod role;
 pub mod routing_algorithm;
+pub mod routing_algorithm_activation;
 pub mod user;
+pub mod user_key_store;
 pub mod user_role;
+pub mod webhook_delivery_attempt;
@@ -0,0 +1,72 @@
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::{associations::HasTable, debug_query, pg::Pg, ExpressionMethods, QueryDsl};
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    audit_log::{AuditLog, AuditLogListConstraints, AuditLogNew},
+    errors,
+    query::generics::db_metrics,
+    schema::audit_log::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl AuditLogNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<AuditLog> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl AuditLog {
+    /// Lists the entries matching the constraints, the latest first
+    #[instrument(skip(conn))]
+    pub async fn filter_by_constraints(
+        conn: &PgPooledConn,
+        constraints: AuditLogListConstraints,
+    ) -> StorageResult<Vec<Self>> {
+        let mut filter = <Self as HasTable>::table()
+            .order(dsl::created_at.desc())
+            .into_boxed();
+
+        if let Some(merchant_id) = constraints.merchant_id {
+            filter = filter.filter(dsl::merchant_id.eq(merchant_id));
+        }
+        if let Some(actor_type) = constraints.actor_type {
+            filter = filter.filter(dsl::actor_type.eq(actor_type));
+        }
+        if let Some(actor_id) = constraints.actor_id {
+            filter = filter.filter(dsl::actor_id.eq(actor_id));
+        }
+        if let Some(resource_type) = constraints.resource_type {
+            filter = filter.filter(dsl::resource_type.eq(resource_type));
+        }
+        if let Some(resource_id) = constraints.resource_id {
+            filter = filter.filter(dsl::resource_id.eq(resource_id));
+        }
+        if let Some(start_time) = constraints.start_time {
+            filter = filter.filter(dsl::created_at.ge(start_time));
+        }
+        if let Some(end_time) = constraints.end_time {
+            filter = filter.filter(dsl::created_at.le(end_time));
+        }
+        if let Some(limit) = constraints.limit {
+            filter = filter.limit(limit);
+        }
+        if let Some(offset) = constraints.offset {
+            filter = filter.offset(offset);
+        }
+
+        router_env::logger::debug!(query = %debug_query::<Pg, _>(&filter).to_string());
+
+        db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            filter.get_results_async(conn),
+            db_metrics::DatabaseOperation::Filter,
+        )
+        .await
+        .into_report()
+        .change_context(errors::DatabaseError::Others)
+        .attach_printable("Error filtering audit log entries by specified constraints")
+    }
+}
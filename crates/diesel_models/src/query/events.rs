@@ -1,4 +1,4 @@
-use diesel::{associations::HasTable, ExpressionMethods};
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
 use router_env::{instrument, tracing};
 
 use super::generics;
@@ -16,6 +16,33 @@ impl EventNew {
 }
 
 impl Event {
+    #[instrument(skip(conn))]
+    pub async fn find_by_event_id(conn: &PgPooledConn, event_id: &str) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::event_id.eq(event_id.to_owned()),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn list_by_merchant_id_primary_object_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        primary_object_id: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::primary_object_id.eq(primary_object_id.to_owned())),
+            None,
+            None,
+            Some(dsl::created_at.asc()),
+        )
+        .await
+    }
+
     #[instrument(skip(conn))]
     pub async fn update(
         conn: &PgPooledConn,
@@ -34,4 +61,24 @@ impl Event {
         )
         .await
     }
+
+    /// Increments the delivery attempts of the event in the same statement, so that concurrent
+    /// deliveries of the event never claim the same attempt number
+    #[instrument(skip(conn))]
+    pub async fn increment_delivery_attempts(
+        conn: &PgPooledConn,
+        event_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::event_id.eq(event_id.to_owned()),
+            dsl::delivery_attempts.eq(dsl::delivery_attempts + 1),
+        )
+        .await
+    }
 }
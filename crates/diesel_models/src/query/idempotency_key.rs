@@ -0,0 +1,97 @@
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::{
+    associations::HasTable, debug_query, pg::Pg, BoolExpressionMethods, ExpressionMethods, QueryDsl,
+};
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+use time::PrimitiveDateTime;
+
+use super::generics::{self, db_metrics};
+use crate::{
+    errors,
+    idempotency_key::{IdempotencyKey, IdempotencyKeyNew, IdempotencyKeyUpdate},
+    schema::idempotency_key::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl IdempotencyKeyNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<IdempotencyKey> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl IdempotencyKey {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_key(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        key: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::key.eq(key.to_owned())),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn update_by_merchant_id_key(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        key: &str,
+        idempotency_key_update: IdempotencyKeyUpdate,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::key.eq(key.to_owned())),
+            idempotency_key_update,
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn delete_expired_by_merchant_id_key(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        key: &str,
+        now: PrimitiveDateTime,
+    ) -> StorageResult<bool> {
+        generics::generic_delete::<<Self as HasTable>::Table, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::key.eq(key.to_owned()))
+                .and(dsl::expires_at.le(now)),
+        )
+        .await
+    }
+
+    /// Deletes every key that expired by `now`, returning the number of keys deleted
+    #[instrument(skip(conn))]
+    pub async fn delete_expired(
+        conn: &PgPooledConn,
+        now: PrimitiveDateTime,
+    ) -> StorageResult<usize> {
+        let query = diesel::delete(<Self as HasTable>::table().filter(dsl::expires_at.le(now)));
+        router_env::logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+
+        db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            query.execute_async(conn),
+            db_metrics::DatabaseOperation::Delete,
+        )
+        .await
+        .into_report()
+        .change_context(errors::DatabaseError::Others)
+        .attach_printable("Error while deleting the expired idempotency keys")
+    }
+}
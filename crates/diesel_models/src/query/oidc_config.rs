@@ -0,0 +1,69 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::tracing::{self, instrument};
+
+use crate::{
+    oidc_config::*,
+    query::generics,
+    schema::{oidc_config::dsl, oidc_identity::dsl as identity_dsl},
+    PgPooledConn, StorageResult,
+};
+
+impl OidcConfigNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<OidcConfig> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl OidcConfig {
+    pub async fn find_by_org_id(conn: &PgPooledConn, org_id: &str) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::org_id.eq(org_id.to_owned()),
+        )
+        .await
+    }
+
+    pub async fn update_by_org_id(
+        conn: &PgPooledConn,
+        org_id: &str,
+        oidc_config_update: OidcConfigUpdate,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::org_id.eq(org_id.to_owned()),
+            OidcConfigUpdateInternal::from(oidc_config_update),
+        )
+        .await
+    }
+}
+
+impl OidcIdentityNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<OidcIdentity> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl OidcIdentity {
+    pub async fn find_by_org_id_issuer_subject(
+        conn: &PgPooledConn,
+        org_id: &str,
+        issuer: &str,
+        subject: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            identity_dsl::org_id
+                .eq(org_id.to_owned())
+                .and(identity_dsl::issuer.eq(issuer.to_owned()))
+                .and(identity_dsl::subject.eq(subject.to_owned())),
+        )
+        .await
+    }
+}
@@ -1,4 +1,9 @@
-use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods, Table};
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::{
+    associations::HasTable, debug_query, pg::Pg, BoolExpressionMethods, ExpressionMethods,
+    QueryDsl, Table,
+};
+use error_stack::{IntoReport, ResultExt};
 use router_env::{instrument, tracing};
 use time::PrimitiveDateTime;
 
@@ -6,8 +11,10 @@ use super::generics;
 use crate::{
     enums, errors,
     process_tracker::{
-        ProcessTracker, ProcessTrackerNew, ProcessTrackerUpdate, ProcessTrackerUpdateInternal,
+        ProcessTracker, ProcessTrackerListConstraints, ProcessTrackerNew, ProcessTrackerUpdate,
+        ProcessTrackerUpdateInternal,
     },
+    query::generics::db_metrics,
     schema::process_tracker::dsl,
     PgPooledConn, StorageResult,
 };
@@ -41,6 +48,24 @@ impl ProcessTracker {
         }
     }
 
+    /// Updates the task only if its status is still one of `statuses`, returning `None` if it is
+    /// not, so that the check and the update cannot race with the scheduler.
+    #[instrument(skip(conn))]
+    pub async fn update_if_status_in(
+        self,
+        conn: &PgPooledConn,
+        statuses: Vec<enums::ProcessTrackerStatus>,
+        process: ProcessTrackerUpdate,
+    ) -> StorageResult<Option<Self>> {
+        generics::generic_update_with_results::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::id.eq(self.id).and(dsl::status.eq_any(statuses)),
+            ProcessTrackerUpdateInternal::from(process),
+        )
+        .await
+        .map(|processes| processes.into_iter().next())
+    }
+
     #[instrument(skip(conn))]
     pub async fn update_process_status_by_ids(
         conn: &PgPooledConn,
@@ -118,13 +143,112 @@ impl ProcessTracker {
         Ok(x)
     }
 
+    #[instrument(skip(conn))]
+    pub async fn filter_by_constraints(
+        conn: &PgPooledConn,
+        constraints: ProcessTrackerListConstraints,
+    ) -> StorageResult<Vec<Self>> {
+        let mut filter = <Self as HasTable>::table()
+            .order(dsl::schedule_time.asc())
+            .into_boxed();
+
+        if let Some(runner) = constraints.runner {
+            filter = filter.filter(dsl::runner.eq(runner));
+        }
+        if let Some(status) = constraints.status {
+            filter = filter.filter(dsl::status.eq(status));
+        }
+        if let Some(merchant_id) = constraints.merchant_id {
+            filter = filter.filter(
+                diesel::dsl::sql::<diesel::sql_types::Bool>("tracking_data ->> 'merchant_id' = ")
+                    .bind::<diesel::sql_types::Text, _>(merchant_id),
+            );
+        }
+        if let Some(schedule_time_start) = constraints.schedule_time_start {
+            filter = filter.filter(dsl::schedule_time.ge(schedule_time_start));
+        }
+        if let Some(schedule_time_end) = constraints.schedule_time_end {
+            filter = filter.filter(dsl::schedule_time.le(schedule_time_end));
+        }
+        if let Some(limit) = constraints.limit {
+            filter = filter.limit(limit);
+        }
+        if let Some(offset) = constraints.offset {
+            filter = filter.offset(offset);
+        }
+
+        router_env::logger::debug!(query = %debug_query::<Pg, _>(&filter).to_string());
+
+        db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            filter.get_results_async(conn),
+            db_metrics::DatabaseOperation::Filter,
+        )
+        .await
+        .into_report()
+        .change_context(errors::DatabaseError::Others)
+        .attach_printable("Error filtering processes by specified constraints")
+    }
+
+    /// Counts the tasks in `status` for each runner.
+    #[instrument(skip(conn))]
+    pub async fn count_by_runner(
+        conn: &PgPooledConn,
+        status: enums::ProcessTrackerStatus,
+    ) -> StorageResult<Vec<(Option<String>, i64)>> {
+        let query = <Self as HasTable>::table()
+            .filter(dsl::status.eq(status))
+            .group_by(dsl::runner)
+            .select((dsl::runner, diesel::dsl::count_star()));
+
+        router_env::logger::debug!(query = %debug_query::<Pg, _>(&query).to_string());
+
+        db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            query.get_results_async(conn),
+            db_metrics::DatabaseOperation::Filter,
+        )
+        .await
+        .into_report()
+        .change_context(errors::DatabaseError::Others)
+        .attach_printable("Error counting processes by runner")
+    }
+
+    /// Finds the tasks that were handed to a consumer but have not been updated since
+    /// `lease_expiry`, which happens when the consumer crashed while holding them. The tasks still
+    /// waiting in the stream (`Processing`) have not been handed to any consumer yet, and are left
+    /// alone however long the backlog is.
+    #[instrument(skip(conn))]
+    pub async fn find_processes_with_expired_lease(
+        conn: &PgPooledConn,
+        lease_expiry: PrimitiveDateTime,
+        limit: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<
+            <Self as HasTable>::Table,
+            _,
+            <<Self as HasTable>::Table as Table>::PrimaryKey,
+            _,
+        >(
+            conn,
+            dsl::status
+                .eq(enums::ProcessTrackerStatus::ProcessStarted)
+                .and(dsl::updated_at.lt(lease_expiry)),
+            limit,
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Requeues the tasks found by [`Self::find_processes_with_expired_lease`], skipping any that
+    /// a consumer has picked up or finished in the meantime. Returns the IDs of the tasks
+    /// requeued.
     #[instrument(skip(conn))]
     pub async fn reinitialize_limbo_processes(
         conn: &PgPooledConn,
         ids: Vec<String>,
         schedule_time: PrimitiveDateTime,
-    ) -> StorageResult<usize> {
-        generics::generic_update::<<Self as HasTable>::Table, _, _>(
+    ) -> StorageResult<Vec<String>> {
+        generics::generic_update_with_results::<<Self as HasTable>::Table, _, _, Self>(
             conn,
             dsl::status
                 .eq(enums::ProcessTrackerStatus::ProcessStarted)
@@ -132,8 +256,11 @@ impl ProcessTracker {
             (
                 dsl::status.eq(enums::ProcessTrackerStatus::Processing),
                 dsl::schedule_time.eq(schedule_time),
+                dsl::crash_count.eq(dsl::crash_count + 1),
+                dsl::updated_at.eq(common_utils::date_time::now()),
             ),
         )
         .await
+        .map(|processes| processes.into_iter().map(|process| process.id).collect())
     }
 }
@@ -0,0 +1,146 @@
+use async_bb8_diesel::AsyncRunQueryDsl;
+use diesel::{
+    associations::HasTable, result::Error as DieselError, BoolExpressionMethods, ExpressionMethods,
+    QueryDsl,
+};
+use error_stack::IntoReport;
+use router_env::tracing::{self, instrument};
+
+use crate::{
+    errors,
+    query::generics::{self, db_metrics},
+    role::*,
+    schema::{roles::dsl, user_roles},
+    PgPooledConn, StorageResult,
+};
+
+impl RoleNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<Role> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl Role {
+    pub async fn find_by_role_id_merchant_id(
+        conn: &PgPooledConn,
+        role_id: &str,
+        merchant_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::role_id
+                .eq(role_id.to_owned())
+                .and(dsl::merchant_id.eq(merchant_id.to_owned())),
+        )
+        .await
+    }
+
+    pub async fn update_by_role_id_merchant_id(
+        conn: &PgPooledConn,
+        role_id: &str,
+        merchant_id: &str,
+        role_update: RoleUpdate,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::role_id
+                .eq(role_id.to_owned())
+                .and(dsl::merchant_id.eq(merchant_id.to_owned())),
+            RoleUpdateInternal::from(role_update),
+        )
+        .await
+    }
+
+    /// Locks the role in share mode until the end of the transaction, held while the role is being
+    /// assigned so that it cannot be deleted in the meantime
+    pub async fn lock_for_assignment_by_role_id_merchant_id(
+        conn: &PgPooledConn,
+        role_id: &str,
+        merchant_id: &str,
+    ) -> StorageResult<Self> {
+        let query = <Self as HasTable>::table()
+            .filter(
+                dsl::role_id
+                    .eq(role_id.to_owned())
+                    .and(dsl::merchant_id.eq(merchant_id.to_owned())),
+            )
+            .for_share();
+
+        Self::get_locked_role(query.get_result_async(conn)).await
+    }
+
+    /// Locks the role exclusively until the end of the transaction, waiting for the transactions
+    /// assigning the role to complete
+    pub async fn lock_for_deletion_by_role_id_merchant_id(
+        conn: &PgPooledConn,
+        role_id: &str,
+        merchant_id: &str,
+    ) -> StorageResult<Self> {
+        let query = <Self as HasTable>::table()
+            .filter(
+                dsl::role_id
+                    .eq(role_id.to_owned())
+                    .and(dsl::merchant_id.eq(merchant_id.to_owned())),
+            )
+            .for_update();
+
+        Self::get_locked_role(query.get_result_async(conn)).await
+    }
+
+    async fn get_locked_role(
+        query: impl std::future::Future<Output = Result<Self, DieselError>>,
+    ) -> StorageResult<Self> {
+        db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            query,
+            db_metrics::DatabaseOperation::FindOne,
+        )
+        .await
+        .into_report()
+        .map_err(|error| match error.current_context() {
+            DieselError::NotFound => error.change_context(errors::DatabaseError::NotFound),
+            _ => error.change_context(errors::DatabaseError::Others),
+        })
+    }
+
+    /// Deletes the role only if it is not assigned to any user of the merchant. The check alone
+    /// does not see assignments committed after the statement started, the role must be locked
+    /// first with [`Self::lock_for_deletion_by_role_id_merchant_id`] in the same transaction.
+    pub async fn delete_unassigned_by_role_id_merchant_id(
+        conn: &PgPooledConn,
+        role_id: &str,
+        merchant_id: &str,
+    ) -> StorageResult<bool> {
+        generics::generic_delete::<<Self as HasTable>::Table, _>(
+            conn,
+            dsl::role_id
+                .eq(role_id.to_owned())
+                .and(dsl::merchant_id.eq(merchant_id.to_owned()))
+                .and(diesel::dsl::not(diesel::dsl::exists(
+                    user_roles::table
+                        .filter(user_roles::role_id.eq(role_id.to_owned()))
+                        .filter(user_roles::merchant_id.eq(merchant_id.to_owned())),
+                ))),
+        )
+        .await
+    }
+
+    pub async fn list_by_merchant_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id.eq(merchant_id.to_owned()),
+            None,
+            None,
+            Some(dsl::created_at.asc()),
+        )
+        .await
+    }
+}
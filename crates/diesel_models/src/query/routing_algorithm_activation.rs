@@ -0,0 +1,53 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    routing_algorithm_activation::{RoutingAlgorithmActivation, RoutingAlgorithmActivationNew},
+    schema::routing_algorithm_activation::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl RoutingAlgorithmActivationNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<RoutingAlgorithmActivation> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl RoutingAlgorithmActivation {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_profile_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        profile_id: Option<&str>,
+        limit: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        match profile_id {
+            Some(profile_id) => {
+                generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+                    conn,
+                    dsl::merchant_id
+                        .eq(merchant_id.to_owned())
+                        .and(dsl::profile_id.eq(profile_id.to_owned())),
+                    limit,
+                    None,
+                    Some(dsl::created_at.desc()),
+                )
+                .await
+            }
+            None => {
+                generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+                    conn,
+                    dsl::merchant_id
+                        .eq(merchant_id.to_owned())
+                        .and(dsl::profile_id.is_null()),
+                    limit,
+                    None,
+                    Some(dsl::created_at.desc()),
+                )
+                .await
+            }
+        }
+    }
+}
@@ -1,9 +1,10 @@
 use async_bb8_diesel::AsyncRunQueryDsl;
 use diesel::{
-    associations::HasTable, debug_query, result::Error as DieselError, ExpressionMethods,
-    JoinOnDsl, QueryDsl,
+    associations::HasTable, debug_query, result::Error as DieselError, BoolExpressionMethods,
+    ExpressionMethods, JoinOnDsl, QueryDsl,
 };
 use error_stack::{report, IntoReport};
+use masking::Secret;
 use router_env::{
     logger,
     tracing::{self, instrument},
@@ -64,6 +65,61 @@ impl User {
         })
     }
 
+    /// Records the time step of an accepted TOTP, unless a code of the same or a later step has
+    /// already been accepted, in which case no user is found to be updated
+    pub async fn update_totp_last_used_step_by_user_id(
+        conn: &PgPooledConn,
+        user_id: &str,
+        totp_step: i64,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_results::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            users_dsl::user_id.eq(user_id.to_owned()).and(
+                users_dsl::totp_last_used_step
+                    .is_null()
+                    .or(users_dsl::totp_last_used_step.lt(totp_step)),
+            ),
+            UserUpdateInternal::from(UserUpdate::TotpStepUpdate {
+                totp_last_used_step: totp_step,
+            }),
+        )
+        .await?
+        .first()
+        .cloned()
+        .ok_or_else(|| {
+            report!(errors::DatabaseError::NotFound)
+                .attach_printable("TOTP of the same or a later step already used")
+        })
+    }
+
+    /// Replaces the recovery codes, unless they have changed since they were read, in which case
+    /// no user is found to be updated
+    pub async fn update_recovery_codes_by_user_id_recovery_codes(
+        conn: &PgPooledConn,
+        user_id: &str,
+        recovery_codes: Vec<Secret<String>>,
+        remaining_recovery_codes: Vec<Secret<String>>,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_results::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            users_dsl::user_id
+                .eq(user_id.to_owned())
+                .and(users_dsl::totp_recovery_codes.eq(recovery_codes)),
+            UserUpdateInternal::from(UserUpdate::TotpUpdate {
+                totp_status: None,
+                totp_secret: None,
+                totp_recovery_codes: Some(remaining_recovery_codes),
+            }),
+        )
+        .await?
+        .first()
+        .cloned()
+        .ok_or_else(|| {
+            report!(errors::DatabaseError::NotFound)
+                .attach_printable("Recovery codes changed since they were read")
+        })
+    }
+
     pub async fn delete_by_user_id(conn: &PgPooledConn, user_id: &str) -> StorageResult<bool> {
         generics::generic_delete::<<Self as HasTable>::Table, _>(
             conn,
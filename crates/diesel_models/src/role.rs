@@ -0,0 +1,68 @@
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use time::PrimitiveDateTime;
+
+use crate::schema::roles;
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, Identifiable, Queryable)]
+#[diesel(table_name = roles)]
+pub struct Role {
+    pub id: i32,
+    pub role_id: String,
+    pub role_name: String,
+    pub merchant_id: String,
+    pub org_id: String,
+    pub permissions: Vec<String>,
+    pub created_by: String,
+    pub created_at: PrimitiveDateTime,
+    pub last_modified_by: String,
+    pub last_modified_at: PrimitiveDateTime,
+}
+
+#[derive(router_derive::Setter, Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = roles)]
+pub struct RoleNew {
+    pub role_id: String,
+    pub role_name: String,
+    pub merchant_id: String,
+    pub org_id: String,
+    pub permissions: Vec<String>,
+    pub created_by: String,
+    pub created_at: PrimitiveDateTime,
+    pub last_modified_by: String,
+    pub last_modified_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = roles)]
+pub struct RoleUpdateInternal {
+    role_name: Option<String>,
+    permissions: Option<Vec<String>>,
+    last_modified_by: String,
+    last_modified_at: PrimitiveDateTime,
+}
+
+pub enum RoleUpdate {
+    UpdateDetails {
+        role_name: Option<String>,
+        permissions: Option<Vec<String>>,
+        last_modified_by: String,
+    },
+}
+
+impl From<RoleUpdate> for RoleUpdateInternal {
+    fn from(value: RoleUpdate) -> Self {
+        let last_modified_at = common_utils::date_time::now();
+        match value {
+            RoleUpdate::UpdateDetails {
+                role_name,
+                permissions,
+                last_modified_by,
+            } => Self {
+                role_name,
+                permissions,
+                last_modified_by,
+                last_modified_at,
+            },
+        }
+    }
+}
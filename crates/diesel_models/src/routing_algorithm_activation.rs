@@ -0,0 +1,27 @@
+use diesel::{Identifiable, Insertable, Queryable};
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::routing_algorithm_activation};
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = routing_algorithm_activation)]
+pub struct RoutingAlgorithmActivationNew {
+    pub activation_id: String,
+    pub merchant_id: String,
+    pub profile_id: Option<String>,
+    pub algorithm_id: Option<String>,
+    pub previous_algorithm_id: Option<String>,
+    pub action: storage_enums::RoutingActivationAction,
+}
+
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = routing_algorithm_activation, primary_key(activation_id))]
+pub struct RoutingAlgorithmActivation {
+    pub activation_id: String,
+    pub merchant_id: String,
+    pub profile_id: Option<String>,
+    pub algorithm_id: Option<String>,
+    pub previous_algorithm_id: Option<String>,
+    pub action: storage_enums::RoutingActivationAction,
+    pub created_at: PrimitiveDateTime,
+}
@@ -3,7 +3,7 @@ use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
 use masking::Secret;
 use time::PrimitiveDateTime;
 
-use crate::schema::users;
+use crate::{encryption::Encryption, enums::TotpStatus, schema::users};
 
 pub mod dashboard_metadata;
 
@@ -19,6 +19,12 @@ pub struct User {
     pub is_verified: bool,
     pub created_at: PrimitiveDateTime,
     pub last_modified_at: PrimitiveDateTime,
+    pub totp_status: TotpStatus,
+    pub totp_secret: Option<Encryption>,
+    /// Hashes of the recovery codes that have not been used yet
+    pub totp_recovery_codes: Option<Vec<Secret<String>>>,
+    /// The time step of the last TOTP accepted, codes of the same or an earlier step are rejected
+    pub totp_last_used_step: Option<i64>,
 }
 
 #[derive(
@@ -42,6 +48,10 @@ pub struct UserUpdateInternal {
     password: Option<Secret<String>>,
     is_verified: Option<bool>,
     last_modified_at: PrimitiveDateTime,
+    totp_status: Option<TotpStatus>,
+    totp_secret: Option<Option<Encryption>>,
+    totp_recovery_codes: Option<Option<Vec<Secret<String>>>>,
+    totp_last_used_step: Option<i64>,
 }
 
 #[derive(Debug)]
@@ -52,6 +62,15 @@ pub enum UserUpdate {
         password: Option<Secret<String>>,
         is_verified: Option<bool>,
     },
+    TotpUpdate {
+        totp_status: Option<TotpStatus>,
+        totp_secret: Option<Encryption>,
+        totp_recovery_codes: Option<Vec<Secret<String>>>,
+    },
+    TotpStepUpdate {
+        totp_last_used_step: i64,
+    },
+    TotpReset,
 }
 
 impl From<UserUpdate> for UserUpdateInternal {
@@ -63,6 +82,10 @@ impl From<UserUpdate> for UserUpdateInternal {
                 password: None,
                 is_verified: Some(true),
                 last_modified_at,
+                totp_status: None,
+                totp_secret: None,
+                totp_recovery_codes: None,
+                totp_last_used_step: None,
             },
             UserUpdate::AccountUpdate {
                 name,
@@ -73,6 +96,46 @@ impl From<UserUpdate> for UserUpdateInternal {
                 password,
                 is_verified,
                 last_modified_at,
+                totp_status: None,
+                totp_secret: None,
+                totp_recovery_codes: None,
+                totp_last_used_step: None,
+            },
+            UserUpdate::TotpUpdate {
+                totp_status,
+                totp_secret,
+                totp_recovery_codes,
+            } => Self {
+                name: None,
+                password: None,
+                is_verified: None,
+                last_modified_at,
+                totp_status,
+                totp_secret: totp_secret.map(Some),
+                totp_recovery_codes: totp_recovery_codes.map(Some),
+                totp_last_used_step: None,
+            },
+            UserUpdate::TotpStepUpdate {
+                totp_last_used_step,
+            } => Self {
+                name: None,
+                password: None,
+                is_verified: None,
+                last_modified_at,
+                totp_status: None,
+                totp_secret: None,
+                totp_recovery_codes: None,
+                totp_last_used_step: Some(totp_last_used_step),
+            },
+            UserUpdate::TotpReset => Self {
+                name: None,
+                password: None,
+                is_verified: None,
+                last_modified_at,
+                totp_status: Some(TotpStatus::NotSet),
+                totp_secret: Some(None),
+                totp_recovery_codes: Some(None),
+                totp_last_used_step: None,
             },
         }
     }
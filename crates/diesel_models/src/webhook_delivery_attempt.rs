@@ -0,0 +1,40 @@
+use diesel::{Identifiable, Insertable, Queryable};
+use masking::Secret;
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::webhook_delivery_attempt};
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = webhook_delivery_attempt)]
+pub struct WebhookDeliveryAttemptNew {
+    pub attempt_id: String,
+    pub event_id: String,
+    pub merchant_id: String,
+    pub attempt_number: i32,
+    pub delivery_trigger: storage_enums::WebhookDeliveryAttemptTrigger,
+    pub request_url: String,
+    pub request_body: Secret<String>,
+    pub response_status_code: Option<i32>,
+    pub response_body: Option<Secret<String>>,
+    pub error_message: Option<String>,
+    pub latency_ms: i64,
+    pub is_delivered: bool,
+}
+
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = webhook_delivery_attempt, primary_key(attempt_id))]
+pub struct WebhookDeliveryAttempt {
+    pub attempt_id: String,
+    pub event_id: String,
+    pub merchant_id: String,
+    pub attempt_number: i32,
+    pub delivery_trigger: storage_enums::WebhookDeliveryAttemptTrigger,
+    pub request_url: String,
+    pub request_body: Secret<String>,
+    pub response_status_code: Option<i32>,
+    pub response_body: Option<Secret<String>>,
+    pub error_message: Option<String>,
+    pub latency_ms: i64,
+    pub is_delivered: bool,
+    pub created_at: PrimitiveDateTime,
+}
@@ -0,0 +1,499 @@
+//! Entries the drainer fails to apply to the database are moved to a dead-letter stream of the
+//! same partition instead of being dropped, so that they can be inspected and replayed once the
+//! cause of the failure is fixed.
+
+use std::{collections::HashMap, sync::Arc};
+
+use common_utils::ext_traits::StringExt;
+use diesel_models::{
+    address::Address, customers::Customer, errors::DatabaseError, kv, mandate::Mandate,
+    payment_attempt::PaymentAttempt, payment_method::PaymentMethod, refund::Refund, PaymentIntent,
+    PgPooledConn,
+};
+use error_stack::{IntoReport, ResultExt};
+use redis_interface::RedisEntryId;
+use serde::Serialize;
+use time::PrimitiveDateTime;
+
+use crate::{
+    connection::pg_connection,
+    errors::{self, DrainerError},
+    logger, metrics, services,
+    settings::DeadLetterCommand,
+    utils,
+};
+
+/// Fields added to a dead-lettered entry, on top of the fields of the original entry.
+const SOURCE_ENTRY_ID_FIELD: &str = "dead_letter_source_entry_id";
+const ERROR_FIELD: &str = "dead_letter_error";
+const FAILED_AT_FIELD: &str = "dead_letter_failed_at";
+
+#[derive(Debug, Serialize)]
+pub struct DeadLetterEntry {
+    pub partition: u8,
+    /// ID of the entry in the dead-letter stream
+    pub entry_id: String,
+    /// ID the entry had in the drainer stream
+    pub source_entry_id: Option<String>,
+    pub error: Option<String>,
+    /// Unix timestamp at which the entry failed
+    pub failed_at: Option<String>,
+    /// Fields of the original entry
+    pub fields: HashMap<String, String>,
+}
+
+impl DeadLetterEntry {
+    fn new(partition: u8, (entry_id, mut fields): (String, HashMap<String, String>)) -> Self {
+        Self {
+            partition,
+            entry_id,
+            source_entry_id: fields.remove(SOURCE_ENTRY_ID_FIELD),
+            error: fields.remove(ERROR_FIELD),
+            failed_at: fields.remove(FAILED_AT_FIELD),
+            fields,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplayedEntry {
+    pub partition: u8,
+    pub entry_id: String,
+    pub status: ReplayStatus,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayStatus {
+    /// The entry was pushed back to its partition
+    Replayed,
+    /// The entry was removed from the dead-letter stream in the meantime, by another replay
+    NotFound,
+    /// The entry was left in the dead-letter stream, as the row it updates was modified after the
+    /// update was made, and replaying it would overwrite the newer changes
+    Stale,
+}
+
+/// Pushes the fields of a dead-lettered entry back to the drainer stream and removes the entry
+/// from the dead-letter stream in a single step, so that an entry is never replayed twice nor
+/// lost. Returns 0 if the entry is no longer in the dead-letter stream.
+///
+/// KEYS[1]: dead-letter stream, KEYS[2]: drainer stream, both in the slot of the partition
+/// ARGV[1]: ID of the entry in the dead-letter stream, ARGV[2..]: fields and values of the entry
+const REPLAY_ENTRY_SCRIPT: &str = r#"
+if #redis.call('XRANGE', KEYS[1], ARGV[1], ARGV[1]) == 0 then
+    return 0
+end
+redis.call('XADD', KEYS[2], '*', unpack(ARGV, 2))
+redis.call('XDEL', KEYS[1], ARGV[1])
+return 1
+"#;
+
+#[router_env::instrument(skip_all)]
+pub async fn push_entry(
+    store: &services::Store,
+    dead_letter_stream_name: &str,
+    (source_entry_id, fields): &(String, HashMap<String, String>),
+    error: &str,
+) -> errors::DrainerResult<()> {
+    let failed_at = common_utils::date_time::now_unix_timestamp().to_string();
+    let dead_letter_fields = fields
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.clone()))
+        .chain([
+            (SOURCE_ENTRY_ID_FIELD, source_entry_id.clone()),
+            (ERROR_FIELD, error.to_owned()),
+            (FAILED_AT_FIELD, failed_at),
+        ])
+        .collect::<Vec<_>>();
+
+    store
+        .redis_conn
+        .stream_append_entry(
+            dead_letter_stream_name,
+            &RedisEntryId::AutoGeneratedID,
+            dead_letter_fields,
+        )
+        .await
+        .map_err(DrainerError::from)
+        .into_report()?;
+
+    metrics::ENTRIES_DEAD_LETTERED.add(
+        &metrics::CONTEXT,
+        1,
+        &[metrics::KeyValue::new(
+            "stream",
+            dead_letter_stream_name.to_owned(),
+        )],
+    );
+
+    Ok(())
+}
+
+pub async fn list_entries(
+    store: Arc<services::Store>,
+    partition: u8,
+    count: u64,
+) -> errors::DrainerResult<Vec<DeadLetterEntry>> {
+    read_entries(store, partition, "-", "+", Some(count)).await
+}
+
+pub async fn find_entry(
+    store: Arc<services::Store>,
+    partition: u8,
+    entry_id: &str,
+) -> errors::DrainerResult<Option<DeadLetterEntry>> {
+    Ok(read_entries(store, partition, entry_id, entry_id, Some(1))
+        .await?
+        .pop())
+}
+
+/// Appends the original fields of the entries back to the drainer stream of the partition and
+/// removes them from the dead-letter stream. Entries failing again are dead-lettered again.
+///
+/// The updates of rows modified since the update was made are left in the dead-letter stream
+/// unless `force` is set, so that a replay never overwrites newer changes.
+pub async fn replay_entries(
+    store: Arc<services::Store>,
+    partition: u8,
+    entry_ids: Vec<String>,
+    count: u64,
+    force: bool,
+) -> errors::DrainerResult<Vec<ReplayedEntry>> {
+    let entries = if entry_ids.is_empty() {
+        list_entries(store.clone(), partition, count).await?
+    } else {
+        let mut entries = Vec::with_capacity(entry_ids.len());
+        for entry_id in entry_ids {
+            let entry = find_entry(store.clone(), partition, &entry_id)
+                .await?
+                .ok_or_else(|| {
+                    DrainerError::InvalidCommand(format!(
+                        "dead-lettered entry {entry_id} does not exist in partition {partition}"
+                    ))
+                })
+                .into_report()?;
+            entries.push(entry);
+        }
+        entries
+    };
+
+    let stream_name = utils::get_drainer_stream_name(store.clone(), partition);
+    let dead_letter_stream_name =
+        utils::get_drainer_dead_letter_stream_name(store.clone(), partition);
+    let conn = pg_connection(&store.master_pool).await?;
+
+    let mut replayed_entries = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let status = if !force && is_stale_entry(&conn, &entry).await? {
+            logger::warn!(partition, entry_id = %entry.entry_id, "Skipped the replay of a stale dead-lettered entry");
+            metrics::ENTRIES_REPLAY_SKIPPED.add(
+                &metrics::CONTEXT,
+                1,
+                &[metrics::KeyValue::new("stream", stream_name.clone())],
+            );
+            ReplayStatus::Stale
+        } else {
+            replay_entry(&store, &dead_letter_stream_name, &stream_name, &entry).await?
+        };
+
+        if status == ReplayStatus::Replayed {
+            logger::info!(partition, entry_id = %entry.entry_id, "Replayed dead-lettered entry");
+            metrics::ENTRIES_REPLAYED.add(
+                &metrics::CONTEXT,
+                1,
+                &[metrics::KeyValue::new("stream", stream_name.clone())],
+            );
+        }
+
+        replayed_entries.push(ReplayedEntry {
+            partition,
+            entry_id: entry.entry_id,
+            status,
+        });
+    }
+
+    Ok(replayed_entries)
+}
+
+async fn replay_entry(
+    store: &services::Store,
+    dead_letter_stream_name: &str,
+    stream_name: &str,
+    entry: &DeadLetterEntry,
+) -> errors::DrainerResult<ReplayStatus> {
+    let arguments = std::iter::once(entry.entry_id.clone())
+        .chain(
+            entry
+                .fields
+                .iter()
+                .flat_map(|(key, value)| [key.clone(), value.clone()]),
+        )
+        .collect::<Vec<_>>();
+
+    let replayed: i64 = store
+        .redis_conn
+        .evaluate_redis_script(
+            REPLAY_ENTRY_SCRIPT,
+            vec![dead_letter_stream_name.to_owned(), stream_name.to_owned()],
+            arguments,
+        )
+        .await
+        .map_err(DrainerError::from)
+        .into_report()?;
+
+    Ok(if replayed == 0 {
+        ReplayStatus::NotFound
+    } else {
+        ReplayStatus::Replayed
+    })
+}
+
+/// Returns whether the entry updates a row that was written to after the entry failed, by a
+/// later entry of the partition or outside of the drainer. Inserts are never stale, replaying the
+/// insert of an existing row fails with a unique violation.
+async fn is_stale_entry(
+    conn: &PgPooledConn,
+    entry: &DeadLetterEntry,
+) -> errors::DrainerResult<bool> {
+    let updatable = match get_update(entry) {
+        Some(updatable) => updatable,
+        None => return Ok(false),
+    };
+    // The entries without a failure time cannot be checked, and are only replayed when forced
+    let failed_at = match entry
+        .failed_at
+        .as_ref()
+        .and_then(|failed_at| failed_at.parse::<i64>().ok())
+    {
+        Some(failed_at) => failed_at,
+        None => return Ok(true),
+    };
+    let is_modified_after_failure =
+        |modified_at: PrimitiveDateTime| is_modified_after(modified_at, failed_at);
+
+    let is_stale = match updatable {
+        kv::Updateable::PaymentIntentUpdate(update) => {
+            PaymentIntent::find_by_payment_id_merchant_id(
+                conn,
+                &update.orig.payment_id,
+                &update.orig.merchant_id,
+            )
+            .await
+            .map(|current| is_modified_after_failure(current.modified_at))
+        }
+        kv::Updateable::PaymentAttemptUpdate(update) => {
+            PaymentAttempt::find_by_merchant_id_attempt_id(
+                conn,
+                &update.orig.merchant_id,
+                &update.orig.attempt_id,
+            )
+            .await
+            .map(|current| is_modified_after_failure(current.modified_at))
+        }
+        kv::Updateable::RefundUpdate(update) => Refund::find_by_merchant_id_refund_id(
+            conn,
+            &update.orig.merchant_id,
+            &update.orig.refund_id,
+        )
+        .await
+        .map(|current| is_modified_after_failure(current.updated_at)),
+        kv::Updateable::AddressUpdate(update) => {
+            Address::find_by_address_id(conn, &update.orig.address_id)
+                .await
+                .map(|current| is_modified_after_failure(current.modified_at))
+        }
+        kv::Updateable::CustomerUpdate(update) => Customer::find_by_customer_id_merchant_id(
+            conn,
+            &update.orig.customer_id,
+            &update.orig.merchant_id,
+        )
+        .await
+        .map(|current| is_modified_after_failure(current.modified_at)),
+        // Mandates do not record when they were last modified, and the drainer does not change
+        // them beyond the update, so any difference with the row the update was made on is a
+        // newer change
+        kv::Updateable::MandateUpdate(update) => Mandate::find_by_merchant_id_mandate_id(
+            conn,
+            &update.orig.merchant_id,
+            &update.orig.mandate_id,
+        )
+        .await
+        .map(|current| {
+            serde_json::to_value(&current).ok() != serde_json::to_value(&update.orig).ok()
+        }),
+        kv::Updateable::PaymentMethodUpdate(update) => {
+            PaymentMethod::find_by_payment_method_id(conn, &update.orig.payment_method_id)
+                .await
+                .map(|current| is_modified_after_failure(current.last_modified))
+        }
+    };
+
+    match is_stale {
+        Ok(is_stale) => Ok(is_stale),
+        // The update fails again and is dead-lettered again if the row still does not exist
+        Err(error) if matches!(error.current_context(), DatabaseError::NotFound) => Ok(false),
+        Err(error) => Err(error).change_context(DrainerError::UnexpectedError(
+            "Failed to find the row updated by the dead-lettered entry".to_string(),
+        )),
+    }
+}
+
+fn get_update(entry: &DeadLetterEntry) -> Option<kv::Updateable> {
+    let db_op: Option<kv::DBOperation> = entry
+        .fields
+        .get("typed_sql")
+        .and_then(|typed_sql| typed_sql.parse_struct("DBOperation").ok());
+    match db_op {
+        Some(kv::DBOperation::Update { updatable }) => Some(updatable),
+        _ => None,
+    }
+}
+
+/// Returns whether the row was modified after the unix timestamp. Timestamps are only compared to
+/// the second, as the failure time of the entries is.
+fn is_modified_after(modified_at: PrimitiveDateTime, timestamp: i64) -> bool {
+    modified_at.assume_utc().unix_timestamp() > timestamp
+}
+
+/// Runs a dead-letter command and prints its result to stdout as JSON.
+pub async fn run_command(
+    store: Arc<services::Store>,
+    command: DeadLetterCommand,
+) -> errors::DrainerResult<()> {
+    let output = match command {
+        DeadLetterCommand::List { partition, count } => {
+            to_json(&list_entries(store, partition, count).await?)
+        }
+        DeadLetterCommand::Inspect {
+            partition,
+            entry_id,
+        } => {
+            let entry = find_entry(store, partition, &entry_id)
+                .await?
+                .ok_or_else(|| {
+                    DrainerError::InvalidCommand(format!(
+                        "dead-lettered entry {entry_id} does not exist in partition {partition}"
+                    ))
+                })
+                .into_report()?;
+            to_json(&entry)
+        }
+        DeadLetterCommand::Replay {
+            partition,
+            entry_ids,
+            count,
+            force,
+        } => to_json(&replay_entries(store, partition, entry_ids, count, force).await?),
+    }?;
+
+    println!("{output}");
+    Ok(())
+}
+
+async fn read_entries(
+    store: Arc<services::Store>,
+    partition: u8,
+    start: &str,
+    end: &str,
+    count: Option<u64>,
+) -> errors::DrainerResult<Vec<DeadLetterEntry>> {
+    if partition >= store.config.drainer_num_partitions {
+        return Err(DrainerError::InvalidCommand(format!(
+            "partition must be less than {}",
+            store.config.drainer_num_partitions
+        )))
+        .into_report();
+    }
+
+    let dead_letter_stream_name =
+        utils::get_drainer_dead_letter_stream_name(store.clone(), partition);
+    let entries = store
+        .redis_conn
+        .stream_read_range(&dead_letter_stream_name, start, end, count)
+        .await
+        .map_err(DrainerError::from)
+        .into_report()?;
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| DeadLetterEntry::new(partition, entry))
+        .collect())
+}
+
+fn to_json<T: Serialize>(value: &T) -> errors::DrainerResult<String> {
+    serde_json::to_string_pretty(value)
+        .into_report()
+        .change_context(DrainerError::UnexpectedError(
+            "Failed to serialize the dead-letter command output".to_string(),
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn get_entry(typed_sql: &str) -> DeadLetterEntry {
+        DeadLetterEntry::new(
+            0,
+            (
+                "1703845200000-0".to_string(),
+                HashMap::from([
+                    ("typed_sql".to_string(), typed_sql.to_string()),
+                    (FAILED_AT_FIELD.to_string(), "1703845200".to_string()),
+                ]),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_only_updates_are_checked_for_staleness() {
+        let delete = serde_json::to_string(&kv::TypedSql {
+            op: kv::DBOperation::Delete {
+                deletable: kv::Deletable::Customer {
+                    customer_id: "cus_123".to_string(),
+                    merchant_id: "merchant_123".to_string(),
+                },
+            },
+        })
+        .unwrap();
+        assert!(get_update(&get_entry(&delete)).is_none());
+        assert!(get_update(&get_entry("not typed sql")).is_none());
+    }
+
+    #[test]
+    fn test_is_modified_after() {
+        let failed_at = common_utils::date_time::now();
+        let timestamp = failed_at.assume_utc().unix_timestamp();
+
+        assert!(is_modified_after(
+            failed_at + time::Duration::seconds(1),
+            timestamp
+        ));
+        assert!(!is_modified_after(failed_at, timestamp));
+        assert!(!is_modified_after(
+            failed_at - time::Duration::minutes(5),
+            timestamp
+        ));
+    }
+
+    #[test]
+    fn test_replay_status_is_serialized_in_snake_case() {
+        let entry = ReplayedEntry {
+            partition: 5,
+            entry_id: "1703845200000-0".to_string(),
+            status: ReplayStatus::NotFound,
+        };
+
+        assert_eq!(
+            serde_json::to_value(entry).unwrap(),
+            serde_json::json!({
+                "partition": 5,
+                "entry_id": "1703845200000-0",
+                "status": "not_found",
+            })
+        );
+    }
+}
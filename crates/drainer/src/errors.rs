@@ -13,6 +13,12 @@ pub enum DrainerError {
     SignalError(String),
     #[error("Unexpected error occurred: {0}")]
     UnexpectedError(String),
+    #[error("Failed to obtain a database connection")]
+    DatabaseConnectionError,
+    #[error("Invalid command: {0}")]
+    InvalidCommand(String),
+    #[error("I/O: {0}")]
+    IoError(std::io::Error),
 }
 
 pub type DrainerResult<T> = error_stack::Result<T, DrainerError>;
@@ -28,3 +34,9 @@ impl From<error_stack::Report<redis::errors::RedisError>> for DrainerError {
         Self::RedisError(err)
     }
 }
+
+impl From<std::io::Error> for DrainerError {
+    fn from(err: std::io::Error) -> Self {
+        Self::IoError(err)
+    }
+}
@@ -0,0 +1,278 @@
+use std::sync::Arc;
+
+use actix_web::{web, HttpResponse, Scope};
+use async_bb8_diesel::AsyncRunQueryDsl;
+use error_stack::IntoReport;
+use router_env::{instrument, tracing};
+use serde::Serialize;
+
+use crate::{
+    errors::{self, DrainerError},
+    logger, metrics, services, utils,
+};
+
+pub struct Health;
+
+impl Health {
+    pub fn server(store: Arc<services::Store>) -> Scope {
+        web::scope("health")
+            .app_data(web::Data::new(store))
+            .service(web::resource("").route(web::get().to(health)))
+            .service(web::resource("/ready").route(web::get().to(deep_health_check)))
+            .service(web::resource("/lag").route(web::get().to(stream_lag)))
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DrainerHealthCheckResponse {
+    pub database: bool,
+    pub redis: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartitionLag {
+    pub partition: u8,
+    /// Number of entries waiting to be drained
+    pub stream_length: usize,
+    /// Seconds since the oldest entry waiting to be drained was pushed
+    pub oldest_entry_age_seconds: Option<i64>,
+    /// Number of entries that could not be applied to the database
+    pub dead_letter_length: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DrainerLagResponse {
+    pub total_stream_length: usize,
+    pub max_oldest_entry_age_seconds: Option<i64>,
+    pub total_dead_letter_length: usize,
+    pub partitions: Vec<PartitionLag>,
+}
+
+#[instrument(skip_all)]
+pub async fn start_web_server(
+    server: crate::settings::Server,
+    store: Arc<services::Store>,
+) -> Result<actix_web::dev::Server, DrainerError> {
+    let web_server = actix_web::HttpServer::new(move || {
+        actix_web::App::new().service(Health::server(store.clone()))
+    })
+    .bind((server.host.as_str(), server.port))?
+    .workers(server.workers)
+    .run();
+
+    Ok(web_server)
+}
+
+/// Liveness check, succeeds as long as the drainer process is running.
+#[instrument(skip_all)]
+pub async fn health() -> impl actix_web::Responder {
+    logger::info!("Drainer health was called");
+    HttpResponse::Ok().body("Drainer health is good")
+}
+
+/// Readiness check, succeeds when both Postgres and Redis are reachable.
+#[instrument(skip_all)]
+pub async fn deep_health_check(
+    store: web::Data<Arc<services::Store>>,
+) -> impl actix_web::Responder {
+    logger::info!("Drainer deep health check was called");
+
+    let database = match health_check_db(&store).await {
+        Ok(()) => true,
+        Err(error) => {
+            logger::error!(?error, "Drainer database health check failed");
+            false
+        }
+    };
+
+    // The connection pool flags Redis as unavailable as soon as it loses the connection
+    let redis = store
+        .redis_conn
+        .is_redis_available
+        .load(std::sync::atomic::Ordering::SeqCst)
+        && match store
+            .redis_conn
+            .stream_get_length(utils::get_drainer_stream_name(store.get_ref().clone(), 0))
+            .await
+        {
+            Ok(_) => true,
+            Err(error) => {
+                logger::error!(?error, "Drainer redis health check failed");
+                false
+            }
+        };
+
+    let response = DrainerHealthCheckResponse { database, redis };
+    if database && redis {
+        HttpResponse::Ok().json(response)
+    } else {
+        HttpResponse::ServiceUnavailable().json(response)
+    }
+}
+
+/// Reports how far behind the drainer is on each partition.
+#[instrument(skip_all)]
+pub async fn stream_lag(store: web::Data<Arc<services::Store>>) -> impl actix_web::Responder {
+    match get_drainer_lag(store.get_ref().clone()).await {
+        Ok(lag) => HttpResponse::Ok().json(lag),
+        Err(error) => {
+            logger::error!(?error, "Failed to fetch the drainer stream lag");
+            HttpResponse::ServiceUnavailable().finish()
+        }
+    }
+}
+
+/// Records the lag of every partition in the metrics every `lag_interval` milliseconds, instead
+/// of adding the Redis calls it takes to every drain iteration.
+pub async fn record_lag_periodically(store: Arc<services::Store>, lag_interval: u32) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_millis(lag_interval.into()));
+    loop {
+        interval.tick().await;
+        if let Err(error) = get_drainer_lag(store.clone()).await {
+            logger::error!(?error, "Failed to record the drainer stream lag");
+        }
+    }
+}
+
+/// Fetches the lag of every partition, recording it in the per-partition metrics.
+pub async fn get_drainer_lag(
+    store: Arc<services::Store>,
+) -> errors::DrainerResult<DrainerLagResponse> {
+    let mut partitions = Vec::with_capacity(store.config.drainer_num_partitions.into());
+    for partition in 0..store.config.drainer_num_partitions {
+        partitions.push(get_partition_lag(store.clone(), partition).await?);
+    }
+
+    Ok(DrainerLagResponse::from_partitions(partitions))
+}
+
+impl DrainerLagResponse {
+    pub fn from_partitions(partitions: Vec<PartitionLag>) -> Self {
+        Self {
+            total_stream_length: partitions.iter().map(|lag| lag.stream_length).sum(),
+            max_oldest_entry_age_seconds: partitions
+                .iter()
+                .filter_map(|lag| lag.oldest_entry_age_seconds)
+                .max(),
+            total_dead_letter_length: partitions.iter().map(|lag| lag.dead_letter_length).sum(),
+            partitions,
+        }
+    }
+}
+
+/// Fetches the lag of a partition and records it in the per-partition metrics.
+pub async fn get_partition_lag(
+    store: Arc<services::Store>,
+    partition: u8,
+) -> errors::DrainerResult<PartitionLag> {
+    let stream_name = utils::get_drainer_stream_name(store.clone(), partition);
+    let dead_letter_stream_name =
+        utils::get_drainer_dead_letter_stream_name(store.clone(), partition);
+
+    let stream_length = store
+        .redis_conn
+        .stream_get_length(stream_name.as_str())
+        .await
+        .map_err(DrainerError::from)
+        .into_report()?;
+
+    let oldest_entry_age_seconds = store
+        .redis_conn
+        .stream_read_range(&stream_name, "-", "+", Some(1))
+        .await
+        .map_err(DrainerError::from)
+        .into_report()?
+        .first()
+        .and_then(|(_, fields)| utils::get_entry_age(fields.get("pushed_at")));
+
+    let dead_letter_length = store
+        .redis_conn
+        .stream_get_length(dead_letter_stream_name.as_str())
+        .await
+        .map_err(DrainerError::from)
+        .into_report()?;
+
+    let attributes = [metrics::KeyValue::new("partition", i64::from(partition))];
+    metrics::STREAM_LENGTH.record(
+        &metrics::CONTEXT,
+        u64::try_from(stream_length).unwrap_or(u64::MAX),
+        &attributes,
+    );
+    metrics::DEAD_LETTER_STREAM_LENGTH.record(
+        &metrics::CONTEXT,
+        u64::try_from(dead_letter_length).unwrap_or(u64::MAX),
+        &attributes,
+    );
+    if let Some(age) = oldest_entry_age_seconds {
+        metrics::OLDEST_ENTRY_AGE_SECONDS.record(&metrics::CONTEXT, age, &attributes);
+    }
+
+    Ok(PartitionLag {
+        partition,
+        stream_length,
+        oldest_entry_age_seconds,
+        dead_letter_length,
+    })
+}
+
+async fn health_check_db(store: &services::Store) -> Result<(), String> {
+    let conn = store
+        .master_pool
+        .get()
+        .await
+        .map_err(|error| format!("{error:?}"))?;
+
+    diesel::sql_query("SELECT 1")
+        .execute_async(&*conn)
+        .await
+        .map(|_| ())
+        .map_err(|error| format!("{error:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lag_is_aggregated_over_partitions() {
+        let lag = DrainerLagResponse::from_partitions(vec![
+            PartitionLag {
+                partition: 0,
+                stream_length: 10,
+                oldest_entry_age_seconds: Some(5),
+                dead_letter_length: 1,
+            },
+            PartitionLag {
+                partition: 1,
+                stream_length: 0,
+                oldest_entry_age_seconds: None,
+                dead_letter_length: 0,
+            },
+            PartitionLag {
+                partition: 2,
+                stream_length: 3,
+                oldest_entry_age_seconds: Some(42),
+                dead_letter_length: 2,
+            },
+        ]);
+
+        assert_eq!(lag.total_stream_length, 13);
+        assert_eq!(lag.max_oldest_entry_age_seconds, Some(42));
+        assert_eq!(lag.total_dead_letter_length, 3);
+        assert_eq!(lag.partitions.len(), 3);
+    }
+
+    #[test]
+    fn test_lag_of_empty_partitions() {
+        let lag = DrainerLagResponse::from_partitions(vec![PartitionLag {
+            partition: 0,
+            stream_length: 0,
+            oldest_entry_age_seconds: None,
+            dead_letter_length: 0,
+        }]);
+
+        assert_eq!(lag.total_stream_length, 0);
+        assert_eq!(lag.max_oldest_entry_age_seconds, None);
+        assert_eq!(lag.total_dead_letter_length, 0);
+    }
+}
@@ -1,5 +1,7 @@
 mod connection;
+pub mod dead_letter;
 pub mod errors;
+pub mod health_check;
 pub mod logger;
 pub(crate) mod metrics;
 pub mod services;
@@ -8,7 +10,7 @@ mod utils;
 use std::sync::{atomic, Arc};
 
 use common_utils::{ext_traits::StringExt, signals::get_allowed_signals};
-use diesel_models::kv;
+use diesel_models::{errors::DatabaseError, kv, payment_method::PaymentMethod};
 use error_stack::{IntoReport, ResultExt};
 use router_env::{instrument, tracing};
 use tokio::sync::{mpsc, oneshot};
@@ -21,6 +23,7 @@ pub async fn start_drainer(
     max_read_count: u64,
     shutdown_interval: u32,
     loop_interval: u32,
+    lag_interval: u32,
 ) -> errors::DrainerResult<()> {
     let mut stream_index: u8 = 0;
     let jobs_picked = Arc::new(atomic::AtomicU8::new(0));
@@ -47,9 +50,20 @@ pub async fn start_drainer(
     // Spawn a task to monitor if redis is down or not
     tokio::spawn(async move { redis_conn_clone.on_error(redis_error_tx).await });
 
+    let redis_conn_clone = store.redis_conn.clone();
+
+    // Spawn a task to record the reconnections and failovers of redis
+    tokio::spawn(async move { redis_conn_clone.on_reconnect().await });
+
     //Spawns a task to send shutdown signal if redis goes down
     tokio::spawn(redis_error_receiver(redis_error_rx, tx));
 
+    // Spawn a task to record the lag of the partitions in the metrics
+    let lag_handle = tokio::spawn(health_check::record_lag_periodically(
+        store.clone(),
+        lag_interval,
+    ));
+
     let active_tasks = Arc::new(atomic::AtomicU64::new(0));
     'event: loop {
         metrics::DRAINER_HEALTH.add(&metrics::CONTEXT, 1, &[]);
@@ -89,6 +103,7 @@ pub async fn start_drainer(
             }
         }
     }
+    lag_handle.abort();
     handle.close();
     task_handle
         .await
@@ -125,11 +140,14 @@ async fn drainer_handler(
     active_tasks.fetch_add(1, atomic::Ordering::Release);
 
     let stream_name = utils::get_drainer_stream_name(store.clone(), stream_index);
+    let dead_letter_stream_name =
+        utils::get_drainer_dead_letter_stream_name(store.clone(), stream_index);
 
     let drainer_result = Box::pin(drainer(
         store.clone(),
         max_read_count,
         stream_name.as_str(),
+        dead_letter_stream_name.as_str(),
         jobs_picked,
     ))
     .await;
@@ -152,6 +170,7 @@ async fn drainer(
     store: Arc<Store>,
     max_read_count: u64,
     stream_name: &str,
+    dead_letter_stream_name: &str,
     jobs_picked: Arc<atomic::AtomicU8>,
 ) -> errors::DrainerResult<()> {
     let stream_read =
@@ -177,7 +196,7 @@ async fn drainer(
             }
         };
     // parse_stream_entries returns error if no entries is found, handle it
-    let (entries, last_entry_id) = utils::parse_stream_entries(&stream_read, stream_name)?;
+    let (entries, _) = utils::parse_stream_entries(&stream_read, stream_name)?;
     let read_count = entries.len();
 
     metrics::JOBS_PICKED_PER_STREAM.add(
@@ -191,7 +210,9 @@ async fn drainer(
 
     let session_id = common_utils::generate_id_with_default_len("drainer_session");
 
-    // TODO: Handle errors when deserialization fails and when DB error occurs
+    let mut last_drained_entry_id = None;
+    let mut drained_count = 0;
+
     for entry in entries {
         let typed_sql = entry.1.get("typed_sql").map_or(String::new(), Clone::clone);
         let request_id = entry
@@ -205,134 +226,319 @@ async fn drainer(
         tracing::Span::current().record("global_id", global_id);
         tracing::Span::current().record("session_id", &session_id);
 
-        let result = typed_sql.parse_struct("DBOperation");
-
-        let db_op = match result {
-            Ok(f) => f,
-            Err(err) => {
-                logger::error!(operation= "deserialization",error = %err);
-                metrics::STREAM_PARSE_FAIL.add(&metrics::CONTEXT, 1, &[]);
-                continue;
-            }
-        };
-
-        let conn = pg_connection(&store.master_pool).await;
-        let insert_op = "insert";
-        let update_op = "update";
-        let payment_intent = "payment_intent";
-        let payment_attempt = "payment_attempt";
-        let refund = "refund";
-        let reverse_lookup = "reverse_lookup";
-        let address = "address";
-        match db_op {
-            // TODO: Handle errors
-            kv::DBOperation::Insert { insertable } => {
-                let (_, execution_time) = common_utils::date_time::time_it(|| async {
-                    match insertable {
-                        kv::Insertable::PaymentIntent(a) => {
-                            macro_util::handle_resp!(
-                                a.insert(&conn).await,
-                                insert_op,
-                                payment_intent
-                            )
-                        }
-                        kv::Insertable::PaymentAttempt(a) => {
-                            macro_util::handle_resp!(
-                                a.insert(&conn).await,
-                                insert_op,
-                                payment_attempt
-                            )
-                        }
-                        kv::Insertable::Refund(a) => {
-                            macro_util::handle_resp!(a.insert(&conn).await, insert_op, refund)
-                        }
-                        kv::Insertable::Address(addr) => {
-                            macro_util::handle_resp!(addr.insert(&conn).await, insert_op, address)
-                        }
-                        kv::Insertable::ReverseLookUp(rev) => {
-                            macro_util::handle_resp!(
-                                rev.insert(&conn).await,
-                                insert_op,
-                                reverse_lookup
-                            )
-                        }
-                    }
-                })
-                .await;
-                metrics::QUERY_EXECUTION_TIME.record(
-                    &metrics::CONTEXT,
-                    execution_time,
-                    &[metrics::KeyValue {
-                        key: "operation".into(),
-                        value: insert_op.into(),
-                    }],
+        match drain_entry(&store, typed_sql, pushed_at).await {
+            Ok(()) => (),
+            // The entry is left in the stream, along with the ones after it, for the next
+            // iteration to apply them in order once the database is back
+            Err(EntryError::Transient(error)) => {
+                logger::error!(
+                    entry_id = %entry.0,
+                    %error,
+                    "Leaving the entry in the stream after failing to apply it"
                 );
-                utils::push_drainer_delay(pushed_at, insert_op.to_string());
+                break;
             }
-            kv::DBOperation::Update { updatable } => {
-                let (_, execution_time) = common_utils::date_time::time_it(|| async {
-                    match updatable {
-                        kv::Updateable::PaymentIntentUpdate(a) => {
-                            macro_util::handle_resp!(
-                                a.orig.update(&conn, a.update_data).await,
-                                update_op,
-                                payment_intent
-                            )
-                        }
-                        kv::Updateable::PaymentAttemptUpdate(a) => {
-                            macro_util::handle_resp!(
-                                a.orig.update_with_attempt_id(&conn, a.update_data).await,
-                                update_op,
-                                payment_attempt
-                            )
-                        }
-                        kv::Updateable::RefundUpdate(a) => {
-                            macro_util::handle_resp!(
-                                a.orig.update(&conn, a.update_data).await,
-                                update_op,
-                                refund
-                            )
-                        }
-                        kv::Updateable::AddressUpdate(a) => macro_util::handle_resp!(
-                            a.orig.update(&conn, a.update_data).await,
-                            update_op,
-                            address
-                        ),
-                    }
-                })
-                .await;
-                metrics::QUERY_EXECUTION_TIME.record(
-                    &metrics::CONTEXT,
-                    execution_time,
-                    &[metrics::KeyValue {
-                        key: "operation".into(),
-                        value: update_op.into(),
-                    }],
-                );
-                utils::push_drainer_delay(pushed_at, update_op.to_string());
-            }
-            kv::DBOperation::Delete => {
-                // [#224]: Implement this
-                logger::error!("Not implemented!");
+            Err(EntryError::Permanent(error)) => {
+                // The entry is left in the stream, along with the ones after it, if it cannot be
+                // dead-lettered, so that it is not lost when the stream is trimmed
+                if let Err(dead_letter_error) =
+                    dead_letter::push_entry(&store, dead_letter_stream_name, entry, &error).await
+                {
+                    logger::error!(
+                        entry_id = %entry.0,
+                        ?dead_letter_error,
+                        "Failed to move the entry to the dead-letter stream"
+                    );
+                    break;
+                }
             }
-        };
+        }
+
+        last_drained_entry_id = Some(entry.0.as_str());
+        drained_count += 1;
     }
 
+    let last_drained_entry_id = match last_drained_entry_id {
+        Some(entry_id) => entry_id,
+        None => return Ok(()),
+    };
+
     let entries_trimmed =
-        utils::trim_from_stream(stream_name, last_entry_id.as_str(), &store.redis_conn).await?;
+        utils::trim_from_stream(stream_name, last_drained_entry_id, &store.redis_conn).await?;
 
-    if read_count != entries_trimmed {
+    if drained_count != entries_trimmed {
         logger::error!(
             read_entries = %read_count,
+            drained_entries = %drained_count,
             trimmed_entries = %entries_trimmed,
             ?entries,
-            "Assertion Failed no. of entries read from the stream doesn't match no. of entries trimmed"
+            "Assertion Failed no. of entries drained from the stream doesn't match no. of entries trimmed"
         );
     }
 
     Ok(())
 }
 
+/// Error a stream entry failed to be applied to the database with.
+#[derive(Debug)]
+enum EntryError {
+    /// The database could not be reached or gave up on the query, applying the entry again may
+    /// succeed
+    Transient(String),
+    /// The entry fails the same way however many times it is applied
+    Permanent(String),
+}
+
+impl EntryError {
+    fn from_database_error(error: &error_stack::Report<DatabaseError>) -> Self {
+        if is_transient_error(error) {
+            Self::Transient(format!("{error:?}"))
+        } else {
+            Self::Permanent(format!("{error:?}"))
+        }
+    }
+}
+
+/// Returns whether the database error may go away on its own, such as a dropped connection or
+/// a serialization failure, as opposed to the errors the query would fail with again.
+fn is_transient_error(error: &error_stack::Report<DatabaseError>) -> bool {
+    matches!(
+        error.current_context(),
+        DatabaseError::DatabaseConnectionError
+    ) || matches!(
+        error.downcast_ref::<diesel::result::Error>(),
+        Some(
+            diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::SerializationFailure
+                    | diesel::result::DatabaseErrorKind::ClosedConnection
+                    | diesel::result::DatabaseErrorKind::UnableToSendCommand,
+                _,
+            ) | diesel::result::Error::BrokenTransactionManager
+        )
+    )
+}
+
+/// Applies a single stream entry to the database, retrying it in place as long as it fails with
+/// a transient error, up to the configured number of retries.
+async fn drain_entry(
+    store: &Store,
+    typed_sql: String,
+    pushed_at: Option<&String>,
+) -> Result<(), EntryError> {
+    let mut retries = 0;
+    loop {
+        let db_op = typed_sql.parse_struct("DBOperation").map_err(|err| {
+            logger::error!(operation= "deserialization",error = %err);
+            metrics::STREAM_PARSE_FAIL.add(&metrics::CONTEXT, 1, &[]);
+            EntryError::Permanent(format!("{err:?}"))
+        })?;
+
+        match execute_db_operation(store, db_op, pushed_at).await {
+            Err(EntryError::Transient(error)) if retries < store.config.drainer_max_db_retries => {
+                retries += 1;
+                logger::warn!(%error, retries, "Retrying the entry after a transient database error");
+                metrics::QUERY_RETRIES.add(&metrics::CONTEXT, 1, &[]);
+                tokio::time::sleep(std::time::Duration::from_millis(
+                    u64::from(store.config.drainer_db_retry_interval) * u64::from(retries),
+                ))
+                .await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Applies a single stream entry to the database, returning the error it failed with.
+async fn execute_db_operation(
+    store: &Store,
+    db_op: kv::DBOperation,
+    pushed_at: Option<&String>,
+) -> Result<(), EntryError> {
+    let conn = pg_connection(&store.master_pool).await.map_err(|error| {
+        logger::error!(?error);
+        EntryError::Transient(format!("{error:?}"))
+    })?;
+    let insert_op = "insert";
+    let update_op = "update";
+    let delete_op = "delete";
+    let payment_intent = "payment_intent";
+    let payment_attempt = "payment_attempt";
+    let refund = "refund";
+    let reverse_lookup = "reverse_lookup";
+    let address = "address";
+    let customer = "customer";
+    let mandate = "mandate";
+    let payment_method = "payment_method";
+    match db_op {
+        kv::DBOperation::Insert { insertable } => {
+            let (result, execution_time) = common_utils::date_time::time_it(|| async {
+                match insertable {
+                    kv::Insertable::PaymentIntent(a) => {
+                        macro_util::handle_resp!(a.insert(&conn).await, insert_op, payment_intent)
+                    }
+                    kv::Insertable::PaymentAttempt(a) => {
+                        macro_util::handle_resp!(a.insert(&conn).await, insert_op, payment_attempt)
+                    }
+                    kv::Insertable::Refund(a) => {
+                        macro_util::handle_resp!(a.insert(&conn).await, insert_op, refund)
+                    }
+                    kv::Insertable::Address(addr) => {
+                        macro_util::handle_resp!(addr.insert(&conn).await, insert_op, address)
+                    }
+                    kv::Insertable::ReverseLookUp(rev) => {
+                        macro_util::handle_resp!(rev.insert(&conn).await, insert_op, reverse_lookup)
+                    }
+                    kv::Insertable::Customer(a) => {
+                        macro_util::handle_resp!(a.insert(&conn).await, insert_op, customer)
+                    }
+                    kv::Insertable::Mandate(a) => {
+                        macro_util::handle_resp!(a.insert(&conn).await, insert_op, mandate)
+                    }
+                    kv::Insertable::PaymentMethod(a) => {
+                        macro_util::handle_resp!(a.insert(&conn).await, insert_op, payment_method)
+                    }
+                }
+            })
+            .await;
+            metrics::QUERY_EXECUTION_TIME.record(
+                &metrics::CONTEXT,
+                execution_time,
+                &[metrics::KeyValue {
+                    key: "operation".into(),
+                    value: insert_op.into(),
+                }],
+            );
+            utils::push_drainer_delay(pushed_at, insert_op.to_string());
+            result
+        }
+        kv::DBOperation::Update { updatable } => {
+            let (result, execution_time) = common_utils::date_time::time_it(|| async {
+                match updatable {
+                    kv::Updateable::PaymentIntentUpdate(a) => {
+                        macro_util::handle_resp!(
+                            a.orig.update(&conn, a.update_data).await,
+                            update_op,
+                            payment_intent
+                        )
+                    }
+                    kv::Updateable::PaymentAttemptUpdate(a) => {
+                        macro_util::handle_resp!(
+                            a.orig.update_with_attempt_id(&conn, a.update_data).await,
+                            update_op,
+                            payment_attempt
+                        )
+                    }
+                    kv::Updateable::RefundUpdate(a) => {
+                        macro_util::handle_resp!(
+                            a.orig.update(&conn, a.update_data).await,
+                            update_op,
+                            refund
+                        )
+                    }
+                    kv::Updateable::AddressUpdate(a) => macro_util::handle_resp!(
+                        a.orig.update(&conn, a.update_data).await,
+                        update_op,
+                        address
+                    ),
+                    kv::Updateable::CustomerUpdate(a) => macro_util::handle_resp!(
+                        diesel_models::customers::Customer::update_by_customer_id_merchant_id(
+                            &conn,
+                            a.orig.customer_id.clone(),
+                            a.orig.merchant_id.clone(),
+                            a.update_data,
+                        )
+                        .await,
+                        update_op,
+                        customer
+                    ),
+                    kv::Updateable::MandateUpdate(a) => macro_util::handle_resp!(
+                        diesel_models::mandate::Mandate::update_by_merchant_id_mandate_id(
+                            &conn,
+                            &a.orig.merchant_id,
+                            &a.orig.mandate_id,
+                            a.update_data,
+                        )
+                        .await,
+                        update_op,
+                        mandate
+                    ),
+                    kv::Updateable::PaymentMethodUpdate(a) => macro_util::handle_resp!(
+                        a.orig
+                            .update_with_payment_method_id(&conn, a.update_data)
+                            .await,
+                        update_op,
+                        payment_method
+                    ),
+                }
+            })
+            .await;
+            metrics::QUERY_EXECUTION_TIME.record(
+                &metrics::CONTEXT,
+                execution_time,
+                &[metrics::KeyValue {
+                    key: "operation".into(),
+                    value: update_op.into(),
+                }],
+            );
+            utils::push_drainer_delay(pushed_at, update_op.to_string());
+            result
+        }
+        kv::DBOperation::Delete { deletable } => {
+            let (result, execution_time) = common_utils::date_time::time_it(|| async {
+                match deletable {
+                    kv::Deletable::Customer {
+                        customer_id,
+                        merchant_id,
+                    } => macro_util::handle_resp!(
+                        diesel_models::customers::Customer::delete_by_customer_id_merchant_id(
+                            &conn,
+                            &customer_id,
+                            &merchant_id,
+                        )
+                        .await,
+                        delete_op,
+                        customer
+                    ),
+                    kv::Deletable::PaymentMethod {
+                        merchant_id,
+                        payment_method_id,
+                    } => macro_util::handle_resp!(
+                        // The row may already have been deleted from the database by the
+                        // request deleting it from redis
+                        match PaymentMethod::delete_by_merchant_id_payment_method_id(
+                            &conn,
+                            &merchant_id,
+                            &payment_method_id,
+                        )
+                        .await
+                        {
+                            Err(error)
+                                if matches!(error.current_context(), DatabaseError::NotFound) =>
+                            {
+                                Ok(None)
+                            }
+                            result => result.map(Some),
+                        },
+                        delete_op,
+                        payment_method
+                    ),
+                }
+            })
+            .await;
+            metrics::QUERY_EXECUTION_TIME.record(
+                &metrics::CONTEXT,
+                execution_time,
+                &[metrics::KeyValue {
+                    key: "operation".into(),
+                    value: delete_op.into(),
+                }],
+            );
+            utils::push_drainer_delay(pushed_at, delete_op.to_string());
+            result
+        }
+    }
+}
+
 mod macro_util {
 
     macro_rules! handle_resp {
@@ -346,6 +552,7 @@ mod macro_util {
                             value: $table.into(),
                         }
                     ]);
+                    Ok(())
                 }
                 Err(err) => {
                     logger::error!(operation = %$op_type, table = %$table, ?err);
@@ -355,9 +562,86 @@ mod macro_util {
                             value: $table.into(),
                         }
                     ]);
+                    Err(EntryError::from_database_error(&err))
                 }
             }
         };
     }
     pub(crate) use handle_resp;
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use diesel::result::{DatabaseErrorKind, Error as DieselError};
+
+    use super::*;
+
+    fn get_database_error(
+        error: DieselError,
+        context: DatabaseError,
+    ) -> error_stack::Report<DatabaseError> {
+        Err::<(), _>(error)
+            .into_report()
+            .change_context(context)
+            .unwrap_err()
+    }
+
+    #[test]
+    fn test_transient_database_errors_are_retried() {
+        for kind in [
+            DatabaseErrorKind::SerializationFailure,
+            DatabaseErrorKind::ClosedConnection,
+            DatabaseErrorKind::UnableToSendCommand,
+        ] {
+            let error = get_database_error(
+                DieselError::DatabaseError(kind, Box::new(String::new())),
+                DatabaseError::Others,
+            );
+            assert!(matches!(
+                EntryError::from_database_error(&error),
+                EntryError::Transient(_)
+            ));
+        }
+
+        let error = error_stack::report!(DatabaseError::DatabaseConnectionError);
+        assert!(is_transient_error(&error));
+    }
+
+    #[test]
+    fn test_other_database_errors_are_dead_lettered() {
+        let unique_violation = get_database_error(
+            DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, Box::new(String::new())),
+            DatabaseError::UniqueViolation,
+        );
+        assert!(matches!(
+            EntryError::from_database_error(&unique_violation),
+            EntryError::Permanent(_)
+        ));
+
+        let not_found = get_database_error(DieselError::NotFound, DatabaseError::NotFound);
+        assert!(!is_transient_error(&not_found));
+    }
+
+    #[test]
+    fn test_deletions_are_read_from_the_stream() {
+        let typed_sql = serde_json::to_string(&kv::TypedSql {
+            op: kv::DBOperation::Delete {
+                deletable: kv::Deletable::PaymentMethod {
+                    merchant_id: "merchant_123".to_string(),
+                    payment_method_id: "pm_123".to_string(),
+                },
+            },
+        })
+        .unwrap();
+
+        let db_op: kv::DBOperation = typed_sql.parse_struct("DBOperation").unwrap();
+        assert!(matches!(
+            db_op,
+            kv::DBOperation::Delete {
+                deletable: kv::Deletable::PaymentMethod { payment_method_id, .. },
+            } if payment_method_id == "pm_123"
+        ));
+    }
+}
@@ -1,4 +1,7 @@
-use drainer::{errors::DrainerResult, logger::logger, services, settings, start_drainer};
+use drainer::{
+    dead_letter, errors::DrainerResult, health_check, logger::logger, services, settings,
+    start_drainer,
+};
 
 #[tokio::main]
 async fn main() -> DrainerResult<()> {
@@ -15,10 +18,15 @@ async fn main() -> DrainerResult<()> {
     let store = services::Store::new(&conf, false).await;
     let store = std::sync::Arc::new(store);
 
+    if let Some(settings::Command::DeadLetter(command)) = cmd_line.command {
+        return dead_letter::run_command(store, command).await;
+    }
+
     let number_of_streams = store.config.drainer_num_partitions;
     let max_read_count = conf.drainer.max_read_count;
     let shutdown_intervals = conf.drainer.shutdown_interval;
     let loop_interval = conf.drainer.loop_interval;
+    let lag_interval = conf.drainer.lag_interval;
 
     let _guard = router_env::setup(
         &conf.log,
@@ -29,12 +37,26 @@ async fn main() -> DrainerResult<()> {
     logger::debug!(startup_config=?conf);
     logger::info!("Drainer started [{:?}] [{:?}]", conf.drainer, conf.log);
 
+    #[allow(clippy::expect_used)]
+    let web_server = Box::pin(health_check::start_web_server(
+        conf.drainer.server.clone(),
+        store.clone(),
+    ))
+    .await
+    .expect("Failed to create the drainer health check server");
+
+    tokio::spawn(async move {
+        let _ = web_server.await;
+        logger::error!("The drainer health check server stopped");
+    });
+
     start_drainer(
         store.clone(),
         number_of_streams,
         max_read_count,
         shutdown_intervals,
         loop_interval,
+        lag_interval,
     )
     .await?;
 
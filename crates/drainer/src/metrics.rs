@@ -1,6 +1,7 @@
 pub use router_env::opentelemetry::KeyValue;
 use router_env::{
-    counter_metric, global_meter, histogram_metric, histogram_metric_i64, metrics_context,
+    counter_metric, global_meter, histogram_metric, histogram_metric_i64, histogram_metric_u64,
+    metrics_context,
 };
 
 metrics_context!(CONTEXT);
@@ -16,9 +17,16 @@ counter_metric!(SUCCESSFUL_SHUTDOWN, DRAINER_METER);
 counter_metric!(STREAM_EMPTY, DRAINER_METER);
 counter_metric!(STREAM_PARSE_FAIL, DRAINER_METER);
 counter_metric!(DRAINER_HEALTH, DRAINER_METER);
+counter_metric!(ENTRIES_DEAD_LETTERED, DRAINER_METER);
+counter_metric!(ENTRIES_REPLAYED, DRAINER_METER);
+counter_metric!(ENTRIES_REPLAY_SKIPPED, DRAINER_METER);
+counter_metric!(QUERY_RETRIES, DRAINER_METER);
 
 histogram_metric!(QUERY_EXECUTION_TIME, DRAINER_METER); // Time in (ms) milliseconds
 histogram_metric!(REDIS_STREAM_READ_TIME, DRAINER_METER); // Time in (ms) milliseconds
 histogram_metric!(REDIS_STREAM_TRIM_TIME, DRAINER_METER); // Time in (ms) milliseconds
 histogram_metric!(CLEANUP_TIME, DRAINER_METER); // Time in (ms) milliseconds
 histogram_metric_i64!(DRAINER_DELAY_SECONDS, DRAINER_METER); // Time in (s) seconds
+histogram_metric_i64!(OLDEST_ENTRY_AGE_SECONDS, DRAINER_METER); // Time in (s) seconds
+histogram_metric_u64!(STREAM_LENGTH, DRAINER_METER);
+histogram_metric_u64!(DEAD_LETTER_STREAM_LENGTH, DRAINER_METER);
@@ -13,7 +13,10 @@ pub struct Store {
 #[derive(Clone)]
 pub struct StoreConfig {
     pub drainer_stream_name: String,
+    pub drainer_dead_letter_stream_name: String,
     pub drainer_num_partitions: u8,
+    pub drainer_max_db_retries: u8,
+    pub drainer_db_retry_interval: u32,
 }
 
 impl Store {
@@ -29,7 +32,10 @@ impl Store {
             redis_conn: Arc::new(crate::connection::redis_connection(config).await),
             config: StoreConfig {
                 drainer_stream_name: config.drainer.stream_name.clone(),
+                drainer_dead_letter_stream_name: config.drainer.dead_letter_stream_name.clone(),
                 drainer_num_partitions: config.drainer.num_partitions,
+                drainer_max_db_retries: config.drainer.max_db_retries,
+                drainer_db_retry_interval: config.drainer.db_retry_interval,
             },
             request_id: None,
         }
@@ -39,4 +45,12 @@ impl Store {
         // Example: {shard_5}_drainer_stream
         format!("{{{}}}_{}", shard_key, self.config.drainer_stream_name,)
     }
+
+    pub fn drainer_dead_letter_stream(&self, shard_key: &str) -> String {
+        // Example: {shard_5}_drainer_dead_letter_stream
+        format!(
+            "{{{}}}_{}",
+            shard_key, self.config.drainer_dead_letter_stream_name
+        )
+    }
 }
@@ -23,6 +23,48 @@ pub struct CmdLineConf {
     /// Application will look for "config/config.toml" if this option isn't specified.
     #[arg(short = 'f', long, value_name = "FILE")]
     pub config_path: Option<PathBuf>,
+    /// Runs a maintenance command instead of starting the drainer.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// Inspects and replays the stream entries that could not be applied to the database.
+    #[command(subcommand)]
+    DeadLetter(DeadLetterCommand),
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum DeadLetterCommand {
+    /// Lists the dead-lettered entries of a partition, oldest first.
+    List {
+        #[arg(short, long)]
+        partition: u8,
+        #[arg(short, long, default_value_t = 100)]
+        count: u64,
+    },
+    /// Prints a single dead-lettered entry along with the error it failed with.
+    Inspect {
+        #[arg(short, long)]
+        partition: u8,
+        #[arg(short, long)]
+        entry_id: String,
+    },
+    /// Pushes dead-lettered entries back to their partition, so that the drainer applies them
+    /// again. Replays the oldest `count` entries when no entry IDs are given.
+    Replay {
+        #[arg(short, long)]
+        partition: u8,
+        #[arg(short, long = "entry-id")]
+        entry_ids: Vec<String>,
+        #[arg(short, long, default_value_t = 100)]
+        count: u64,
+        /// Replays the updates of rows modified since the update was made as well, overwriting
+        /// the newer changes.
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -36,6 +78,14 @@ pub struct Settings {
     pub kms: kms::KmsConfig,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct Server {
+    pub port: u16,
+    pub workers: usize,
+    pub host: String,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(default)]
 pub struct Database {
@@ -52,10 +102,29 @@ pub struct Database {
 #[serde(default)]
 pub struct DrainerSettings {
     pub stream_name: String,
+    pub dead_letter_stream_name: String,
     pub num_partitions: u8,
     pub max_read_count: u64,
     pub shutdown_interval: u32, // in milliseconds
     pub loop_interval: u32,     // in milliseconds
+    /// Interval at which the lag of every partition is recorded in the metrics
+    pub lag_interval: u32, // in milliseconds
+    /// Number of times an entry failing with a transient database error is retried before the
+    /// partition is left for the next iteration
+    pub max_db_retries: u8,
+    pub db_retry_interval: u32, // in milliseconds
+    /// Server exposing the health checks and the lag of the drainer
+    pub server: Server,
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: 8081,
+            workers: 1,
+        }
+    }
 }
 
 impl Default for Database {
@@ -76,14 +145,29 @@ impl Default for DrainerSettings {
     fn default() -> Self {
         Self {
             stream_name: "DRAINER_STREAM".into(),
+            dead_letter_stream_name: "DRAINER_DEAD_LETTER_STREAM".into(),
             num_partitions: 64,
             max_read_count: 100,
             shutdown_interval: 1000, // in milliseconds
             loop_interval: 100,      // in milliseconds
+            lag_interval: 60000,     // in milliseconds
+            max_db_retries: 3,
+            db_retry_interval: 100, // in milliseconds
+            server: Server::default(),
         }
     }
 }
 
+impl Server {
+    fn validate(&self) -> Result<(), errors::DrainerError> {
+        common_utils::fp_utils::when(self.host.is_default_or_empty(), || {
+            Err(errors::DrainerError::ConfigParsingError(
+                "drainer server host must not be empty".into(),
+            ))
+        })
+    }
+}
+
 impl Database {
     fn validate(&self) -> Result<(), errors::DrainerError> {
         use common_utils::fp_utils::when;
@@ -116,11 +200,33 @@ impl Database {
 
 impl DrainerSettings {
     fn validate(&self) -> Result<(), errors::DrainerError> {
-        common_utils::fp_utils::when(self.stream_name.is_default_or_empty(), || {
+        use common_utils::fp_utils::when;
+
+        when(self.stream_name.is_default_or_empty(), || {
             Err(errors::DrainerError::ConfigParsingError(
                 "drainer stream name must not be empty".into(),
             ))
-        })
+        })?;
+
+        when(self.dead_letter_stream_name.is_default_or_empty(), || {
+            Err(errors::DrainerError::ConfigParsingError(
+                "drainer dead letter stream name must not be empty".into(),
+            ))
+        })?;
+
+        when(self.dead_letter_stream_name == self.stream_name, || {
+            Err(errors::DrainerError::ConfigParsingError(
+                "drainer dead letter stream name must differ from the stream name".into(),
+            ))
+        })?;
+
+        when(self.lag_interval == 0, || {
+            Err(errors::DrainerError::ConfigParsingError(
+                "drainer lag interval must not be zero".into(),
+            ))
+        })?;
+
+        self.server.validate()
     }
 }
 
@@ -153,7 +259,8 @@ impl Settings {
                     .try_parsing(true)
                     .separator("__")
                     .list_separator(",")
-                    .with_list_parse_key("redis.cluster_urls"),
+                    .with_list_parse_key("redis.cluster_urls")
+                    .with_list_parse_key("redis.sentinel_urls"),
             )
             .build()?;
 
@@ -39,6 +39,8 @@ pub async fn read_from_stream(
 ) -> errors::DrainerResult<StreamReadResult> {
     // "0-0" id gives first entry
     let stream_id = "0-0";
+    // Read from the primary, a lagging replica could still hold the entries trimmed after the
+    // previous iteration and have them applied twice
     let (output, execution_time) = common_utils::date_time::time_it(|| async {
         redis
             .stream_read_entries(stream_name, stream_id, Some(max_read_count))
@@ -129,24 +131,27 @@ pub fn parse_stream_entries<'a>(
 }
 
 pub fn push_drainer_delay(pushed_at: Option<&String>, operation: String) {
-    if let Some(pushed_at) = pushed_at {
-        if let Ok(time) = pushed_at.parse::<i64>() {
-            let drained_at = common_utils::date_time::now_unix_timestamp();
-            let delay = drained_at - time;
-
-            logger::debug!(operation = operation, delay = delay);
-            metrics::DRAINER_DELAY_SECONDS.record(
-                &metrics::CONTEXT,
-                delay,
-                &[metrics::KeyValue {
-                    key: "operation".into(),
-                    value: operation.into(),
-                }],
-            );
-        }
+    if let Some(delay) = get_entry_age(pushed_at) {
+        logger::debug!(operation = operation, delay = delay);
+        metrics::DRAINER_DELAY_SECONDS.record(
+            &metrics::CONTEXT,
+            delay,
+            &[metrics::KeyValue {
+                key: "operation".into(),
+                value: operation.into(),
+            }],
+        );
     }
 }
 
+/// Returns the number of seconds since the entry was pushed to the stream, from its `pushed_at`
+/// field.
+pub fn get_entry_age(pushed_at: Option<&String>) -> Option<i64> {
+    pushed_at
+        .and_then(|pushed_at| pushed_at.parse::<i64>().ok())
+        .map(|pushed_at| common_utils::date_time::now_unix_timestamp() - pushed_at)
+}
+
 // Here the output is in the format (stream_index, jobs_picked),
 // similar to the first argument of the function
 pub async fn increment_stream_index(
@@ -172,3 +177,10 @@ pub(crate) fn get_stream_key_flag(store: Arc<services::Store>, stream_index: u8)
 pub(crate) fn get_drainer_stream_name(store: Arc<services::Store>, stream_index: u8) -> String {
     store.drainer_stream(format!("shard_{stream_index}").as_str())
 }
+
+pub(crate) fn get_drainer_dead_letter_stream_name(
+    store: Arc<services::Store>,
+    stream_index: u8,
+) -> String {
+    store.drainer_dead_letter_stream(format!("shard_{stream_index}").as_str())
+}
@@ -57,6 +57,7 @@ fn get_program_data() -> (ast::Program<DummyOutput>, inputs::BackendInput) {
             mandate_type: None,
             payment_type: None,
         },
+        temporal: inputs::TemporalInput::default(),
     };
 
     let (_, program) = parser::program(code1).expect("Parser");
@@ -30,10 +30,42 @@ pub struct PaymentInput {
     pub setup_future_usage: Option<enums::SetupFutureUsage>,
 }
 
+/// The moment at which the routing decision is made, broken down in UTC
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemporalInput {
+    pub day_of_week: Option<enums::DayOfWeek>,
+    /// Minutes elapsed since midnight
+    pub time_of_day: Option<i64>,
+}
+
+impl TemporalInput {
+    pub fn from_unix_timestamp(timestamp: i64) -> Self {
+        const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+        // The unix epoch fell on a Thursday
+        let day_of_week = match (timestamp.div_euclid(SECONDS_PER_DAY) + 3).rem_euclid(7) {
+            0 => enums::DayOfWeek::Monday,
+            1 => enums::DayOfWeek::Tuesday,
+            2 => enums::DayOfWeek::Wednesday,
+            3 => enums::DayOfWeek::Thursday,
+            4 => enums::DayOfWeek::Friday,
+            5 => enums::DayOfWeek::Saturday,
+            _ => enums::DayOfWeek::Sunday,
+        };
+
+        Self {
+            day_of_week: Some(day_of_week),
+            time_of_day: Some(timestamp.rem_euclid(SECONDS_PER_DAY) / 60),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackendInput {
     pub metadata: Option<FxHashMap<String, String>>,
     pub payment: PaymentInput,
     pub payment_method: PaymentMethodInput,
     pub mandate: MandateData,
+    #[serde(default)]
+    pub temporal: TemporalInput,
 }
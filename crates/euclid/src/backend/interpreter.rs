@@ -3,10 +3,12 @@ pub mod types;
 use crate::{
     backend::{self, inputs, EuclidBackend},
     frontend::ast,
+    types::{CompiledRegexes, StrValueRefinement},
 };
 
 pub struct InterpreterBackend<O> {
     program: ast::Program<O>,
+    regexes: CompiledRegexes,
 }
 
 impl<O> InterpreterBackend<O>
@@ -26,6 +28,10 @@ where
                 ast::ComparisonType::GreaterThanEqual => num >= other,
                 ast::ComparisonType::Equal => num == other,
                 ast::ComparisonType::NotEqual => num != other,
+                ast::ComparisonType::StartsWith
+                | ast::ComparisonType::EndsWith
+                | ast::ComparisonType::Contains
+                | ast::ComparisonType::Matches => false,
             };
 
             if res {
@@ -36,12 +42,59 @@ where
         Ok(false)
     }
 
+    fn eval_str_comparison(
+        value: &str,
+        comparison_type: &ast::ComparisonType,
+        others: &[String],
+        regexes: &CompiledRegexes,
+    ) -> Option<bool> {
+        match comparison_type {
+            ast::ComparisonType::Equal => Some(others.iter().any(|other| value == other)),
+            ast::ComparisonType::NotEqual => Some(others.iter().all(|other| value != other)),
+            comparison_type => {
+                let refinement: Option<StrValueRefinement> = comparison_type.clone().into();
+                refinement.map(|refinement| {
+                    others
+                        .iter()
+                        .any(|pattern| refinement.check_compiled(pattern, value, regexes))
+                })
+            }
+        }
+    }
+
     fn eval_comparison(
         comparison: &ast::Comparison,
         ctx: &types::Context,
+        regexes: &CompiledRegexes,
     ) -> Result<bool, types::InterpreterError> {
         use ast::{ComparisonType::*, ValueType::*};
 
+        let invalid_comparison = || types::InterpreterError {
+            error_type: types::InterpreterErrorType::InvalidComparison,
+            metadata: comparison.metadata.clone(),
+        };
+
+        match &comparison.value {
+            MetadataVariant(md) => {
+                return ctx.get_metadata(&md.key).map_or(Ok(false), |value| {
+                    Self::eval_str_comparison(
+                        value,
+                        &comparison.comparison,
+                        std::slice::from_ref(&md.value),
+                        regexes,
+                    )
+                    .ok_or_else(invalid_comparison)
+                });
+            }
+            MetadataVariantArray(mda) => {
+                return ctx.get_metadata(&mda.key).map_or(Ok(false), |value| {
+                    Self::eval_str_comparison(value, &comparison.comparison, &mda.values, regexes)
+                        .ok_or_else(invalid_comparison)
+                });
+            }
+            _ => {}
+        }
+
         let value = ctx
             .get(&comparison.lhs)
             .ok_or_else(|| types::InterpreterError {
@@ -68,10 +121,18 @@ where
                 (Number(n), Equal, NumberComparisonArray(ncvec)) => {
                     Self::eval_number_comparison_array(*n, ncvec)
                 }
-                _ => Err(types::InterpreterError {
-                    error_type: types::InterpreterErrorType::InvalidComparison,
-                    metadata: comparison.metadata.clone(),
-                }),
+                (StrValue(s1), comparison_type, StrValue(s2)) => Self::eval_str_comparison(
+                    s1,
+                    comparison_type,
+                    std::slice::from_ref(s2),
+                    regexes,
+                )
+                .ok_or_else(invalid_comparison),
+                (StrValue(s), comparison_type, StrArray(svec)) => {
+                    Self::eval_str_comparison(s, comparison_type, svec, regexes)
+                        .ok_or_else(invalid_comparison)
+                }
+                _ => Err(invalid_comparison()),
             }
         } else {
             Ok(false)
@@ -81,9 +142,10 @@ where
     fn eval_if_condition(
         condition: &ast::IfCondition,
         ctx: &types::Context,
+        regexes: &CompiledRegexes,
     ) -> Result<bool, types::InterpreterError> {
         for comparison in condition {
-            let res = Self::eval_comparison(comparison, ctx)?;
+            let res = Self::eval_comparison(comparison, ctx, regexes)?;
 
             if !res {
                 return Ok(false);
@@ -96,8 +158,9 @@ where
     fn eval_if_statement(
         stmt: &ast::IfStatement,
         ctx: &types::Context,
+        regexes: &CompiledRegexes,
     ) -> Result<bool, types::InterpreterError> {
-        let cond_res = Self::eval_if_condition(&stmt.condition, ctx)?;
+        let cond_res = Self::eval_if_condition(&stmt.condition, ctx, regexes)?;
 
         if !cond_res {
             return Ok(false);
@@ -105,7 +168,7 @@ where
 
         if let Some(ref nested) = stmt.nested {
             for nested_if in nested {
-                let res = Self::eval_if_statement(nested_if, ctx)?;
+                let res = Self::eval_if_statement(nested_if, ctx, regexes)?;
 
                 if res {
                     return Ok(true);
@@ -121,9 +184,10 @@ where
     fn eval_rule_statements(
         statements: &[ast::IfStatement],
         ctx: &types::Context,
+        regexes: &CompiledRegexes,
     ) -> Result<bool, types::InterpreterError> {
         for stmt in statements {
-            let res = Self::eval_if_statement(stmt, ctx)?;
+            let res = Self::eval_if_statement(stmt, ctx, regexes)?;
 
             if res {
                 return Ok(true);
@@ -137,16 +201,18 @@ where
     fn eval_rule(
         rule: &ast::Rule<O>,
         ctx: &types::Context,
+        regexes: &CompiledRegexes,
     ) -> Result<bool, types::InterpreterError> {
-        Self::eval_rule_statements(&rule.statements, ctx)
+        Self::eval_rule_statements(&rule.statements, ctx, regexes)
     }
 
     fn eval_program(
         program: &ast::Program<O>,
         ctx: &types::Context,
+        regexes: &CompiledRegexes,
     ) -> Result<backend::BackendOutput<O>, types::InterpreterError> {
         for rule in &program.rules {
-            let res = Self::eval_rule(rule, ctx)?;
+            let res = Self::eval_rule(rule, ctx, regexes)?;
 
             if res {
                 return Ok(backend::BackendOutput {
@@ -161,6 +227,53 @@ where
             rule_name: None,
         })
     }
+
+    fn compile_statement_regexes(
+        stmt: &ast::IfStatement,
+        regexes: &mut CompiledRegexes,
+    ) -> Result<(), types::InterpreterError> {
+        for comparison in &stmt.condition {
+            if comparison.comparison != ast::ComparisonType::Matches {
+                continue;
+            }
+
+            let patterns = match &comparison.value {
+                ast::ValueType::StrValue(pattern) => std::slice::from_ref(pattern),
+                ast::ValueType::MetadataVariant(md) => std::slice::from_ref(&md.value),
+                ast::ValueType::StrArray(patterns) => patterns.as_slice(),
+                ast::ValueType::MetadataVariantArray(mda) => mda.values.as_slice(),
+                _ => &[],
+            };
+
+            for pattern in patterns {
+                regexes
+                    .insert(pattern)
+                    .map_err(|err| types::InterpreterError {
+                        error_type: types::InterpreterErrorType::InvalidRegex(err.to_string()),
+                        metadata: comparison.metadata.clone(),
+                    })?;
+            }
+        }
+
+        stmt.nested
+            .iter()
+            .flatten()
+            .try_for_each(|nested_stmt| Self::compile_statement_regexes(nested_stmt, regexes))
+    }
+
+    /// Compiles the patterns of all `Matches` comparisons in the program once, so that
+    /// executions only run the compiled regular expressions
+    fn compile_regexes(
+        program: &ast::Program<O>,
+    ) -> Result<CompiledRegexes, types::InterpreterError> {
+        let mut regexes = CompiledRegexes::default();
+
+        for stmt in program.rules.iter().flat_map(|rule| rule.statements.iter()) {
+            Self::compile_statement_regexes(stmt, &mut regexes)?;
+        }
+
+        Ok(regexes)
+    }
 }
 
 impl<O> EuclidBackend<O> for InterpreterBackend<O>
@@ -170,11 +283,13 @@ where
     type Error = types::InterpreterError;
 
     fn with_program(program: ast::Program<O>) -> Result<Self, Self::Error> {
-        Ok(Self { program })
+        let regexes = Self::compile_regexes(&program)?;
+
+        Ok(Self { program, regexes })
     }
 
     fn execute(&self, input: inputs::BackendInput) -> Result<super::BackendOutput<O>, Self::Error> {
         let ctx: types::Context = input.into();
-        Self::eval_program(&self.program, &ctx)
+        Self::eval_program(&self.program, &ctx, &self.regexes)
     }
 }
@@ -11,6 +11,8 @@ pub enum InterpreterErrorType {
     InvalidKey(String),
     #[error("Invalid Comparison")]
     InvalidComparison,
+    #[error("Invalid regular expression: {0}")]
+    InvalidRegex(String),
 }
 
 #[derive(Debug, Clone, Serialize, thiserror::Error)]
@@ -25,13 +27,22 @@ impl fmt::Display for InterpreterError {
     }
 }
 
-pub struct Context(HashMap<String, Option<ValueType>>);
+pub struct Context {
+    values: HashMap<String, Option<ValueType>>,
+    metadata: HashMap<String, String>,
+}
+
+impl Context {
+    pub fn get_metadata(&self, key: &str) -> Option<&String> {
+        self.metadata.get(key)
+    }
+}
 
 impl Deref for Context {
     type Target = HashMap<String, Option<ValueType>>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.values
     }
 }
 
@@ -74,8 +85,33 @@ impl From<inputs::BackendInput> for Context {
                 EuclidKey::PaymentCurrency.to_string(),
                 Some(ValueType::EnumVariant(input.payment.currency.to_string())),
             ),
+            (
+                EuclidKey::CardBin.to_string(),
+                input.payment.card_bin.map(ValueType::StrValue),
+            ),
+            (
+                EuclidKey::BusinessLabel.to_string(),
+                input.payment.business_label.map(ValueType::StrValue),
+            ),
+            (
+                EuclidKey::DayOfWeek.to_string(),
+                input
+                    .temporal
+                    .day_of_week
+                    .map(|dow| ValueType::EnumVariant(dow.to_string())),
+            ),
+            (
+                EuclidKey::TimeOfDay.to_string(),
+                input.temporal.time_of_day.map(ValueType::Number),
+            ),
         ]);
 
-        Self(ctx)
+        Self {
+            values: ctx,
+            metadata: input
+                .metadata
+                .map(|metadata| metadata.into_iter().collect())
+                .unwrap_or_default(),
+        }
     }
 }
@@ -7,10 +7,12 @@ use crate::{
         dir::{self, EuclidDirFilter},
         vir,
     },
+    types::{CompiledRegexes, EuclidValue, MetadataValue, StrValue, StrValueRefinement},
 };
 
 pub struct VirInterpreterBackend<O> {
     program: vir::ValuedProgram<O>,
+    regexes: CompiledRegexes,
 }
 
 impl<O> VirInterpreterBackend<O>
@@ -18,46 +20,66 @@ where
     O: Clone,
 {
     #[inline]
-    fn eval_comparison(comp: &vir::ValuedComparison, ctx: &types::Context) -> bool {
+    fn eval_comparison(
+        comp: &vir::ValuedComparison,
+        ctx: &types::Context,
+        regexes: &CompiledRegexes,
+    ) -> bool {
         match &comp.logic {
             vir::ValuedComparisonLogic::PositiveDisjunction => {
-                comp.values.iter().any(|v| ctx.check_presence(v))
+                comp.values.iter().any(|v| ctx.check_presence(v, regexes))
             }
             vir::ValuedComparisonLogic::NegativeConjunction => {
-                comp.values.iter().all(|v| !ctx.check_presence(v))
+                comp.values.iter().all(|v| !ctx.check_presence(v, regexes))
             }
         }
     }
 
     #[inline]
-    fn eval_condition(cond: &vir::ValuedIfCondition, ctx: &types::Context) -> bool {
-        cond.iter().all(|comp| Self::eval_comparison(comp, ctx))
+    fn eval_condition(
+        cond: &vir::ValuedIfCondition,
+        ctx: &types::Context,
+        regexes: &CompiledRegexes,
+    ) -> bool {
+        cond.iter()
+            .all(|comp| Self::eval_comparison(comp, ctx, regexes))
     }
 
-    fn eval_statement(stmt: &vir::ValuedIfStatement, ctx: &types::Context) -> bool {
-        Self::eval_condition(&stmt.condition, ctx)
+    fn eval_statement(
+        stmt: &vir::ValuedIfStatement,
+        ctx: &types::Context,
+        regexes: &CompiledRegexes,
+    ) -> bool {
+        Self::eval_condition(&stmt.condition, ctx, regexes)
             .then(|| {
                 stmt.nested.as_ref().map_or(true, |nested_stmts| {
-                    nested_stmts.iter().any(|s| Self::eval_statement(s, ctx))
+                    nested_stmts
+                        .iter()
+                        .any(|s| Self::eval_statement(s, ctx, regexes))
                 })
             })
             .unwrap_or(false)
     }
 
-    fn eval_rule(rule: &vir::ValuedRule<O>, ctx: &types::Context) -> bool {
+    fn eval_rule(
+        rule: &vir::ValuedRule<O>,
+        ctx: &types::Context,
+        regexes: &CompiledRegexes,
+    ) -> bool {
         rule.statements
             .iter()
-            .any(|stmt| Self::eval_statement(stmt, ctx))
+            .any(|stmt| Self::eval_statement(stmt, ctx, regexes))
     }
 
     fn eval_program(
         program: &vir::ValuedProgram<O>,
         ctx: &types::Context,
+        regexes: &CompiledRegexes,
     ) -> backend::BackendOutput<O> {
         program
             .rules
             .iter()
-            .find(|rule| Self::eval_rule(rule, ctx))
+            .find(|rule| Self::eval_rule(rule, ctx, regexes))
             .map_or_else(
                 || backend::BackendOutput {
                     connector_selection: program.default_selection.clone(),
@@ -69,6 +91,51 @@ where
                 },
             )
     }
+
+    fn compile_statement_regexes(
+        stmt: &vir::ValuedIfStatement,
+        regexes: &mut CompiledRegexes,
+    ) -> Result<(), types::VirInterpreterError> {
+        for value in stmt.condition.iter().flat_map(|comp| comp.values.iter()) {
+            match value {
+                EuclidValue::CardBin(StrValue {
+                    value: pattern,
+                    refinement: Some(StrValueRefinement::Matches),
+                })
+                | EuclidValue::BusinessLabel(StrValue {
+                    value: pattern,
+                    refinement: Some(StrValueRefinement::Matches),
+                })
+                | EuclidValue::Metadata(MetadataValue {
+                    value: pattern,
+                    refinement: Some(StrValueRefinement::Matches),
+                    ..
+                }) => regexes
+                    .insert(pattern)
+                    .map_err(|err| types::VirInterpreterError::InvalidRegex(err.to_string()))?,
+                _ => {}
+            }
+        }
+
+        stmt.nested
+            .iter()
+            .flatten()
+            .try_for_each(|nested_stmt| Self::compile_statement_regexes(nested_stmt, regexes))
+    }
+
+    /// Compiles the patterns of all `Matches` comparisons in the program once, so that
+    /// executions only run the compiled regular expressions
+    fn compile_regexes(
+        program: &vir::ValuedProgram<O>,
+    ) -> Result<CompiledRegexes, types::VirInterpreterError> {
+        let mut regexes = CompiledRegexes::default();
+
+        for stmt in program.rules.iter().flat_map(|rule| rule.statements.iter()) {
+            Self::compile_statement_regexes(stmt, &mut regexes)?;
+        }
+
+        Ok(regexes)
+    }
 }
 
 impl<O> EuclidBackend<O> for VirInterpreterBackend<O>
@@ -84,8 +151,11 @@ where
         let vir_program = dir::lowering::lower_program(dir_program)
             .map_err(types::VirInterpreterError::LoweringError)?;
 
+        let regexes = Self::compile_regexes(&vir_program)?;
+
         Ok(Self {
             program: vir_program,
+            regexes,
         })
     }
 
@@ -94,7 +164,7 @@ where
         input: inputs::BackendInput,
     ) -> Result<backend::BackendOutput<O>, Self::Error> {
         let ctx = types::Context::from_input(input);
-        Ok(Self::eval_program(&self.program, &ctx))
+        Ok(Self::eval_program(&self.program, &ctx, &self.regexes))
     }
 }
 #[cfg(all(test, feature = "ast_parser"))]
@@ -145,6 +215,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            temporal: inputs::TemporalInput::default(),
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -185,6 +256,7 @@ mod test {
                 mandate_type: None,
                 payment_type: Some(enums::PaymentType::SetupMandate),
             },
+            temporal: inputs::TemporalInput::default(),
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -226,6 +298,7 @@ mod test {
                 mandate_type: Some(enums::MandateType::SingleUse),
                 payment_type: None,
             },
+            temporal: inputs::TemporalInput::default(),
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -267,6 +340,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            temporal: inputs::TemporalInput::default(),
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -308,6 +382,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            temporal: inputs::TemporalInput::default(),
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -349,6 +424,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            temporal: inputs::TemporalInput::default(),
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -390,6 +466,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            temporal: inputs::TemporalInput::default(),
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -431,6 +508,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            temporal: inputs::TemporalInput::default(),
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -474,6 +552,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            temporal: inputs::TemporalInput::default(),
         };
 
         let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
@@ -515,6 +594,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            temporal: inputs::TemporalInput::default(),
         };
         let mut inp_equal = inp_greater.clone();
         inp_equal.payment.amount = 123;
@@ -565,6 +645,7 @@ mod test {
                 mandate_type: None,
                 payment_type: None,
             },
+            temporal: inputs::TemporalInput::default(),
         };
         let mut inp_equal = inp_lower.clone();
         inp_equal.payment.amount = 123;
@@ -580,4 +661,162 @@ mod test {
             "rule_1"
         );
     }
+
+    #[test]
+    fn test_temporal_keys() {
+        let program_str = r#"
+        default: ["stripe", "adyen"]
+
+        rule_1: ["adyen"]
+        {
+           day_of_week = (saturday, sunday)
+           time_of_day >= 22:00
+           time_of_day < 06:00
+        }
+        "#;
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let inp_weekday = inputs::BackendInput {
+            metadata: None,
+            payment: inputs::PaymentInput {
+                amount: 120,
+                card_bin: None,
+                currency: enums::Currency::USD,
+                authentication_type: Some(enums::AuthenticationType::NoThreeDs),
+                capture_method: Some(enums::CaptureMethod::Automatic),
+                business_country: Some(enums::Country::UnitedStatesOfAmerica),
+                billing_country: Some(enums::Country::France),
+                business_label: None,
+                setup_future_usage: None,
+            },
+            payment_method: inputs::PaymentMethodInput {
+                payment_method: Some(enums::PaymentMethod::PayLater),
+                payment_method_type: Some(enums::PaymentMethodType::Affirm),
+                card_network: None,
+            },
+            mandate: inputs::MandateData {
+                mandate_acceptance_type: None,
+                mandate_type: None,
+                payment_type: None,
+            },
+            // Wednesday, 2023-11-15 12:00:00 UTC
+            temporal: inputs::TemporalInput::from_unix_timestamp(1_700_049_600),
+        };
+        let mut inp_weekend = inp_weekday.clone();
+        // Saturday, 2023-11-18 12:00:00 UTC
+        inp_weekend.temporal = inputs::TemporalInput::from_unix_timestamp(1_700_308_800);
+        let mut inp_night = inp_weekday.clone();
+        // Wednesday, 2023-11-15 23:30:00 UTC
+        inp_night.temporal = inputs::TemporalInput::from_unix_timestamp(1_700_091_000);
+
+        assert_eq!(
+            inp_weekend.temporal.day_of_week,
+            Some(enums::DayOfWeek::Saturday)
+        );
+        assert_eq!(inp_night.temporal.time_of_day, Some(23 * 60 + 30));
+
+        let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
+        let result_weekday = backend.execute(inp_weekday).expect("Execution");
+        let result_weekend = backend.execute(inp_weekend).expect("Execution");
+        let result_night = backend.execute(inp_night).expect("Execution");
+        assert!(result_weekday.rule_name.is_none());
+        assert_eq!(
+            result_weekend.rule_name.expect("Rule Name").as_str(),
+            "rule_1"
+        );
+        assert_eq!(
+            result_night.rule_name.expect("Rule Name").as_str(),
+            "rule_1"
+        );
+    }
+
+    #[test]
+    fn test_str_pattern_operators() {
+        let program_str = r#"
+        default: ["stripe", "adyen"]
+
+        visa_test_cards: ["adyen"]
+        {
+           "channel" = ("pos", "kiosk") & card_bin ^= "4111"
+        }
+
+        corporate: ["stripe"]
+        {
+           "email" $= "@corp.com"
+           business_label =~ "^corp_[a-z]+$"
+        }
+        "#;
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let inp = inputs::BackendInput {
+            metadata: Some(FxHashMap::from_iter([(
+                "channel".to_string(),
+                "kiosk".to_string(),
+            )])),
+            payment: inputs::PaymentInput {
+                amount: 120,
+                card_bin: Some("411111".to_string()),
+                currency: enums::Currency::USD,
+                authentication_type: Some(enums::AuthenticationType::NoThreeDs),
+                capture_method: Some(enums::CaptureMethod::Automatic),
+                business_country: Some(enums::Country::UnitedStatesOfAmerica),
+                billing_country: Some(enums::Country::France),
+                business_label: None,
+                setup_future_usage: None,
+            },
+            payment_method: inputs::PaymentMethodInput {
+                payment_method: Some(enums::PaymentMethod::Card),
+                payment_method_type: Some(enums::PaymentMethodType::Credit),
+                card_network: None,
+            },
+            mandate: inputs::MandateData {
+                mandate_acceptance_type: None,
+                mandate_type: None,
+                payment_type: None,
+            },
+            temporal: inputs::TemporalInput::default(),
+        };
+        let mut inp_web = inp.clone();
+        inp_web.metadata = Some(FxHashMap::from_iter([
+            ("channel".to_string(), "web".to_string()),
+            ("email".to_string(), "jane@corp.com".to_string()),
+        ]));
+        let mut inp_label = inp.clone();
+        inp_label.metadata = None;
+        inp_label.payment.business_label = Some("corp_emea".to_string());
+        let mut inp_none = inp_label.clone();
+        inp_none.payment.business_label = Some("retail_emea".to_string());
+
+        let backend = VirInterpreterBackend::<DummyOutput>::with_program(program).expect("Program");
+        let result = backend.execute(inp).expect("Execution");
+        let result_web = backend.execute(inp_web).expect("Execution");
+        let result_label = backend.execute(inp_label).expect("Execution");
+        let result_none = backend.execute(inp_none).expect("Execution");
+        assert_eq!(
+            result.rule_name.expect("Rule Name").as_str(),
+            "visa_test_cards"
+        );
+        assert_eq!(
+            result_web.rule_name.expect("Rule Name").as_str(),
+            "corporate"
+        );
+        assert_eq!(
+            result_label.rule_name.expect("Rule Name").as_str(),
+            "corporate"
+        );
+        assert!(result_none.rule_name.is_none());
+    }
+
+    #[test]
+    fn test_invalid_regex_is_rejected() {
+        let program_str = r#"
+        default: ["stripe", "adyen"]
+
+        rule_1: ["adyen"]
+        {
+           business_label =~ "corp_("
+        }
+        "#;
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+
+        assert!(VirInterpreterBackend::<DummyOutput>::with_program(program).is_err());
+    }
 }
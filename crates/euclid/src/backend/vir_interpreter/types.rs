@@ -3,27 +3,57 @@ use rustc_hash::{FxHashMap, FxHashSet};
 use crate::{
     backend::inputs::BackendInput,
     dssa,
-    types::{self, EuclidKey, EuclidValue, MetadataValue, NumValueRefinement, StrValue},
+    types::{
+        self, CompiledRegexes, EuclidKey, EuclidValue, MetadataValue, NumValueRefinement, StrValue,
+    },
 };
 
 #[derive(Debug, Clone, serde::Serialize, thiserror::Error)]
 pub enum VirInterpreterError {
     #[error("Error when lowering the program: {0:?}")]
     LoweringError(dssa::types::AnalysisError),
+    #[error("Invalid regular expression in the program: {0}")]
+    InvalidRegex(String),
 }
 
 pub struct Context {
     atomic_values: FxHashSet<EuclidValue>,
     numeric_values: FxHashMap<EuclidKey, EuclidValue>,
+    str_values: FxHashMap<EuclidKey, String>,
+    metadata_values: FxHashMap<String, String>,
 }
 
 impl Context {
-    pub fn check_presence(&self, value: &EuclidValue) -> bool {
+    pub fn check_presence(&self, value: &EuclidValue, regexes: &CompiledRegexes) -> bool {
         let key = value.get_key();
 
         match key.key_type() {
-            types::DataType::MetadataValue => self.atomic_values.contains(value),
-            types::DataType::StrValue => self.atomic_values.contains(value),
+            types::DataType::MetadataValue => match value {
+                EuclidValue::Metadata(MetadataValue {
+                    key: metadata_key,
+                    value: pattern,
+                    refinement: Some(refinement),
+                }) => self
+                    .metadata_values
+                    .get(metadata_key)
+                    .map_or(false, |ctx_value| {
+                        refinement.check_compiled(pattern, ctx_value, regexes)
+                    }),
+                _ => self.atomic_values.contains(value),
+            },
+            types::DataType::StrValue => match value {
+                EuclidValue::CardBin(StrValue {
+                    value: pattern,
+                    refinement: Some(refinement),
+                })
+                | EuclidValue::BusinessLabel(StrValue {
+                    value: pattern,
+                    refinement: Some(refinement),
+                }) => self.str_values.get(&key).map_or(false, |ctx_value| {
+                    refinement.check_compiled(pattern, ctx_value, regexes)
+                }),
+                _ => self.atomic_values.contains(value),
+            },
             types::DataType::EnumVariant => self.atomic_values.contains(value),
             types::DataType::Number => {
                 let ctx_num_value = self
@@ -56,6 +86,7 @@ impl Context {
         let payment_method = input.payment_method;
         let meta_data = input.metadata;
         let payment_mandate = input.mandate;
+        let temporal = input.temporal;
 
         let mut enum_values: FxHashSet<EuclidValue> =
             FxHashSet::from_iter([EuclidValue::PaymentCurrency(payment.currency)]);
@@ -68,9 +99,17 @@ impl Context {
             enum_values.insert(EuclidValue::PaymentMethodType(pmt));
         }
 
+        let mut str_values: FxHashMap<EuclidKey, String> = FxHashMap::default();
+        let mut metadata_values: FxHashMap<String, String> = FxHashMap::default();
+
         if let Some(met) = meta_data {
             for (key, value) in met.into_iter() {
-                enum_values.insert(EuclidValue::Metadata(MetadataValue { key, value }));
+                metadata_values.insert(key.clone(), value.clone());
+                enum_values.insert(EuclidValue::Metadata(MetadataValue {
+                    key,
+                    value,
+                    refinement: None,
+                }));
             }
         }
 
@@ -94,11 +133,17 @@ impl Context {
             enum_values.insert(EuclidValue::BillingCountry(country));
         }
         if let Some(card_bin) = payment.card_bin {
-            enum_values.insert(EuclidValue::CardBin(StrValue { value: card_bin }));
+            str_values.insert(EuclidKey::CardBin, card_bin.clone());
+            enum_values.insert(EuclidValue::CardBin(StrValue {
+                value: card_bin,
+                refinement: None,
+            }));
         }
         if let Some(business_label) = payment.business_label {
+            str_values.insert(EuclidKey::BusinessLabel, business_label.clone());
             enum_values.insert(EuclidValue::BusinessLabel(StrValue {
                 value: business_label,
+                refinement: None,
             }));
         }
         if let Some(setup_future_usage) = payment.setup_future_usage {
@@ -113,8 +158,11 @@ impl Context {
         if let Some(mandate_acceptance_type) = payment_mandate.mandate_acceptance_type {
             enum_values.insert(EuclidValue::MandateAcceptanceType(mandate_acceptance_type));
         }
+        if let Some(day_of_week) = temporal.day_of_week {
+            enum_values.insert(EuclidValue::DayOfWeek(day_of_week));
+        }
 
-        let numeric_values: FxHashMap<EuclidKey, EuclidValue> = FxHashMap::from_iter([(
+        let mut numeric_values: FxHashMap<EuclidKey, EuclidValue> = FxHashMap::from_iter([(
             EuclidKey::PaymentAmount,
             EuclidValue::PaymentAmount(types::NumValue {
                 number: payment.amount,
@@ -122,9 +170,21 @@ impl Context {
             }),
         )]);
 
+        if let Some(time_of_day) = temporal.time_of_day {
+            numeric_values.insert(
+                EuclidKey::TimeOfDay,
+                EuclidValue::TimeOfDay(types::NumValue {
+                    number: time_of_day,
+                    refinement: None,
+                }),
+            );
+        }
+
         Self {
             atomic_values: enum_values,
             numeric_values,
+            str_values,
+            metadata_values,
         }
     }
 }
@@ -14,7 +14,7 @@ use crate::{
         dir::{self, EuclidDirFilter},
         vir,
     },
-    types::{DataType, Metadata},
+    types::{DataType, Metadata, StrValueRefinement},
 };
 
 /// Analyses conflicting assertions on the same key in a conjunctive context.
@@ -54,6 +54,87 @@ pub fn analyze_conflicting_assertions(
     Ok(())
 }
 
+/// Analyses string assertions on the same key in a conjunctive context that cannot hold at the
+/// same time.
+///
+/// For example,
+/// ```notrust
+/// card_bin ^= "4111" && ... && card_bin ^= "5500"
+/// ```notrust
+/// No card BIN starts with both prefixes, so the condition will never evaluate to `true`.
+/// Assertions whose compatibility cannot be decided statically, such as two regular expressions,
+/// are assumed to be compatible.
+pub fn analyze_conflicting_str_assertions(
+    keywise_str_assertions: &FxHashMap<dir::DirKey, FxHashSet<&dir::DirValue>>,
+    assertion_metadata: &FxHashMap<&dir::DirValue, &Metadata>,
+) -> Result<(), types::AnalysisError> {
+    for (key, value_set) in keywise_str_assertions {
+        let values = value_set.iter().copied().collect::<Vec<_>>();
+
+        for (index, first) in values.iter().enumerate() {
+            for second in values.iter().skip(index + 1) {
+                if !str_assertions_conflict(first, second) {
+                    continue;
+                }
+
+                let err_type = types::AnalysisErrorType::ConflictingAssertions {
+                    key: key.clone(),
+                    values: [first, second]
+                        .into_iter()
+                        .map(|val| types::ValueData {
+                            value: (*val).clone(),
+                            metadata: assertion_metadata
+                                .get(*val)
+                                .map(|meta| (*meta).clone())
+                                .unwrap_or_default(),
+                        })
+                        .collect(),
+                };
+
+                Err(types::AnalysisError {
+                    error_type: err_type,
+                    metadata: Default::default(),
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn get_str_assertion(value: &dir::DirValue) -> Option<(String, Option<StrValueRefinement>)> {
+    value
+        .get_str_val()
+        .map(|str_val| (str_val.value, str_val.refinement))
+        .or_else(|| {
+            value
+                .get_metadata_val()
+                .map(|metadata_val| (metadata_val.value, metadata_val.refinement))
+        })
+}
+
+fn str_assertions_conflict(first_value: &dir::DirValue, second_value: &dir::DirValue) -> bool {
+    let ((first, first_refinement), (second, second_refinement)) = match (
+        get_str_assertion(first_value),
+        get_str_assertion(second_value),
+    ) {
+        (Some(first), Some(second)) => (first, second),
+        _ => return false,
+    };
+
+    match (first_refinement, second_refinement) {
+        (None, None) => first != second,
+        (None, Some(refinement)) => !refinement.check(&second, &first),
+        (Some(refinement), None) => !refinement.check(&first, &second),
+        (Some(StrValueRefinement::StartsWith), Some(StrValueRefinement::StartsWith)) => {
+            !first.starts_with(&second) && !second.starts_with(&first)
+        }
+        (Some(StrValueRefinement::EndsWith), Some(StrValueRefinement::EndsWith)) => {
+            !first.ends_with(&second) && !second.ends_with(&first)
+        }
+        _ => false,
+    }
+}
+
 /// Analyses exhaustive negations on the same key in a conjunctive context.
 ///
 /// For example,
@@ -147,6 +228,8 @@ fn perform_condition_analyses(
         FxHashMap::default();
     let mut keywise_negations: FxHashMap<dir::DirKey, FxHashSet<&dir::DirValue>> =
         FxHashMap::default();
+    let mut keywise_str_assertions: FxHashMap<dir::DirKey, FxHashSet<&dir::DirValue>> =
+        FxHashMap::default();
 
     for ctx_val in context {
         let key = if let Some(k) = ctx_val.value.get_key() {
@@ -159,16 +242,17 @@ fn perform_condition_analyses(
             continue;
         }
 
-        if !matches!(key.kind.get_type(), DataType::EnumVariant) {
-            continue;
-        }
+        // String assertions are allowed to overlap, so they are checked separately from the
+        // assertions on enum keys where any two distinct values conflict
+        let assertions = match key.kind.get_type() {
+            DataType::EnumVariant => &mut keywise_assertions,
+            DataType::StrValue | DataType::MetadataValue => &mut keywise_str_assertions,
+            DataType::Number => continue,
+        };
 
         match ctx_val.value {
             types::CtxValueKind::Assertion(val) => {
-                keywise_assertions
-                    .entry(key.clone())
-                    .or_default()
-                    .insert(val);
+                assertions.entry(key.clone()).or_default().insert(val);
 
                 assertion_metadata.insert(val, ctx_val.metadata);
             }
@@ -190,6 +274,7 @@ fn perform_condition_analyses(
     }
 
     analyze_conflicting_assertions(&keywise_assertions, &assertion_metadata)?;
+    analyze_conflicting_str_assertions(&keywise_str_assertions, &assertion_metadata)?;
     analyze_exhaustive_negations(&keywise_negations, &keywise_negation_metadata)?;
     analyze_negated_assertions(
         &keywise_assertions,
@@ -197,6 +282,12 @@ fn perform_condition_analyses(
         &keywise_negations,
         &negation_metadata,
     )?;
+    analyze_negated_assertions(
+        &keywise_str_assertions,
+        &assertion_metadata,
+        &keywise_negations,
+        &negation_metadata,
+    )?;
 
     Ok(())
 }
@@ -286,6 +377,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_conflicting_day_of_week_detection() {
+        let program_str = r#"
+            default: ["stripe", "adyen"]
+
+            weekend_maintenance: ["adyen"]
+            {
+                day_of_week = saturday & time_of_day < 06:00 {
+                    day_of_week = sunday
+                }
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let analysis_result = analyze(program, None);
+
+        if let Err(types::AnalysisError {
+            error_type: types::AnalysisErrorType::ConflictingAssertions { key, values },
+            ..
+        }) = analysis_result
+        {
+            assert!(
+                matches!(key.kind, dir::DirKeyKind::DayOfWeek),
+                "Key should be day_of_week"
+            );
+            let values: Vec<dir::DirValue> = values.into_iter().map(|v| v.value).collect();
+            assert!(
+                values.contains(&dirval!(DayOfWeek = Saturday)),
+                "Condition should include day_of_week = saturday"
+            );
+            assert!(
+                values.contains(&dirval!(DayOfWeek = Sunday)),
+                "Condition should include day_of_week = sunday"
+            );
+        } else {
+            panic!("Did not receive conflicting assertions error");
+        }
+    }
+
+    #[test]
+    fn test_conflicting_str_assertion_detection() {
+        let program_str = r#"
+            default: ["stripe", "adyen"]
+
+            visa_bins: ["adyen"]
+            {
+                card_bin ^= "4111" {
+                    card_bin ^= "41"
+                    card_bin ^= "5500"
+                }
+            }
+        "#;
+
+        let (_, program) = ast::parser::program::<DummyOutput>(program_str).expect("Program");
+        let analysis_result = analyze(program, None);
+
+        if let Err(types::AnalysisError {
+            error_type: types::AnalysisErrorType::ConflictingAssertions { key, values },
+            ..
+        }) = analysis_result
+        {
+            assert!(
+                matches!(key.kind, dir::DirKeyKind::CardBin),
+                "Key should be card_bin"
+            );
+            let values: Vec<String> = values
+                .into_iter()
+                .filter_map(|v| v.value.get_str_val())
+                .map(|v| v.value)
+                .collect();
+            assert_eq!(values.len(), 2, "There should be 2 conflicting conditions");
+            assert!(values.contains(&"4111".to_string()));
+            assert!(values.contains(&"5500".to_string()));
+        } else {
+            panic!("Did not receive conflicting assertions error");
+        }
+    }
+
     #[test]
     fn test_exhaustive_negation_detection() {
         let program_str = r#"
@@ -20,6 +20,12 @@ pub struct MetadataValue {
     pub value: String,
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MetadataArrayValue {
+    pub key: String,
+    pub values: Vec<String>,
+}
+
 /// Represents a value in the DSL
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", content = "value", rename_all = "snake_case")]
@@ -43,6 +49,12 @@ pub enum ValueType {
     /// conditions like "500 < amount < 1000"
     /// eg: payment.amount = (> 500, < 1000)
     NumberComparisonArray(Vec<NumberComparison>),
+    /// Similar to NumberArray but for arbitrary strings
+    /// eg: card_bin = ("411111", "422222")
+    StrArray(Vec<String>),
+    /// Similar to StrArray but for the values of a single metadata key
+    /// eg: "channel" = ("pos", "kiosk")
+    MetadataVariantArray(MetadataArrayValue),
 }
 
 impl ValueType {
@@ -55,6 +67,8 @@ impl ValueType {
             Self::NumberComparisonArray(_) => DataType::Number,
             Self::NumberArray(_) => DataType::Number,
             Self::EnumVariantArray(_) => DataType::EnumVariant,
+            Self::StrArray(_) => DataType::StrValue,
+            Self::MetadataVariantArray(_) => DataType::MetadataValue,
         }
     }
 }
@@ -77,6 +91,24 @@ pub enum ComparisonType {
     LessThanEqual,
     GreaterThan,
     GreaterThanEqual,
+    /// String prefix match, written as `^=`
+    StartsWith,
+    /// String suffix match, written as `$=`
+    EndsWith,
+    /// String substring match, written as `*=`
+    Contains,
+    /// Regular expression match, written as `=~`
+    Matches,
+}
+
+impl ComparisonType {
+    /// Whether the comparison is one of the string pattern operators
+    pub fn is_str_pattern(&self) -> bool {
+        matches!(
+            self,
+            Self::StartsWith | Self::EndsWith | Self::Contains | Self::Matches
+        )
+    }
 }
 
 /// Represents a single comparison condition.
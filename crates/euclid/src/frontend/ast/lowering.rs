@@ -68,20 +68,24 @@ macro_rules! lower_enum {
 /// payment_amount = 17052001
 /// ```notrust
 /// This is for the cases in which there are numerical values involved and they are lowered
-/// accordingly on basis of the supplied key, currently payment_amount is the only key having this
-/// use case
+/// accordingly on basis of the supplied key, such as payment_amount and time_of_day. An optional
+/// validation closure can be supplied to restrict the range of accepted numbers
 
 macro_rules! lower_number {
-    ($key:ident, $value:ident, $comp:ident) => {
+    ($key:ident, $value:ident, $comp:ident $(, $validation_closure:expr)?) => {
         match $value {
-            ast::ValueType::Number(num) => Ok(vec![dir::DirValue::$key(types::NumValue {
-                number: num,
-                refinement: $comp.into(),
-            })]),
+            ast::ValueType::Number(num) => {
+                $($validation_closure(num)?;)?
+                Ok(vec![dir::DirValue::$key(types::NumValue {
+                    number: num,
+                    refinement: $comp.into(),
+                })])
+            }
 
             ast::ValueType::NumberArray(na) => na
                 .into_iter()
                 .map(|num| {
+                    $($validation_closure(num)?;)?
                     Ok(dir::DirValue::$key(types::NumValue {
                         number: num,
                         refinement: $comp.clone().into(),
@@ -92,6 +96,7 @@ macro_rules! lower_number {
             ast::ValueType::NumberComparisonArray(nca) => nca
                 .into_iter()
                 .map(|nc| {
+                    $($validation_closure(nc.number)?;)?
                     Ok(dir::DirValue::$key(types::NumValue {
                         number: nc.number,
                         refinement: nc.comparison_type.into(),
@@ -113,44 +118,101 @@ macro_rules! lower_number {
 /// For example
 /// ```notrust
 /// card_bin = "123456"
+/// card_bin ^= ("4111", "5500")
 /// ```notrust
 ///
 /// This serves for the purpose were we have the DirKey as Card_bin and value as an arbitrary string
-/// So particularly it lowers an arbitrary value to a predefined key.
+/// So particularly it lowers an arbitrary value to a predefined key. String pattern operators are
+/// carried over as a refinement of every value in the comparison.
 
 macro_rules! lower_str {
-    ($key:ident, $value:ident $(, $validation_closure:expr)?) => {
+    ($key:ident, $value:ident, $comp:ident $(, $validation_closure:expr)?) => {{
+        let refinement: Option<types::StrValueRefinement> = $comp.into();
+
         match $value {
             ast::ValueType::StrValue(st) => {
-                $($validation_closure(&st)?;)?
-                Ok(vec![dir::DirValue::$key(types::StrValue { value: st })])
+                $($validation_closure(&st, refinement)?;)?
+                validate_str_pattern(dir::DirKeyKind::$key, &st, refinement)?;
+                Ok(vec![dir::DirValue::$key(types::StrValue {
+                    value: st,
+                    refinement,
+                })])
             }
+            ast::ValueType::StrArray(sa) => sa
+                .into_iter()
+                .map(|st| {
+                    $($validation_closure(&st, refinement)?;)?
+                    validate_str_pattern(dir::DirKeyKind::$key, &st, refinement)?;
+                    Ok(dir::DirValue::$key(types::StrValue {
+                        value: st,
+                        refinement,
+                    }))
+                })
+                .collect(),
             _ => Err(AnalysisErrorType::InvalidType {
                 key: dir::DirKeyKind::$key.to_string(),
                 expected: DataType::StrValue,
                 got: $value.get_type(),
             }),
         }
-    };
+    }};
 }
 
 macro_rules! lower_metadata {
-    ($key:ident, $value:ident) => {
+    ($key:ident, $value:ident, $comp:ident) => {{
+        let refinement: Option<types::StrValueRefinement> = $comp.into();
+
         match $value {
             ast::ValueType::MetadataVariant(md) => {
+                validate_str_pattern(dir::DirKeyKind::$key, &md.value, refinement)?;
                 Ok(vec![dir::DirValue::$key(types::MetadataValue {
                     key: md.key,
                     value: md.value,
+                    refinement,
                 })])
             }
+            ast::ValueType::MetadataVariantArray(mda) => mda
+                .values
+                .into_iter()
+                .map(|value| {
+                    validate_str_pattern(dir::DirKeyKind::$key, &value, refinement)?;
+                    Ok(dir::DirValue::$key(types::MetadataValue {
+                        key: mda.key.clone(),
+                        value,
+                        refinement,
+                    }))
+                })
+                .collect(),
             _ => Err(AnalysisErrorType::InvalidType {
                 key: dir::DirKeyKind::$key.to_string(),
                 expected: DataType::MetadataValue,
                 got: $value.get_type(),
             }),
         }
-    };
+    }};
 }
+
+/// Ensures that the patterns of regular expression comparisons compile, so that invalid
+/// expressions are reported when the program is created rather than silently never matching
+fn validate_str_pattern(
+    key: dir::DirKeyKind,
+    value: &str,
+    refinement: Option<types::StrValueRefinement>,
+) -> Result<(), AnalysisErrorType> {
+    match refinement {
+        Some(types::StrValueRefinement::Matches) => {
+            regex::Regex::new(value)
+                .map(|_| ())
+                .map_err(|err| AnalysisErrorType::InvalidValue {
+                    key,
+                    value: value.to_string(),
+                    message: Some(err.to_string()),
+                })
+        }
+        _ => Ok(()),
+    }
+}
+
 /// lowers the comparison operators for different subtle value types present
 /// by throwing required errors for comparisons that can't be performed for a certain value type
 /// for example
@@ -219,6 +281,19 @@ fn lower_comparison_inner<O: EuclidDirFilter>(
             })?;
         }
 
+        (operator, value)
+            if operator.is_str_pattern()
+                && !matches!(
+                    value.get_type(),
+                    DataType::StrValue | DataType::MetadataValue
+                ) =>
+        {
+            Err(AnalysisErrorType::InvalidComparison {
+                operator: operator.clone(),
+                value_type: value.get_type(),
+            })?;
+        }
+
         _ => {}
     }
 
@@ -273,26 +348,55 @@ fn lower_comparison_inner<O: EuclidDirFilter>(
         dir::DirKeyKind::CardRedirectType => lower_enum!(CardRedirectType, value),
 
         dir::DirKeyKind::CardBin => {
-            let validation_closure = |st: &String| -> Result<(), AnalysisErrorType> {
-                if st.len() == 6 && st.chars().all(|x| x.is_ascii_digit()) {
+            let validation_closure = |st: &String,
+                                      refinement: Option<types::StrValueRefinement>|
+             -> Result<(), AnalysisErrorType> {
+                let all_digits = st.chars().all(|x| x.is_ascii_digit());
+                let (is_valid, message) = match refinement {
+                    None => (st.len() == 6 && all_digits, "Expected 6 digits"),
+                    Some(types::StrValueRefinement::Matches) => (true, ""),
+                    Some(_) => (
+                        (1..=6).contains(&st.len()) && all_digits,
+                        "Expected between 1 and 6 digits",
+                    ),
+                };
+
+                if is_valid {
                     Ok(())
                 } else {
                     Err(AnalysisErrorType::InvalidValue {
                         key: dir::DirKeyKind::CardBin,
                         value: st.clone(),
-                        message: Some("Expected 6 digits".to_string()),
+                        message: Some(message.to_string()),
                     })
                 }
             };
-            lower_str!(CardBin, value, validation_closure)
+            lower_str!(CardBin, value, comparison, validation_closure)
         }
 
-        dir::DirKeyKind::BusinessLabel => lower_str!(BusinessLabel, value),
+        dir::DirKeyKind::BusinessLabel => lower_str!(BusinessLabel, value, comparison),
 
-        dir::DirKeyKind::MetaData => lower_metadata!(MetaData, value),
+        dir::DirKeyKind::MetaData => lower_metadata!(MetaData, value, comparison),
 
         dir::DirKeyKind::PaymentAmount => lower_number!(PaymentAmount, value, comparison),
 
+        dir::DirKeyKind::DayOfWeek => lower_enum!(DayOfWeek, value),
+
+        dir::DirKeyKind::TimeOfDay => {
+            let validation_closure = |minutes: i64| -> Result<(), AnalysisErrorType> {
+                if (0..types::MINUTES_PER_DAY).contains(&minutes) {
+                    Ok(())
+                } else {
+                    Err(AnalysisErrorType::InvalidValue {
+                        key: dir::DirKeyKind::TimeOfDay,
+                        value: minutes.to_string(),
+                        message: Some("Expected a time between 00:00 and 23:59".to_string()),
+                    })
+                }
+            };
+            lower_number!(TimeOfDay, value, comparison, validation_closure)
+        }
+
         dir::DirKeyKind::Connector => Err(AnalysisErrorType::InvalidKey(
             dir::DirKeyKind::Connector.to_string(),
         )),
@@ -312,6 +416,10 @@ fn lower_comparison<O: EuclidDirFilter>(
         ast::ComparisonType::LessThanEqual => dir::DirComparisonLogic::PositiveDisjunction,
         ast::ComparisonType::GreaterThanEqual => dir::DirComparisonLogic::PositiveDisjunction,
         ast::ComparisonType::GreaterThan => dir::DirComparisonLogic::PositiveDisjunction,
+        ast::ComparisonType::StartsWith => dir::DirComparisonLogic::PositiveDisjunction,
+        ast::ComparisonType::EndsWith => dir::DirComparisonLogic::PositiveDisjunction,
+        ast::ComparisonType::Contains => dir::DirComparisonLogic::PositiveDisjunction,
+        ast::ComparisonType::Matches => dir::DirComparisonLogic::PositiveDisjunction,
     };
     let values = lower_comparison_inner::<O>(comp).map_err(|etype| AnalysisError {
         error_type: etype,
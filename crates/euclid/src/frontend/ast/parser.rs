@@ -10,6 +10,7 @@ pub enum EuclidError {
     InvalidConnector(String),
     InvalidOperator(String),
     InvalidNumber(String),
+    InvalidTime(String),
 }
 
 pub trait EuclidParsable: Sized {
@@ -70,6 +71,36 @@ pub fn num_i64(input: &str) -> ParseResult<&str, i64> {
     )(input)
 }
 
+/// Parses a 24-hour `HH:MM` time literal into the number of minutes since midnight
+pub fn time_minutes(input: &str) -> ParseResult<&str, i64> {
+    let two_digits = || complete::take_while_m_n(2, 2, |c: char| c.is_ascii_digit());
+
+    error::context(
+        "time_minutes",
+        combinator::map_res(
+            sequence::separated_pair(two_digits(), complete::tag(":"), two_digits()),
+            |(hours, minutes): (&str, &str)| {
+                let invalid_time = || EuclidError::InvalidTime(format!("{hours}:{minutes}"));
+                let hours = hours.parse::<i64>().map_err(|_| invalid_time())?;
+                let minutes = minutes.parse::<i64>().map_err(|_| invalid_time())?;
+
+                if hours < 24 && minutes < 60 {
+                    Ok(hours * 60 + minutes)
+                } else {
+                    Err(invalid_time())
+                }
+            },
+        ),
+    )(input)
+}
+
+/// Time literals are only accepted for the values of this key
+const TIME_OF_DAY_KEY: &str = "time_of_day";
+
+pub fn num_or_time(input: &str) -> ParseResult<&str, i64> {
+    branch::alt((time_minutes, num_i64))(input)
+}
+
 pub fn string_str(input: &str) -> ParseResult<&str, String> {
     error::context(
         "String",
@@ -113,10 +144,13 @@ pub fn percentage(input: &str) -> ParseResult<&str, u8> {
 }
 
 pub fn number_value(input: &str) -> ParseResult<&str, ast::ValueType> {
-    error::context(
-        "number_value",
-        combinator::map(num_i64, ast::ValueType::Number),
-    )(input)
+    number_value_of(num_i64)(input)
+}
+
+fn number_value_of<'a>(
+    num: fn(&'a str) -> ParseResult<&'a str, i64>,
+) -> impl FnMut(&'a str) -> ParseResult<&'a str, ast::ValueType> {
+    error::context("number_value", combinator::map(num, ast::ValueType::Number))
 }
 
 pub fn str_value(input: &str) -> ParseResult<&str, ast::ValueType> {
@@ -143,12 +177,18 @@ pub fn enum_variant_value(input: &str) -> ParseResult<&str, ast::ValueType> {
 }
 
 pub fn number_array_value(input: &str) -> ParseResult<&str, ast::ValueType> {
+    number_array_value_of(num_i64)(input)
+}
+
+fn number_array_value_of<'a>(
+    num: fn(&'a str) -> ParseResult<&'a str, i64>,
+) -> impl FnMut(&'a str) -> ParseResult<&'a str, ast::ValueType> {
     let many_with_comma = multi::many0(sequence::preceded(
         skip_ws(complete::tag(",")),
-        skip_ws(num_i64),
+        skip_ws(num),
     ));
 
-    let full_sequence = sequence::pair(skip_ws(num_i64), many_with_comma);
+    let full_sequence = sequence::pair(skip_ws(num), many_with_comma);
 
     error::context(
         "number_array_value",
@@ -164,7 +204,7 @@ pub fn number_array_value(input: &str) -> ParseResult<&str, ast::ValueType> {
                 ast::ValueType::NumberArray(rest)
             },
         ),
-    )(input)
+    )
 }
 
 pub fn enum_variant_array_value(input: &str) -> ParseResult<&str, ast::ValueType> {
@@ -192,7 +232,45 @@ pub fn enum_variant_array_value(input: &str) -> ParseResult<&str, ast::ValueType
     )(input)
 }
 
+pub fn string_array(input: &str) -> ParseResult<&str, Vec<String>> {
+    let many_with_comma = multi::many0(sequence::preceded(
+        skip_ws(complete::tag(",")),
+        skip_ws(string_str),
+    ));
+
+    let full_sequence = sequence::pair(skip_ws(string_str), many_with_comma);
+
+    error::context(
+        "string_array",
+        combinator::map(
+            sequence::delimited(
+                skip_ws(complete::tag("(")),
+                full_sequence,
+                skip_ws(complete::tag(")")),
+            ),
+            |tup: (String, Vec<String>)| {
+                let mut rest = tup.1;
+                rest.insert(0, tup.0);
+                rest
+            },
+        ),
+    )(input)
+}
+
+pub fn str_array_value(input: &str) -> ParseResult<&str, ast::ValueType> {
+    error::context(
+        "str_array_value",
+        combinator::map(string_array, ast::ValueType::StrArray),
+    )(input)
+}
+
 pub fn number_comparison(input: &str) -> ParseResult<&str, ast::NumberComparison> {
+    number_comparison_of(num_i64)(input)
+}
+
+fn number_comparison_of<'a>(
+    num: fn(&'a str) -> ParseResult<&'a str, i64>,
+) -> impl FnMut(&'a str) -> ParseResult<&'a str, ast::NumberComparison> {
     let operator = combinator::map_res(
         branch::alt((
             complete::tag(">="),
@@ -212,22 +290,28 @@ pub fn number_comparison(input: &str) -> ParseResult<&str, ast::NumberComparison
     error::context(
         "number_comparison",
         combinator::map(
-            sequence::pair(operator, num_i64),
+            sequence::pair(operator, num),
             |tup: (ast::ComparisonType, i64)| ast::NumberComparison {
                 comparison_type: tup.0,
                 number: tup.1,
             },
         ),
-    )(input)
+    )
 }
 
 pub fn number_comparison_array_value(input: &str) -> ParseResult<&str, ast::ValueType> {
+    number_comparison_array_value_of(num_i64)(input)
+}
+
+fn number_comparison_array_value_of<'a>(
+    num: fn(&'a str) -> ParseResult<&'a str, i64>,
+) -> impl FnMut(&'a str) -> ParseResult<&'a str, ast::ValueType> {
     let many_with_comma = multi::many0(sequence::preceded(
         skip_ws(complete::tag(",")),
-        skip_ws(number_comparison),
+        skip_ws(number_comparison_of(num)),
     ));
 
-    let full_sequence = sequence::pair(skip_ws(number_comparison), many_with_comma);
+    let full_sequence = sequence::pair(skip_ws(number_comparison_of(num)), many_with_comma);
 
     error::context(
         "number_comparison_array_value",
@@ -243,7 +327,7 @@ pub fn number_comparison_array_value(input: &str) -> ParseResult<&str, ast::Valu
                 ast::ValueType::NumberComparisonArray(rest)
             },
         ),
-    )(input)
+    )
 }
 
 pub fn value_type(input: &str) -> ParseResult<&str, ast::ValueType> {
@@ -256,6 +340,20 @@ pub fn value_type(input: &str) -> ParseResult<&str, ast::ValueType> {
             number_array_value,
             number_comparison_array_value,
             str_value,
+            str_array_value,
+        )),
+    )(input)
+}
+
+/// The values of the time of day key, where numbers can also be written as `HH:MM` time literals
+pub fn time_value_type(input: &str) -> ParseResult<&str, ast::ValueType> {
+    error::context(
+        "time_value_type",
+        branch::alt((
+            number_value_of(num_or_time),
+            number_array_value_of(num_or_time),
+            number_comparison_array_value_of(num_or_time),
+            value_type,
         )),
     )(input)
 }
@@ -268,6 +366,10 @@ pub fn comparison_type(input: &str) -> ParseResult<&str, ast::ComparisonType> {
                 complete::tag("/="),
                 complete::tag(">="),
                 complete::tag("<="),
+                complete::tag("^="),
+                complete::tag("$="),
+                complete::tag("*="),
+                complete::tag("=~"),
                 complete::tag("="),
                 complete::tag(">"),
                 complete::tag("<"),
@@ -276,6 +378,10 @@ pub fn comparison_type(input: &str) -> ParseResult<&str, ast::ComparisonType> {
                 "/=" => Ok(ast::ComparisonType::NotEqual),
                 ">=" => Ok(ast::ComparisonType::GreaterThanEqual),
                 "<=" => Ok(ast::ComparisonType::LessThanEqual),
+                "^=" => Ok(ast::ComparisonType::StartsWith),
+                "$=" => Ok(ast::ComparisonType::EndsWith),
+                "*=" => Ok(ast::ComparisonType::Contains),
+                "=~" => Ok(ast::ComparisonType::Matches),
                 "=" => Ok(ast::ComparisonType::Equal),
                 ">" => Ok(ast::ComparisonType::GreaterThan),
                 "<" => Ok(ast::ComparisonType::LessThan),
@@ -286,24 +392,32 @@ pub fn comparison_type(input: &str) -> ParseResult<&str, ast::ComparisonType> {
 }
 
 pub fn comparison(input: &str) -> ParseResult<&str, ast::Comparison> {
-    error::context(
-        "condition",
-        combinator::map(
-            sequence::tuple((
-                skip_ws(complete::take_while1(|c: char| {
-                    c.is_ascii_alphabetic() || c == '.' || c == '_'
-                })),
-                skip_ws(comparison_type),
-                skip_ws(value_type),
-            )),
-            |tup: (&str, ast::ComparisonType, ast::ValueType)| ast::Comparison {
-                lhs: tup.0.to_string(),
-                comparison: tup.1,
-                value: tup.2,
-                metadata: std::collections::HashMap::new(),
-            },
-        ),
-    )(input)
+    error::context("condition", comparison_inner)(input)
+}
+
+fn comparison_inner(input: &str) -> ParseResult<&str, ast::Comparison> {
+    let (input, (lhs, comparison)) = sequence::pair(
+        skip_ws(complete::take_while1(|c: char| {
+            c.is_ascii_alphabetic() || c == '.' || c == '_'
+        })),
+        skip_ws(comparison_type),
+    )(input)?;
+
+    let (input, value) = if lhs == TIME_OF_DAY_KEY {
+        skip_ws(time_value_type)(input)?
+    } else {
+        skip_ws(value_type)(input)?
+    };
+
+    Ok((
+        input,
+        ast::Comparison {
+            lhs: lhs.to_string(),
+            comparison,
+            value,
+            metadata: std::collections::HashMap::new(),
+        },
+    ))
 }
 
 pub fn arbitrary_comparison(input: &str) -> ParseResult<&str, ast::Comparison> {
@@ -313,15 +427,23 @@ pub fn arbitrary_comparison(input: &str) -> ParseResult<&str, ast::Comparison> {
             sequence::tuple((
                 skip_ws(string_str),
                 skip_ws(comparison_type),
-                skip_ws(string_str),
+                skip_ws(branch::alt((str_value, str_array_value))),
             )),
-            |tup: (String, ast::ComparisonType, String)| ast::Comparison {
+            |tup: (String, ast::ComparisonType, ast::ValueType)| ast::Comparison {
                 lhs: "metadata".to_string(),
                 comparison: tup.1,
-                value: ast::ValueType::MetadataVariant(ast::MetadataValue {
-                    key: tup.0,
-                    value: tup.2,
-                }),
+                value: match tup.2 {
+                    ast::ValueType::StrArray(values) => {
+                        ast::ValueType::MetadataVariantArray(ast::MetadataArrayValue {
+                            key: tup.0,
+                            values,
+                        })
+                    }
+                    ast::ValueType::StrValue(value) => {
+                        ast::ValueType::MetadataVariant(ast::MetadataValue { key: tup.0, value })
+                    }
+                    value => value,
+                },
                 metadata: std::collections::HashMap::new(),
             },
         ),
@@ -439,3 +561,27 @@ pub fn program<O: EuclidParsable + 'static>(input: &str) -> ParseResult<&str, as
         ),
     )(input)
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_time_literals_only_for_time_of_day() {
+        let (_, time) = comparison("time_of_day >= 22:30").unwrap();
+        assert!(matches!(time.value, ast::ValueType::Number(1350)));
+
+        let (_, times) = comparison("time_of_day = (06:00, 540)").unwrap();
+        assert!(matches!(
+            times.value,
+            ast::ValueType::NumberArray(numbers) if numbers == vec![360, 540]
+        ));
+
+        let (rest, amount) = comparison("amount > 10:00").unwrap();
+        assert!(matches!(amount.value, ast::ValueType::Number(10)));
+        assert_eq!(rest, ":00");
+
+        assert!(comparison("amount = (10:00, 20)").is_err());
+    }
+}
@@ -34,6 +34,7 @@ macro_rules! dirval {
     ($key:ident s= $str:literal) => {{
         $crate::frontend::dir::DirValue::$key($crate::types::StrValue {
             value: $str.to_string(),
+            refinement: None,
         })
     }};
 
@@ -41,6 +42,7 @@ macro_rules! dirval {
         $crate::frontend::dir::DirValue::MetaData($crate::types::MetadataValue {
             key: $key.to_string(),
             value: $str.to_string(),
+            refinement: None,
         })
     }};
 }
@@ -82,12 +84,14 @@ macro_rules! dirval {
     ($key:ident s= $str:literal) => {{
         $crate::frontend::dir::DirValue::$key($crate::types::StrValue {
             value: $str.to_string(),
+            refinement: None,
         })
     }};
     ($key:literal = $str:literal) => {{
         $crate::frontend::dir::DirValue::MetaData($crate::types::MetadataValue {
             key: $key.to_string(),
             value: $str.to_string(),
+            refinement: None,
         })
     }};
 }
@@ -312,6 +316,20 @@ pub enum DirKeyKind {
     )]
     #[serde(rename = "card_redirect")]
     CardRedirectType,
+    #[strum(
+        serialize = "day_of_week",
+        detailed_message = "Day of the week on which the payment is made, in UTC",
+        props(Category = "Time")
+    )]
+    #[serde(rename = "day_of_week")]
+    DayOfWeek,
+    #[strum(
+        serialize = "time_of_day",
+        detailed_message = "Time of the day at which the payment is made, as minutes since midnight UTC",
+        props(Category = "Time")
+    )]
+    #[serde(rename = "time_of_day")]
+    TimeOfDay,
 }
 
 pub trait EuclidDirFilter: Sized
@@ -359,6 +377,8 @@ impl DirKeyKind {
             Self::BusinessLabel => types::DataType::StrValue,
             Self::SetupFutureUsage => types::DataType::EnumVariant,
             Self::CardRedirectType => types::DataType::EnumVariant,
+            Self::DayOfWeek => types::DataType::EnumVariant,
+            Self::TimeOfDay => types::DataType::Number,
         }
     }
     pub fn get_value_set(&self) -> Option<Vec<DirValue>> {
@@ -485,6 +505,8 @@ impl DirKeyKind {
                     .map(DirValue::CardRedirectType)
                     .collect(),
             ),
+            Self::DayOfWeek => Some(enums::DayOfWeek::iter().map(DirValue::DayOfWeek).collect()),
+            Self::TimeOfDay => None,
         }
     }
 }
@@ -550,6 +572,10 @@ pub enum DirValue {
     SetupFutureUsage(enums::SetupFutureUsage),
     #[serde(rename = "card_redirect")]
     CardRedirectType(enums::CardRedirectType),
+    #[serde(rename = "day_of_week")]
+    DayOfWeek(enums::DayOfWeek),
+    #[serde(rename = "time_of_day")]
+    TimeOfDay(types::NumValue),
 }
 
 impl DirValue {
@@ -583,6 +609,8 @@ impl DirValue {
             Self::CardRedirectType(_) => (DirKeyKind::CardRedirectType, None),
             Self::VoucherType(_) => (DirKeyKind::VoucherType, None),
             Self::GiftCardType(_) => (DirKeyKind::GiftCardType, None),
+            Self::DayOfWeek(_) => (DirKeyKind::DayOfWeek, None),
+            Self::TimeOfDay(_) => (DirKeyKind::TimeOfDay, None),
         };
 
         DirKey::new(kind, data)
@@ -617,19 +645,21 @@ impl DirValue {
             Self::BusinessLabel(_) => None,
             Self::SetupFutureUsage(_) => None,
             Self::CardRedirectType(_) => None,
+            Self::DayOfWeek(_) => None,
+            Self::TimeOfDay(_) => None,
         }
     }
 
     pub fn get_str_val(&self) -> Option<types::StrValue> {
         match self {
-            Self::CardBin(val) => Some(val.clone()),
+            Self::CardBin(val) | Self::BusinessLabel(val) => Some(val.clone()),
             _ => None,
         }
     }
 
     pub fn get_num_value(&self) -> Option<types::NumValue> {
         match self {
-            Self::PaymentAmount(val) => Some(val.clone()),
+            Self::PaymentAmount(val) | Self::TimeOfDay(val) => Some(val.clone()),
             _ => None,
         }
     }
@@ -662,6 +692,7 @@ impl DirValue {
             (Self::UpiType(ut1), Self::UpiType(ut2)) => ut1 == ut2,
             (Self::VoucherType(vt1), Self::VoucherType(vt2)) => vt1 == vt2,
             (Self::CardRedirectType(crt1), Self::CardRedirectType(crt2)) => crt1 == crt2,
+            (Self::DayOfWeek(dow1), Self::DayOfWeek(dow2)) => dow1 == dow2,
             _ => false,
         }
     }
@@ -744,6 +775,8 @@ mod test {
             dirval!(CaptureMethod = Manual),
             dirval!(BillingCountry = UnitedStatesOfAmerica),
             dirval!(BusinessCountry = France),
+            dirval!(DayOfWeek = Saturday),
+            dirval!(TimeOfDay = 360),
         ];
 
         for val in values {
@@ -1,6 +1,7 @@
 pub mod transformers;
 
 use euclid_macros::EnumNums;
+use rustc_hash::FxHashMap;
 use serde::Serialize;
 use strum::VariantNames;
 
@@ -15,6 +16,9 @@ use crate::{
 
 pub type Metadata = std::collections::HashMap<String, serde_json::Value>;
 
+/// Upper bound (exclusive) of the values accepted for the `time_of_day` key
+pub const MINUTES_PER_DAY: i64 = 24 * 60;
+
 #[derive(
     Debug,
     Clone,
@@ -59,6 +63,10 @@ pub enum EuclidKey {
     BusinessLabel,
     #[strum(serialize = "setup_future_usage")]
     SetupFutureUsage,
+    #[strum(serialize = "day_of_week")]
+    DayOfWeek,
+    #[strum(serialize = "time_of_day")]
+    TimeOfDay,
 }
 impl EuclidDirFilter for DummyOutput {
     const ALLOWED: &'static [DirKeyKind] = &[
@@ -76,6 +84,9 @@ impl EuclidDirFilter for DummyOutput {
         DirKeyKind::MandateType,
         DirKeyKind::PaymentType,
         DirKeyKind::SetupFutureUsage,
+        DirKeyKind::BusinessLabel,
+        DirKeyKind::DayOfWeek,
+        DirKeyKind::TimeOfDay,
     ];
 }
 impl EuclidAnalysable for DummyOutput {
@@ -89,6 +100,7 @@ impl EuclidAnalysable for DummyOutput {
                     DirValue::MetaData(MetadataValue {
                         key: metadata_key.clone(),
                         value: metadata_value.clone(),
+                        refinement: None,
                     }),
                     std::collections::HashMap::from_iter([(
                         "DUMMY_OUTPUT".to_string(),
@@ -137,6 +149,8 @@ impl EuclidKey {
             Self::PaymentType => DataType::EnumVariant,
             Self::BusinessLabel => DataType::StrValue,
             Self::SetupFutureUsage => DataType::EnumVariant,
+            Self::DayOfWeek => DataType::EnumVariant,
+            Self::TimeOfDay => DataType::Number,
         }
     }
 }
@@ -162,6 +176,10 @@ impl From<ast::ComparisonType> for Option<NumValueRefinement> {
             ast::ComparisonType::LessThan => Some(NumValueRefinement::LessThan),
             ast::ComparisonType::LessThanEqual => Some(NumValueRefinement::LessThanEqual),
             ast::ComparisonType::GreaterThanEqual => Some(NumValueRefinement::GreaterThanEqual),
+            ast::ComparisonType::StartsWith
+            | ast::ComparisonType::EndsWith
+            | ast::ComparisonType::Contains
+            | ast::ComparisonType::Matches => None,
         }
     }
 }
@@ -178,15 +196,91 @@ impl From<NumValueRefinement> for ast::ComparisonType {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrValueRefinement {
+    StartsWith,
+    EndsWith,
+    Contains,
+    Matches,
+}
+
+impl From<ast::ComparisonType> for Option<StrValueRefinement> {
+    fn from(comp_type: ast::ComparisonType) -> Self {
+        match comp_type {
+            ast::ComparisonType::StartsWith => Some(StrValueRefinement::StartsWith),
+            ast::ComparisonType::EndsWith => Some(StrValueRefinement::EndsWith),
+            ast::ComparisonType::Contains => Some(StrValueRefinement::Contains),
+            ast::ComparisonType::Matches => Some(StrValueRefinement::Matches),
+            ast::ComparisonType::Equal
+            | ast::ComparisonType::NotEqual
+            | ast::ComparisonType::LessThan
+            | ast::ComparisonType::LessThanEqual
+            | ast::ComparisonType::GreaterThan
+            | ast::ComparisonType::GreaterThanEqual => None,
+        }
+    }
+}
+
+impl StrValueRefinement {
+    /// Checks whether `value` satisfies this refinement of `pattern`. `Matches` patterns are
+    /// compiled on every call and never match if they are not valid regular expressions, so
+    /// backends evaluating a program repeatedly should use [`Self::check_compiled`] instead.
+    pub fn check(&self, pattern: &str, value: &str) -> bool {
+        match self {
+            Self::StartsWith => value.starts_with(pattern),
+            Self::EndsWith => value.ends_with(pattern),
+            Self::Contains => value.contains(pattern),
+            Self::Matches => {
+                regex::Regex::new(pattern).map_or(false, |regex| regex.is_match(value))
+            }
+        }
+    }
+
+    /// Same as [`Self::check`], but looks `Matches` patterns up in the regular expressions
+    /// compiled for the program being evaluated
+    pub fn check_compiled(&self, pattern: &str, value: &str, regexes: &CompiledRegexes) -> bool {
+        match self {
+            Self::Matches => regexes.is_match(pattern, value),
+            refinement => refinement.check(pattern, value),
+        }
+    }
+}
+
+/// The regular expressions used by the `Matches` comparisons of a program, keyed by pattern.
+/// They are compiled once when a backend is created with the program.
+#[derive(Debug, Clone, Default)]
+pub struct CompiledRegexes(FxHashMap<String, regex::Regex>);
+
+impl CompiledRegexes {
+    pub fn insert(&mut self, pattern: &str) -> Result<(), regex::Error> {
+        if !self.0.contains_key(pattern) {
+            self.0
+                .insert(pattern.to_string(), regex::Regex::new(pattern)?);
+        }
+
+        Ok(())
+    }
+
+    /// Patterns that were not compiled for the program never match
+    pub fn is_match(&self, pattern: &str, value: &str) -> bool {
+        self.0
+            .get(pattern)
+            .map_or(false, |regex| regex.is_match(value))
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, serde::Serialize)]
 pub struct StrValue {
     pub value: String,
+    pub refinement: Option<StrValueRefinement>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, serde::Serialize)]
 pub struct MetadataValue {
     pub key: String,
     pub value: String,
+    pub refinement: Option<StrValueRefinement>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, serde::Serialize)]
@@ -252,12 +346,14 @@ pub enum EuclidValue {
     BillingCountry(enums::Country),
     BusinessLabel(StrValue),
     SetupFutureUsage(enums::SetupFutureUsage),
+    DayOfWeek(enums::DayOfWeek),
+    TimeOfDay(NumValue),
 }
 
 impl EuclidValue {
     pub fn get_num_value(&self) -> Option<NumValue> {
         match self {
-            Self::PaymentAmount(val) => Some(val.clone()),
+            Self::PaymentAmount(val) | Self::TimeOfDay(val) => Some(val.clone()),
             _ => None,
         }
     }
@@ -280,6 +376,8 @@ impl EuclidValue {
             Self::BillingCountry(_) => EuclidKey::BillingCountry,
             Self::BusinessLabel(_) => EuclidKey::BusinessLabel,
             Self::SetupFutureUsage(_) => EuclidKey::SetupFutureUsage,
+            Self::DayOfWeek(_) => EuclidKey::DayOfWeek,
+            Self::TimeOfDay(_) => EuclidKey::TimeOfDay,
         }
     }
 }
@@ -302,6 +400,30 @@ mod global_type_tests {
         assert!(val1.fits(&val2))
     }
 
+    #[test]
+    fn test_str_value_refinement_check() {
+        assert!(StrValueRefinement::StartsWith.check("4111", "411111"));
+        assert!(!StrValueRefinement::StartsWith.check("4111", "511111"));
+        assert!(StrValueRefinement::EndsWith.check("@corp.com", "jane@corp.com"));
+        assert!(StrValueRefinement::Contains.check("kiosk", "pos_kiosk_01"));
+        assert!(StrValueRefinement::Matches.check("^4[0-9]{5}$", "411111"));
+        assert!(!StrValueRefinement::Matches.check("^4[0-9]{5}$", "41111a"));
+        assert!(!StrValueRefinement::Matches.check("(", "("));
+    }
+
+    #[test]
+    fn test_str_value_refinement_check_compiled() {
+        let mut regexes = CompiledRegexes::default();
+        assert!(regexes.insert("^4[0-9]{5}$").is_ok());
+        assert!(regexes.insert("(").is_err());
+
+        let matches = StrValueRefinement::Matches;
+        assert!(matches.check_compiled("^4[0-9]{5}$", "411111", &regexes));
+        assert!(!matches.check_compiled("^4[0-9]{5}$", "41111a", &regexes));
+        assert!(!matches.check_compiled("^5", "511111", &regexes));
+        assert!(StrValueRefinement::StartsWith.check_compiled("5", "511111", &regexes));
+    }
+
     #[test]
     fn test_num_value_fits_less_than() {
         let val1 = NumValue {
@@ -38,6 +38,7 @@ impl IntoContext for BackendInput {
         if let Some(business_label) = self.payment.business_label {
             ctx.push(dir::DirValue::BusinessLabel(StrValue {
                 value: business_label,
+                refinement: None,
             }));
         }
         if let Some(billing_country) = self.payment.billing_country {
@@ -72,6 +73,15 @@ impl IntoContext for BackendInput {
         if let Some(payment_type) = self.mandate.payment_type {
             ctx.push(dir::DirValue::PaymentType(payment_type));
         }
+        if let Some(day_of_week) = self.temporal.day_of_week {
+            ctx.push(dir::DirValue::DayOfWeek(day_of_week));
+        }
+        if let Some(time_of_day) = self.temporal.time_of_day {
+            ctx.push(dir::DirValue::TimeOfDay(NumValue {
+                number: time_of_day,
+                refinement: None,
+            }));
+        }
 
         Ok(ctx)
     }
@@ -6,7 +6,7 @@
 //!
 //!
 
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug};
 
 use common_utils::{
     errors::CustomResult,
@@ -15,11 +15,14 @@ use common_utils::{
 };
 use error_stack::{IntoReport, ResultExt};
 use fred::{
-    interfaces::{HashesInterface, KeysInterface, StreamsInterface},
+    interfaces::{
+        ClientLike, HashesInterface, KeysInterface, LuaInterface, SetsInterface, StreamsInterface,
+    },
     prelude::RedisErrorKind,
     types::{
         Expiration, FromRedis, MultipleIDs, MultipleKeys, MultipleOrderedPairs, MultipleStrings,
-        RedisKey, RedisMap, RedisValue, Scanner, SetOptions, XCap, XReadResponse,
+        MultipleValues, RedisKey, RedisMap, RedisValue, ScanResult, Scanner, SetOptions,
+        ValueScanResult, XCap, XReadResponse,
     },
 };
 use futures::StreamExt;
@@ -50,6 +53,24 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::SetFailed)
     }
 
+    /// Sets the key without the default expiry, for values that must outlive the default TTL.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn set_key_without_expiry<V>(
+        &self,
+        key: &str,
+        value: V,
+    ) -> CustomResult<(), errors::RedisError>
+    where
+        V: TryInto<RedisValue> + Debug + Send + Sync,
+        V::Error: Into<fred::error::RedisError> + Send + Sync,
+    {
+        self.pool
+            .set(key, value, None, None, false)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::SetFailed)
+    }
+
     pub async fn set_multiple_keys_if_not_exist<V>(
         &self,
         value: V,
@@ -374,6 +395,77 @@ impl super::RedisConnectionPool {
         Ok(hsetnx)
     }
 
+    /// Returns all the keys matching the pattern, iterating over the whole keyspace. In cluster
+    /// mode the keyspace of every primary node is iterated over, keys whose hash slot is
+    /// migrated meanwhile may then be missed or returned twice.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn scan_keys(
+        &self,
+        pattern: &str,
+        count: Option<u32>,
+    ) -> CustomResult<Vec<String>, errors::RedisError> {
+        let client = self.pool.next();
+        if client.is_clustered() {
+            collect_scanned_keys(client.scan_cluster(pattern, count, None)).await
+        } else {
+            collect_scanned_keys(client.scan(pattern, count, None)).await
+        }
+    }
+
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn add_member_to_set(
+        &self,
+        key: &str,
+        member: &str,
+    ) -> CustomResult<(), errors::RedisError> {
+        self.pool
+            .sadd(key, member)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::SetFailed)
+    }
+
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn remove_members_from_set(
+        &self,
+        key: &str,
+        members: Vec<String>,
+    ) -> CustomResult<(), errors::RedisError> {
+        self.pool
+            .srem(key, members)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::DeleteFailed)
+    }
+
+    /// Returns the members of the set matching the pattern, with `SSCAN`
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn scan_set_members(
+        &self,
+        key: &str,
+        pattern: &str,
+        count: Option<u32>,
+    ) -> CustomResult<Vec<String>, errors::RedisError> {
+        let pages = self.pool.next().sscan::<&str, &str>(key, pattern, count);
+        futures::pin_mut!(pages);
+        let mut members = Vec::new();
+        while let Some(page) = pages.next().await {
+            let mut page: ValueScanResult = page
+                .into_report()
+                .change_context(errors::RedisError::ScanFailed)?;
+            members.extend(
+                page.take_results()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|member| member.into_string()),
+            );
+            page.next()
+                .into_report()
+                .change_context(errors::RedisError::ScanFailed)?;
+        }
+        Ok(members)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn hscan(
         &self,
@@ -405,6 +497,53 @@ impl super::RedisConnectionPool {
             .await)
     }
 
+    /// Scans the hash on a replica if `use_replicas_for_reads` is set, the fields may then lag
+    /// behind the primary.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn hscan_from_replica(
+        &self,
+        key: &str,
+        pattern: &str,
+        count: Option<u32>,
+    ) -> CustomResult<Vec<String>, errors::RedisError> {
+        match self.config.use_replicas_for_reads {
+            true => self.hscan_replicas(key, pattern, count).await,
+            false => self.hscan(key, pattern, count).await,
+        }
+    }
+
+    /// The scanning interface of `fred` only reaches the primary, so the cursor is driven here
+    /// with plain `HSCAN` commands sent to a replica.
+    async fn hscan_replicas(
+        &self,
+        key: &str,
+        pattern: &str,
+        count: Option<u32>,
+    ) -> CustomResult<Vec<String>, errors::RedisError> {
+        let replicas = self.pool.next().replicas();
+        let mut values = Vec::new();
+        let mut cursor = "0".to_string();
+        loop {
+            let mut args = vec![key.to_string(), cursor, "MATCH".into(), pattern.to_string()];
+            if let Some(count) = count {
+                args.extend(["COUNT".into(), count.to_string()]);
+            }
+            let (next_cursor, entries): (String, Vec<String>) = replicas
+                .custom(fred::cmd!("HSCAN"), args)
+                .await
+                .into_report()
+                .change_context(errors::RedisError::ScanFailed)?;
+
+            // The entries alternate between the fields and their values
+            values.extend(entries.into_iter().skip(1).step_by(2));
+            if next_cursor == "0" {
+                break;
+            }
+            cursor = next_cursor;
+        }
+        Ok(values)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn hscan_and_deserialize<T>(
         &self,
@@ -416,13 +555,21 @@ impl super::RedisConnectionPool {
         T: serde::de::DeserializeOwned,
     {
         let redis_results = self.hscan(key, pattern, count).await?;
-        Ok(redis_results
-            .iter()
-            .filter_map(|v| {
-                let r: T = v.parse_struct(std::any::type_name::<T>()).ok()?;
-                Some(r)
-            })
-            .collect())
+        Ok(deserialize_values(&redis_results))
+    }
+
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn hscan_and_deserialize_from_replica<T>(
+        &self,
+        key: &str,
+        pattern: &str,
+        count: Option<u32>,
+    ) -> CustomResult<Vec<T>, errors::RedisError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let redis_results = self.hscan_from_replica(key, pattern, count).await?;
+        Ok(deserialize_values(&redis_results))
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -441,6 +588,75 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::GetHashFieldFailed)
     }
 
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn delete_hash_field(
+        &self,
+        key: &str,
+        field: &str,
+    ) -> CustomResult<DelReply, errors::RedisError> {
+        self.pool
+            .hdel(key, field)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::DeleteFailed)
+    }
+
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn get_hash_fields<V>(&self, key: &str) -> CustomResult<V, errors::RedisError>
+    where
+        V: FromRedis + Unpin + Send + 'static,
+    {
+        self.pool
+            .hgetall(key)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::GetHashFieldFailed)
+    }
+
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn increment_field_in_hash(
+        &self,
+        key: &str,
+        field: &str,
+        increment: i64,
+        ttl: Option<i64>,
+    ) -> CustomResult<i64, errors::RedisError> {
+        let output: Result<i64, _> = self
+            .pool
+            .hincrby(key, field, increment)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::IncrementHashFieldFailed);
+
+        output
+            .async_and_then(|inner| async {
+                self.set_expiry(key, ttl.unwrap_or(self.config.default_hash_ttl.into()))
+                    .await?;
+                Ok(inner)
+            })
+            .await
+    }
+
+    /// Runs the Lua script atomically against the keys, with the arguments
+    #[instrument(level = "DEBUG", skip(self, lua_script))]
+    pub async fn evaluate_redis_script<V, T>(
+        &self,
+        lua_script: &'static str,
+        keys: Vec<String>,
+        values: V,
+    ) -> CustomResult<T, errors::RedisError>
+    where
+        V: TryInto<MultipleValues> + Debug + Send + Sync,
+        V::Error: Into<fred::error::RedisError> + Send + Sync,
+        T: FromRedis + Unpin + Send + 'static,
+    {
+        self.pool
+            .eval(lua_script, keys, values)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::ScriptExecutionFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn get_hash_field_and_deserialize<V>(
         &self,
@@ -553,21 +769,44 @@ impl super::RedisConnectionPool {
         K: Into<MultipleKeys> + Debug + Send + Sync,
         Ids: Into<MultipleIDs> + Debug + Send + Sync,
     {
+        let count = Some(read_count.unwrap_or(self.config.default_stream_read_count));
         self.pool
-            .xread_map(
-                Some(read_count.unwrap_or(self.config.default_stream_read_count)),
-                None,
-                streams,
-                ids,
-            )
+            .xread_map(count, None, streams, ids)
             .await
             .into_report()
-            .map_err(|err| match err.current_context().kind() {
-                RedisErrorKind::NotFound | RedisErrorKind::Parse => {
-                    err.change_context(errors::RedisError::StreamEmptyOrNotAvailable)
-                }
-                _ => err.change_context(errors::RedisError::StreamReadFailed),
-            })
+            .map_err(map_stream_read_error)
+    }
+
+    /// Reads the entries with IDs between `start` and `end`, both inclusive. The special IDs `-`
+    /// and `+` stand for the first and last entries of the stream.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn stream_read_range(
+        &self,
+        stream: &str,
+        start: &str,
+        end: &str,
+        count: Option<u64>,
+    ) -> CustomResult<Vec<(String, HashMap<String, String>)>, errors::RedisError> {
+        self.pool
+            .xrange(stream, start, end, count)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::StreamReadFailed)
+    }
+
+    /// Returns the ID of the last entry of the stream, or `None` if the stream is empty.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn stream_get_last_entry_id(
+        &self,
+        stream: &str,
+    ) -> CustomResult<Option<String>, errors::RedisError> {
+        let entries: Vec<(String, HashMap<String, String>)> = self
+            .pool
+            .xrevrange(stream, "+", "-", Some(1))
+            .await
+            .into_report()
+            .change_context(errors::RedisError::StreamReadFailed)?;
+        Ok(entries.into_iter().next().map(|(entry_id, _)| entry_id))
     }
 
     #[instrument(level = "DEBUG", skip(self))]
@@ -693,10 +932,57 @@ impl super::RedisConnectionPool {
     }
 }
 
+/// Collects the keys of the pages returned by `SCAN`, each page being requested once the previous
+/// one has been consumed
+async fn collect_scanned_keys(
+    pages: impl futures::Stream<Item = Result<ScanResult, fred::error::RedisError>>,
+) -> CustomResult<Vec<String>, errors::RedisError> {
+    futures::pin_mut!(pages);
+    let mut keys = Vec::new();
+    while let Some(page) = pages.next().await {
+        let mut page = page
+            .into_report()
+            .change_context(errors::RedisError::ScanFailed)?;
+        keys.extend(
+            page.take_results()
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|key| key.into_string()),
+        );
+        page.next()
+            .into_report()
+            .change_context(errors::RedisError::ScanFailed)?;
+    }
+    Ok(keys)
+}
+
+fn deserialize_values<T: serde::de::DeserializeOwned>(values: &[String]) -> Vec<T> {
+    values
+        .iter()
+        .filter_map(|v| {
+            let r: T = v.parse_struct(std::any::type_name::<T>()).ok()?;
+            Some(r)
+        })
+        .collect()
+}
+
+fn map_stream_read_error(
+    err: error_stack::Report<fred::error::RedisError>,
+) -> error_stack::Report<errors::RedisError> {
+    match err.current_context().kind() {
+        RedisErrorKind::NotFound | RedisErrorKind::Parse => {
+            err.change_context(errors::RedisError::StreamEmptyOrNotAvailable)
+        }
+        _ => err.change_context(errors::RedisError::StreamReadFailed),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used, clippy::unwrap_used)]
 
+    use fred::interfaces::ClientLike;
+
     use crate::{errors::RedisError, RedisConnectionPool, RedisEntryId, RedisSettings};
 
     #[tokio::test]
@@ -728,6 +1014,137 @@ mod tests {
         assert!(is_invalid_redis_entry_error);
     }
 
+    #[tokio::test]
+    async fn test_hscan_with_replicas_for_reads() {
+        let fields = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings {
+                    use_replicas_for_reads: true,
+                    ..RedisSettings::default()
+                })
+                .await
+                .expect("failed to create redis connection pool");
+                let _ = pool
+                    .set_hash_fields("replica_hash", ("field", "value".to_string()), None)
+                    .await;
+
+                // Act
+                // Without any replica the scan falls back to the primary
+                pool.hscan_from_replica("replica_hash", "*", None).await
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(fields.unwrap(), vec!["value".to_string()]);
+    }
+
+    /// Requires a replica of the local redis, started with
+    /// `redis-server --port 6380 --replicaof 127.0.0.1 6379`
+    #[tokio::test]
+    #[ignore]
+    async fn test_reads_from_replica() {
+        let (replica_role, primary_role, fields) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings {
+                    use_replicas_for_reads: true,
+                    ..RedisSettings::default()
+                })
+                .await
+                .expect("failed to create redis connection pool");
+                pool.set_hash_fields("replica_read_hash", ("field", "value".to_string()), None)
+                    .await
+                    .expect("failed to set hash field");
+                // Waits for the writes to reach the replica
+                let _: i64 = pool
+                    .pool
+                    .next()
+                    .custom(fred::cmd!("WAIT"), vec!["1", "1000"])
+                    .await
+                    .expect("failed to wait for the replica");
+
+                // Act
+                let replica_role: String = pool
+                    .pool
+                    .next()
+                    .replicas()
+                    .custom(fred::cmd!("ROLE"), Vec::<String>::new())
+                    .await
+                    .map(|role: Vec<fred::types::RedisValue>| {
+                        role.first()
+                            .and_then(|role| role.as_string())
+                            .unwrap_or_default()
+                    })
+                    .expect("failed to get the role of the replica");
+                let primary_role: String = pool
+                    .pool
+                    .next()
+                    .custom(fred::cmd!("ROLE"), Vec::<String>::new())
+                    .await
+                    .map(|role: Vec<fred::types::RedisValue>| {
+                        role.first()
+                            .and_then(|role| role.as_string())
+                            .unwrap_or_default()
+                    })
+                    .expect("failed to get the role of the primary");
+                let fields = pool
+                    .hscan_from_replica("replica_read_hash", "*", None)
+                    .await
+                    .expect("failed to scan the hash on the replica");
+
+                (replica_role, primary_role, fields)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        // Assert
+        assert_eq!(replica_role, "slave");
+        assert_eq!(primary_role, "master");
+        assert_eq!(fields, vec!["value".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_scan_set_members() {
+        let (matching, remaining) = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                // Arrange
+                let pool = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                let _ = pool.delete_key("scanned_set").await;
+                for member in ["prefix_1", "prefix_2", "other"] {
+                    pool.add_member_to_set("scanned_set", member)
+                        .await
+                        .expect("failed to add the member to the set");
+                }
+
+                // Act
+                let mut matching = pool
+                    .scan_set_members("scanned_set", "prefix_*", None)
+                    .await
+                    .expect("failed to scan the set");
+                matching.sort();
+                pool.remove_members_from_set("scanned_set", matching.clone())
+                    .await
+                    .expect("failed to remove the members from the set");
+                let remaining = pool
+                    .scan_set_members("scanned_set", "*", None)
+                    .await
+                    .expect("failed to scan the set");
+
+                (matching, remaining)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(matching, vec!["prefix_1", "prefix_2"]);
+        assert_eq!(remaining, vec!["other"]);
+    }
+
     #[tokio::test]
     async fn test_delete_existing_key_success() {
         let is_success = tokio::task::spawn_blocking(move || {
@@ -52,6 +52,12 @@ pub enum RedisError {
     SetHashFieldFailed,
     #[error("Failed to get hash field in Redis")]
     GetHashFieldFailed,
+    #[error("Failed to increment hash field in Redis")]
+    IncrementHashFieldFailed,
+    #[error("Failed to run script in Redis")]
+    ScriptExecutionFailed,
+    #[error("Failed to scan keys in Redis")]
+    ScanFailed,
     #[error("The requested value was not found in Redis")]
     NotFound,
     #[error("Invalid RedisEntryId provided")]
@@ -66,4 +72,6 @@ pub enum RedisError {
     OnMessageError,
     #[error("Got an unknown result from redis")]
     UnknownResult,
+    #[error("The KV writes of the merchant are paused")]
+    KvWritesPaused,
 }
@@ -0,0 +1,8 @@
+pub use router_env::opentelemetry::KeyValue;
+use router_env::{counter_metric, global_meter, metrics_context};
+
+metrics_context!(CONTEXT);
+global_meter!(REDIS_METER, "REDIS_INTERFACE");
+
+counter_metric!(REDIS_RECONNECTIONS, REDIS_METER);
+counter_metric!(REDIS_FAILOVERS, REDIS_METER); // No. of reconnections to a different primary
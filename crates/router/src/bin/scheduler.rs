@@ -3,7 +3,7 @@ use std::{str::FromStr, sync::Arc};
 
 use common_utils::ext_traits::{OptionExt, StringExt};
 use diesel_models::process_tracker as storage;
-use error_stack::ResultExt;
+use error_stack::{IntoReport, ResultExt};
 use router::{
     configs::settings::{CmdLineConf, Settings},
     core::errors::{self, CustomResult},
@@ -79,6 +79,10 @@ pub enum PTRunner {
     PaymentsSyncWorkflow,
     RefundWorkflowRouter,
     DeleteTokenizeDataWorkflow,
+    OutgoingWebhookRetryWorkflow,
+    IdempotencyKeyPurgeWorkflow,
+    #[cfg(feature = "kv_store")]
+    StorageSchemeMigrationWorkflow,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -103,6 +107,16 @@ impl ProcessTrackerWorkflows<routes::AppState> for WorkflowRunner {
             Some(PTRunner::DeleteTokenizeDataWorkflow) => {
                 Box::new(workflows::tokenized_data::DeleteTokenizeDataWorkflow)
             }
+            Some(PTRunner::OutgoingWebhookRetryWorkflow) => {
+                Box::new(workflows::outgoing_webhook_retry::OutgoingWebhookRetryWorkflow)
+            }
+            Some(PTRunner::IdempotencyKeyPurgeWorkflow) => {
+                Box::new(workflows::idempotency_key_purge::IdempotencyKeyPurgeWorkflow)
+            }
+            #[cfg(feature = "kv_store")]
+            Some(PTRunner::StorageSchemeMigrationWorkflow) => {
+                Box::new(workflows::storage_scheme_migration::StorageSchemeMigrationWorkflow)
+            }
             _ => Err(ProcessTrackerError::UnexpectedFlow)?,
         };
         let app_state = &state.clone();
@@ -142,6 +156,21 @@ async fn start_scheduler(
         .scheduler
         .clone()
         .ok_or(errors::ProcessTrackerError::ConfigurationError)?;
+
+    // The occurrences of a recurring job with an unknown runner could never be executed
+    scheduler_settings
+        .recurring_jobs
+        .iter()
+        .try_for_each(|(job_name, job)| {
+            PTRunner::from_str(&job.runner)
+                .map(|_| ())
+                .into_report()
+                .change_context(ProcessTrackerError::ConfigurationError)
+                .attach_printable_lazy(|| {
+                    format!("Unknown runner {} of recurring job {job_name}", job.runner)
+                })
+        })?;
+
     scheduler::start_process_tracker(
         state,
         scheduler_flow,
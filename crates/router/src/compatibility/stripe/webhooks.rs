@@ -11,7 +11,10 @@ use super::{
     payment_intents::types::StripePaymentIntentResponse, refunds::types::StripeRefundResponse,
 };
 use crate::{
-    core::{errors, webhooks::types::OutgoingWebhookType},
+    core::{
+        errors,
+        webhooks::types::{OutgoingWebhookSigningKeys, OutgoingWebhookType},
+    },
     headers,
     services::request::Maskable,
 };
@@ -28,13 +31,12 @@ pub struct StripeOutgoingWebhook {
 }
 
 impl OutgoingWebhookType for StripeOutgoingWebhook {
-    fn get_outgoing_webhooks_signature(
+    fn get_outgoing_webhooks_signature_headers(
         &self,
-        payment_response_hash_key: Option<String>,
-    ) -> errors::CustomResult<Option<String>, errors::WebhooksFlowError> {
-        let timestamp = self.created;
-
-        let payment_response_hash_key = payment_response_hash_key
+        signing_keys: Option<&OutgoingWebhookSigningKeys>,
+        timestamp: i64,
+    ) -> errors::CustomResult<Vec<(String, Maskable<String>)>, errors::WebhooksFlowError> {
+        let signing_keys = signing_keys
             .ok_or(errors::WebhooksFlowError::MerchantConfigNotFound)
             .into_report()
             .attach_printable("For stripe compatibility payment_response_hash_key is mandatory")?;
@@ -45,25 +47,26 @@ impl OutgoingWebhookType for StripeOutgoingWebhook {
                 .attach_printable("failed encoding outgoing webhook payload")?;
 
         let new_signature_payload = format!("{timestamp}.{webhook_signature_payload}");
-        let v1 = hex::encode(
-            common_utils::crypto::HmacSha256::sign_message(
-                &common_utils::crypto::HmacSha256,
-                payment_response_hash_key.as_bytes(),
-                new_signature_payload.as_bytes(),
-            )
-            .change_context(errors::WebhooksFlowError::OutgoingWebhookSigningFailed)
-            .attach_printable("Failed to sign the message")?,
-        );
+        let v1 = signing_keys
+            .keys()
+            .map(|key| {
+                common_utils::crypto::HmacSha256::sign_message(
+                    &common_utils::crypto::HmacSha256,
+                    key.as_bytes(),
+                    new_signature_payload.as_bytes(),
+                )
+                .change_context(errors::WebhooksFlowError::OutgoingWebhookSigningFailed)
+                .attach_printable("Failed to sign the message")
+                .map(|signature| format!("v1={}", hex::encode(signature)))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .join(",");
 
         let t = timestamp;
-        Ok(Some(format!("t={t},v1={v1}")))
-    }
-
-    fn add_webhook_header(header: &mut Vec<(String, Maskable<String>)>, signature: String) {
-        header.push((
+        Ok(vec![(
             headers::STRIPE_COMPATIBLE_WEBHOOK_SIGNATURE.to_string(),
-            signature.into(),
-        ))
+            format!("t={t},{v1}").into(),
+        )])
     }
 }
 
@@ -42,6 +42,7 @@ where
     let start_instant = Instant::now();
     logger::info!(tag = ?Tag::BeginRequest, payload = ?payload);
 
+    let mut idempotency_guard = None;
     let res = match metrics::request::record_request_time_metric(
         api::server_wrap_util(
             &flow,
@@ -51,6 +52,7 @@ where
             func,
             api_authentication,
             lock_action,
+            &mut idempotency_guard,
         ),
         &flow,
     )
@@ -108,6 +110,7 @@ where
         Ok(api::ApplicationResponse::FileData((file_data, content_type))) => {
             api::http_response_file_data(file_data, content_type)
         }
+        Ok(api::ApplicationResponse::Replay(response)) => response.into_http_response(),
         Ok(api::ApplicationResponse::JsonForRedirection(response)) => {
             match serde_json::to_string(&response) {
                 Ok(res) => api::http_redirect_response(res, response),
@@ -148,6 +151,10 @@ where
 
         Err(error) => api::log_and_return_error_response(error),
     };
+    let res = match idempotency_guard {
+        Some(idempotency_guard) => idempotency_guard.record(state.as_ref(), res).await,
+        None => res,
+    };
 
     let response_code = res.status().as_u16();
     let end_instant = Instant::now();
@@ -93,6 +93,27 @@ impl Default for super::settings::EphemeralConfig {
     }
 }
 
+impl Default for super::settings::CircuitBreakerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            failure_threshold: 5,
+            cooldown_secs: 60,
+            half_open_probes: 3,
+            success_threshold: 2,
+        }
+    }
+}
+
+impl Default for super::settings::IdempotencySettings {
+    fn default() -> Self {
+        Self {
+            ttl_in_seconds: 86400,
+            in_flight_expiry_in_seconds: 180,
+        }
+    }
+}
+
 #[cfg(feature = "kv_store")]
 impl Default for super::settings::DrainerSettings {
     fn default() -> Self {
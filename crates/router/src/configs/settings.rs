@@ -108,6 +108,8 @@ pub struct Settings {
     pub multiple_api_version_supported_connectors: MultipleApiVersionSupportedConnectors,
     pub applepay_merchant_configs: ApplepayMerchantConfigs,
     pub lock_settings: LockSettings,
+    pub circuit_breaker: CircuitBreakerSettings,
+    pub idempotency: IdempotencySettings,
     pub temp_locker_enable_config: TempLockerEnableConfig,
     pub payment_link: PaymentLink,
     #[cfg(feature = "olap")]
@@ -121,6 +123,8 @@ pub struct Settings {
     pub events: EventsConfig,
     #[cfg(feature = "olap")]
     pub connector_onboarding: ConnectorOnboarding,
+    #[cfg(feature = "olap")]
+    pub sso: SsoSettings,
 }
 
 #[cfg(feature = "frm")]
@@ -794,6 +798,7 @@ impl Settings {
                     .list_separator(",")
                     .with_list_parse_key("log.telemetry.route_to_trace")
                     .with_list_parse_key("redis.cluster_urls")
+                    .with_list_parse_key("redis.sentinel_urls")
                     .with_list_parse_key("events.kafka.brokers")
                     .with_list_parse_key("connectors.supported.wallets")
                     .with_list_parse_key("connector_request_reference_id_config.merchant_ids_send_payment_id_as_connector_request_id"),
@@ -848,6 +853,8 @@ impl Settings {
         #[cfg(feature = "s3")]
         self.file_upload_config.validate()?;
         self.lock_settings.validate()?;
+        self.circuit_breaker.validate()?;
+        self.idempotency.validate()?;
         self.events.validate()?;
         Ok(())
     }
@@ -877,6 +884,32 @@ pub struct Payouts {
     pub payout_eligibility: bool,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CircuitBreakerSettings {
+    /// Whether merchant connector accounts failing repeatedly are removed from routing
+    pub enabled: bool,
+    /// The number of consecutive 5xx responses or timeouts from a merchant connector account
+    /// after which it is removed from routing
+    pub failure_threshold: u32,
+    /// The time after which a tripped merchant connector account is probed again
+    pub cooldown_secs: u32,
+    /// The number of payments routed to a merchant connector account per cool-down period while
+    /// it is being probed
+    pub half_open_probes: u32,
+    /// The number of successful probes after which a merchant connector account is routed to again
+    pub success_threshold: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct IdempotencySettings {
+    /// The time for which the response of a completed request is replayed
+    pub ttl_in_seconds: u32,
+    /// The time after which a request that has not completed no longer holds its idempotency key
+    pub in_flight_expiry_in_seconds: u32,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct LockSettings {
     pub redis_lock_expiry_seconds: u32,
@@ -906,6 +939,14 @@ impl<'de> Deserialize<'de> for LockSettings {
     }
 }
 
+#[cfg(feature = "olap")]
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SsoSettings {
+    /// Allows identity providers served over HTTP or from private addresses, for local
+    /// development only
+    pub allow_insecure_providers: bool,
+}
+
 #[cfg(feature = "olap")]
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct ConnectorOnboarding {
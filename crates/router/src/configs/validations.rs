@@ -193,3 +193,58 @@ impl super::settings::LockSettings {
         })
     }
 }
+
+impl super::settings::CircuitBreakerSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        if !self.enabled {
+            return Ok(());
+        }
+
+        when(self.failure_threshold.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "circuit breaker failure_threshold must not be empty or 0".into(),
+            ))
+        })?;
+
+        when(self.cooldown_secs.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "circuit breaker cooldown_secs must not be empty or 0".into(),
+            ))
+        })?;
+
+        when(self.half_open_probes.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "circuit breaker half_open_probes must not be empty or 0".into(),
+            ))
+        })?;
+
+        when(self.success_threshold.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "circuit breaker success_threshold must not be empty or 0".into(),
+            ))
+        })
+    }
+}
+
+impl super::settings::IdempotencySettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.ttl_in_seconds.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "idempotency ttl_in_seconds must not be empty or 0".into(),
+            ))
+        })?;
+
+        when(
+            self.in_flight_expiry_in_seconds.is_default_or_empty(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "idempotency in_flight_expiry_in_seconds must not be empty or 0".into(),
+                ))
+            },
+        )
+    }
+}
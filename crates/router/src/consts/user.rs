@@ -1,2 +1,27 @@
 pub const MAX_NAME_LENGTH: usize = 70;
 pub const MAX_COMPANY_NAME_LENGTH: usize = 70;
+
+// Two factor authentication
+pub const TOTP_ISSUER_NAME: &str = "Hyperswitch";
+pub const TOTP_SECRET_LENGTH_IN_BYTES: usize = 20;
+pub const TOTP_DIGITS: u32 = 6;
+pub const TOTP_PERIOD_IN_SECS: u64 = 30;
+/// The number of periods before and after the current one whose codes are accepted, to allow
+/// for clock drift
+pub const TOTP_TOLERANCE: u64 = 1;
+pub const RECOVERY_CODES_COUNT: usize = 8;
+pub const RECOVERY_CODE_LENGTH: usize = 8;
+pub const TWO_FACTOR_AUTH_TOKEN_TIME_IN_SECS: u64 = 60 * 5; // 5 mins
+pub const MAX_TWO_FACTOR_AUTH_ATTEMPTS: i64 = 5;
+pub const REDIS_TWO_FACTOR_AUTH_ATTEMPTS_PREFIX: &str = "TWO_FACTOR_AUTH_ATTEMPTS_";
+
+// Single sign-on
+pub const OIDC_DISCOVERY_PATH: &str = ".well-known/openid-configuration";
+pub const OIDC_SCOPES: &str = "openid email profile";
+pub const OIDC_DEFAULT_GROUPS_CLAIM: &str = "groups";
+pub const OIDC_STATE_LENGTH: usize = 32;
+pub const OIDC_NONCE_LENGTH: usize = 32;
+/// Within the 43 to 128 characters allowed by [RFC 7636](https://www.rfc-editor.org/rfc/rfc7636)
+pub const OIDC_CODE_VERIFIER_LENGTH: usize = 64;
+pub const OIDC_AUTHORIZATION_TIME_IN_SECS: i64 = 60 * 10; // 10 mins
+pub const REDIS_OIDC_STATE_PREFIX: &str = "OIDC_STATE_";
@@ -9,3 +9,7 @@ pub const ROLE_ID_MERCHANT_DEVELOPER: &str = "merchant_developer";
 pub const ROLE_ID_MERCHANT_OPERATOR: &str = "merchant_operator";
 pub const ROLE_ID_MERCHANT_CUSTOMER_SUPPORT: &str = "merchant_customer_support";
 pub const INTERNAL_USER_MERCHANT_ID: &str = "juspay000";
+
+// Custom Roles
+pub const CUSTOM_ROLE_ID_PREFIX: &str = "role";
+pub const MAX_ROLE_NAME_LENGTH: usize = 64;
@@ -1,6 +1,7 @@
 pub mod admin;
 pub mod api_keys;
 pub mod api_locking;
+pub mod audit_log;
 pub mod cache;
 pub mod cards_info;
 pub mod conditional_config;
@@ -16,6 +17,7 @@ pub mod files;
 #[cfg(feature = "frm")]
 pub mod fraud_check;
 pub mod gsm;
+pub mod idempotency;
 pub mod locker_migration;
 pub mod mandate;
 pub mod metrics;
@@ -25,8 +27,11 @@ pub mod payments;
 #[cfg(feature = "payouts")]
 pub mod payouts;
 pub mod pm_auth;
+pub mod process_tracker;
 pub mod refunds;
 pub mod routing;
+#[cfg(feature = "kv_store")]
+pub mod storage_scheme_migration;
 pub mod surcharge_decision_config;
 #[cfg(feature = "olap")]
 pub mod user;
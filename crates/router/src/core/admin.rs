@@ -19,7 +19,7 @@ use crate::{
     consts,
     core::{
         errors::{self, RouterResponse, RouterResult, StorageErrorExt},
-        payments::helpers,
+        payments::{helpers, routing::circuit_breaker},
         routing::helpers as routing_helpers,
         utils as core_utils,
     },
@@ -80,6 +80,10 @@ pub async fn create_merchant_account(
             .transpose()?
             .map(Into::into);
 
+    if let Some(ref webhook_details) = req.webhook_details {
+        validate_webhook_details(webhook_details)?;
+    }
+
     let webhook_details =
         req.webhook_details
             .as_ref()
@@ -475,6 +479,10 @@ pub async fn merchant_account_update(
         }))?;
     }
 
+    if let Some(ref webhook_details) = req.webhook_details {
+        validate_webhook_details(webhook_details)?;
+    }
+
     if let Some(ref routing_algorithm) = req.routing_algorithm {
         let _: api_models::routing::RoutingAlgorithm = routing_algorithm
             .clone()
@@ -683,6 +691,32 @@ async fn validate_merchant_id<S: Into<String>>(
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)
 }
 
+fn validate_webhook_details(webhook_details: &api::WebhookDetails) -> RouterResult<()> {
+    let mut webhook_endpoint_urls = Vec::new();
+    for webhook_endpoint in webhook_details.webhook_endpoints.iter().flatten() {
+        utils::when(webhook_endpoint.enabled_events.is_empty(), || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: "`enabled_events` of a webhook endpoint must not be empty".to_string(),
+            })
+            .into_report()
+        })?;
+
+        let webhook_endpoint_url = webhook_endpoint.url.peek();
+        utils::when(
+            webhook_endpoint_urls.contains(&webhook_endpoint_url),
+            || {
+                Err(errors::ApiErrorResponse::InvalidRequestData {
+                    message: format!("Duplicate webhook endpoint url '{webhook_endpoint_url}'"),
+                })
+                .into_report()
+            },
+        )?;
+        webhook_endpoint_urls.push(webhook_endpoint_url);
+    }
+
+    Ok(())
+}
+
 fn validate_certificate_in_mca_metadata(
     connector_metadata: Secret<serde_json::Value>,
 ) -> RouterResult<()> {
@@ -1083,6 +1117,84 @@ pub async fn retrieve_payment_connector(
     Ok(service_api::ApplicationResponse::Json(mca.try_into()?))
 }
 
+async fn find_merchant_connector_account(
+    store: &dyn StorageInterface,
+    merchant_id: &str,
+    merchant_connector_id: &str,
+) -> RouterResult<domain::MerchantConnectorAccount> {
+    let key_store = store
+        .get_merchant_key_store_by_merchant_id(merchant_id, &store.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    store
+        .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+            merchant_id,
+            merchant_connector_id,
+            &key_store,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+            id: merchant_connector_id.to_string(),
+        })
+}
+
+fn get_circuit_breaker_response(
+    merchant_connector_id: String,
+    circuit: circuit_breaker::Circuit,
+) -> admin_types::MerchantConnectorCircuitBreakerResponse {
+    admin_types::MerchantConnectorCircuitBreakerResponse {
+        merchant_connector_id,
+        state: circuit.state,
+        consecutive_failures: circuit.failures,
+        probe_successes: circuit.probe_successes,
+        opened_at: circuit
+            .opened_at
+            .and_then(|opened_at| time::OffsetDateTime::from_unix_timestamp(opened_at).ok())
+            .map(date_time::convert_to_pdt),
+    }
+}
+
+pub async fn retrieve_connector_circuit_breaker(
+    state: AppState,
+    merchant_id: String,
+    merchant_connector_id: String,
+) -> RouterResponse<admin_types::MerchantConnectorCircuitBreakerResponse> {
+    let mca =
+        find_merchant_connector_account(state.store.as_ref(), &merchant_id, &merchant_connector_id)
+            .await?;
+
+    let circuit = circuit_breaker::get_circuit(&state, &mca.merchant_connector_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch connector circuit breaker")?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        get_circuit_breaker_response(mca.merchant_connector_id, circuit),
+    ))
+}
+
+/// Closes the circuit breaker of a merchant connector account, routing payments to it again
+/// without waiting for the cool-down and probes
+pub async fn reset_connector_circuit_breaker(
+    state: AppState,
+    merchant_id: String,
+    merchant_connector_id: String,
+) -> RouterResponse<admin_types::MerchantConnectorCircuitBreakerResponse> {
+    let mca =
+        find_merchant_connector_account(state.store.as_ref(), &merchant_id, &merchant_connector_id)
+            .await?;
+
+    circuit_breaker::force_close_circuit(&state, &mca.merchant_connector_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to reset connector circuit breaker")?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        get_circuit_breaker_response(mca.merchant_connector_id, Default::default()),
+    ))
+}
+
 pub async fn list_payment_connectors(
     state: AppState,
     merchant_id: String,
@@ -1439,6 +1551,10 @@ pub async fn create_business_profile(
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
+    if let Some(ref webhook_details) = request.webhook_details {
+        validate_webhook_details(webhook_details)?;
+    }
+
     if let Some(ref routing_algorithm) = request.routing_algorithm {
         let _: api_models::routing::RoutingAlgorithm = routing_algorithm
             .clone()
@@ -1539,6 +1655,10 @@ pub async fn update_business_profile(
         })?
     }
 
+    if let Some(ref webhook_details) = request.webhook_details {
+        validate_webhook_details(webhook_details)?;
+    }
+
     let webhook_details = request
         .webhook_details
         .as_ref()
@@ -1576,6 +1696,8 @@ pub async fn update_business_profile(
         payout_routing_algorithm: request.payout_routing_algorithm,
         is_recon_enabled: None,
         applepay_verified_domains: request.applepay_verified_domains,
+        previous_payment_response_hash_key: None,
+        previous_payment_response_hash_key_expires_at: None,
     };
 
     let updated_business_profile = db
@@ -1591,6 +1713,86 @@ pub async fn update_business_profile(
     ))
 }
 
+/// Default duration for which outgoing webhooks are signed with both the previous and the new key
+const DEFAULT_WEBHOOK_SIGNING_KEY_OVERLAP_WINDOW_SECS: u32 = 24 * 60 * 60;
+/// Maximum duration for which outgoing webhooks are signed with both the previous and the new key
+const MAX_WEBHOOK_SIGNING_KEY_OVERLAP_WINDOW_SECS: u32 = 7 * 24 * 60 * 60;
+
+pub async fn rotate_webhook_signing_key(
+    state: AppState,
+    profile_id: &str,
+    merchant_id: &str,
+    request: admin_types::WebhookSigningKeyRotateRequest,
+) -> RouterResponse<admin_types::WebhookSigningKeyRotateResponse> {
+    let db = state.store.as_ref();
+    let business_profile = db
+        .find_business_profile_by_profile_id(profile_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::BusinessProfileNotFound {
+            id: profile_id.to_owned(),
+        })?;
+
+    if business_profile.merchant_id != merchant_id {
+        Err(errors::ApiErrorResponse::AccessForbidden {
+            resource: profile_id.to_string(),
+        })?
+    }
+
+    let overlap_window_secs = request
+        .overlap_window_secs
+        .unwrap_or(DEFAULT_WEBHOOK_SIGNING_KEY_OVERLAP_WINDOW_SECS);
+    utils::when(
+        overlap_window_secs > MAX_WEBHOOK_SIGNING_KEY_OVERLAP_WINDOW_SECS,
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "`overlap_window_secs` must not be greater than {MAX_WEBHOOK_SIGNING_KEY_OVERLAP_WINDOW_SECS}"
+                ),
+            })
+            .into_report()
+        },
+    )?;
+
+    // The previous key of an earlier rotation is replaced, even if its overlap window is ongoing.
+    // Without a previous key, the expiry is set to the current time so that any stale previous
+    // key is no longer used.
+    let previous_key = business_profile
+        .payment_response_hash_key
+        .clone()
+        .filter(|_| overlap_window_secs > 0);
+    let previous_key_expires_at = date_time::now().saturating_add(time::Duration::seconds(
+        previous_key
+            .as_ref()
+            .map_or(0, |_| i64::from(overlap_window_secs)),
+    ));
+
+    let new_key = generate_cryptographically_secure_random_string(64);
+    let business_profile_update = storage::business_profile::BusinessProfileUpdateInternal {
+        modified_at: Some(date_time::now()),
+        payment_response_hash_key: Some(new_key.clone()),
+        previous_payment_response_hash_key: previous_key,
+        previous_payment_response_hash_key_expires_at: Some(previous_key_expires_at),
+        ..Default::default()
+    };
+
+    let updated_business_profile = db
+        .update_business_profile_by_profile_id(business_profile, business_profile_update)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::BusinessProfileNotFound {
+            id: profile_id.to_owned(),
+        })?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        admin_types::WebhookSigningKeyRotateResponse {
+            profile_id: updated_business_profile.profile_id,
+            payment_response_hash_key: new_key,
+            previous_key_expires_at: updated_business_profile
+                .previous_payment_response_hash_key_expires_at
+                .filter(|expires_at| *expires_at > date_time::now()),
+        },
+    ))
+}
+
 pub(crate) fn validate_auth_and_metadata_type(
     connector_name: api_models::enums::Connector,
     val: &types::ConnectorAuthType,
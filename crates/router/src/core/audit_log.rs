@@ -0,0 +1,587 @@
+//! Audit log of the changes made through the dashboard and the admin API.
+//!
+//! Every successful mutating request authenticated with the JWT of a user or with the admin API
+//! key is recorded along with who made it. Where the resource being changed is identified in the
+//! path of the request, it is snapshotted before the change, the response being kept as the
+//! snapshot after it. The routing of the merchant, and the role of an invited user who already
+//! exists, are snapshotted as well. The request and the snapshots are stored with their PII
+//! masked.
+
+use std::str::FromStr;
+
+use actix_web::{http::Method, HttpRequest};
+use api_models::audit_log::{AuditLogListConstraints, AuditLogListResponse};
+use diesel_models::enums::{AuditActorType, UserStatus};
+use error_stack::{IntoReport, ResultExt};
+use masking::{ExposeInterface, Secret};
+use router_env::{instrument, logger, tracing, types::FlowMetric, Flow};
+use serde::Serialize;
+
+use super::errors::{self, RouterResponse, RouterResult};
+use crate::{
+    db::StorageInterface,
+    routes::{app::AppStateInfo, lock_utils::ApiIdentifier},
+    services::{authentication::AuthenticationType, ApplicationResponse},
+    types::{
+        domain, storage,
+        transformers::{ForeignFrom, ForeignInto, ForeignTryFrom},
+    },
+    AppState,
+};
+
+/// The number of entries listed when the request does not limit them
+const DEFAULT_LIST_LIMIT: i64 = 100;
+
+/// The path parameters through which the admin API identifies the merchant
+const MERCHANT_ID_PATH_PARAMS: [&str; 2] = ["merchant_id", "account_id"];
+
+#[derive(Clone, Debug)]
+pub struct AuditLogInput {
+    actor_type: AuditActorType,
+    actor_id: Option<String>,
+    merchant_id: Option<String>,
+    flow: Flow,
+    resource_type: ApiIdentifier,
+    resource_id: Option<String>,
+    request: serde_json::Value,
+    snapshot_before: Option<serde_json::Value>,
+    /// The email of the user being invited, through which the user is identified if they exist
+    invitee_email: Option<Secret<String>>,
+}
+
+/// The routing algorithms active for the merchant and for each of its business profiles
+#[derive(Serialize)]
+struct RoutingSnapshot {
+    routing_algorithm: Option<serde_json::Value>,
+    profiles: Vec<ProfileRoutingSnapshot>,
+}
+
+#[derive(Serialize)]
+struct ProfileRoutingSnapshot {
+    profile_id: String,
+    routing_algorithm: Option<serde_json::Value>,
+}
+
+/// The role of a user in the merchant, if they have one
+#[derive(Serialize)]
+struct UserRoleSnapshot {
+    user_id: String,
+    role_id: Option<String>,
+    status: Option<UserStatus>,
+}
+
+impl AuditLogInput {
+    /// Returns `None` if the request does not change anything, or if it is not made by a user or
+    /// with the admin API key.
+    pub fn from_request(
+        flow: &impl FlowMetric,
+        request: &HttpRequest,
+        auth_type: &AuthenticationType,
+        payload: &impl Serialize,
+        masked_request: &serde_json::Value,
+    ) -> Option<Self> {
+        if matches!(
+            *request.method(),
+            Method::GET | Method::HEAD | Method::OPTIONS
+        ) {
+            return None;
+        }
+
+        let (actor_type, actor_id, merchant_id) = match auth_type {
+            AuthenticationType::MerchantJwt {
+                merchant_id,
+                user_id: Some(user_id),
+            } => (
+                AuditActorType::User,
+                Some(user_id.to_owned()),
+                Some(merchant_id.to_owned()),
+            ),
+            AuthenticationType::AdminApiKey => (AuditActorType::AdminApiKey, None, None),
+            _ => return None,
+        };
+
+        let flow = Flow::from_str(&flow.to_string()).ok()?;
+        let resource_type = ApiIdentifier::from(flow.clone());
+
+        let path_params = request.match_info();
+        let merchant_id = merchant_id
+            .or_else(|| {
+                MERCHANT_ID_PATH_PARAMS
+                    .iter()
+                    .find_map(|name| path_params.get(name))
+                    .map(ToString::to_string)
+            })
+            .or_else(|| match resource_type {
+                // The merchant account routes identify the merchant as the resource
+                ApiIdentifier::MerchantAccount => path_params.get("id").map(ToString::to_string),
+                _ => None,
+            });
+        // The resource is identified by the last of the path parameters, not counting the ones
+        // identifying the merchant
+        let resource_id = path_params
+            .iter()
+            .filter(|(name, _)| !MERCHANT_ID_PATH_PARAMS.contains(name))
+            .last()
+            .map(|(_, value)| value.to_string());
+        // The email is masked in the request, and is only kept to find the invitee
+        let invitee_email = match flow {
+            Flow::InviteUser => serde_json::to_value(payload)
+                .ok()
+                .and_then(|payload| {
+                    payload
+                        .get("email")
+                        .and_then(serde_json::Value::as_str)
+                        .map(ToString::to_string)
+                })
+                .map(Secret::new),
+            _ => None,
+        };
+
+        Some(Self {
+            actor_type,
+            actor_id,
+            merchant_id,
+            flow,
+            resource_type,
+            resource_id,
+            request: masked_request.clone(),
+            snapshot_before: None,
+            invitee_email,
+        })
+    }
+
+    /// Snapshots the resource about to be changed. The request goes through even if the snapshot
+    /// could not be taken.
+    pub async fn with_snapshot_before<A>(mut self, state: &A) -> Self
+    where
+        A: AppStateInfo,
+    {
+        let db = state.store();
+        // The invitee is identified by their user ID, their email being PII
+        if let Some(invitee_email) = &self.invitee_email {
+            match db
+                .find_user_by_email(invitee_email.clone().expose().as_str())
+                .await
+            {
+                Ok(user) => self.resource_id = Some(user.user_id),
+                Err(error) if error.current_context().is_db_not_found() => {}
+                Err(error) => logger::warn!(?error, "Failed to find the invited user"),
+            }
+        }
+
+        let snapshot = match (&self.merchant_id, &self.resource_id) {
+            // Routing changes apply to the merchant, whether or not they identify an algorithm
+            (Some(merchant_id), _) if matches!(self.resource_type, ApiIdentifier::Routing) => {
+                get_routing_snapshot(db.as_ref(), merchant_id)
+                    .await
+                    .map(Some)
+            }
+            (Some(merchant_id), Some(resource_id)) => {
+                get_resource_snapshot(db.as_ref(), &self.resource_type, merchant_id, resource_id)
+                    .await
+            }
+            _ => Ok(None),
+        };
+        match snapshot {
+            Ok(snapshot) => self.snapshot_before = snapshot,
+            Err(error) => {
+                logger::warn!(?error, "Failed to snapshot the resource before the change")
+            }
+        }
+        self
+    }
+
+    /// Records the change, given the masked response to the request. Failing to record the
+    /// change does not fail the request, which has already been processed.
+    #[instrument(skip_all)]
+    pub async fn record<A>(self, state: &A, masked_response: Option<serde_json::Value>)
+    where
+        A: AppStateInfo,
+    {
+        // The merchant created through the admin API is only known from the response
+        let merchant_id = self.merchant_id.or_else(|| {
+            masked_response
+                .as_ref()
+                .and_then(|response| response.get("merchant_id"))
+                .and_then(serde_json::Value::as_str)
+                .map(ToString::to_string)
+        });
+
+        let audit_log = storage::AuditLogNew {
+            actor_type: self.actor_type,
+            actor_id: self.actor_id,
+            merchant_id,
+            flow: self.flow.to_string(),
+            resource_type: self.resource_type.to_string(),
+            resource_id: self.resource_id,
+            request_id: state.get_request_id(),
+            request: Some(self.request),
+            snapshot_before: self.snapshot_before,
+            snapshot_after: masked_response,
+            created_at: common_utils::date_time::now(),
+        };
+        if let Err(error) = state.store().insert_audit_log(audit_log).await {
+            logger::error!(?error, "Failed to record the change in the audit log");
+        }
+    }
+}
+
+/// Returns the resource as it is returned by its API, for the resources whose API identifies
+/// them in the path
+async fn get_resource_snapshot(
+    db: &dyn StorageInterface,
+    resource_type: &ApiIdentifier,
+    merchant_id: &str,
+    resource_id: &str,
+) -> RouterResult<Option<serde_json::Value>> {
+    match resource_type {
+        ApiIdentifier::MerchantAccount => {
+            let key_store = get_merchant_key_store(db, merchant_id).await?;
+            let merchant_account = db
+                .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+                .await
+                .change_context(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+            let response = api_models::admin::MerchantAccountResponse::try_from(merchant_account)
+                .change_context(errors::ApiErrorResponse::InternalServerError)?;
+            mask_snapshot(&response).map(Some)
+        }
+        ApiIdentifier::MerchantConnector => {
+            let key_store = get_merchant_key_store(db, merchant_id).await?;
+            let merchant_connector_account = db
+                .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
+                    merchant_id,
+                    resource_id,
+                    &key_store,
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
+                    id: resource_id.to_string(),
+                })?;
+            let response =
+                api_models::admin::MerchantConnectorResponse::try_from(merchant_connector_account)?;
+            mask_snapshot(&response).map(Some)
+        }
+        ApiIdentifier::ApiKeys => db
+            .find_api_key_by_merchant_id_key_id_optional(merchant_id, resource_id)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)?
+            .map(|api_key| {
+                mask_snapshot(&api_models::api_keys::RetrieveApiKeyResponse::foreign_from(
+                    api_key,
+                ))
+            })
+            .transpose(),
+        ApiIdentifier::Business => {
+            let business_profile = db
+                .find_business_profile_by_profile_id(resource_id)
+                .await
+                .change_context(errors::ApiErrorResponse::BusinessProfileNotFound {
+                    id: resource_id.to_string(),
+                })?;
+            let response =
+                api_models::admin::BusinessProfileResponse::foreign_try_from(business_profile)
+                    .change_context(errors::ApiErrorResponse::InternalServerError)?;
+            mask_snapshot(&response).map(Some)
+        }
+        // Only the invited user is identified as a user resource
+        ApiIdentifier::User => {
+            let user_role = db
+                .list_user_roles_by_user_id(resource_id)
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)?
+                .into_iter()
+                .find(|user_role| user_role.merchant_id == merchant_id);
+            mask_snapshot(&UserRoleSnapshot {
+                user_id: resource_id.to_string(),
+                role_id: user_role
+                    .as_ref()
+                    .map(|user_role| user_role.role_id.clone()),
+                status: user_role.map(|user_role| user_role.status),
+            })
+            .map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+async fn get_routing_snapshot(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> RouterResult<serde_json::Value> {
+    let key_store = get_merchant_key_store(db, merchant_id).await?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await
+        .change_context(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let profiles = db
+        .list_business_profile_by_merchant_id(merchant_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?
+        .into_iter()
+        .map(|business_profile| ProfileRoutingSnapshot {
+            profile_id: business_profile.profile_id,
+            routing_algorithm: business_profile.routing_algorithm,
+        })
+        .collect();
+
+    mask_snapshot(&RoutingSnapshot {
+        routing_algorithm: merchant_account.routing_algorithm,
+        profiles,
+    })
+}
+
+async fn get_merchant_key_store(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> RouterResult<domain::MerchantKeyStore> {
+    db.get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .change_context(errors::ApiErrorResponse::MerchantAccountNotFound)
+}
+
+fn mask_snapshot<T: Serialize>(snapshot: &T) -> RouterResult<serde_json::Value> {
+    masking::masked_serialize(snapshot)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize the snapshot of the resource")
+}
+
+#[instrument(skip(state))]
+pub async fn list_audit_logs(
+    state: AppState,
+    merchant_id: &str,
+    constraints: AuditLogListConstraints,
+) -> RouterResponse<AuditLogListResponse> {
+    let audit_logs = state
+        .store
+        .filter_audit_logs_by_constraints(storage::AuditLogListConstraints {
+            merchant_id: Some(merchant_id.to_owned()),
+            actor_type: constraints.actor_type,
+            actor_id: constraints.actor_id,
+            resource_type: constraints.resource_type,
+            resource_id: constraints.resource_id,
+            start_time: constraints.start_time,
+            end_time: constraints.end_time,
+            limit: Some(constraints.limit.unwrap_or(DEFAULT_LIST_LIMIT)),
+            offset: constraints.offset,
+        })
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list audit log entries")?;
+
+    Ok(ApplicationResponse::Json(AuditLogListResponse {
+        count: audit_logs.len(),
+        data: audit_logs
+            .into_iter()
+            .map(ForeignInto::foreign_into)
+            .collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use actix_web::test::TestRequest;
+
+    use super::*;
+    use crate::utils::test_utils::get_mock_state;
+
+    fn get_user_auth(merchant_id: &str) -> AuthenticationType {
+        AuthenticationType::MerchantJwt {
+            merchant_id: merchant_id.to_string(),
+            user_id: Some("audit_test_user".to_string()),
+        }
+    }
+
+    fn get_invite_input() -> AuditLogInput {
+        let request = TestRequest::post()
+            .uri("/user/user/invite")
+            .to_http_request();
+        let payload = serde_json::json!({
+            "email": "invitee@example.com",
+            "name": "Invitee",
+            "role_id": "merchant_view_only",
+        });
+        AuditLogInput::from_request(
+            &Flow::InviteUser,
+            &request,
+            &get_user_auth("audit_test_merchant"),
+            &payload,
+            &serde_json::json!({}),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_request_skips_reads_and_other_actors() {
+        let request = TestRequest::get()
+            .uri("/api_keys/merchant/key")
+            .to_http_request();
+        assert!(AuditLogInput::from_request(
+            &Flow::ApiKeyRetrieve,
+            &request,
+            &get_user_auth("merchant"),
+            &(),
+            &serde_json::json!({}),
+        )
+        .is_none());
+
+        let request = TestRequest::post()
+            .uri("/api_keys/merchant/key")
+            .to_http_request();
+        assert!(AuditLogInput::from_request(
+            &Flow::ApiKeyUpdate,
+            &request,
+            &AuthenticationType::ApiKey {
+                merchant_id: "merchant".to_string(),
+                key_id: "key".to_string(),
+            },
+            &(),
+            &serde_json::json!({}),
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_from_request_identifies_the_resource() {
+        let request = TestRequest::post()
+            .uri("/api_keys/merchant/key")
+            .param("merchant_id", "merchant")
+            .param("key_id", "key")
+            .to_http_request();
+        let input = AuditLogInput::from_request(
+            &Flow::ApiKeyUpdate,
+            &request,
+            &AuthenticationType::AdminApiKey,
+            &(),
+            &serde_json::json!({ "name": "key" }),
+        )
+        .unwrap();
+        assert_eq!(input.actor_type, AuditActorType::AdminApiKey);
+        assert_eq!(input.actor_id, None);
+        assert_eq!(input.merchant_id.as_deref(), Some("merchant"));
+        assert_eq!(input.resource_id.as_deref(), Some("key"));
+        assert!(matches!(input.resource_type, ApiIdentifier::ApiKeys));
+        assert_eq!(input.request, serde_json::json!({ "name": "key" }));
+
+        let request = TestRequest::post()
+            .uri("/accounts/merchant")
+            .param("id", "merchant")
+            .to_http_request();
+        let input = AuditLogInput::from_request(
+            &Flow::MerchantsAccountUpdate,
+            &request,
+            &AuthenticationType::AdminApiKey,
+            &(),
+            &serde_json::json!({}),
+        )
+        .unwrap();
+        assert_eq!(input.merchant_id.as_deref(), Some("merchant"));
+        assert_eq!(input.resource_id.as_deref(), Some("merchant"));
+
+        let input = get_invite_input();
+        assert_eq!(input.actor_type, AuditActorType::User);
+        assert_eq!(input.actor_id.as_deref(), Some("audit_test_user"));
+        assert_eq!(input.merchant_id.as_deref(), Some("audit_test_merchant"));
+        assert_eq!(input.resource_id, None);
+        assert_eq!(
+            input.invitee_email.map(ExposeInterface::expose).as_deref(),
+            Some("invitee@example.com")
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_invite_is_snapshotted_with_the_role_of_the_invitee() {
+        let state = get_mock_state().await;
+
+        // The invitee does not exist yet
+        let input = get_invite_input().with_snapshot_before(&state).await;
+        assert_eq!(input.resource_id, None);
+        assert_eq!(input.snapshot_before, None);
+
+        state
+            .store
+            .insert_user(storage::UserNew {
+                user_id: "audit_test_invitee".to_string(),
+                email: "invitee@example.com".to_string().try_into().unwrap(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let input = get_invite_input().with_snapshot_before(&state).await;
+        assert_eq!(input.resource_id.as_deref(), Some("audit_test_invitee"));
+        assert_eq!(
+            input.snapshot_before,
+            Some(serde_json::json!({
+                "user_id": "audit_test_invitee",
+                "role_id": null,
+                "status": null,
+            }))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_list_audit_logs_of_the_merchant() {
+        let state = get_mock_state().await;
+        for (merchant_id, key_id) in [
+            ("merchant", "key_1"),
+            ("merchant", "key_2"),
+            ("other", "key"),
+        ] {
+            let request = TestRequest::post()
+                .uri("/api_keys")
+                .param("merchant_id", merchant_id)
+                .param("key_id", key_id)
+                .to_http_request();
+            AuditLogInput::from_request(
+                &Flow::ApiKeyUpdate,
+                &request,
+                &AuthenticationType::AdminApiKey,
+                &(),
+                &serde_json::json!({}),
+            )
+            .unwrap()
+            .record(&state, Some(serde_json::json!({ "key_id": key_id })))
+            .await;
+        }
+
+        let response = list_audit_logs(
+            state.clone(),
+            "merchant",
+            AuditLogListConstraints::default(),
+        )
+        .await
+        .unwrap();
+        let audit_logs = match response {
+            ApplicationResponse::Json(audit_logs) => audit_logs,
+            _ => panic!("Unexpected response"),
+        };
+        assert_eq!(audit_logs.count, 2);
+        assert!(audit_logs
+            .data
+            .iter()
+            .all(|audit_log| audit_log.merchant_id.as_deref() == Some("merchant")));
+
+        let response = list_audit_logs(
+            state,
+            "merchant",
+            AuditLogListConstraints {
+                resource_id: Some("key_2".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        match response {
+            ApplicationResponse::Json(audit_logs) => {
+                assert_eq!(audit_logs.count, 1);
+                assert_eq!(
+                    audit_logs.data[0].snapshot_after,
+                    Some(serde_json::json!({ "key_id": "key_2" }))
+                );
+            }
+            _ => panic!("Unexpected response"),
+        }
+    }
+}
@@ -1,9 +1,14 @@
+use api_models::cache::{CacheInvalidationRequest, CacheStatistics, CacheStatisticsResponse};
 use common_utils::errors::CustomResult;
 use error_stack::{report, ResultExt};
-use storage_impl::redis::cache::CacheKind;
+use storage_impl::redis::cache::{self as redis_cache, CacheKind};
 
-use super::errors;
-use crate::{db::cache::publish_into_redact_channel, routes::AppState, services};
+use super::errors::{self, StorageErrorExt};
+use crate::{
+    db::{cache::publish_into_redact_channel, StorageInterface},
+    routes::AppState,
+    services,
+};
 
 pub async fn invalidate(
     state: AppState,
@@ -23,3 +28,116 @@ pub async fn invalidate(
             .attach_printable("Failed to invalidate cache"))
     }
 }
+
+/// Invalidates the entries matching the request in the caches of every instance
+pub async fn invalidate_matching(
+    state: AppState,
+    request: CacheInvalidationRequest,
+) -> CustomResult<services::api::ApplicationResponse<serde_json::Value>, errors::ApiErrorResponse> {
+    let store = state.store.as_ref();
+    let redis_conn = store
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")?;
+    let cache_keys = match request {
+        CacheInvalidationRequest::KeyPrefix { prefix } => {
+            if prefix.is_empty() {
+                return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                    message: "prefix must not be empty".to_string(),
+                }));
+            }
+            redis_cache::delete_matching_redis_keys(
+                &redis_conn,
+                &[redis_cache::get_key_prefix_pattern(&prefix)],
+                |key| key.starts_with(&prefix),
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to delete the matching keys from redis")?;
+            vec![CacheKind::KeyPrefix(prefix.into())]
+        }
+        CacheInvalidationRequest::Merchant { merchant_id } => {
+            let cache_keys = get_merchant_cache_keys(store, &merchant_id).await?;
+            redis_cache::delete_matching_redis_keys(
+                &redis_conn,
+                &redis_cache::get_merchant_key_patterns(&merchant_id),
+                |key| redis_cache::is_merchant_key(key, &merchant_id),
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to delete the keys of the merchant from redis")?;
+            cache_keys
+        }
+    };
+
+    let result = publish_into_redact_channel(store, cache_keys)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    if result > 0 {
+        Ok(services::api::ApplicationResponse::StatusOk)
+    } else {
+        Err(report!(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to invalidate cache"))
+    }
+}
+
+async fn get_merchant_cache_keys(
+    store: &dyn StorageInterface,
+    merchant_id: &str,
+) -> CustomResult<Vec<CacheKind<'static>>, errors::ApiErrorResponse> {
+    let key_store = store
+        .get_merchant_key_store_by_merchant_id(merchant_id, &store.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = store
+        .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let api_keys = store
+        .list_api_keys_by_merchant_id(merchant_id, None, None)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list the API keys of the merchant")?;
+
+    // The entries keyed by the publishable key or the API keys of the merchant cannot be told
+    // apart from their key
+    let mut cache_keys = vec![CacheKind::Merchant(merchant_id.to_owned().into())];
+    cache_keys.extend(
+        merchant_account
+            .publishable_key
+            .map(|publishable_key| CacheKind::Accounts(publishable_key.into())),
+    );
+    cache_keys.extend(
+        api_keys
+            .into_iter()
+            .map(|api_key| CacheKind::Accounts(api_key.hashed_api_key.into_inner().into())),
+    );
+    Ok(cache_keys)
+}
+
+/// The statistics are local to the instance serving the request
+pub async fn retrieve_statistics(
+    _state: AppState,
+) -> CustomResult<
+    services::api::ApplicationResponse<CacheStatisticsResponse>,
+    errors::ApiErrorResponse,
+> {
+    let caches = redis_cache::named_caches()
+        .iter()
+        .map(|cache| {
+            let statistics = cache.statistics();
+            CacheStatistics {
+                name: statistics.name.to_string(),
+                hits: statistics.hits,
+                misses: statistics.misses,
+                entry_count: statistics.entry_count,
+                estimated_size_in_bytes: statistics.estimated_size_in_bytes,
+            }
+        })
+        .collect();
+
+    Ok(services::api::ApplicationResponse::Json(
+        CacheStatisticsResponse { caches },
+    ))
+}
@@ -45,7 +45,12 @@ pub async fn create_customer(
     // Consider a scenerio where the address is inserted and then when inserting the customer,
     // it errors out, now the address that was inserted is not deleted
     match db
-        .find_customer_by_customer_id_merchant_id(customer_id, merchant_id, &key_store)
+        .find_customer_by_customer_id_merchant_id(
+            customer_id,
+            merchant_id,
+            &key_store,
+            merchant_account.storage_scheme,
+        )
         .await
     {
         Err(err) => {
@@ -115,7 +120,7 @@ pub async fn create_customer(
     .attach_printable("Failed while encrypting Customer")?;
 
     let customer = db
-        .insert_customer(new_customer, &key_store)
+        .insert_customer(new_customer, &key_store, merchant_account.storage_scheme)
         .await
         .to_duplicate_response(errors::CustomersErrorResponse::CustomerAlreadyExists)?;
 
@@ -139,6 +144,7 @@ pub async fn retrieve_customer(
             &req.customer_id,
             &merchant_account.merchant_id,
             &key_store,
+            merchant_account.storage_scheme,
         )
         .await
         .switch()?;
@@ -158,13 +164,17 @@ pub async fn retrieve_customer(
 #[instrument(skip(state))]
 pub async fn list_customers(
     state: AppState,
-    merchant_id: String,
+    merchant_account: domain::MerchantAccount,
     key_store: domain::MerchantKeyStore,
 ) -> errors::CustomerResponse<Vec<customers::CustomerResponse>> {
     let db = state.store.as_ref();
 
     let domain_customers = db
-        .list_customers_by_merchant_id(&merchant_id, &key_store)
+        .list_customers_by_merchant_id(
+            &merchant_account.merchant_id,
+            &key_store,
+            merchant_account.storage_scheme,
+        )
         .await
         .switch()?;
 
@@ -189,12 +199,17 @@ pub async fn delete_customer(
         &req.customer_id,
         &merchant_account.merchant_id,
         &key_store,
+        merchant_account.storage_scheme,
     )
     .await
     .switch()?;
 
     let customer_mandates = db
-        .find_mandate_by_merchant_id_customer_id(&merchant_account.merchant_id, &req.customer_id)
+        .find_mandate_by_merchant_id_customer_id(
+            &merchant_account.merchant_id,
+            &req.customer_id,
+            merchant_account.storage_scheme,
+        )
         .await
         .switch()?;
 
@@ -208,6 +223,7 @@ pub async fn delete_customer(
         .find_payment_method_by_customer_id_merchant_id_list(
             &req.customer_id,
             &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
         )
         .await
     {
@@ -226,6 +242,7 @@ pub async fn delete_customer(
                 db.delete_payment_method_by_merchant_id_payment_method_id(
                     &merchant_account.merchant_id,
                     &pm.payment_method_id,
+                    merchant_account.storage_scheme,
                 )
                 .await
                 .switch()?;
@@ -304,6 +321,7 @@ pub async fn delete_customer(
         merchant_account.merchant_id,
         updated_customer,
         &key_store,
+        merchant_account.storage_scheme,
     )
     .await
     .switch()?;
@@ -332,6 +350,7 @@ pub async fn update_customer(
             &update_customer.customer_id,
             &merchant_account.merchant_id,
             &key_store,
+            merchant_account.storage_scheme,
         )
         .await
         .switch()?;
@@ -423,6 +442,7 @@ pub async fn update_customer(
             .switch()
             .attach_printable("Failed while encrypting while updating customer")?,
             &key_store,
+            merchant_account.storage_scheme,
         )
         .await
         .switch()?;
@@ -290,6 +290,15 @@ impl ConnectorError {
     }
 }
 
+impl WebhooksFlowError {
+    pub fn is_webhook_delivery_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::CallToMerchantFailed | Self::NotReceivedByMerchant
+        )
+    }
+}
+
 #[cfg(feature = "detailed_errors")]
 pub mod error_stack_parsing {
 
@@ -394,4 +403,12 @@ pub enum ConditionalConfigError {
     DslExecutionError,
     #[error("Error constructing the Input")]
     InputConstructionError,
+    #[error("Failed to fetch the authorization rate statistics of connectors")]
+    SuccessRateFetchFailed,
+    #[error("Failed to record the authorization outcome of a payment attempt")]
+    SuccessRateRecordFailed,
+    #[error("Failed to fetch the circuit breaker state of a merchant connector account")]
+    CircuitBreakerFetchFailed,
+    #[error("Failed to update the circuit breaker state of a merchant connector account")]
+    CircuitBreakerUpdateFailed,
 }
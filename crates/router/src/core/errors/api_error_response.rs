@@ -240,6 +240,12 @@ pub enum ApiErrorResponse {
     InvalidConnectorConfiguration { config: String },
     #[error(error_type = ErrorType::ValidationError, code = "HE_01", message = "Failed to convert currency to minor unit")]
     CurrencyConversionFailed,
+    #[error(error_type = ErrorType::DuplicateRequest, code = "IR_25", message = "The idempotency key was already used with a different request")]
+    IdempotencyKeyReused,
+    #[error(error_type = ErrorType::LockTimeout, code = "IR_26", message = "A request with the same idempotency key is still being processed")]
+    IdempotentRequestInProgress,
+    #[error(error_type = ErrorType::ServerNotAvailable, code = "HE_06", message = "The resource is temporarily unavailable, retry the request later")]
+    ServiceUnavailable,
 }
 
 impl PTError for ApiErrorResponse {
@@ -273,6 +273,15 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::CurrencyConversionFailed => {
                 AER::Unprocessable(ApiError::new("HE", 2, "Failed to convert currency to minor unit", None))
             }
+            Self::IdempotencyKeyReused => {
+                AER::Conflict(ApiError::new("IR", 25, "The idempotency key was already used with a different request", None))
+            }
+            Self::IdempotentRequestInProgress => {
+                AER::Conflict(ApiError::new("IR", 26, "A request with the same idempotency key is still being processed", None))
+            }
+            Self::ServiceUnavailable => {
+                AER::ServiceUnavailable(ApiError::new("HE", 6, "The resource is temporarily unavailable, retry the request later", None))
+            }
         }
     }
 }
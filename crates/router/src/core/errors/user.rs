@@ -54,6 +54,40 @@ pub enum UserErrors {
     MerchantIdParsingError,
     #[error("ChangePasswordError")]
     ChangePasswordError,
+    #[error("RoleNameParsingError")]
+    RoleNameParsingError,
+    #[error("RoleNameAlreadyExists")]
+    RoleNameAlreadyExists,
+    #[error("RoleInUse")]
+    RoleInUse,
+    #[error("InvalidTotp")]
+    InvalidTotp,
+    #[error("InvalidRecoveryCode")]
+    InvalidRecoveryCode,
+    #[error("TwoFactorAuthNotSetup")]
+    TwoFactorAuthNotSetup,
+    #[error("TwoFactorAuthAlreadySetup")]
+    TwoFactorAuthAlreadySetup,
+    #[error("TooManyTwoFactorAuthAttempts")]
+    TooManyTwoFactorAuthAttempts,
+    #[error("TwoFactorAuthTokenInvalid")]
+    TwoFactorAuthTokenInvalid,
+    #[error("SsoConfigParsingError: {0}")]
+    SsoConfigParsingError(String),
+    #[error("SsoNotConfigured")]
+    SsoNotConfigured,
+    #[error("SsoStateInvalid")]
+    SsoStateInvalid,
+    #[error("SsoProviderError")]
+    SsoProviderError,
+    #[error("SsoIdTokenInvalid")]
+    SsoIdTokenInvalid,
+    #[error("SsoNoRoleMapped")]
+    SsoNoRoleMapped,
+    #[error("SsoIdentityNotLinked")]
+    SsoIdentityNotLinked,
+    #[error("SsoIdentityAlreadyLinked")]
+    SsoIdentityAlreadyLinked,
 }
 
 impl common_utils::errors::ErrorSwitch<api_models::errors::types::ApiErrorResponse> for UserErrors {
@@ -157,6 +191,94 @@ impl common_utils::errors::ErrorSwitch<api_models::errors::types::ApiErrorRespon
                 "Old and new password cannot be same",
                 None,
             )),
+            Self::RoleNameParsingError => {
+                AER::BadRequest(ApiError::new(sub_code, 30, "Invalid Role Name", None))
+            }
+            Self::RoleNameAlreadyExists => AER::BadRequest(ApiError::new(
+                sub_code,
+                31,
+                "A role already exists with this name",
+                None,
+            )),
+            Self::RoleInUse => AER::BadRequest(ApiError::new(
+                sub_code,
+                32,
+                "The role is assigned to users",
+                None,
+            )),
+            Self::InvalidTotp => AER::BadRequest(ApiError::new(sub_code, 33, "Invalid TOTP", None)),
+            Self::InvalidRecoveryCode => {
+                AER::BadRequest(ApiError::new(sub_code, 34, "Invalid Recovery Code", None))
+            }
+            Self::TwoFactorAuthNotSetup => AER::BadRequest(ApiError::new(
+                sub_code,
+                35,
+                "Two factor authentication is not setup",
+                None,
+            )),
+            Self::TwoFactorAuthAlreadySetup => AER::BadRequest(ApiError::new(
+                sub_code,
+                36,
+                "Two factor authentication is already setup",
+                None,
+            )),
+            Self::TooManyTwoFactorAuthAttempts => AER::Unauthorized(ApiError::new(
+                sub_code,
+                37,
+                "Too many attempts, sign in again",
+                None,
+            )),
+            Self::TwoFactorAuthTokenInvalid => AER::Unauthorized(ApiError::new(
+                sub_code,
+                38,
+                "Invalid or expired token, sign in again",
+                None,
+            )),
+            Self::SsoConfigParsingError(error_message) => {
+                AER::BadRequest(ApiError::new(sub_code, 39, error_message, None))
+            }
+            Self::SsoNotConfigured => AER::BadRequest(ApiError::new(
+                sub_code,
+                40,
+                "Single sign-on is not configured for the organization",
+                None,
+            )),
+            Self::SsoStateInvalid => AER::Unauthorized(ApiError::new(
+                sub_code,
+                41,
+                "Invalid or expired sign in attempt, sign in again",
+                None,
+            )),
+            Self::SsoProviderError => AER::BadRequest(ApiError::new(
+                sub_code,
+                42,
+                "Failed to sign in with the identity provider",
+                None,
+            )),
+            Self::SsoIdTokenInvalid => AER::Unauthorized(ApiError::new(
+                sub_code,
+                43,
+                "Invalid ID token from the identity provider",
+                None,
+            )),
+            Self::SsoNoRoleMapped => AER::Unauthorized(ApiError::new(
+                sub_code,
+                44,
+                "User is not a member of any group allowed to sign in",
+                None,
+            )),
+            Self::SsoIdentityNotLinked => AER::Unauthorized(ApiError::new(
+                sub_code,
+                45,
+                "Sign in and link the identity to your account before using single sign-on",
+                None,
+            )),
+            Self::SsoIdentityAlreadyLinked => AER::BadRequest(ApiError::new(
+                sub_code,
+                46,
+                "Identity is already linked to another account",
+                None,
+            )),
         }
     }
 }
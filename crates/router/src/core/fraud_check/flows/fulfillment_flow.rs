@@ -106,6 +106,7 @@ pub async fn construct_fulfillment_router_data<'a>(
         external_latency: None,
         apple_pay_flow: None,
         frm_metadata: None,
+        merchant_connector_id: None,
     };
     Ok(router_data)
 }
@@ -97,6 +97,7 @@ impl ConstructFlowSpecificData<RecordReturn, FraudCheckRecordReturnData, FraudCh
             connector_api_version: None,
             apple_pay_flow: None,
             frm_metadata: None,
+            merchant_connector_id: None,
         };
 
         Ok(router_data)
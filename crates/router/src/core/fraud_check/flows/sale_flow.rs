@@ -93,6 +93,7 @@ impl ConstructFlowSpecificData<frm_api::Sale, FraudCheckSaleData, FraudCheckResp
             connector_api_version: None,
             apple_pay_flow: None,
             frm_metadata: None,
+            merchant_connector_id: None,
         };
 
         Ok(router_data)
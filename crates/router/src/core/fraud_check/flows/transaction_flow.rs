@@ -109,6 +109,7 @@ impl
             connector_api_version: None,
             apple_pay_flow: None,
             frm_metadata: None,
+            merchant_connector_id: None,
         };
 
         Ok(router_data)
@@ -0,0 +1,541 @@
+//! Idempotency of the create endpoints, driven by the `Idempotency-Key` header.
+//!
+//! The first request made with a key holds it while it is processed. Once it completes, the
+//! response rendered for it, errors included, is stored against the key and replayed as is to
+//! the retries of the request. The keys are held in Postgres, the completed responses being
+//! cached in Redis so that replaying them does not hit the database. Expired keys are purged by
+//! the `IDEMPOTENCY_KEY_PURGE_WORKFLOW` recurring job.
+
+use actix_web::{
+    body::MessageBody,
+    http::{header, StatusCode},
+    HttpRequest, HttpResponse,
+};
+use common_utils::{
+    crypto::{self, GenerateDigest},
+    date_time,
+    ext_traits::StringExt,
+};
+use error_stack::{report, IntoReport, ResultExt};
+use redis_interface as redis;
+use router_env::{instrument, logger, tracing, types::FlowMetric, Flow};
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use super::errors::{self, RouterResult};
+use crate::{
+    headers, routes::app::AppStateInfo, services::authentication::get_header_value_by_key,
+    types::storage,
+};
+
+pub const IDEMPOTENCY_KEY_PREFIX: &str = "IDEMPOTENCY";
+
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+/// The flows whose requests are made idempotent by the `Idempotency-Key` header
+const IDEMPOTENT_FLOWS: [Flow; 3] = [
+    Flow::PaymentsCreate,
+    Flow::RefundsCreate,
+    Flow::PayoutsCreate,
+];
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct IdempotencyRecord {
+    request_fingerprint: String,
+    /// The response of the request, set once the request has completed
+    response: Option<StoredResponse>,
+}
+
+/// The response rendered for an idempotent request, replayed as is to its retries
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StoredResponse {
+    pub status_code: u16,
+    pub content_type: String,
+    pub body: String,
+}
+
+impl StoredResponse {
+    pub fn into_http_response(self) -> HttpResponse {
+        HttpResponse::build(
+            StatusCode::from_u16(self.status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        )
+        .insert_header((header::CONTENT_TYPE, self.content_type))
+        .insert_header((headers::IDEMPOTENT_REPLAYED, "true"))
+        .body(self.body)
+    }
+}
+
+#[derive(Debug)]
+pub enum IdempotencyStatus {
+    /// The key is held by the caller, which is to process the request
+    Acquired(IdempotencyGuard),
+    /// The request has already been processed with the given response
+    Replay(StoredResponse),
+}
+
+/// Holds the key of a request being processed, until its response is stored or the key released
+#[derive(Debug)]
+pub struct IdempotencyGuard {
+    input: IdempotencyInput,
+}
+
+#[derive(Clone, Debug)]
+pub struct IdempotencyInput {
+    merchant_id: String,
+    key: String,
+    request_fingerprint: String,
+}
+
+impl IdempotencyInput {
+    /// Returns `None` if the flow is not idempotent or if the request carries no
+    /// `Idempotency-Key` header.
+    pub fn from_request<T>(
+        flow: &impl FlowMetric,
+        request: &HttpRequest,
+        merchant_id: &str,
+        payload: &T,
+    ) -> RouterResult<Option<Self>>
+    where
+        T: Serialize,
+    {
+        let flow = flow.to_string();
+        if !IDEMPOTENT_FLOWS
+            .iter()
+            .any(|idempotent_flow| idempotent_flow.to_string() == flow)
+        {
+            return Ok(None);
+        }
+
+        let key = match get_header_value_by_key(headers::IDEMPOTENCY_KEY.into(), request.headers())?
+        {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+        if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LENGTH {
+            return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "`{}` must be between 1 and {MAX_IDEMPOTENCY_KEY_LENGTH} characters long",
+                    headers::IDEMPOTENCY_KEY
+                ),
+            }));
+        }
+
+        let request_body = serde_json::to_vec(payload)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to serialize the request")?;
+        // The flow and the path are part of the fingerprint, a key cannot be reused across
+        // endpoints, nor have a response replayed in the shape of another API
+        let request_fingerprint = crypto::Sha256
+            .generate_digest(
+                &[
+                    flow.as_bytes(),
+                    b":",
+                    request.path().as_bytes(),
+                    b":",
+                    &request_body,
+                ]
+                .concat(),
+            )
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fingerprint the request")?;
+
+        Ok(Some(Self {
+            merchant_id: merchant_id.to_owned(),
+            key: key.to_owned(),
+            request_fingerprint: hex::encode(request_fingerprint),
+        }))
+    }
+
+    fn get_redis_key(&self) -> String {
+        format!(
+            "{}_{}_{}",
+            IDEMPOTENCY_KEY_PREFIX, self.merchant_id, self.key
+        )
+    }
+
+    /// Acquires the key for the request, unless the request has already been processed in which
+    /// case its response is to be replayed.
+    #[instrument(skip_all)]
+    pub async fn acquire<A>(self, state: &A) -> RouterResult<IdempotencyStatus>
+    where
+        A: AppStateInfo,
+    {
+        let existing = match self.find_in_cache(state).await {
+            Some(cached) => Some(cached),
+            None => self.insert_into_postgres(state).await?,
+        };
+
+        match existing {
+            None => Ok(IdempotencyStatus::Acquired(IdempotencyGuard {
+                input: self,
+            })),
+            Some(existing) if existing.request_fingerprint != self.request_fingerprint => {
+                Err(errors::ApiErrorResponse::IdempotencyKeyReused).into_report()
+            }
+            Some(IdempotencyRecord {
+                response: Some(response),
+                ..
+            }) => Ok(IdempotencyStatus::Replay(response)),
+            Some(_) => Err(errors::ApiErrorResponse::IdempotentRequestInProgress).into_report(),
+        }
+    }
+
+    /// Returns the completed record cached against the key, if any. Only the completed records are
+    /// cached, the keys of the requests in progress are only ever held in Postgres.
+    async fn find_in_cache<A>(&self, state: &A) -> Option<IdempotencyRecord>
+    where
+        A: AppStateInfo,
+    {
+        let result = match state.store().get_redis_conn() {
+            Ok(redis_conn) => {
+                redis_conn
+                    .get_and_deserialize_key::<IdempotencyRecord>(
+                        &self.get_redis_key(),
+                        "IdempotencyRecord",
+                    )
+                    .await
+            }
+            Err(error) => Err(error),
+        };
+
+        match result {
+            Ok(record) => Some(record),
+            Err(error) if *error.current_context() == redis::errors::RedisError::NotFound => None,
+            Err(error) => {
+                logger::warn!(?error, "Failed to read the cached idempotency key");
+                None
+            }
+        }
+    }
+
+    /// Caches the completed record against the key until it expires in Postgres
+    async fn cache<A>(&self, state: &A, record: &IdempotencyRecord, expires_at: PrimitiveDateTime)
+    where
+        A: AppStateInfo,
+    {
+        let ttl_in_seconds = (expires_at - date_time::now()).whole_seconds();
+        if ttl_in_seconds <= 0 {
+            return;
+        }
+
+        let result = match state.store().get_redis_conn() {
+            Ok(redis_conn) => {
+                redis_conn
+                    .serialize_and_set_key_with_expiry(
+                        &self.get_redis_key(),
+                        record,
+                        ttl_in_seconds,
+                    )
+                    .await
+            }
+            Err(error) => Err(error),
+        };
+        if let Err(error) = result {
+            logger::warn!(?error, "Failed to cache the idempotency key");
+        }
+    }
+
+    /// Returns the record already held against the key, if any
+    async fn insert_into_postgres<A>(&self, state: &A) -> RouterResult<Option<IdempotencyRecord>>
+    where
+        A: AppStateInfo,
+    {
+        let db = state.store();
+        let now = date_time::now();
+        let idempotency_key = storage::IdempotencyKeyNew {
+            merchant_id: self.merchant_id.clone(),
+            key: self.key.clone(),
+            request_fingerprint: self.request_fingerprint.clone(),
+            expires_at: now
+                + time::Duration::seconds(
+                    state.conf().idempotency.in_flight_expiry_in_seconds.into(),
+                ),
+        };
+
+        match db.insert_idempotency_key(idempotency_key.clone()).await {
+            Ok(_) => return Ok(None),
+            Err(error) if is_duplicate(error.current_context()) => {}
+            Err(error) => {
+                return Err(error).change_context(errors::ApiErrorResponse::InternalServerError)
+            }
+        }
+
+        let existing = db
+            .find_idempotency_key_by_merchant_id_key(&self.merchant_id, &self.key)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+        if existing.expires_at > now {
+            let record = IdempotencyRecord {
+                request_fingerprint: existing.request_fingerprint,
+                response: existing
+                    .response
+                    .map(|response| response.parse_struct("StoredResponse"))
+                    .transpose()
+                    .change_context(errors::ApiErrorResponse::InternalServerError)?,
+            };
+            if record.response.is_some() {
+                self.cache(state, &record, existing.expires_at).await;
+            }
+            return Ok(Some(record));
+        }
+
+        // The key has expired and is taken over, unless another request does so first
+        db.delete_expired_idempotency_key_by_merchant_id_key(&self.merchant_id, &self.key, now)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+        match db.insert_idempotency_key(idempotency_key).await {
+            Ok(_) => Ok(None),
+            Err(error) if is_duplicate(error.current_context()) => {
+                Err(error).change_context(errors::ApiErrorResponse::IdempotentRequestInProgress)
+            }
+            Err(error) => Err(error).change_context(errors::ApiErrorResponse::InternalServerError),
+        }
+    }
+}
+
+impl IdempotencyGuard {
+    /// Stores the response rendered for the request against the key. The response is stored
+    /// whatever its status, as the request may have had side effects before failing, unless its
+    /// body is streamed or is not text, in which case the key is released.
+    pub async fn record<A>(self, state: &A, response: HttpResponse) -> HttpResponse
+    where
+        A: AppStateInfo,
+    {
+        let (response, body) = response.into_parts();
+        let body = match body.try_into_bytes() {
+            Ok(body) => body,
+            Err(body) => {
+                self.release(state).await;
+                return response.set_body(body);
+            }
+        };
+
+        let stored_response = String::from_utf8(body.to_vec())
+            .ok()
+            .map(|body| StoredResponse {
+                status_code: response.status().as_u16(),
+                content_type: response
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|content_type| content_type.to_str().ok())
+                    .unwrap_or(mime::APPLICATION_JSON.essence_str())
+                    .to_owned(),
+                body,
+            });
+        if let Err(error) = self.store_response(state, stored_response).await {
+            logger::error!(
+                ?error,
+                "Failed to store the response of the idempotent request"
+            );
+        }
+        response.set_body(body).map_into_boxed_body()
+    }
+
+    /// Releases the key of a request which has not been processed, so that it can be retried
+    pub async fn release<A>(self, state: &A)
+    where
+        A: AppStateInfo,
+    {
+        if let Err(error) = self.store_response(state, None).await {
+            logger::error!(?error, "Failed to release the idempotency key");
+        }
+    }
+
+    /// Stores the response against the key, or releases the key if there is no response to store
+    async fn store_response<A>(
+        &self,
+        state: &A,
+        response: Option<StoredResponse>,
+    ) -> RouterResult<()>
+    where
+        A: AppStateInfo,
+    {
+        let now = date_time::now();
+        let ttl_in_seconds = state.conf().idempotency.ttl_in_seconds;
+        let idempotency_key_update = match &response {
+            Some(response) => storage::IdempotencyKeyUpdate {
+                response: Some(
+                    serde_json::to_string(response)
+                        .into_report()
+                        .change_context(errors::ApiErrorResponse::InternalServerError)?,
+                ),
+                expires_at: now + time::Duration::seconds(ttl_in_seconds.into()),
+            },
+            // An expired key is taken over by the next request made with it
+            None => storage::IdempotencyKeyUpdate {
+                response: None,
+                expires_at: now,
+            },
+        };
+        let idempotency_key = state
+            .store()
+            .update_idempotency_key_by_merchant_id_key(
+                &self.input.merchant_id,
+                &self.input.key,
+                idempotency_key_update,
+            )
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+        if let Some(response) = response {
+            let record = IdempotencyRecord {
+                request_fingerprint: self.input.request_fingerprint.clone(),
+                response: Some(response),
+            };
+            self.input
+                .cache(state, &record, idempotency_key.expires_at)
+                .await;
+        }
+
+        Ok(())
+    }
+}
+
+fn is_duplicate(error: &errors::StorageError) -> bool {
+    error.is_db_unique_violation() || matches!(error, errors::StorageError::DuplicateValue { .. })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use actix_web::ResponseError;
+
+    use super::*;
+    use crate::{db::idempotency_key::IdempotencyKeyInterface, utils::test_utils::get_mock_state};
+
+    fn get_input(key: &str, request_fingerprint: &str) -> IdempotencyInput {
+        IdempotencyInput {
+            merchant_id: "merchant_idempotency_test".to_string(),
+            key: key.to_string(),
+            request_fingerprint: request_fingerprint.to_string(),
+        }
+    }
+
+    fn acquired(status: IdempotencyStatus) -> IdempotencyGuard {
+        match status {
+            IdempotencyStatus::Acquired(guard) => guard,
+            IdempotencyStatus::Replay(response) => panic!("unexpected replay of {response:?}"),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_replay_of_recorded_response() {
+        let state = get_mock_state().await;
+        let key = uuid::Uuid::new_v4().to_string();
+
+        let guard = acquired(
+            get_input(&key, "fingerprint")
+                .acquire(&state)
+                .await
+                .unwrap(),
+        );
+        let response = HttpResponse::BadRequest()
+            .content_type(mime::APPLICATION_JSON)
+            .body(r#"{"error":"declined"}"#);
+        let response = guard.record(&state, response).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        match get_input(&key, "fingerprint")
+            .acquire(&state)
+            .await
+            .unwrap()
+        {
+            IdempotencyStatus::Replay(response) => assert_eq!(
+                response,
+                StoredResponse {
+                    status_code: 400,
+                    content_type: mime::APPLICATION_JSON.to_string(),
+                    body: r#"{"error":"declined"}"#.to_string(),
+                }
+            ),
+            IdempotencyStatus::Acquired(_) => panic!("the key was acquired twice"),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_key_reused_with_different_request() {
+        let state = get_mock_state().await;
+        let key = uuid::Uuid::new_v4().to_string();
+
+        acquired(
+            get_input(&key, "fingerprint")
+                .acquire(&state)
+                .await
+                .unwrap(),
+        );
+        let error = get_input(&key, "other_fingerprint")
+            .acquire(&state)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::IdempotencyKeyReused
+        ));
+        assert_eq!(error.current_context().status_code(), StatusCode::CONFLICT);
+    }
+
+    #[actix_rt::test]
+    async fn test_request_in_progress_and_released() {
+        let state = get_mock_state().await;
+        let key = uuid::Uuid::new_v4().to_string();
+
+        let guard = acquired(
+            get_input(&key, "fingerprint")
+                .acquire(&state)
+                .await
+                .unwrap(),
+        );
+        let error = get_input(&key, "fingerprint")
+            .acquire(&state)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::ApiErrorResponse::IdempotentRequestInProgress
+        ));
+
+        guard.release(&state).await;
+        acquired(
+            get_input(&key, "fingerprint")
+                .acquire(&state)
+                .await
+                .unwrap(),
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_key_held_in_postgres_and_purged_on_expiry() {
+        let state = get_mock_state().await;
+        let key = uuid::Uuid::new_v4().to_string();
+
+        let guard = acquired(
+            get_input(&key, "fingerprint")
+                .acquire(&state)
+                .await
+                .unwrap(),
+        );
+        let stored = state
+            .store
+            .find_idempotency_key_by_merchant_id_key("merchant_idempotency_test", &key)
+            .await
+            .unwrap();
+        assert_eq!(stored.request_fingerprint, "fingerprint");
+
+        guard.release(&state).await;
+        let purged_count = state
+            .store
+            .delete_expired_idempotency_keys(common_utils::date_time::now())
+            .await
+            .unwrap();
+        assert_eq!(purged_count, 1);
+        assert!(state
+            .store
+            .find_idempotency_key_by_merchant_id_key("merchant_idempotency_test", &key)
+            .await
+            .unwrap_err()
+            .current_context()
+            .is_db_not_found());
+    }
+}
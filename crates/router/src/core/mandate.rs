@@ -32,11 +32,20 @@ pub async fn get_mandate(
     let mandate = state
         .store
         .as_ref()
-        .find_mandate_by_merchant_id_mandate_id(&merchant_account.merchant_id, &req.mandate_id)
+        .find_mandate_by_merchant_id_mandate_id(
+            &merchant_account.merchant_id,
+            &req.mandate_id,
+            merchant_account.storage_scheme,
+        )
         .await
         .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
     Ok(services::ApplicationResponse::Json(
-        mandates::MandateResponse::from_db_mandate(&state, mandate).await?,
+        mandates::MandateResponse::from_db_mandate(
+            &state,
+            mandate,
+            merchant_account.storage_scheme,
+        )
+        .await?,
     ))
 }
 
@@ -54,6 +63,7 @@ pub async fn revoke_mandate(
             storage::MandateUpdate::StatusUpdate {
                 mandate_status: storage::enums::MandateStatus::Revoked,
             },
+            merchant_account.storage_scheme,
         )
         .await
         .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
@@ -72,13 +82,14 @@ pub async fn update_connector_mandate_id(
     merchant_account: String,
     mandate_ids_opt: Option<api_models::payments::MandateIds>,
     resp: Result<types::PaymentsResponseData, types::ErrorResponse>,
+    storage_scheme: storage::enums::MerchantStorageScheme,
 ) -> RouterResponse<mandates::MandateResponse> {
     let connector_mandate_id = Option::foreign_try_from(resp)?;
     //Ignore updation if the payment_attempt mandate_id or connector_mandate_id is not present
     if let Some((mandate_ids, connector_id)) = mandate_ids_opt.zip(connector_mandate_id) {
         let mandate_id = &mandate_ids.mandate_id;
         let mandate = db
-            .find_mandate_by_merchant_id_mandate_id(&merchant_account, mandate_id)
+            .find_mandate_by_merchant_id_mandate_id(&merchant_account, mandate_id, storage_scheme)
             .await
             .change_context(errors::ApiErrorResponse::MandateNotFound)?;
         // only update the connector_mandate_id if existing is none
@@ -89,6 +100,7 @@ pub async fn update_connector_mandate_id(
                 storage::MandateUpdate::ConnectorReferenceUpdate {
                     connector_mandate_ids: Some(connector_id),
                 },
+                storage_scheme,
             )
             .await
             .change_context(errors::ApiErrorResponse::MandateUpdateFailed)?;
@@ -105,7 +117,11 @@ pub async fn get_customer_mandates(
 ) -> RouterResponse<Vec<mandates::MandateResponse>> {
     let mandates = state
         .store
-        .find_mandate_by_merchant_id_customer_id(&merchant_account.merchant_id, &req.customer_id)
+        .find_mandate_by_merchant_id_customer_id(
+            &merchant_account.merchant_id,
+            &req.customer_id,
+            merchant_account.storage_scheme,
+        )
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable_lazy(|| {
@@ -120,7 +136,14 @@ pub async fn get_customer_mandates(
     } else {
         let mut response_vec = Vec::with_capacity(mandates.len());
         for mandate in mandates {
-            response_vec.push(mandates::MandateResponse::from_db_mandate(&state, mandate).await?);
+            response_vec.push(
+                mandates::MandateResponse::from_db_mandate(
+                    &state,
+                    mandate,
+                    merchant_account.storage_scheme,
+                )
+                .await?,
+            );
         }
         Ok(services::ApplicationResponse::Json(response_vec))
     }
@@ -144,6 +167,7 @@ pub async fn mandate_procedure<F, FData>(
     maybe_customer: &Option<domain::Customer>,
     pm_id: Option<String>,
     merchant_connector_id: Option<String>,
+    storage_scheme: storage_enums::MerchantStorageScheme,
 ) -> errors::RouterResult<types::RouterData<F, FData, types::PaymentsResponseData>>
 where
     FData: MandateBehaviour,
@@ -155,7 +179,11 @@ where
                 let mandate_id = &mandate_id.mandate_id;
                 let mandate = state
                     .store
-                    .find_mandate_by_merchant_id_mandate_id(resp.merchant_id.as_ref(), mandate_id)
+                    .find_mandate_by_merchant_id_mandate_id(
+                        resp.merchant_id.as_ref(),
+                        mandate_id,
+                        storage_scheme,
+                    )
                     .await
                     .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
                 let mandate = match mandate.mandate_type {
@@ -167,6 +195,7 @@ where
                             storage::MandateUpdate::StatusUpdate {
                                 mandate_status: storage_enums::MandateStatus::Revoked,
                             },
+                            storage_scheme,
                         )
                         .await
                         .change_context(errors::ApiErrorResponse::MandateUpdateFailed),
@@ -181,6 +210,7 @@ where
                                         + resp.request.get_amount(),
                                 ),
                             },
+                            storage_scheme,
                         )
                         .await
                         .change_context(errors::ApiErrorResponse::MandateUpdateFailed),
@@ -262,7 +292,7 @@ where
                         }));
                         state
                             .store
-                            .insert_mandate(new_mandate_data)
+                            .insert_mandate(new_mandate_data, storage_scheme)
                             .await
                             .to_duplicate_response(errors::ApiErrorResponse::DuplicateMandate)?;
                         metrics::MANDATE_COUNT.add(
@@ -287,15 +317,17 @@ pub async fn retrieve_mandates_list(
     let mandates = state
         .store
         .as_ref()
-        .find_mandates_by_merchant_id(&merchant_account.merchant_id, constraints)
+        .find_mandates_by_merchant_id(
+            &merchant_account.merchant_id,
+            constraints,
+            merchant_account.storage_scheme,
+        )
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Unable to retrieve mandates")?;
-    let mandates_list = future::try_join_all(
-        mandates
-            .into_iter()
-            .map(|mandate| mandates::MandateResponse::from_db_mandate(&state, mandate)),
-    )
+    let mandates_list = future::try_join_all(mandates.into_iter().map(|mandate| {
+        mandates::MandateResponse::from_db_mandate(&state, mandate, merchant_account.storage_scheme)
+    }))
     .await?;
     Ok(services::ApplicationResponse::Json(mandates_list))
 }
@@ -77,3 +77,14 @@ counter_metric!(
     ROUTING_RETRIEVE_CONFIG_FOR_PROFILE_SUCCESS_RESPONSE,
     GLOBAL_METER
 );
+counter_metric!(ROUTING_SIMULATE_CONFIG, GLOBAL_METER);
+counter_metric!(ROUTING_SIMULATE_CONFIG_SUCCESS_RESPONSE, GLOBAL_METER);
+counter_metric!(ROUTING_RETRIEVE_ACTIVATION_HISTORY, GLOBAL_METER);
+counter_metric!(
+    ROUTING_RETRIEVE_ACTIVATION_HISTORY_SUCCESS_RESPONSE,
+    GLOBAL_METER
+);
+counter_metric!(ROUTING_DIFF_CONFIG, GLOBAL_METER);
+counter_metric!(ROUTING_DIFF_CONFIG_SUCCESS_RESPONSE, GLOBAL_METER);
+counter_metric!(ROUTING_ROLLBACK_CONFIG, GLOBAL_METER);
+counter_metric!(ROUTING_ROLLBACK_CONFIG_SUCCESS_RESPONSE, GLOBAL_METER);
@@ -92,6 +92,7 @@ impl Feature<api::SetupMandate, types::SetupMandateRequestData> for types::Setup
             maybe_customer,
             pm_id,
             connector.merchant_connector_id.clone(),
+            merchant_account.storage_scheme,
         )
         .await
     }
@@ -225,6 +226,7 @@ impl types::SetupMandateRouterData {
                     maybe_customer,
                     pm_id,
                     connector.merchant_connector_id.clone(),
+                    merchant_account.storage_scheme,
                 )
                 .await?)
             }
@@ -17,7 +17,7 @@ use crate::{
         errors::{self, RouterResult, StorageErrorExt},
         mandate,
         payment_methods::PaymentMethodRetrieve,
-        payments::{helpers as payments_helpers, types::MultipleCaptureData, PaymentData},
+        payments::{helpers as payments_helpers, routing, types::MultipleCaptureData, PaymentData},
         utils as core_utils,
     },
     routes::{metrics, AppState},
@@ -722,6 +722,7 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
     // Stage 1
 
     let payment_attempt = payment_data.payment_attempt.clone();
+    let previous_attempt_status = payment_attempt.status;
 
     let m_db = state.clone().store;
     let m_payment_attempt_update = payment_attempt_update.clone();
@@ -761,6 +762,8 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
 
     payment_data.payment_attempt = payment_attempt;
 
+    routing::success_rate::record_attempt_outcome(state, &payment_data, previous_attempt_status);
+
     let amount_captured = get_total_amount_captured(
         router_data.request,
         router_data.amount_captured,
@@ -818,6 +821,7 @@ async fn payment_response_update_tracker<F: Clone, T: types::Capturable>(
                 m_router_data_merchant_id,
                 m_payment_data_mandate_id,
                 m_router_data_response,
+                storage_scheme,
             )
             .await
         }
@@ -1,3 +1,5 @@
+pub mod circuit_breaker;
+pub mod success_rate;
 mod transformers;
 
 use std::{
@@ -12,7 +14,6 @@ use api_models::{
     payments::Address,
     routing::ConnectorSelection,
 };
-use common_utils::static_cache::StaticCache;
 use diesel_models::enums as storage_enums;
 use error_stack::{IntoReport, ResultExt};
 use euclid::{
@@ -31,6 +32,7 @@ use rand::{
     SeedableRng,
 };
 use rustc_hash::FxHashMap;
+use storage_impl::redis::cache::{Cache, CGRAPH_CACHE, ROUTING_CACHE};
 
 #[cfg(not(feature = "business_profile_routing"))]
 use crate::utils::StringExt;
@@ -52,6 +54,69 @@ pub(super) enum CachedAlgorithm {
     Priority(Vec<routing_types::RoutableConnectorChoice>),
     VolumeSplit(Vec<routing_types::ConnectorVolumeSplit>),
     Advanced(backend::VirInterpreterBackend<ConnectorSelection>),
+    SuccessRateBased(routing_types::SuccessRateBasedAlgorithm),
+}
+
+impl CachedAlgorithm {
+    fn from_routing_algorithm(algorithm: routing_types::RoutingAlgorithm) -> RoutingResult<Self> {
+        Ok(match algorithm {
+            routing_types::RoutingAlgorithm::Single(conn) => Self::Single(conn),
+            routing_types::RoutingAlgorithm::Priority(plist) => Self::Priority(plist),
+            routing_types::RoutingAlgorithm::VolumeSplit(splits) => Self::VolumeSplit(splits),
+            routing_types::RoutingAlgorithm::Advanced(program) => {
+                let interpreter = backend::VirInterpreterBackend::with_program(program)
+                    .into_report()
+                    .change_context(errors::RoutingError::DslBackendInitError)
+                    .attach_printable("Error initializing DSL interpreter backend")?;
+
+                Self::Advanced(interpreter)
+            }
+            routing_types::RoutingAlgorithm::SuccessRateBased(algorithm) => {
+                Self::SuccessRateBased(algorithm)
+            }
+        })
+    }
+}
+
+/// Replays historical payments through a routing algorithm without affecting live traffic.
+pub struct RoutingSimulator(CachedAlgorithm);
+
+impl RoutingSimulator {
+    pub fn new(algorithm: routing_types::RoutingAlgorithm) -> RoutingResult<Self> {
+        CachedAlgorithm::from_routing_algorithm(algorithm).map(Self)
+    }
+
+    /// Returns the connector the algorithm would have routed the payment to.
+    ///
+    /// Volume splits are seeded with the payment ID so that repeated simulations over the same
+    /// payments produce the same result. Success rate based algorithms depend on live
+    /// authorization rates, so the first configured connector is reported for them.
+    pub fn simulate(
+        &self,
+        payment_intent: &oss_storage::PaymentIntent,
+        payment_attempt: &oss_storage::PaymentAttempt,
+    ) -> RoutingResult<Option<routing_types::RoutableConnectorChoice>> {
+        let connectors = match &self.0 {
+            CachedAlgorithm::Single(conn) => vec![(**conn).clone()],
+            CachedAlgorithm::Priority(plist) => plist.clone(),
+            CachedAlgorithm::VolumeSplit(splits) => {
+                perform_volume_split(splits.to_vec(), Some(&payment_intent.payment_id))
+                    .change_context(errors::RoutingError::ConnectorSelectionFailed)?
+            }
+            CachedAlgorithm::Advanced(interpreter) => {
+                let backend_input =
+                    make_dsl_input_for_historical_payment(payment_intent, payment_attempt)?;
+                execute_dsl_and_get_connector_v1_with_seed(
+                    backend_input,
+                    interpreter,
+                    Some(&payment_intent.payment_id),
+                )?
+            }
+            CachedAlgorithm::SuccessRateBased(algorithm) => algorithm.connectors.clone(),
+        };
+
+        Ok(connectors.into_iter().next())
+    }
 }
 
 pub struct SessionFlowRoutingInput<'a> {
@@ -78,11 +143,21 @@ pub struct SessionRoutingPmTypeInput<'a> {
     ))]
     profile_id: Option<String>,
 }
-static ROUTING_CACHE: StaticCache<CachedAlgorithm> = StaticCache::new();
-static KGRAPH_CACHE: StaticCache<euclid_graph::KnowledgeGraph<'_>> = StaticCache::new();
-
 type RoutingResult<O> = oss_errors::CustomResult<O, errors::RoutingError>;
 
+/// The routing and cgraph caches hold their entries along with the modification time of the
+/// configuration they were built from, entries built from an older configuration are stale.
+async fn get_cached_entry<T: Send + Sync + 'static>(
+    cache: &Cache,
+    key: &str,
+    timestamp: i64,
+) -> Option<Arc<T>> {
+    cache
+        .get_val::<(i64, Arc<T>)>(key)
+        .await
+        .and_then(|(cached_timestamp, entry)| (timestamp <= cached_timestamp).then_some(entry))
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[serde(untagged)]
 enum MerchantAccountRoutingAlgorithm {
@@ -199,6 +274,86 @@ where
         payment: payment_input,
         payment_method: payment_method_input,
         mandate: mandate_data,
+        temporal: dsl_inputs::TemporalInput::from_unix_timestamp(
+            common_utils::date_time::now_unix_timestamp(),
+        ),
+    })
+}
+
+/// Builds the DSL input for a payment that has already been processed. Fields that are not
+/// persisted with the payment, such as the card BIN, card network and billing country, are left
+/// empty, so rules depending on them will not match. Time based rules are evaluated against the
+/// creation time of the payment.
+pub fn make_dsl_input_for_historical_payment(
+    payment_intent: &oss_storage::PaymentIntent,
+    payment_attempt: &oss_storage::PaymentAttempt,
+) -> RoutingResult<dsl_inputs::BackendInput> {
+    let mandate_data = dsl_inputs::MandateData {
+        mandate_acceptance_type: None,
+        mandate_type: payment_attempt.mandate_details.as_ref().map(
+            |mandate_type| match mandate_type {
+                data_models::mandates::MandateDataType::SingleUse(_) => {
+                    euclid_enums::MandateType::SingleUse
+                }
+                data_models::mandates::MandateDataType::MultiUse(_) => {
+                    euclid_enums::MandateType::MultiUse
+                }
+            },
+        ),
+        payment_type: Some(payment_attempt.mandate_details.as_ref().map_or_else(
+            || euclid_enums::PaymentType::NonMandate,
+            |_| euclid_enums::PaymentType::SetupMandate,
+        )),
+    };
+
+    let payment_method_input = dsl_inputs::PaymentMethodInput {
+        payment_method: payment_attempt.payment_method,
+        payment_method_type: payment_attempt.payment_method_type,
+        card_network: None,
+    };
+
+    let payment_input = dsl_inputs::PaymentInput {
+        amount: payment_intent.amount,
+        card_bin: None,
+        currency: payment_attempt
+            .currency
+            .or(payment_intent.currency)
+            .get_required_value("currency")
+            .change_context(errors::RoutingError::DslMissingRequiredField {
+                field_name: "currency".to_string(),
+            })?,
+        authentication_type: payment_attempt.authentication_type,
+        capture_method: payment_attempt
+            .capture_method
+            .and_then(|cm| cm.foreign_into()),
+        business_country: payment_intent
+            .business_country
+            .map(api_enums::Country::from_alpha2),
+        billing_country: None,
+        business_label: payment_intent.business_label.clone(),
+        setup_future_usage: payment_intent.setup_future_usage,
+    };
+
+    let metadata = payment_intent
+        .metadata
+        .clone()
+        .map(|val| val.parse_value("routing_parameters"))
+        .transpose()
+        .change_context(errors::RoutingError::MetadataParsingError)
+        .attach_printable("Unable to parse routing_parameters from metadata of payment_intent")
+        .unwrap_or_else(|err| {
+            logger::error!(error=?err);
+            None
+        });
+
+    Ok(dsl_inputs::BackendInput {
+        metadata,
+        payment: payment_input,
+        payment_method: payment_method_input,
+        mandate: mandate_data,
+        temporal: dsl_inputs::TemporalInput::from_unix_timestamp(
+            payment_intent.created_at.assume_utc().unix_timestamp(),
+        ),
     })
 }
 
@@ -230,7 +385,7 @@ pub async fn perform_static_routing_v1<F: Clone>(
 
         return Ok(fallback_config);
     };
-    let key = ensure_algorithm_cached_v1(
+    let cached_algorithm = ensure_algorithm_cached_v1(
         state,
         merchant_id,
         algorithm_ref.timestamp,
@@ -239,11 +394,6 @@ pub async fn perform_static_routing_v1<F: Clone>(
         payment_data.payment_intent.profile_id.clone(),
     )
     .await?;
-    let cached_algorithm: Arc<CachedAlgorithm> = ROUTING_CACHE
-        .retrieve(&key)
-        .into_report()
-        .change_context(errors::RoutingError::CacheMiss)
-        .attach_printable("Unable to retrieve cached routing algorithm even after refresh")?;
 
     Ok(match cached_algorithm.as_ref() {
         CachedAlgorithm::Single(conn) => vec![(**conn).clone()],
@@ -258,6 +408,12 @@ pub async fn perform_static_routing_v1<F: Clone>(
 
             execute_dsl_and_get_connector_v1(backend_input, interpreter)?
         }
+
+        CachedAlgorithm::SuccessRateBased(algorithm) => {
+            let key = success_rate::SuccessRateKey::from_payment_data(payment_data);
+
+            success_rate::perform_success_rate_routing(state, &key, algorithm).await
+        }
     })
 }
 
@@ -267,7 +423,7 @@ async fn ensure_algorithm_cached_v1(
     timestamp: i64,
     algorithm_id: &str,
     #[cfg(feature = "business_profile_routing")] profile_id: Option<String>,
-) -> RoutingResult<String> {
+) -> RoutingResult<Arc<CachedAlgorithm>> {
     #[cfg(feature = "business_profile_routing")]
     let key = {
         let profile_id = profile_id
@@ -281,31 +437,20 @@ async fn ensure_algorithm_cached_v1(
     #[cfg(not(feature = "business_profile_routing"))]
     let key = format!("dsl_{merchant_id}");
 
-    let present = ROUTING_CACHE
-        .present(&key)
-        .into_report()
-        .change_context(errors::RoutingError::DslCachePoisoned)
-        .attach_printable("Error checking presence of DSL")?;
-
-    let expired = ROUTING_CACHE
-        .expired(&key, timestamp)
-        .into_report()
-        .change_context(errors::RoutingError::DslCachePoisoned)
-        .attach_printable("Error checking expiry of DSL in cache")?;
-
-    if !present || expired {
-        refresh_routing_cache_v1(
-            state,
-            key.clone(),
-            algorithm_id,
-            timestamp,
-            #[cfg(feature = "business_profile_routing")]
-            profile_id,
-        )
-        .await?;
-    };
-
-    Ok(key)
+    match get_cached_entry(&ROUTING_CACHE, &key, timestamp).await {
+        Some(cached_algorithm) => Ok(cached_algorithm),
+        None => {
+            refresh_routing_cache_v1(
+                state,
+                key,
+                algorithm_id,
+                timestamp,
+                #[cfg(feature = "business_profile_routing")]
+                profile_id,
+            )
+            .await
+        }
+    }
 }
 
 pub fn perform_straight_through_routing<F: Clone>(
@@ -334,6 +479,14 @@ pub fn perform_straight_through_routing<F: Clone>(
 fn execute_dsl_and_get_connector_v1(
     backend_input: dsl_inputs::BackendInput,
     interpreter: &backend::VirInterpreterBackend<ConnectorSelection>,
+) -> RoutingResult<Vec<routing_types::RoutableConnectorChoice>> {
+    execute_dsl_and_get_connector_v1_with_seed(backend_input, interpreter, None)
+}
+
+fn execute_dsl_and_get_connector_v1_with_seed(
+    backend_input: dsl_inputs::BackendInput,
+    interpreter: &backend::VirInterpreterBackend<ConnectorSelection>,
+    rng_seed: Option<&str>,
 ) -> RoutingResult<Vec<routing_types::RoutableConnectorChoice>> {
     let routing_output: routing_types::RoutingAlgorithm = interpreter
         .execute(backend_input)
@@ -344,8 +497,10 @@ fn execute_dsl_and_get_connector_v1(
     Ok(match routing_output {
         routing_types::RoutingAlgorithm::Priority(plist) => plist,
 
-        routing_types::RoutingAlgorithm::VolumeSplit(splits) => perform_volume_split(splits, None)
-            .change_context(errors::RoutingError::DslFinalConnectorSelectionFailed)?,
+        routing_types::RoutingAlgorithm::VolumeSplit(splits) => {
+            perform_volume_split(splits, rng_seed)
+                .change_context(errors::RoutingError::DslFinalConnectorSelectionFailed)?
+        }
 
         _ => Err(errors::RoutingError::DslIncorrectSelectionAlgorithm)
             .into_report()
@@ -353,13 +508,13 @@ fn execute_dsl_and_get_connector_v1(
     })
 }
 
-pub async fn refresh_routing_cache_v1(
+pub(super) async fn refresh_routing_cache_v1(
     state: &AppState,
     key: String,
     algorithm_id: &str,
     timestamp: i64,
     #[cfg(feature = "business_profile_routing")] profile_id: Option<String>,
-) -> RoutingResult<()> {
+) -> RoutingResult<Arc<CachedAlgorithm>> {
     #[cfg(feature = "business_profile_routing")]
     let algorithm = {
         let algorithm = state
@@ -393,29 +548,13 @@ pub async fn refresh_routing_cache_v1(
             .attach_printable("Error parsing routing algorithm from configs")?;
         algorithm
     };
-    let cached_algorithm = match algorithm {
-        routing_types::RoutingAlgorithm::Single(conn) => CachedAlgorithm::Single(conn),
-        routing_types::RoutingAlgorithm::Priority(plist) => CachedAlgorithm::Priority(plist),
-        routing_types::RoutingAlgorithm::VolumeSplit(splits) => {
-            CachedAlgorithm::VolumeSplit(splits)
-        }
-        routing_types::RoutingAlgorithm::Advanced(program) => {
-            let interpreter = backend::VirInterpreterBackend::with_program(program)
-                .into_report()
-                .change_context(errors::RoutingError::DslBackendInitError)
-                .attach_printable("Error initializing DSL interpreter backend")?;
-
-            CachedAlgorithm::Advanced(interpreter)
-        }
-    };
+    let cached_algorithm = Arc::new(CachedAlgorithm::from_routing_algorithm(algorithm)?);
 
     ROUTING_CACHE
-        .save(key, cached_algorithm, timestamp)
-        .into_report()
-        .change_context(errors::RoutingError::DslCachePoisoned)
-        .attach_printable("Error saving DSL to cache")?;
+        .push(key, (timestamp, Arc::clone(&cached_algorithm)))
+        .await;
 
-    Ok(())
+    Ok(cached_algorithm)
 }
 
 pub fn perform_volume_split(
@@ -454,12 +593,12 @@ pub fn perform_volume_split(
     Ok(splits.into_iter().map(|sp| sp.connector).collect())
 }
 
-pub async fn get_merchant_kgraph<'a>(
+pub async fn get_merchant_kgraph(
     state: &AppState,
     key_store: &domain::MerchantKeyStore,
     merchant_last_modified: i64,
     #[cfg(feature = "business_profile_routing")] profile_id: Option<String>,
-) -> RoutingResult<Arc<euclid_graph::KnowledgeGraph<'a>>> {
+) -> RoutingResult<Arc<euclid_graph::KnowledgeGraph<'static>>> {
     #[cfg(feature = "business_profile_routing")]
     let key = {
         let profile_id = profile_id
@@ -473,37 +612,20 @@ pub async fn get_merchant_kgraph<'a>(
     #[cfg(not(feature = "business_profile_routing"))]
     let key = format!("kgraph_{}", key_store.merchant_id);
 
-    let kgraph_present = KGRAPH_CACHE
-        .present(&key)
-        .into_report()
-        .change_context(errors::RoutingError::KgraphCacheFailure)
-        .attach_printable("when checking kgraph presence")?;
-
-    let kgraph_expired = KGRAPH_CACHE
-        .expired(&key, merchant_last_modified)
-        .into_report()
-        .change_context(errors::RoutingError::KgraphCacheFailure)
-        .attach_printable("when checking kgraph expiry")?;
-
-    if !kgraph_present || kgraph_expired {
-        refresh_kgraph_cache(
-            state,
-            key_store,
-            merchant_last_modified,
-            key.clone(),
-            #[cfg(feature = "business_profile_routing")]
-            profile_id,
-        )
-        .await?;
+    match get_cached_entry(&CGRAPH_CACHE, &key, merchant_last_modified).await {
+        Some(cached_kgraph) => Ok(cached_kgraph),
+        None => {
+            refresh_kgraph_cache(
+                state,
+                key_store,
+                merchant_last_modified,
+                key,
+                #[cfg(feature = "business_profile_routing")]
+                profile_id,
+            )
+            .await
+        }
     }
-
-    let cached_kgraph = KGRAPH_CACHE
-        .retrieve(&key)
-        .into_report()
-        .change_context(errors::RoutingError::CacheMiss)
-        .attach_printable("when retrieving kgraph")?;
-
-    Ok(cached_kgraph)
 }
 
 pub async fn refresh_kgraph_cache(
@@ -512,7 +634,7 @@ pub async fn refresh_kgraph_cache(
     timestamp: i64,
     key: String,
     #[cfg(feature = "business_profile_routing")] profile_id: Option<String>,
-) -> RoutingResult<()> {
+) -> RoutingResult<Arc<euclid_graph::KnowledgeGraph<'static>>> {
     let mut merchant_connector_accounts = state
         .store
         .find_merchant_connector_account_by_merchant_id_and_disabled_list(
@@ -544,14 +666,13 @@ pub async fn refresh_kgraph_cache(
         .into_report()
         .change_context(errors::RoutingError::KgraphCacheRefreshFailed)
         .attach_printable("when construction kgraph")?;
+    let kgraph = Arc::new(kgraph);
 
-    KGRAPH_CACHE
-        .save(key, kgraph, timestamp)
-        .into_report()
-        .change_context(errors::RoutingError::KgraphCacheRefreshFailed)
-        .attach_printable("when saving kgraph to cache")?;
+    CGRAPH_CACHE
+        .push(key, (timestamp, Arc::clone(&kgraph)))
+        .await;
 
-    Ok(())
+    Ok(kgraph)
 }
 
 async fn perform_kgraph_filtering(
@@ -708,6 +829,10 @@ pub async fn perform_eligibility_analysis_with_fallback<F: Clone>(
             .collect::<Vec<_>>(),
     );
 
+    // Applied once to the final list, as probing a connector claims one of its probes
+    let final_selection =
+        circuit_breaker::perform_circuit_breaker_filtering(state, final_selection).await;
+
     let final_selected_connectors = final_selection
         .iter()
         .map(|item| item.connector)
@@ -819,6 +944,9 @@ pub async fn perform_session_flow_routing(
             mandate_type: None,
             payment_type: None,
         },
+        temporal: dsl_inputs::TemporalInput::from_unix_timestamp(
+            common_utils::date_time::now_unix_timestamp(),
+        ),
     };
 
     for connector_data in session_input.chosen.iter() {
@@ -882,7 +1010,7 @@ async fn perform_session_routing_for_pm_type(
     let chosen_connectors = match session_pm_input.routing_algorithm {
         MerchantAccountRoutingAlgorithm::V1(algorithm_ref) => {
             if let Some(ref algorithm_id) = algorithm_ref.algorithm_id {
-                let key = ensure_algorithm_cached_v1(
+                let cached_algorithm = ensure_algorithm_cached_v1(
                     &session_pm_input.state.clone(),
                     merchant_id,
                     algorithm_ref.timestamp,
@@ -892,12 +1020,6 @@ async fn perform_session_routing_for_pm_type(
                 )
                 .await?;
 
-                let cached_algorithm = ROUTING_CACHE
-                    .retrieve(&key)
-                    .into_report()
-                    .change_context(errors::RoutingError::CacheMiss)
-                    .attach_printable("unable to retrieve cached routing algorithm")?;
-
                 match cached_algorithm.as_ref() {
                     CachedAlgorithm::Single(conn) => vec![(**conn).clone()],
                     CachedAlgorithm::Priority(plist) => plist.clone(),
@@ -909,6 +1031,9 @@ async fn perform_session_routing_for_pm_type(
                         session_pm_input.backend_input.clone(),
                         interpreter,
                     )?,
+                    // Session tokens are fetched before the payment method is known, so the
+                    // connectors are not ranked by their authorization rate
+                    CachedAlgorithm::SuccessRateBased(algorithm) => algorithm.connectors.clone(),
                 }
             } else {
                 routing_helpers::get_merchant_default_config(
@@ -1053,6 +1178,9 @@ pub fn make_dsl_input_for_surcharge(
         payment: payment_input,
         payment_method: payment_method_input,
         mandate: mandate_data,
+        temporal: dsl_inputs::TemporalInput::from_unix_timestamp(
+            common_utils::date_time::now_unix_timestamp(),
+        ),
     };
     Ok(backend_input)
 }
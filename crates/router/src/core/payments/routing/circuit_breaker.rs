@@ -0,0 +1,689 @@
+//! A circuit breaker per merchant connector account. Consecutive 5xx responses or timeouts from a
+//! connector open the circuit, removing the merchant connector account from routing. After a
+//! cool-down, the circuit is half-opened and a few payments are routed to the merchant connector
+//! account to probe it, closing the circuit again once enough of them succeed.
+
+use std::collections::HashMap;
+
+use api_models::enums::CircuitBreakerState;
+use error_stack::{IntoReport, ResultExt};
+use redis_interface::RedisConnectionPool;
+use router_env::{instrument, tracing};
+use tracing_futures::Instrument;
+
+use super::RoutingResult;
+use crate::{
+    configs::settings::CircuitBreakerSettings,
+    core::errors::{self, CustomResult},
+    logger,
+    routes::metrics,
+    types::{self, api::routing as routing_types},
+    AppState,
+};
+
+/// Circuits without any activity for this long are forgotten, and hence closed
+const CIRCUIT_TTL_SECS: i64 = 24 * 60 * 60;
+
+const STATE_FIELD: &str = "state";
+const FAILURES_FIELD: &str = "failures";
+const OPENED_AT_FIELD: &str = "opened_at";
+const PROBE_WINDOW_STARTED_AT_FIELD: &str = "probe_window_started_at";
+const PROBES_FIELD: &str = "probes";
+const PROBE_SUCCESSES_FIELD: &str = "probe_successes";
+
+/// Applies the outcome of a connector call to the circuit, returning its state before and after.
+/// The circuit is read and updated in a single step so that concurrent outcomes are not lost.
+///
+/// KEYS: the circuit. ARGV: the outcome, failure threshold, success threshold, now and TTL.
+const RECORD_OUTCOME_SCRIPT: &str = r#"
+local key = KEYS[1]
+local outcome, now, ttl = ARGV[1], ARGV[4], tonumber(ARGV[5])
+local from = redis.call('HGET', key, 'state') or 'closed'
+local to = from
+
+local function open()
+    redis.call('HSET', key, 'state', 'open', 'failures', 0, 'opened_at', now, 'probes', 0,
+        'probe_successes', 0)
+    return 'open'
+end
+
+if from == 'closed' then
+    if outcome == 'failure' then
+        if redis.call('HINCRBY', key, 'failures', 1) >= tonumber(ARGV[2]) then
+            to = open()
+        end
+    elseif redis.call('HGET', key, 'failures') then
+        redis.call('HSET', key, 'failures', 0)
+    end
+elseif from == 'half_open' then
+    if outcome == 'failure' then
+        to = open()
+    elseif redis.call('HINCRBY', key, 'probe_successes', 1) >= tonumber(ARGV[3]) then
+        redis.call('DEL', key)
+        to = 'closed'
+    end
+end
+-- Outcomes of calls made before the circuit was opened do not affect it
+
+if redis.call('EXISTS', key) == 1 then
+    redis.call('EXPIRE', key, ttl)
+end
+return {from, to}
+"#;
+
+/// Claims one of the probes of a circuit which is open past its cool-down or half-open, returning
+/// its state before the claim and whether the claim succeeded. Once the cool-down of an open
+/// circuit has elapsed it is half-opened, and a limited number of probes are let through per
+/// cool-down period. Probes whose outcome was never recorded must not keep the circuit half-open
+/// forever, so a fresh set of probes is let through every cool-down period.
+///
+/// KEYS: the circuit. ARGV: now, cool-down, probes per cool-down and TTL.
+const CLAIM_PROBE_SCRIPT: &str = r#"
+local key = KEYS[1]
+local now, cooldown, max_probes = tonumber(ARGV[1]), tonumber(ARGV[2]), tonumber(ARGV[3])
+local ttl = tonumber(ARGV[4])
+local state = redis.call('HGET', key, 'state') or 'closed'
+
+if state == 'closed' then
+    return {state, 'true'}
+end
+
+if state == 'open' then
+    local opened_at = tonumber(redis.call('HGET', key, 'opened_at') or '0')
+    if now < opened_at + cooldown then
+        return {state, 'false'}
+    end
+    redis.call('HSET', key, 'state', 'half_open', 'probe_window_started_at', now, 'probes', 1,
+        'probe_successes', 0)
+    redis.call('EXPIRE', key, ttl)
+    return {state, 'true'}
+end
+
+local started_at = tonumber(redis.call('HGET', key, 'probe_window_started_at') or '0')
+if now >= started_at + cooldown then
+    redis.call('HSET', key, 'probe_window_started_at', now, 'probes', 1)
+    redis.call('EXPIRE', key, ttl)
+    return {state, 'true'}
+end
+
+local probes = redis.call('HINCRBY', key, 'probes', 1)
+redis.call('EXPIRE', key, ttl)
+if probes <= max_probes then
+    return {state, 'true'}
+end
+return {state, 'false'}
+"#;
+
+fn get_circuit_key(merchant_connector_id: &str) -> String {
+    format!("circuit_breaker_{merchant_connector_id}")
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Circuit {
+    pub state: CircuitBreakerState,
+    pub failures: u32,
+    pub opened_at: Option<i64>,
+    pub probe_window_started_at: Option<i64>,
+    pub probes: u32,
+    pub probe_successes: u32,
+}
+
+/// Whether payments may be routed to a merchant connector account, as far as its circuit is known
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Routability {
+    Routable,
+    /// Routable only if one of the probes of the circuit can be claimed
+    Probe,
+    Blocked,
+}
+
+impl Circuit {
+    fn from_fields(fields: &HashMap<String, String>) -> Self {
+        let parse = |field: &str| fields.get(field).and_then(|value| value.parse().ok());
+
+        Self {
+            state: fields
+                .get(STATE_FIELD)
+                .and_then(|state| state.parse().ok())
+                .unwrap_or_default(),
+            failures: parse(FAILURES_FIELD).unwrap_or_default(),
+            opened_at: parse(OPENED_AT_FIELD),
+            probe_window_started_at: parse(PROBE_WINDOW_STARTED_AT_FIELD),
+            probes: parse(PROBES_FIELD).unwrap_or_default(),
+            probe_successes: parse(PROBE_SUCCESSES_FIELD).unwrap_or_default(),
+        }
+    }
+
+    fn has_cooled_down(&self, settings: &CircuitBreakerSettings, now: i64) -> bool {
+        self.opened_at.map_or(true, |opened_at| {
+            now >= opened_at + i64::from(settings.cooldown_secs)
+        })
+    }
+
+    fn has_probe_window_elapsed(&self, settings: &CircuitBreakerSettings, now: i64) -> bool {
+        self.probe_window_started_at.map_or(true, |started_at| {
+            now >= started_at + i64::from(settings.cooldown_secs)
+        })
+    }
+
+    fn get_routability(&self, settings: &CircuitBreakerSettings, now: i64) -> Routability {
+        match self.state {
+            CircuitBreakerState::Closed => Routability::Routable,
+            CircuitBreakerState::Open if self.has_cooled_down(settings, now) => Routability::Probe,
+            CircuitBreakerState::HalfOpen
+                if self.has_probe_window_elapsed(settings, now)
+                    || self.probes < settings.half_open_probes =>
+            {
+                Routability::Probe
+            }
+            CircuitBreakerState::Open | CircuitBreakerState::HalfOpen => Routability::Blocked,
+        }
+    }
+}
+
+/// Whether a call to a connector counts as a failure of the merchant connector account
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectorCallOutcome {
+    Success,
+    Failure,
+}
+
+impl ConnectorCallOutcome {
+    pub fn from_status_code(status_code: u16) -> Self {
+        match status_code {
+            500..=599 => Self::Failure,
+            _ => Self::Success,
+        }
+    }
+
+    fn as_script_arg(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+        }
+    }
+}
+
+fn parse_circuit_state(state: &str) -> RoutingResult<CircuitBreakerState> {
+    state
+        .parse()
+        .into_report()
+        .change_context(errors::RoutingError::CircuitBreakerUpdateFailed)
+        .attach_printable_lazy(|| format!("Unknown circuit breaker state {state}"))
+}
+
+fn record_transition(
+    merchant_connector_id: &str,
+    from: CircuitBreakerState,
+    to: CircuitBreakerState,
+) {
+    logger::info!(
+        merchant_connector_id,
+        from = %from,
+        to = %to,
+        "Connector circuit breaker transitioned"
+    );
+    metrics::CIRCUIT_BREAKER_TRANSITION_COUNT.add(
+        &metrics::CONTEXT,
+        1,
+        &[
+            metrics::request::add_attributes("from", from.to_string()),
+            metrics::request::add_attributes("to", to.to_string()),
+        ],
+    );
+}
+
+fn get_redis_conn(
+    state: &AppState,
+    error: errors::RoutingError,
+) -> RoutingResult<std::sync::Arc<RedisConnectionPool>> {
+    state
+        .store
+        .get_redis_conn()
+        .change_context(error)
+        .attach_printable("Failed to get redis connection")
+}
+
+async fn fetch_circuit(
+    redis: &RedisConnectionPool,
+    merchant_connector_id: &str,
+) -> RoutingResult<Circuit> {
+    let fields = redis
+        .get_hash_fields::<HashMap<String, String>>(&get_circuit_key(merchant_connector_id))
+        .await
+        .change_context(errors::RoutingError::CircuitBreakerFetchFailed)?;
+
+    Ok(Circuit::from_fields(&fields))
+}
+
+pub async fn get_circuit(state: &AppState, merchant_connector_id: &str) -> RoutingResult<Circuit> {
+    let redis = get_redis_conn(state, errors::RoutingError::CircuitBreakerFetchFailed)?;
+    fetch_circuit(&redis, merchant_connector_id).await
+}
+
+/// Closes the circuit of the merchant connector account on request, without waiting for the
+/// cool-down and probes
+pub async fn force_close_circuit(
+    state: &AppState,
+    merchant_connector_id: &str,
+) -> RoutingResult<()> {
+    let redis = get_redis_conn(state, errors::RoutingError::CircuitBreakerUpdateFailed)?;
+    let circuit = fetch_circuit(&redis, merchant_connector_id).await?;
+    redis
+        .delete_key(&get_circuit_key(merchant_connector_id))
+        .await
+        .change_context(errors::RoutingError::CircuitBreakerUpdateFailed)?;
+
+    if circuit.state != CircuitBreakerState::Closed {
+        record_transition(
+            merchant_connector_id,
+            circuit.state,
+            CircuitBreakerState::Closed,
+        );
+    }
+
+    Ok(())
+}
+
+/// Applies the outcome of a connector call to the circuit, returning its state before and after
+async fn apply_outcome(
+    redis: &RedisConnectionPool,
+    merchant_connector_id: &str,
+    outcome: ConnectorCallOutcome,
+    settings: &CircuitBreakerSettings,
+    now: i64,
+) -> RoutingResult<(CircuitBreakerState, CircuitBreakerState)> {
+    let (from, to): (String, String) = redis
+        .evaluate_redis_script(
+            RECORD_OUTCOME_SCRIPT,
+            vec![get_circuit_key(merchant_connector_id)],
+            vec![
+                outcome.as_script_arg().to_string(),
+                settings.failure_threshold.to_string(),
+                settings.success_threshold.to_string(),
+                now.to_string(),
+                CIRCUIT_TTL_SECS.to_string(),
+            ],
+        )
+        .await
+        .change_context(errors::RoutingError::CircuitBreakerUpdateFailed)?;
+
+    Ok((parse_circuit_state(&from)?, parse_circuit_state(&to)?))
+}
+
+/// Claims a probe of the circuit, returning its state before the claim and whether it succeeded
+async fn claim_probe(
+    redis: &RedisConnectionPool,
+    merchant_connector_id: &str,
+    settings: &CircuitBreakerSettings,
+    now: i64,
+) -> RoutingResult<(CircuitBreakerState, bool)> {
+    let (state, claimed): (String, String) = redis
+        .evaluate_redis_script(
+            CLAIM_PROBE_SCRIPT,
+            vec![get_circuit_key(merchant_connector_id)],
+            vec![
+                now.to_string(),
+                settings.cooldown_secs.to_string(),
+                settings.half_open_probes.to_string(),
+                CIRCUIT_TTL_SECS.to_string(),
+            ],
+        )
+        .await
+        .change_context(errors::RoutingError::CircuitBreakerUpdateFailed)?;
+
+    Ok((parse_circuit_state(&state)?, claimed == "true"))
+}
+
+/// Records the outcome of a call to a connector against the circuit of the merchant connector
+/// account the call was made with
+#[instrument(skip(state))]
+pub async fn record_connector_call_outcome(
+    state: &AppState,
+    merchant_connector_id: &str,
+    outcome: ConnectorCallOutcome,
+) -> RoutingResult<()> {
+    let settings = &state.conf.circuit_breaker;
+    if !settings.enabled {
+        return Ok(());
+    }
+
+    let redis = get_redis_conn(state, errors::RoutingError::CircuitBreakerUpdateFailed)?;
+    let (from, to) = apply_outcome(
+        &redis,
+        merchant_connector_id,
+        outcome,
+        settings,
+        common_utils::date_time::now_unix_timestamp(),
+    )
+    .await?;
+
+    if from != to {
+        record_transition(merchant_connector_id, from, to);
+    }
+
+    Ok(())
+}
+
+/// Records the response of a connector against the circuit of the merchant connector account in
+/// the background. Errors other than timeouts are not the connector's doing, and are not counted.
+pub fn record_connector_response(
+    state: &AppState,
+    merchant_connector_id: Option<&String>,
+    response: &CustomResult<Result<types::Response, types::Response>, errors::ApiClientError>,
+) {
+    let merchant_connector_id = match merchant_connector_id {
+        Some(merchant_connector_id) if state.conf.circuit_breaker.enabled => {
+            merchant_connector_id.clone()
+        }
+        _ => return,
+    };
+    let outcome = match response {
+        Ok(Ok(_)) => ConnectorCallOutcome::Success,
+        Ok(Err(body)) => ConnectorCallOutcome::from_status_code(body.status_code),
+        Err(error) if error.current_context().is_upstream_timeout() => {
+            ConnectorCallOutcome::Failure
+        }
+        Err(_) => return,
+    };
+
+    let state = state.clone();
+    tokio::spawn(
+        async move {
+            record_connector_call_outcome(&state, &merchant_connector_id, outcome)
+                .await
+                .map_err(|error| {
+                    logger::error!(?error, "Failed to record connector circuit breaker outcome")
+                })
+                .ok();
+        }
+        .in_current_span(),
+    );
+}
+
+/// Removes the connectors whose merchant connector account has an open circuit from the final
+/// list of connectors of a payment. The payment is routed to the first of the connectors, so the
+/// probes of a circuit are only claimed if its connector comes first, and the connectors behind it
+/// are left out while they are being probed. Connectors chosen without a merchant connector
+/// account cannot be tracked and are kept, as are the connectors whose circuit cannot be fetched.
+#[instrument(skip_all)]
+pub async fn perform_circuit_breaker_filtering(
+    state: &AppState,
+    chosen: Vec<routing_types::RoutableConnectorChoice>,
+) -> Vec<routing_types::RoutableConnectorChoice> {
+    let settings = &state.conf.circuit_breaker;
+    if !settings.enabled {
+        return chosen;
+    }
+    let redis = match get_redis_conn(state, errors::RoutingError::CircuitBreakerFetchFailed) {
+        Ok(redis) => redis,
+        Err(error) => {
+            logger::error!(?error, "Failed to check connector circuit breakers");
+            return chosen;
+        }
+    };
+    let now = common_utils::date_time::now_unix_timestamp();
+
+    let chosen = chosen
+        .into_iter()
+        .map(|choice| {
+            #[cfg(feature = "connector_choice_mca_id")]
+            let merchant_connector_id = choice.merchant_connector_id.clone();
+            #[cfg(not(feature = "connector_choice_mca_id"))]
+            let merchant_connector_id: Option<String> = None;
+
+            (choice, merchant_connector_id)
+        })
+        .collect::<Vec<_>>();
+    let circuits =
+        futures::future::join_all(chosen.iter().map(|(_, merchant_connector_id)| async {
+            match merchant_connector_id {
+                Some(merchant_connector_id) => {
+                    fetch_circuit(&redis, merchant_connector_id).await.map(Some)
+                }
+                None => Ok(None),
+            }
+        }))
+        .await;
+
+    let mut final_selection = Vec::with_capacity(chosen.len());
+    for ((choice, merchant_connector_id), circuit) in chosen.into_iter().zip(circuits) {
+        let routability = match circuit {
+            Ok(Some(circuit)) => circuit.get_routability(settings, now),
+            Ok(None) => Routability::Routable,
+            Err(error) => {
+                logger::error!(?error, "Failed to check connector circuit breaker");
+                Routability::Routable
+            }
+        };
+
+        let routable = match (routability, merchant_connector_id) {
+            (Routability::Routable, _) => true,
+            (Routability::Probe, Some(merchant_connector_id)) if final_selection.is_empty() => {
+                match claim_probe(&redis, &merchant_connector_id, settings, now).await {
+                    Ok((from, claimed)) => {
+                        if from == CircuitBreakerState::Open && claimed {
+                            record_transition(
+                                &merchant_connector_id,
+                                CircuitBreakerState::Open,
+                                CircuitBreakerState::HalfOpen,
+                            );
+                        }
+                        claimed
+                    }
+                    Err(error) => {
+                        logger::error!(?error, "Failed to claim connector circuit breaker probe");
+                        true
+                    }
+                }
+            }
+            (Routability::Probe, _) | (Routability::Blocked, _) => false,
+        };
+
+        if routable {
+            final_selection.push(choice);
+        } else {
+            metrics::CIRCUIT_BREAKER_FILTERED_CONNECTOR_COUNT.add(
+                &metrics::CONTEXT,
+                1,
+                &[metrics::request::add_attributes(
+                    "connector",
+                    choice.connector.to_string(),
+                )],
+            );
+        }
+    }
+
+    final_selection
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+    use redis_interface::RedisSettings;
+
+    use super::*;
+
+    fn settings() -> CircuitBreakerSettings {
+        CircuitBreakerSettings {
+            enabled: true,
+            failure_threshold: 3,
+            cooldown_secs: 60,
+            half_open_probes: 2,
+            success_threshold: 2,
+        }
+    }
+
+    #[test]
+    fn test_script_states_match_circuit_states() {
+        assert_eq!(CircuitBreakerState::Closed.to_string(), "closed");
+        assert_eq!(CircuitBreakerState::Open.to_string(), "open");
+        assert_eq!(CircuitBreakerState::HalfOpen.to_string(), "half_open");
+    }
+
+    #[test]
+    fn test_circuit_routability() {
+        assert_eq!(
+            Circuit::default().get_routability(&settings(), 1_000),
+            Routability::Routable
+        );
+
+        let open_circuit = Circuit {
+            state: CircuitBreakerState::Open,
+            opened_at: Some(1_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            open_circuit.get_routability(&settings(), 1_059),
+            Routability::Blocked
+        );
+        assert_eq!(
+            open_circuit.get_routability(&settings(), 1_060),
+            Routability::Probe
+        );
+
+        let half_open_circuit = Circuit {
+            state: CircuitBreakerState::HalfOpen,
+            probe_window_started_at: Some(1_000),
+            probes: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            half_open_circuit.get_routability(&settings(), 1_030),
+            Routability::Blocked
+        );
+        assert_eq!(
+            half_open_circuit.get_routability(&settings(), 1_060),
+            Routability::Probe
+        );
+    }
+
+    #[test]
+    fn test_circuit_cool_down() {
+        let circuit = Circuit {
+            state: CircuitBreakerState::Open,
+            opened_at: Some(1_000),
+            ..Default::default()
+        };
+        assert!(!circuit.has_cooled_down(&settings(), 1_059));
+        assert!(circuit.has_cooled_down(&settings(), 1_060));
+    }
+
+    #[test]
+    fn test_circuit_parsed_from_fields() {
+        let fields = HashMap::from([
+            (STATE_FIELD.to_string(), "half_open".to_string()),
+            (FAILURES_FIELD.to_string(), "4".to_string()),
+            (OPENED_AT_FIELD.to_string(), "1000".to_string()),
+        ]);
+
+        assert_eq!(
+            Circuit::from_fields(&fields),
+            Circuit {
+                state: CircuitBreakerState::HalfOpen,
+                failures: 4,
+                opened_at: Some(1_000),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_circuit_lifecycle() {
+        let transitions = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                let redis = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                let id = "mca_circuit_breaker_lifecycle";
+                let _ = redis.delete_key(&get_circuit_key(id)).await;
+                let settings = settings();
+                let mut transitions = Vec::new();
+
+                // Consecutive failures open the circuit
+                for _ in 0..3 {
+                    transitions.push(
+                        apply_outcome(&redis, id, ConnectorCallOutcome::Failure, &settings, 1_000)
+                            .await
+                            .unwrap(),
+                    );
+                }
+                let opened = fetch_circuit(&redis, id).await.unwrap();
+
+                // No probe before the cool-down, then as many probes as allowed
+                let claims = [
+                    claim_probe(&redis, id, &settings, 1_030).await.unwrap(),
+                    claim_probe(&redis, id, &settings, 1_060).await.unwrap(),
+                    claim_probe(&redis, id, &settings, 1_061).await.unwrap(),
+                    claim_probe(&redis, id, &settings, 1_062).await.unwrap(),
+                ];
+
+                // Enough successful probes close the circuit
+                for _ in 0..2 {
+                    transitions.push(
+                        apply_outcome(&redis, id, ConnectorCallOutcome::Success, &settings, 1_063)
+                            .await
+                            .unwrap(),
+                    );
+                }
+                let closed = fetch_circuit(&redis, id).await.unwrap();
+
+                (transitions, opened, claims, closed)
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+        let (transitions, opened, claims, closed) = transitions;
+
+        use CircuitBreakerState::{Closed, HalfOpen, Open};
+        assert_eq!(
+            transitions,
+            vec![
+                (Closed, Closed),
+                (Closed, Closed),
+                (Closed, Open),
+                (HalfOpen, HalfOpen),
+                (HalfOpen, Closed)
+            ]
+        );
+        assert_eq!(opened.state, Open);
+        assert_eq!(opened.opened_at, Some(1_000));
+        assert_eq!(
+            claims,
+            [
+                (Open, false),
+                (Open, true),
+                (HalfOpen, true),
+                (HalfOpen, false)
+            ]
+        );
+        assert_eq!(closed, Circuit::default());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_failures_are_all_counted() {
+        let circuit = tokio::task::spawn_blocking(move || {
+            futures::executor::block_on(async {
+                let redis = RedisConnectionPool::new(&RedisSettings::default())
+                    .await
+                    .expect("failed to create redis connection pool");
+                let id = "mca_circuit_breaker_concurrent";
+                let _ = redis.delete_key(&get_circuit_key(id)).await;
+                let settings = CircuitBreakerSettings {
+                    failure_threshold: 100,
+                    ..settings()
+                };
+
+                futures::future::join_all((0..20).map(|_| {
+                    apply_outcome(&redis, id, ConnectorCallOutcome::Failure, &settings, 1_000)
+                }))
+                .await;
+                fetch_circuit(&redis, id).await.unwrap()
+            })
+        })
+        .await
+        .expect("Spawn block failure");
+
+        assert_eq!(circuit.failures, 20);
+    }
+}
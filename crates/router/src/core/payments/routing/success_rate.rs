@@ -0,0 +1,403 @@
+//! Success rate based routing orders connectors by their authorization rate over a sliding
+//! window. The window is made up of fixed size buckets stored in Redis, each bucket being a hash
+//! holding the number of attempts and authorizations of every connector for a given merchant,
+//! profile, payment method and currency.
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use api_models::routing::{self as routing_types, SUCCESS_RATE_MAX_WINDOW_MINUTES};
+use diesel_models::enums as storage_enums;
+use error_stack::ResultExt;
+use rand::Rng;
+use router_env::{instrument, tracing};
+use tracing_futures::Instrument;
+
+use super::RoutingResult;
+use crate::{
+    core::{errors, payments as payments_oss},
+    logger,
+    types::storage as oss_storage,
+    AppState,
+};
+
+/// The size of the buckets the sliding window is made up of, in seconds
+const BUCKET_SIZE_SECS: i64 = 300;
+const ATTEMPTS_FIELD_SUFFIX: &str = ":attempts";
+const AUTHORIZED_FIELD_SUFFIX: &str = ":authorized";
+
+/// Identifies the authorization rate statistics a payment contributes to and is routed with
+#[derive(Clone, Debug)]
+pub struct SuccessRateKey {
+    merchant_id: String,
+    profile_id: Option<String>,
+    payment_method: Option<storage_enums::PaymentMethod>,
+    currency: storage_enums::Currency,
+}
+
+impl SuccessRateKey {
+    pub fn from_payment_data<F: Clone>(payment_data: &payments_oss::PaymentData<F>) -> Self {
+        Self {
+            merchant_id: payment_data.payment_attempt.merchant_id.clone(),
+            profile_id: payment_data.payment_intent.profile_id.clone(),
+            payment_method: payment_data.payment_attempt.payment_method,
+            currency: payment_data.currency,
+        }
+    }
+
+    fn get_bucket_key(&self, bucket: i64) -> String {
+        format!(
+            "success_rate_{}_{}_{}_{}_{bucket}",
+            self.merchant_id,
+            self.profile_id.as_deref().unwrap_or_default(),
+            self.payment_method
+                .map(|payment_method| payment_method.to_string())
+                .unwrap_or_default(),
+            self.currency,
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConnectorStatistics {
+    pub attempts: u64,
+    pub authorized: u64,
+}
+
+impl ConnectorStatistics {
+    // Precision loss is acceptable here, the counts are bounded by the window
+    #[allow(clippy::as_conversions)]
+    fn get_authorization_rate(&self, min_attempts: u32) -> Option<f64> {
+        (self.attempts > 0 && self.attempts >= u64::from(min_attempts))
+            .then(|| self.authorized as f64 / self.attempts as f64)
+    }
+}
+
+/// The label the statistics of a connector choice are recorded under
+fn get_choice_connector_label(choice: &routing_types::RoutableConnectorChoice) -> String {
+    choice.to_string()
+}
+
+/// The label the statistics of the connector a payment attempt was routed through are recorded
+/// under, matching [`get_choice_connector_label`]
+fn get_attempt_connector_label(payment_attempt: &oss_storage::PaymentAttempt) -> Option<String> {
+    let connector = payment_attempt.connector.clone()?;
+
+    #[cfg(not(feature = "connector_choice_mca_id"))]
+    if let Some(ref sub_label) = payment_attempt.business_sub_label {
+        return Some(format!("{connector}_{sub_label}"));
+    }
+
+    Some(connector)
+}
+
+/// Whether the attempt was authorized, if the status change is the outcome of its authorization.
+/// Attempts which already had an outcome, such as authorized attempts being captured, are not
+/// counted again.
+pub fn get_authorization_outcome(
+    previous_status: storage_enums::AttemptStatus,
+    current_status: storage_enums::AttemptStatus,
+) -> Option<bool> {
+    let outcome = |status| match status {
+        storage_enums::AttemptStatus::Authorized
+        | storage_enums::AttemptStatus::Charged
+        | storage_enums::AttemptStatus::PartialCharged
+        | storage_enums::AttemptStatus::PartialChargedAndChargeable
+        | storage_enums::AttemptStatus::CaptureInitiated => Some(true),
+        storage_enums::AttemptStatus::AuthorizationFailed
+        | storage_enums::AttemptStatus::Failure => Some(false),
+        _ => None,
+    };
+
+    match outcome(previous_status) {
+        Some(_) => None,
+        None => outcome(current_status),
+    }
+}
+
+/// Records the outcome of the authorization of the payment attempt, if it has one, in the
+/// background. Failures are logged and do not affect the payment.
+pub fn record_attempt_outcome<F: Clone>(
+    state: &AppState,
+    payment_data: &payments_oss::PaymentData<F>,
+    previous_status: storage_enums::AttemptStatus,
+) {
+    let is_authorized =
+        match get_authorization_outcome(previous_status, payment_data.payment_attempt.status) {
+            Some(is_authorized) => is_authorized,
+            None => return,
+        };
+    let connector_label = match get_attempt_connector_label(&payment_data.payment_attempt) {
+        Some(connector_label) => connector_label,
+        None => return,
+    };
+
+    let state = state.clone();
+    let key = SuccessRateKey::from_payment_data(payment_data);
+    tokio::spawn(
+        async move {
+            record_authorization_outcome(&state, &key, &connector_label, is_authorized)
+                .await
+                .map_err(|error| {
+                    logger::error!(
+                        ?error,
+                        "Failed to record authorization outcome for success rate based routing"
+                    )
+                })
+                .ok();
+        }
+        .in_current_span(),
+    );
+}
+
+#[instrument(skip(state))]
+pub async fn record_authorization_outcome(
+    state: &AppState,
+    key: &SuccessRateKey,
+    connector_label: &str,
+    is_authorized: bool,
+) -> RoutingResult<()> {
+    let redis = state
+        .store
+        .get_redis_conn()
+        .change_context(errors::RoutingError::SuccessRateRecordFailed)
+        .attach_printable("Failed to get redis connection")?;
+
+    let bucket_key =
+        key.get_bucket_key(common_utils::date_time::now_unix_timestamp() / BUCKET_SIZE_SECS);
+    // Buckets are kept for as long as the longest window can span them
+    let ttl = i64::from(SUCCESS_RATE_MAX_WINDOW_MINUTES) * 60 + BUCKET_SIZE_SECS;
+
+    redis
+        .increment_field_in_hash(
+            &bucket_key,
+            &format!("{connector_label}{ATTEMPTS_FIELD_SUFFIX}"),
+            1,
+            Some(ttl),
+        )
+        .await
+        .change_context(errors::RoutingError::SuccessRateRecordFailed)?;
+
+    if is_authorized {
+        redis
+            .increment_field_in_hash(
+                &bucket_key,
+                &format!("{connector_label}{AUTHORIZED_FIELD_SUFFIX}"),
+                1,
+                Some(ttl),
+            )
+            .await
+            .change_context(errors::RoutingError::SuccessRateRecordFailed)?;
+    }
+
+    Ok(())
+}
+
+async fn get_window_statistics(
+    state: &AppState,
+    key: &SuccessRateKey,
+    window_minutes: u16,
+) -> RoutingResult<HashMap<String, ConnectorStatistics>> {
+    let redis = state
+        .store
+        .get_redis_conn()
+        .change_context(errors::RoutingError::SuccessRateFetchFailed)
+        .attach_printable("Failed to get redis connection")?;
+
+    let current_bucket = common_utils::date_time::now_unix_timestamp() / BUCKET_SIZE_SECS;
+    let bucket_count = (i64::from(window_minutes) * 60 + BUCKET_SIZE_SECS - 1) / BUCKET_SIZE_SECS;
+    let bucket_keys = ((current_bucket - bucket_count + 1)..=current_bucket)
+        .map(|bucket| key.get_bucket_key(bucket))
+        .collect::<Vec<_>>();
+
+    let buckets = futures::future::try_join_all(
+        bucket_keys
+            .iter()
+            .map(|bucket_key| redis.get_hash_fields::<HashMap<String, u64>>(bucket_key)),
+    )
+    .await
+    .change_context(errors::RoutingError::SuccessRateFetchFailed)?;
+
+    let mut statistics = HashMap::<String, ConnectorStatistics>::new();
+    for (field, count) in buckets.into_iter().flatten() {
+        if let Some(connector_label) = field.strip_suffix(ATTEMPTS_FIELD_SUFFIX) {
+            statistics
+                .entry(connector_label.to_string())
+                .or_default()
+                .attempts += count;
+        } else if let Some(connector_label) = field.strip_suffix(AUTHORIZED_FIELD_SUFFIX) {
+            statistics
+                .entry(connector_label.to_string())
+                .or_default()
+                .authorized += count;
+        }
+    }
+
+    Ok(statistics)
+}
+
+/// Orders the connectors of the algorithm by their authorization rate, best first. Connectors
+/// without enough attempts in the window keep their configured order, after the ranked ones. When
+/// `explored_index` is set, the connector at that position is moved to the front instead.
+fn order_connectors(
+    algorithm: &routing_types::SuccessRateBasedAlgorithm,
+    statistics: &HashMap<String, ConnectorStatistics>,
+    explored_index: Option<usize>,
+) -> Vec<routing_types::RoutableConnectorChoice> {
+    let mut ranked = algorithm
+        .connectors
+        .iter()
+        .map(|choice| {
+            let authorization_rate = statistics
+                .get(&get_choice_connector_label(choice))
+                .and_then(|stats| stats.get_authorization_rate(algorithm.min_attempts));
+            (choice.clone(), authorization_rate)
+        })
+        .collect::<Vec<_>>();
+
+    // The sort is stable, so connectors with equal rates keep their configured order
+    ranked.sort_by(|(_, a), (_, b)| match (a, b) {
+        (Some(a), Some(b)) => b.partial_cmp(a).unwrap_or(Ordering::Equal),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+
+    let mut connectors = ranked
+        .into_iter()
+        .map(|(choice, _)| choice)
+        .collect::<Vec<_>>();
+
+    if let Some(index) = explored_index.filter(|index| *index < connectors.len()) {
+        let explored = connectors.remove(index);
+        connectors.insert(0, explored);
+    }
+
+    connectors
+}
+
+/// Performs success rate based routing. The statistics are best effort: if they cannot be
+/// fetched, the connectors are routed to in their configured order.
+#[instrument(skip_all)]
+pub async fn perform_success_rate_routing(
+    state: &AppState,
+    key: &SuccessRateKey,
+    algorithm: &routing_types::SuccessRateBasedAlgorithm,
+) -> Vec<routing_types::RoutableConnectorChoice> {
+    let statistics = get_window_statistics(state, key, algorithm.window_minutes)
+        .await
+        .map_err(|error| {
+            logger::error!(
+                ?error,
+                "Failed to fetch connector statistics for success rate based routing"
+            )
+        })
+        .unwrap_or_default();
+
+    let mut rng = rand::thread_rng();
+    let explored_index = (rng.gen_range(0..100) < algorithm.exploration_percent)
+        .then(|| rng.gen_range(0..algorithm.connectors.len().max(1)));
+
+    order_connectors(algorithm, &statistics, explored_index)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn choice(
+        connector: api_models::enums::RoutableConnectors,
+    ) -> routing_types::RoutableConnectorChoice {
+        serde_json::from_value(serde_json::json!({ "connector": connector })).unwrap()
+    }
+
+    fn algorithm(min_attempts: u32) -> routing_types::SuccessRateBasedAlgorithm {
+        routing_types::SuccessRateBasedAlgorithm {
+            connectors: vec![
+                choice(api_models::enums::RoutableConnectors::Stripe),
+                choice(api_models::enums::RoutableConnectors::Adyen),
+                choice(api_models::enums::RoutableConnectors::Checkout),
+            ],
+            window_minutes: 60,
+            min_attempts,
+            exploration_percent: 10,
+        }
+    }
+
+    fn labels(connectors: Vec<routing_types::RoutableConnectorChoice>) -> Vec<String> {
+        connectors.iter().map(get_choice_connector_label).collect()
+    }
+
+    #[test]
+    fn test_connectors_ordered_by_authorization_rate() {
+        let statistics = HashMap::from([
+            (
+                "stripe".to_string(),
+                ConnectorStatistics {
+                    attempts: 100,
+                    authorized: 70,
+                },
+            ),
+            (
+                "adyen".to_string(),
+                ConnectorStatistics {
+                    attempts: 100,
+                    authorized: 90,
+                },
+            ),
+            (
+                "checkout".to_string(),
+                ConnectorStatistics {
+                    attempts: 5,
+                    authorized: 5,
+                },
+            ),
+        ]);
+
+        assert_eq!(
+            labels(order_connectors(&algorithm(20), &statistics, None)),
+            vec!["adyen", "stripe", "checkout"]
+        );
+    }
+
+    #[test]
+    fn test_configured_order_kept_without_statistics() {
+        assert_eq!(
+            labels(order_connectors(&algorithm(20), &HashMap::new(), None)),
+            vec!["stripe", "adyen", "checkout"]
+        );
+    }
+
+    #[test]
+    fn test_explored_connector_moved_to_front() {
+        assert_eq!(
+            labels(order_connectors(&algorithm(20), &HashMap::new(), Some(2))),
+            vec!["checkout", "stripe", "adyen"]
+        );
+    }
+
+    #[test]
+    fn test_authorization_outcome_counted_once() {
+        use storage_enums::AttemptStatus;
+
+        assert_eq!(
+            get_authorization_outcome(AttemptStatus::Pending, AttemptStatus::Charged),
+            Some(true)
+        );
+        assert_eq!(
+            get_authorization_outcome(
+                AttemptStatus::AuthenticationPending,
+                AttemptStatus::AuthorizationFailed
+            ),
+            Some(false)
+        );
+        assert_eq!(
+            get_authorization_outcome(AttemptStatus::Authorized, AttemptStatus::Charged),
+            None
+        );
+        assert_eq!(
+            get_authorization_outcome(AttemptStatus::Started, AttemptStatus::Pending),
+            None
+        );
+    }
+}
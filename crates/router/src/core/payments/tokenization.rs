@@ -97,7 +97,10 @@ where
 
                 if is_duplicate {
                     let existing_pm = db
-                        .find_payment_method(&locker_response.0.payment_method_id)
+                        .find_payment_method(
+                            &locker_response.0.payment_method_id,
+                            merchant_account.storage_scheme,
+                        )
                         .await;
                     match existing_pm {
                         Ok(pm) => {
@@ -106,10 +109,15 @@ where
                                 connector_token,
                             )?;
                             if let Some(metadata) = pm_metadata {
-                                payment_methods::cards::update_payment_method(db, pm, metadata)
-                                    .await
-                                    .change_context(errors::ApiErrorResponse::InternalServerError)
-                                    .attach_printable("Failed to add payment method in db")?;
+                                payment_methods::cards::update_payment_method(
+                                    db,
+                                    pm,
+                                    metadata,
+                                    merchant_account.storage_scheme,
+                                )
+                                .await
+                                .change_context(errors::ApiErrorResponse::InternalServerError)
+                                .attach_printable("Failed to add payment method in db")?;
                             };
                         }
                         Err(error) => {
@@ -129,6 +137,7 @@ where
                                             pm_metadata,
                                             pm_data_encrypted,
                                             key_store,
+                                            merchant_account.storage_scheme,
                                         )
                                         .await
                                     }
@@ -155,6 +164,7 @@ where
                         pm_metadata,
                         pm_data_encrypted,
                         key_store,
+                        merchant_account.storage_scheme,
                     )
                     .await?;
                 };
@@ -166,6 +166,7 @@ where
         external_latency: None,
         apple_pay_flow,
         frm_metadata: None,
+        merchant_connector_id: merchant_connector_account.get_mca_id(),
     };
 
     Ok(router_data)
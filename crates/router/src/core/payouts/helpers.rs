@@ -259,6 +259,7 @@ pub async fn save_payout_data_to_locker(
         None,
         card_details_encrypted,
         key_store,
+        merchant_account.storage_scheme,
     )
     .await?;
 
@@ -279,7 +280,12 @@ pub async fn get_or_create_customer_details(
     let key = key_store.key.get_inner().peek();
 
     match db
-        .find_customer_optional_by_customer_id_merchant_id(&customer_id, merchant_id, key_store)
+        .find_customer_optional_by_customer_id_merchant_id(
+            &customer_id,
+            merchant_id,
+            key_store,
+            merchant_account.storage_scheme,
+        )
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)?
     {
@@ -311,7 +317,7 @@ pub async fn get_or_create_customer_details(
             };
 
             Ok(Some(
-                db.insert_customer(customer, key_store)
+                db.insert_customer(customer, key_store, merchant_account.storage_scheme)
                     .await
                     .change_context(errors::ApiErrorResponse::InternalServerError)?,
             ))
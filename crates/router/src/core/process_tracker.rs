@@ -0,0 +1,381 @@
+use api_models::process_tracker::{
+    ProcessTrackerFinishRequest, ProcessTrackerListConstraints, ProcessTrackerListResponse,
+    ProcessTrackerRescheduleRequest, ProcessTrackerResponse,
+};
+use diesel_models::enums::ProcessTrackerStatus;
+use error_stack::{report, ResultExt};
+use router_env::{instrument, tracing};
+
+use crate::{
+    core::errors::{self, RouterResponse, RouterResult},
+    db::StorageInterface,
+    services::ApplicationResponse,
+    types::{storage, transformers::ForeignInto},
+    AppState,
+};
+
+/// Business status recorded on the tasks finished through the admin API
+const CANCELLED_BY_ADMIN: &str = "CANCELLED_BY_ADMIN";
+
+/// Business status the consumer expects on the tasks it picks up
+const PENDING: &str = "Pending";
+
+/// The number of tasks listed when the request does not limit them
+const DEFAULT_LIST_LIMIT: i64 = 100;
+
+/// The statuses of the tasks the scheduler does not hold, see [`ensure_not_in_flight`]
+const NOT_IN_FLIGHT_STATUSES: [ProcessTrackerStatus; 3] = [
+    ProcessTrackerStatus::New,
+    ProcessTrackerStatus::Pending,
+    ProcessTrackerStatus::Finish,
+];
+
+#[instrument(skip(state))]
+pub async fn list_processes(
+    state: AppState,
+    constraints: ProcessTrackerListConstraints,
+) -> RouterResponse<ProcessTrackerListResponse> {
+    let mut constraints: storage::ProcessTrackerListConstraints = constraints.foreign_into();
+    constraints.limit = Some(constraints.limit.unwrap_or(DEFAULT_LIST_LIMIT));
+
+    let processes = state
+        .store
+        .filter_processes_by_constraints(constraints)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list process tracker tasks")?;
+
+    Ok(ApplicationResponse::Json(ProcessTrackerListResponse {
+        count: processes.len(),
+        data: processes
+            .into_iter()
+            .map(ForeignInto::foreign_into)
+            .collect(),
+    }))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_process(
+    state: AppState,
+    process_tracker_id: String,
+) -> RouterResponse<ProcessTrackerResponse> {
+    let process = find_process(state.store.as_ref(), &process_tracker_id).await?;
+
+    Ok(ApplicationResponse::Json(process.foreign_into()))
+}
+
+/// Queues the task to run as soon as the producer picks it up, keeping its retry count.
+#[instrument(skip(state))]
+pub async fn retry_process(
+    state: AppState,
+    process_tracker_id: String,
+) -> RouterResponse<ProcessTrackerResponse> {
+    let db = state.store.as_ref();
+    let process = find_process(db, &process_tracker_id).await?;
+    ensure_not_in_flight(&process)?;
+
+    update_process(
+        db,
+        process,
+        NOT_IN_FLIGHT_STATUSES.to_vec(),
+        storage::ProcessTrackerUpdate::Update {
+            name: None,
+            retry_count: None,
+            schedule_time: Some(common_utils::date_time::now()),
+            tracking_data: None,
+            business_status: Some(PENDING.to_string()),
+            status: Some(ProcessTrackerStatus::Pending),
+            updated_at: Some(common_utils::date_time::now()),
+        },
+    )
+    .await
+}
+
+#[instrument(skip(state))]
+pub async fn reschedule_process(
+    state: AppState,
+    request: ProcessTrackerRescheduleRequest,
+) -> RouterResponse<ProcessTrackerResponse> {
+    let db = state.store.as_ref();
+    let process = find_process(db, &request.process_tracker_id).await?;
+    ensure_not_in_flight(&process)?;
+
+    if process.status == ProcessTrackerStatus::Finish {
+        return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: "Finished tasks cannot be rescheduled, retry the task instead".to_string(),
+        }));
+    }
+
+    update_process(
+        db,
+        process,
+        vec![ProcessTrackerStatus::New, ProcessTrackerStatus::Pending],
+        storage::ProcessTrackerUpdate::Update {
+            name: None,
+            retry_count: None,
+            schedule_time: Some(request.schedule_time),
+            tracking_data: None,
+            business_status: None,
+            status: None,
+            updated_at: Some(common_utils::date_time::now()),
+        },
+    )
+    .await
+}
+
+#[instrument(skip(state))]
+pub async fn finish_process(
+    state: AppState,
+    request: ProcessTrackerFinishRequest,
+) -> RouterResponse<ProcessTrackerResponse> {
+    let db = state.store.as_ref();
+    let process = find_process(db, &request.process_tracker_id).await?;
+    ensure_not_in_flight(&process)?;
+
+    update_process(
+        db,
+        process,
+        NOT_IN_FLIGHT_STATUSES.to_vec(),
+        storage::ProcessTrackerUpdate::StatusUpdate {
+            status: ProcessTrackerStatus::Finish,
+            business_status: Some(
+                request
+                    .business_status
+                    .unwrap_or_else(|| CANCELLED_BY_ADMIN.to_string()),
+            ),
+        },
+    )
+    .await
+}
+
+async fn find_process(
+    db: &dyn StorageInterface,
+    process_tracker_id: &str,
+) -> RouterResult<storage::ProcessTracker> {
+    db.find_process_by_id(process_tracker_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch process tracker task")?
+        .ok_or_else(|| {
+            report!(errors::ApiErrorResponse::GenericNotFoundError {
+                message: format!("Process tracker task {process_tracker_id} does not exist"),
+            })
+        })
+}
+
+/// Tasks picked by the producer or a consumer are owned by the scheduler until the consumer
+/// updates them, or the cleaner requeues them once their lease expires.
+pub(crate) fn ensure_not_in_flight(process: &storage::ProcessTracker) -> RouterResult<()> {
+    match process.status {
+        ProcessTrackerStatus::Processing | ProcessTrackerStatus::ProcessStarted => {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "Process tracker task {} is being processed by the scheduler",
+                    process.id
+                ),
+            }))
+        }
+        ProcessTrackerStatus::New
+        | ProcessTrackerStatus::Pending
+        | ProcessTrackerStatus::Finish => Ok(()),
+    }
+}
+
+/// Updates the task only if it still has one of the `expected_statuses` it was checked against,
+/// failing if the scheduler picked it up in the meantime.
+async fn update_process(
+    db: &dyn StorageInterface,
+    process: storage::ProcessTracker,
+    expected_statuses: Vec<ProcessTrackerStatus>,
+    process_update: storage::ProcessTrackerUpdate,
+) -> RouterResponse<ProcessTrackerResponse> {
+    let process_tracker_id = process.id.clone();
+    let updated_process = db
+        .update_process_if_status_in(process, expected_statuses, process_update)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to update process tracker task")?
+        .ok_or_else(|| {
+            report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "Process tracker task {process_tracker_id} was picked up by the scheduler, try again later"
+                ),
+            })
+        })?;
+
+    Ok(ApplicationResponse::Json(updated_process.foreign_into()))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use super::*;
+    use crate::utils::test_utils::get_mock_state;
+
+    async fn insert_task(
+        state: &AppState,
+        id: &str,
+        status: ProcessTrackerStatus,
+    ) -> storage::ProcessTracker {
+        let now = common_utils::date_time::now();
+        state
+            .store
+            .insert_process(storage::ProcessTrackerNew {
+                id: id.to_string(),
+                name: Some("TEST_TASK".to_string()),
+                tag: vec![],
+                runner: Some("TEST_WORKFLOW".to_string()),
+                retry_count: 0,
+                schedule_time: Some(now),
+                rule: String::new(),
+                tracking_data: serde_json::json!({}),
+                business_status: PENDING.to_string(),
+                status,
+                event: vec![],
+                created_at: now,
+                updated_at: now,
+            })
+            .await
+            .unwrap()
+    }
+
+    fn get_response(response: RouterResponse<ProcessTrackerResponse>) -> ProcessTrackerResponse {
+        match response.unwrap() {
+            ApplicationResponse::Json(process) => process,
+            _ => panic!("Unexpected response"),
+        }
+    }
+
+    fn is_precondition_failed(response: RouterResponse<ProcessTrackerResponse>) -> bool {
+        matches!(
+            response.unwrap_err().current_context(),
+            errors::ApiErrorResponse::PreconditionFailed { .. }
+        )
+    }
+
+    #[actix_rt::test]
+    async fn test_list_processes_is_limited_by_default() {
+        let state = get_mock_state().await;
+        for index in 0..=DEFAULT_LIST_LIMIT {
+            insert_task(&state, &format!("task_{index}"), ProcessTrackerStatus::New).await;
+        }
+
+        let response = list_processes(state, ProcessTrackerListConstraints::default())
+            .await
+            .unwrap();
+
+        match response {
+            ApplicationResponse::Json(processes) => {
+                assert_eq!(
+                    processes.count,
+                    usize::try_from(DEFAULT_LIST_LIMIT).unwrap()
+                )
+            }
+            _ => panic!("Unexpected response"),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_finish_and_retry_process() {
+        let state = get_mock_state().await;
+        insert_task(&state, "task", ProcessTrackerStatus::Pending).await;
+
+        let finished = get_response(
+            finish_process(
+                state.clone(),
+                ProcessTrackerFinishRequest {
+                    process_tracker_id: "task".to_string(),
+                    business_status: None,
+                },
+            )
+            .await,
+        );
+        assert_eq!(finished.status, ProcessTrackerStatus::Finish);
+        assert_eq!(finished.business_status, CANCELLED_BY_ADMIN);
+
+        assert!(is_precondition_failed(
+            reschedule_process(
+                state.clone(),
+                ProcessTrackerRescheduleRequest {
+                    process_tracker_id: "task".to_string(),
+                    schedule_time: common_utils::date_time::now(),
+                },
+            )
+            .await
+        ));
+
+        let retried = get_response(retry_process(state, "task".to_string()).await);
+        assert_eq!(retried.status, ProcessTrackerStatus::Pending);
+        assert_eq!(retried.business_status, PENDING);
+    }
+
+    #[actix_rt::test]
+    async fn test_in_flight_process_is_not_changed() {
+        let state = get_mock_state().await;
+        insert_task(&state, "task", ProcessTrackerStatus::ProcessStarted).await;
+
+        assert!(is_precondition_failed(
+            retry_process(state.clone(), "task".to_string()).await
+        ));
+        assert!(is_precondition_failed(
+            finish_process(
+                state.clone(),
+                ProcessTrackerFinishRequest {
+                    process_tracker_id: "task".to_string(),
+                    business_status: None,
+                },
+            )
+            .await
+        ));
+
+        let process = state
+            .store
+            .find_process_by_id("task")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(process.status, ProcessTrackerStatus::ProcessStarted);
+    }
+
+    #[actix_rt::test]
+    async fn test_process_picked_up_after_the_check_is_not_changed() {
+        let state = get_mock_state().await;
+        let process = insert_task(&state, "task", ProcessTrackerStatus::Pending).await;
+        ensure_not_in_flight(&process).unwrap();
+
+        // The producer picks the task up between the check and the update
+        state
+            .store
+            .process_tracker_update_process_status_by_ids(
+                vec!["task".to_string()],
+                storage::ProcessTrackerUpdate::StatusUpdate {
+                    status: ProcessTrackerStatus::Processing,
+                    business_status: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!(is_precondition_failed(
+            update_process(
+                state.store.as_ref(),
+                process,
+                NOT_IN_FLIGHT_STATUSES.to_vec(),
+                storage::ProcessTrackerUpdate::StatusUpdate {
+                    status: ProcessTrackerStatus::Finish,
+                    business_status: Some(CANCELLED_BY_ADMIN.to_string()),
+                },
+            )
+            .await
+        ));
+
+        let process = state
+            .store
+            .find_process_by_id("task")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(process.status, ProcessTrackerStatus::Processing);
+    }
+}
@@ -1,13 +1,22 @@
+pub mod diff;
 pub mod helpers;
 pub mod transformers;
 
+#[cfg(feature = "olap")]
+use std::collections::BTreeMap;
+
 use api_models::routing::{self as routing_types, RoutingAlgorithmId};
 #[cfg(feature = "business_profile_routing")]
 use api_models::routing::{RoutingRetrieveLinkQuery, RoutingRetrieveQuery};
 #[cfg(not(feature = "business_profile_routing"))]
-use common_utils::ext_traits::{Encode, StringExt};
+use common_utils::ext_traits::StringExt;
+#[cfg(feature = "olap")]
+use data_models::payments::payment_intent::{
+    PaymentIntentFetchConstraints, PaymentIntentListParams,
+};
 #[cfg(not(feature = "business_profile_routing"))]
 use diesel_models::configs;
+use diesel_models::enums as storage_enums;
 #[cfg(feature = "business_profile_routing")]
 use diesel_models::routing_algorithm::RoutingAlgorithm;
 use error_stack::{IntoReport, ResultExt};
@@ -18,15 +27,19 @@ use crate::types::transformers::{ForeignInto, ForeignTryInto};
 use crate::{
     consts,
     core::{
-        errors::{RouterResponse, StorageErrorExt},
-        metrics, utils as core_utils,
+        errors::{RouterResponse, RouterResult, StorageErrorExt},
+        metrics,
+        payments::routing as payments_routing,
+        utils as core_utils,
     },
+    db::StorageInterface,
+    logger,
     routes::AppState,
-    types::domain,
+    types::{domain, transformers::ForeignFrom},
     utils::{self, OptionExt, ValueExt},
 };
 #[cfg(not(feature = "business_profile_routing"))]
-use crate::{core::errors, services::api as service_api, types::storage};
+use crate::{core::errors, services::api as service_api};
 #[cfg(feature = "business_profile_routing")]
 use crate::{errors, services::api as service_api};
 
@@ -212,8 +225,15 @@ pub async fn create_routing_config(
 
         if records_are_empty {
             merchant_dictionary.active_id = Some(algorithm_id.clone());
-            algorithm_ref.update_algorithm_id(algorithm_id);
-            helpers::update_merchant_active_algorithm_ref(db, &key_store, algorithm_ref).await?;
+            algorithm_ref.update_algorithm_id(algorithm_id.clone());
+            helpers::activate_merchant_algorithm_ref(
+                db,
+                &key_store,
+                algorithm_ref,
+                None,
+                storage_enums::RoutingActivationAction::Activate,
+            )
+            .await?;
         }
 
         helpers::update_merchant_routing_dictionary(
@@ -235,6 +255,27 @@ pub async fn link_routing_config(
     algorithm_id: String,
 ) -> RouterResponse<routing_types::RoutingDictionaryRecord> {
     metrics::ROUTING_LINK_CONFIG.add(&metrics::CONTEXT, 1, &[]);
+    let response = activate_routing_algorithm(
+        &state,
+        &merchant_account,
+        #[cfg(not(feature = "business_profile_routing"))]
+        &key_store,
+        algorithm_id,
+        storage_enums::RoutingActivationAction::Activate,
+    )
+    .await?;
+
+    metrics::ROUTING_LINK_CONFIG_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
+    Ok(service_api::ApplicationResponse::Json(response))
+}
+
+async fn activate_routing_algorithm(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    #[cfg(not(feature = "business_profile_routing"))] key_store: &domain::MerchantKeyStore,
+    algorithm_id: String,
+    action: storage_enums::RoutingActivationAction,
+) -> RouterResult<routing_types::RoutingDictionaryRecord> {
     let db = state.store.as_ref();
     #[cfg(feature = "business_profile_routing")]
     {
@@ -276,14 +317,18 @@ pub async fn link_routing_config(
             },
         )?;
 
+        let previous_algorithm_id = routing_ref.algorithm_id.clone();
         routing_ref.update_algorithm_id(algorithm_id);
-        helpers::update_business_profile_active_algorithm_ref(db, business_profile, routing_ref)
-            .await?;
+        helpers::activate_business_profile_algorithm_ref(
+            db,
+            business_profile,
+            routing_ref,
+            previous_algorithm_id,
+            action,
+        )
+        .await?;
 
-        metrics::ROUTING_LINK_CONFIG_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
-        Ok(service_api::ApplicationResponse::Json(
-            routing_algorithm.foreign_into(),
-        ))
+        Ok(routing_algorithm.foreign_into())
     }
 
     #[cfg(not(feature = "business_profile_routing"))]
@@ -321,6 +366,7 @@ pub async fn link_routing_config(
         record.modified_at = modified_at;
         merchant_dictionary.active_id = Some(record.id.clone());
         let response = record.clone();
+        let previous_algorithm_id = routing_ref.algorithm_id.clone();
         routing_ref.update_algorithm_id(algorithm_id);
         helpers::update_merchant_routing_dictionary(
             db,
@@ -328,10 +374,16 @@ pub async fn link_routing_config(
             merchant_dictionary,
         )
         .await?;
-        helpers::update_merchant_active_algorithm_ref(db, &key_store, routing_ref).await?;
+        helpers::activate_merchant_algorithm_ref(
+            db,
+            key_store,
+            routing_ref,
+            previous_algorithm_id,
+            action,
+        )
+        .await?;
 
-        metrics::ROUTING_LINK_CONFIG_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
-        Ok(service_api::ApplicationResponse::Json(response))
+        Ok(response)
     }
 }
 
@@ -464,10 +516,12 @@ pub async fn unlink_routing_config(
                             .await
                             .to_not_found_response(errors::ApiErrorResponse::ResourceIdNotFound)?;
                         let response = record.foreign_into();
-                        helpers::update_business_profile_active_algorithm_ref(
+                        helpers::activate_business_profile_algorithm_ref(
                             db,
                             business_profile,
                             routing_algorithm,
+                            Some(algorithm_id),
+                            storage_enums::RoutingActivationAction::Deactivate,
                         )
                         .await?;
 
@@ -548,41 +602,14 @@ pub async fn unlink_routing_config(
         )
         .await?;
 
-        let ref_value =
-            Encode::<routing_types::RoutingAlgorithmRef>::encode_to_value(&routing_algorithm)
-                .change_context(errors::ApiErrorResponse::InternalServerError)
-                .attach_printable("Failed converting routing algorithm ref to json value")?;
-
-        let merchant_account_update = storage::MerchantAccountUpdate::Update {
-            merchant_name: None,
-            merchant_details: None,
-            return_url: None,
-            webhook_details: None,
-            sub_merchants_enabled: None,
-            parent_merchant_id: None,
-            enable_payment_response_hash: None,
-            payment_response_hash_key: None,
-            redirect_to_merchant_with_http_post: None,
-            publishable_key: None,
-            locker_id: None,
-            metadata: None,
-            routing_algorithm: Some(ref_value),
-            primary_business_details: None,
-            intent_fulfillment_time: None,
-            frm_routing_algorithm: None,
-            payout_routing_algorithm: None,
-            default_profile: None,
-            payment_link_config: None,
-        };
-
-        db.update_specific_fields_in_merchant(
-            &key_store.merchant_id,
-            merchant_account_update,
+        helpers::activate_merchant_algorithm_ref(
+            db,
             &key_store,
+            routing_algorithm,
+            Some(active_algorithm_id),
+            storage_enums::RoutingActivationAction::Deactivate,
         )
-        .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Failed to update routing algorithm ref in merchant account")?;
+        .await?;
 
         metrics::ROUTING_UNLINK_CONFIG_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
         Ok(service_api::ApplicationResponse::Json(response))
@@ -879,3 +906,504 @@ pub async fn update_default_routing_config_for_profile(
         },
     ))
 }
+
+/// The results of a routing simulation, aggregated over the replayed payments
+#[cfg(feature = "olap")]
+#[derive(Debug, Default)]
+struct SimulationResults {
+    connectors: BTreeMap<String, routing_types::ConnectorSimulationResult>,
+    skipped_payment_count: u64,
+    rerouted_payment_count: u64,
+}
+
+#[cfg(feature = "olap")]
+impl SimulationResults {
+    /// Records a payment actually routed to `actual_connector`, which the simulated algorithm
+    /// would have routed to `simulated_connector`. Amounts only add up within a currency, so the
+    /// payments without a currency are counted without being part of the volumes.
+    fn add_payment(
+        &mut self,
+        actual_connector: &str,
+        simulated_connector: String,
+        currency: Option<storage_enums::Currency>,
+        amount: i64,
+    ) {
+        if simulated_connector != actual_connector {
+            self.rerouted_payment_count += 1;
+        }
+
+        let actual = self.get_connector_result(actual_connector.to_string());
+        actual.actual_payment_count += 1;
+        if let Some(currency) = currency {
+            add_currency_volume(&mut actual.actual_volume, currency, amount);
+        }
+
+        let simulated = self.get_connector_result(simulated_connector);
+        simulated.simulated_payment_count += 1;
+        if let Some(currency) = currency {
+            add_currency_volume(&mut simulated.simulated_volume, currency, amount);
+        }
+    }
+
+    fn get_connector_result(
+        &mut self,
+        connector: String,
+    ) -> &mut routing_types::ConnectorSimulationResult {
+        self.connectors.entry(connector.clone()).or_insert_with(|| {
+            routing_types::ConnectorSimulationResult {
+                connector,
+                ..Default::default()
+            }
+        })
+    }
+}
+
+#[cfg(feature = "olap")]
+fn add_currency_volume(
+    volumes: &mut Vec<routing_types::CurrencyVolume>,
+    currency: storage_enums::Currency,
+    amount: i64,
+) {
+    match volumes
+        .iter_mut()
+        .find(|volume| volume.currency == currency)
+    {
+        Some(volume) => volume.amount += amount,
+        None => volumes.push(routing_types::CurrencyVolume { currency, amount }),
+    }
+}
+
+#[cfg(feature = "olap")]
+pub async fn simulate_routing_config(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    algorithm_id: String,
+    request: routing_types::RoutingSimulationRequest,
+) -> RouterResponse<routing_types::RoutingSimulationResponse> {
+    metrics::ROUTING_SIMULATE_CONFIG.add(&metrics::CONTEXT, 1, &[]);
+    let db = state.store.as_ref();
+
+    utils::when(request.ending_at <= request.starting_at, || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "`ending_at` must be after `starting_at`".to_string(),
+        })
+        .into_report()
+    })?;
+
+    let limit = request
+        .limit
+        .unwrap_or(routing_types::ROUTING_SIMULATION_MAX_PAYMENTS);
+    utils::when(
+        limit == 0 || limit > routing_types::ROUTING_SIMULATION_MAX_PAYMENTS,
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "`limit` must be between 1 and {}",
+                    routing_types::ROUTING_SIMULATION_MAX_PAYMENTS
+                ),
+            })
+            .into_report()
+        },
+    )?;
+
+    let (algorithm, profile_id) =
+        get_merchant_routing_algorithm(db, &merchant_account.merchant_id, &algorithm_id).await?;
+    let profile_id = profile_id.or(request.profile_id);
+
+    let simulator = payments_routing::RoutingSimulator::new(algorithm)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Error initializing the routing algorithm for simulation")?;
+
+    let constraints = PaymentIntentFetchConstraints::List(Box::new(PaymentIntentListParams {
+        offset: 0,
+        starting_at: Some(request.starting_at),
+        ending_at: Some(request.ending_at),
+        connector: None,
+        currency: None,
+        status: None,
+        payment_method: None,
+        payment_method_type: None,
+        authentication_type: None,
+        profile_id,
+        customer_id: None,
+        starting_after_id: None,
+        ending_before_id: None,
+        limit: Some(limit),
+    }));
+
+    let payments = db
+        .get_filtered_payment_intents_attempt(
+            &merchant_account.merchant_id,
+            &constraints,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let mut results = SimulationResults::default();
+    for (payment_intent, payment_attempt) in payments.iter() {
+        let actual_connector = match payment_attempt.connector.as_ref() {
+            Some(connector) => connector,
+            None => {
+                results.skipped_payment_count += 1;
+                continue;
+            }
+        };
+
+        let simulated_connector = match simulator.simulate(payment_intent, payment_attempt) {
+            Ok(Some(choice)) => choice.connector.to_string(),
+            Ok(None) => {
+                results.skipped_payment_count += 1;
+                continue;
+            }
+            Err(error) => {
+                logger::warn!(
+                    ?error,
+                    payment_id = %payment_intent.payment_id,
+                    "Unable to simulate routing for payment"
+                );
+                results.skipped_payment_count += 1;
+                continue;
+            }
+        };
+
+        results.add_payment(
+            actual_connector,
+            simulated_connector,
+            payment_intent.currency,
+            payment_intent.amount,
+        );
+    }
+
+    let response = routing_types::RoutingSimulationResponse {
+        algorithm_id,
+        total_payment_count: u64::try_from(payments.len())
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)?,
+        skipped_payment_count: results.skipped_payment_count,
+        rerouted_payment_count: results.rerouted_payment_count,
+        connectors: results.connectors.into_values().collect(),
+    };
+
+    metrics::ROUTING_SIMULATE_CONFIG_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
+    Ok(service_api::ApplicationResponse::Json(response))
+}
+
+/// Resolves the business profile whose routing activation history is requested. Activations are
+/// tracked per business profile with business profile routing, and per merchant otherwise.
+async fn get_routing_activation_profile_id(
+    #[cfg(feature = "business_profile_routing")] db: &dyn StorageInterface,
+    #[cfg(feature = "business_profile_routing")] merchant_id: &str,
+    profile_id: Option<String>,
+) -> RouterResult<Option<String>> {
+    #[cfg(feature = "business_profile_routing")]
+    {
+        let profile_id = profile_id
+            .get_required_value("profile_id")
+            .change_context(errors::ApiErrorResponse::MissingRequiredField {
+                field_name: "profile_id",
+            })
+            .attach_printable("Profile_id not provided")?;
+
+        core_utils::validate_and_get_business_profile(db, Some(&profile_id), merchant_id)
+            .await?
+            .get_required_value("BusinessProfile")
+            .change_context(errors::ApiErrorResponse::BusinessProfileNotFound {
+                id: profile_id.clone(),
+            })?;
+
+        Ok(Some(profile_id))
+    }
+
+    #[cfg(not(feature = "business_profile_routing"))]
+    {
+        utils::when(profile_id.is_some(), || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: "`profile_id` is only supported with business profile routing".to_string(),
+            })
+            .into_report()
+        })?;
+
+        Ok(None)
+    }
+}
+
+/// Fetches a routing algorithm of the merchant, along with the business profile it belongs to
+/// when business profile routing is enabled
+async fn get_merchant_routing_algorithm(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    algorithm_id: &str,
+) -> RouterResult<(routing_types::RoutingAlgorithm, Option<String>)> {
+    #[cfg(feature = "business_profile_routing")]
+    {
+        let routing_algorithm = db
+            .find_routing_algorithm_by_algorithm_id_merchant_id(algorithm_id, merchant_id)
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::ResourceIdNotFound)?;
+
+        core_utils::validate_and_get_business_profile(
+            db,
+            Some(&routing_algorithm.profile_id),
+            merchant_id,
+        )
+        .await?
+        .get_required_value("BusinessProfile")
+        .change_context(errors::ApiErrorResponse::ResourceIdNotFound)?;
+
+        let algorithm: routing_types::RoutingAlgorithm = routing_algorithm
+            .algorithm_data
+            .parse_value("RoutingAlgorithm")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Error deserializing routing algorithm")?;
+
+        Ok((algorithm, Some(routing_algorithm.profile_id)))
+    }
+
+    #[cfg(not(feature = "business_profile_routing"))]
+    {
+        let merchant_dictionary = helpers::get_merchant_routing_dictionary(db, merchant_id).await?;
+
+        utils::when(
+            !merchant_dictionary
+                .records
+                .iter()
+                .any(|rec| rec.id == algorithm_id),
+            || {
+                Err(errors::ApiErrorResponse::ResourceIdNotFound)
+                    .into_report()
+                    .attach_printable(
+                        "Algorithm with the given ID not found in the merchant dictionary",
+                    )
+            },
+        )?;
+
+        let algorithm_config = db
+            .find_config_by_key(algorithm_id)
+            .await
+            .change_context(errors::ApiErrorResponse::ResourceIdNotFound)
+            .attach_printable("Routing config not found in DB")?;
+
+        let algorithm: routing_types::RoutingAlgorithm = algorithm_config
+            .config
+            .parse_struct("RoutingAlgorithm")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Error deserializing routing algorithm config")?;
+
+        Ok((algorithm, None))
+    }
+}
+
+pub async fn retrieve_routing_activation_history(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    query: routing_types::RoutingActivationHistoryQuery,
+) -> RouterResponse<Vec<routing_types::RoutingActivationRecord>> {
+    metrics::ROUTING_RETRIEVE_ACTIVATION_HISTORY.add(&metrics::CONTEXT, 1, &[]);
+    let db = state.store.as_ref();
+    let profile_id = get_routing_activation_profile_id(
+        #[cfg(feature = "business_profile_routing")]
+        db,
+        #[cfg(feature = "business_profile_routing")]
+        &merchant_account.merchant_id,
+        query.profile_id,
+    )
+    .await?;
+
+    let activations = db
+        .list_routing_algorithm_activations_by_merchant_id_profile_id(
+            &merchant_account.merchant_id,
+            profile_id.as_deref(),
+            query.limit,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve routing algorithm activations")?;
+
+    metrics::ROUTING_RETRIEVE_ACTIVATION_HISTORY_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
+    Ok(service_api::ApplicationResponse::Json(
+        activations
+            .into_iter()
+            .map(ForeignFrom::foreign_from)
+            .collect(),
+    ))
+}
+
+pub async fn diff_routing_configs(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    request: routing_types::RoutingDiffRequest,
+) -> RouterResponse<routing_types::RoutingDiffResponse> {
+    metrics::ROUTING_DIFF_CONFIG.add(&metrics::CONTEXT, 1, &[]);
+    let db = state.store.as_ref();
+
+    let (base, _) = get_merchant_routing_algorithm(
+        db,
+        &merchant_account.merchant_id,
+        &request.base_algorithm_id,
+    )
+    .await?;
+    let (compare, _) = get_merchant_routing_algorithm(
+        db,
+        &merchant_account.merchant_id,
+        &request.compare_algorithm_id,
+    )
+    .await?;
+
+    let changes = diff::diff_routing_algorithms(&base, &compare)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to compute the difference between routing algorithms")?;
+
+    let response = routing_types::RoutingDiffResponse {
+        base_algorithm_id: request.base_algorithm_id,
+        compare_algorithm_id: request.compare_algorithm_id,
+        base_kind: base.get_kind(),
+        compare_kind: compare.get_kind(),
+        changes,
+    };
+
+    metrics::ROUTING_DIFF_CONFIG_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
+    Ok(service_api::ApplicationResponse::Json(response))
+}
+
+/// Re-activates the algorithm that was active before the most recent activation or deactivation.
+/// Rolling back twice in a row therefore restores the algorithm that was rolled back from.
+pub async fn rollback_routing_config(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    #[cfg(not(feature = "business_profile_routing"))] key_store: domain::MerchantKeyStore,
+    request: routing_types::RoutingRollbackRequest,
+) -> RouterResponse<routing_types::RoutingDictionaryRecord> {
+    metrics::ROUTING_ROLLBACK_CONFIG.add(&metrics::CONTEXT, 1, &[]);
+    let db = state.store.as_ref();
+    let profile_id = get_routing_activation_profile_id(
+        #[cfg(feature = "business_profile_routing")]
+        db,
+        #[cfg(feature = "business_profile_routing")]
+        &merchant_account.merchant_id,
+        request.profile_id,
+    )
+    .await?;
+
+    let latest_activation = db
+        .list_routing_algorithm_activations_by_merchant_id_profile_id(
+            &merchant_account.merchant_id,
+            profile_id.as_deref(),
+            Some(1),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve routing algorithm activations")?
+        .into_iter()
+        .next()
+        .ok_or(errors::ApiErrorResponse::PreconditionFailed {
+            message: "No routing algorithm has been activated yet".to_string(),
+        })
+        .into_report()?;
+
+    let previous_algorithm_id = latest_activation
+        .previous_algorithm_id
+        .ok_or(errors::ApiErrorResponse::PreconditionFailed {
+            message: "No previously active routing algorithm to roll back to".to_string(),
+        })
+        .into_report()?;
+
+    let response = activate_routing_algorithm(
+        &state,
+        &merchant_account,
+        #[cfg(not(feature = "business_profile_routing"))]
+        &key_store,
+        previous_algorithm_id,
+        storage_enums::RoutingActivationAction::Rollback,
+    )
+    .await?;
+
+    metrics::ROUTING_ROLLBACK_CONFIG_SUCCESS_RESPONSE.add(&metrics::CONTEXT, 1, &[]);
+    Ok(service_api::ApplicationResponse::Json(response))
+}
+
+#[cfg(all(test, feature = "olap"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulation_results_volumes_grouped_by_currency() {
+        let mut results = SimulationResults::default();
+        results.add_payment(
+            "stripe",
+            "stripe".to_string(),
+            Some(storage_enums::Currency::USD),
+            1000,
+        );
+        results.add_payment(
+            "stripe",
+            "stripe".to_string(),
+            Some(storage_enums::Currency::USD),
+            500,
+        );
+        results.add_payment(
+            "stripe",
+            "stripe".to_string(),
+            Some(storage_enums::Currency::JPY),
+            300,
+        );
+
+        let stripe = &results.connectors["stripe"];
+        assert_eq!(stripe.actual_payment_count, 3);
+        assert_eq!(stripe.simulated_payment_count, 3);
+        assert_eq!(
+            stripe.actual_volume,
+            vec![
+                routing_types::CurrencyVolume {
+                    currency: storage_enums::Currency::USD,
+                    amount: 1500,
+                },
+                routing_types::CurrencyVolume {
+                    currency: storage_enums::Currency::JPY,
+                    amount: 300,
+                },
+            ]
+        );
+        assert_eq!(stripe.simulated_volume, stripe.actual_volume);
+        assert_eq!(results.rerouted_payment_count, 0);
+    }
+
+    #[test]
+    fn test_simulation_results_rerouted_payments() {
+        let mut results = SimulationResults::default();
+        results.add_payment(
+            "stripe",
+            "adyen".to_string(),
+            Some(storage_enums::Currency::EUR),
+            700,
+        );
+        results.add_payment("stripe", "stripe".to_string(), None, 200);
+
+        assert_eq!(results.rerouted_payment_count, 1);
+
+        let stripe = &results.connectors["stripe"];
+        assert_eq!(stripe.actual_payment_count, 2);
+        assert_eq!(stripe.simulated_payment_count, 1);
+        // The payment without a currency is counted, but not part of the volumes
+        assert_eq!(
+            stripe.actual_volume,
+            vec![routing_types::CurrencyVolume {
+                currency: storage_enums::Currency::EUR,
+                amount: 700,
+            }]
+        );
+        assert!(stripe.simulated_volume.is_empty());
+
+        let adyen = &results.connectors["adyen"];
+        assert_eq!(adyen.actual_payment_count, 0);
+        assert_eq!(adyen.simulated_payment_count, 1);
+        assert_eq!(
+            adyen.simulated_volume,
+            vec![routing_types::CurrencyVolume {
+                currency: storage_enums::Currency::EUR,
+                amount: 700,
+            }]
+        );
+    }
+}
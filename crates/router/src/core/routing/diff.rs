@@ -0,0 +1,448 @@
+use std::collections::BTreeMap;
+
+use api_models::routing::{
+    self as routing_types, RoutingAlgorithm, RoutingConfigChange, RoutingConfigChangeType,
+};
+use serde_json::Value;
+
+type DiffResult<T> = Result<T, serde_json::Error>;
+
+/// Computes the changes needed to turn the `base` algorithm into the `compare` algorithm.
+///
+/// Algorithms of the same kind are compared element by element: priority lists by position,
+/// volume splits by connector and advanced programs rule by rule, keyed on the rule name and its
+/// occurrence among the rules of the same name.
+/// Algorithms of different kinds are reported as a single modification of the whole algorithm.
+pub fn diff_routing_algorithms(
+    base: &RoutingAlgorithm,
+    compare: &RoutingAlgorithm,
+) -> DiffResult<Vec<RoutingConfigChange>> {
+    let mut changes = Vec::new();
+
+    match (base, compare) {
+        (RoutingAlgorithm::Single(base), RoutingAlgorithm::Single(compare)) => {
+            diff_values(
+                &mut changes,
+                "connector".to_string(),
+                Some(serde_json::to_value(base)?),
+                Some(serde_json::to_value(compare)?),
+            );
+        }
+        (RoutingAlgorithm::Priority(base), RoutingAlgorithm::Priority(compare)) => {
+            diff_lists(&mut changes, "priority", base, compare)?;
+        }
+        (RoutingAlgorithm::VolumeSplit(base), RoutingAlgorithm::VolumeSplit(compare)) => {
+            diff_volume_splits(&mut changes, "volume_split", base, compare)?;
+        }
+        (RoutingAlgorithm::SuccessRateBased(base), RoutingAlgorithm::SuccessRateBased(compare)) => {
+            diff_lists(
+                &mut changes,
+                "connectors",
+                &base.connectors,
+                &compare.connectors,
+            )?;
+            diff_values(
+                &mut changes,
+                "window_minutes".to_string(),
+                Some(base.window_minutes.into()),
+                Some(compare.window_minutes.into()),
+            );
+            diff_values(
+                &mut changes,
+                "min_attempts".to_string(),
+                Some(base.min_attempts.into()),
+                Some(compare.min_attempts.into()),
+            );
+            diff_values(
+                &mut changes,
+                "exploration_percent".to_string(),
+                Some(base.exploration_percent.into()),
+                Some(compare.exploration_percent.into()),
+            );
+        }
+        (RoutingAlgorithm::Advanced(base), RoutingAlgorithm::Advanced(compare)) => {
+            diff_connector_selections(
+                &mut changes,
+                "default_selection",
+                &base.default_selection,
+                &compare.default_selection,
+            )?;
+
+            let base_rules = get_keyed_rules(&base.rules);
+            let compare_rules = get_keyed_rules(&compare.rules);
+            let base_rules_by_key = base_rules.iter().cloned().collect::<BTreeMap<_, _>>();
+            let compare_rules_by_key = compare_rules.iter().cloned().collect::<BTreeMap<_, _>>();
+
+            for (key, base_rule) in base_rules_by_key.iter() {
+                let path = get_rule_path(key);
+                match compare_rules_by_key.get(key) {
+                    Some(compare_rule) => {
+                        diff_connector_selections(
+                            &mut changes,
+                            &format!("{path}.connector_selection"),
+                            &base_rule.connector_selection,
+                            &compare_rule.connector_selection,
+                        )?;
+                        diff_values(
+                            &mut changes,
+                            format!("{path}.statements"),
+                            Some(serde_json::to_value(&base_rule.statements)?),
+                            Some(serde_json::to_value(&compare_rule.statements)?),
+                        );
+                    }
+                    None => diff_values(
+                        &mut changes,
+                        path,
+                        Some(serde_json::to_value(base_rule)?),
+                        None,
+                    ),
+                }
+            }
+            for (key, compare_rule) in compare_rules_by_key.iter() {
+                if !base_rules_by_key.contains_key(key) {
+                    diff_values(
+                        &mut changes,
+                        get_rule_path(key),
+                        None,
+                        Some(serde_json::to_value(compare_rule)?),
+                    );
+                }
+            }
+
+            // Rules are evaluated in order, so a reordering changes the routing decisions even
+            // when none of the rules themselves changed
+            let base_order = base_rules
+                .iter()
+                .map(|(key, _)| key)
+                .filter(|key| compare_rules_by_key.contains_key(*key))
+                .map(get_rule_path)
+                .collect::<Vec<_>>();
+            let compare_order = compare_rules
+                .iter()
+                .map(|(key, _)| key)
+                .filter(|key| base_rules_by_key.contains_key(*key))
+                .map(get_rule_path)
+                .collect::<Vec<_>>();
+            diff_values(
+                &mut changes,
+                "rule_order".to_string(),
+                Some(serde_json::to_value(base_order)?),
+                Some(serde_json::to_value(compare_order)?),
+            );
+
+            diff_values(
+                &mut changes,
+                "metadata".to_string(),
+                Some(serde_json::to_value(&base.metadata)?),
+                Some(serde_json::to_value(&compare.metadata)?),
+            );
+        }
+        (base, compare) => {
+            diff_values(
+                &mut changes,
+                "algorithm".to_string(),
+                Some(serde_json::to_value(base)?),
+                Some(serde_json::to_value(compare)?),
+            );
+        }
+    }
+
+    Ok(changes)
+}
+
+type Rule = euclid::frontend::ast::Rule<routing_types::ConnectorSelection>;
+
+/// Identifies a rule by its name and the number of rules of the same name before it, so that
+/// rules sharing a name are compared one by one instead of being merged
+type RuleKey<'a> = (&'a str, usize);
+
+fn get_keyed_rules(rules: &[Rule]) -> Vec<(RuleKey<'_>, &Rule)> {
+    let mut occurrences = BTreeMap::<&str, usize>::new();
+    rules
+        .iter()
+        .map(|rule| {
+            let occurrence = occurrences.entry(rule.name.as_str()).or_default();
+            let key = (rule.name.as_str(), *occurrence);
+            *occurrence += 1;
+            (key, rule)
+        })
+        .collect()
+}
+
+/// The path of a rule, its name suffixed with its occurrence if an earlier rule has the same name
+fn get_rule_path((name, occurrence): &RuleKey<'_>) -> String {
+    match occurrence {
+        0 => format!("rules.{name}"),
+        occurrence => format!("rules.{name}[{occurrence}]"),
+    }
+}
+
+fn diff_connector_selections(
+    changes: &mut Vec<RoutingConfigChange>,
+    path: &str,
+    base: &routing_types::ConnectorSelection,
+    compare: &routing_types::ConnectorSelection,
+) -> DiffResult<()> {
+    match (base, compare) {
+        (
+            routing_types::ConnectorSelection::Priority(base),
+            routing_types::ConnectorSelection::Priority(compare),
+        ) => diff_lists(changes, &format!("{path}.priority"), base, compare),
+        (
+            routing_types::ConnectorSelection::VolumeSplit(base),
+            routing_types::ConnectorSelection::VolumeSplit(compare),
+        ) => diff_volume_splits(changes, &format!("{path}.volume_split"), base, compare),
+        (base, compare) => {
+            diff_values(
+                changes,
+                path.to_string(),
+                Some(serde_json::to_value(base)?),
+                Some(serde_json::to_value(compare)?),
+            );
+            Ok(())
+        }
+    }
+}
+
+fn diff_lists<T: serde::Serialize>(
+    changes: &mut Vec<RoutingConfigChange>,
+    path: &str,
+    base: &[T],
+    compare: &[T],
+) -> DiffResult<()> {
+    for index in 0..base.len().max(compare.len()) {
+        diff_values(
+            changes,
+            format!("{path}[{index}]"),
+            base.get(index).map(serde_json::to_value).transpose()?,
+            compare.get(index).map(serde_json::to_value).transpose()?,
+        );
+    }
+    Ok(())
+}
+
+fn diff_volume_splits(
+    changes: &mut Vec<RoutingConfigChange>,
+    path: &str,
+    base: &[routing_types::ConnectorVolumeSplit],
+    compare: &[routing_types::ConnectorVolumeSplit],
+) -> DiffResult<()> {
+    let base_splits = base
+        .iter()
+        .map(|split| (split.connector.to_string(), split.split))
+        .collect::<BTreeMap<_, _>>();
+    let compare_splits = compare
+        .iter()
+        .map(|split| (split.connector.to_string(), split.split))
+        .collect::<BTreeMap<_, _>>();
+
+    for (connector, split) in base_splits.iter() {
+        diff_values(
+            changes,
+            format!("{path}.{connector}"),
+            Some((*split).into()),
+            compare_splits.get(connector).map(|split| (*split).into()),
+        );
+    }
+    for (connector, split) in compare_splits.iter() {
+        if !base_splits.contains_key(connector) {
+            diff_values(
+                changes,
+                format!("{path}.{connector}"),
+                None,
+                Some((*split).into()),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn diff_values(
+    changes: &mut Vec<RoutingConfigChange>,
+    path: String,
+    base: Option<Value>,
+    compare: Option<Value>,
+) {
+    let change_type = match (&base, &compare) {
+        (Some(base), Some(compare)) if base == compare => return,
+        (Some(_), Some(_)) => RoutingConfigChangeType::Modified,
+        (Some(_), None) => RoutingConfigChangeType::Removed,
+        (None, Some(_)) => RoutingConfigChangeType::Added,
+        (None, None) => return,
+    };
+
+    changes.push(RoutingConfigChange {
+        path,
+        change_type,
+        base,
+        compare,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use euclid::frontend::ast;
+
+    use super::*;
+
+    fn choice(connector: &str) -> routing_types::RoutableConnectorChoice {
+        serde_json::from_value(serde_json::json!({ "connector": connector })).unwrap()
+    }
+
+    fn rule(name: &str, connector: &str) -> ast::Rule<routing_types::ConnectorSelection> {
+        ast::Rule {
+            name: name.to_string(),
+            connector_selection: routing_types::ConnectorSelection::Priority(vec![choice(
+                connector,
+            )]),
+            statements: Vec::new(),
+        }
+    }
+
+    fn program(
+        rules: Vec<ast::Rule<routing_types::ConnectorSelection>>,
+    ) -> ast::Program<routing_types::ConnectorSelection> {
+        ast::Program {
+            default_selection: routing_types::ConnectorSelection::Priority(vec![choice("stripe")]),
+            rules,
+            metadata: Default::default(),
+        }
+    }
+
+    fn paths(changes: &[RoutingConfigChange]) -> Vec<(&str, RoutingConfigChangeType)> {
+        changes
+            .iter()
+            .map(|change| (change.path.as_str(), change.change_type))
+            .collect()
+    }
+
+    #[test]
+    fn test_identical_algorithms_have_no_changes() {
+        let algorithm = RoutingAlgorithm::Advanced(program(vec![rule("cards", "adyen")]));
+
+        let changes = diff_routing_algorithms(&algorithm, &algorithm).unwrap();
+
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_priority_lists_are_compared_by_position() {
+        let base = RoutingAlgorithm::Priority(vec![choice("stripe"), choice("adyen")]);
+        let compare =
+            RoutingAlgorithm::Priority(vec![choice("stripe"), choice("checkout"), choice("adyen")]);
+
+        let changes = diff_routing_algorithms(&base, &compare).unwrap();
+
+        assert_eq!(
+            paths(&changes),
+            vec![
+                ("priority[1]", RoutingConfigChangeType::Modified),
+                ("priority[2]", RoutingConfigChangeType::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_volume_splits_are_compared_by_connector() {
+        let base = RoutingAlgorithm::VolumeSplit(vec![
+            routing_types::ConnectorVolumeSplit {
+                connector: choice("stripe"),
+                split: 50,
+            },
+            routing_types::ConnectorVolumeSplit {
+                connector: choice("adyen"),
+                split: 50,
+            },
+        ]);
+        let compare = RoutingAlgorithm::VolumeSplit(vec![
+            routing_types::ConnectorVolumeSplit {
+                connector: choice("adyen"),
+                split: 50,
+            },
+            routing_types::ConnectorVolumeSplit {
+                connector: choice("checkout"),
+                split: 50,
+            },
+        ]);
+
+        let changes = diff_routing_algorithms(&base, &compare).unwrap();
+
+        assert_eq!(
+            paths(&changes),
+            vec![
+                ("volume_split.stripe", RoutingConfigChangeType::Removed),
+                ("volume_split.checkout", RoutingConfigChangeType::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_advanced_programs_are_compared_rule_by_rule() {
+        let base = RoutingAlgorithm::Advanced(program(vec![
+            rule("cards", "adyen"),
+            rule("wallets", "stripe"),
+            rule("bank_debits", "stripe"),
+        ]));
+        let compare = RoutingAlgorithm::Advanced(program(vec![
+            rule("wallets", "stripe"),
+            rule("cards", "checkout"),
+            rule("pay_later", "klarna"),
+        ]));
+
+        let changes = diff_routing_algorithms(&base, &compare).unwrap();
+
+        assert_eq!(
+            paths(&changes),
+            vec![
+                ("rules.bank_debits", RoutingConfigChangeType::Removed),
+                (
+                    "rules.cards.connector_selection.priority[0]",
+                    RoutingConfigChangeType::Modified
+                ),
+                ("rules.pay_later", RoutingConfigChangeType::Added),
+                ("rule_order", RoutingConfigChangeType::Modified),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rules_sharing_a_name_are_not_merged() {
+        let base = RoutingAlgorithm::Advanced(program(vec![
+            rule("cards", "adyen"),
+            rule("cards", "stripe"),
+        ]));
+        let compare = RoutingAlgorithm::Advanced(program(vec![
+            rule("cards", "adyen"),
+            rule("cards", "checkout"),
+            rule("cards", "klarna"),
+        ]));
+
+        let changes = diff_routing_algorithms(&base, &compare).unwrap();
+
+        assert_eq!(
+            paths(&changes),
+            vec![
+                (
+                    "rules.cards[1].connector_selection.priority[0]",
+                    RoutingConfigChangeType::Modified
+                ),
+                ("rules.cards[2]", RoutingConfigChangeType::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_algorithms_of_different_kinds_are_replaced() {
+        let base = RoutingAlgorithm::Single(Box::new(choice("stripe")));
+        let compare = RoutingAlgorithm::Priority(vec![choice("stripe")]);
+
+        let changes = diff_routing_algorithms(&base, &compare).unwrap();
+
+        assert_eq!(
+            paths(&changes),
+            vec![("algorithm", RoutingConfigChangeType::Modified)]
+        );
+    }
+}
@@ -12,6 +12,7 @@ use error_stack::ResultExt;
 use rustc_hash::FxHashSet;
 
 use crate::{
+    consts,
     core::errors::{self, RouterResult},
     db::StorageInterface,
     types::{domain, storage},
@@ -193,11 +194,59 @@ pub async fn update_merchant_active_algorithm_ref(
     key_store: &domain::MerchantKeyStore,
     algorithm_id: routing_types::RoutingAlgorithmRef,
 ) -> RouterResult<()> {
-    let ref_value = Encode::<routing_types::RoutingAlgorithmRef>::encode_to_value(&algorithm_id)
+    let merchant_account_update = get_merchant_account_routing_algorithm_update(&algorithm_id)?;
+
+    db.update_specific_fields_in_merchant(
+        &key_store.merchant_id,
+        merchant_account_update,
+        key_store,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to update routing algorithm ref in merchant account")?;
+
+    Ok(())
+}
+
+/// Changes the active routing algorithm of the merchant, recording the change in the activation
+/// history of the merchant in the same transaction
+pub async fn activate_merchant_algorithm_ref(
+    db: &dyn StorageInterface,
+    key_store: &domain::MerchantKeyStore,
+    algorithm_id: routing_types::RoutingAlgorithmRef,
+    previous_algorithm_id: Option<String>,
+    action: storage::enums::RoutingActivationAction,
+) -> RouterResult<()> {
+    let merchant_account_update = get_merchant_account_routing_algorithm_update(&algorithm_id)?;
+    let activation = get_routing_algorithm_activation(
+        &key_store.merchant_id,
+        None,
+        algorithm_id.algorithm_id,
+        previous_algorithm_id,
+        action,
+    );
+
+    db.update_merchant_routing_algorithm_with_activation(
+        &key_store.merchant_id,
+        merchant_account_update,
+        key_store,
+        activation,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to activate routing algorithm in merchant account")?;
+
+    Ok(())
+}
+
+fn get_merchant_account_routing_algorithm_update(
+    algorithm_id: &routing_types::RoutingAlgorithmRef,
+) -> RouterResult<storage::MerchantAccountUpdate> {
+    let ref_value = Encode::<routing_types::RoutingAlgorithmRef>::encode_to_value(algorithm_id)
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Failed converting routing algorithm ref to json value")?;
 
-    let merchant_account_update = storage::MerchantAccountUpdate::Update {
+    Ok(storage::MerchantAccountUpdate::Update {
         merchant_name: None,
         merchant_details: None,
         return_url: None,
@@ -217,30 +266,79 @@ pub async fn update_merchant_active_algorithm_ref(
         payout_routing_algorithm: None,
         default_profile: None,
         payment_link_config: None,
-    };
+    })
+}
 
-    db.update_specific_fields_in_merchant(
-        &key_store.merchant_id,
-        merchant_account_update,
-        key_store,
-    )
-    .await
-    .change_context(errors::ApiErrorResponse::InternalServerError)
-    .attach_printable("Failed to update routing algorithm ref in merchant account")?;
+/// The entry of the activation history of the merchant or, with business profile routing, of the
+/// business profile, for a change of the active routing algorithm
+fn get_routing_algorithm_activation(
+    merchant_id: &str,
+    profile_id: Option<String>,
+    algorithm_id: Option<String>,
+    previous_algorithm_id: Option<String>,
+    action: storage::enums::RoutingActivationAction,
+) -> storage::RoutingAlgorithmActivationNew {
+    storage::RoutingAlgorithmActivationNew {
+        activation_id: common_utils::generate_id(consts::ID_LENGTH, "routing_activation"),
+        merchant_id: merchant_id.to_owned(),
+        profile_id,
+        algorithm_id,
+        previous_algorithm_id,
+        action,
+    }
+}
 
+pub async fn update_business_profile_active_algorithm_ref(
+    db: &dyn StorageInterface,
+    current_business_profile: BusinessProfile,
+    algorithm_id: routing_types::RoutingAlgorithmRef,
+) -> RouterResult<()> {
+    let business_profile_update = get_business_profile_routing_algorithm_update(&algorithm_id)?;
+
+    db.update_business_profile_by_profile_id(current_business_profile, business_profile_update)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to update routing algorithm ref in business profile")?;
     Ok(())
 }
 
-pub async fn update_business_profile_active_algorithm_ref(
+/// Changes the active routing algorithm of the business profile, recording the change in the
+/// activation history of the business profile in the same transaction
+pub async fn activate_business_profile_algorithm_ref(
     db: &dyn StorageInterface,
     current_business_profile: BusinessProfile,
     algorithm_id: routing_types::RoutingAlgorithmRef,
+    previous_algorithm_id: Option<String>,
+    action: storage::enums::RoutingActivationAction,
 ) -> RouterResult<()> {
-    let ref_val = Encode::<routing_types::RoutingAlgorithmRef>::encode_to_value(&algorithm_id)
+    let business_profile_update = get_business_profile_routing_algorithm_update(&algorithm_id)?;
+    let activation = get_routing_algorithm_activation(
+        &current_business_profile.merchant_id,
+        Some(current_business_profile.profile_id.clone()),
+        algorithm_id.algorithm_id,
+        previous_algorithm_id,
+        action,
+    );
+
+    db.update_business_profile_routing_algorithm_with_activation(
+        current_business_profile,
+        business_profile_update,
+        activation,
+    )
+    .await
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to activate routing algorithm in business profile")?;
+    Ok(())
+}
+
+fn get_business_profile_routing_algorithm_update(
+    algorithm_id: &routing_types::RoutingAlgorithmRef,
+) -> RouterResult<BusinessProfileUpdateInternal> {
+    let ref_val = Encode::<routing_types::RoutingAlgorithmRef>::encode_to_value(algorithm_id)
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Failed to convert routing ref to value")?;
 
-    let business_profile_update = BusinessProfileUpdateInternal {
+    Ok(BusinessProfileUpdateInternal {
         profile_name: None,
         return_url: None,
         enable_payment_response_hash: None,
@@ -255,12 +353,9 @@ pub async fn update_business_profile_active_algorithm_ref(
         applepay_verified_domains: None,
         modified_at: None,
         is_recon_enabled: None,
-    };
-    db.update_business_profile_by_profile_id(current_business_profile, business_profile_update)
-        .await
-        .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Failed to update routing algorithm ref in business profile")?;
-    Ok(())
+        previous_payment_response_hash_key: None,
+        previous_payment_response_hash_key_expires_at: None,
+    })
 }
 
 pub async fn get_merchant_connector_agnostic_mandate_config(
@@ -422,6 +517,12 @@ pub async fn validate_connectors_in_routing_config(
             }
         }
 
+        routing_types::RoutingAlgorithm::SuccessRateBased(algorithm) => {
+            for choice in &algorithm.connectors {
+                check_connector_choice(choice)?;
+            }
+        }
+
         routing_types::RoutingAlgorithm::Advanced(program) => {
             let check_connector_selection =
                 |selection: &routing_types::ConnectorSelection| -> RouterResult<()> {
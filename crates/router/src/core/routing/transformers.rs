@@ -1,11 +1,12 @@
 use api_models::routing::{
-    MerchantRoutingAlgorithm, RoutingAlgorithm as Algorithm, RoutingAlgorithmKind,
-    RoutingDictionaryRecord,
+    MerchantRoutingAlgorithm, RoutingActivationRecord, RoutingAlgorithm as Algorithm,
+    RoutingAlgorithmKind, RoutingDictionaryRecord,
 };
 use common_utils::ext_traits::ValueExt;
 use diesel_models::{
     enums as storage_enums,
     routing_algorithm::{RoutingAlgorithm, RoutingProfileMetadata},
+    routing_algorithm_activation::RoutingAlgorithmActivation,
 };
 
 use crate::{
@@ -44,6 +45,19 @@ impl ForeignFrom<RoutingAlgorithm> for RoutingDictionaryRecord {
     }
 }
 
+impl ForeignFrom<RoutingAlgorithmActivation> for RoutingActivationRecord {
+    fn foreign_from(value: RoutingAlgorithmActivation) -> Self {
+        Self {
+            activation_id: value.activation_id,
+            profile_id: value.profile_id,
+            algorithm_id: value.algorithm_id,
+            previous_algorithm_id: value.previous_algorithm_id,
+            action: value.action,
+            created_at: value.created_at,
+        }
+    }
+}
+
 impl ForeignTryFrom<RoutingAlgorithm> for MerchantRoutingAlgorithm {
     type Error = error_stack::Report<errors::ParsingError>;
 
@@ -70,6 +84,7 @@ impl ForeignFrom<storage_enums::RoutingAlgorithmKind> for RoutingAlgorithmKind {
             storage_enums::RoutingAlgorithmKind::Priority => Self::Priority,
             storage_enums::RoutingAlgorithmKind::VolumeSplit => Self::VolumeSplit,
             storage_enums::RoutingAlgorithmKind::Advanced => Self::Advanced,
+            storage_enums::RoutingAlgorithmKind::SuccessRateBased => Self::SuccessRateBased,
         }
     }
 }
@@ -81,6 +96,7 @@ impl ForeignFrom<RoutingAlgorithmKind> for storage_enums::RoutingAlgorithmKind {
             RoutingAlgorithmKind::Priority => Self::Priority,
             RoutingAlgorithmKind::VolumeSplit => Self::VolumeSplit,
             RoutingAlgorithmKind::Advanced => Self::Advanced,
+            RoutingAlgorithmKind::SuccessRateBased => Self::SuccessRateBased,
         }
     }
 }
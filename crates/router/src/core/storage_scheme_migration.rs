@@ -0,0 +1,715 @@
+//! Online migration of a merchant between the `PostgresOnly` and `RedisKv` storage schemes.
+//!
+//! A migration enables dual-read for the merchant, so that its reads missing in Postgres are
+//! retried against Redis, waits for the drainer to apply the entries already queued on the
+//! streams of the merchant's partitions, then switches the storage scheme of the merchant
+//! account. Migrating to `RedisKv` flushes the stale copies left in Redis by an earlier `RedisKv`
+//! period before switching. Migrating to `PostgresOnly` then pauses the merchant's KV writes and
+//! waits for the drainer again, so that no entry is left on the streams to overwrite the rows
+//! written to Postgres once the merchant is switched. Requests making KV writes in the meantime
+//! are answered with a 503, to be retried. The merchant's Redis keys are flushed and its writes
+//! resumed after the switch.
+//!
+//! The migration is driven by a process tracker task, and can be aborted until the storage
+//! scheme has been switched.
+
+use std::collections::BTreeSet;
+
+use api_models::admin::{StorageSchemeMigrationRequest, StorageSchemeMigrationResponse};
+use common_utils::ext_traits::ValueExt;
+use diesel_models::enums::{
+    MerchantStorageScheme, ProcessTrackerStatus, StorageSchemeMigrationStage,
+};
+use error_stack::{report, IntoReport, ResultExt};
+use redis_interface::RedisConnectionPool;
+use router_env::{instrument, logger, tracing};
+use scheduler::db::process_tracker::ProcessTrackerExt;
+use storage_impl::redis::{
+    cache::KV_WRITES_PAUSED_CACHE_TTL,
+    kv_store::{
+        get_dual_read_key, get_kv_writes_paused_key, get_merchant_kv_key_patterns,
+        is_merchant_kv_key,
+    },
+};
+
+use super::errors::{self, RouterResponse, RouterResult, StorageErrorExt};
+use crate::{
+    core::process_tracker::ensure_not_in_flight,
+    db::StorageInterface,
+    routes::AppState,
+    services::ApplicationResponse,
+    types::{domain, storage},
+    utils::storage_partitioning::{KvStorePartition, PartitionKey},
+};
+
+pub const STORAGE_SCHEME_MIGRATION_RUNNER: &str = "STORAGE_SCHEME_MIGRATION_WORKFLOW";
+const STORAGE_SCHEME_MIGRATION_TASK: &str = "STORAGE_SCHEME_MIGRATION";
+const STORAGE_SCHEME_MIGRATION_TAG: &str = "STORAGE_SCHEME_MIGRATION";
+
+/// Business statuses recorded on the migration tasks once they are finished
+const COMPLETED: &str = "COMPLETED";
+const ABORTED: &str = "ABORTED";
+
+/// Interval at which the workflow checks whether the drainer caught up with the watermarks
+const DRAINER_POLL_INTERVAL_IN_SECONDS: i64 = 10;
+
+// The watermarks are recorded a poll interval after the writes are paused, by which time no
+// instance still holds a cached flag from before the pause
+const _: () = assert!(DRAINER_POLL_INTERVAL_IN_SECONDS as u64 > KV_WRITES_PAUSED_CACHE_TTL);
+
+#[instrument(skip(state))]
+pub async fn start_migration(
+    state: AppState,
+    request: StorageSchemeMigrationRequest,
+) -> RouterResponse<StorageSchemeMigrationResponse> {
+    let db = state.store.as_ref();
+    let merchant_id = request.merchant_id;
+    let (merchant_account, _) = get_merchant_account(db, &merchant_id).await?;
+
+    if merchant_account.storage_scheme == request.target_storage_scheme {
+        return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "Merchant account is already using the {} storage scheme",
+                request.target_storage_scheme
+            ),
+        }));
+    }
+
+    if let Some((process, tracking_data)) = find_latest_migration(db, &merchant_id).await? {
+        if process.status != ProcessTrackerStatus::Finish {
+            return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "Storage scheme migration {} is in progress ({})",
+                    process.id, tracking_data.stage
+                ),
+            }));
+        }
+    }
+
+    let redis_conn = get_redis_conn(db)?;
+    set_dual_read(&redis_conn, &merchant_id, true)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to enable dual-read for the merchant")?;
+
+    let watermarks = get_drainer_stream_watermarks(&state, &redis_conn, &merchant_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to record the drainer stream watermarks")?;
+
+    let tracking_data = storage::StorageSchemeMigrationTrackingData {
+        merchant_id: merchant_id.clone(),
+        source_storage_scheme: merchant_account.storage_scheme,
+        target_storage_scheme: request.target_storage_scheme,
+        stage: StorageSchemeMigrationStage::WaitingForDrainer,
+        watermarks,
+        flushed_keys: None,
+    };
+    let process_tracker_id = scheduler::utils::get_process_tracker_id(
+        STORAGE_SCHEME_MIGRATION_RUNNER,
+        STORAGE_SCHEME_MIGRATION_TASK,
+        &common_utils::generate_id_with_default_len("migration"),
+        &merchant_id,
+    );
+    let mut process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        STORAGE_SCHEME_MIGRATION_TASK,
+        STORAGE_SCHEME_MIGRATION_RUNNER,
+        &tracking_data,
+        common_utils::date_time::now(),
+    )
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to construct the storage scheme migration task")?;
+    process_tracker_entry.tag = vec![String::from(STORAGE_SCHEME_MIGRATION_TAG)];
+
+    let process = db
+        .insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert the storage scheme migration task")?;
+
+    Ok(ApplicationResponse::Json(
+        migration_response(&redis_conn, process, tracking_data).await?,
+    ))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_migration(
+    state: AppState,
+    merchant_id: String,
+) -> RouterResponse<StorageSchemeMigrationResponse> {
+    let db = state.store.as_ref();
+    let (process, tracking_data) =
+        find_latest_migration(db, &merchant_id)
+            .await?
+            .ok_or_else(|| {
+                report!(errors::ApiErrorResponse::GenericNotFoundError {
+                    message: format!(
+                        "No storage scheme migration found for merchant {merchant_id}"
+                    ),
+                })
+            })?;
+
+    Ok(ApplicationResponse::Json(
+        migration_response(&get_redis_conn(db)?, process, tracking_data).await?,
+    ))
+}
+
+/// Aborts the latest migration of the merchant. Once the storage scheme has been switched, the
+/// merchant has to be migrated back instead.
+#[instrument(skip(state))]
+pub async fn abort_migration(
+    state: AppState,
+    merchant_id: String,
+) -> RouterResponse<StorageSchemeMigrationResponse> {
+    let db = state.store.as_ref();
+    let (process, mut tracking_data) =
+        find_latest_migration(db, &merchant_id)
+            .await?
+            .ok_or_else(|| {
+                report!(errors::ApiErrorResponse::GenericNotFoundError {
+                    message: format!(
+                        "No storage scheme migration found for merchant {merchant_id}"
+                    ),
+                })
+            })?;
+    ensure_not_in_flight(&process)?;
+
+    if process.status == ProcessTrackerStatus::Finish || !is_before_switch(tracking_data.stage) {
+        return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "Storage scheme migration {} cannot be aborted ({}), migrate the merchant back instead",
+                process.id, tracking_data.stage
+            ),
+        }));
+    }
+
+    tracking_data.stage = StorageSchemeMigrationStage::Aborted;
+    let process_id = process.id.clone();
+    let process = db
+        .update_process_if_status_in(
+            vec![ProcessTrackerStatus::New, ProcessTrackerStatus::Pending],
+            process,
+            storage::ProcessTrackerUpdate::Update {
+                name: None,
+                retry_count: None,
+                schedule_time: None,
+                tracking_data: Some(
+                    serde_json::to_value(&tracking_data)
+                        .into_report()
+                        .change_context(errors::ApiErrorResponse::InternalServerError)?,
+                ),
+                business_status: Some(ABORTED.to_string()),
+                status: Some(ProcessTrackerStatus::Finish),
+                updated_at: Some(common_utils::date_time::now()),
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to update the storage scheme migration task")?
+        .ok_or_else(|| {
+            report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: format!(
+                    "Storage scheme migration {process_id} was picked up by the scheduler, try again later"
+                ),
+            })
+        })?;
+
+    let redis_conn = get_redis_conn(db)?;
+    set_kv_writes_paused(&redis_conn, &merchant_id, false)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to resume the KV writes of the merchant")?;
+    set_dual_read(&redis_conn, &merchant_id, false)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to disable dual-read for the merchant")?;
+
+    Ok(ApplicationResponse::Json(
+        migration_response(&redis_conn, process, tracking_data).await?,
+    ))
+}
+
+/// Step taken by the migration workflow on a run
+#[derive(Debug, PartialEq)]
+enum MigrationStep {
+    /// Poll the drainer streams again later
+    WaitForDrainer,
+    /// Flush the stale Redis keys and switch the merchant to `RedisKv`
+    SwitchToRedisKv,
+    /// Pause the merchant's KV writes
+    PauseWrites,
+    /// Record the watermarks of the entries queued before the writes were paused
+    RecordWatermarks,
+    /// Switch the merchant to `PostgresOnly`, flush its Redis keys and resume its writes
+    SwitchToPostgresOnly,
+}
+
+fn get_next_step(
+    stage: StorageSchemeMigrationStage,
+    target_storage_scheme: MerchantStorageScheme,
+    is_drained: bool,
+) -> Option<MigrationStep> {
+    match (stage, target_storage_scheme) {
+        (StorageSchemeMigrationStage::PausingWrites, MerchantStorageScheme::PostgresOnly) => {
+            Some(MigrationStep::RecordWatermarks)
+        }
+        (
+            StorageSchemeMigrationStage::WaitingForDrainer
+            | StorageSchemeMigrationStage::WaitingForDrainerWithWritesPaused,
+            _,
+        ) if !is_drained => Some(MigrationStep::WaitForDrainer),
+        (StorageSchemeMigrationStage::WaitingForDrainer, MerchantStorageScheme::RedisKv) => {
+            Some(MigrationStep::SwitchToRedisKv)
+        }
+        (StorageSchemeMigrationStage::WaitingForDrainer, MerchantStorageScheme::PostgresOnly) => {
+            Some(MigrationStep::PauseWrites)
+        }
+        (
+            StorageSchemeMigrationStage::WaitingForDrainerWithWritesPaused,
+            MerchantStorageScheme::PostgresOnly,
+        ) => Some(MigrationStep::SwitchToPostgresOnly),
+        _ => None,
+    }
+}
+
+fn is_before_switch(stage: StorageSchemeMigrationStage) -> bool {
+    matches!(
+        stage,
+        StorageSchemeMigrationStage::WaitingForDrainer
+            | StorageSchemeMigrationStage::PausingWrites
+            | StorageSchemeMigrationStage::WaitingForDrainerWithWritesPaused
+    )
+}
+
+#[instrument(skip_all)]
+pub async fn start_storage_scheme_migration_workflow(
+    state: &AppState,
+    process: &storage::ProcessTracker,
+) -> Result<(), errors::ProcessTrackerError> {
+    let db = &*state.store;
+    let mut tracking_data: storage::StorageSchemeMigrationTrackingData = process
+        .tracking_data
+        .clone()
+        .parse_value("StorageSchemeMigrationTrackingData")?;
+    let redis_conn = db.get_redis_conn()?;
+
+    let is_drained = get_pending_drainer_streams(&redis_conn, &tracking_data.watermarks)
+        .await?
+        .is_empty();
+    let next_step = get_next_step(
+        tracking_data.stage,
+        tracking_data.target_storage_scheme,
+        is_drained,
+    )
+    .ok_or(errors::ProcessTrackerError::UnexpectedFlow)?;
+    let poll_time = common_utils::date_time::now()
+        .saturating_add(time::Duration::seconds(DRAINER_POLL_INTERVAL_IN_SECONDS));
+
+    let merchant_id = tracking_data.merchant_id.clone();
+    match next_step {
+        MigrationStep::WaitForDrainer => process.clone().retry(db.as_scheduler(), poll_time).await,
+        MigrationStep::SwitchToRedisKv => {
+            tracking_data.flushed_keys =
+                Some(flush_merchant_kv_keys(&redis_conn, &merchant_id).await?);
+            switch_storage_scheme(db, &merchant_id, MerchantStorageScheme::RedisKv).await?;
+            set_dual_read(&redis_conn, &merchant_id, false).await?;
+            finish_migration(db, process.clone(), tracking_data).await
+        }
+        MigrationStep::PauseWrites => {
+            set_kv_writes_paused(&redis_conn, &merchant_id, true).await?;
+            // Writes that checked the flag before it was set, or whose instance cached it before,
+            // may still be queued, so the watermarks are only recorded on the next run
+            tracking_data.stage = StorageSchemeMigrationStage::PausingWrites;
+            update_migration(db, process.clone(), &tracking_data, poll_time).await
+        }
+        MigrationStep::RecordWatermarks => {
+            tracking_data.watermarks =
+                get_drainer_stream_watermarks(state, &redis_conn, &merchant_id).await?;
+            tracking_data.stage = StorageSchemeMigrationStage::WaitingForDrainerWithWritesPaused;
+            update_migration(
+                db,
+                process.clone(),
+                &tracking_data,
+                common_utils::date_time::now(),
+            )
+            .await
+        }
+        MigrationStep::SwitchToPostgresOnly => {
+            switch_storage_scheme(db, &merchant_id, MerchantStorageScheme::PostgresOnly).await?;
+            tracking_data.flushed_keys =
+                Some(flush_merchant_kv_keys(&redis_conn, &merchant_id).await?);
+            set_kv_writes_paused(&redis_conn, &merchant_id, false).await?;
+            set_dual_read(&redis_conn, &merchant_id, false).await?;
+            finish_migration(db, process.clone(), tracking_data).await
+        }
+    }
+}
+
+async fn update_migration(
+    db: &dyn StorageInterface,
+    process: storage::ProcessTracker,
+    tracking_data: &storage::StorageSchemeMigrationTrackingData,
+    schedule_time: time::PrimitiveDateTime,
+) -> Result<(), errors::ProcessTrackerError> {
+    db.update_process(
+        process,
+        storage::ProcessTrackerUpdate::Update {
+            name: None,
+            retry_count: None,
+            schedule_time: Some(schedule_time),
+            tracking_data: Some(
+                serde_json::to_value(tracking_data)
+                    .map_err(|_| errors::ProcessTrackerError::SerializationFailed)?,
+            ),
+            business_status: None,
+            status: Some(ProcessTrackerStatus::Pending),
+            updated_at: Some(common_utils::date_time::now()),
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+async fn finish_migration(
+    db: &dyn StorageInterface,
+    process: storage::ProcessTracker,
+    mut tracking_data: storage::StorageSchemeMigrationTrackingData,
+) -> Result<(), errors::ProcessTrackerError> {
+    tracking_data.stage = StorageSchemeMigrationStage::Completed;
+    db.update_process(
+        process,
+        storage::ProcessTrackerUpdate::Update {
+            name: None,
+            retry_count: None,
+            schedule_time: None,
+            tracking_data: Some(
+                serde_json::to_value(&tracking_data)
+                    .map_err(|_| errors::ProcessTrackerError::SerializationFailed)?,
+            ),
+            business_status: Some(COMPLETED.to_string()),
+            status: Some(ProcessTrackerStatus::Finish),
+            updated_at: Some(common_utils::date_time::now()),
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+async fn switch_storage_scheme(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    storage_scheme: MerchantStorageScheme,
+) -> Result<(), errors::ProcessTrackerError> {
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
+        .await?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await?;
+    db.update_merchant(
+        merchant_account,
+        storage::MerchantAccountUpdate::StorageSchemeUpdate { storage_scheme },
+        &key_store,
+    )
+    .await?;
+    logger::info!(%merchant_id, %storage_scheme, "Switched merchant storage scheme");
+    Ok(())
+}
+
+async fn get_merchant_account(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> RouterResult<(domain::MerchantAccount, domain::MerchantKeyStore)> {
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    Ok((merchant_account, key_store))
+}
+
+async fn find_latest_migration(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> RouterResult<
+    Option<(
+        storage::ProcessTracker,
+        storage::StorageSchemeMigrationTrackingData,
+    )>,
+> {
+    let latest_process = db
+        .filter_processes_by_constraints(storage::ProcessTrackerListConstraints {
+            runner: Some(STORAGE_SCHEME_MIGRATION_RUNNER.to_string()),
+            merchant_id: Some(merchant_id.to_string()),
+            ..Default::default()
+        })
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list the storage scheme migration tasks")?
+        .into_iter()
+        .max_by_key(|process| process.created_at);
+
+    latest_process
+        .map(|process| {
+            let tracking_data = process
+                .tracking_data
+                .clone()
+                .parse_value("StorageSchemeMigrationTrackingData")
+                .change_context(errors::ApiErrorResponse::InternalServerError)?;
+            Ok((process, tracking_data))
+        })
+        .transpose()
+}
+
+async fn migration_response(
+    redis_conn: &RedisConnectionPool,
+    process: storage::ProcessTracker,
+    tracking_data: storage::StorageSchemeMigrationTrackingData,
+) -> RouterResult<StorageSchemeMigrationResponse> {
+    let pending_drainer_streams = match tracking_data.stage {
+        StorageSchemeMigrationStage::WaitingForDrainer
+        | StorageSchemeMigrationStage::WaitingForDrainerWithWritesPaused => {
+            get_pending_drainer_streams(redis_conn, &tracking_data.watermarks)
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to read the drainer streams")?
+        }
+        StorageSchemeMigrationStage::PausingWrites
+        | StorageSchemeMigrationStage::Completed
+        | StorageSchemeMigrationStage::Aborted => Vec::new(),
+    };
+
+    Ok(StorageSchemeMigrationResponse {
+        merchant_id: tracking_data.merchant_id,
+        migration_id: process.id,
+        source_storage_scheme: tracking_data.source_storage_scheme,
+        target_storage_scheme: tracking_data.target_storage_scheme,
+        stage: tracking_data.stage,
+        pending_drainer_streams,
+        flushed_keys: tracking_data.flushed_keys,
+        created_at: process.created_at,
+        updated_at: process.updated_at,
+    })
+}
+
+fn get_redis_conn(db: &dyn StorageInterface) -> RouterResult<std::sync::Arc<RedisConnectionPool>> {
+    db.get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")
+}
+
+async fn set_dual_read(
+    redis_conn: &RedisConnectionPool,
+    merchant_id: &str,
+    enable: bool,
+) -> errors::CustomResult<(), errors::RedisError> {
+    set_merchant_flag(redis_conn, &get_dual_read_key(merchant_id), enable).await
+}
+
+async fn set_kv_writes_paused(
+    redis_conn: &RedisConnectionPool,
+    merchant_id: &str,
+    pause: bool,
+) -> errors::CustomResult<(), errors::RedisError> {
+    set_merchant_flag(redis_conn, &get_kv_writes_paused_key(merchant_id), pause).await
+}
+
+async fn set_merchant_flag(
+    redis_conn: &RedisConnectionPool,
+    key: &str,
+    set: bool,
+) -> errors::CustomResult<(), errors::RedisError> {
+    if set {
+        redis_conn.set_key_without_expiry(key, "true").await
+    } else {
+        redis_conn.delete_key(key).await.map(|_| ())
+    }
+}
+
+/// Returns the keys holding the KV rows of the merchant and their reverse lookups
+async fn get_merchant_kv_keys(
+    redis_conn: &RedisConnectionPool,
+    merchant_id: &str,
+) -> errors::CustomResult<Vec<String>, errors::RedisError> {
+    let mut keys = Vec::new();
+    for pattern in get_merchant_kv_key_patterns(merchant_id) {
+        keys.extend(redis_conn.scan_keys(&pattern, None).await?);
+    }
+    keys.sort();
+    keys.dedup();
+
+    let mut merchant_keys = Vec::new();
+    for key in keys {
+        if is_merchant_kv_key(redis_conn, &key, merchant_id).await? {
+            merchant_keys.push(key);
+        }
+    }
+    Ok(merchant_keys)
+}
+
+/// Records the ID of the last entry of the drainer streams of the partitions holding the
+/// merchant's keys and their reverse lookups. The keys are partitioned on their own name, see
+/// [`PartitionKey`].
+async fn get_drainer_stream_watermarks(
+    state: &AppState,
+    redis_conn: &RedisConnectionPool,
+    merchant_id: &str,
+) -> errors::CustomResult<Vec<storage::DrainerStreamWatermark>, errors::RedisError> {
+    let stream_names = get_merchant_kv_keys(redis_conn, merchant_id)
+        .await?
+        .iter()
+        .map(|key| {
+            let shard_key = <diesel_models::PaymentIntent as KvStorePartition>::shard_key(
+                PartitionKey::MerchantIdPaymentIdCombination { combination: key },
+                state.conf.drainer.num_partitions,
+            );
+            format!("{{{shard_key}}}_{}", state.conf.drainer.stream_name)
+        })
+        .collect::<BTreeSet<_>>();
+
+    let mut watermarks = Vec::new();
+    for stream_name in stream_names {
+        if let Some(entry_id) = redis_conn.stream_get_last_entry_id(&stream_name).await? {
+            watermarks.push(storage::DrainerStreamWatermark {
+                stream_name,
+                entry_id,
+            });
+        }
+    }
+    Ok(watermarks)
+}
+
+/// The drainer trims the entries it applied, so a stream is drained past its watermark once its
+/// first entry is newer than the watermark.
+async fn get_pending_drainer_streams(
+    redis_conn: &RedisConnectionPool,
+    watermarks: &[storage::DrainerStreamWatermark],
+) -> errors::CustomResult<Vec<String>, errors::RedisError> {
+    let mut pending_streams = Vec::new();
+    for watermark in watermarks {
+        let first_entry = redis_conn
+            .stream_read_range(&watermark.stream_name, "-", "+", Some(1))
+            .await?;
+        let is_pending = first_entry.first().map_or(false, |(entry_id, _)| {
+            parse_stream_entry_id(entry_id) <= parse_stream_entry_id(&watermark.entry_id)
+        });
+        if is_pending {
+            pending_streams.push(watermark.stream_name.clone());
+        }
+    }
+    Ok(pending_streams)
+}
+
+/// Stream entry IDs are made of a milliseconds timestamp and a sequence number
+fn parse_stream_entry_id(entry_id: &str) -> (u64, u64) {
+    let (timestamp, sequence) = entry_id.split_once('-').unwrap_or((entry_id, "0"));
+    (
+        timestamp.parse().unwrap_or_default(),
+        sequence.parse().unwrap_or_default(),
+    )
+}
+
+async fn flush_merchant_kv_keys(
+    redis_conn: &RedisConnectionPool,
+    merchant_id: &str,
+) -> errors::CustomResult<usize, errors::RedisError> {
+    let keys = get_merchant_kv_keys(redis_conn, merchant_id).await?;
+    for key in &keys {
+        redis_conn.delete_key(key).await?;
+    }
+    logger::info!(%merchant_id, flushed_keys = keys.len(), "Flushed merchant KV keys");
+    Ok(keys.len())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_migration_to_redis_kv_switches_once_drained() {
+        assert_eq!(
+            get_next_step(
+                StorageSchemeMigrationStage::WaitingForDrainer,
+                MerchantStorageScheme::RedisKv,
+                false,
+            ),
+            Some(MigrationStep::WaitForDrainer)
+        );
+        assert_eq!(
+            get_next_step(
+                StorageSchemeMigrationStage::WaitingForDrainer,
+                MerchantStorageScheme::RedisKv,
+                true,
+            ),
+            Some(MigrationStep::SwitchToRedisKv)
+        );
+    }
+
+    #[test]
+    fn test_migration_to_postgres_only_drains_with_writes_paused_before_switching() {
+        let target = MerchantStorageScheme::PostgresOnly;
+        assert_eq!(
+            get_next_step(
+                StorageSchemeMigrationStage::WaitingForDrainer,
+                target,
+                false
+            ),
+            Some(MigrationStep::WaitForDrainer)
+        );
+        assert_eq!(
+            get_next_step(StorageSchemeMigrationStage::WaitingForDrainer, target, true),
+            Some(MigrationStep::PauseWrites)
+        );
+        // The watermarks of the previous stage say nothing about the writes in flight
+        assert_eq!(
+            get_next_step(StorageSchemeMigrationStage::PausingWrites, target, true),
+            Some(MigrationStep::RecordWatermarks)
+        );
+        assert_eq!(
+            get_next_step(
+                StorageSchemeMigrationStage::WaitingForDrainerWithWritesPaused,
+                target,
+                false,
+            ),
+            Some(MigrationStep::WaitForDrainer)
+        );
+        assert_eq!(
+            get_next_step(
+                StorageSchemeMigrationStage::WaitingForDrainerWithWritesPaused,
+                target,
+                true,
+            ),
+            Some(MigrationStep::SwitchToPostgresOnly)
+        );
+    }
+
+    #[test]
+    fn test_finished_migrations_have_no_next_step() {
+        for stage in [
+            StorageSchemeMigrationStage::Completed,
+            StorageSchemeMigrationStage::Aborted,
+        ] {
+            for target in [
+                MerchantStorageScheme::PostgresOnly,
+                MerchantStorageScheme::RedisKv,
+            ] {
+                assert_eq!(get_next_step(stage, target, true), None);
+                assert!(!is_before_switch(stage));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_stream_entry_id() {
+        assert_eq!(parse_stream_entry_id("1700000000000-5"), (1700000000000, 5));
+        assert_eq!(parse_stream_entry_id("1700000000000"), (1700000000000, 0));
+        assert!(
+            parse_stream_entry_id("1700000000000-10") > parse_stream_entry_id("1700000000000-9")
+        );
+    }
+}
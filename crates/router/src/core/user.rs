@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use api_models::user as user_api;
 #[cfg(feature = "email")]
 use diesel_models::user_role::UserRoleNew;
@@ -24,6 +26,8 @@ use crate::{
 pub mod dashboard_metadata;
 #[cfg(feature = "dummy_connector")]
 pub mod sample_data;
+pub mod sso;
+pub mod two_factor_auth;
 
 #[cfg(feature = "email")]
 pub async fn signup_with_merchant_id(
@@ -119,12 +123,8 @@ pub async fn signin(
 
     user_from_db.compare_password(request.password)?;
 
-    let user_role = user_from_db.get_role_from_db(state.clone()).await?;
-    let token =
-        utils::user::generate_jwt_auth_token(state.clone(), &user_from_db, &user_role).await?;
-
     Ok(ApplicationResponse::Json(
-        utils::user::get_dashboard_entry_response(state, user_from_db, user_role, token)?,
+        utils::user::get_sign_in_response(state, user_from_db).await?,
     ))
 }
 
@@ -359,7 +359,12 @@ pub async fn invite_user(
             .attach_printable("User Inviting themself");
     }
 
-    utils::user_role::validate_role_id(request.role_id.as_str())?;
+    utils::user_role::validate_role_id(
+        &state,
+        request.role_id.as_str(),
+        user_from_token.merchant_id.as_str(),
+    )
+    .await?;
     let invitee_email = domain::UserEmail::from_pii_email(request.email.clone())?;
 
     let invitee_user = state
@@ -536,14 +541,20 @@ pub async fn switch_merchant_id(
         .await
         .change_context(UserErrors::InternalServerError)?;
 
+    // Signing in through the single sign-on of an organization only grants access to its merchants
     let active_user_roles = user_roles
         .into_iter()
-        .filter(|role| role.status == UserStatus::Active)
+        .filter(|role| {
+            role.status == UserStatus::Active
+                && (!user_from_token.org_scoped || role.org_id == user_from_token.org_id)
+        })
         .collect::<Vec<_>>();
 
     let user = user_from_token.get_user(state.clone()).await?.into();
 
-    let (token, role_id) = if utils::user_role::is_internal_role(&user_from_token.role_id) {
+    let (token, role_id) = if !user_from_token.org_scoped
+        && utils::user_role::is_internal_role(&user_from_token.role_id)
+    {
         let key_store = state
             .store
             .get_merchant_key_store_by_merchant_id(
@@ -588,7 +599,11 @@ pub async fn switch_merchant_id(
             .ok_or(UserErrors::InvalidRoleOperation.into())
             .attach_printable("User doesn't have access to switch")?;
 
-        let token = utils::user::generate_jwt_auth_token(state, &user, user_role).await?;
+        let token = if user_from_token.org_scoped {
+            utils::user::generate_org_scoped_jwt_auth_token(state, &user, user_role).await?
+        } else {
+            utils::user::generate_jwt_auth_token(state, &user, user_role).await?
+        };
         (token, user_role.role_id.clone())
     };
 
@@ -664,6 +679,15 @@ pub async fn get_users_for_merchant_account(
     state: AppState,
     user_from_token: auth::UserFromToken,
 ) -> UserResponse<user_api::GetUsersResponse> {
+    let custom_role_names: HashMap<String, String> = state
+        .store
+        .list_roles_by_merchant_id(user_from_token.merchant_id.as_str())
+        .await
+        .change_context(UserErrors::InternalServerError)?
+        .into_iter()
+        .map(|role| (role.role_id, role.role_name))
+        .collect();
+
     let users = state
         .store
         .find_users_and_roles_by_merchant_id(user_from_token.merchant_id.as_str())
@@ -671,7 +695,12 @@ pub async fn get_users_for_merchant_account(
         .change_context(UserErrors::InternalServerError)
         .attach_printable("No users for given merchant id")?
         .into_iter()
-        .filter_map(|(user, role)| domain::UserAndRoleJoined(user, role).try_into().ok())
+        .filter_map(|(user, role)| {
+            let custom_role_name = custom_role_names.get(&role.role_id).cloned();
+            domain::UserAndRoleJoined(user, role, custom_role_name)
+                .try_into()
+                .ok()
+        })
         .collect();
 
     Ok(ApplicationResponse::Json(user_api::GetUsersResponse(users)))
@@ -699,12 +728,9 @@ pub async fn verify_email(
         .change_context(UserErrors::InternalServerError)?;
 
     let user_from_db: domain::UserFromStorage = user.into();
-    let user_role = user_from_db.get_role_from_db(state.clone()).await?;
-    let token =
-        utils::user::generate_jwt_auth_token(state.clone(), &user_from_db, &user_role).await?;
 
     Ok(ApplicationResponse::Json(
-        utils::user::get_dashboard_entry_response(state, user_from_db, user_role, token)?,
+        utils::user::get_sign_in_response(state, user_from_db).await?,
     ))
 }
 
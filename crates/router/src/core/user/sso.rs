@@ -0,0 +1,749 @@
+use api_models::user::{self as user_api, sso as sso_api};
+use common_utils::ext_traits::ValueExt;
+use diesel_models::{
+    enums::UserStatus,
+    oidc_config::{OidcConfig, OidcConfigNew, OidcConfigUpdate, OidcIdentity, OidcIdentityNew},
+    user as storage_user,
+    user_role::{UserRole, UserRoleNew, UserRoleUpdate},
+};
+use error_stack::{IntoReport, ResultExt};
+use masking::{ExposeInterface, Secret};
+
+use crate::{
+    consts,
+    core::errors::{UserErrors, UserResponse, UserResult},
+    routes::AppState,
+    services::{
+        authentication::{SsoRole, TwoFactorAuthToken, UserFromToken},
+        ApplicationResponse,
+    },
+    types::domain,
+    utils::{self, user::oidc},
+};
+
+pub async fn set_oidc_config(
+    state: AppState,
+    user_from_token: UserFromToken,
+    req: sso_api::SetOidcConfigRequest,
+) -> UserResponse<sso_api::OidcConfigResponse> {
+    if user_from_token.role_id != consts::user_role::ROLE_ID_ORGANIZATION_ADMIN {
+        return Err(UserErrors::InvalidRoleOperation.into())
+            .attach_printable("Only organization admins can configure single sign-on");
+    }
+
+    validate_url(&state, &req.issuer_url, "issuer_url")?;
+    validate_url(&state, &req.redirect_url, "redirect_url")?;
+    oidc::validate_provider_url(&state, &req.issuer_url)
+        .await
+        .change_context(UserErrors::SsoConfigParsingError(
+            "issuer_url must be a public HTTPS URL".to_string(),
+        ))?;
+    for role_id in req
+        .role_mappings
+        .iter()
+        .map(|role_mapping| &role_mapping.role_id)
+        .chain(req.default_role_id.iter())
+    {
+        utils::user_role::validate_role_id(&state, role_id, &user_from_token.merchant_id).await?;
+    }
+
+    // Fails early if the identity provider cannot be reached at the issuer URL
+    oidc::get_provider_metadata(&state, &req.issuer_url).await?;
+
+    let existing_config = find_oidc_config(&state, &user_from_token.org_id).await?;
+    let master_key = state.store.get_master_key();
+    let client_secret = match (req.client_secret, &existing_config) {
+        (Some(client_secret), _) => domain::types::encrypt(client_secret, master_key)
+            .await
+            .change_context(UserErrors::InternalServerError)?
+            .into(),
+        (None, Some(existing_config)) => existing_config.client_secret.clone(),
+        (None, None) => {
+            return Err(
+                UserErrors::SsoConfigParsingError("client_secret is required".to_string()).into(),
+            )
+        }
+    };
+    let role_mappings = serde_json::to_value(req.role_mappings)
+        .into_report()
+        .change_context(UserErrors::InternalServerError)?;
+    let groups_claim = req
+        .groups_claim
+        .unwrap_or_else(|| consts::user::OIDC_DEFAULT_GROUPS_CLAIM.to_string());
+    let is_enabled = req.is_enabled.unwrap_or(true);
+
+    let oidc_config = if existing_config.is_some() {
+        state
+            .store
+            .update_oidc_config_by_org_id(
+                &user_from_token.org_id,
+                OidcConfigUpdate::Update {
+                    merchant_id: user_from_token.merchant_id,
+                    issuer_url: req.issuer_url,
+                    client_id: req.client_id,
+                    client_secret,
+                    redirect_url: req.redirect_url,
+                    groups_claim,
+                    role_mappings,
+                    default_role_id: req.default_role_id,
+                    is_enabled,
+                    last_modified_by: user_from_token.user_id,
+                },
+            )
+            .await
+    } else {
+        let now = common_utils::date_time::now();
+        state
+            .store
+            .insert_oidc_config(OidcConfigNew {
+                org_id: user_from_token.org_id,
+                merchant_id: user_from_token.merchant_id,
+                issuer_url: req.issuer_url,
+                client_id: req.client_id,
+                client_secret,
+                redirect_url: req.redirect_url,
+                groups_claim,
+                role_mappings,
+                default_role_id: req.default_role_id,
+                is_enabled,
+                created_by: user_from_token.user_id.clone(),
+                created_at: now,
+                last_modified_by: user_from_token.user_id,
+                last_modified_at: now,
+            })
+            .await
+    }
+    .change_context(UserErrors::InternalServerError)?;
+
+    Ok(ApplicationResponse::Json(get_oidc_config_response(
+        oidc_config,
+    )?))
+}
+
+pub async fn get_oidc_config(
+    state: AppState,
+    user_from_token: UserFromToken,
+) -> UserResponse<sso_api::OidcConfigResponse> {
+    let oidc_config = find_oidc_config(&state, &user_from_token.org_id)
+        .await?
+        .ok_or(UserErrors::SsoNotConfigured)?;
+
+    Ok(ApplicationResponse::Json(get_oidc_config_response(
+        oidc_config,
+    )?))
+}
+
+pub async fn authorize_with_oidc(
+    state: AppState,
+    req: sso_api::OidcAuthorizeRequest,
+) -> UserResponse<sso_api::OidcAuthorizeResponse> {
+    get_authorization_url(state, req.org_id, None).await
+}
+
+/// Begins linking an identity of the identity provider of the organization to the signed in
+/// user, completed by [`oidc_link_callback`]
+pub async fn link_with_oidc(
+    state: AppState,
+    user_from_token: UserFromToken,
+    req: sso_api::OidcAuthorizeRequest,
+) -> UserResponse<sso_api::OidcAuthorizeResponse> {
+    get_authorization_url(state, req.org_id, Some(user_from_token.user_id)).await
+}
+
+pub async fn oidc_callback(
+    state: AppState,
+    req: sso_api::OidcCallbackRequest,
+) -> UserResponse<sso_api::OidcCallbackResponse> {
+    let authorization_state = oidc::take_authorization_state(&state, &req.state).await?;
+    if authorization_state.link_user_id.is_some() {
+        return Err(UserErrors::SsoStateInvalid)
+            .into_report()
+            .attach_printable("Linking state used to sign in");
+    }
+    let oidc_config = find_enabled_oidc_config(&state, &authorization_state.org_id).await?;
+    let (issuer, claims) =
+        get_id_token_claims(&state, &oidc_config, authorization_state, req.code).await?;
+
+    let role_id =
+        get_role_id_for_groups(&oidc_config, &claims.get_groups(&oidc_config.groups_claim))?;
+    utils::user_role::validate_role_id(&state, &role_id, &oidc_config.merchant_id).await?;
+
+    let user = provision_user(&state, &oidc_config, &issuer, claims).await?;
+    let user_role = update_user_role(&state, &oidc_config, user.get_user_id(), role_id).await?;
+
+    if user.is_two_factor_auth_setup() {
+        let sso_role = SsoRole {
+            org_id: user_role.org_id,
+            merchant_id: user_role.merchant_id,
+            role_id: user_role.role_id,
+        };
+        let token = TwoFactorAuthToken::new_token(
+            user.get_user_id().to_string(),
+            Some(sso_role),
+            &state.conf,
+        )
+        .await?;
+        return Ok(ApplicationResponse::Json(
+            user_api::SignInResponse::TwoFactorAuth(user_api::TwoFactorAuthRequiredResponse {
+                token: token.into(),
+                email: user.get_email(),
+            }),
+        ));
+    }
+
+    let token =
+        utils::user::generate_org_scoped_jwt_auth_token(state.clone(), &user, &user_role).await?;
+
+    Ok(ApplicationResponse::Json(
+        user_api::SignInResponse::DashboardEntry(utils::user::get_dashboard_entry_response(
+            state, user, user_role, token,
+        )?),
+    ))
+}
+
+pub async fn oidc_link_callback(
+    state: AppState,
+    user_from_token: UserFromToken,
+    req: sso_api::OidcCallbackRequest,
+) -> UserResponse<()> {
+    let authorization_state = oidc::take_authorization_state(&state, &req.state).await?;
+    if authorization_state.link_user_id.as_deref() != Some(user_from_token.user_id.as_str()) {
+        return Err(UserErrors::SsoStateInvalid)
+            .into_report()
+            .attach_printable("State not issued to link the identity to the signed in user");
+    }
+    let oidc_config = find_enabled_oidc_config(&state, &authorization_state.org_id).await?;
+    let (issuer, claims) =
+        get_id_token_claims(&state, &oidc_config, authorization_state, req.code).await?;
+
+    match find_oidc_identity(&state, &oidc_config.org_id, &issuer, &claims.sub).await? {
+        Some(identity) if identity.user_id == user_from_token.user_id => {}
+        Some(_) => return Err(UserErrors::SsoIdentityAlreadyLinked.into()),
+        None => {
+            insert_oidc_identity(
+                &state,
+                &oidc_config.org_id,
+                issuer,
+                claims.sub,
+                user_from_token.user_id,
+            )
+            .await?;
+        }
+    }
+
+    Ok(ApplicationResponse::StatusOk)
+}
+
+async fn get_authorization_url(
+    state: AppState,
+    org_id: String,
+    link_user_id: Option<String>,
+) -> UserResponse<sso_api::OidcAuthorizeResponse> {
+    let oidc_config = find_enabled_oidc_config(&state, &org_id).await?;
+    let metadata = oidc::get_provider_metadata(&state, &oidc_config.issuer_url).await?;
+
+    let csrf_state = oidc::generate_state();
+    let authorization_state = oidc::AuthorizationState::new(org_id, link_user_id);
+    let authorization_url = oidc::get_authorization_url(
+        &metadata,
+        &oidc_config.client_id,
+        &oidc_config.redirect_url,
+        &csrf_state,
+        &authorization_state,
+    )?;
+    oidc::store_authorization_state(&state, &csrf_state, &authorization_state).await?;
+
+    Ok(ApplicationResponse::Json(sso_api::OidcAuthorizeResponse {
+        authorization_url,
+    }))
+}
+
+/// Exchanges the authorization code for the validated claims of the ID token, along with the
+/// issuer they were validated against
+async fn get_id_token_claims(
+    state: &AppState,
+    oidc_config: &OidcConfig,
+    authorization_state: oidc::AuthorizationState,
+    code: Secret<String>,
+) -> UserResult<(String, oidc::IdTokenClaims)> {
+    let metadata = oidc::get_provider_metadata(state, &oidc_config.issuer_url).await?;
+
+    let client_secret = domain::types::decrypt::<String, masking::WithType>(
+        Some(oidc_config.client_secret.clone()),
+        state.store.get_master_key(),
+    )
+    .await
+    .change_context(UserErrors::InternalServerError)?
+    .map(common_utils::crypto::Encryptable::into_inner)
+    .ok_or(UserErrors::InternalServerError)?;
+
+    let id_token = oidc::exchange_code_for_id_token(
+        state,
+        &metadata,
+        oidc_config.client_id.clone(),
+        client_secret,
+        oidc_config.redirect_url.clone(),
+        code,
+        authorization_state.code_verifier,
+    )
+    .await?;
+    let claims = oidc::validate_id_token(
+        state,
+        &metadata,
+        &id_token,
+        &oidc_config.client_id,
+        &authorization_state.nonce,
+    )
+    .await?;
+
+    Ok((metadata.issuer, claims))
+}
+
+/// Finds the user the identity is linked to, creating the user on their first sign in.
+///
+/// An identity not linked yet is never linked to an existing user with the same email, existing
+/// users link their identity themselves after signing in, see [`link_with_oidc`].
+async fn provision_user(
+    state: &AppState,
+    oidc_config: &OidcConfig,
+    issuer: &str,
+    claims: oidc::IdTokenClaims,
+) -> UserResult<domain::UserFromStorage> {
+    if let Some(identity) =
+        find_oidc_identity(state, &oidc_config.org_id, issuer, &claims.sub).await?
+    {
+        return state
+            .store
+            .find_user_by_id(&identity.user_id)
+            .await
+            .change_context(UserErrors::InternalServerError)
+            .map(Into::into);
+    }
+
+    let email = get_verified_email(&claims)?;
+    let find_user = state
+        .store
+        .find_user_by_email(email.clone().into_inner().expose().expose().as_str())
+        .await;
+
+    let user = match find_user {
+        Ok(_) => {
+            return Err(UserErrors::SsoIdentityNotLinked)
+                .into_report()
+                .attach_printable("User with the email exists but has not linked the identity");
+        }
+        Err(e) if e.current_context().is_db_not_found() => {
+            let name = claims
+                .name
+                .clone()
+                .and_then(|name| domain::UserName::new(name).ok())
+                .map_or_else(|| get_name_from_email(&email), Ok)?;
+            let new_user = domain::NewUser::try_from((email, name, oidc_config.to_owned()))?;
+
+            let mut store_user: storage_user::UserNew = new_user.try_into()?;
+            store_user.set_is_verified(true);
+            state.store.insert_user(store_user).await.map_err(|e| {
+                if e.current_context().is_db_unique_violation() {
+                    e.change_context(UserErrors::UserExists)
+                } else {
+                    e.change_context(UserErrors::InternalServerError)
+                }
+            })?
+        }
+        Err(e) => return Err(e.change_context(UserErrors::InternalServerError)),
+    };
+
+    insert_oidc_identity(
+        state,
+        &oidc_config.org_id,
+        issuer.to_owned(),
+        claims.sub,
+        user.user_id.clone(),
+    )
+    .await?;
+
+    Ok(user.into())
+}
+
+/// Providers omitting `email_verified` are not trusted to have verified the email
+fn get_verified_email(claims: &oidc::IdTokenClaims) -> UserResult<domain::UserEmail> {
+    if claims.email_verified != Some(true) {
+        return Err(UserErrors::SsoIdTokenInvalid)
+            .into_report()
+            .attach_printable("Email not verified by the identity provider");
+    }
+    domain::UserEmail::new(
+        claims
+            .email
+            .clone()
+            .ok_or(UserErrors::SsoIdTokenInvalid)
+            .into_report()
+            .attach_printable("Email claim not found in ID token")?,
+    )
+}
+
+async fn find_oidc_identity(
+    state: &AppState,
+    org_id: &str,
+    issuer: &str,
+    subject: &str,
+) -> UserResult<Option<OidcIdentity>> {
+    match state
+        .store
+        .find_oidc_identity_by_org_id_issuer_subject(org_id, issuer, subject)
+        .await
+    {
+        Ok(identity) => Ok(Some(identity)),
+        Err(e) if e.current_context().is_db_not_found() => Ok(None),
+        Err(e) => Err(e.change_context(UserErrors::InternalServerError)),
+    }
+}
+
+/// Fails if either the identity or the user is already linked within the organization
+async fn insert_oidc_identity(
+    state: &AppState,
+    org_id: &str,
+    issuer: String,
+    subject: String,
+    user_id: String,
+) -> UserResult<OidcIdentity> {
+    state
+        .store
+        .insert_oidc_identity(OidcIdentityNew {
+            org_id: org_id.to_owned(),
+            issuer,
+            subject,
+            user_id,
+            created_at: common_utils::date_time::now(),
+        })
+        .await
+        .map_err(|e| {
+            if e.current_context().is_db_unique_violation() {
+                e.change_context(UserErrors::SsoIdentityAlreadyLinked)
+            } else {
+                e.change_context(UserErrors::InternalServerError)
+            }
+        })
+}
+
+async fn update_user_role(
+    state: &AppState,
+    oidc_config: &OidcConfig,
+    user_id: &str,
+    role_id: String,
+) -> UserResult<UserRole> {
+    let user_role = state
+        .store
+        .list_user_roles_by_user_id(user_id)
+        .await
+        .change_context(UserErrors::InternalServerError)?
+        .into_iter()
+        .find(|user_role| user_role.merchant_id == oidc_config.merchant_id);
+
+    match user_role {
+        // Organization admins keep their role, so that single sign-on cannot lock them out of
+        // its configuration
+        Some(user_role)
+            if user_role.role_id == consts::user_role::ROLE_ID_ORGANIZATION_ADMIN
+                || (user_role.role_id == role_id && user_role.status == UserStatus::Active) =>
+        {
+            Ok(user_role)
+        }
+        Some(user_role) => {
+            let user_role = if user_role.role_id == role_id {
+                user_role
+            } else {
+                state
+                    .store
+                    .update_user_role_by_user_id_merchant_id(
+                        user_id,
+                        &oidc_config.merchant_id,
+                        UserRoleUpdate::UpdateRole {
+                            role_id,
+                            modified_by: user_id.to_owned(),
+                        },
+                    )
+                    .await
+                    .change_context(UserErrors::InternalServerError)?
+            };
+            if user_role.status == UserStatus::Active {
+                return Ok(user_role);
+            }
+            state
+                .store
+                .update_user_role_by_user_id_merchant_id(
+                    user_id,
+                    &oidc_config.merchant_id,
+                    UserRoleUpdate::UpdateStatus {
+                        status: UserStatus::Active,
+                        modified_by: user_id.to_owned(),
+                    },
+                )
+                .await
+                .change_context(UserErrors::InternalServerError)
+        }
+        None => {
+            let now = common_utils::date_time::now();
+            state
+                .store
+                .insert_user_role(UserRoleNew {
+                    user_id: user_id.to_owned(),
+                    merchant_id: oidc_config.merchant_id.clone(),
+                    role_id,
+                    org_id: oidc_config.org_id.clone(),
+                    status: UserStatus::Active,
+                    created_by: user_id.to_owned(),
+                    last_modified_by: user_id.to_owned(),
+                    created_at: now,
+                    last_modified: now,
+                })
+                .await
+                .change_context(UserErrors::InternalServerError)
+        }
+    }
+}
+
+/// The first mapping whose group the user belongs to decides their role
+fn get_role_id_for_groups(oidc_config: &OidcConfig, groups: &[String]) -> UserResult<String> {
+    let role_mappings: Vec<sso_api::OidcRoleMapping> = oidc_config
+        .role_mappings
+        .clone()
+        .parse_value("Vec<OidcRoleMapping>")
+        .change_context(UserErrors::InternalServerError)?;
+
+    role_mappings
+        .into_iter()
+        .find(|role_mapping| groups.contains(&role_mapping.group))
+        .map(|role_mapping| role_mapping.role_id)
+        .or_else(|| oidc_config.default_role_id.clone())
+        .ok_or(UserErrors::SsoNoRoleMapped)
+        .into_report()
+        .attach_printable_lazy(|| format!("No role mapped for groups {groups:?}"))
+}
+
+fn get_name_from_email(email: &domain::UserEmail) -> UserResult<domain::UserName> {
+    let email = email.clone().into_inner().expose().expose();
+    let name = email
+        .split_once('@')
+        .map_or(email.as_str(), |(name, _domain)| name);
+    domain::UserName::new(Secret::new(name.to_string()))
+}
+
+async fn find_oidc_config(state: &AppState, org_id: &str) -> UserResult<Option<OidcConfig>> {
+    match state.store.find_oidc_config_by_org_id(org_id).await {
+        Ok(oidc_config) => Ok(Some(oidc_config)),
+        Err(e) if e.current_context().is_db_not_found() => Ok(None),
+        Err(e) => Err(e.change_context(UserErrors::InternalServerError)),
+    }
+}
+
+async fn find_enabled_oidc_config(state: &AppState, org_id: &str) -> UserResult<OidcConfig> {
+    find_oidc_config(state, org_id)
+        .await?
+        .filter(|oidc_config| oidc_config.is_enabled)
+        .ok_or(UserErrors::SsoNotConfigured)
+        .into_report()
+}
+
+/// Plain HTTP is only allowed along with insecure identity providers, for local development
+fn validate_url(state: &AppState, url: &str, field_name: &str) -> UserResult<()> {
+    let url =
+        url::Url::parse(url)
+            .into_report()
+            .change_context(UserErrors::SsoConfigParsingError(format!(
+                "Invalid {field_name}"
+            )))?;
+    let is_allowed_scheme = url.scheme() == "https"
+        || (url.scheme() == "http" && state.conf.sso.allow_insecure_providers);
+    if !is_allowed_scheme {
+        return Err(UserErrors::SsoConfigParsingError(format!(
+            "{field_name} must be an HTTPS URL"
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+fn get_oidc_config_response(oidc_config: OidcConfig) -> UserResult<sso_api::OidcConfigResponse> {
+    Ok(sso_api::OidcConfigResponse {
+        role_mappings: oidc_config
+            .role_mappings
+            .parse_value("Vec<OidcRoleMapping>")
+            .change_context(UserErrors::InternalServerError)?,
+        org_id: oidc_config.org_id,
+        merchant_id: oidc_config.merchant_id,
+        issuer_url: oidc_config.issuer_url,
+        client_id: oidc_config.client_id,
+        redirect_url: oidc_config.redirect_url,
+        groups_claim: oidc_config.groups_claim,
+        default_role_id: oidc_config.default_role_id,
+        is_enabled: oidc_config.is_enabled,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use super::*;
+    use crate::utils::test_utils::get_mock_state;
+
+    const ORG_ID: &str = "sso_test_org";
+    const ISSUER: &str = "https://idp.example.com";
+
+    fn get_oidc_config() -> OidcConfig {
+        let now = common_utils::date_time::now();
+        OidcConfig {
+            org_id: ORG_ID.to_string(),
+            merchant_id: "sso_test_merchant".to_string(),
+            issuer_url: ISSUER.to_string(),
+            client_id: "client".to_string(),
+            client_secret: diesel_models::encryption::Encryption::new(Vec::new().into()),
+            redirect_url: "https://dashboard.example.com/sso".to_string(),
+            groups_claim: consts::user::OIDC_DEFAULT_GROUPS_CLAIM.to_string(),
+            role_mappings: serde_json::json!([]),
+            default_role_id: None,
+            is_enabled: true,
+            created_by: "admin".to_string(),
+            created_at: now,
+            last_modified_by: "admin".to_string(),
+            last_modified_at: now,
+        }
+    }
+
+    fn get_claims(subject: &str, email_verified: Option<bool>) -> oidc::IdTokenClaims {
+        serde_json::from_value(serde_json::json!({
+            "sub": subject,
+            "email": "user@example.com",
+            "email_verified": email_verified,
+        }))
+        .unwrap()
+    }
+
+    async fn insert_user(state: &AppState, org_id: Option<&str>) {
+        state
+            .store
+            .insert_user(storage_user::UserNew {
+                user_id: "sso_test_user".to_string(),
+                email: "user@example.com".to_string().try_into().unwrap(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        if let Some(org_id) = org_id {
+            let now = common_utils::date_time::now();
+            state
+                .store
+                .insert_user_role(UserRoleNew {
+                    user_id: "sso_test_user".to_string(),
+                    merchant_id: "other_merchant".to_string(),
+                    role_id: consts::user_role::ROLE_ID_MERCHANT_VIEW_ONLY.to_string(),
+                    org_id: org_id.to_string(),
+                    status: UserStatus::Active,
+                    created_by: "admin".to_string(),
+                    last_modified_by: "admin".to_string(),
+                    created_at: now,
+                    last_modified: now,
+                })
+                .await
+                .unwrap();
+        }
+    }
+
+    fn get_error(result: UserResult<domain::UserFromStorage>) -> error_stack::Report<UserErrors> {
+        match result {
+            Ok(_) => panic!("Expected the user not to be provisioned"),
+            Err(e) => e,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_existing_user_is_not_linked_by_email() {
+        for org_id in [None, Some("other_org"), Some(ORG_ID)] {
+            let state = get_mock_state().await;
+            insert_user(&state, org_id).await;
+
+            let result = provision_user(
+                &state,
+                &get_oidc_config(),
+                ISSUER,
+                get_claims("subject", Some(true)),
+            )
+            .await;
+            assert!(matches!(
+                get_error(result).current_context(),
+                UserErrors::SsoIdentityNotLinked
+            ));
+            assert!(find_oidc_identity(&state, ORG_ID, ISSUER, "subject")
+                .await
+                .unwrap()
+                .is_none());
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_linked_user_is_found_by_identity() {
+        let state = get_mock_state().await;
+        insert_user(&state, Some(ORG_ID)).await;
+        insert_oidc_identity(
+            &state,
+            ORG_ID,
+            ISSUER.to_string(),
+            "subject".to_string(),
+            "sso_test_user".to_string(),
+        )
+        .await
+        .unwrap();
+
+        // Signing in is bound to the identity, not to the email
+        let user = provision_user(
+            &state,
+            &get_oidc_config(),
+            ISSUER,
+            get_claims("subject", None),
+        )
+        .await
+        .unwrap();
+        assert_eq!(user.get_user_id(), "sso_test_user");
+    }
+
+    #[actix_rt::test]
+    async fn test_unverified_email_is_rejected() {
+        let state = get_mock_state().await;
+        let oidc_config = get_oidc_config();
+
+        for email_verified in [None, Some(false)] {
+            let result = provision_user(
+                &state,
+                &oidc_config,
+                ISSUER,
+                get_claims("subject", email_verified),
+            )
+            .await;
+            assert!(matches!(
+                get_error(result).current_context(),
+                UserErrors::SsoIdTokenInvalid
+            ));
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_new_user_is_created_and_linked() {
+        let state = get_mock_state().await;
+
+        let user = provision_user(
+            &state,
+            &get_oidc_config(),
+            ISSUER,
+            get_claims("subject", Some(true)),
+        )
+        .await
+        .unwrap();
+
+        let identity = find_oidc_identity(&state, ORG_ID, ISSUER, "subject")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(identity.user_id, user.get_user_id());
+    }
+}
@@ -0,0 +1,352 @@
+use api_models::user as user_api;
+use diesel_models::{
+    enums::{TotpStatus, UserStatus},
+    user as storage_user,
+};
+use error_stack::{IntoReport, ResultExt};
+use masking::{ExposeInterface, PeekInterface};
+
+use crate::{
+    core::errors::{UserErrors, UserResponse, UserResult},
+    routes::AppState,
+    services::{
+        authentication::{self as auth, UserFromToken},
+        ApplicationResponse,
+    },
+    types::domain,
+    utils::{self, user::two_factor_auth as tfa_utils},
+};
+
+pub async fn begin_totp(
+    state: AppState,
+    user_from_token: UserFromToken,
+) -> UserResponse<user_api::BeginTotpResponse> {
+    let user: domain::UserFromStorage = user_from_token.get_user(state.clone()).await?.into();
+    if user.is_two_factor_auth_setup() {
+        return Err(UserErrors::TwoFactorAuthAlreadySetup.into());
+    }
+
+    let key_store = user.get_or_create_key_store(&state).await?;
+    let secret = tfa_utils::generate_totp_secret();
+    let encrypted_secret = domain::types::encrypt(secret.clone(), key_store.key.get_inner().peek())
+        .await
+        .change_context(UserErrors::InternalServerError)?;
+
+    state
+        .store
+        .update_user_by_user_id(
+            user.get_user_id(),
+            storage_user::UserUpdate::TotpUpdate {
+                totp_status: None,
+                totp_secret: Some(encrypted_secret.into()),
+                totp_recovery_codes: None,
+            },
+        )
+        .await
+        .change_context(UserErrors::InternalServerError)?;
+
+    let email = user.get_email();
+    let totp_url = tfa_utils::get_totp_url(email.peek(), &secret)?;
+    let qr_code = utils::QrImage::new_from_data(totp_url.clone().expose())
+        .change_context(UserErrors::InternalServerError)
+        .attach_printable("Failed to generate QR code for the TOTP url")?;
+
+    Ok(ApplicationResponse::Json(user_api::BeginTotpResponse {
+        secret: tfa_utils::encode_totp_secret(&secret),
+        totp_url,
+        qr_code: qr_code.data.into(),
+    }))
+}
+
+pub async fn verify_totp(
+    state: AppState,
+    user_from_token: UserFromToken,
+    req: user_api::VerifyTotpRequest,
+) -> UserResponse<user_api::RecoveryCodesResponse> {
+    let user: domain::UserFromStorage = user_from_token.get_user(state.clone()).await?.into();
+    if user.is_two_factor_auth_setup() {
+        return Err(UserErrors::TwoFactorAuthAlreadySetup.into());
+    }
+
+    let secret = user
+        .decrypt_totp_secret(&state)
+        .await?
+        .ok_or(UserErrors::TwoFactorAuthNotSetup)
+        .into_report()
+        .attach_printable("TOTP enrolment has not been started")?;
+
+    tfa_utils::verify_totp(&state, &user, &secret, &req.totp).await?;
+
+    let recovery_codes = update_recovery_codes(&state, &user, Some(TotpStatus::Set)).await?;
+
+    Ok(ApplicationResponse::Json(user_api::RecoveryCodesResponse {
+        recovery_codes,
+    }))
+}
+
+pub async fn generate_recovery_codes(
+    state: AppState,
+    user_from_token: UserFromToken,
+) -> UserResponse<user_api::RecoveryCodesResponse> {
+    let user: domain::UserFromStorage = user_from_token.get_user(state.clone()).await?.into();
+    if !user.is_two_factor_auth_setup() {
+        return Err(UserErrors::TwoFactorAuthNotSetup.into());
+    }
+
+    let recovery_codes = update_recovery_codes(&state, &user, None).await?;
+
+    Ok(ApplicationResponse::Json(user_api::RecoveryCodesResponse {
+        recovery_codes,
+    }))
+}
+
+pub async fn signin_with_two_factor_auth(
+    state: AppState,
+    req: user_api::TwoFactorAuthSignInRequest,
+) -> UserResponse<user_api::TwoFactorAuthSignInResponse> {
+    let token = auth::decode_jwt::<auth::TwoFactorAuthToken>(req.token.peek(), &state)
+        .await
+        .change_context(UserErrors::TwoFactorAuthTokenInvalid)?;
+    if token.purpose != auth::TokenPurpose::TwoFactorAuth {
+        return Err(UserErrors::TwoFactorAuthTokenInvalid.into());
+    }
+
+    let user: domain::UserFromStorage = state
+        .store
+        .find_user_by_id(&token.user_id)
+        .await
+        .change_context(UserErrors::InternalServerError)?
+        .into();
+    if !user.is_two_factor_auth_setup() {
+        return Err(UserErrors::TwoFactorAuthNotSetup.into());
+    }
+
+    tfa_utils::check_two_factor_auth_attempts(&state, user.get_user_id()).await?;
+
+    match (req.totp, req.recovery_code) {
+        (Some(totp), _) => {
+            let secret = user
+                .decrypt_totp_secret(&state)
+                .await?
+                .ok_or(UserErrors::InternalServerError)
+                .into_report()
+                .attach_printable("TOTP secret not found for user with two factor auth")?;
+            tfa_utils::verify_totp(&state, &user, &secret, &totp).await?;
+        }
+        (None, Some(recovery_code)) => {
+            let recovery_codes = user.get_recovery_codes().unwrap_or_default();
+            let remaining_recovery_codes =
+                tfa_utils::use_recovery_code(recovery_code, recovery_codes.clone())?
+                    .ok_or(UserErrors::InvalidRecoveryCode)?;
+
+            // The codes are replaced only if unchanged since read, a code submitted concurrently
+            // is used up once
+            state
+                .store
+                .update_user_recovery_codes_if_unchanged(
+                    user.get_user_id(),
+                    recovery_codes,
+                    remaining_recovery_codes,
+                )
+                .await
+                .map_err(|e| {
+                    if e.current_context().is_db_not_found() {
+                        e.change_context(UserErrors::InvalidRecoveryCode)
+                            .attach_printable("Recovery codes changed since read")
+                    } else {
+                        e.change_context(UserErrors::InternalServerError)
+                    }
+                })?;
+        }
+        (None, None) => {
+            return Err(UserErrors::InvalidTotp)
+                .into_report()
+                .attach_printable("Neither TOTP nor recovery code provided");
+        }
+    }
+
+    tfa_utils::reset_two_factor_auth_attempts(&state, user.get_user_id()).await?;
+
+    let (user_role, token) = match token.sso_role {
+        // Signing in through single sign-on only grants the role given by the organization
+        Some(sso_role) => {
+            let user_role = state
+                .store
+                .list_user_roles_by_user_id(user.get_user_id())
+                .await
+                .change_context(UserErrors::InternalServerError)?
+                .into_iter()
+                .find(|user_role| {
+                    user_role.merchant_id == sso_role.merchant_id
+                        && user_role.org_id == sso_role.org_id
+                        && user_role.role_id == sso_role.role_id
+                        && user_role.status == UserStatus::Active
+                })
+                .ok_or(UserErrors::TwoFactorAuthTokenInvalid)
+                .into_report()
+                .attach_printable("Role given through single sign-on changed since sign in")?;
+            let token =
+                utils::user::generate_org_scoped_jwt_auth_token(state.clone(), &user, &user_role)
+                    .await?;
+            (user_role, token)
+        }
+        None => {
+            let user_role = user.get_role_from_db(state.clone()).await?;
+            let token =
+                utils::user::generate_jwt_auth_token(state.clone(), &user, &user_role).await?;
+            (user_role, token)
+        }
+    };
+
+    Ok(ApplicationResponse::Json(
+        utils::user::get_dashboard_entry_response(state, user, user_role, token)?,
+    ))
+}
+
+pub async fn reset_two_factor_auth(
+    state: AppState,
+    user_from_token: UserFromToken,
+    req: user_api::ResetTwoFactorAuthRequest,
+) -> UserResponse<()> {
+    if user_from_token.user_id == req.user_id {
+        return Err(UserErrors::InvalidRoleOperation.into())
+            .attach_printable("User resetting their own two factor auth");
+    }
+
+    // Any existing user can be invited to a merchant without their consent, so the reset is only
+    // allowed for the users who belong to the organization alone and have accepted their role in
+    // the merchant
+    let user_roles = state
+        .store
+        .list_user_roles_by_user_id(&req.user_id)
+        .await
+        .change_context(UserErrors::InternalServerError)?;
+    let is_user_in_merchant = user_roles.iter().any(|user_role| {
+        user_role.merchant_id == user_from_token.merchant_id
+            && user_role.status == UserStatus::Active
+    });
+    if !is_user_in_merchant {
+        return Err(UserErrors::InvalidRoleOperation.into())
+            .attach_printable("User is not an active member of the merchant");
+    }
+    if user_roles
+        .iter()
+        .any(|user_role| user_role.org_id != user_from_token.org_id)
+    {
+        return Err(UserErrors::InvalidRoleOperation.into())
+            .attach_printable("User is a member of other organizations");
+    }
+
+    state
+        .store
+        .update_user_by_user_id(&req.user_id, storage_user::UserUpdate::TotpReset)
+        .await
+        .change_context(UserErrors::InternalServerError)?;
+
+    tfa_utils::reset_two_factor_auth_attempts(&state, &req.user_id).await?;
+
+    Ok(ApplicationResponse::StatusOk)
+}
+
+async fn update_recovery_codes(
+    state: &AppState,
+    user: &domain::UserFromStorage,
+    totp_status: Option<TotpStatus>,
+) -> UserResult<Vec<masking::Secret<String>>> {
+    let (recovery_codes, hashed_recovery_codes) = tfa_utils::generate_recovery_codes()?;
+
+    state
+        .store
+        .update_user_by_user_id(
+            user.get_user_id(),
+            storage_user::UserUpdate::TotpUpdate {
+                totp_status,
+                totp_secret: None,
+                totp_recovery_codes: Some(hashed_recovery_codes),
+            },
+        )
+        .await
+        .change_context(UserErrors::InternalServerError)?;
+
+    Ok(recovery_codes)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use diesel_models::user_role::UserRoleNew;
+
+    use super::*;
+    use crate::utils::test_utils::get_mock_state;
+
+    const ORG_ID: &str = "tfa_test_org";
+    const MERCHANT_ID: &str = "tfa_test_merchant";
+    const USER_ID: &str = "tfa_test_user";
+
+    async fn insert_user_role(
+        state: &AppState,
+        merchant_id: &str,
+        org_id: &str,
+        status: UserStatus,
+    ) {
+        let now = common_utils::date_time::now();
+        state
+            .store
+            .insert_user_role(UserRoleNew {
+                user_id: USER_ID.to_string(),
+                merchant_id: merchant_id.to_string(),
+                role_id: crate::consts::user_role::ROLE_ID_MERCHANT_VIEW_ONLY.to_string(),
+                org_id: org_id.to_string(),
+                status,
+                created_by: "admin".to_string(),
+                last_modified_by: "admin".to_string(),
+                created_at: now,
+                last_modified: now,
+            })
+            .await
+            .unwrap();
+    }
+
+    fn get_admin() -> UserFromToken {
+        UserFromToken {
+            user_id: "tfa_test_admin".to_string(),
+            merchant_id: MERCHANT_ID.to_string(),
+            role_id: crate::consts::user_role::ROLE_ID_MERCHANT_ADMIN.to_string(),
+            org_id: ORG_ID.to_string(),
+            org_scoped: false,
+        }
+    }
+
+    async fn is_reset_rejected(state: &AppState) -> bool {
+        let result = reset_two_factor_auth(
+            state.clone(),
+            get_admin(),
+            user_api::ResetTwoFactorAuthRequest {
+                user_id: USER_ID.to_string(),
+            },
+        )
+        .await;
+        result.map_or_else(
+            |error| matches!(error.current_context(), UserErrors::InvalidRoleOperation),
+            |_| false,
+        )
+    }
+
+    #[actix_rt::test]
+    async fn test_reset_of_user_invited_from_other_org_is_rejected() {
+        let state = get_mock_state().await;
+        insert_user_role(&state, "other_merchant", "other_org", UserStatus::Active).await;
+        insert_user_role(&state, MERCHANT_ID, ORG_ID, UserStatus::Active).await;
+
+        assert!(is_reset_rejected(&state).await);
+    }
+
+    #[actix_rt::test]
+    async fn test_reset_of_user_not_accepting_the_role_is_rejected() {
+        let state = get_mock_state().await;
+        insert_user_role(&state, MERCHANT_ID, ORG_ID, UserStatus::InvitationSent).await;
+
+        assert!(is_reset_rejected(&state).await);
+    }
+}
@@ -1,13 +1,17 @@
 use api_models::user_role as user_role_api;
-use diesel_models::user_role::UserRoleUpdate;
+use diesel_models::{
+    role::{Role, RoleNew, RoleUpdate},
+    user_role::UserRoleUpdate,
+};
 use error_stack::ResultExt;
 
 use crate::{
-    core::errors::{UserErrors, UserResponse},
+    consts,
+    core::errors::{UserErrors, UserResponse, UserResult},
     routes::AppState,
     services::{
         authentication::{self as auth},
-        authorization::{info, predefined_permissions},
+        authorization::{self, info, permissions::Permission, predefined_permissions},
         ApplicationResponse,
     },
     utils,
@@ -26,41 +30,202 @@ pub async fn get_authorization_info(
     ))
 }
 
-pub async fn list_roles(_state: AppState) -> UserResponse<user_role_api::ListRolesResponse> {
-    Ok(ApplicationResponse::Json(user_role_api::ListRolesResponse(
+pub async fn list_roles(
+    state: AppState,
+    user_from_token: auth::UserFromToken,
+) -> UserResponse<user_role_api::ListRolesResponse> {
+    let mut roles: Vec<user_role_api::RoleInfoResponse> =
         predefined_permissions::PREDEFINED_PERMISSIONS
             .iter()
-            .filter_map(|(role_id, role_info)| {
-                utils::user_role::get_role_name_and_permission_response(role_info).map(
-                    |(permissions, role_name)| user_role_api::RoleInfoResponse {
-                        permissions,
-                        role_id,
-                        role_name,
-                    },
-                )
-            })
-            .collect(),
+            .filter_map(|(role_id, role_info)| get_predefined_role_response(role_id, role_info))
+            .collect();
+
+    let custom_roles = state
+        .store
+        .list_roles_by_merchant_id(user_from_token.merchant_id.as_str())
+        .await
+        .change_context(UserErrors::InternalServerError)?;
+    for role in custom_roles {
+        roles.push(utils::user_role::get_custom_role_response(role)?);
+    }
+
+    Ok(ApplicationResponse::Json(user_role_api::ListRolesResponse(
+        roles,
     )))
 }
 
 pub async fn get_role(
-    _state: AppState,
+    state: AppState,
+    user_from_token: auth::UserFromToken,
     role: user_role_api::GetRoleRequest,
 ) -> UserResponse<user_role_api::RoleInfoResponse> {
-    let info = predefined_permissions::PREDEFINED_PERMISSIONS
-        .get_key_value(role.role_id.as_str())
-        .and_then(|(role_id, role_info)| {
-            utils::user_role::get_role_name_and_permission_response(role_info).map(
-                |(permissions, role_name)| user_role_api::RoleInfoResponse {
-                    permissions,
-                    role_id,
-                    role_name,
-                },
-            )
+    if let Some((role_id, role_info)) =
+        predefined_permissions::PREDEFINED_PERMISSIONS.get_key_value(role.role_id.as_str())
+    {
+        let info =
+            get_predefined_role_response(role_id, role_info).ok_or(UserErrors::InvalidRoleId)?;
+        return Ok(ApplicationResponse::Json(info));
+    }
+
+    let role = find_custom_role(&state, &role.role_id, &user_from_token.merchant_id).await?;
+    Ok(ApplicationResponse::Json(
+        utils::user_role::get_custom_role_response(role)?,
+    ))
+}
+
+pub async fn create_role(
+    state: AppState,
+    user_from_token: auth::UserFromToken,
+    req: user_role_api::CreateRoleRequest,
+) -> UserResponse<user_role_api::RoleInfoResponse> {
+    let role_name = utils::user_role::validate_role_name(&req.role_name)?;
+    let user_permissions = get_user_permissions(&state, &user_from_token).await?;
+    let permissions =
+        utils::user_role::validate_role_permissions(req.permissions, &user_permissions)?;
+
+    let now = common_utils::date_time::now();
+    let role = state
+        .store
+        .insert_role(RoleNew {
+            role_id: utils::generate_id(
+                consts::ID_LENGTH,
+                consts::user_role::CUSTOM_ROLE_ID_PREFIX,
+            ),
+            role_name,
+            merchant_id: user_from_token.merchant_id,
+            org_id: user_from_token.org_id,
+            permissions,
+            created_by: user_from_token.user_id.clone(),
+            created_at: now,
+            last_modified_by: user_from_token.user_id,
+            last_modified_at: now,
+        })
+        .await
+        .map_err(|e| {
+            if e.current_context().is_db_unique_violation() {
+                e.change_context(UserErrors::RoleNameAlreadyExists)
+            } else {
+                e.change_context(UserErrors::InternalServerError)
+            }
+        })?;
+
+    Ok(ApplicationResponse::Json(
+        utils::user_role::get_custom_role_response(role)?,
+    ))
+}
+
+pub async fn update_role(
+    state: AppState,
+    user_from_token: auth::UserFromToken,
+    role_id: &str,
+    req: user_role_api::UpdateRoleRequest,
+) -> UserResponse<user_role_api::RoleInfoResponse> {
+    let role_name = req
+        .role_name
+        .as_deref()
+        .map(utils::user_role::validate_role_name)
+        .transpose()?;
+    let permissions = match req.permissions {
+        Some(permissions) => {
+            let user_permissions = get_user_permissions(&state, &user_from_token).await?;
+            Some(utils::user_role::validate_role_permissions(
+                permissions,
+                &user_permissions,
+            )?)
+        }
+        None => None,
+    };
+
+    let role = state
+        .store
+        .update_role_by_role_id_merchant_id(
+            role_id,
+            user_from_token.merchant_id.as_str(),
+            RoleUpdate::UpdateDetails {
+                role_name,
+                permissions,
+                last_modified_by: user_from_token.user_id,
+            },
+        )
+        .await
+        .map_err(|e| {
+            if e.current_context().is_db_not_found() {
+                e.change_context(UserErrors::InvalidRoleId)
+            } else if e.current_context().is_db_unique_violation() {
+                e.change_context(UserErrors::RoleNameAlreadyExists)
+            } else {
+                e.change_context(UserErrors::InternalServerError)
+            }
+        })?;
+
+    Ok(ApplicationResponse::Json(
+        utils::user_role::get_custom_role_response(role)?,
+    ))
+}
+
+pub async fn delete_role(
+    state: AppState,
+    user_from_token: auth::UserFromToken,
+    req: user_role_api::GetRoleRequest,
+) -> UserResponse<()> {
+    let merchant_id = user_from_token.merchant_id.as_str();
+    let role = find_custom_role(&state, &req.role_id, merchant_id).await?;
+
+    // The role is kept while it is assigned, including to users yet to accept their invitation
+    state
+        .store
+        .delete_unassigned_role_by_role_id_merchant_id(&role.role_id, merchant_id)
+        .await
+        .map_err(|e| {
+            if e.current_context().is_db_not_found() {
+                e.change_context(UserErrors::RoleInUse)
+            } else {
+                e.change_context(UserErrors::InternalServerError)
+            }
+        })?;
+
+    Ok(ApplicationResponse::StatusOk)
+}
+
+fn get_predefined_role_response(
+    role_id: &str,
+    role_info: &predefined_permissions::RoleInfo,
+) -> Option<user_role_api::RoleInfoResponse> {
+    utils::user_role::get_role_name_and_permission_response(role_info).map(
+        |(permissions, role_name)| user_role_api::RoleInfoResponse {
+            permissions,
+            role_id: role_id.to_string(),
+            role_name: role_name.to_string(),
+            is_custom: false,
+        },
+    )
+}
+
+async fn find_custom_role(state: &AppState, role_id: &str, merchant_id: &str) -> UserResult<Role> {
+    state
+        .store
+        .find_role_by_role_id_merchant_id(role_id, merchant_id)
+        .await
+        .map_err(|e| {
+            if e.current_context().is_db_not_found() {
+                e.change_context(UserErrors::InvalidRoleId)
+            } else {
+                e.change_context(UserErrors::InternalServerError)
+            }
         })
-        .ok_or(UserErrors::InvalidRoleId)?;
+}
 
-    Ok(ApplicationResponse::Json(info))
+async fn get_user_permissions(
+    state: &AppState,
+    user_from_token: &auth::UserFromToken,
+) -> UserResult<Vec<Permission>> {
+    authorization::get_permissions(
+        state,
+        &user_from_token.role_id,
+        &user_from_token.merchant_id,
+    )
+    .await
+    .change_context(UserErrors::InternalServerError)
 }
 
 pub async fn update_user_role(
@@ -70,7 +235,7 @@ pub async fn update_user_role(
 ) -> UserResponse<()> {
     let merchant_id = user_from_token.merchant_id;
     let role_id = req.role_id.clone();
-    utils::user_role::validate_role_id(role_id.as_str())?;
+    utils::user_role::validate_role_id(&state, role_id.as_str(), merchant_id.as_str()).await?;
 
     if user_from_token.user_id == req.user_id {
         return Err(UserErrors::InvalidRoleOperation.into())
@@ -99,3 +264,170 @@ pub async fn update_user_role(
 
     Ok(ApplicationResponse::StatusOk)
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use diesel_models::{enums::UserStatus, user_role::UserRoleNew};
+
+    use super::*;
+    use crate::utils::test_utils::get_mock_state;
+
+    fn get_user_from_token(role_id: &str) -> auth::UserFromToken {
+        auth::UserFromToken {
+            user_id: "user_role_test_user".to_string(),
+            merchant_id: "user_role_test_merchant".to_string(),
+            role_id: role_id.to_string(),
+            org_id: "user_role_test_org".to_string(),
+            org_scoped: false,
+        }
+    }
+
+    fn get_json<T>(response: ApplicationResponse<T>) -> T {
+        match response {
+            ApplicationResponse::Json(response) => response,
+            _ => panic!("unexpected response"),
+        }
+    }
+
+    async fn create_test_role(state: &AppState) -> user_role_api::RoleInfoResponse {
+        get_json(
+            create_role(
+                state.clone(),
+                get_user_from_token(consts::user_role::ROLE_ID_MERCHANT_ADMIN),
+                user_role_api::CreateRoleRequest {
+                    role_name: "Refund support".to_string(),
+                    permissions: vec![
+                        user_role_api::Permission::PaymentRead,
+                        user_role_api::Permission::RefundWrite,
+                    ],
+                },
+            )
+            .await
+            .unwrap(),
+        )
+    }
+
+    #[actix_rt::test]
+    async fn test_create_and_update_role() {
+        let state = get_mock_state().await;
+        let user_from_token = get_user_from_token(consts::user_role::ROLE_ID_MERCHANT_ADMIN);
+
+        let role = create_test_role(&state).await;
+        assert!(role.is_custom);
+        assert_eq!(role.role_name, "Refund support");
+        assert_eq!(
+            authorization::get_permissions(&state, &role.role_id, &user_from_token.merchant_id)
+                .await
+                .unwrap(),
+            vec![Permission::PaymentRead, Permission::RefundWrite]
+        );
+
+        // Role names are unique per merchant
+        let error = create_role(
+            state.clone(),
+            user_from_token.clone(),
+            user_role_api::CreateRoleRequest {
+                role_name: "Refund support".to_string(),
+                permissions: vec![user_role_api::Permission::PaymentRead],
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            UserErrors::RoleNameAlreadyExists
+        ));
+
+        let updated_role = get_json(
+            update_role(
+                state.clone(),
+                user_from_token.clone(),
+                &role.role_id,
+                user_role_api::UpdateRoleRequest {
+                    role_name: None,
+                    permissions: Some(vec![user_role_api::Permission::RefundRead]),
+                },
+            )
+            .await
+            .unwrap(),
+        );
+        assert_eq!(updated_role.role_name, "Refund support");
+        assert_eq!(
+            updated_role.permissions,
+            vec![user_role_api::Permission::RefundRead]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_role_permissions_are_held_by_the_user() {
+        let state = get_mock_state().await;
+        let user_from_token = get_user_from_token(consts::user_role::ROLE_ID_MERCHANT_VIEW_ONLY);
+
+        let error = create_role(
+            state.clone(),
+            user_from_token,
+            user_role_api::CreateRoleRequest {
+                role_name: "Refund operator".to_string(),
+                permissions: vec![
+                    user_role_api::Permission::RefundRead,
+                    user_role_api::Permission::RefundWrite,
+                ],
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            UserErrors::InvalidRoleOperation
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn test_delete_role() {
+        let state = get_mock_state().await;
+        let user_from_token = get_user_from_token(consts::user_role::ROLE_ID_MERCHANT_ADMIN);
+        let role = create_test_role(&state).await;
+
+        let now = common_utils::date_time::now();
+        state
+            .store
+            .insert_user_role(UserRoleNew {
+                user_id: "user_role_test_invitee".to_string(),
+                merchant_id: user_from_token.merchant_id.clone(),
+                role_id: role.role_id.clone(),
+                org_id: user_from_token.org_id.clone(),
+                status: UserStatus::InvitationSent,
+                created_by: user_from_token.user_id.clone(),
+                last_modified_by: user_from_token.user_id.clone(),
+                created_at: now,
+                last_modified: now,
+            })
+            .await
+            .unwrap();
+        let delete_request = || user_role_api::GetRoleRequest {
+            role_id: role.role_id.clone(),
+        };
+
+        // The role is assigned to a user yet to accept their invitation
+        let error = delete_role(state.clone(), user_from_token.clone(), delete_request())
+            .await
+            .unwrap_err();
+        assert!(matches!(error.current_context(), UserErrors::RoleInUse));
+
+        state
+            .store
+            .delete_user_role("user_role_test_invitee")
+            .await
+            .unwrap();
+        delete_role(state.clone(), user_from_token.clone(), delete_request())
+            .await
+            .unwrap();
+
+        let error = get_role(state.clone(), user_from_token, delete_request())
+            .await
+            .unwrap_err();
+        assert!(matches!(error.current_context(), UserErrors::InvalidRoleId));
+    }
+}
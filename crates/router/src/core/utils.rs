@@ -176,6 +176,7 @@ pub async fn construct_payout_router_data<'a, F>(
         external_latency: None,
         apple_pay_flow: None,
         frm_metadata: None,
+        merchant_connector_id: merchant_connector_account.get_mca_id(),
     };
 
     Ok(router_data)
@@ -328,6 +329,7 @@ pub async fn construct_refund_router_data<'a, F>(
         external_latency: None,
         apple_pay_flow: None,
         frm_metadata: None,
+        merchant_connector_id: merchant_connector_account.get_mca_id(),
     };
 
     Ok(router_data)
@@ -558,6 +560,7 @@ pub async fn construct_accept_dispute_router_data<'a>(
         external_latency: None,
         apple_pay_flow: None,
         frm_metadata: None,
+        merchant_connector_id: merchant_connector_account.get_mca_id(),
     };
     Ok(router_data)
 }
@@ -646,6 +649,7 @@ pub async fn construct_submit_evidence_router_data<'a>(
         external_latency: None,
         apple_pay_flow: None,
         frm_metadata: None,
+        merchant_connector_id: merchant_connector_account.get_mca_id(),
     };
     Ok(router_data)
 }
@@ -740,6 +744,7 @@ pub async fn construct_upload_file_router_data<'a>(
         external_latency: None,
         apple_pay_flow: None,
         frm_metadata: None,
+        merchant_connector_id: merchant_connector_account.get_mca_id(),
     };
     Ok(router_data)
 }
@@ -831,6 +836,7 @@ pub async fn construct_defend_dispute_router_data<'a>(
         external_latency: None,
         apple_pay_flow: None,
         frm_metadata: None,
+        merchant_connector_id: merchant_connector_account.get_mca_id(),
     };
     Ok(router_data)
 }
@@ -915,6 +921,7 @@ pub async fn construct_retrieve_file_router_data<'a>(
         external_latency: None,
         apple_pay_flow: None,
         frm_metadata: None,
+        merchant_connector_id: merchant_connector_account.get_mca_id(),
     };
     Ok(router_data)
 }
@@ -1,5 +1,6 @@
 pub mod types;
 pub mod utils;
+pub mod webhook_events;
 
 use std::{str::FromStr, time::Instant};
 
@@ -10,8 +11,8 @@ use api_models::{
 };
 use common_utils::{errors::ReportSwitchExt, events::ApiEventsType, request::RequestContent};
 use error_stack::{report, IntoReport, ResultExt};
-use masking::ExposeInterface;
 use router_env::{instrument, tracing, tracing_actix_web::RequestId};
+use scheduler::{db::process_tracker::ProcessTrackerExt, errors as sch_errors};
 
 use super::{errors::StorageErrorExt, metrics};
 #[cfg(feature = "stripe")]
@@ -35,11 +36,17 @@ use crate::{
         storage::{self, enums},
         transformers::{ForeignInto, ForeignTryInto},
     },
-    utils::{self as helper_utils, generate_id, OptionExt, ValueExt},
+    utils::{self as helper_utils, generate_id, Encode, OptionExt, ValueExt},
+    workflows::outgoing_webhook_retry,
 };
 
 const OUTGOING_WEBHOOK_TIMEOUT_SECS: u64 = 5;
 const MERCHANT_ID: &str = "merchant_id";
+const OUTGOING_WEBHOOK_RETRY_TAG: &str = "OUTGOING_WEBHOOK";
+const OUTGOING_WEBHOOK_RETRY_TASK: &str = "OUTGOING_WEBHOOK_RETRY";
+const OUTGOING_WEBHOOK_RETRY_RUNNER: &str = "OUTGOING_WEBHOOK_RETRY_WORKFLOW";
+/// Maximum number of characters of the merchant's response body stored for a delivery attempt
+const DELIVERY_ATTEMPT_RESPONSE_BODY_MAX_LENGTH: usize = 8192;
 
 pub async fn payments_incoming_webhook_flow<
     W: types::OutgoingWebhookType,
@@ -431,6 +438,7 @@ pub async fn mandates_incoming_webhook_flow<W: types::OutgoingWebhookType>(
                 .find_mandate_by_merchant_id_mandate_id(
                     &merchant_account.merchant_id,
                     mandate_id.as_str(),
+                    merchant_account.storage_scheme,
                 )
                 .await
                 .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?,
@@ -440,6 +448,7 @@ pub async fn mandates_incoming_webhook_flow<W: types::OutgoingWebhookType>(
                 .find_mandate_by_merchant_id_connector_mandate_id(
                     &merchant_account.merchant_id,
                     connector_mandate_id.as_str(),
+                    merchant_account.storage_scheme,
                 )
                 .await
                 .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?,
@@ -457,12 +466,17 @@ pub async fn mandates_incoming_webhook_flow<W: types::OutgoingWebhookType>(
                 &merchant_account.merchant_id,
                 &mandate.mandate_id,
                 storage::MandateUpdate::StatusUpdate { mandate_status },
+                merchant_account.storage_scheme,
             )
             .await
             .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
         let mandates_response = Box::new(
-            api::mandates::MandateResponse::from_db_mandate(&state, updated_mandate.clone())
-                .await?,
+            api::mandates::MandateResponse::from_db_mandate(
+                &state,
+                updated_mandate.clone(),
+                merchant_account.storage_scheme,
+            )
+            .await?,
         );
         let event_type: Option<enums::EventType> = updated_mandate.mandate_status.foreign_into();
         if let Some(outgoing_event_type) = event_type {
@@ -707,8 +721,10 @@ pub async fn create_event_and_trigger_outgoing_webhook<W: types::OutgoingWebhook
         event_class,
         is_webhook_notified: false,
         intent_reference_id,
-        primary_object_id,
+        primary_object_id: primary_object_id.clone(),
         primary_object_type,
+        merchant_id: Some(merchant_account.merchant_id.clone()),
+        business_profile_id: Some(business_profile.profile_id.clone()),
     };
 
     let event_insert_result = state.store.insert_event(new_event).await;
@@ -729,6 +745,16 @@ pub async fn create_event_and_trigger_outgoing_webhook<W: types::OutgoingWebhook
     }?;
 
     if state.conf.webhooks.outgoing_enabled {
+        let tracking_data = storage::OutgoingWebhookTrackingData {
+            event_id: event.event_id.clone(),
+            merchant_id: merchant_account.merchant_id.clone(),
+            business_profile_id: business_profile.profile_id.clone(),
+            event_type,
+            event_class,
+            primary_object_id,
+            primary_object_type,
+        };
+
         let outgoing_webhook = api::OutgoingWebhook {
             merchant_id: merchant_account.merchant_id.clone(),
             event_id: event.event_id,
@@ -740,11 +766,27 @@ pub async fn create_event_and_trigger_outgoing_webhook<W: types::OutgoingWebhook
         // Using a tokio spawn here and not arbiter because not all caller of this function
         // may have an actix arbiter
         tokio::spawn(async move {
-            let result =
-                trigger_webhook_to_merchant::<W>(business_profile, outgoing_webhook, state).await;
+            let result = trigger_webhook_to_merchant::<W>(
+                business_profile,
+                outgoing_webhook,
+                &state,
+                enums::WebhookDeliveryAttemptTrigger::InitialAttempt,
+            )
+            .await;
 
-            if let Err(e) = result {
-                logger::error!(?e);
+            if let Err(error) = result {
+                logger::error!(?error);
+
+                if error.current_context().is_webhook_delivery_retryable() {
+                    add_outgoing_webhook_retry_task(&*state.store, &tracking_data)
+                        .await
+                        .map_err(|error| {
+                            logger::error!(
+                                outgoing_webhook_retry_task_insertion_failure=?error
+                            )
+                        })
+                        .ok();
+                }
             }
         });
     }
@@ -752,10 +794,44 @@ pub async fn create_event_and_trigger_outgoing_webhook<W: types::OutgoingWebhook
     Ok(())
 }
 
+/// Delivers the outgoing webhook using the payload format of the merchant's compatible
+/// connector, if any.
+#[instrument(skip_all)]
+pub async fn trigger_appropriate_webhook_to_merchant(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    business_profile: diesel_models::business_profile::BusinessProfile,
+    webhook: api::OutgoingWebhook,
+    delivery_trigger: enums::WebhookDeliveryAttemptTrigger,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    match merchant_account.get_compatible_connector() {
+        #[cfg(feature = "stripe")]
+        Some(api_models::enums::Connector::Stripe) => {
+            trigger_webhook_to_merchant::<stripe_webhooks::StripeOutgoingWebhook>(
+                business_profile,
+                webhook,
+                state,
+                delivery_trigger,
+            )
+            .await
+        }
+        _ => {
+            trigger_webhook_to_merchant::<api_models::webhooks::OutgoingWebhook>(
+                business_profile,
+                webhook,
+                state,
+                delivery_trigger,
+            )
+            .await
+        }
+    }
+}
+
 pub async fn trigger_webhook_to_merchant<W: types::OutgoingWebhookType>(
     business_profile: diesel_models::business_profile::BusinessProfile,
     webhook: api::OutgoingWebhook,
-    state: AppState,
+    state: &AppState,
+    delivery_trigger: enums::WebhookDeliveryAttemptTrigger,
 ) -> CustomResult<(), errors::WebhooksFlowError> {
     let webhook_details_json = business_profile
         .webhook_details
@@ -767,27 +843,122 @@ pub async fn trigger_webhook_to_merchant<W: types::OutgoingWebhookType>(
             .parse_value("WebhookDetails")
             .change_context(errors::WebhooksFlowError::MerchantWebhookDetailsNotFound)?;
 
-    let webhook_url = webhook_details
-        .webhook_url
-        .get_required_value("webhook_url")
-        .change_context(errors::WebhooksFlowError::MerchantWebhookURLNotConfigured)
-        .map(ExposeInterface::expose)?;
+    let webhook_urls = utils::get_subscribed_webhook_urls(&webhook_details, webhook.event_type);
+
+    if webhook_urls.is_empty() {
+        if webhook_details.webhook_url.is_none()
+            && webhook_details
+                .webhook_endpoints
+                .as_ref()
+                .map_or(true, Vec::is_empty)
+        {
+            Err(errors::WebhooksFlowError::MerchantWebhookURLNotConfigured).into_report()?
+        }
+
+        logger::info!(
+            "No webhook endpoint subscribed to {} events, skipping delivery",
+            webhook.event_type
+        );
+        return Ok(());
+    }
 
     let outgoing_webhook_event_id = webhook.event_id.clone();
 
+    // The attempt is counted before delivering, concurrent deliveries of the event are numbered
+    // in the order they claim their attempt
+    let delivery_attempt = state
+        .store
+        .increment_event_delivery_attempts(&outgoing_webhook_event_id)
+        .await
+        .change_context(errors::WebhooksFlowError::WebhookEventUpdationFailed)?
+        .delivery_attempts;
+
+    // Endpoints which already received the event are not retried automatically
+    let delivered_webhook_urls = match delivery_trigger {
+        enums::WebhookDeliveryAttemptTrigger::AutomaticRetry => state
+            .store
+            .find_webhook_delivery_attempts_by_merchant_id_event_id(
+                &business_profile.merchant_id,
+                &outgoing_webhook_event_id,
+            )
+            .await
+            .map_err(|error| logger::error!(webhook_delivery_attempts_fetch_failure=?error))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|delivery_attempt| delivery_attempt.is_delivered)
+            .map(|delivery_attempt| delivery_attempt.request_url)
+            .collect(),
+        enums::WebhookDeliveryAttemptTrigger::InitialAttempt
+        | enums::WebhookDeliveryAttemptTrigger::ManualRetry => Vec::new(),
+    };
+
+    let mut delivery_error = None;
+    for webhook_url in webhook_urls
+        .into_iter()
+        .filter(|webhook_url| !delivered_webhook_urls.contains(webhook_url))
+    {
+        let delivery_result = trigger_webhook_to_endpoint::<W>(
+            state,
+            &business_profile,
+            webhook.clone(),
+            webhook_url,
+            delivery_attempt,
+            delivery_trigger,
+        )
+        .await;
+
+        if let Err(error) = delivery_result {
+            logger::error!(?error, "Outgoing webhook delivery to endpoint failed");
+            delivery_error.get_or_insert(error);
+        }
+    }
+
+    match delivery_error {
+        None => {
+            let update_event = storage::EventUpdate::UpdateWebhookNotified {
+                is_webhook_notified: Some(true),
+            };
+            state
+                .store
+                .update_event(outgoing_webhook_event_id, update_event)
+                .await
+                .change_context(errors::WebhooksFlowError::WebhookEventUpdationFailed)?;
+            Ok(())
+        }
+        Some(error) => Err(error),
+    }
+}
+
+/// Posts the outgoing webhook to a single endpoint of the merchant, and records the delivery
+/// attempt.
+async fn trigger_webhook_to_endpoint<W: types::OutgoingWebhookType>(
+    state: &AppState,
+    business_profile: &diesel_models::business_profile::BusinessProfile,
+    webhook: api::OutgoingWebhook,
+    webhook_url: String,
+    delivery_attempt: i32,
+    delivery_trigger: enums::WebhookDeliveryAttemptTrigger,
+) -> CustomResult<(), errors::WebhooksFlowError> {
+    let outgoing_webhook_event_id = webhook.event_id.clone();
+
     let transformed_outgoing_webhook = W::from(webhook);
 
-    let outgoing_webhooks_signature = transformed_outgoing_webhook
-        .get_outgoing_webhooks_signature(business_profile.payment_response_hash_key.clone())?;
+    let signing_keys = types::OutgoingWebhookSigningKeys::from_business_profile(business_profile);
+    let signature_headers = transformed_outgoing_webhook.get_outgoing_webhooks_signature_headers(
+        signing_keys.as_ref(),
+        common_utils::date_time::now_unix_timestamp(),
+    )?;
 
     let mut header = vec![(
         reqwest::header::CONTENT_TYPE.to_string(),
         "application/json".into(),
     )];
+    header.extend(signature_headers);
 
-    if let Some(signature) = outgoing_webhooks_signature {
-        W::add_webhook_header(&mut header, signature)
-    }
+    let request_body =
+        Encode::<serde_json::Value>::encode_to_string_of_json(&transformed_outgoing_webhook)
+            .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)
+            .attach_printable("failed encoding outgoing webhook payload")?;
 
     let request = services::RequestBuilder::new()
         .method(services::Method::Post)
@@ -797,10 +968,13 @@ pub async fn trigger_webhook_to_merchant<W: types::OutgoingWebhookType>(
         .set_body(RequestContent::Json(Box::new(transformed_outgoing_webhook)))
         .build();
 
+    let delivery_start_instant = Instant::now();
     let response = state
         .api_client
-        .send_request(&state, request, Some(OUTGOING_WEBHOOK_TIMEOUT_SECS), false)
+        .send_request(state, request, Some(OUTGOING_WEBHOOK_TIMEOUT_SECS), false)
         .await;
+    let latency_ms =
+        i64::try_from(delivery_start_instant.elapsed().as_millis()).unwrap_or(i64::MAX);
 
     metrics::WEBHOOK_OUTGOING_COUNT.add(
         &metrics::CONTEXT,
@@ -810,15 +984,44 @@ pub async fn trigger_webhook_to_merchant<W: types::OutgoingWebhookType>(
             business_profile.merchant_id.clone(),
         )],
     );
-    logger::debug!(outgoing_webhook_response=?response);
+    logger::debug!(outgoing_webhook_response=?response, delivery_attempt);
+
+    let mut new_delivery_attempt = storage::WebhookDeliveryAttemptNew {
+        attempt_id: generate_id(consts::ID_LENGTH, "whda"),
+        event_id: outgoing_webhook_event_id,
+        merchant_id: business_profile.merchant_id.clone(),
+        attempt_number: delivery_attempt,
+        delivery_trigger,
+        request_url: webhook_url,
+        request_body: request_body.into(),
+        response_status_code: None,
+        response_body: None,
+        error_message: None,
+        latency_ms,
+        is_delivered: false,
+    };
 
-    match response {
-        Err(e) => {
-            // [#217]: Schedule webhook for retry.
-            Err(e).change_context(errors::WebhooksFlowError::CallToMerchantFailed)?;
+    let delivery_result = match response {
+        Err(error) => {
+            new_delivery_attempt.error_message = Some(error.current_context().to_string());
+            Err(error).change_context(errors::WebhooksFlowError::CallToMerchantFailed)
         }
         Ok(res) => {
-            if res.status().is_success() {
+            let status_code = res.status();
+            new_delivery_attempt.response_status_code = Some(i32::from(status_code.as_u16()));
+            new_delivery_attempt.response_body = res
+                .text()
+                .await
+                .map_err(|error| logger::warn!(outgoing_webhook_response_read_failure=?error))
+                .ok()
+                .map(|body| {
+                    body.chars()
+                        .take(DELIVERY_ATTEMPT_RESPONSE_BODY_MAX_LENGTH)
+                        .collect::<String>()
+                        .into()
+                });
+
+            if status_code.is_success() {
                 metrics::WEBHOOK_OUTGOING_RECEIVED_COUNT.add(
                     &metrics::CONTEXT,
                     1,
@@ -827,14 +1030,8 @@ pub async fn trigger_webhook_to_merchant<W: types::OutgoingWebhookType>(
                         business_profile.merchant_id.clone(),
                     )],
                 );
-                let update_event = storage::EventUpdate::UpdateWebhookNotified {
-                    is_webhook_notified: Some(true),
-                };
-                state
-                    .store
-                    .update_event(outgoing_webhook_event_id, update_event)
-                    .await
-                    .change_context(errors::WebhooksFlowError::WebhookEventUpdationFailed)?;
+                new_delivery_attempt.is_delivered = true;
+                Ok(())
             } else {
                 metrics::WEBHOOK_OUTGOING_NOT_RECEIVED_COUNT.add(
                     &metrics::CONTEXT,
@@ -844,12 +1041,53 @@ pub async fn trigger_webhook_to_merchant<W: types::OutgoingWebhookType>(
                         business_profile.merchant_id.clone(),
                     )],
                 );
-                // [#217]: Schedule webhook for retry.
-                Err(errors::WebhooksFlowError::NotReceivedByMerchant).into_report()?;
+                Err(report!(errors::WebhooksFlowError::NotReceivedByMerchant))
             }
         }
-    }
+    };
+
+    state
+        .store
+        .insert_webhook_delivery_attempt(new_delivery_attempt)
+        .await
+        .map_err(|error| logger::error!(webhook_delivery_attempt_insertion_failure=?error))
+        .ok();
+
+    delivery_result
+}
 
+// Add outgoing_webhook_retry task to the process_tracker table.
+// The first retry is scheduled based on the `start_after` of the retry mapping, subsequent
+// retries are scheduled by the workflow itself, based on the retry_count.
+#[instrument(skip_all)]
+pub async fn add_outgoing_webhook_retry_task(
+    db: &dyn StorageInterface,
+    tracking_data: &storage::OutgoingWebhookTrackingData,
+) -> Result<(), sch_errors::ProcessTrackerError> {
+    let schedule_time = outgoing_webhook_retry::get_outgoing_webhook_retry_schedule_time(
+        db,
+        &tracking_data.merchant_id,
+        0,
+    )
+    .await
+    .ok_or(sch_errors::ProcessTrackerError::ConfigurationError)?;
+
+    let process_tracker_id = scheduler::utils::get_process_tracker_id(
+        OUTGOING_WEBHOOK_RETRY_RUNNER,
+        OUTGOING_WEBHOOK_RETRY_TASK,
+        &tracking_data.event_id,
+        &tracking_data.merchant_id,
+    );
+    let mut process_tracker_entry = <storage::ProcessTracker>::make_process_tracker_new(
+        process_tracker_id,
+        OUTGOING_WEBHOOK_RETRY_TASK,
+        OUTGOING_WEBHOOK_RETRY_RUNNER,
+        tracking_data,
+        schedule_time,
+    )?;
+    process_tracker_entry.tag = vec![String::from(OUTGOING_WEBHOOK_RETRY_TAG)];
+
+    db.insert_process(process_tracker_entry).await?;
     Ok(())
 }
 
@@ -1,5 +1,6 @@
 use api_models::webhooks;
 use common_utils::{crypto::SignMessage, ext_traits};
+use diesel_models::business_profile::BusinessProfile;
 use error_stack::ResultExt;
 use serde::Serialize;
 
@@ -8,38 +9,176 @@ use crate::{core::errors, headers, services::request::Maskable};
 pub trait OutgoingWebhookType:
     Serialize + From<webhooks::OutgoingWebhook> + Sync + Send + std::fmt::Debug + 'static
 {
-    fn get_outgoing_webhooks_signature(
+    /// Get the headers carrying the signatures of the webhook, one for each of the signing keys.
+    /// The `timestamp` is the time of delivery, which is part of the signed payload so that
+    /// merchants can reject replayed webhooks.
+    fn get_outgoing_webhooks_signature_headers(
         &self,
-        payment_response_hash_key: Option<String>,
-    ) -> errors::CustomResult<Option<String>, errors::WebhooksFlowError>;
+        signing_keys: Option<&OutgoingWebhookSigningKeys>,
+        timestamp: i64,
+    ) -> errors::CustomResult<Vec<(String, Maskable<String>)>, errors::WebhooksFlowError>;
+}
+
+/// The keys used to sign the outgoing webhooks of a business profile. While the overlap window of
+/// a key rotation lasts, webhooks are signed with both the current and the previous key.
+#[derive(Clone, Debug)]
+pub struct OutgoingWebhookSigningKeys {
+    pub current_key: String,
+    pub previous_key: Option<String>,
+}
+
+impl OutgoingWebhookSigningKeys {
+    pub fn from_business_profile(business_profile: &BusinessProfile) -> Option<Self> {
+        let now = common_utils::date_time::now();
+        let previous_key = business_profile
+            .previous_payment_response_hash_key
+            .clone()
+            .filter(|_| {
+                business_profile
+                    .previous_payment_response_hash_key_expires_at
+                    .map_or(false, |expires_at| expires_at > now)
+            });
+
+        business_profile
+            .payment_response_hash_key
+            .clone()
+            .map(|current_key| Self {
+                current_key,
+                previous_key,
+            })
+    }
 
-    fn add_webhook_header(header: &mut Vec<(String, Maskable<String>)>, signature: String);
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.current_key).chain(self.previous_key.as_ref())
+    }
+
+    /// The key signing the payload alone. That header carries a single signature, so the previous
+    /// key keeps signing it until the overlap window ends, leaving merchants the window to move
+    /// their verifiers over to the current key.
+    pub fn legacy_key(&self) -> &String {
+        self.previous_key.as_ref().unwrap_or(&self.current_key)
+    }
 }
 
 impl OutgoingWebhookType for webhooks::OutgoingWebhook {
-    fn get_outgoing_webhooks_signature(
+    fn get_outgoing_webhooks_signature_headers(
         &self,
-        payment_response_hash_key: Option<String>,
-    ) -> errors::CustomResult<Option<String>, errors::WebhooksFlowError> {
+        signing_keys: Option<&OutgoingWebhookSigningKeys>,
+        timestamp: i64,
+    ) -> errors::CustomResult<Vec<(String, Maskable<String>)>, errors::WebhooksFlowError> {
+        let signing_keys = match signing_keys {
+            Some(signing_keys) => signing_keys,
+            None => return Ok(Vec::new()),
+        };
+
         let webhook_signature_payload =
             ext_traits::Encode::<serde_json::Value>::encode_to_string_of_json(self)
                 .change_context(errors::WebhooksFlowError::OutgoingWebhookEncodingFailed)
                 .attach_printable("failed encoding outgoing webhook payload")?;
 
-        Ok(payment_response_hash_key
-            .map(|key| {
-                common_utils::crypto::HmacSha512::sign_message(
-                    &common_utils::crypto::HmacSha512,
-                    key.as_bytes(),
-                    webhook_signature_payload.as_bytes(),
-                )
-            })
-            .transpose()
-            .change_context(errors::WebhooksFlowError::OutgoingWebhookSigningFailed)
-            .attach_printable("Failed to sign the message")?
-            .map(hex::encode))
+        get_signature_headers(&webhook_signature_payload, signing_keys, timestamp)
+    }
+}
+
+fn get_signature_headers(
+    payload: &str,
+    signing_keys: &OutgoingWebhookSigningKeys,
+    timestamp: i64,
+) -> errors::CustomResult<Vec<(String, Maskable<String>)>, errors::WebhooksFlowError> {
+    // Retained for merchants verifying the signature of the payload alone
+    let signature = sign_hmac_sha512(signing_keys.legacy_key(), payload)?;
+
+    let timestamped_signature_payload = format!("{timestamp}.{payload}");
+    let timestamped_signatures = signing_keys
+        .keys()
+        .map(|key| {
+            sign_hmac_sha512(key, &timestamped_signature_payload)
+                .map(|signature| format!("v1={signature}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .join(",");
+
+    Ok(vec![
+        (headers::X_WEBHOOK_SIGNATURE.to_string(), signature.into()),
+        (
+            headers::X_WEBHOOK_SIGNATURE_TIMESTAMPED.to_string(),
+            format!("t={timestamp},{timestamped_signatures}").into(),
+        ),
+    ])
+}
+
+fn sign_hmac_sha512(
+    key: &str,
+    payload: &str,
+) -> errors::CustomResult<String, errors::WebhooksFlowError> {
+    common_utils::crypto::HmacSha512::sign_message(
+        &common_utils::crypto::HmacSha512,
+        key.as_bytes(),
+        payload.as_bytes(),
+    )
+    .change_context(errors::WebhooksFlowError::OutgoingWebhookSigningFailed)
+    .attach_printable("Failed to sign the message")
+    .map(hex::encode)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    const PAYLOAD: &str = r#"{"event_id":"evt_123"}"#;
+    const TIMESTAMP: i64 = 1_700_000_000;
+
+    fn get_header(headers: &[(String, Maskable<String>)], name: &str) -> String {
+        headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.clone().into_inner())
+            .unwrap()
     }
-    fn add_webhook_header(header: &mut Vec<(String, Maskable<String>)>, signature: String) {
-        header.push((headers::X_WEBHOOK_SIGNATURE.to_string(), signature.into()))
+
+    #[test]
+    fn test_signature_headers_without_rotation() {
+        let signing_keys = OutgoingWebhookSigningKeys {
+            current_key: "current".to_string(),
+            previous_key: None,
+        };
+        let headers = get_signature_headers(PAYLOAD, &signing_keys, TIMESTAMP).unwrap();
+
+        assert_eq!(
+            get_header(&headers, headers::X_WEBHOOK_SIGNATURE),
+            sign_hmac_sha512("current", PAYLOAD).unwrap()
+        );
+        assert_eq!(
+            get_header(&headers, headers::X_WEBHOOK_SIGNATURE_TIMESTAMPED),
+            format!(
+                "t={TIMESTAMP},v1={}",
+                sign_hmac_sha512("current", &format!("{TIMESTAMP}.{PAYLOAD}")).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_signature_headers_during_rotation() {
+        let signing_keys = OutgoingWebhookSigningKeys {
+            current_key: "current".to_string(),
+            previous_key: Some("previous".to_string()),
+        };
+        let headers = get_signature_headers(PAYLOAD, &signing_keys, TIMESTAMP).unwrap();
+
+        // The verifiers of the legacy header still hold the previous key
+        assert_eq!(
+            get_header(&headers, headers::X_WEBHOOK_SIGNATURE),
+            sign_hmac_sha512("previous", PAYLOAD).unwrap()
+        );
+        let timestamped_payload = format!("{TIMESTAMP}.{PAYLOAD}");
+        assert_eq!(
+            get_header(&headers, headers::X_WEBHOOK_SIGNATURE_TIMESTAMPED),
+            format!(
+                "t={TIMESTAMP},v1={},v1={}",
+                sign_hmac_sha512("current", &timestamped_payload).unwrap(),
+                sign_hmac_sha512("previous", &timestamped_payload).unwrap()
+            )
+        );
     }
 }
@@ -2,6 +2,7 @@ use std::marker::PhantomData;
 
 use common_utils::{errors::CustomResult, ext_traits::ValueExt};
 use error_stack::ResultExt;
+use masking::PeekInterface;
 
 use crate::{
     core::{
@@ -58,6 +59,36 @@ pub async fn is_webhook_event_disabled(
     }
 }
 
+/// Get the urls of the endpoints the outgoing webhook for the `event_type` should be posted to.
+/// The `webhook_url` receives all events, while each of the `webhook_endpoints` receives only the
+/// events it is subscribed to.
+pub fn get_subscribed_webhook_urls(
+    webhook_details: &api::WebhookDetails,
+    event_type: api_models::enums::EventType,
+) -> Vec<String> {
+    let subscribed_endpoint_urls = webhook_details
+        .webhook_endpoints
+        .iter()
+        .flatten()
+        .filter(|endpoint| endpoint.enabled_events.contains(&event_type))
+        .map(|endpoint| endpoint.url.peek());
+
+    let mut webhook_urls: Vec<String> = Vec::new();
+    for url in webhook_details
+        .webhook_url
+        .as_ref()
+        .map(PeekInterface::peek)
+        .into_iter()
+        .chain(subscribed_endpoint_urls)
+    {
+        if !webhook_urls.contains(url) {
+            webhook_urls.push(url.to_owned());
+        }
+    }
+
+    webhook_urls
+}
+
 pub async fn construct_webhook_router_data<'a>(
     connector_name: &str,
     merchant_connector_account: domain::MerchantConnectorAccount,
@@ -114,6 +145,90 @@ pub async fn construct_webhook_router_data<'a>(
         external_latency: None,
         apple_pay_flow: None,
         frm_metadata: None,
+        merchant_connector_id: None,
     };
     Ok(router_data)
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use api_models::{admin::WebhookEndpoint, enums::EventType};
+
+    use super::*;
+
+    fn webhook_details(
+        webhook_url: Option<&str>,
+        webhook_endpoints: Option<Vec<(&str, Vec<EventType>)>>,
+    ) -> api::WebhookDetails {
+        serde_json::from_value(serde_json::json!({
+            "webhook_url": webhook_url,
+            "webhook_endpoints": webhook_endpoints.map(|endpoints| {
+                endpoints
+                    .into_iter()
+                    .map(|(url, enabled_events)| WebhookEndpoint {
+                        url: url.to_string().into(),
+                        enabled_events,
+                    })
+                    .collect::<Vec<_>>()
+            }),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_webhook_url_receives_all_events() {
+        let webhook_details = webhook_details(Some("https://merchant.com/webhooks"), None);
+
+        assert_eq!(
+            get_subscribed_webhook_urls(&webhook_details, EventType::DisputeOpened),
+            vec!["https://merchant.com/webhooks"]
+        );
+    }
+
+    #[test]
+    fn test_webhook_endpoints_receive_subscribed_events() {
+        let webhook_details = webhook_details(
+            Some("https://merchant.com/webhooks"),
+            Some(vec![
+                (
+                    "https://merchant.com/refunds",
+                    vec![EventType::RefundSucceeded, EventType::RefundFailed],
+                ),
+                (
+                    "https://merchant.com/disputes",
+                    vec![EventType::DisputeOpened],
+                ),
+                (
+                    "https://merchant.com/webhooks",
+                    vec![EventType::RefundFailed],
+                ),
+            ]),
+        );
+
+        assert_eq!(
+            get_subscribed_webhook_urls(&webhook_details, EventType::RefundFailed),
+            vec![
+                "https://merchant.com/webhooks",
+                "https://merchant.com/refunds"
+            ]
+        );
+        assert_eq!(
+            get_subscribed_webhook_urls(&webhook_details, EventType::PaymentSucceeded),
+            vec!["https://merchant.com/webhooks"]
+        );
+    }
+
+    #[test]
+    fn test_no_webhook_endpoint_subscribed() {
+        let webhook_details = webhook_details(
+            None,
+            Some(vec![(
+                "https://merchant.com/refunds",
+                vec![EventType::RefundSucceeded],
+            )]),
+        );
+
+        assert!(get_subscribed_webhook_urls(&webhook_details, EventType::PaymentFailed).is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+use api_models::webhook_events::{
+    EventDeliveryAttemptResponse, EventListConstraints, EventListItemResponse,
+};
+use error_stack::{report, IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+
+use crate::{
+    core::errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    db::StorageInterface,
+    logger,
+    routes::AppState,
+    services::ApplicationResponse,
+    types::{api, domain, storage, transformers::ForeignInto},
+    workflows::outgoing_webhook_retry,
+};
+
+#[instrument(skip(state))]
+pub async fn list_events(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    constraints: EventListConstraints,
+) -> RouterResponse<Vec<EventListItemResponse>> {
+    let events = state
+        .store
+        .list_events_by_merchant_id_primary_object_id(
+            &merchant_account.merchant_id,
+            &constraints.object_id,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list events")?;
+
+    Ok(ApplicationResponse::Json(
+        events.into_iter().map(ForeignInto::foreign_into).collect(),
+    ))
+}
+
+#[instrument(skip(state))]
+pub async fn list_delivery_attempts(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    event_id: String,
+) -> RouterResponse<Vec<EventDeliveryAttemptResponse>> {
+    let db = &*state.store;
+    let event = find_merchant_event(db, &merchant_account.merchant_id, &event_id).await?;
+
+    let delivery_attempts = db
+        .find_webhook_delivery_attempts_by_merchant_id_event_id(
+            &merchant_account.merchant_id,
+            &event.event_id,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list webhook delivery attempts")?;
+
+    Ok(ApplicationResponse::Json(
+        delivery_attempts
+            .into_iter()
+            .map(ForeignInto::foreign_into)
+            .collect(),
+    ))
+}
+
+/// Redeliver the webhook for an event to the merchant, with the current state of the object the
+/// event was raised for. The outcome of the delivery is reflected in the returned event.
+#[instrument(skip(state))]
+pub async fn retry_delivery(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    event_id: String,
+) -> RouterResponse<EventListItemResponse> {
+    let db = &*state.store;
+    let event = find_merchant_event(db, &merchant_account.merchant_id, &event_id).await?;
+
+    let business_profile_id = event
+        .business_profile_id
+        .clone()
+        .ok_or(errors::ApiErrorResponse::PreconditionFailed {
+            message: "The event was not raised for a business profile, and cannot be redelivered"
+                .to_string(),
+        })
+        .into_report()?;
+
+    let business_profile = db
+        .find_business_profile_by_profile_id(&business_profile_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::BusinessProfileNotFound {
+            id: business_profile_id.clone(),
+        })?;
+
+    let tracking_data = storage::OutgoingWebhookTrackingData {
+        event_id: event.event_id.clone(),
+        merchant_id: merchant_account.merchant_id.clone(),
+        business_profile_id,
+        event_type: event.event_type,
+        event_class: event.event_class,
+        primary_object_id: event.primary_object_id.clone(),
+        primary_object_type: event.primary_object_type,
+    };
+
+    let content = outgoing_webhook_retry::get_outgoing_webhook_content(
+        &state,
+        &merchant_account,
+        key_store,
+        &tracking_data,
+    )
+    .await
+    .map_err(|error| {
+        logger::error!(?error);
+        report!(errors::ApiErrorResponse::InternalServerError)
+    })
+    .attach_printable("Failed to fetch the object the event was raised for")?;
+
+    let outgoing_webhook = api::OutgoingWebhook {
+        merchant_id: merchant_account.merchant_id.clone(),
+        event_id: event.event_id.clone(),
+        event_type: event.event_type,
+        content,
+        timestamp: event.created_at,
+    };
+
+    let delivery_result = super::trigger_appropriate_webhook_to_merchant(
+        &state,
+        &merchant_account,
+        business_profile,
+        outgoing_webhook,
+        storage::enums::WebhookDeliveryAttemptTrigger::ManualRetry,
+    )
+    .await;
+
+    match delivery_result {
+        Ok(()) => {}
+        // The failed delivery has been recorded, and is reflected in the event
+        Err(error) if error.current_context().is_webhook_delivery_retryable() => {
+            logger::warn!(?error, "Manual outgoing webhook delivery failed");
+        }
+        Err(error) => match error.current_context() {
+            errors::WebhooksFlowError::MerchantWebhookDetailsNotFound
+            | errors::WebhooksFlowError::MerchantWebhookURLNotConfigured => Err(error
+                .change_context(errors::ApiErrorResponse::PreconditionFailed {
+                    message: "Webhook URL is not configured for the business profile".to_string(),
+                }))?,
+            _ => Err(error.change_context(errors::ApiErrorResponse::WebhookProcessingFailure))?,
+        },
+    }
+
+    let event = db
+        .find_event_by_event_id(&event.event_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch event after redelivery")?;
+
+    Ok(ApplicationResponse::Json(event.foreign_into()))
+}
+
+async fn find_merchant_event(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    event_id: &str,
+) -> RouterResult<storage::Event> {
+    let event_not_found = || errors::ApiErrorResponse::GenericNotFoundError {
+        message: "Event does not exist in our records".to_string(),
+    };
+
+    let event = db
+        .find_event_by_event_id(event_id)
+        .await
+        .to_not_found_response(event_not_found())?;
+
+    // Events are looked up by their ID alone, ensure they belong to the merchant
+    if event.merchant_id.as_deref() != Some(merchant_id) {
+        return Err(report!(event_not_found()));
+    }
+
+    Ok(event)
+}
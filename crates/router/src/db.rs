@@ -1,5 +1,6 @@
 pub mod address;
 pub mod api_keys;
+pub mod audit_log;
 pub mod authorization;
 pub mod business_profile;
 pub mod cache;
@@ -14,12 +15,14 @@ pub mod events;
 pub mod file;
 pub mod fraud_check;
 pub mod gsm;
+pub mod idempotency_key;
 mod kafka_store;
 pub mod locker_mock_up;
 pub mod mandate;
 pub mod merchant_account;
 pub mod merchant_connector_account;
 pub mod merchant_key_store;
+pub mod oidc_config;
 pub mod organization;
 pub mod payment_link;
 pub mod payment_method;
@@ -27,9 +30,13 @@ pub mod payout_attempt;
 pub mod payouts;
 pub mod refund;
 pub mod reverse_lookup;
+pub mod role;
 pub mod routing_algorithm;
+pub mod routing_algorithm_activation;
 pub mod user;
+pub mod user_key_store;
 pub mod user_role;
+pub mod webhook_delivery_attempt;
 
 use data_models::payments::{
     payment_attempt::PaymentAttemptInterface, payment_intent::PaymentIntentInterface,
@@ -98,11 +105,18 @@ pub trait StorageInterface:
     + business_profile::BusinessProfileInterface
     + OrganizationInterface
     + routing_algorithm::RoutingAlgorithmInterface
+    + routing_algorithm_activation::RoutingAlgorithmActivationInterface
     + gsm::GsmInterface
+    + idempotency_key::IdempotencyKeyInterface
     + user::UserInterface
+    + user_key_store::UserKeyStoreInterface
     + user_role::UserRoleInterface
+    + role::RoleInterface
+    + oidc_config::OidcConfigInterface
     + authorization::AuthorizationInterface
     + user::sample_data::BatchSampleDataInterface
+    + webhook_delivery_attempt::WebhookDeliveryAttemptInterface
+    + audit_log::AuditLogInterface
     + 'static
 {
     fn get_scheduler_db(&self) -> Box<dyn scheduler::SchedulerInterface>;
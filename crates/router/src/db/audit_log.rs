@@ -0,0 +1,135 @@
+use error_stack::{IntoReport, ResultExt};
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+/// The audit log is append-only, its entries can only be inserted and listed
+#[async_trait::async_trait]
+pub trait AuditLogInterface {
+    async fn insert_audit_log(
+        &self,
+        audit_log: storage::AuditLogNew,
+    ) -> CustomResult<storage::AuditLog, errors::StorageError>;
+
+    async fn filter_audit_logs_by_constraints(
+        &self,
+        constraints: storage::AuditLogListConstraints,
+    ) -> CustomResult<Vec<storage::AuditLog>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl AuditLogInterface for Store {
+    async fn insert_audit_log(
+        &self,
+        audit_log: storage::AuditLogNew,
+    ) -> CustomResult<storage::AuditLog, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        audit_log
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn filter_audit_logs_by_constraints(
+        &self,
+        constraints: storage::AuditLogListConstraints,
+    ) -> CustomResult<Vec<storage::AuditLog>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::AuditLog::filter_by_constraints(&conn, constraints)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditLogInterface for MockDb {
+    async fn insert_audit_log(
+        &self,
+        audit_log: storage::AuditLogNew,
+    ) -> CustomResult<storage::AuditLog, errors::StorageError> {
+        let mut audit_logs = self.audit_logs.lock().await;
+        let audit_log = storage::AuditLog {
+            id: i64::try_from(audit_logs.len())
+                .into_report()
+                .change_context(errors::StorageError::MockDbError)?
+                + 1,
+            actor_type: audit_log.actor_type,
+            actor_id: audit_log.actor_id,
+            merchant_id: audit_log.merchant_id,
+            flow: audit_log.flow,
+            resource_type: audit_log.resource_type,
+            resource_id: audit_log.resource_id,
+            request_id: audit_log.request_id,
+            request: audit_log.request,
+            snapshot_before: audit_log.snapshot_before,
+            snapshot_after: audit_log.snapshot_after,
+            created_at: audit_log.created_at,
+        };
+        audit_logs.push(audit_log.clone());
+        Ok(audit_log)
+    }
+
+    async fn filter_audit_logs_by_constraints(
+        &self,
+        constraints: storage::AuditLogListConstraints,
+    ) -> CustomResult<Vec<storage::AuditLog>, errors::StorageError> {
+        let mut audit_logs = self
+            .audit_logs
+            .lock()
+            .await
+            .iter()
+            .filter(|audit_log| {
+                constraints
+                    .merchant_id
+                    .as_ref()
+                    .map_or(true, |merchant_id| {
+                        audit_log.merchant_id.as_ref() == Some(merchant_id)
+                    })
+                    && constraints
+                        .actor_type
+                        .map_or(true, |actor_type| audit_log.actor_type == actor_type)
+                    && constraints.actor_id.as_ref().map_or(true, |actor_id| {
+                        audit_log.actor_id.as_ref() == Some(actor_id)
+                    })
+                    && constraints
+                        .resource_type
+                        .as_ref()
+                        .map_or(true, |resource_type| {
+                            &audit_log.resource_type == resource_type
+                        })
+                    && constraints
+                        .resource_id
+                        .as_ref()
+                        .map_or(true, |resource_id| {
+                            audit_log.resource_id.as_ref() == Some(resource_id)
+                        })
+                    && constraints
+                        .start_time
+                        .map_or(true, |start_time| audit_log.created_at >= start_time)
+                    && constraints
+                        .end_time
+                        .map_or(true, |end_time| audit_log.created_at <= end_time)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        // The latest first, the entries being inserted in order
+        audit_logs.reverse();
+
+        let offset = constraints
+            .offset
+            .and_then(|offset| usize::try_from(offset).ok())
+            .unwrap_or(0);
+        let limit = constraints
+            .limit
+            .and_then(|limit| usize::try_from(limit).ok())
+            .unwrap_or(usize::MAX);
+
+        Ok(audit_logs.into_iter().skip(offset).take(limit).collect())
+    }
+}
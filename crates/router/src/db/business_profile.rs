@@ -1,10 +1,12 @@
+use async_bb8_diesel::AsyncConnection;
+use diesel_models::errors::DatabaseError;
 use error_stack::IntoReport;
 
 use super::Store;
 use crate::{
     connection,
     core::errors::{self, CustomResult},
-    db::MockDb,
+    db::{routing_algorithm_activation::RoutingAlgorithmActivationInterface, MockDb},
     types::storage::{self, business_profile},
 };
 
@@ -32,6 +34,15 @@ pub trait BusinessProfileInterface {
         business_profile_update: business_profile::BusinessProfileUpdateInternal,
     ) -> CustomResult<business_profile::BusinessProfile, errors::StorageError>;
 
+    /// Updates the business profile and records the change of its routing algorithm in the
+    /// activation history, in the same transaction
+    async fn update_business_profile_routing_algorithm_with_activation(
+        &self,
+        current_state: business_profile::BusinessProfile,
+        business_profile_update: business_profile::BusinessProfileUpdateInternal,
+        activation: storage::RoutingAlgorithmActivationNew,
+    ) -> CustomResult<business_profile::BusinessProfile, errors::StorageError>;
+
     async fn delete_business_profile_by_profile_id_merchant_id(
         &self,
         profile_id: &str,
@@ -101,6 +112,34 @@ impl BusinessProfileInterface for Store {
         .into_report()
     }
 
+    async fn update_business_profile_routing_algorithm_with_activation(
+        &self,
+        current_state: business_profile::BusinessProfile,
+        business_profile_update: business_profile::BusinessProfileUpdateInternal,
+        activation: storage::RoutingAlgorithmActivationNew,
+    ) -> CustomResult<business_profile::BusinessProfile, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        conn.transaction_async(|conn| async move {
+            let updated_business_profile =
+                storage::business_profile::BusinessProfile::update_by_profile_id(
+                    current_state,
+                    &conn,
+                    business_profile_update,
+                )
+                .await
+                .map_err(|error| *error.current_context())?;
+            activation
+                .insert(&conn)
+                .await
+                .map_err(|error| *error.current_context())?;
+            Ok::<_, DatabaseError>(updated_business_profile)
+        })
+        .await
+        .into_report()
+        .map_err(Into::into)
+        .into_report()
+    }
+
     async fn delete_business_profile_by_profile_id_merchant_id(
         &self,
         profile_id: &str,
@@ -190,6 +229,19 @@ impl BusinessProfileInterface for MockDb {
             )
     }
 
+    async fn update_business_profile_routing_algorithm_with_activation(
+        &self,
+        current_state: business_profile::BusinessProfile,
+        business_profile_update: business_profile::BusinessProfileUpdateInternal,
+        activation: storage::RoutingAlgorithmActivationNew,
+    ) -> CustomResult<business_profile::BusinessProfile, errors::StorageError> {
+        let updated_business_profile = self
+            .update_business_profile_by_profile_id(current_state, business_profile_update)
+            .await?;
+        self.insert_routing_algorithm_activation(activation).await?;
+        Ok(updated_business_profile)
+    }
+
     async fn delete_business_profile_by_profile_id_merchant_id(
         &self,
         profile_id: &str,
@@ -2,7 +2,7 @@ use common_utils::ext_traits::AsyncExt;
 use error_stack::ResultExt;
 use redis_interface::errors::RedisError;
 use storage_impl::redis::{
-    cache::{Cache, CacheKind, Cacheable},
+    cache::{Cache, CacheKind, Cacheable, REDIS_CACHE_KEYS_SET},
     pub_sub::PubSubInterface,
 };
 
@@ -37,6 +37,10 @@ where
             .serialize_and_set_key(key, &data)
             .await
             .change_context(errors::StorageError::KVError)?;
+        redis
+            .add_member_to_set(REDIS_CACHE_KEYS_SET, key)
+            .await
+            .change_context(errors::StorageError::KVError)?;
         Ok::<_, error_stack::Report<errors::StorageError>>(data)
     };
     match redis_val {
@@ -1,22 +1,17 @@
 use common_utils::ext_traits::AsyncExt;
-use error_stack::{IntoReport, ResultExt};
+use error_stack::ResultExt;
 use futures::future::try_join_all;
-use masking::PeekInterface;
 use router_env::{instrument, tracing};
 
-use super::{MockDb, Store};
+use super::MockDb;
 use crate::{
-    connection,
-    core::{
-        customers::REDACTED,
-        errors::{self, CustomResult},
-    },
+    core::errors::{self, CustomResult},
     types::{
         domain::{
             self,
             behaviour::{Conversion, ReverseConversion},
         },
-        storage,
+        storage::{self, enums::MerchantStorageScheme},
     },
 };
 
@@ -29,6 +24,7 @@ where
         &self,
         customer_id: &str,
         merchant_id: &str,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<bool, errors::StorageError>;
 
     async fn find_customer_optional_by_customer_id_merchant_id(
@@ -36,6 +32,7 @@ where
         customer_id: &str,
         merchant_id: &str,
         key_store: &domain::MerchantKeyStore,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Option<domain::Customer>, errors::StorageError>;
 
     async fn update_customer_by_customer_id_merchant_id(
@@ -44,6 +41,7 @@ where
         merchant_id: String,
         customer: storage::CustomerUpdate,
         key_store: &domain::MerchantKeyStore,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<domain::Customer, errors::StorageError>;
 
     async fn find_customer_by_customer_id_merchant_id(
@@ -51,93 +49,179 @@ where
         customer_id: &str,
         merchant_id: &str,
         key_store: &domain::MerchantKeyStore,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<domain::Customer, errors::StorageError>;
 
     async fn list_customers_by_merchant_id(
         &self,
         merchant_id: &str,
         key_store: &domain::MerchantKeyStore,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<domain::Customer>, errors::StorageError>;
 
     async fn insert_customer(
         &self,
         customer_data: domain::Customer,
         key_store: &domain::MerchantKeyStore,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<domain::Customer, errors::StorageError>;
 }
 
-#[async_trait::async_trait]
-impl CustomerInterface for Store {
-    async fn find_customer_optional_by_customer_id_merchant_id(
-        &self,
-        customer_id: &str,
-        merchant_id: &str,
-        key_store: &domain::MerchantKeyStore,
-    ) -> CustomResult<Option<domain::Customer>, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
-        let maybe_customer: Option<domain::Customer> =
-            storage::Customer::find_optional_by_customer_id_merchant_id(
+#[cfg(not(feature = "kv_store"))]
+mod storage {
+    use common_utils::{ext_traits::AsyncExt, fallback_dual_read_not_found};
+    use error_stack::{IntoReport, ResultExt};
+    use futures::future::try_join_all;
+    use masking::PeekInterface;
+    use router_env::{instrument, tracing};
+
+    use super::CustomerInterface;
+    use crate::{
+        connection,
+        core::{
+            customers::REDACTED,
+            errors::{self, CustomResult},
+        },
+        services::Store,
+        types::{
+            domain::{
+                self,
+                behaviour::{Conversion, ReverseConversion},
+            },
+            storage::{self, enums::MerchantStorageScheme},
+        },
+    };
+
+    #[async_trait::async_trait]
+    impl CustomerInterface for Store {
+        async fn find_customer_optional_by_customer_id_merchant_id(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+            key_store: &domain::MerchantKeyStore,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<Option<domain::Customer>, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            let maybe_customer: Option<domain::Customer> =
+                storage::Customer::find_optional_by_customer_id_merchant_id(
+                    &conn,
+                    customer_id,
+                    merchant_id,
+                )
+                .await
+                .map_err(Into::into)
+                .into_report()?
+                .async_map(|c| async {
+                    c.convert(key_store.key.get_inner())
+                        .await
+                        .change_context(errors::StorageError::DecryptionError)
+                })
+                .await
+                .transpose()?;
+            maybe_customer.map_or(Ok(None), |customer| {
+                // in the future, once #![feature(is_some_and)] is stable, we can make this more concise:
+                // `if customer.name.is_some_and(|ref name| name == REDACTED) ...`
+                match customer.name {
+                    Some(ref name) if name.peek() == REDACTED => {
+                        Err(errors::StorageError::CustomerRedacted)?
+                    }
+                    _ => Ok(Some(customer)),
+                }
+            })
+        }
+
+        #[instrument(skip_all)]
+        async fn update_customer_by_customer_id_merchant_id(
+            &self,
+            customer_id: String,
+            merchant_id: String,
+            customer: storage::CustomerUpdate,
+            key_store: &domain::MerchantKeyStore,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<domain::Customer, errors::StorageError> {
+            let conn = connection::pg_connection_write(self).await?;
+            storage::Customer::update_by_customer_id_merchant_id(
                 &conn,
                 customer_id,
                 merchant_id,
+                customer.into(),
             )
             .await
             .map_err(Into::into)
-            .into_report()?
-            .async_map(|c| async {
+            .into_report()
+            .async_and_then(|c| async {
                 c.convert(key_store.key.get_inner())
                     .await
                     .change_context(errors::StorageError::DecryptionError)
             })
             .await
-            .transpose()?;
-        maybe_customer.map_or(Ok(None), |customer| {
-            // in the future, once #![feature(is_some_and)] is stable, we can make this more concise:
-            // `if customer.name.is_some_and(|ref name| name == REDACTED) ...`
+        }
+
+        async fn find_customer_by_customer_id_merchant_id(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+            key_store: &domain::MerchantKeyStore,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<domain::Customer, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            let customer: domain::Customer =
+                storage::Customer::find_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
+                    .await
+                    .map_err(Into::into)
+                    .into_report()
+                    .async_and_then(|c| async {
+                        c.convert(key_store.key.get_inner())
+                            .await
+                            .change_context(errors::StorageError::DecryptionError)
+                    })
+                    .await?;
             match customer.name {
                 Some(ref name) if name.peek() == REDACTED => {
                     Err(errors::StorageError::CustomerRedacted)?
                 }
-                _ => Ok(Some(customer)),
+                _ => Ok(customer),
             }
-        })
-    }
+        }
 
-    #[instrument(skip_all)]
-    async fn update_customer_by_customer_id_merchant_id(
-        &self,
-        customer_id: String,
-        merchant_id: String,
-        customer: storage::CustomerUpdate,
-        key_store: &domain::MerchantKeyStore,
-    ) -> CustomResult<domain::Customer, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        storage::Customer::update_by_customer_id_merchant_id(
-            &conn,
-            customer_id,
-            merchant_id,
-            customer.into(),
-        )
-        .await
-        .map_err(Into::into)
-        .into_report()
-        .async_and_then(|c| async {
-            c.convert(key_store.key.get_inner())
+        async fn list_customers_by_merchant_id(
+            &self,
+            merchant_id: &str,
+            key_store: &domain::MerchantKeyStore,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<Vec<domain::Customer>, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+
+            let encrypted_customers = storage::Customer::list_by_merchant_id(&conn, merchant_id)
                 .await
-                .change_context(errors::StorageError::DecryptionError)
-        })
-        .await
-    }
+                .map_err(Into::into)
+                .into_report()?;
 
-    async fn find_customer_by_customer_id_merchant_id(
-        &self,
-        customer_id: &str,
-        merchant_id: &str,
-        key_store: &domain::MerchantKeyStore,
-    ) -> CustomResult<domain::Customer, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
-        let customer: domain::Customer =
-            storage::Customer::find_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
+            let customers = try_join_all(encrypted_customers.into_iter().map(
+                |encrypted_customer| async {
+                    encrypted_customer
+                        .convert(key_store.key.get_inner())
+                        .await
+                        .change_context(errors::StorageError::DecryptionError)
+                },
+            ))
+            .await?;
+
+            Ok(customers)
+        }
+
+        async fn insert_customer(
+            &self,
+            customer_data: domain::Customer,
+            key_store: &domain::MerchantKeyStore,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<domain::Customer, errors::StorageError> {
+            let conn = connection::pg_connection_write(self).await?;
+            customer_data
+                .construct_new()
+                .await
+                .change_context(errors::StorageError::EncryptionError)?
+                .insert(&conn)
                 .await
                 .map_err(Into::into)
                 .into_report()
@@ -146,72 +230,444 @@ impl CustomerInterface for Store {
                         .await
                         .change_context(errors::StorageError::DecryptionError)
                 })
-                .await?;
-        match customer.name {
-            Some(ref name) if name.peek() == REDACTED => {
-                Err(errors::StorageError::CustomerRedacted)?
-            }
-            _ => Ok(customer),
+                .await
+        }
+
+        async fn delete_customer_by_customer_id_merchant_id(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<bool, errors::StorageError> {
+            let conn = connection::pg_connection_write(self).await?;
+            storage::Customer::delete_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
+                .await
+                .map_err(Into::into)
+                .into_report()
         }
     }
+}
 
-    async fn list_customers_by_merchant_id(
-        &self,
-        merchant_id: &str,
-        key_store: &domain::MerchantKeyStore,
-    ) -> CustomResult<Vec<domain::Customer>, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
+#[cfg(feature = "kv_store")]
+mod storage {
+    use std::collections::HashSet;
 
-        let encrypted_customers = storage::Customer::list_by_merchant_id(&conn, merchant_id)
-            .await
-            .map_err(Into::into)
-            .into_report()?;
+    use common_utils::ext_traits::AsyncExt;
+    use error_stack::{IntoReport, ResultExt};
+    use futures::future::try_join_all;
+    use masking::PeekInterface;
+    use redis_interface::HsetnxReply;
+    use router_env::{instrument, tracing};
+    use storage_impl::redis::kv_store::{
+        kv_wrapper, scan_merchant_kv_rows, KvOperation, RedisConnInterface,
+    };
+
+    use super::CustomerInterface;
+    use crate::{
+        connection,
+        core::{
+            customers::REDACTED,
+            errors::{self, utils::RedisErrorExt, CustomResult},
+        },
+        services::Store,
+        types::{
+            domain::{
+                self,
+                behaviour::{Conversion, ReverseConversion},
+            },
+            storage::{self, enums::MerchantStorageScheme, kv},
+        },
+        utils::{self, db_utils},
+    };
 
-        let customers = try_join_all(encrypted_customers.into_iter().map(
-            |encrypted_customer| async {
-                encrypted_customer
-                    .convert(key_store.key.get_inner())
+    #[async_trait::async_trait]
+    impl CustomerInterface for Store {
+        async fn find_customer_optional_by_customer_id_merchant_id(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+            key_store: &domain::MerchantKeyStore,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<Option<domain::Customer>, errors::StorageError> {
+            let database_call = || async {
+                let conn = connection::pg_connection_read(self).await?;
+                storage::Customer::find_optional_by_customer_id_merchant_id(
+                    &conn,
+                    customer_id,
+                    merchant_id,
+                )
+                .await
+                .map_err(Into::into)
+                .into_report()
+            };
+            let key = format!("mid_{merchant_id}_cust_{customer_id}");
+            let field = format!("cust_{customer_id}");
+            let redis_call = || async {
+                kv_wrapper(self, KvOperation::<storage::Customer>::HGet(&field), &key)
+                    .await?
+                    .try_into_hget()
+                    .map(Some)
+            };
+            let maybe_customer = match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => match database_call().await? {
+                    Some(customer) => Ok(Some(customer)),
+                    None if self.is_dual_read_enabled(merchant_id).await => {
+                        Box::pin(db_utils::try_redis_get_else_try_database_get(
+                            redis_call(),
+                            database_call,
+                        ))
+                        .await
+                    }
+                    None => Ok(None),
+                },
+                MerchantStorageScheme::RedisKv => {
+                    Box::pin(db_utils::try_redis_get_else_try_database_get(
+                        redis_call(),
+                        database_call,
+                    ))
+                    .await
+                }
+            }?
+            .async_map(|c| async {
+                c.convert(key_store.key.get_inner())
                     .await
                     .change_context(errors::StorageError::DecryptionError)
-            },
-        ))
-        .await?;
+            })
+            .await
+            .transpose()?;
 
-        Ok(customers)
-    }
+            maybe_customer.map_or(Ok(None), |customer| {
+                // in the future, once #![feature(is_some_and)] is stable, we can make this more concise:
+                // `if customer.name.is_some_and(|ref name| name == REDACTED) ...`
+                match customer.name {
+                    Some(ref name) if name.peek() == REDACTED => {
+                        Err(errors::StorageError::CustomerRedacted)?
+                    }
+                    _ => Ok(Some(customer)),
+                }
+            })
+        }
 
-    async fn insert_customer(
-        &self,
-        customer_data: domain::Customer,
-        key_store: &domain::MerchantKeyStore,
-    ) -> CustomResult<domain::Customer, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        customer_data
-            .construct_new()
-            .await
-            .change_context(errors::StorageError::EncryptionError)?
-            .insert(&conn)
-            .await
-            .map_err(Into::into)
-            .into_report()
+        #[instrument(skip_all)]
+        async fn update_customer_by_customer_id_merchant_id(
+            &self,
+            customer_id: String,
+            merchant_id: String,
+            customer: storage::CustomerUpdate,
+            key_store: &domain::MerchantKeyStore,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<domain::Customer, errors::StorageError> {
+            let updated_customer = match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_connection_write(self).await?;
+                    storage::Customer::update_by_customer_id_merchant_id(
+                        &conn,
+                        customer_id,
+                        merchant_id,
+                        customer.into(),
+                    )
+                    .await
+                    .map_err(Into::into)
+                    .into_report()?
+                }
+                MerchantStorageScheme::RedisKv => {
+                    let key = format!("mid_{merchant_id}_cust_{customer_id}");
+                    let field = format!("cust_{customer_id}");
+                    let database_call = || async {
+                        let conn = connection::pg_connection_read(self).await?;
+                        storage::Customer::find_by_customer_id_merchant_id(
+                            &conn,
+                            &customer_id,
+                            &merchant_id,
+                        )
+                        .await
+                        .map_err(Into::into)
+                        .into_report()
+                    };
+                    let this = Box::pin(db_utils::try_redis_get_else_try_database_get(
+                        async {
+                            kv_wrapper(self, KvOperation::<storage::Customer>::HGet(&field), &key)
+                                .await?
+                                .try_into_hget()
+                        },
+                        database_call,
+                    ))
+                    .await?;
+
+                    let customer_update = storage::CustomerUpdateInternal::from(customer);
+                    let updated_customer = customer_update.clone().apply_changeset(this.clone());
+
+                    let redis_value = utils::Encode::<storage::Customer>::encode_to_string_of_json(
+                        &updated_customer,
+                    )
+                    .change_context(errors::StorageError::SerializationFailed)?;
+
+                    let redis_entry = kv::TypedSql {
+                        op: kv::DBOperation::Update {
+                            updatable: kv::Updateable::CustomerUpdate(kv::CustomerUpdateMems {
+                                orig: this,
+                                update_data: customer_update,
+                            }),
+                        },
+                    };
+
+                    kv_wrapper::<(), _, _>(
+                        self,
+                        KvOperation::Hset::<storage::Customer>((&field, redis_value), redis_entry),
+                        &key,
+                    )
+                    .await
+                    .map_err(|err| err.to_redis_failed_response(&key))?
+                    .try_into_hset()
+                    .change_context(errors::StorageError::KVError)?;
+
+                    updated_customer
+                }
+            };
+
+            updated_customer
+                .convert(key_store.key.get_inner())
+                .await
+                .change_context(errors::StorageError::DecryptionError)
+        }
+
+        async fn find_customer_by_customer_id_merchant_id(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+            key_store: &domain::MerchantKeyStore,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<domain::Customer, errors::StorageError> {
+            let database_call = || async {
+                let conn = connection::pg_connection_read(self).await?;
+                storage::Customer::find_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
+                    .await
+                    .map_err(Into::into)
+                    .into_report()
+            };
+            let key = format!("mid_{merchant_id}_cust_{customer_id}");
+            let field = format!("cust_{customer_id}");
+            let redis_call = || async {
+                kv_wrapper(self, KvOperation::<storage::Customer>::HGet(&field), &key)
+                    .await?
+                    .try_into_hget()
+            };
+            let customer: domain::Customer = match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                    self,
+                    merchant_id,
+                    database_call().await,
+                    Box::pin(db_utils::try_redis_get_else_try_database_get(
+                        redis_call(),
+                        database_call,
+                    ))
+                    .await
+                ),
+                MerchantStorageScheme::RedisKv => {
+                    Box::pin(db_utils::try_redis_get_else_try_database_get(
+                        redis_call(),
+                        database_call,
+                    ))
+                    .await
+                }
+            }
             .async_and_then(|c| async {
                 c.convert(key_store.key.get_inner())
                     .await
                     .change_context(errors::StorageError::DecryptionError)
             })
-            .await
-    }
+            .await?;
 
-    async fn delete_customer_by_customer_id_merchant_id(
-        &self,
-        customer_id: &str,
-        merchant_id: &str,
-    ) -> CustomResult<bool, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        storage::Customer::delete_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
+            match customer.name {
+                Some(ref name) if name.peek() == REDACTED => {
+                    Err(errors::StorageError::CustomerRedacted)?
+                }
+                _ => Ok(customer),
+            }
+        }
+
+        async fn list_customers_by_merchant_id(
+            &self,
+            merchant_id: &str,
+            key_store: &domain::MerchantKeyStore,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<Vec<domain::Customer>, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+
+            let database_customers = storage::Customer::list_by_merchant_id(&conn, merchant_id)
+                .await
+                .map_err(Into::into)
+                .into_report()?;
+
+            let encrypted_customers = match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => database_customers,
+                MerchantStorageScheme::RedisKv => {
+                    // Customers that are yet to be drained are only present in redis, while the
+                    // ones that were drained and evicted from redis are only in the database
+                    let redis_customers = scan_merchant_kv_rows::<storage::Customer>(
+                        &self
+                            .get_redis_conn()
+                            .map_err(Into::<errors::StorageError>::into)?,
+                        merchant_id,
+                        "cust",
+                        "cust_*",
+                    )
+                    .await
+                    .change_context(errors::StorageError::KVError)?
+                    .into_iter()
+                    .filter(|customer| customer.merchant_id == merchant_id)
+                    .collect::<Vec<_>>();
+
+                    let redis_customer_ids = redis_customers
+                        .iter()
+                        .map(|customer| customer.customer_id.clone())
+                        .collect::<HashSet<_>>();
+
+                    database_customers
+                        .into_iter()
+                        .filter(|customer| !redis_customer_ids.contains(&customer.customer_id))
+                        .chain(redis_customers)
+                        .collect()
+                }
+            };
+
+            let customers = try_join_all(encrypted_customers.into_iter().map(
+                |encrypted_customer| async {
+                    encrypted_customer
+                        .convert(key_store.key.get_inner())
+                        .await
+                        .change_context(errors::StorageError::DecryptionError)
+                },
+            ))
+            .await?;
+
+            Ok(customers)
+        }
+
+        async fn insert_customer(
+            &self,
+            customer_data: domain::Customer,
+            key_store: &domain::MerchantKeyStore,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<domain::Customer, errors::StorageError> {
+            let customer_new = customer_data
+                .construct_new()
+                .await
+                .change_context(errors::StorageError::EncryptionError)?;
+            let created_customer = match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_connection_write(self).await?;
+                    customer_new
+                        .insert(&conn)
+                        .await
+                        .map_err(Into::into)
+                        .into_report()?
+                }
+                MerchantStorageScheme::RedisKv => {
+                    let key = format!(
+                        "mid_{}_cust_{}",
+                        customer_new.merchant_id, customer_new.customer_id
+                    );
+                    let field = format!("cust_{}", customer_new.customer_id);
+                    let created_customer = storage::Customer {
+                        id: 0i32,
+                        customer_id: customer_new.customer_id.clone(),
+                        merchant_id: customer_new.merchant_id.clone(),
+                        name: customer_new.name.clone(),
+                        email: customer_new.email.clone(),
+                        phone: customer_new.phone.clone(),
+                        phone_country_code: customer_new.phone_country_code.clone(),
+                        description: customer_new.description.clone(),
+                        created_at: customer_new.created_at,
+                        metadata: customer_new.metadata.clone(),
+                        connector_customer: customer_new.connector_customer.clone(),
+                        modified_at: customer_new.modified_at,
+                        address_id: customer_new.address_id.clone(),
+                    };
+
+                    let redis_entry = kv::TypedSql {
+                        op: kv::DBOperation::Insert {
+                            insertable: kv::Insertable::Customer(customer_new),
+                        },
+                    };
+
+                    match kv_wrapper::<storage::Customer, _, _>(
+                        self,
+                        KvOperation::<storage::Customer>::HSetNx(
+                            &field,
+                            &created_customer,
+                            redis_entry,
+                        ),
+                        &key,
+                    )
+                    .await
+                    .map_err(|err| err.to_redis_failed_response(&key))?
+                    .try_into_hsetnx()
+                    {
+                        Ok(HsetnxReply::KeyNotSet) => Err(errors::StorageError::DuplicateValue {
+                            entity: "customer",
+                            key: Some(created_customer.customer_id),
+                        })
+                        .into_report(),
+                        Ok(HsetnxReply::KeySet) => Ok(created_customer),
+                        Err(er) => Err(er).change_context(errors::StorageError::KVError),
+                    }?
+                }
+            };
+
+            created_customer
+                .convert(key_store.key.get_inner())
+                .await
+                .change_context(errors::StorageError::DecryptionError)
+        }
+
+        async fn delete_customer_by_customer_id_merchant_id(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<bool, errors::StorageError> {
+            // The customer is deleted from redis first, along with a deletion queued after its
+            // pending insertion, so that the drainer does not insert it back into the database
+            let deleted_from_redis = match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => false,
+                MerchantStorageScheme::RedisKv => {
+                    let key = format!("mid_{merchant_id}_cust_{customer_id}");
+                    let field = format!("cust_{customer_id}");
+                    let redis_entry = kv::TypedSql {
+                        op: kv::DBOperation::Delete {
+                            deletable: kv::Deletable::Customer {
+                                customer_id: customer_id.to_string(),
+                                merchant_id: merchant_id.to_string(),
+                            },
+                        },
+                    };
+
+                    let reply = kv_wrapper::<storage::Customer, _, _>(
+                        self,
+                        KvOperation::<storage::Customer>::HDel(&field, redis_entry),
+                        &key,
+                    )
+                    .await
+                    .map_err(|err| err.to_redis_failed_response(&key))?
+                    .try_into_hdel()
+                    .change_context(errors::StorageError::KVError)?;
+                    matches!(reply, redis_interface::DelReply::KeyDeleted)
+                }
+            };
+
+            let conn = connection::pg_connection_write(self).await?;
+            let deleted_from_database = storage::Customer::delete_by_customer_id_merchant_id(
+                &conn,
+                customer_id,
+                merchant_id,
+            )
             .await
             .map_err(Into::into)
-            .into_report()
+            .into_report()?;
+
+            Ok(deleted_from_redis || deleted_from_database)
+        }
     }
 }
 
@@ -223,6 +679,7 @@ impl CustomerInterface for MockDb {
         customer_id: &str,
         merchant_id: &str,
         key_store: &domain::MerchantKeyStore,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Option<domain::Customer>, errors::StorageError> {
         let customers = self.customers.lock().await;
         let customer = customers
@@ -245,6 +702,7 @@ impl CustomerInterface for MockDb {
         &self,
         merchant_id: &str,
         key_store: &domain::MerchantKeyStore,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<domain::Customer>, errors::StorageError> {
         let customers = self.customers.lock().await;
 
@@ -272,6 +730,7 @@ impl CustomerInterface for MockDb {
         _merchant_id: String,
         _customer: storage::CustomerUpdate,
         _key_store: &domain::MerchantKeyStore,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<domain::Customer, errors::StorageError> {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
@@ -282,6 +741,7 @@ impl CustomerInterface for MockDb {
         _customer_id: &str,
         _merchant_id: &str,
         _key_store: &domain::MerchantKeyStore,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<domain::Customer, errors::StorageError> {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
@@ -292,6 +752,7 @@ impl CustomerInterface for MockDb {
         &self,
         customer_data: domain::Customer,
         key_store: &domain::MerchantKeyStore,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<domain::Customer, errors::StorageError> {
         let mut customers = self.customers.lock().await;
 
@@ -311,6 +772,7 @@ impl CustomerInterface for MockDb {
         &self,
         _customer_id: &str,
         _merchant_id: &str,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<bool, errors::StorageError> {
         // [#172]: Implement function for `MockDb`
         Err(errors::StorageError::MockDbError)?
@@ -13,11 +13,24 @@ pub trait EventInterface {
         &self,
         event: storage::EventNew,
     ) -> CustomResult<storage::Event, errors::StorageError>;
+    async fn find_event_by_event_id(
+        &self,
+        event_id: &str,
+    ) -> CustomResult<storage::Event, errors::StorageError>;
+    async fn list_events_by_merchant_id_primary_object_id(
+        &self,
+        merchant_id: &str,
+        primary_object_id: &str,
+    ) -> CustomResult<Vec<storage::Event>, errors::StorageError>;
     async fn update_event(
         &self,
         event_id: String,
         event: storage::EventUpdate,
     ) -> CustomResult<storage::Event, errors::StorageError>;
+    async fn increment_event_delivery_attempts(
+        &self,
+        event_id: &str,
+    ) -> CustomResult<storage::Event, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -29,6 +42,27 @@ impl EventInterface for Store {
         let conn = connection::pg_connection_write(self).await?;
         event.insert(&conn).await.map_err(Into::into).into_report()
     }
+    async fn find_event_by_event_id(
+        &self,
+        event_id: &str,
+    ) -> CustomResult<storage::Event, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::Event::find_by_event_id(&conn, event_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+    async fn list_events_by_merchant_id_primary_object_id(
+        &self,
+        merchant_id: &str,
+        primary_object_id: &str,
+    ) -> CustomResult<Vec<storage::Event>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::Event::list_by_merchant_id_primary_object_id(&conn, merchant_id, primary_object_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
     async fn update_event(
         &self,
         event_id: String,
@@ -40,6 +74,16 @@ impl EventInterface for Store {
             .map_err(Into::into)
             .into_report()
     }
+    async fn increment_event_delivery_attempts(
+        &self,
+        event_id: &str,
+    ) -> CustomResult<storage::Event, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::Event::increment_delivery_attempts(&conn, event_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
 }
 
 #[async_trait::async_trait]
@@ -65,12 +109,47 @@ impl EventInterface for MockDb {
             primary_object_id: event.primary_object_id,
             primary_object_type: event.primary_object_type,
             created_at: now,
+            delivery_attempts: 0,
+            merchant_id: event.merchant_id,
+            business_profile_id: event.business_profile_id,
         };
 
         locked_events.push(stored_event.clone());
 
         Ok(stored_event)
     }
+    async fn find_event_by_event_id(
+        &self,
+        event_id: &str,
+    ) -> CustomResult<storage::Event, errors::StorageError> {
+        let locked_events = self.events.lock().await;
+        locked_events
+            .iter()
+            .find(|e| e.event_id == event_id)
+            .cloned()
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "No event available with event_id = {event_id}"
+                ))
+                .into(),
+            )
+    }
+    async fn list_events_by_merchant_id_primary_object_id(
+        &self,
+        merchant_id: &str,
+        primary_object_id: &str,
+    ) -> CustomResult<Vec<storage::Event>, errors::StorageError> {
+        let locked_events = self.events.lock().await;
+
+        Ok(locked_events
+            .iter()
+            .filter(|e| {
+                e.merchant_id.as_deref() == Some(merchant_id)
+                    && e.primary_object_id == primary_object_id
+            })
+            .cloned()
+            .collect())
+    }
     async fn update_event(
         &self,
         event_id: String,
@@ -92,6 +171,20 @@ impl EventInterface for MockDb {
             }
         }
 
+        Ok(event_to_update.clone())
+    }
+    async fn increment_event_delivery_attempts(
+        &self,
+        event_id: &str,
+    ) -> CustomResult<storage::Event, errors::StorageError> {
+        let mut locked_events = self.events.lock().await;
+        let event_to_update = locked_events
+            .iter_mut()
+            .find(|e| e.event_id == event_id)
+            .ok_or(errors::StorageError::MockDbError)?;
+
+        event_to_update.delivery_attempts += 1;
+
         Ok(event_to_update.clone())
     }
 }
@@ -122,6 +215,8 @@ mod tests {
                 intent_reference_id: Some("test".into()),
                 primary_object_id: "primary_object_tet".into(),
                 primary_object_type: enums::EventObjectType::PaymentDetails,
+                merchant_id: Some("merchant_1".into()),
+                business_profile_id: Some("profile_1".into()),
             })
             .await
             .unwrap();
@@ -141,5 +236,30 @@ mod tests {
         assert!(updated_event.is_webhook_notified);
         assert_eq!(updated_event.primary_object_id, "primary_object_tet");
         assert_eq!(updated_event.id, 0);
+
+        for delivery_attempt in 1..=2 {
+            let updated_event = mockdb
+                .increment_event_delivery_attempts("test_event_id")
+                .await
+                .unwrap();
+
+            assert!(updated_event.is_webhook_notified);
+            assert_eq!(updated_event.delivery_attempts, delivery_attempt);
+        }
+
+        let found_event = mockdb
+            .find_event_by_event_id("test_event_id")
+            .await
+            .unwrap();
+
+        assert_eq!(found_event.delivery_attempts, 2);
+
+        let listed_events = mockdb
+            .list_events_by_merchant_id_primary_object_id("merchant_1", "primary_object_tet")
+            .await
+            .unwrap();
+
+        assert_eq!(listed_events.len(), 1);
+        assert_eq!(listed_events[0].event_id, "test_event_id");
     }
 }
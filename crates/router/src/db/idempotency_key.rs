@@ -0,0 +1,213 @@
+use error_stack::IntoReport;
+use time::PrimitiveDateTime;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait IdempotencyKeyInterface {
+    async fn insert_idempotency_key(
+        &self,
+        idempotency_key: storage::IdempotencyKeyNew,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError>;
+
+    async fn find_idempotency_key_by_merchant_id_key(
+        &self,
+        merchant_id: &str,
+        key: &str,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError>;
+
+    async fn update_idempotency_key_by_merchant_id_key(
+        &self,
+        merchant_id: &str,
+        key: &str,
+        idempotency_key_update: storage::IdempotencyKeyUpdate,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError>;
+
+    /// Deletes the key only if it has expired, so that a key taken over by another request in the
+    /// meantime is left untouched
+    async fn delete_expired_idempotency_key_by_merchant_id_key(
+        &self,
+        merchant_id: &str,
+        key: &str,
+        now: PrimitiveDateTime,
+    ) -> CustomResult<bool, errors::StorageError>;
+
+    /// Deletes every key that expired by `now`, returning the number of keys deleted
+    async fn delete_expired_idempotency_keys(
+        &self,
+        now: PrimitiveDateTime,
+    ) -> CustomResult<usize, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl IdempotencyKeyInterface for Store {
+    async fn insert_idempotency_key(
+        &self,
+        idempotency_key: storage::IdempotencyKeyNew,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        idempotency_key
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_idempotency_key_by_merchant_id_key(
+        &self,
+        merchant_id: &str,
+        key: &str,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError> {
+        // Read from the primary, the key is looked up right after a conflicting insert
+        let conn = connection::pg_connection_write(self).await?;
+        storage::IdempotencyKey::find_by_merchant_id_key(&conn, merchant_id, key)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn update_idempotency_key_by_merchant_id_key(
+        &self,
+        merchant_id: &str,
+        key: &str,
+        idempotency_key_update: storage::IdempotencyKeyUpdate,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::IdempotencyKey::update_by_merchant_id_key(
+            &conn,
+            merchant_id,
+            key,
+            idempotency_key_update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn delete_expired_idempotency_key_by_merchant_id_key(
+        &self,
+        merchant_id: &str,
+        key: &str,
+        now: PrimitiveDateTime,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::IdempotencyKey::delete_expired_by_merchant_id_key(&conn, merchant_id, key, now)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn delete_expired_idempotency_keys(
+        &self,
+        now: PrimitiveDateTime,
+    ) -> CustomResult<usize, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::IdempotencyKey::delete_expired(&conn, now)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl IdempotencyKeyInterface for MockDb {
+    async fn insert_idempotency_key(
+        &self,
+        idempotency_key: storage::IdempotencyKeyNew,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError> {
+        let mut idempotency_keys = self.idempotency_keys.lock().await;
+        if idempotency_keys.iter().any(|existing| {
+            existing.merchant_id == idempotency_key.merchant_id
+                && existing.key == idempotency_key.key
+        }) {
+            Err(errors::StorageError::DuplicateValue {
+                entity: "idempotency_key",
+                key: Some(idempotency_key.key.clone()),
+            })?
+        }
+        let idempotency_key = storage::IdempotencyKey {
+            merchant_id: idempotency_key.merchant_id,
+            key: idempotency_key.key,
+            request_fingerprint: idempotency_key.request_fingerprint,
+            response: None,
+            created_at: common_utils::date_time::now(),
+            expires_at: idempotency_key.expires_at,
+        };
+        idempotency_keys.push(idempotency_key.clone());
+        Ok(idempotency_key)
+    }
+
+    async fn find_idempotency_key_by_merchant_id_key(
+        &self,
+        merchant_id: &str,
+        key: &str,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError> {
+        self.idempotency_keys
+            .lock()
+            .await
+            .iter()
+            .find(|idempotency_key| {
+                idempotency_key.merchant_id == merchant_id && idempotency_key.key == key
+            })
+            .cloned()
+            .ok_or(errors::StorageError::ValueNotFound(format!(
+                "No idempotency key found for merchant_id = {merchant_id} and key = {key}"
+            )))
+            .into_report()
+    }
+
+    async fn update_idempotency_key_by_merchant_id_key(
+        &self,
+        merchant_id: &str,
+        key: &str,
+        idempotency_key_update: storage::IdempotencyKeyUpdate,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError> {
+        self.idempotency_keys
+            .lock()
+            .await
+            .iter_mut()
+            .find(|idempotency_key| {
+                idempotency_key.merchant_id == merchant_id && idempotency_key.key == key
+            })
+            .map(|idempotency_key| {
+                idempotency_key.response = idempotency_key_update.response;
+                idempotency_key.expires_at = idempotency_key_update.expires_at;
+                idempotency_key.clone()
+            })
+            .ok_or(errors::StorageError::ValueNotFound(format!(
+                "No idempotency key found for merchant_id = {merchant_id} and key = {key}"
+            )))
+            .into_report()
+    }
+
+    async fn delete_expired_idempotency_key_by_merchant_id_key(
+        &self,
+        merchant_id: &str,
+        key: &str,
+        now: PrimitiveDateTime,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let mut idempotency_keys = self.idempotency_keys.lock().await;
+        let count = idempotency_keys.len();
+        idempotency_keys.retain(|idempotency_key| {
+            idempotency_key.merchant_id != merchant_id
+                || idempotency_key.key != key
+                || idempotency_key.expires_at > now
+        });
+        Ok(idempotency_keys.len() < count)
+    }
+
+    async fn delete_expired_idempotency_keys(
+        &self,
+        now: PrimitiveDateTime,
+    ) -> CustomResult<usize, errors::StorageError> {
+        let mut idempotency_keys = self.idempotency_keys.lock().await;
+        let count = idempotency_keys.len();
+        idempotency_keys.retain(|idempotency_key| idempotency_key.expires_at > now);
+        Ok(count - idempotency_keys.len())
+    }
+}
@@ -9,8 +9,9 @@ use diesel_models::{
     enums,
     enums::ProcessTrackerStatus,
     ephemeral_key::{EphemeralKey, EphemeralKeyNew},
+    oidc_config as oidc_config_storage,
     reverse_lookup::{ReverseLookup, ReverseLookupNew},
-    user_role as user_storage,
+    role as role_storage, user_role as user_storage,
 };
 use masking::Secret;
 use redis_interface::{errors::RedisError, RedisConnectionPool, RedisEntryId};
@@ -24,7 +25,10 @@ use time::PrimitiveDateTime;
 
 use super::{
     dashboard_metadata::DashboardMetadataInterface,
+    oidc_config::OidcConfigInterface,
+    role::RoleInterface,
     user::{sample_data::BatchSampleDataInterface, UserInterface},
+    user_key_store::UserKeyStoreInterface,
     user_role::UserRoleInterface,
 };
 use crate::{
@@ -32,6 +36,7 @@ use crate::{
     db::{
         address::AddressInterface,
         api_keys::ApiKeyInterface,
+        audit_log::AuditLogInterface,
         authorization::AuthorizationInterface,
         business_profile::BusinessProfileInterface,
         capture::CaptureInterface,
@@ -43,6 +48,7 @@ use crate::{
         events::EventInterface,
         file::FileMetadataInterface,
         gsm::GsmInterface,
+        idempotency_key::IdempotencyKeyInterface,
         locker_mock_up::LockerMockUpInterface,
         mandate::MandateInterface,
         merchant_account::MerchantAccountInterface,
@@ -55,6 +61,8 @@ use crate::{
         refund::RefundInterface,
         reverse_lookup::ReverseLookupInterface,
         routing_algorithm::RoutingAlgorithmInterface,
+        routing_algorithm_activation::RoutingAlgorithmActivationInterface,
+        webhook_delivery_attempt::WebhookDeliveryAttemptInterface,
         MasterKeyInterface, StorageInterface,
     },
     services::{authentication, kafka::KafkaProducer, Store},
@@ -303,9 +311,10 @@ impl CustomerInterface for KafkaStore {
         &self,
         customer_id: &str,
         merchant_id: &str,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<bool, errors::StorageError> {
         self.diesel_store
-            .delete_customer_by_customer_id_merchant_id(customer_id, merchant_id)
+            .delete_customer_by_customer_id_merchant_id(customer_id, merchant_id, storage_scheme)
             .await
     }
 
@@ -314,9 +323,15 @@ impl CustomerInterface for KafkaStore {
         customer_id: &str,
         merchant_id: &str,
         key_store: &domain::MerchantKeyStore,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Option<domain::Customer>, errors::StorageError> {
         self.diesel_store
-            .find_customer_optional_by_customer_id_merchant_id(customer_id, merchant_id, key_store)
+            .find_customer_optional_by_customer_id_merchant_id(
+                customer_id,
+                merchant_id,
+                key_store,
+                storage_scheme,
+            )
             .await
     }
 
@@ -326,6 +341,7 @@ impl CustomerInterface for KafkaStore {
         merchant_id: String,
         customer: storage::CustomerUpdate,
         key_store: &domain::MerchantKeyStore,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<domain::Customer, errors::StorageError> {
         self.diesel_store
             .update_customer_by_customer_id_merchant_id(
@@ -333,6 +349,7 @@ impl CustomerInterface for KafkaStore {
                 merchant_id,
                 customer,
                 key_store,
+                storage_scheme,
             )
             .await
     }
@@ -341,9 +358,10 @@ impl CustomerInterface for KafkaStore {
         &self,
         merchant_id: &str,
         key_store: &domain::MerchantKeyStore,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<domain::Customer>, errors::StorageError> {
         self.diesel_store
-            .list_customers_by_merchant_id(merchant_id, key_store)
+            .list_customers_by_merchant_id(merchant_id, key_store, storage_scheme)
             .await
     }
 
@@ -352,9 +370,15 @@ impl CustomerInterface for KafkaStore {
         customer_id: &str,
         merchant_id: &str,
         key_store: &domain::MerchantKeyStore,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<domain::Customer, errors::StorageError> {
         self.diesel_store
-            .find_customer_by_customer_id_merchant_id(customer_id, merchant_id, key_store)
+            .find_customer_by_customer_id_merchant_id(
+                customer_id,
+                merchant_id,
+                key_store,
+                storage_scheme,
+            )
             .await
     }
 
@@ -362,9 +386,10 @@ impl CustomerInterface for KafkaStore {
         &self,
         customer_data: domain::Customer,
         key_store: &domain::MerchantKeyStore,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<domain::Customer, errors::StorageError> {
         self.diesel_store
-            .insert_customer(customer_data, key_store)
+            .insert_customer(customer_data, key_store, storage_scheme)
             .await
     }
 }
@@ -464,6 +489,23 @@ impl EventInterface for KafkaStore {
         self.diesel_store.insert_event(event).await
     }
 
+    async fn find_event_by_event_id(
+        &self,
+        event_id: &str,
+    ) -> CustomResult<storage::Event, errors::StorageError> {
+        self.diesel_store.find_event_by_event_id(event_id).await
+    }
+
+    async fn list_events_by_merchant_id_primary_object_id(
+        &self,
+        merchant_id: &str,
+        primary_object_id: &str,
+    ) -> CustomResult<Vec<storage::Event>, errors::StorageError> {
+        self.diesel_store
+            .list_events_by_merchant_id_primary_object_id(merchant_id, primary_object_id)
+            .await
+    }
+
     async fn update_event(
         &self,
         event_id: String,
@@ -471,6 +513,15 @@ impl EventInterface for KafkaStore {
     ) -> CustomResult<storage::Event, errors::StorageError> {
         self.diesel_store.update_event(event_id, event).await
     }
+
+    async fn increment_event_delivery_attempts(
+        &self,
+        event_id: &str,
+    ) -> CustomResult<storage::Event, errors::StorageError> {
+        self.diesel_store
+            .increment_event_delivery_attempts(event_id)
+            .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -503,9 +554,10 @@ impl MandateInterface for KafkaStore {
         &self,
         merchant_id: &str,
         mandate_id: &str,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::Mandate, errors::StorageError> {
         self.diesel_store
-            .find_mandate_by_merchant_id_mandate_id(merchant_id, mandate_id)
+            .find_mandate_by_merchant_id_mandate_id(merchant_id, mandate_id, storage_scheme)
             .await
     }
 
@@ -513,9 +565,14 @@ impl MandateInterface for KafkaStore {
         &self,
         merchant_id: &str,
         connector_mandate_id: &str,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::Mandate, errors::StorageError> {
         self.diesel_store
-            .find_mandate_by_merchant_id_connector_mandate_id(merchant_id, connector_mandate_id)
+            .find_mandate_by_merchant_id_connector_mandate_id(
+                merchant_id,
+                connector_mandate_id,
+                storage_scheme,
+            )
             .await
     }
 
@@ -523,9 +580,10 @@ impl MandateInterface for KafkaStore {
         &self,
         merchant_id: &str,
         customer_id: &str,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<storage::Mandate>, errors::StorageError> {
         self.diesel_store
-            .find_mandate_by_merchant_id_customer_id(merchant_id, customer_id)
+            .find_mandate_by_merchant_id_customer_id(merchant_id, customer_id, storage_scheme)
             .await
     }
 
@@ -534,9 +592,15 @@ impl MandateInterface for KafkaStore {
         merchant_id: &str,
         mandate_id: &str,
         mandate: storage::MandateUpdate,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::Mandate, errors::StorageError> {
         self.diesel_store
-            .update_mandate_by_merchant_id_mandate_id(merchant_id, mandate_id, mandate)
+            .update_mandate_by_merchant_id_mandate_id(
+                merchant_id,
+                mandate_id,
+                mandate,
+                storage_scheme,
+            )
             .await
     }
 
@@ -544,17 +608,21 @@ impl MandateInterface for KafkaStore {
         &self,
         merchant_id: &str,
         mandate_constraints: api_models::mandates::MandateListConstraints,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<storage::Mandate>, errors::StorageError> {
         self.diesel_store
-            .find_mandates_by_merchant_id(merchant_id, mandate_constraints)
+            .find_mandates_by_merchant_id(merchant_id, mandate_constraints, storage_scheme)
             .await
     }
 
     async fn insert_mandate(
         &self,
         mandate: storage::MandateNew,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::Mandate, errors::StorageError> {
-        self.diesel_store.insert_mandate(mandate).await
+        self.diesel_store
+            .insert_mandate(mandate, storage_scheme)
+            .await
     }
 }
 
@@ -633,6 +701,23 @@ impl MerchantAccountInterface for KafkaStore {
             .await
     }
 
+    async fn update_merchant_routing_algorithm_with_activation(
+        &self,
+        merchant_id: &str,
+        merchant_account: storage::MerchantAccountUpdate,
+        key_store: &domain::MerchantKeyStore,
+        activation: storage::RoutingAlgorithmActivationNew,
+    ) -> CustomResult<domain::MerchantAccount, errors::StorageError> {
+        self.diesel_store
+            .update_merchant_routing_algorithm_with_activation(
+                merchant_id,
+                merchant_account,
+                key_store,
+                activation,
+            )
+            .await
+    }
+
     async fn find_merchant_account_by_publishable_key(
         &self,
         publishable_key: &str,
@@ -1234,9 +1319,10 @@ impl PaymentMethodInterface for KafkaStore {
     async fn find_payment_method(
         &self,
         payment_method_id: &str,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
         self.diesel_store
-            .find_payment_method(payment_method_id)
+            .find_payment_method(payment_method_id, storage_scheme)
             .await
     }
 
@@ -1244,26 +1330,35 @@ impl PaymentMethodInterface for KafkaStore {
         &self,
         customer_id: &str,
         merchant_id: &str,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError> {
         self.diesel_store
-            .find_payment_method_by_customer_id_merchant_id_list(customer_id, merchant_id)
+            .find_payment_method_by_customer_id_merchant_id_list(
+                customer_id,
+                merchant_id,
+                storage_scheme,
+            )
             .await
     }
 
     async fn insert_payment_method(
         &self,
         m: storage::PaymentMethodNew,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
-        self.diesel_store.insert_payment_method(m).await
+        self.diesel_store
+            .insert_payment_method(m, storage_scheme)
+            .await
     }
 
     async fn update_payment_method(
         &self,
         payment_method: storage::PaymentMethod,
         payment_method_update: storage::PaymentMethodUpdate,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
         self.diesel_store
-            .update_payment_method(payment_method, payment_method_update)
+            .update_payment_method(payment_method, payment_method_update, storage_scheme)
             .await
     }
 
@@ -1271,9 +1366,14 @@ impl PaymentMethodInterface for KafkaStore {
         &self,
         merchant_id: &str,
         payment_method_id: &str,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
         self.diesel_store
-            .delete_payment_method_by_merchant_id_payment_method_id(merchant_id, payment_method_id)
+            .delete_payment_method_by_merchant_id_payment_method_id(
+                merchant_id,
+                payment_method_id,
+                storage_scheme,
+            )
             .await
     }
 }
@@ -1346,7 +1446,7 @@ impl ProcessTrackerInterface for KafkaStore {
         &self,
         ids: Vec<String>,
         schedule_time: PrimitiveDateTime,
-    ) -> CustomResult<usize, errors::StorageError> {
+    ) -> CustomResult<Vec<String>, errors::StorageError> {
         self.diesel_store
             .reinitialize_limbo_processes(ids, schedule_time)
             .await
@@ -1367,6 +1467,17 @@ impl ProcessTrackerInterface for KafkaStore {
         self.diesel_store.update_process(this, process).await
     }
 
+    async fn update_process_if_status_in(
+        &self,
+        this: storage::ProcessTracker,
+        statuses: Vec<ProcessTrackerStatus>,
+        process: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .update_process_if_status_in(this, statuses, process)
+            .await
+    }
+
     async fn process_tracker_update_process_status_by_ids(
         &self,
         task_ids: Vec<String>,
@@ -1404,6 +1515,32 @@ impl ProcessTrackerInterface for KafkaStore {
             .find_processes_by_time_status(time_lower_limit, time_upper_limit, status, limit)
             .await
     }
+
+    async fn find_processes_with_expired_lease(
+        &self,
+        lease_expiry: PrimitiveDateTime,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .find_processes_with_expired_lease(lease_expiry, limit)
+            .await
+    }
+
+    async fn filter_processes_by_constraints(
+        &self,
+        constraints: storage::ProcessTrackerListConstraints,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        self.diesel_store
+            .filter_processes_by_constraints(constraints)
+            .await
+    }
+
+    async fn count_processes_by_runner(
+        &self,
+        status: ProcessTrackerStatus,
+    ) -> CustomResult<Vec<(Option<String>, i64)>, errors::StorageError> {
+        self.diesel_store.count_processes_by_runner(status).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -1666,6 +1803,21 @@ impl BusinessProfileInterface for KafkaStore {
             .await
     }
 
+    async fn update_business_profile_routing_algorithm_with_activation(
+        &self,
+        current_state: business_profile::BusinessProfile,
+        business_profile_update: business_profile::BusinessProfileUpdateInternal,
+        activation: storage::RoutingAlgorithmActivationNew,
+    ) -> CustomResult<business_profile::BusinessProfile, errors::StorageError> {
+        self.diesel_store
+            .update_business_profile_routing_algorithm_with_activation(
+                current_state,
+                business_profile_update,
+                activation,
+            )
+            .await
+    }
+
     async fn delete_business_profile_by_profile_id_merchant_id(
         &self,
         profile_id: &str,
@@ -1894,6 +2046,31 @@ impl UserInterface for KafkaStore {
             .await
     }
 
+    async fn update_user_totp_last_used_step(
+        &self,
+        user_id: &str,
+        totp_step: i64,
+    ) -> CustomResult<storage::User, errors::StorageError> {
+        self.diesel_store
+            .update_user_totp_last_used_step(user_id, totp_step)
+            .await
+    }
+
+    async fn update_user_recovery_codes_if_unchanged(
+        &self,
+        user_id: &str,
+        recovery_codes: Vec<Secret<String>>,
+        remaining_recovery_codes: Vec<Secret<String>>,
+    ) -> CustomResult<storage::User, errors::StorageError> {
+        self.diesel_store
+            .update_user_recovery_codes_if_unchanged(
+                user_id,
+                recovery_codes,
+                remaining_recovery_codes,
+            )
+            .await
+    }
+
     async fn delete_user_by_user_id(
         &self,
         user_id: &str,
@@ -1911,6 +2088,29 @@ impl UserInterface for KafkaStore {
     }
 }
 
+#[async_trait::async_trait]
+impl UserKeyStoreInterface for KafkaStore {
+    async fn insert_user_key_store(
+        &self,
+        user_key_store: domain::UserKeyStore,
+        key: &Secret<Vec<u8>>,
+    ) -> CustomResult<domain::UserKeyStore, errors::StorageError> {
+        self.diesel_store
+            .insert_user_key_store(user_key_store, key)
+            .await
+    }
+
+    async fn get_user_key_store_by_user_id(
+        &self,
+        user_id: &str,
+        key: &Secret<Vec<u8>>,
+    ) -> CustomResult<domain::UserKeyStore, errors::StorageError> {
+        self.diesel_store
+            .get_user_key_store_by_user_id(user_id, key)
+            .await
+    }
+}
+
 impl RedisConnInterface for KafkaStore {
     fn get_redis_conn(&self) -> CustomResult<Arc<RedisConnectionPool>, RedisError> {
         self.diesel_store.get_redis_conn()
@@ -1952,6 +2152,101 @@ impl UserRoleInterface for KafkaStore {
     }
 }
 
+#[async_trait::async_trait]
+impl RoleInterface for KafkaStore {
+    async fn insert_role(
+        &self,
+        role: role_storage::RoleNew,
+    ) -> CustomResult<role_storage::Role, errors::StorageError> {
+        self.diesel_store.insert_role(role).await
+    }
+
+    async fn find_role_by_role_id_merchant_id(
+        &self,
+        role_id: &str,
+        merchant_id: &str,
+    ) -> CustomResult<role_storage::Role, errors::StorageError> {
+        self.diesel_store
+            .find_role_by_role_id_merchant_id(role_id, merchant_id)
+            .await
+    }
+
+    async fn update_role_by_role_id_merchant_id(
+        &self,
+        role_id: &str,
+        merchant_id: &str,
+        role_update: role_storage::RoleUpdate,
+    ) -> CustomResult<role_storage::Role, errors::StorageError> {
+        self.diesel_store
+            .update_role_by_role_id_merchant_id(role_id, merchant_id, role_update)
+            .await
+    }
+
+    async fn delete_unassigned_role_by_role_id_merchant_id(
+        &self,
+        role_id: &str,
+        merchant_id: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        self.diesel_store
+            .delete_unassigned_role_by_role_id_merchant_id(role_id, merchant_id)
+            .await
+    }
+
+    async fn list_roles_by_merchant_id(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<role_storage::Role>, errors::StorageError> {
+        self.diesel_store
+            .list_roles_by_merchant_id(merchant_id)
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl OidcConfigInterface for KafkaStore {
+    async fn insert_oidc_config(
+        &self,
+        oidc_config: oidc_config_storage::OidcConfigNew,
+    ) -> CustomResult<oidc_config_storage::OidcConfig, errors::StorageError> {
+        self.diesel_store.insert_oidc_config(oidc_config).await
+    }
+
+    async fn find_oidc_config_by_org_id(
+        &self,
+        org_id: &str,
+    ) -> CustomResult<oidc_config_storage::OidcConfig, errors::StorageError> {
+        self.diesel_store.find_oidc_config_by_org_id(org_id).await
+    }
+
+    async fn update_oidc_config_by_org_id(
+        &self,
+        org_id: &str,
+        oidc_config_update: oidc_config_storage::OidcConfigUpdate,
+    ) -> CustomResult<oidc_config_storage::OidcConfig, errors::StorageError> {
+        self.diesel_store
+            .update_oidc_config_by_org_id(org_id, oidc_config_update)
+            .await
+    }
+
+    async fn insert_oidc_identity(
+        &self,
+        oidc_identity: oidc_config_storage::OidcIdentityNew,
+    ) -> CustomResult<oidc_config_storage::OidcIdentity, errors::StorageError> {
+        self.diesel_store.insert_oidc_identity(oidc_identity).await
+    }
+
+    async fn find_oidc_identity_by_org_id_issuer_subject(
+        &self,
+        org_id: &str,
+        issuer: &str,
+        subject: &str,
+    ) -> CustomResult<oidc_config_storage::OidcIdentity, errors::StorageError> {
+        self.diesel_store
+            .find_oidc_identity_by_org_id_issuer_subject(org_id, issuer, subject)
+            .await
+    }
+}
+
 #[async_trait::async_trait]
 impl DashboardMetadataInterface for KafkaStore {
     async fn insert_metadata(
@@ -2152,3 +2447,122 @@ impl AuthorizationInterface for KafkaStore {
             .await
     }
 }
+
+#[async_trait::async_trait]
+impl WebhookDeliveryAttemptInterface for KafkaStore {
+    async fn insert_webhook_delivery_attempt(
+        &self,
+        delivery_attempt: storage::WebhookDeliveryAttemptNew,
+    ) -> CustomResult<storage::WebhookDeliveryAttempt, errors::StorageError> {
+        self.diesel_store
+            .insert_webhook_delivery_attempt(delivery_attempt)
+            .await
+    }
+
+    async fn find_webhook_delivery_attempts_by_merchant_id_event_id(
+        &self,
+        merchant_id: &str,
+        event_id: &str,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError> {
+        self.diesel_store
+            .find_webhook_delivery_attempts_by_merchant_id_event_id(merchant_id, event_id)
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl RoutingAlgorithmActivationInterface for KafkaStore {
+    async fn insert_routing_algorithm_activation(
+        &self,
+        activation: storage::RoutingAlgorithmActivationNew,
+    ) -> CustomResult<storage::RoutingAlgorithmActivation, errors::StorageError> {
+        self.diesel_store
+            .insert_routing_algorithm_activation(activation)
+            .await
+    }
+
+    async fn list_routing_algorithm_activations_by_merchant_id_profile_id(
+        &self,
+        merchant_id: &str,
+        profile_id: Option<&str>,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::RoutingAlgorithmActivation>, errors::StorageError> {
+        self.diesel_store
+            .list_routing_algorithm_activations_by_merchant_id_profile_id(
+                merchant_id,
+                profile_id,
+                limit,
+            )
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl IdempotencyKeyInterface for KafkaStore {
+    async fn insert_idempotency_key(
+        &self,
+        idempotency_key: storage::IdempotencyKeyNew,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError> {
+        self.diesel_store
+            .insert_idempotency_key(idempotency_key)
+            .await
+    }
+
+    async fn find_idempotency_key_by_merchant_id_key(
+        &self,
+        merchant_id: &str,
+        key: &str,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError> {
+        self.diesel_store
+            .find_idempotency_key_by_merchant_id_key(merchant_id, key)
+            .await
+    }
+
+    async fn update_idempotency_key_by_merchant_id_key(
+        &self,
+        merchant_id: &str,
+        key: &str,
+        idempotency_key_update: storage::IdempotencyKeyUpdate,
+    ) -> CustomResult<storage::IdempotencyKey, errors::StorageError> {
+        self.diesel_store
+            .update_idempotency_key_by_merchant_id_key(merchant_id, key, idempotency_key_update)
+            .await
+    }
+
+    async fn delete_expired_idempotency_key_by_merchant_id_key(
+        &self,
+        merchant_id: &str,
+        key: &str,
+        now: PrimitiveDateTime,
+    ) -> CustomResult<bool, errors::StorageError> {
+        self.diesel_store
+            .delete_expired_idempotency_key_by_merchant_id_key(merchant_id, key, now)
+            .await
+    }
+
+    async fn delete_expired_idempotency_keys(
+        &self,
+        now: PrimitiveDateTime,
+    ) -> CustomResult<usize, errors::StorageError> {
+        self.diesel_store.delete_expired_idempotency_keys(now).await
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditLogInterface for KafkaStore {
+    async fn insert_audit_log(
+        &self,
+        audit_log: storage::AuditLogNew,
+    ) -> CustomResult<storage::AuditLog, errors::StorageError> {
+        self.diesel_store.insert_audit_log(audit_log).await
+    }
+
+    async fn filter_audit_logs_by_constraints(
+        &self,
+        constraints: storage::AuditLogListConstraints,
+    ) -> CustomResult<Vec<storage::AuditLog>, errors::StorageError> {
+        self.diesel_store
+            .filter_audit_logs_by_constraints(constraints)
+            .await
+    }
+}
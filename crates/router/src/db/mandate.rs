@@ -1,10 +1,9 @@
 use error_stack::{IntoReport, ResultExt};
 
-use super::{MockDb, Store};
+use super::MockDb;
 use crate::{
-    connection,
     core::{errors, errors::CustomResult},
-    types::storage::{self, MandateDbExt},
+    types::storage::{self, enums::MerchantStorageScheme},
 };
 
 #[async_trait::async_trait]
@@ -13,18 +12,21 @@ pub trait MandateInterface {
         &self,
         merchant_id: &str,
         mandate_id: &str,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::Mandate, errors::StorageError>;
 
     async fn find_mandate_by_merchant_id_connector_mandate_id(
         &self,
         merchant_id: &str,
         connector_mandate_id: &str,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::Mandate, errors::StorageError>;
 
     async fn find_mandate_by_merchant_id_customer_id(
         &self,
         merchant_id: &str,
         customer_id: &str,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<storage::Mandate>, errors::StorageError>;
 
     async fn update_mandate_by_merchant_id_mandate_id(
@@ -32,97 +34,505 @@ pub trait MandateInterface {
         merchant_id: &str,
         mandate_id: &str,
         mandate: storage::MandateUpdate,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::Mandate, errors::StorageError>;
 
     async fn find_mandates_by_merchant_id(
         &self,
         merchant_id: &str,
         mandate_constraints: api_models::mandates::MandateListConstraints,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<storage::Mandate>, errors::StorageError>;
 
     async fn insert_mandate(
         &self,
         mandate: storage::MandateNew,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::Mandate, errors::StorageError>;
 }
 
-#[async_trait::async_trait]
-impl MandateInterface for Store {
-    async fn find_mandate_by_merchant_id_mandate_id(
-        &self,
-        merchant_id: &str,
-        mandate_id: &str,
-    ) -> CustomResult<storage::Mandate, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
-        storage::Mandate::find_by_merchant_id_mandate_id(&conn, merchant_id, mandate_id)
-            .await
-            .map_err(Into::into)
-            .into_report()
-    }
+#[cfg(not(feature = "kv_store"))]
+mod storage {
+    use error_stack::IntoReport;
 
-    async fn find_mandate_by_merchant_id_connector_mandate_id(
-        &self,
-        merchant_id: &str,
-        connector_mandate_id: &str,
-    ) -> CustomResult<storage::Mandate, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
-        storage::Mandate::find_by_merchant_id_connector_mandate_id(
-            &conn,
-            merchant_id,
-            connector_mandate_id,
-        )
-        .await
-        .map_err(Into::into)
-        .into_report()
-    }
+    use super::MandateInterface;
+    use crate::{
+        connection,
+        core::errors::{self, CustomResult},
+        services::Store,
+        types::storage::{self, enums::MerchantStorageScheme, MandateDbExt},
+    };
 
-    async fn find_mandate_by_merchant_id_customer_id(
-        &self,
-        merchant_id: &str,
-        customer_id: &str,
-    ) -> CustomResult<Vec<storage::Mandate>, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
-        storage::Mandate::find_by_merchant_id_customer_id(&conn, merchant_id, customer_id)
+    #[async_trait::async_trait]
+    impl MandateInterface for Store {
+        async fn find_mandate_by_merchant_id_mandate_id(
+            &self,
+            merchant_id: &str,
+            mandate_id: &str,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::Mandate, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            storage::Mandate::find_by_merchant_id_mandate_id(&conn, merchant_id, mandate_id)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
+
+        async fn find_mandate_by_merchant_id_connector_mandate_id(
+            &self,
+            merchant_id: &str,
+            connector_mandate_id: &str,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::Mandate, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            storage::Mandate::find_by_merchant_id_connector_mandate_id(
+                &conn,
+                merchant_id,
+                connector_mandate_id,
+            )
             .await
             .map_err(Into::into)
             .into_report()
-    }
+        }
 
-    async fn update_mandate_by_merchant_id_mandate_id(
-        &self,
-        merchant_id: &str,
-        mandate_id: &str,
-        mandate: storage::MandateUpdate,
-    ) -> CustomResult<storage::Mandate, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        storage::Mandate::update_by_merchant_id_mandate_id(&conn, merchant_id, mandate_id, mandate)
+        async fn find_mandate_by_merchant_id_customer_id(
+            &self,
+            merchant_id: &str,
+            customer_id: &str,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<Vec<storage::Mandate>, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            storage::Mandate::find_by_merchant_id_customer_id(&conn, merchant_id, customer_id)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
+
+        async fn update_mandate_by_merchant_id_mandate_id(
+            &self,
+            merchant_id: &str,
+            mandate_id: &str,
+            mandate: storage::MandateUpdate,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::Mandate, errors::StorageError> {
+            let conn = connection::pg_connection_write(self).await?;
+            storage::Mandate::update_by_merchant_id_mandate_id(
+                &conn,
+                merchant_id,
+                mandate_id,
+                mandate,
+            )
             .await
             .map_err(Into::into)
             .into_report()
+        }
+
+        async fn find_mandates_by_merchant_id(
+            &self,
+            merchant_id: &str,
+            mandate_constraints: api_models::mandates::MandateListConstraints,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<Vec<storage::Mandate>, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            storage::Mandate::filter_by_constraints(&conn, merchant_id, mandate_constraints)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
+
+        async fn insert_mandate(
+            &self,
+            mandate: storage::MandateNew,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::Mandate, errors::StorageError> {
+            let conn = connection::pg_connection_write(self).await?;
+            mandate
+                .insert(&conn)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
     }
+}
 
-    async fn find_mandates_by_merchant_id(
-        &self,
+#[cfg(feature = "kv_store")]
+mod storage {
+    use std::collections::HashSet;
+
+    use common_utils::{fallback_dual_read_not_found, fallback_reverse_lookup_not_found};
+    use error_stack::{IntoReport, ResultExt};
+    use redis_interface::HsetnxReply;
+    use storage_impl::redis::kv_store::{
+        kv_wrapper, scan_merchant_kv_rows, KvOperation, RedisConnInterface,
+    };
+
+    use super::MandateInterface;
+    use crate::{
+        connection,
+        core::errors::{self, utils::RedisErrorExt, CustomResult},
+        db::reverse_lookup::ReverseLookupInterface,
+        services::Store,
+        types::storage::{self, enums::MerchantStorageScheme, kv, MandateDbExt},
+        utils::{self, db_utils},
+    };
+
+    /// Mandates that are yet to be drained are only present in redis, while the ones that were
+    /// drained and evicted from redis are only in the database
+    async fn scan_redis_mandates(
+        store: &Store,
         merchant_id: &str,
-        mandate_constraints: api_models::mandates::MandateListConstraints,
     ) -> CustomResult<Vec<storage::Mandate>, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
-        storage::Mandate::filter_by_constraints(&conn, merchant_id, mandate_constraints)
-            .await
-            .map_err(Into::into)
-            .into_report()
+        let redis_conn = store
+            .get_redis_conn()
+            .map_err(Into::<errors::StorageError>::into)?;
+        Ok(scan_merchant_kv_rows::<storage::Mandate>(
+            &redis_conn,
+            merchant_id,
+            "mandate",
+            "mandate_*",
+        )
+        .await
+        .change_context(errors::StorageError::KVError)?
+        .into_iter()
+        .filter(|mandate| mandate.merchant_id == merchant_id)
+        .collect())
     }
 
-    async fn insert_mandate(
-        &self,
-        mandate: storage::MandateNew,
-    ) -> CustomResult<storage::Mandate, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        mandate
-            .insert(&conn)
+    /// Replaces the mandates read from the database with their copy in redis, which is the latest
+    fn merge_redis_mandates(
+        mandates: Vec<storage::Mandate>,
+        redis_mandates: Vec<storage::Mandate>,
+    ) -> Vec<storage::Mandate> {
+        let redis_mandate_ids = redis_mandates
+            .iter()
+            .map(|mandate| mandate.mandate_id.clone())
+            .collect::<HashSet<_>>();
+
+        mandates
+            .into_iter()
+            .filter(|mandate| !redis_mandate_ids.contains(&mandate.mandate_id))
+            .chain(redis_mandates)
+            .collect()
+    }
+
+    #[async_trait::async_trait]
+    impl MandateInterface for Store {
+        async fn find_mandate_by_merchant_id_mandate_id(
+            &self,
+            merchant_id: &str,
+            mandate_id: &str,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::Mandate, errors::StorageError> {
+            let database_call = || async {
+                let conn = connection::pg_connection_read(self).await?;
+                storage::Mandate::find_by_merchant_id_mandate_id(&conn, merchant_id, mandate_id)
+                    .await
+                    .map_err(Into::into)
+                    .into_report()
+            };
+            match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                    self,
+                    merchant_id,
+                    database_call().await,
+                    self.find_mandate_by_merchant_id_mandate_id(
+                        merchant_id,
+                        mandate_id,
+                        MerchantStorageScheme::RedisKv,
+                    )
+                    .await
+                ),
+                MerchantStorageScheme::RedisKv => {
+                    let key = format!("mid_{merchant_id}_mandate_{mandate_id}");
+                    let field = format!("mandate_{mandate_id}");
+                    Box::pin(db_utils::try_redis_get_else_try_database_get(
+                        async {
+                            kv_wrapper(self, KvOperation::<storage::Mandate>::HGet(&field), key)
+                                .await?
+                                .try_into_hget()
+                        },
+                        database_call,
+                    ))
+                    .await
+                }
+            }
+        }
+
+        async fn find_mandate_by_merchant_id_connector_mandate_id(
+            &self,
+            merchant_id: &str,
+            connector_mandate_id: &str,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::Mandate, errors::StorageError> {
+            let database_call = || async {
+                let conn = connection::pg_connection_read(self).await?;
+                storage::Mandate::find_by_merchant_id_connector_mandate_id(
+                    &conn,
+                    merchant_id,
+                    connector_mandate_id,
+                )
+                .await
+                .map_err(Into::into)
+                .into_report()
+            };
+            match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                    self,
+                    merchant_id,
+                    database_call().await,
+                    self.find_mandate_by_merchant_id_connector_mandate_id(
+                        merchant_id,
+                        connector_mandate_id,
+                        MerchantStorageScheme::RedisKv,
+                    )
+                    .await
+                ),
+                MerchantStorageScheme::RedisKv => {
+                    let lookup_id =
+                        format!("mid_{merchant_id}_conn_mandate_{connector_mandate_id}");
+                    let lookup = fallback_reverse_lookup_not_found!(
+                        self.get_lookup_by_lookup_id(&lookup_id, storage_scheme)
+                            .await,
+                        database_call().await
+                    );
+
+                    let key = &lookup.pk_id;
+                    Box::pin(db_utils::try_redis_get_else_try_database_get(
+                        async {
+                            kv_wrapper(
+                                self,
+                                KvOperation::<storage::Mandate>::HGet(&lookup.sk_id),
+                                key,
+                            )
+                            .await?
+                            .try_into_hget()
+                        },
+                        database_call,
+                    ))
+                    .await
+                }
+            }
+        }
+
+        async fn find_mandate_by_merchant_id_customer_id(
+            &self,
+            merchant_id: &str,
+            customer_id: &str,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<Vec<storage::Mandate>, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            let mandates =
+                storage::Mandate::find_by_merchant_id_customer_id(&conn, merchant_id, customer_id)
+                    .await
+                    .map_err(Into::into)
+                    .into_report()?;
+
+            match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => Ok(mandates),
+                MerchantStorageScheme::RedisKv => {
+                    let redis_mandates = scan_redis_mandates(self, merchant_id)
+                        .await?
+                        .into_iter()
+                        .filter(|mandate| mandate.customer_id == customer_id)
+                        .collect();
+                    Ok(merge_redis_mandates(mandates, redis_mandates))
+                }
+            }
+        }
+
+        async fn update_mandate_by_merchant_id_mandate_id(
+            &self,
+            merchant_id: &str,
+            mandate_id: &str,
+            mandate_update: storage::MandateUpdate,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::Mandate, errors::StorageError> {
+            match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_connection_write(self).await?;
+                    storage::Mandate::update_by_merchant_id_mandate_id(
+                        &conn,
+                        merchant_id,
+                        mandate_id,
+                        mandate_update,
+                    )
+                    .await
+                    .map_err(Into::into)
+                    .into_report()
+                }
+                MerchantStorageScheme::RedisKv => {
+                    let key = format!("mid_{merchant_id}_mandate_{mandate_id}");
+                    let field = format!("mandate_{mandate_id}");
+                    let this = self
+                        .find_mandate_by_merchant_id_mandate_id(
+                            merchant_id,
+                            mandate_id,
+                            storage_scheme,
+                        )
+                        .await?;
+                    let updated_mandate = mandate_update.clone().apply_changeset(this.clone());
+
+                    let redis_value = utils::Encode::<storage::Mandate>::encode_to_string_of_json(
+                        &updated_mandate,
+                    )
+                    .change_context(errors::StorageError::SerializationFailed)?;
+
+                    let redis_entry = kv::TypedSql {
+                        op: kv::DBOperation::Update {
+                            updatable: kv::Updateable::MandateUpdate(kv::MandateUpdateMems {
+                                orig: this,
+                                update_data: mandate_update,
+                            }),
+                        },
+                    };
+
+                    kv_wrapper::<(), _, _>(
+                        self,
+                        KvOperation::Hset::<storage::Mandate>((&field, redis_value), redis_entry),
+                        &key,
+                    )
+                    .await
+                    .map_err(|err| err.to_redis_failed_response(&key))?
+                    .try_into_hset()
+                    .change_context(errors::StorageError::KVError)?;
+
+                    Ok(updated_mandate)
+                }
+            }
+        }
+
+        async fn find_mandates_by_merchant_id(
+            &self,
+            merchant_id: &str,
+            mandate_constraints: api_models::mandates::MandateListConstraints,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<Vec<storage::Mandate>, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            let mandates = storage::Mandate::filter_by_constraints(
+                &conn,
+                merchant_id,
+                mandate_constraints.clone(),
+            )
             .await
             .map_err(Into::into)
-            .into_report()
+            .into_report()?;
+
+            match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => Ok(mandates),
+                MerchantStorageScheme::RedisKv => {
+                    let redis_mandates = scan_redis_mandates(self, merchant_id)
+                        .await?
+                        .into_iter()
+                        .filter(|mandate| {
+                            storage::mandate::satisfies_constraints(mandate, &mandate_constraints)
+                        })
+                        .collect();
+
+                    // The database only returns the latest mandates up to the limit, the latest
+                    // of those and the ones in redis are the latest mandates overall
+                    let mut mandates = merge_redis_mandates(mandates, redis_mandates);
+                    mandates.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+                    if let Some(limit) = mandate_constraints.limit {
+                        mandates.truncate(usize::try_from(limit).unwrap_or_default());
+                    }
+                    Ok(mandates)
+                }
+            }
+        }
+
+        async fn insert_mandate(
+            &self,
+            mandate: storage::MandateNew,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::Mandate, errors::StorageError> {
+            match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_connection_write(self).await?;
+                    mandate
+                        .insert(&conn)
+                        .await
+                        .map_err(Into::into)
+                        .into_report()
+                }
+                MerchantStorageScheme::RedisKv => {
+                    let key = format!("mid_{}_mandate_{}", mandate.merchant_id, mandate.mandate_id);
+                    let field = format!("mandate_{}", mandate.mandate_id);
+                    let created_mandate = storage::Mandate {
+                        id: 0i32,
+                        mandate_id: mandate.mandate_id.clone(),
+                        customer_id: mandate.customer_id.clone(),
+                        merchant_id: mandate.merchant_id.clone(),
+                        payment_method_id: mandate.payment_method_id.clone(),
+                        mandate_status: mandate.mandate_status,
+                        mandate_type: mandate.mandate_type,
+                        customer_accepted_at: mandate.customer_accepted_at,
+                        customer_ip_address: mandate.customer_ip_address.clone(),
+                        customer_user_agent: mandate.customer_user_agent.clone(),
+                        network_transaction_id: mandate.network_transaction_id.clone(),
+                        previous_attempt_id: mandate.previous_attempt_id.clone(),
+                        created_at: mandate
+                            .created_at
+                            .unwrap_or_else(common_utils::date_time::now),
+                        mandate_amount: mandate.mandate_amount,
+                        mandate_currency: mandate.mandate_currency,
+                        amount_captured: mandate.amount_captured,
+                        connector: mandate.connector.clone(),
+                        connector_mandate_id: mandate.connector_mandate_id.clone(),
+                        start_date: mandate.start_date,
+                        end_date: mandate.end_date,
+                        metadata: mandate.metadata.clone(),
+                        connector_mandate_ids: mandate.connector_mandate_ids.clone(),
+                        original_payment_id: mandate.original_payment_id.clone(),
+                        merchant_connector_id: mandate.merchant_connector_id.clone(),
+                    };
+
+                    if let Some(connector_mandate_id) = &created_mandate.connector_mandate_id {
+                        self.insert_reverse_lookup(
+                            storage::ReverseLookupNew {
+                                lookup_id: format!(
+                                    "mid_{}_conn_mandate_{}",
+                                    created_mandate.merchant_id, connector_mandate_id
+                                ),
+                                sk_id: field.clone(),
+                                pk_id: key.clone(),
+                                source: "mandate".to_string(),
+                                updated_by: storage_scheme.to_string(),
+                            },
+                            storage_scheme,
+                        )
+                        .await?;
+                    }
+
+                    let redis_entry = kv::TypedSql {
+                        op: kv::DBOperation::Insert {
+                            insertable: kv::Insertable::Mandate(mandate),
+                        },
+                    };
+
+                    match kv_wrapper::<storage::Mandate, _, _>(
+                        self,
+                        KvOperation::<storage::Mandate>::HSetNx(
+                            &field,
+                            &created_mandate,
+                            redis_entry,
+                        ),
+                        &key,
+                    )
+                    .await
+                    .map_err(|err| err.to_redis_failed_response(&key))?
+                    .try_into_hsetnx()
+                    {
+                        Ok(HsetnxReply::KeyNotSet) => Err(errors::StorageError::DuplicateValue {
+                            entity: "mandate",
+                            key: Some(created_mandate.mandate_id),
+                        })
+                        .into_report(),
+                        Ok(HsetnxReply::KeySet) => Ok(created_mandate),
+                        Err(er) => Err(er).change_context(errors::StorageError::KVError),
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -132,6 +542,7 @@ impl MandateInterface for MockDb {
         &self,
         merchant_id: &str,
         mandate_id: &str,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::Mandate, errors::StorageError> {
         self.mandates
             .lock()
@@ -147,6 +558,7 @@ impl MandateInterface for MockDb {
         &self,
         merchant_id: &str,
         connector_mandate_id: &str,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::Mandate, errors::StorageError> {
         self.mandates
             .lock()
@@ -165,6 +577,7 @@ impl MandateInterface for MockDb {
         &self,
         merchant_id: &str,
         customer_id: &str,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<storage::Mandate>, errors::StorageError> {
         return Ok(self
             .mandates
@@ -183,6 +596,7 @@ impl MandateInterface for MockDb {
         merchant_id: &str,
         mandate_id: &str,
         mandate_update: storage::MandateUpdate,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::Mandate, errors::StorageError> {
         let mut mandates = self.mandates.lock().await;
         match mandates
@@ -215,32 +629,12 @@ impl MandateInterface for MockDb {
         &self,
         merchant_id: &str,
         mandate_constraints: api_models::mandates::MandateListConstraints,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<storage::Mandate>, errors::StorageError> {
         let mandates = self.mandates.lock().await;
         let mandates_iter = mandates.iter().filter(|mandate| {
-            let mut checker = mandate.merchant_id == merchant_id;
-            if let Some(created_time) = mandate_constraints.created_time {
-                checker &= mandate.created_at == created_time;
-            }
-            if let Some(created_time_lt) = mandate_constraints.created_time_lt {
-                checker &= mandate.created_at < created_time_lt;
-            }
-            if let Some(created_time_gt) = mandate_constraints.created_time_gt {
-                checker &= mandate.created_at > created_time_gt;
-            }
-            if let Some(created_time_lte) = mandate_constraints.created_time_lte {
-                checker &= mandate.created_at <= created_time_lte;
-            }
-            if let Some(created_time_gte) = mandate_constraints.created_time_gte {
-                checker &= mandate.created_at >= created_time_gte;
-            }
-            if let Some(connector) = &mandate_constraints.connector {
-                checker &= mandate.connector == *connector;
-            }
-            if let Some(mandate_status) = mandate_constraints.mandate_status {
-                checker &= mandate.mandate_status == mandate_status;
-            }
-            checker
+            mandate.merchant_id == merchant_id
+                && storage::mandate::satisfies_constraints(mandate, &mandate_constraints)
         });
 
         let mandates: Vec<storage::Mandate> = if let Some(limit) = mandate_constraints.limit {
@@ -258,6 +652,7 @@ impl MandateInterface for MockDb {
     async fn insert_mandate(
         &self,
         mandate_new: storage::MandateNew,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::Mandate, errors::StorageError> {
         let mut mandates = self.mandates.lock().await;
         let mandate = storage::Mandate {
@@ -296,3 +691,144 @@ impl MandateInterface for MockDb {
         Ok(mandate)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[allow(clippy::unwrap_used)]
+    mod mockdb_mandate_interface {
+        use api_models::mandates::MandateListConstraints;
+        use diesel_models::enums::MandateStatus;
+        use redis_interface::RedisSettings;
+        use time::macros::datetime;
+
+        use crate::{
+            db::{mandate::MandateInterface, MockDb},
+            types::storage::{self, enums::MerchantStorageScheme},
+        };
+
+        fn create_mandate_new(
+            mandate_id: &str,
+            mandate_status: MandateStatus,
+            connector: &str,
+            created_at: time::PrimitiveDateTime,
+        ) -> storage::MandateNew {
+            storage::MandateNew {
+                mandate_id: mandate_id.into(),
+                customer_id: "customer_1".into(),
+                merchant_id: "merchant_1".into(),
+                mandate_status,
+                connector: connector.into(),
+                created_at: Some(created_at),
+                ..Default::default()
+            }
+        }
+
+        fn get_constraints() -> MandateListConstraints {
+            MandateListConstraints {
+                limit: None,
+                mandate_status: None,
+                connector: None,
+                created_time: None,
+                created_time_lt: None,
+                created_time_gt: None,
+                created_time_lte: None,
+                created_time_gte: None,
+            }
+        }
+
+        async fn find_mandate_ids(
+            mockdb: &MockDb,
+            constraints: MandateListConstraints,
+        ) -> Vec<String> {
+            mockdb
+                .find_mandates_by_merchant_id(
+                    "merchant_1",
+                    constraints,
+                    MerchantStorageScheme::PostgresOnly,
+                )
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|mandate| mandate.mandate_id)
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn test_find_mandates_by_merchant_id() {
+            #[allow(clippy::expect_used)]
+            let mockdb = MockDb::new(&RedisSettings::default())
+                .await
+                .expect("Failed to create a mock DB");
+
+            for mandate in [
+                create_mandate_new(
+                    "mandate_1",
+                    MandateStatus::Active,
+                    "stripe",
+                    datetime!(2023-01-01 0:00),
+                ),
+                create_mandate_new(
+                    "mandate_2",
+                    MandateStatus::Revoked,
+                    "stripe",
+                    datetime!(2023-01-02 0:00),
+                ),
+                create_mandate_new(
+                    "mandate_3",
+                    MandateStatus::Active,
+                    "adyen",
+                    datetime!(2023-01-03 0:00),
+                ),
+            ] {
+                mockdb
+                    .insert_mandate(mandate, MerchantStorageScheme::PostgresOnly)
+                    .await
+                    .unwrap();
+            }
+
+            assert_eq!(
+                find_mandate_ids(
+                    &mockdb,
+                    MandateListConstraints {
+                        mandate_status: Some(MandateStatus::Active),
+                        ..get_constraints()
+                    },
+                )
+                .await,
+                vec!["mandate_1", "mandate_3"]
+            );
+            assert_eq!(
+                find_mandate_ids(
+                    &mockdb,
+                    MandateListConstraints {
+                        connector: Some("stripe".into()),
+                        created_time_gt: Some(datetime!(2023-01-01 0:00)),
+                        ..get_constraints()
+                    },
+                )
+                .await,
+                vec!["mandate_2"]
+            );
+            assert_eq!(
+                find_mandate_ids(
+                    &mockdb,
+                    MandateListConstraints {
+                        limit: Some(1),
+                        ..get_constraints()
+                    },
+                )
+                .await,
+                vec!["mandate_1"]
+            );
+            assert!(mockdb
+                .find_mandates_by_merchant_id(
+                    "merchant_2",
+                    get_constraints(),
+                    MerchantStorageScheme::PostgresOnly,
+                )
+                .await
+                .unwrap()
+                .is_empty());
+        }
+    }
+}
@@ -1,7 +1,9 @@
 #[cfg(feature = "olap")]
 use std::collections::HashMap;
 
+use async_bb8_diesel::AsyncConnection;
 use common_utils::ext_traits::AsyncExt;
+use diesel_models::errors::DatabaseError;
 use error_stack::{IntoReport, ResultExt};
 #[cfg(feature = "accounts_cache")]
 use storage_impl::redis::cache::{CacheKind, ACCOUNTS_CACHE};
@@ -53,6 +55,16 @@ where
         merchant_key_store: &domain::MerchantKeyStore,
     ) -> CustomResult<domain::MerchantAccount, errors::StorageError>;
 
+    /// Updates the merchant account and records the change of its routing algorithm in the
+    /// activation history, in the same transaction
+    async fn update_merchant_routing_algorithm_with_activation(
+        &self,
+        merchant_id: &str,
+        merchant_account: storage::MerchantAccountUpdate,
+        merchant_key_store: &domain::MerchantKeyStore,
+        activation: storage::RoutingAlgorithmActivationNew,
+    ) -> CustomResult<domain::MerchantAccount, errors::StorageError>;
+
     async fn find_merchant_account_by_publishable_key(
         &self,
         publishable_key: &str,
@@ -181,6 +193,46 @@ impl MerchantAccountInterface for Store {
             .change_context(errors::StorageError::DecryptionError)
     }
 
+    async fn update_merchant_routing_algorithm_with_activation(
+        &self,
+        merchant_id: &str,
+        merchant_account: storage::MerchantAccountUpdate,
+        merchant_key_store: &domain::MerchantKeyStore,
+        activation: storage::RoutingAlgorithmActivationNew,
+    ) -> CustomResult<domain::MerchantAccount, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        let merchant_id = merchant_id.to_owned();
+        let updated_merchant_account = conn
+            .transaction_async(|conn| async move {
+                let updated_merchant_account =
+                    storage::MerchantAccount::update_with_specific_fields(
+                        &conn,
+                        &merchant_id,
+                        merchant_account.into(),
+                    )
+                    .await
+                    .map_err(|error| *error.current_context())?;
+                activation
+                    .insert(&conn)
+                    .await
+                    .map_err(|error| *error.current_context())?;
+                Ok::<_, DatabaseError>(updated_merchant_account)
+            })
+            .await
+            .into_report()
+            .map_err(Into::into)
+            .into_report()?;
+
+        #[cfg(feature = "accounts_cache")]
+        {
+            publish_and_redact_merchant_account_cache(self, &updated_merchant_account).await?;
+        }
+        updated_merchant_account
+            .convert(merchant_key_store.key.get_inner())
+            .await
+            .change_context(errors::StorageError::DecryptionError)
+    }
+
     async fn find_merchant_account_by_publishable_key(
         &self,
         publishable_key: &str,
@@ -429,6 +481,17 @@ impl MerchantAccountInterface for MockDb {
         Err(errors::StorageError::MockDbError)?
     }
 
+    async fn update_merchant_routing_algorithm_with_activation(
+        &self,
+        _merchant_id: &str,
+        _merchant_account: storage::MerchantAccountUpdate,
+        _merchant_key_store: &domain::MerchantKeyStore,
+        _activation: storage::RoutingAlgorithmActivationNew,
+    ) -> CustomResult<domain::MerchantAccount, errors::StorageError> {
+        // [#TODO]: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
     async fn find_merchant_account_by_publishable_key(
         &self,
         _publishable_key: &str,
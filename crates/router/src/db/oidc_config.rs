@@ -0,0 +1,257 @@
+use diesel_models::{errors::DatabaseError, oidc_config as storage};
+use error_stack::{IntoReport, ResultExt};
+
+use super::MockDb;
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    services::Store,
+};
+
+#[async_trait::async_trait]
+pub trait OidcConfigInterface {
+    async fn insert_oidc_config(
+        &self,
+        oidc_config: storage::OidcConfigNew,
+    ) -> CustomResult<storage::OidcConfig, errors::StorageError>;
+
+    async fn find_oidc_config_by_org_id(
+        &self,
+        org_id: &str,
+    ) -> CustomResult<storage::OidcConfig, errors::StorageError>;
+
+    async fn update_oidc_config_by_org_id(
+        &self,
+        org_id: &str,
+        oidc_config_update: storage::OidcConfigUpdate,
+    ) -> CustomResult<storage::OidcConfig, errors::StorageError>;
+
+    async fn insert_oidc_identity(
+        &self,
+        oidc_identity: storage::OidcIdentityNew,
+    ) -> CustomResult<storage::OidcIdentity, errors::StorageError>;
+
+    async fn find_oidc_identity_by_org_id_issuer_subject(
+        &self,
+        org_id: &str,
+        issuer: &str,
+        subject: &str,
+    ) -> CustomResult<storage::OidcIdentity, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl OidcConfigInterface for Store {
+    async fn insert_oidc_config(
+        &self,
+        oidc_config: storage::OidcConfigNew,
+    ) -> CustomResult<storage::OidcConfig, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        oidc_config
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_oidc_config_by_org_id(
+        &self,
+        org_id: &str,
+    ) -> CustomResult<storage::OidcConfig, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::OidcConfig::find_by_org_id(&conn, org_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn update_oidc_config_by_org_id(
+        &self,
+        org_id: &str,
+        oidc_config_update: storage::OidcConfigUpdate,
+    ) -> CustomResult<storage::OidcConfig, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::OidcConfig::update_by_org_id(&conn, org_id, oidc_config_update)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn insert_oidc_identity(
+        &self,
+        oidc_identity: storage::OidcIdentityNew,
+    ) -> CustomResult<storage::OidcIdentity, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        oidc_identity
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_oidc_identity_by_org_id_issuer_subject(
+        &self,
+        org_id: &str,
+        issuer: &str,
+        subject: &str,
+    ) -> CustomResult<storage::OidcIdentity, errors::StorageError> {
+        // Read from the primary, an identity linked just before is to be found
+        let conn = connection::pg_connection_write(self).await?;
+        storage::OidcIdentity::find_by_org_id_issuer_subject(&conn, org_id, issuer, subject)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl OidcConfigInterface for MockDb {
+    async fn insert_oidc_config(
+        &self,
+        oidc_config: storage::OidcConfigNew,
+    ) -> CustomResult<storage::OidcConfig, errors::StorageError> {
+        let mut oidc_configs = self.oidc_configs.lock().await;
+        if oidc_configs
+            .iter()
+            .any(|oidc_config_inner| oidc_config_inner.org_id == oidc_config.org_id)
+        {
+            Err(errors::StorageError::DuplicateValue {
+                entity: "org_id",
+                key: Some(oidc_config.org_id.clone()),
+            })?
+        }
+        let oidc_config = storage::OidcConfig {
+            org_id: oidc_config.org_id,
+            merchant_id: oidc_config.merchant_id,
+            issuer_url: oidc_config.issuer_url,
+            client_id: oidc_config.client_id,
+            client_secret: oidc_config.client_secret,
+            redirect_url: oidc_config.redirect_url,
+            groups_claim: oidc_config.groups_claim,
+            role_mappings: oidc_config.role_mappings,
+            default_role_id: oidc_config.default_role_id,
+            is_enabled: oidc_config.is_enabled,
+            created_by: oidc_config.created_by,
+            created_at: oidc_config.created_at,
+            last_modified_by: oidc_config.last_modified_by,
+            last_modified_at: oidc_config.last_modified_at,
+        };
+        oidc_configs.push(oidc_config.clone());
+        Ok(oidc_config)
+    }
+
+    async fn find_oidc_config_by_org_id(
+        &self,
+        org_id: &str,
+    ) -> CustomResult<storage::OidcConfig, errors::StorageError> {
+        let oidc_configs = self.oidc_configs.lock().await;
+        oidc_configs
+            .iter()
+            .find(|oidc_config| oidc_config.org_id == org_id)
+            .cloned()
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "No oidc config available for org_id = {org_id}"
+                ))
+                .into(),
+            )
+    }
+
+    async fn update_oidc_config_by_org_id(
+        &self,
+        org_id: &str,
+        oidc_config_update: storage::OidcConfigUpdate,
+    ) -> CustomResult<storage::OidcConfig, errors::StorageError> {
+        let mut oidc_configs = self.oidc_configs.lock().await;
+        oidc_configs
+            .iter_mut()
+            .find(|oidc_config| oidc_config.org_id == org_id)
+            .map(|oidc_config| {
+                *oidc_config = match &oidc_config_update {
+                    storage::OidcConfigUpdate::Update {
+                        merchant_id,
+                        issuer_url,
+                        client_id,
+                        client_secret,
+                        redirect_url,
+                        groups_claim,
+                        role_mappings,
+                        default_role_id,
+                        is_enabled,
+                        last_modified_by,
+                    } => storage::OidcConfig {
+                        merchant_id: merchant_id.to_owned(),
+                        issuer_url: issuer_url.to_owned(),
+                        client_id: client_id.to_owned(),
+                        client_secret: client_secret.to_owned(),
+                        redirect_url: redirect_url.to_owned(),
+                        groups_claim: groups_claim.to_owned(),
+                        role_mappings: role_mappings.to_owned(),
+                        default_role_id: default_role_id.to_owned(),
+                        is_enabled: *is_enabled,
+                        last_modified_by: last_modified_by.to_owned(),
+                        last_modified_at: common_utils::date_time::now(),
+                        ..oidc_config.to_owned()
+                    },
+                };
+                oidc_config.to_owned()
+            })
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "No oidc config available for org_id = {org_id}"
+                ))
+                .into(),
+            )
+    }
+
+    async fn insert_oidc_identity(
+        &self,
+        oidc_identity: storage::OidcIdentityNew,
+    ) -> CustomResult<storage::OidcIdentity, errors::StorageError> {
+        let mut oidc_identities = self.oidc_identities.lock().await;
+        // Mirrors the unique indexes of the table
+        if oidc_identities.iter().any(|oidc_identity_inner| {
+            oidc_identity_inner.org_id == oidc_identity.org_id
+                && ((oidc_identity_inner.issuer == oidc_identity.issuer
+                    && oidc_identity_inner.subject == oidc_identity.subject)
+                    || oidc_identity_inner.user_id == oidc_identity.user_id)
+        }) {
+            Err(errors::StorageError::DatabaseError(
+                DatabaseError::UniqueViolation.into(),
+            ))?
+        }
+        let oidc_identity = storage::OidcIdentity {
+            id: oidc_identities
+                .len()
+                .try_into()
+                .into_report()
+                .change_context(errors::StorageError::MockDbError)?,
+            org_id: oidc_identity.org_id,
+            issuer: oidc_identity.issuer,
+            subject: oidc_identity.subject,
+            user_id: oidc_identity.user_id,
+            created_at: oidc_identity.created_at,
+        };
+        oidc_identities.push(oidc_identity.clone());
+        Ok(oidc_identity)
+    }
+
+    async fn find_oidc_identity_by_org_id_issuer_subject(
+        &self,
+        org_id: &str,
+        issuer: &str,
+        subject: &str,
+    ) -> CustomResult<storage::OidcIdentity, errors::StorageError> {
+        let oidc_identities = self.oidc_identities.lock().await;
+        oidc_identities
+            .iter()
+            .find(|oidc_identity| {
+                oidc_identity.org_id == org_id
+                    && oidc_identity.issuer == issuer
+                    && oidc_identity.subject == subject
+            })
+            .cloned()
+            .ok_or_else(|| {
+                errors::StorageError::DatabaseError(DatabaseError::NotFound.into()).into()
+            })
+    }
+}
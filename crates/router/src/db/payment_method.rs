@@ -1,11 +1,10 @@
 use diesel_models::payment_method::PaymentMethodUpdateInternal;
 use error_stack::{IntoReport, ResultExt};
 
-use super::{MockDb, Store};
+use super::MockDb;
 use crate::{
-    connection,
     core::errors::{self, CustomResult},
-    types::storage,
+    types::storage::{self, enums::MerchantStorageScheme},
 };
 
 #[async_trait::async_trait]
@@ -13,96 +12,505 @@ pub trait PaymentMethodInterface {
     async fn find_payment_method(
         &self,
         payment_method_id: &str,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError>;
 
     async fn find_payment_method_by_customer_id_merchant_id_list(
         &self,
         customer_id: &str,
         merchant_id: &str,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError>;
 
     async fn insert_payment_method(
         &self,
         payment_method_new: storage::PaymentMethodNew,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError>;
 
     async fn update_payment_method(
         &self,
         payment_method: storage::PaymentMethod,
         payment_method_update: storage::PaymentMethodUpdate,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError>;
 
     async fn delete_payment_method_by_merchant_id_payment_method_id(
         &self,
         merchant_id: &str,
         payment_method_id: &str,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError>;
 }
 
-#[async_trait::async_trait]
-impl PaymentMethodInterface for Store {
-    async fn find_payment_method(
-        &self,
-        payment_method_id: &str,
-    ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
-        storage::PaymentMethod::find_by_payment_method_id(&conn, payment_method_id)
-            .await
-            .map_err(Into::into)
-            .into_report()
-    }
+#[cfg(not(feature = "kv_store"))]
+mod storage {
+    use error_stack::IntoReport;
 
-    async fn insert_payment_method(
-        &self,
-        payment_method_new: storage::PaymentMethodNew,
-    ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        payment_method_new
-            .insert(&conn)
+    use super::PaymentMethodInterface;
+    use crate::{
+        connection,
+        core::errors::{self, CustomResult},
+        services::Store,
+        types::storage::{self, enums::MerchantStorageScheme},
+    };
+
+    #[async_trait::async_trait]
+    impl PaymentMethodInterface for Store {
+        async fn find_payment_method(
+            &self,
+            payment_method_id: &str,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            storage::PaymentMethod::find_by_payment_method_id(&conn, payment_method_id)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
+
+        async fn insert_payment_method(
+            &self,
+            payment_method_new: storage::PaymentMethodNew,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
+            let conn = connection::pg_connection_write(self).await?;
+            payment_method_new
+                .insert(&conn)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
+
+        async fn update_payment_method(
+            &self,
+            payment_method: storage::PaymentMethod,
+            payment_method_update: storage::PaymentMethodUpdate,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
+            let conn = connection::pg_connection_write(self).await?;
+            payment_method
+                .update_with_payment_method_id(&conn, payment_method_update)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
+
+        async fn find_payment_method_by_customer_id_merchant_id_list(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            storage::PaymentMethod::find_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        }
+
+        async fn delete_payment_method_by_merchant_id_payment_method_id(
+            &self,
+            merchant_id: &str,
+            payment_method_id: &str,
+            _storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
+            let conn = connection::pg_connection_write(self).await?;
+            storage::PaymentMethod::delete_by_merchant_id_payment_method_id(
+                &conn,
+                merchant_id,
+                payment_method_id,
+            )
             .await
             .map_err(Into::into)
             .into_report()
+        }
     }
+}
 
-    async fn update_payment_method(
-        &self,
-        payment_method: storage::PaymentMethod,
-        payment_method_update: storage::PaymentMethodUpdate,
-    ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        payment_method
-            .update_with_payment_method_id(&conn, payment_method_update)
-            .await
-            .map_err(Into::into)
-            .into_report()
+#[cfg(feature = "kv_store")]
+mod storage {
+    use std::collections::HashSet;
+
+    use common_utils::{fallback_dual_read_not_found, fallback_reverse_lookup_not_found};
+    use diesel_models::payment_method::PaymentMethodUpdateInternal;
+    use error_stack::{IntoReport, ResultExt};
+    use redis_interface::HsetnxReply;
+    use storage_impl::redis::kv_store::{
+        kv_wrapper, resolve_merchant_id_from_kv_key, KvOperation, RedisConnInterface,
+    };
+
+    use super::PaymentMethodInterface;
+    use crate::{
+        connection,
+        core::errors::{self, utils::RedisErrorExt, CustomResult},
+        db::reverse_lookup::ReverseLookupInterface,
+        logger,
+        services::Store,
+        types::storage::{self, enums::MerchantStorageScheme, kv},
+        utils::{self, db_utils},
+    };
+
+    /// Payment methods are looked up by their ID alone, the merchant they belong to is read off
+    /// the key of their KV row
+    async fn get_payment_method_merchant_id(store: &Store, payment_method_id: &str) -> String {
+        let lookup = store
+            .get_lookup_by_lookup_id(
+                &format!("payment_method_{payment_method_id}"),
+                MerchantStorageScheme::RedisKv,
+            )
+            .await;
+        match (lookup, store.get_redis_conn()) {
+            (Ok(lookup), Ok(redis_conn)) => {
+                resolve_merchant_id_from_kv_key(&redis_conn, &lookup.pk_id)
+                    .await
+                    .ok()
+                    .flatten()
+                    .unwrap_or_default()
+            }
+            _ => String::new(),
+        }
     }
 
-    async fn find_payment_method_by_customer_id_merchant_id_list(
-        &self,
-        customer_id: &str,
-        merchant_id: &str,
-    ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
-        storage::PaymentMethod::find_by_customer_id_merchant_id(&conn, customer_id, merchant_id)
+    #[async_trait::async_trait]
+    impl PaymentMethodInterface for Store {
+        async fn find_payment_method(
+            &self,
+            payment_method_id: &str,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
+            let database_call = || async {
+                let conn = connection::pg_connection_read(self).await?;
+                storage::PaymentMethod::find_by_payment_method_id(&conn, payment_method_id)
+                    .await
+                    .map_err(Into::into)
+                    .into_report()
+            };
+            match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                    self,
+                    &get_payment_method_merchant_id(self, payment_method_id).await,
+                    database_call().await,
+                    self.find_payment_method(payment_method_id, MerchantStorageScheme::RedisKv)
+                        .await
+                ),
+                MerchantStorageScheme::RedisKv => {
+                    let lookup_id = format!("payment_method_{payment_method_id}");
+                    let lookup = fallback_reverse_lookup_not_found!(
+                        self.get_lookup_by_lookup_id(&lookup_id, storage_scheme)
+                            .await,
+                        database_call().await
+                    );
+
+                    let key = &lookup.pk_id;
+                    Box::pin(db_utils::try_redis_get_else_try_database_get(
+                        async {
+                            kv_wrapper(
+                                self,
+                                KvOperation::<storage::PaymentMethod>::HGet(&lookup.sk_id),
+                                key,
+                            )
+                            .await?
+                            .try_into_hget()
+                        },
+                        database_call,
+                    ))
+                    .await
+                }
+            }
+        }
+
+        async fn insert_payment_method(
+            &self,
+            payment_method_new: storage::PaymentMethodNew,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
+            match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_connection_write(self).await?;
+                    payment_method_new
+                        .insert(&conn)
+                        .await
+                        .map_err(Into::into)
+                        .into_report()
+                }
+                MerchantStorageScheme::RedisKv => {
+                    // Payment methods share the hash of their customer, so that listing the
+                    // payment methods of a customer only needs to scan a single key
+                    let key = format!(
+                        "mid_{}_cust_{}",
+                        payment_method_new.merchant_id, payment_method_new.customer_id
+                    );
+                    let field =
+                        format!("payment_method_id_{}", payment_method_new.payment_method_id);
+                    let created_payment_method = storage::PaymentMethod {
+                        id: 0i32,
+                        customer_id: payment_method_new.customer_id.clone(),
+                        merchant_id: payment_method_new.merchant_id.clone(),
+                        payment_method_id: payment_method_new.payment_method_id.clone(),
+                        accepted_currency: payment_method_new.accepted_currency.clone(),
+                        scheme: payment_method_new.scheme.clone(),
+                        token: payment_method_new.token.clone(),
+                        cardholder_name: payment_method_new.cardholder_name.clone(),
+                        issuer_name: payment_method_new.issuer_name.clone(),
+                        issuer_country: payment_method_new.issuer_country.clone(),
+                        payer_country: payment_method_new.payer_country.clone(),
+                        is_stored: payment_method_new.is_stored,
+                        swift_code: payment_method_new.swift_code.clone(),
+                        direct_debit_token: payment_method_new.direct_debit_token.clone(),
+                        created_at: payment_method_new.created_at,
+                        last_modified: payment_method_new.last_modified,
+                        payment_method: payment_method_new.payment_method,
+                        payment_method_type: payment_method_new.payment_method_type,
+                        payment_method_issuer: payment_method_new.payment_method_issuer.clone(),
+                        payment_method_issuer_code: payment_method_new.payment_method_issuer_code,
+                        metadata: payment_method_new.metadata.clone(),
+                        payment_method_data: payment_method_new.payment_method_data.clone(),
+                    };
+
+                    self.insert_reverse_lookup(
+                        storage::ReverseLookupNew {
+                            lookup_id: format!(
+                                "payment_method_{}",
+                                created_payment_method.payment_method_id
+                            ),
+                            sk_id: field.clone(),
+                            pk_id: key.clone(),
+                            source: "payment_method".to_string(),
+                            updated_by: storage_scheme.to_string(),
+                        },
+                        storage_scheme,
+                    )
+                    .await?;
+
+                    let redis_entry = kv::TypedSql {
+                        op: kv::DBOperation::Insert {
+                            insertable: kv::Insertable::PaymentMethod(payment_method_new),
+                        },
+                    };
+
+                    match kv_wrapper::<storage::PaymentMethod, _, _>(
+                        self,
+                        KvOperation::<storage::PaymentMethod>::HSetNx(
+                            &field,
+                            &created_payment_method,
+                            redis_entry,
+                        ),
+                        &key,
+                    )
+                    .await
+                    .map_err(|err| err.to_redis_failed_response(&key))?
+                    .try_into_hsetnx()
+                    {
+                        Ok(HsetnxReply::KeyNotSet) => Err(errors::StorageError::DuplicateValue {
+                            entity: "payment_method",
+                            key: Some(created_payment_method.payment_method_id),
+                        })
+                        .into_report(),
+                        Ok(HsetnxReply::KeySet) => Ok(created_payment_method),
+                        Err(er) => Err(er).change_context(errors::StorageError::KVError),
+                    }
+                }
+            }
+        }
+
+        async fn update_payment_method(
+            &self,
+            payment_method: storage::PaymentMethod,
+            payment_method_update: storage::PaymentMethodUpdate,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
+            match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => {
+                    let conn = connection::pg_connection_write(self).await?;
+                    payment_method
+                        .update_with_payment_method_id(&conn, payment_method_update)
+                        .await
+                        .map_err(Into::into)
+                        .into_report()
+                }
+                MerchantStorageScheme::RedisKv => {
+                    let key = format!(
+                        "mid_{}_cust_{}",
+                        payment_method.merchant_id, payment_method.customer_id
+                    );
+                    let field = format!("payment_method_id_{}", payment_method.payment_method_id);
+                    let updated_payment_method =
+                        PaymentMethodUpdateInternal::from(payment_method_update.clone())
+                            .create_payment_method(payment_method.clone());
+
+                    let redis_value =
+                        utils::Encode::<storage::PaymentMethod>::encode_to_string_of_json(
+                            &updated_payment_method,
+                        )
+                        .change_context(errors::StorageError::SerializationFailed)?;
+
+                    let redis_entry = kv::TypedSql {
+                        op: kv::DBOperation::Update {
+                            updatable: kv::Updateable::PaymentMethodUpdate(
+                                kv::PaymentMethodUpdateMems {
+                                    orig: payment_method,
+                                    update_data: payment_method_update,
+                                },
+                            ),
+                        },
+                    };
+
+                    kv_wrapper::<(), _, _>(
+                        self,
+                        KvOperation::Hset::<storage::PaymentMethod>(
+                            (&field, redis_value),
+                            redis_entry,
+                        ),
+                        &key,
+                    )
+                    .await
+                    .map_err(|err| err.to_redis_failed_response(&key))?
+                    .try_into_hset()
+                    .change_context(errors::StorageError::KVError)?;
+
+                    Ok(updated_payment_method)
+                }
+            }
+        }
+
+        async fn find_payment_method_by_customer_id_merchant_id_list(
+            &self,
+            customer_id: &str,
+            merchant_id: &str,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError> {
+            let conn = connection::pg_connection_read(self).await?;
+            let payment_methods = storage::PaymentMethod::find_by_customer_id_merchant_id(
+                &conn,
+                customer_id,
+                merchant_id,
+            )
             .await
             .map_err(Into::into)
-            .into_report()
-    }
+            .into_report()?;
 
-    async fn delete_payment_method_by_merchant_id_payment_method_id(
-        &self,
-        merchant_id: &str,
-        payment_method_id: &str,
-    ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
-        let conn = connection::pg_connection_write(self).await?;
-        storage::PaymentMethod::delete_by_merchant_id_payment_method_id(
-            &conn,
-            merchant_id,
-            payment_method_id,
-        )
-        .await
-        .map_err(Into::into)
-        .into_report()
+            match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => Ok(payment_methods),
+                MerchantStorageScheme::RedisKv => {
+                    // Payment methods that are yet to be drained are only present in redis, while
+                    // the ones that were drained and evicted from redis are only in the database
+                    let key = format!("mid_{merchant_id}_cust_{customer_id}");
+                    let redis_payment_methods = kv_wrapper(
+                        self,
+                        KvOperation::<storage::PaymentMethod>::Scan("payment_method_id_*"),
+                        &key,
+                    )
+                    .await
+                    .map_err(|err| err.to_redis_failed_response(&key))?
+                    .try_into_scan()
+                    .change_context(errors::StorageError::KVError)?;
+
+                    let redis_payment_method_ids = redis_payment_methods
+                        .iter()
+                        .map(|payment_method| payment_method.payment_method_id.clone())
+                        .collect::<HashSet<_>>();
+
+                    Ok(payment_methods
+                        .into_iter()
+                        .filter(|payment_method| {
+                            !redis_payment_method_ids.contains(&payment_method.payment_method_id)
+                        })
+                        .chain(redis_payment_methods)
+                        .collect())
+                }
+            }
+        }
+
+        async fn delete_payment_method_by_merchant_id_payment_method_id(
+            &self,
+            merchant_id: &str,
+            payment_method_id: &str,
+            storage_scheme: MerchantStorageScheme,
+        ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
+            let database_call = || async {
+                let conn = connection::pg_connection_write(self).await?;
+                storage::PaymentMethod::delete_by_merchant_id_payment_method_id(
+                    &conn,
+                    merchant_id,
+                    payment_method_id,
+                )
+                .await
+                .map_err(Into::into)
+                .into_report()
+            };
+            match storage_scheme {
+                MerchantStorageScheme::PostgresOnly => {
+                    let deleted_payment_method = database_call().await?;
+
+                    // Remove the copy that may be left in redis while dual-read is enabled, so
+                    // that it is not found through it anymore
+                    let key = format!(
+                        "mid_{}_cust_{}",
+                        deleted_payment_method.merchant_id, deleted_payment_method.customer_id
+                    );
+                    let field = format!("payment_method_id_{payment_method_id}");
+                    let redis_result = match self.get_redis_conn() {
+                        Ok(redis_conn) => {
+                            redis_conn.delete_hash_field(&key, &field).await.map(|_| ())
+                        }
+                        Err(error) => Err(error),
+                    };
+                    if let Err(error) = redis_result {
+                        logger::error!(?error, "Failed to delete the payment method from redis");
+                    }
+
+                    Ok(deleted_payment_method)
+                }
+                MerchantStorageScheme::RedisKv => {
+                    let payment_method = self
+                        .find_payment_method(payment_method_id, storage_scheme)
+                        .await?;
+                    if payment_method.merchant_id != merchant_id {
+                        return Err(errors::StorageError::ValueNotFound(format!(
+                            "Payment method {payment_method_id} does not exist for merchant \
+                             {merchant_id}"
+                        )))
+                        .into_report();
+                    }
+
+                    // The payment method is deleted from redis first, along with a deletion
+                    // queued after its pending insertion, so that the drainer does not insert it
+                    // back into the database
+                    let key = format!("mid_{merchant_id}_cust_{}", payment_method.customer_id);
+                    let field = format!("payment_method_id_{payment_method_id}");
+                    let redis_entry = kv::TypedSql {
+                        op: kv::DBOperation::Delete {
+                            deletable: kv::Deletable::PaymentMethod {
+                                merchant_id: merchant_id.to_string(),
+                                payment_method_id: payment_method_id.to_string(),
+                            },
+                        },
+                    };
+
+                    let reply = kv_wrapper::<storage::PaymentMethod, _, _>(
+                        self,
+                        KvOperation::<storage::PaymentMethod>::HDel(&field, redis_entry),
+                        &key,
+                    )
+                    .await
+                    .map_err(|err| err.to_redis_failed_response(&key))?
+                    .try_into_hdel()
+                    .change_context(errors::StorageError::KVError)?;
+
+                    match database_call().await {
+                        // Payment methods that were not drained yet are only present in redis
+                        Err(error)
+                            if error.current_context().is_db_not_found()
+                                && matches!(reply, redis_interface::DelReply::KeyDeleted) =>
+                        {
+                            Ok(payment_method)
+                        }
+                        result => result,
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -111,6 +519,7 @@ impl PaymentMethodInterface for MockDb {
     async fn find_payment_method(
         &self,
         payment_method_id: &str,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
         let payment_methods = self.payment_methods.lock().await;
         let payment_method = payment_methods
@@ -130,6 +539,7 @@ impl PaymentMethodInterface for MockDb {
     async fn insert_payment_method(
         &self,
         payment_method_new: storage::PaymentMethodNew,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
         let mut payment_methods = self.payment_methods.lock().await;
 
@@ -169,6 +579,7 @@ impl PaymentMethodInterface for MockDb {
         &self,
         customer_id: &str,
         merchant_id: &str,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<Vec<storage::PaymentMethod>, errors::StorageError> {
         let payment_methods = self.payment_methods.lock().await;
         let payment_methods_found: Vec<storage::PaymentMethod> = payment_methods
@@ -191,6 +602,7 @@ impl PaymentMethodInterface for MockDb {
         &self,
         merchant_id: &str,
         payment_method_id: &str,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
         let mut payment_methods = self.payment_methods.lock().await;
         match payment_methods.iter().position(|pm| {
@@ -211,6 +623,7 @@ impl PaymentMethodInterface for MockDb {
         &self,
         payment_method: storage::PaymentMethod,
         payment_method_update: storage::PaymentMethodUpdate,
+        _storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<storage::PaymentMethod, errors::StorageError> {
         match self
             .payment_methods
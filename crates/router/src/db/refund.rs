@@ -267,7 +267,9 @@ mod storage {
 
 #[cfg(feature = "kv_store")]
 mod storage {
-    use common_utils::{date_time, fallback_reverse_lookup_not_found};
+    use common_utils::{
+        date_time, fallback_dual_read_not_found, fallback_reverse_lookup_not_found,
+    };
     use error_stack::{IntoReport, ResultExt};
     use redis_interface::HsetnxReply;
     use storage_impl::redis::kv_store::{kv_wrapper, KvOperation};
@@ -301,7 +303,17 @@ mod storage {
                 .into_report()
             };
             match storage_scheme {
-                enums::MerchantStorageScheme::PostgresOnly => database_call().await,
+                enums::MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                    self,
+                    merchant_id,
+                    database_call().await,
+                    self.find_refund_by_internal_reference_id_merchant_id(
+                        internal_reference_id,
+                        merchant_id,
+                        enums::MerchantStorageScheme::RedisKv,
+                    )
+                    .await
+                ),
                 enums::MerchantStorageScheme::RedisKv => {
                     let lookup_id = format!("ref_inter_ref_{merchant_id}_{internal_reference_id}");
                     let lookup = fallback_reverse_lookup_not_found!(
@@ -567,7 +579,17 @@ mod storage {
                     .into_report()
             };
             match storage_scheme {
-                enums::MerchantStorageScheme::PostgresOnly => database_call().await,
+                enums::MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                    self,
+                    merchant_id,
+                    database_call().await,
+                    self.find_refund_by_merchant_id_refund_id(
+                        merchant_id,
+                        refund_id,
+                        enums::MerchantStorageScheme::RedisKv,
+                    )
+                    .await
+                ),
                 enums::MerchantStorageScheme::RedisKv => {
                     let lookup_id = format!("ref_ref_id_{merchant_id}_{refund_id}");
                     let lookup = fallback_reverse_lookup_not_found!(
@@ -614,7 +636,18 @@ mod storage {
                 .into_report()
             };
             match storage_scheme {
-                enums::MerchantStorageScheme::PostgresOnly => database_call().await,
+                enums::MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                    self,
+                    merchant_id,
+                    database_call().await,
+                    self.find_refund_by_merchant_id_connector_refund_id_connector(
+                        merchant_id,
+                        connector_refund_id,
+                        connector,
+                        enums::MerchantStorageScheme::RedisKv,
+                    )
+                    .await
+                ),
                 enums::MerchantStorageScheme::RedisKv => {
                     let lookup_id =
                         format!("ref_connector_{merchant_id}_{connector_refund_id}_{connector}");
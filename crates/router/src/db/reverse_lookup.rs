@@ -62,7 +62,7 @@ mod storage {
 
 #[cfg(feature = "kv_store")]
 mod storage {
-    use error_stack::{IntoReport, ResultExt};
+    use error_stack::{report, IntoReport, ResultExt};
     use redis_interface::SetnxReply;
     use storage_impl::redis::kv_store::{kv_wrapper, KvOperation};
 
@@ -91,6 +91,11 @@ mod storage {
                     new.insert(&conn).await.map_err(Into::into).into_report()
                 }
                 enums::MerchantStorageScheme::RedisKv => {
+                    // The reverse lookup is written for the merchant of the row it points to
+                    if self.are_kv_writes_paused_for_key(&new.pk_id).await {
+                        return Err(report!(errors::RedisError::KvWritesPaused)
+                            .to_redis_failed_response(&new.lookup_id));
+                    }
                     let created_rev_lookup = ReverseLookup {
                         lookup_id: new.lookup_id.clone(),
                         sk_id: new.sk_id.clone(),
@@ -0,0 +1,311 @@
+use async_bb8_diesel::AsyncConnection;
+use diesel_models::{errors::DatabaseError, role as storage};
+use error_stack::{IntoReport, ResultExt};
+#[cfg(feature = "accounts_cache")]
+use storage_impl::redis::cache::{CacheKind, ACCOUNTS_CACHE};
+
+use super::MockDb;
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    services::Store,
+};
+
+#[async_trait::async_trait]
+pub trait RoleInterface {
+    async fn insert_role(
+        &self,
+        role: storage::RoleNew,
+    ) -> CustomResult<storage::Role, errors::StorageError>;
+
+    async fn find_role_by_role_id_merchant_id(
+        &self,
+        role_id: &str,
+        merchant_id: &str,
+    ) -> CustomResult<storage::Role, errors::StorageError>;
+
+    async fn update_role_by_role_id_merchant_id(
+        &self,
+        role_id: &str,
+        merchant_id: &str,
+        role_update: storage::RoleUpdate,
+    ) -> CustomResult<storage::Role, errors::StorageError>;
+
+    /// Deletes the role unless it is assigned to a user of the merchant, in which case nothing is
+    /// found to be deleted
+    async fn delete_unassigned_role_by_role_id_merchant_id(
+        &self,
+        role_id: &str,
+        merchant_id: &str,
+    ) -> CustomResult<bool, errors::StorageError>;
+
+    async fn list_roles_by_merchant_id(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<storage::Role>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl RoleInterface for Store {
+    async fn insert_role(
+        &self,
+        role: storage::RoleNew,
+    ) -> CustomResult<storage::Role, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        role.insert(&conn).await.map_err(Into::into).into_report()
+    }
+
+    async fn find_role_by_role_id_merchant_id(
+        &self,
+        role_id: &str,
+        merchant_id: &str,
+    ) -> CustomResult<storage::Role, errors::StorageError> {
+        let find_call = || async {
+            // Read from the primary, the role is checked on every request made with it
+            let conn = connection::pg_connection_write(self).await?;
+            storage::Role::find_by_role_id_merchant_id(&conn, role_id, merchant_id)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        };
+
+        #[cfg(not(feature = "accounts_cache"))]
+        {
+            find_call().await
+        }
+
+        #[cfg(feature = "accounts_cache")]
+        {
+            super::cache::get_or_populate_in_memory(
+                self,
+                &get_role_cache_key(role_id, merchant_id),
+                find_call,
+                &ACCOUNTS_CACHE,
+            )
+            .await
+        }
+    }
+
+    async fn update_role_by_role_id_merchant_id(
+        &self,
+        role_id: &str,
+        merchant_id: &str,
+        role_update: storage::RoleUpdate,
+    ) -> CustomResult<storage::Role, errors::StorageError> {
+        let update_call = || async {
+            let conn = connection::pg_connection_write(self).await?;
+            storage::Role::update_by_role_id_merchant_id(&conn, role_id, merchant_id, role_update)
+                .await
+                .map_err(Into::into)
+                .into_report()
+        };
+
+        #[cfg(not(feature = "accounts_cache"))]
+        {
+            update_call().await
+        }
+
+        #[cfg(feature = "accounts_cache")]
+        {
+            super::cache::publish_and_redact(
+                self,
+                CacheKind::Accounts(get_role_cache_key(role_id, merchant_id).into()),
+                update_call,
+            )
+            .await
+        }
+    }
+
+    async fn delete_unassigned_role_by_role_id_merchant_id(
+        &self,
+        role_id: &str,
+        merchant_id: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let delete_call = || async {
+            let conn = connection::pg_connection_write(self).await?;
+            let (role_id, merchant_id) = (role_id.to_owned(), merchant_id.to_owned());
+            conn.transaction_async(|conn| async move {
+                // Waits for the transactions assigning the role, their assignments are then seen
+                // by the check of the delete statement
+                match storage::Role::lock_for_deletion_by_role_id_merchant_id(
+                    &conn,
+                    &role_id,
+                    &merchant_id,
+                )
+                .await
+                {
+                    Ok(_) => {}
+                    Err(error) if matches!(error.current_context(), DatabaseError::NotFound) => {
+                        return Ok(false)
+                    }
+                    Err(error) => return Err(*error.current_context()),
+                }
+                storage::Role::delete_unassigned_by_role_id_merchant_id(
+                    &conn,
+                    &role_id,
+                    &merchant_id,
+                )
+                .await
+                .map_err(|error| *error.current_context())
+            })
+            .await
+            .into_report()
+            .map_err(Into::into)
+            .into_report()
+        };
+
+        #[cfg(not(feature = "accounts_cache"))]
+        {
+            delete_call().await
+        }
+
+        #[cfg(feature = "accounts_cache")]
+        {
+            super::cache::publish_and_redact(
+                self,
+                CacheKind::Accounts(get_role_cache_key(role_id, merchant_id).into()),
+                delete_call,
+            )
+            .await
+        }
+    }
+
+    async fn list_roles_by_merchant_id(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<storage::Role>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::Role::list_by_merchant_id(&conn, merchant_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+/// The key of a custom role in the accounts cache, prefixed by the merchant ID so that the role is
+/// invalidated along with the other keys of the merchant
+#[cfg(feature = "accounts_cache")]
+fn get_role_cache_key(role_id: &str, merchant_id: &str) -> String {
+    format!("{merchant_id}_role_{role_id}")
+}
+
+#[async_trait::async_trait]
+impl RoleInterface for MockDb {
+    async fn insert_role(
+        &self,
+        role: storage::RoleNew,
+    ) -> CustomResult<storage::Role, errors::StorageError> {
+        let mut roles = self.roles.lock().await;
+        if roles.iter().any(|role_inner| {
+            role_inner.role_id == role.role_id
+                || (role_inner.merchant_id == role.merchant_id
+                    && role_inner.role_name == role.role_name)
+        }) {
+            // Mirrors the unique constraints of the table
+            Err(errors::StorageError::DatabaseError(
+                DatabaseError::UniqueViolation.into(),
+            ))?
+        }
+        let role = storage::Role {
+            id: roles
+                .len()
+                .try_into()
+                .into_report()
+                .change_context(errors::StorageError::MockDbError)?,
+            role_id: role.role_id,
+            role_name: role.role_name,
+            merchant_id: role.merchant_id,
+            org_id: role.org_id,
+            permissions: role.permissions,
+            created_by: role.created_by,
+            created_at: role.created_at,
+            last_modified_by: role.last_modified_by,
+            last_modified_at: role.last_modified_at,
+        };
+        roles.push(role.clone());
+        Ok(role)
+    }
+
+    async fn find_role_by_role_id_merchant_id(
+        &self,
+        role_id: &str,
+        merchant_id: &str,
+    ) -> CustomResult<storage::Role, errors::StorageError> {
+        let roles = self.roles.lock().await;
+        roles
+            .iter()
+            .find(|role| role.role_id == role_id && role.merchant_id == merchant_id)
+            .cloned()
+            .ok_or_else(|| {
+                errors::StorageError::DatabaseError(DatabaseError::NotFound.into()).into()
+            })
+    }
+
+    async fn update_role_by_role_id_merchant_id(
+        &self,
+        role_id: &str,
+        merchant_id: &str,
+        role_update: storage::RoleUpdate,
+    ) -> CustomResult<storage::Role, errors::StorageError> {
+        let mut roles = self.roles.lock().await;
+        roles
+            .iter_mut()
+            .find(|role| role.role_id == role_id && role.merchant_id == merchant_id)
+            .map(|role| {
+                *role = match &role_update {
+                    storage::RoleUpdate::UpdateDetails {
+                        role_name,
+                        permissions,
+                        last_modified_by,
+                    } => storage::Role {
+                        role_name: role_name.clone().unwrap_or_else(|| role.role_name.clone()),
+                        permissions: permissions
+                            .clone()
+                            .unwrap_or_else(|| role.permissions.clone()),
+                        last_modified_by: last_modified_by.to_owned(),
+                        last_modified_at: common_utils::date_time::now(),
+                        ..role.to_owned()
+                    },
+                };
+                role.to_owned()
+            })
+            .ok_or_else(|| {
+                errors::StorageError::DatabaseError(DatabaseError::NotFound.into()).into()
+            })
+    }
+
+    async fn delete_unassigned_role_by_role_id_merchant_id(
+        &self,
+        role_id: &str,
+        merchant_id: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let user_roles = self.user_roles.lock().await;
+        let mut roles = self.roles.lock().await;
+        let role_index = roles
+            .iter()
+            .position(|role| {
+                role.role_id == role_id
+                    && role.merchant_id == merchant_id
+                    && !user_roles.iter().any(|user_role| {
+                        user_role.role_id == role_id && user_role.merchant_id == merchant_id
+                    })
+            })
+            .ok_or(errors::StorageError::DatabaseError(
+                DatabaseError::NotFound.into(),
+            ))?;
+        roles.remove(role_index);
+        Ok(true)
+    }
+
+    async fn list_roles_by_merchant_id(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<storage::Role>, errors::StorageError> {
+        let roles = self.roles.lock().await;
+        Ok(roles
+            .iter()
+            .filter(|role| role.merchant_id == merchant_id)
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,111 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait RoutingAlgorithmActivationInterface {
+    async fn insert_routing_algorithm_activation(
+        &self,
+        activation: storage::RoutingAlgorithmActivationNew,
+    ) -> CustomResult<storage::RoutingAlgorithmActivation, errors::StorageError>;
+
+    /// Lists the activations of the given profile, most recent first. Activations made at the
+    /// merchant account level are listed when `profile_id` is `None`.
+    async fn list_routing_algorithm_activations_by_merchant_id_profile_id(
+        &self,
+        merchant_id: &str,
+        profile_id: Option<&str>,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::RoutingAlgorithmActivation>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl RoutingAlgorithmActivationInterface for Store {
+    async fn insert_routing_algorithm_activation(
+        &self,
+        activation: storage::RoutingAlgorithmActivationNew,
+    ) -> CustomResult<storage::RoutingAlgorithmActivation, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        activation
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn list_routing_algorithm_activations_by_merchant_id_profile_id(
+        &self,
+        merchant_id: &str,
+        profile_id: Option<&str>,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::RoutingAlgorithmActivation>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::RoutingAlgorithmActivation::find_by_merchant_id_profile_id(
+            &conn,
+            merchant_id,
+            profile_id,
+            limit,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl RoutingAlgorithmActivationInterface for MockDb {
+    async fn insert_routing_algorithm_activation(
+        &self,
+        activation: storage::RoutingAlgorithmActivationNew,
+    ) -> CustomResult<storage::RoutingAlgorithmActivation, errors::StorageError> {
+        let mut activations = self.routing_algorithm_activations.lock().await;
+        if activations
+            .iter()
+            .any(|existing| existing.activation_id == activation.activation_id)
+        {
+            Err(errors::StorageError::DuplicateValue {
+                entity: "activation_id",
+                key: Some(activation.activation_id.clone()),
+            })?
+        }
+        let activation = storage::RoutingAlgorithmActivation {
+            activation_id: activation.activation_id,
+            merchant_id: activation.merchant_id,
+            profile_id: activation.profile_id,
+            algorithm_id: activation.algorithm_id,
+            previous_algorithm_id: activation.previous_algorithm_id,
+            action: activation.action,
+            created_at: common_utils::date_time::now(),
+        };
+        activations.push(activation.clone());
+        Ok(activation)
+    }
+
+    async fn list_routing_algorithm_activations_by_merchant_id_profile_id(
+        &self,
+        merchant_id: &str,
+        profile_id: Option<&str>,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::RoutingAlgorithmActivation>, errors::StorageError> {
+        let activations = self.routing_algorithm_activations.lock().await;
+
+        let matching = activations
+            .iter()
+            .rev()
+            .filter(|activation| {
+                activation.merchant_id == merchant_id
+                    && activation.profile_id.as_deref() == profile_id
+            })
+            .cloned();
+
+        Ok(match limit.and_then(|limit| usize::try_from(limit).ok()) {
+            Some(limit) => matching.take(limit).collect(),
+            None => matching.collect(),
+        })
+    }
+}
@@ -1,6 +1,8 @@
-use diesel_models::{user as storage, user_role::UserRole};
+use diesel_models::{
+    enums::TotpStatus, errors::DatabaseError, user as storage, user_role::UserRole,
+};
 use error_stack::{IntoReport, ResultExt};
-use masking::Secret;
+use masking::{PeekInterface, Secret};
 
 use super::MockDb;
 use crate::{
@@ -33,6 +35,23 @@ pub trait UserInterface {
         user: storage::UserUpdate,
     ) -> CustomResult<storage::User, errors::StorageError>;
 
+    /// Records the time step of an accepted TOTP, failing with not found if a code of the same or
+    /// a later step has already been accepted
+    async fn update_user_totp_last_used_step(
+        &self,
+        user_id: &str,
+        totp_step: i64,
+    ) -> CustomResult<storage::User, errors::StorageError>;
+
+    /// Replaces the recovery codes, failing with not found if they have changed since they were
+    /// read
+    async fn update_user_recovery_codes_if_unchanged(
+        &self,
+        user_id: &str,
+        recovery_codes: Vec<Secret<String>>,
+        remaining_recovery_codes: Vec<Secret<String>>,
+    ) -> CustomResult<storage::User, errors::StorageError>;
+
     async fn delete_user_by_user_id(
         &self,
         user_id: &str,
@@ -92,6 +111,36 @@ impl UserInterface for Store {
             .into_report()
     }
 
+    async fn update_user_totp_last_used_step(
+        &self,
+        user_id: &str,
+        totp_step: i64,
+    ) -> CustomResult<storage::User, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::User::update_totp_last_used_step_by_user_id(&conn, user_id, totp_step)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn update_user_recovery_codes_if_unchanged(
+        &self,
+        user_id: &str,
+        recovery_codes: Vec<Secret<String>>,
+        remaining_recovery_codes: Vec<Secret<String>>,
+    ) -> CustomResult<storage::User, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::User::update_recovery_codes_by_user_id_recovery_codes(
+            &conn,
+            user_id,
+            recovery_codes,
+            remaining_recovery_codes,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
     async fn delete_user_by_user_id(
         &self,
         user_id: &str,
@@ -145,6 +194,10 @@ impl UserInterface for MockDb {
             is_verified: user_data.is_verified,
             created_at: user_data.created_at.unwrap_or(time_now),
             last_modified_at: user_data.created_at.unwrap_or(time_now),
+            totp_status: TotpStatus::NotSet,
+            totp_secret: None,
+            totp_recovery_codes: None,
+            totp_last_used_step: None,
         };
         users.push(user.clone());
         Ok(user)
@@ -163,12 +216,8 @@ impl UserInterface for MockDb {
             .iter()
             .find(|user| user.email == user_email_pii)
             .cloned()
-            .ok_or(
-                errors::StorageError::ValueNotFound(format!(
-                    "No user available for email = {user_email}"
-                ))
-                .into(),
-            )
+            .ok_or(errors::StorageError::DatabaseError(DatabaseError::NotFound.into()).into())
+            .attach_printable_lazy(|| format!("No user available for email = {user_email}"))
     }
 
     async fn find_user_by_id(
@@ -213,6 +262,30 @@ impl UserInterface for MockDb {
                         is_verified: is_verified.unwrap_or(user.is_verified),
                         ..user.to_owned()
                     },
+                    storage::UserUpdate::TotpUpdate {
+                        totp_status,
+                        totp_secret,
+                        totp_recovery_codes,
+                    } => storage::User {
+                        totp_status: totp_status.unwrap_or(user.totp_status),
+                        totp_secret: totp_secret.clone().or(user.totp_secret.clone()),
+                        totp_recovery_codes: totp_recovery_codes
+                            .clone()
+                            .or(user.totp_recovery_codes.clone()),
+                        ..user.to_owned()
+                    },
+                    storage::UserUpdate::TotpStepUpdate {
+                        totp_last_used_step,
+                    } => storage::User {
+                        totp_last_used_step: Some(*totp_last_used_step),
+                        ..user.to_owned()
+                    },
+                    storage::UserUpdate::TotpReset => storage::User {
+                        totp_status: TotpStatus::NotSet,
+                        totp_secret: None,
+                        totp_recovery_codes: None,
+                        ..user.to_owned()
+                    },
                 };
                 user.to_owned()
             })
@@ -224,6 +297,57 @@ impl UserInterface for MockDb {
             )
     }
 
+    async fn update_user_totp_last_used_step(
+        &self,
+        user_id: &str,
+        totp_step: i64,
+    ) -> CustomResult<storage::User, errors::StorageError> {
+        let mut users = self.users.lock().await;
+        users
+            .iter_mut()
+            .find(|user| {
+                user.user_id == user_id
+                    && user
+                        .totp_last_used_step
+                        .map_or(true, |last_used_step| last_used_step < totp_step)
+            })
+            .map(|user| {
+                user.totp_last_used_step = Some(totp_step);
+                user.to_owned()
+            })
+            .ok_or_else(|| {
+                errors::StorageError::DatabaseError(DatabaseError::NotFound.into()).into()
+            })
+    }
+
+    async fn update_user_recovery_codes_if_unchanged(
+        &self,
+        user_id: &str,
+        recovery_codes: Vec<Secret<String>>,
+        remaining_recovery_codes: Vec<Secret<String>>,
+    ) -> CustomResult<storage::User, errors::StorageError> {
+        let mut users = self.users.lock().await;
+        users
+            .iter_mut()
+            .find(|user| {
+                user.user_id == user_id
+                    && user.totp_recovery_codes.as_ref().map_or(false, |codes| {
+                        codes.len() == recovery_codes.len()
+                            && codes
+                                .iter()
+                                .zip(recovery_codes.iter())
+                                .all(|(code, previous_code)| code.peek() == previous_code.peek())
+                    })
+            })
+            .map(|user| {
+                user.totp_recovery_codes = Some(remaining_recovery_codes);
+                user.to_owned()
+            })
+            .ok_or_else(|| {
+                errors::StorageError::DatabaseError(DatabaseError::NotFound.into()).into()
+            })
+    }
+
     async fn delete_user_by_user_id(
         &self,
         user_id: &str,
@@ -246,3 +370,76 @@ impl UserInterface for MockDb {
         Err(errors::StorageError::MockDbError)?
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use masking::Secret;
+
+    use crate::db::{user::UserInterface, MockDb};
+
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_mock_db_user_two_factor_auth_updates() {
+        #[allow(clippy::expect_used)]
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create mock DB");
+        let user_id = "user_two_factor_auth";
+        mock_db
+            .insert_user(diesel_models::user::UserNew {
+                user_id: user_id.to_string(),
+                email: "user@example.com".to_string().try_into().unwrap(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        mock_db
+            .update_user_totp_last_used_step(user_id, 100)
+            .await
+            .unwrap();
+        for replayed_step in [100, 99] {
+            let error = mock_db
+                .update_user_totp_last_used_step(user_id, replayed_step)
+                .await
+                .unwrap_err();
+            assert!(error.current_context().is_db_not_found());
+        }
+        let user = mock_db
+            .update_user_totp_last_used_step(user_id, 101)
+            .await
+            .unwrap();
+        assert_eq!(user.totp_last_used_step, Some(101));
+
+        let recovery_codes = vec![Secret::new("a".to_string()), Secret::new("b".to_string())];
+        mock_db
+            .update_user_by_user_id(
+                user_id,
+                diesel_models::user::UserUpdate::TotpUpdate {
+                    totp_status: None,
+                    totp_secret: None,
+                    totp_recovery_codes: Some(recovery_codes.clone()),
+                },
+            )
+            .await
+            .unwrap();
+        mock_db
+            .update_user_recovery_codes_if_unchanged(
+                user_id,
+                recovery_codes.clone(),
+                vec![Secret::new("b".to_string())],
+            )
+            .await
+            .unwrap();
+        // The same code used concurrently, against the codes read before its first use
+        let error = mock_db
+            .update_user_recovery_codes_if_unchanged(
+                user_id,
+                recovery_codes,
+                vec![Secret::new("b".to_string())],
+            )
+            .await
+            .unwrap_err();
+        assert!(error.current_context().is_db_not_found());
+    }
+}
@@ -1,11 +1,12 @@
-use diesel_models::user_role as storage;
+use async_bb8_diesel::AsyncConnection;
+use diesel_models::{errors::DatabaseError, role::Role, user_role as storage};
 use error_stack::{IntoReport, ResultExt};
 
 use super::MockDb;
 use crate::{
     connection,
     core::errors::{self, CustomResult},
-    services::Store,
+    services::{authorization::predefined_permissions, Store},
 };
 
 #[async_trait::async_trait]
@@ -39,11 +40,17 @@ impl UserRoleInterface for Store {
         user_role: storage::UserRoleNew,
     ) -> CustomResult<storage::UserRole, errors::StorageError> {
         let conn = connection::pg_connection_write(self).await?;
-        user_role
-            .insert(&conn)
-            .await
-            .map_err(Into::into)
-            .into_report()
+        conn.transaction_async(|conn| async move {
+            lock_custom_role(&conn, &user_role.role_id, &user_role.merchant_id).await?;
+            user_role
+                .insert(&conn)
+                .await
+                .map_err(|error| *error.current_context())
+        })
+        .await
+        .into_report()
+        .map_err(Into::into)
+        .into_report()
     }
 
     async fn find_user_role_by_user_id(
@@ -64,13 +71,17 @@ impl UserRoleInterface for Store {
         update: storage::UserRoleUpdate,
     ) -> CustomResult<storage::UserRole, errors::StorageError> {
         let conn = connection::pg_connection_write(self).await?;
-        storage::UserRole::update_by_user_id_merchant_id(
-            &conn,
-            user_id.to_owned(),
-            merchant_id.to_owned(),
-            update,
-        )
+        let (user_id, merchant_id) = (user_id.to_owned(), merchant_id.to_owned());
+        conn.transaction_async(|conn| async move {
+            if let storage::UserRoleUpdate::UpdateRole { role_id, .. } = &update {
+                lock_custom_role(&conn, role_id, &merchant_id).await?;
+            }
+            storage::UserRole::update_by_user_id_merchant_id(&conn, user_id, merchant_id, update)
+                .await
+                .map_err(|error| *error.current_context())
+        })
         .await
+        .into_report()
         .map_err(Into::into)
         .into_report()
     }
@@ -95,6 +106,22 @@ impl UserRoleInterface for Store {
     }
 }
 
+/// Custom roles are locked until the user role is written, so that a role being assigned cannot be
+/// deleted in the meantime. Predefined roles are not stored and cannot be deleted.
+async fn lock_custom_role(
+    conn: &connection::PgPooledConn,
+    role_id: &str,
+    merchant_id: &str,
+) -> Result<(), DatabaseError> {
+    if predefined_permissions::PREDEFINED_PERMISSIONS.contains_key(role_id) {
+        return Ok(());
+    }
+    Role::lock_for_assignment_by_role_id_merchant_id(conn, role_id, merchant_id)
+        .await
+        .map(|_| ())
+        .map_err(|error| *error.current_context())
+}
+
 #[async_trait::async_trait]
 impl UserRoleInterface for MockDb {
     async fn insert_user_role(
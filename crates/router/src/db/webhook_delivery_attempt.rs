@@ -0,0 +1,99 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait WebhookDeliveryAttemptInterface {
+    async fn insert_webhook_delivery_attempt(
+        &self,
+        delivery_attempt: storage::WebhookDeliveryAttemptNew,
+    ) -> CustomResult<storage::WebhookDeliveryAttempt, errors::StorageError>;
+
+    async fn find_webhook_delivery_attempts_by_merchant_id_event_id(
+        &self,
+        merchant_id: &str,
+        event_id: &str,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl WebhookDeliveryAttemptInterface for Store {
+    async fn insert_webhook_delivery_attempt(
+        &self,
+        delivery_attempt: storage::WebhookDeliveryAttemptNew,
+    ) -> CustomResult<storage::WebhookDeliveryAttempt, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        delivery_attempt
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_webhook_delivery_attempts_by_merchant_id_event_id(
+        &self,
+        merchant_id: &str,
+        event_id: &str,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::WebhookDeliveryAttempt::find_by_merchant_id_event_id(&conn, merchant_id, event_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookDeliveryAttemptInterface for MockDb {
+    async fn insert_webhook_delivery_attempt(
+        &self,
+        delivery_attempt: storage::WebhookDeliveryAttemptNew,
+    ) -> CustomResult<storage::WebhookDeliveryAttempt, errors::StorageError> {
+        let mut delivery_attempts = self.webhook_delivery_attempts.lock().await;
+        if delivery_attempts
+            .iter()
+            .any(|attempt| attempt.attempt_id == delivery_attempt.attempt_id)
+        {
+            Err(errors::StorageError::DuplicateValue {
+                entity: "attempt_id",
+                key: Some(delivery_attempt.attempt_id.clone()),
+            })?
+        }
+        let delivery_attempt = storage::WebhookDeliveryAttempt {
+            attempt_id: delivery_attempt.attempt_id,
+            event_id: delivery_attempt.event_id,
+            merchant_id: delivery_attempt.merchant_id,
+            attempt_number: delivery_attempt.attempt_number,
+            delivery_trigger: delivery_attempt.delivery_trigger,
+            request_url: delivery_attempt.request_url,
+            request_body: delivery_attempt.request_body,
+            response_status_code: delivery_attempt.response_status_code,
+            response_body: delivery_attempt.response_body,
+            error_message: delivery_attempt.error_message,
+            latency_ms: delivery_attempt.latency_ms,
+            is_delivered: delivery_attempt.is_delivered,
+            created_at: common_utils::date_time::now(),
+        };
+        delivery_attempts.push(delivery_attempt.clone());
+        Ok(delivery_attempt)
+    }
+
+    async fn find_webhook_delivery_attempts_by_merchant_id_event_id(
+        &self,
+        merchant_id: &str,
+        event_id: &str,
+    ) -> CustomResult<Vec<storage::WebhookDeliveryAttempt>, errors::StorageError> {
+        let delivery_attempts = self.webhook_delivery_attempts.lock().await;
+
+        Ok(delivery_attempts
+            .iter()
+            .filter(|attempt| attempt.merchant_id == merchant_id && attempt.event_id == event_id)
+            .cloned()
+            .collect())
+    }
+}
@@ -75,6 +75,8 @@ Never share your secret api keys. Keep them guarded and secure.
         // crate::routes::admin::delete_merchant_account,
         crate::routes::admin::payment_connector_create,
         crate::routes::admin::payment_connector_retrieve,
+        crate::routes::admin::payment_connector_circuit_breaker_retrieve,
+        crate::routes::admin::payment_connector_circuit_breaker_reset,
         crate::routes::admin::payment_connector_list,
         crate::routes::admin::payment_connector_update,
         crate::routes::admin::payment_connector_delete,
@@ -171,6 +173,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::FrmAction,
         api_models::enums::FrmPreferredFlowTypes,
         api_models::enums::RetryAction,
+        api_models::enums::CircuitBreakerState,
         api_models::enums::AttemptStatus,
         api_models::enums::CaptureStatus,
         api_models::enums::ReconStatus,
@@ -358,6 +361,8 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::types::api::admin::MerchantConnectorId,
         crate::types::api::admin::MerchantDetails,
         crate::types::api::admin::WebhookDetails,
+        api_models::admin::WebhookEndpoint,
+        api_models::admin::MerchantConnectorCircuitBreakerResponse,
         crate::types::api::api_keys::ApiKeyExpiration,
         crate::types::api::api_keys::CreateApiKeyRequest,
         crate::types::api::api_keys::CreateApiKeyResponse,
@@ -2,6 +2,8 @@ use actix_web::{web, HttpRequest, HttpResponse};
 use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
+#[cfg(feature = "kv_store")]
+use crate::core::storage_scheme_migration;
 use crate::{
     core::{admin::*, api_locking},
     services::{api, authentication as auth, authorization::permissions::Permission},
@@ -285,6 +287,112 @@ pub async fn payment_connector_retrieve(
     )
     .await
 }
+/// Merchant Connector - Circuit Breaker Retrieve
+///
+/// Retrieve the circuit breaker state of a Merchant Connector, which removes it from routing after
+/// consecutive connector failures
+#[utoipa::path(
+    get,
+    path = "/accounts/{account_id}/connectors/{connector_id}/circuit_breaker",
+    params(
+        ("account_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("connector_id" = String, Path, description = "The unique identifier for the Merchant Connector")
+    ),
+    responses(
+        (status = 200, description = "Circuit breaker retrieved successfully", body = MerchantConnectorCircuitBreakerResponse),
+        (status = 404, description = "Merchant Connector does not exist in records"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "Retrieve the circuit breaker of a Merchant Connector",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::MerchantConnectorsCircuitBreakerRetrieve))]
+pub async fn payment_connector_circuit_breaker_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let flow = Flow::MerchantConnectorsCircuitBreakerRetrieve;
+    let (merchant_id, merchant_connector_id) = path.into_inner();
+    let payload = admin::MerchantConnectorId {
+        merchant_id: merchant_id.clone(),
+        merchant_connector_id,
+    };
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, req| {
+            retrieve_connector_circuit_breaker(state, req.merchant_id, req.merchant_connector_id)
+        },
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id,
+                required_permission: Permission::MerchantConnectorAccountRead,
+            },
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Merchant Connector - Circuit Breaker Reset
+///
+/// Close the circuit breaker of a Merchant Connector, routing payments to it again without waiting
+/// for its cool-down
+#[utoipa::path(
+    post,
+    path = "/accounts/{account_id}/connectors/{connector_id}/circuit_breaker/reset",
+    params(
+        ("account_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("connector_id" = String, Path, description = "The unique identifier for the Merchant Connector")
+    ),
+    responses(
+        (status = 200, description = "Circuit breaker reset successfully", body = MerchantConnectorCircuitBreakerResponse),
+        (status = 404, description = "Merchant Connector does not exist in records"),
+        (status = 401, description = "Unauthorized request")
+    ),
+    tag = "Merchant Connector Account",
+    operation_id = "Reset the circuit breaker of a Merchant Connector",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::MerchantConnectorsCircuitBreakerReset))]
+pub async fn payment_connector_circuit_breaker_reset(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let flow = Flow::MerchantConnectorsCircuitBreakerReset;
+    let (merchant_id, merchant_connector_id) = path.into_inner();
+    let payload = admin::MerchantConnectorId {
+        merchant_id: merchant_id.clone(),
+        merchant_connector_id,
+    };
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, req| {
+            reset_connector_circuit_breaker(state, req.merchant_id, req.merchant_connector_id)
+        },
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id,
+                required_permission: Permission::MerchantConnectorAccountWrite,
+            },
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
 /// Merchant Connector - List
 ///
 /// List Merchant Connector Details for the merchant
@@ -538,6 +646,34 @@ pub async fn business_profile_update(
     )
     .await
 }
+#[instrument(skip_all, fields(flow = ?Flow::WebhookSigningKeyRotate))]
+pub async fn webhook_signing_key_rotate(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    json_payload: web::Json<api_models::admin::WebhookSigningKeyRotateRequest>,
+) -> HttpResponse {
+    let flow = Flow::WebhookSigningKeyRotate;
+    let (merchant_id, profile_id) = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| rotate_webhook_signing_key(state, &profile_id, &merchant_id, req),
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::MerchantAccountWrite,
+            },
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
 #[instrument(skip_all, fields(flow = ?Flow::BusinessProfileDelete))]
 pub async fn business_profile_delete(
     state: web::Data<AppState>,
@@ -608,3 +744,81 @@ pub async fn merchant_account_kv_status(
     )
     .await
 }
+
+/// Merchant Account - Start Storage Scheme Migration
+///
+/// Migrate the Merchant Account to another storage scheme without downtime
+#[cfg(feature = "kv_store")]
+#[instrument(skip_all, fields(flow = ?Flow::StorageSchemeMigrationStart))]
+pub async fn storage_scheme_migration_start(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<admin::StorageSchemeMigrationRequest>,
+) -> HttpResponse {
+    let flow = Flow::StorageSchemeMigrationStart;
+    let mut payload = json_payload.into_inner();
+    payload.merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, payload| storage_scheme_migration::start_migration(state, payload),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Merchant Account - Retrieve Storage Scheme Migration
+///
+/// Retrieve the status of the latest storage scheme migration of the Merchant Account
+#[cfg(feature = "kv_store")]
+#[instrument(skip_all, fields(flow = ?Flow::StorageSchemeMigrationRetrieve))]
+pub async fn storage_scheme_migration_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::StorageSchemeMigrationRetrieve;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        merchant_id,
+        |state, _, merchant_id| storage_scheme_migration::retrieve_migration(state, merchant_id),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Merchant Account - Abort Storage Scheme Migration
+///
+/// Abort the latest storage scheme migration of the Merchant Account, before its storage scheme
+/// is switched
+#[cfg(feature = "kv_store")]
+#[instrument(skip_all, fields(flow = ?Flow::StorageSchemeMigrationAbort))]
+pub async fn storage_scheme_migration_abort(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::StorageSchemeMigrationAbort;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        merchant_id,
+        |state, _, merchant_id| storage_scheme_migration::abort_migration(state, merchant_id),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
@@ -28,8 +28,8 @@ use super::routing as cloud_routing;
 use super::verification::{apple_pay_merchant_registration, retrieve_apple_pay_verified_domains};
 #[cfg(feature = "olap")]
 use super::{
-    admin::*, api_keys::*, connector_onboarding::*, disputes::*, files::*, gsm::*,
-    locker_migration, payment_link::*, user::*, user_role::*,
+    admin::*, api_keys::*, audit_log::*, connector_onboarding::*, disputes::*, files::*, gsm::*,
+    locker_migration, payment_link::*, process_tracker::*, user::*, user_role::*,
 };
 use super::{cache::*, health::*};
 #[cfg(any(feature = "olap", feature = "oltp"))]
@@ -419,6 +419,17 @@ impl Routing {
                 web::resource("/deactivate")
                     .route(web::post().to(cloud_routing::routing_unlink_config)),
             )
+            .service(
+                web::resource("/history")
+                    .route(web::get().to(cloud_routing::routing_retrieve_activation_history)),
+            )
+            .service(
+                web::resource("/diff").route(web::post().to(cloud_routing::routing_diff_config)),
+            )
+            .service(
+                web::resource("/rollback")
+                    .route(web::post().to(cloud_routing::routing_rollback_config)),
+            )
             .service(
                 web::resource("/decision")
                     .route(web::put().to(cloud_routing::upsert_decision_manager_config))
@@ -441,6 +452,10 @@ impl Routing {
                 web::resource("/{algorithm_id}/activate")
                     .route(web::post().to(cloud_routing::routing_link_config)),
             )
+            .service(
+                web::resource("/{algorithm_id}/simulate")
+                    .route(web::post().to(cloud_routing::routing_simulate_config)),
+            )
             .service(
                 web::resource("/default/profile/{profile_id}").route(
                     web::post().to(cloud_routing::routing_update_default_config_for_profile),
@@ -570,7 +585,7 @@ pub struct MerchantAccount;
 #[cfg(feature = "olap")]
 impl MerchantAccount {
     pub fn server(state: AppState) -> Scope {
-        web::scope("/accounts")
+        let mut route = web::scope("/accounts")
             .app_data(web::Data::new(state))
             .service(web::resource("").route(web::post().to(merchant_account_create)))
             .service(web::resource("/list").route(web::get().to(merchant_account_list)))
@@ -578,13 +593,28 @@ impl MerchantAccount {
                 web::resource("/{id}/kv")
                     .route(web::post().to(merchant_account_toggle_kv))
                     .route(web::get().to(merchant_account_kv_status)),
-            )
-            .service(
-                web::resource("/{id}")
-                    .route(web::get().to(retrieve_merchant_account))
-                    .route(web::post().to(update_merchant_account))
-                    .route(web::delete().to(delete_merchant_account)),
-            )
+            );
+
+        #[cfg(feature = "kv_store")]
+        {
+            route = route
+                .service(
+                    web::resource("/{id}/storage_scheme/migration")
+                        .route(web::post().to(storage_scheme_migration_start))
+                        .route(web::get().to(storage_scheme_migration_retrieve)),
+                )
+                .service(
+                    web::resource("/{id}/storage_scheme/migration/abort")
+                        .route(web::post().to(storage_scheme_migration_abort)),
+                );
+        }
+
+        route.service(
+            web::resource("/{id}")
+                .route(web::get().to(retrieve_merchant_account))
+                .route(web::post().to(update_merchant_account))
+                .route(web::delete().to(delete_merchant_account)),
+        )
     }
 }
 
@@ -614,6 +644,18 @@ impl MerchantConnectorAccount {
                         .route(web::get().to(payment_connector_retrieve))
                         .route(web::post().to(payment_connector_update))
                         .route(web::delete().to(payment_connector_delete)),
+                )
+                .service(
+                    web::resource(
+                        "/{merchant_id}/connectors/{merchant_connector_id}/circuit_breaker",
+                    )
+                    .route(web::get().to(payment_connector_circuit_breaker_retrieve)),
+                )
+                .service(
+                    web::resource(
+                        "/{merchant_id}/connectors/{merchant_connector_id}/circuit_breaker/reset",
+                    )
+                    .route(web::post().to(payment_connector_circuit_breaker_reset)),
                 );
         }
         #[cfg(feature = "oltp")]
@@ -670,6 +712,15 @@ impl Webhooks {
         #[allow(unused_mut)]
         let mut route = web::scope("/webhooks")
             .app_data(web::Data::new(config))
+            .service(web::resource("/events").route(web::get().to(list_webhook_events)))
+            .service(
+                web::resource("/events/{event_id}/attempts")
+                    .route(web::get().to(list_webhook_event_delivery_attempts)),
+            )
+            .service(
+                web::resource("/events/{event_id}/retry")
+                    .route(web::post().to(retry_webhook_event_delivery)),
+            )
             .service(
                 web::resource("/{merchant_id}/{connector_id_or_name}")
                     .route(
@@ -781,7 +832,9 @@ impl Cache {
     pub fn server(state: AppState) -> Scope {
         web::scope("/cache")
             .app_data(web::Data::new(state))
+            .service(web::resource("/invalidate").route(web::post().to(invalidate_matching)))
             .service(web::resource("/invalidate/{key}").route(web::post().to(invalidate)))
+            .service(web::resource("/stats").route(web::get().to(retrieve_statistics)))
     }
 }
 
@@ -820,6 +873,10 @@ impl BusinessProfile {
                     .route(web::post().to(business_profile_update))
                     .route(web::delete().to(business_profile_delete)),
             )
+            .service(
+                web::resource("/{profile_id}/rotate_webhook_signing_key")
+                    .route(web::post().to(webhook_signing_key_rotate)),
+            )
     }
 }
 
@@ -837,6 +894,39 @@ impl Gsm {
     }
 }
 
+pub struct ProcessTracker;
+
+#[cfg(feature = "olap")]
+impl ProcessTracker {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/process_tracker")
+            .app_data(web::Data::new(state))
+            .service(web::resource("").route(web::get().to(list_processes)))
+            .service(web::resource("/{process_tracker_id}").route(web::get().to(retrieve_process)))
+            .service(
+                web::resource("/{process_tracker_id}/retry").route(web::post().to(retry_process)),
+            )
+            .service(
+                web::resource("/{process_tracker_id}/reschedule")
+                    .route(web::post().to(reschedule_process)),
+            )
+            .service(
+                web::resource("/{process_tracker_id}/finish").route(web::post().to(finish_process)),
+            )
+    }
+}
+
+pub struct AuditLogs;
+
+#[cfg(feature = "olap")]
+impl AuditLogs {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/audit_logs")
+            .app_data(web::Data::new(state))
+            .service(web::resource("/{merchant_id}").route(web::get().to(list_audit_logs)))
+    }
+}
+
 #[cfg(all(feature = "olap", feature = "kms"))]
 pub struct Verify;
 
@@ -865,6 +955,28 @@ impl User {
 
         route = route
             .service(web::resource("/signin").route(web::post().to(user_signin)))
+            .service(
+                web::resource("/2fa/signin")
+                    .route(web::post().to(user_signin_with_two_factor_auth)),
+            )
+            .service(web::resource("/2fa/totp/begin").route(web::get().to(begin_totp)))
+            .service(web::resource("/2fa/totp/verify").route(web::post().to(verify_totp)))
+            .service(
+                web::resource("/2fa/recovery_codes/generate")
+                    .route(web::post().to(generate_recovery_codes)),
+            )
+            .service(web::resource("/user/2fa/reset").route(web::post().to(reset_two_factor_auth)))
+            .service(
+                web::resource("/sso/oidc/config")
+                    .route(web::get().to(get_oidc_config))
+                    .route(web::post().to(set_oidc_config)),
+            )
+            .service(web::resource("/sso/oidc/authorize").route(web::get().to(oidc_authorize)))
+            .service(web::resource("/sso/oidc/callback").route(web::post().to(oidc_callback)))
+            .service(web::resource("/sso/oidc/link").route(web::get().to(oidc_link)))
+            .service(
+                web::resource("/sso/oidc/link/callback").route(web::post().to(oidc_link_callback)),
+            )
             .service(web::resource("/change_password").route(web::post().to(change_password)))
             .service(web::resource("/internal_signup").route(web::post().to(internal_user_signup)))
             .service(web::resource("/switch_merchant").route(web::post().to(switch_merchant_id)))
@@ -876,8 +988,14 @@ impl User {
             .service(web::resource("/user/list").route(web::get().to(get_user_details)))
             .service(web::resource("/permission_info").route(web::get().to(get_authorization_info)))
             .service(web::resource("/user/update_role").route(web::post().to(update_user_role)))
+            .service(web::resource("/role").route(web::post().to(create_role)))
             .service(web::resource("/role/list").route(web::get().to(list_roles)))
-            .service(web::resource("/role/{role_id}").route(web::get().to(get_role)))
+            .service(
+                web::resource("/role/{role_id}")
+                    .route(web::get().to(get_role))
+                    .route(web::put().to(update_role))
+                    .route(web::delete().to(delete_role)),
+            )
             .service(
                 web::resource("/data")
                     .route(web::get().to(get_multiple_dashboard_metadata))
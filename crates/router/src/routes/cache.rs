@@ -1,4 +1,5 @@
 use actix_web::{web, HttpRequest, Responder};
+use api_models::cache::CacheInvalidationRequest;
 use router_env::{instrument, tracing, Flow};
 
 use super::AppState;
@@ -28,3 +29,39 @@ pub async fn invalidate(
     )
     .await
 }
+
+#[instrument(skip_all)]
+pub async fn invalidate_matching(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<CacheInvalidationRequest>,
+) -> impl Responder {
+    let flow = Flow::CacheInvalidateMatching;
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, request| cache::invalidate_matching(state, request),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+#[instrument(skip_all)]
+pub async fn retrieve_statistics(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    let flow = Flow::CacheStatisticsRetrieve;
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |state, _, _| cache::retrieve_statistics(state),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
@@ -109,7 +109,7 @@ pub async fn customers_list(state: web::Data<AppState>, req: HttpRequest) -> Htt
         state,
         &req,
         (),
-        |state, auth, _| list_customers(state, auth.merchant_account.merchant_id, auth.key_store),
+        |state, auth, _| list_customers(state, auth.merchant_account, auth.key_store),
         &auth::ApiKeyAuth,
         api_locking::LockAction::NotApplicable,
     )
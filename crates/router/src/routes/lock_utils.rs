@@ -27,6 +27,8 @@ pub enum ApiIdentifier {
     Forex,
     RustLockerMigration,
     Gsm,
+    ProcessTracker,
+    AuditLogs,
     User,
     UserRole,
     ConnectorOnboarding,
@@ -39,7 +41,10 @@ impl From<Flow> for ApiIdentifier {
             | Flow::MerchantsAccountRetrieve
             | Flow::MerchantsAccountUpdate
             | Flow::MerchantsAccountDelete
-            | Flow::MerchantAccountList => Self::MerchantAccount,
+            | Flow::MerchantAccountList
+            | Flow::StorageSchemeMigrationStart
+            | Flow::StorageSchemeMigrationRetrieve
+            | Flow::StorageSchemeMigrationAbort => Self::MerchantAccount,
 
             Flow::RoutingCreateConfig
             | Flow::RoutingLinkConfig
@@ -51,6 +56,10 @@ impl From<Flow> for ApiIdentifier {
             | Flow::RoutingUpdateConfig
             | Flow::RoutingUpdateDefaultConfig
             | Flow::RoutingDeleteConfig
+            | Flow::RoutingSimulateConfig
+            | Flow::RoutingRetrieveActivationHistory
+            | Flow::RoutingDiffConfig
+            | Flow::RoutingRollbackConfig
             | Flow::DecisionManagerDeleteConfig
             | Flow::DecisionManagerRetrieveConfig
             | Flow::DecisionManagerUpsertConfig => Self::Routing,
@@ -61,7 +70,9 @@ impl From<Flow> for ApiIdentifier {
             | Flow::MerchantConnectorsRetrieve
             | Flow::MerchantConnectorsUpdate
             | Flow::MerchantConnectorsDelete
-            | Flow::MerchantConnectorsList => Self::MerchantConnector,
+            | Flow::MerchantConnectorsList
+            | Flow::MerchantConnectorsCircuitBreakerRetrieve
+            | Flow::MerchantConnectorsCircuitBreakerReset => Self::MerchantConnector,
 
             Flow::ConfigKeyCreate
             | Flow::ConfigKeyFetch
@@ -115,7 +126,11 @@ impl From<Flow> for ApiIdentifier {
             | Flow::RefundsUpdate
             | Flow::RefundsList => Self::Refunds,
 
-            Flow::FrmFulfillment | Flow::IncomingWebhookReceive => Self::Webhooks,
+            Flow::FrmFulfillment
+            | Flow::IncomingWebhookReceive
+            | Flow::WebhookEventsList
+            | Flow::WebhookEventDeliveryAttemptList
+            | Flow::WebhookEventDeliveryRetry => Self::Webhooks,
 
             Flow::ApiKeyCreate
             | Flow::ApiKeyRetrieve
@@ -133,13 +148,16 @@ impl From<Flow> for ApiIdentifier {
 
             Flow::CreateFile | Flow::DeleteFile | Flow::RetrieveFile => Self::Files,
 
-            Flow::CacheInvalidate => Self::Cache,
+            Flow::CacheInvalidate
+            | Flow::CacheInvalidateMatching
+            | Flow::CacheStatisticsRetrieve => Self::Cache,
 
             Flow::BusinessProfileCreate
             | Flow::BusinessProfileUpdate
             | Flow::BusinessProfileRetrieve
             | Flow::BusinessProfileDelete
-            | Flow::BusinessProfileList => Self::Business,
+            | Flow::BusinessProfileList
+            | Flow::WebhookSigningKeyRotate => Self::Business,
 
             Flow::PaymentLinkRetrieve | Flow::PaymentLinkInitiate | Flow::PaymentLinkList => {
                 Self::PaymentLink
@@ -153,6 +171,14 @@ impl From<Flow> for ApiIdentifier {
             | Flow::GsmRuleUpdate
             | Flow::GsmRuleDelete => Self::Gsm,
 
+            Flow::ProcessTrackerList
+            | Flow::ProcessTrackerRetrieve
+            | Flow::ProcessTrackerRetry
+            | Flow::ProcessTrackerReschedule
+            | Flow::ProcessTrackerFinish => Self::ProcessTracker,
+
+            Flow::AuditLogList => Self::AuditLogs,
+
             Flow::UserConnectAccount
             | Flow::UserSignUp
             | Flow::UserSignIn
@@ -172,11 +198,26 @@ impl From<Flow> for ApiIdentifier {
             | Flow::InviteUser
             | Flow::UserSignUpWithMerchantId
             | Flow::VerifyEmail
-            | Flow::VerifyEmailRequest => Self::User,
-
-            Flow::ListRoles | Flow::GetRole | Flow::UpdateUserRole | Flow::GetAuthorizationInfo => {
-                Self::UserRole
-            }
+            | Flow::VerifyEmailRequest
+            | Flow::TwoFactorAuthSignIn
+            | Flow::BeginTotp
+            | Flow::VerifyTotp
+            | Flow::GenerateRecoveryCodes
+            | Flow::ResetTwoFactorAuth
+            | Flow::SetOidcConfig
+            | Flow::GetOidcConfig
+            | Flow::OidcAuthorize
+            | Flow::OidcCallback
+            | Flow::OidcLink
+            | Flow::OidcLinkCallback => Self::User,
+
+            Flow::ListRoles
+            | Flow::GetRole
+            | Flow::UpdateUserRole
+            | Flow::GetAuthorizationInfo
+            | Flow::CreateRole
+            | Flow::UpdateRole
+            | Flow::DeleteRole => Self::UserRole,
 
             Flow::GetActionUrl | Flow::SyncOnboardingStatus => Self::ConnectorOnboarding,
         }
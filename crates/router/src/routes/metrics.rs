@@ -113,5 +113,9 @@ counter_metric!(AUTO_RETRY_GSM_MATCH_COUNT, GLOBAL_METER);
 counter_metric!(AUTO_RETRY_EXHAUSTED_COUNT, GLOBAL_METER);
 counter_metric!(AUTO_RETRY_PAYMENT_COUNT, GLOBAL_METER);
 
+// Metrics for the connector circuit breaker
+counter_metric!(CIRCUIT_BREAKER_TRANSITION_COUNT, GLOBAL_METER);
+counter_metric!(CIRCUIT_BREAKER_FILTERED_CONNECTOR_COUNT, GLOBAL_METER);
+
 pub mod request;
 pub mod utils;
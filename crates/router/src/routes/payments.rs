@@ -101,16 +101,12 @@ pub async fn payments_create(
     json_payload: web::Json<payment_types::PaymentsRequest>,
 ) -> impl Responder {
     let flow = Flow::PaymentsCreate;
-    let mut payload = json_payload.into_inner();
+    let payload = json_payload.into_inner();
 
     if let Some(api_enums::CaptureMethod::Scheduled) = payload.capture_method {
         return http_not_implemented();
     };
 
-    if let Err(err) = get_or_generate_payment_id(&mut payload) {
-        return api::log_and_return_error_response(err);
-    }
-
     let locking_action = payload.get_locking_input(flow.clone());
 
     Box::pin(api::server_wrap(
@@ -118,7 +114,9 @@ pub async fn payments_create(
         state,
         &req,
         payload,
-        |state, auth, req| {
+        // The payment ID is generated once the request has been fingerprinted for idempotency
+        |state, auth, mut req| async move {
+            get_or_generate_payment_id(&mut req)?;
             authorize_verify_select::<_, Oss>(
                 payments::PaymentCreate,
                 state,
@@ -128,6 +126,7 @@ pub async fn payments_create(
                 req,
                 api::AuthFlow::Merchant,
             )
+            .await
         },
         match env::which() {
             env::Env::Production => &auth::ApiKeyAuth,
@@ -0,0 +1,126 @@
+use actix_web::{web, HttpRequest, Responder};
+use api_models::process_tracker::{
+    ProcessTrackerFinishRequest, ProcessTrackerId, ProcessTrackerListConstraints,
+    ProcessTrackerRescheduleRequest,
+};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::{api_locking, process_tracker},
+    services::{api, authentication as auth},
+};
+
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerList))]
+pub async fn list_processes(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<ProcessTrackerListConstraints>,
+) -> impl Responder {
+    let flow = Flow::ProcessTrackerList;
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        query.into_inner(),
+        |state, _, constraints| process_tracker::list_processes(state, constraints),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerRetrieve))]
+pub async fn retrieve_process(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::ProcessTrackerRetrieve;
+    let process_tracker_id = ProcessTrackerId {
+        process_tracker_id: path.into_inner(),
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        process_tracker_id,
+        |state, _, req| process_tracker::retrieve_process(state, req.process_tracker_id),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerRetry))]
+pub async fn retry_process(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::ProcessTrackerRetry;
+    let process_tracker_id = ProcessTrackerId {
+        process_tracker_id: path.into_inner(),
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        process_tracker_id,
+        |state, _, req| process_tracker::retry_process(state, req.process_tracker_id),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerReschedule))]
+pub async fn reschedule_process(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<ProcessTrackerRescheduleRequest>,
+) -> impl Responder {
+    let flow = Flow::ProcessTrackerReschedule;
+    let mut payload = json_payload.into_inner();
+    payload.process_tracker_id = path.into_inner();
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, req| process_tracker::reschedule_process(state, req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::ProcessTrackerFinish))]
+pub async fn finish_process(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: Option<web::Json<ProcessTrackerFinishRequest>>,
+) -> impl Responder {
+    let flow = Flow::ProcessTrackerFinish;
+    let mut payload = json_payload
+        .map(|json_payload| json_payload.into_inner())
+        .unwrap_or_default();
+    payload.process_tracker_id = path.into_inner();
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, req| process_tracker::finish_process(state, req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
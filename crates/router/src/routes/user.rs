@@ -389,3 +389,193 @@ pub async fn verify_email_request(
     ))
     .await
 }
+
+pub async fn user_signin_with_two_factor_auth(
+    state: web::Data<AppState>,
+    http_req: HttpRequest,
+    json_payload: web::Json<user_api::TwoFactorAuthSignInRequest>,
+) -> HttpResponse {
+    let flow = Flow::TwoFactorAuthSignIn;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &http_req,
+        json_payload.into_inner(),
+        |state, _, req_body| {
+            user_core::two_factor_auth::signin_with_two_factor_auth(state, req_body)
+        },
+        &auth::NoAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+pub async fn begin_totp(state: web::Data<AppState>, req: HttpRequest) -> HttpResponse {
+    let flow = Flow::BeginTotp;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |state, user, _| user_core::two_factor_auth::begin_totp(state, user),
+        &auth::DashboardNoPermissionAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+pub async fn verify_totp(
+    state: web::Data<AppState>,
+    http_req: HttpRequest,
+    json_payload: web::Json<user_api::VerifyTotpRequest>,
+) -> HttpResponse {
+    let flow = Flow::VerifyTotp;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &http_req,
+        json_payload.into_inner(),
+        |state, user, req_body| user_core::two_factor_auth::verify_totp(state, user, req_body),
+        &auth::DashboardNoPermissionAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+pub async fn generate_recovery_codes(state: web::Data<AppState>, req: HttpRequest) -> HttpResponse {
+    let flow = Flow::GenerateRecoveryCodes;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |state, user, _| user_core::two_factor_auth::generate_recovery_codes(state, user),
+        &auth::DashboardNoPermissionAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+pub async fn reset_two_factor_auth(
+    state: web::Data<AppState>,
+    http_req: HttpRequest,
+    json_payload: web::Json<user_api::ResetTwoFactorAuthRequest>,
+) -> HttpResponse {
+    let flow = Flow::ResetTwoFactorAuth;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &http_req,
+        json_payload.into_inner(),
+        |state, user, req_body| {
+            user_core::two_factor_auth::reset_two_factor_auth(state, user, req_body)
+        },
+        &auth::JWTAuth(Permission::UsersWrite),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+pub async fn set_oidc_config(
+    state: web::Data<AppState>,
+    http_req: HttpRequest,
+    json_payload: web::Json<user_api::sso::SetOidcConfigRequest>,
+) -> HttpResponse {
+    let flow = Flow::SetOidcConfig;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &http_req,
+        json_payload.into_inner(),
+        |state, user, req_body| user_core::sso::set_oidc_config(state, user, req_body),
+        &auth::JWTAuth(Permission::UsersWrite),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+pub async fn get_oidc_config(state: web::Data<AppState>, req: HttpRequest) -> HttpResponse {
+    let flow = Flow::GetOidcConfig;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |state, user, _| user_core::sso::get_oidc_config(state, user),
+        &auth::JWTAuth(Permission::UsersRead),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+pub async fn oidc_authorize(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<user_api::sso::OidcAuthorizeRequest>,
+) -> HttpResponse {
+    let flow = Flow::OidcAuthorize;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        query.into_inner(),
+        |state, _, req_body| user_core::sso::authorize_with_oidc(state, req_body),
+        &auth::NoAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+pub async fn oidc_callback(
+    state: web::Data<AppState>,
+    http_req: HttpRequest,
+    json_payload: web::Json<user_api::sso::OidcCallbackRequest>,
+) -> HttpResponse {
+    let flow = Flow::OidcCallback;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &http_req,
+        json_payload.into_inner(),
+        |state, _, req_body| user_core::sso::oidc_callback(state, req_body),
+        &auth::NoAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+pub async fn oidc_link(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<user_api::sso::OidcAuthorizeRequest>,
+) -> HttpResponse {
+    let flow = Flow::OidcLink;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        query.into_inner(),
+        |state, user, req_body| user_core::sso::link_with_oidc(state, user, req_body),
+        &auth::DashboardNoPermissionAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+pub async fn oidc_link_callback(
+    state: web::Data<AppState>,
+    http_req: HttpRequest,
+    json_payload: web::Json<user_api::sso::OidcCallbackRequest>,
+) -> HttpResponse {
+    let flow = Flow::OidcLinkCallback;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &http_req,
+        json_payload.into_inner(),
+        |state, user, req_body| user_core::sso::oidc_link_callback(state, user, req_body),
+        &auth::DashboardNoPermissionAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
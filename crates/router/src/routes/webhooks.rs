@@ -1,4 +1,5 @@
 use actix_web::{web, HttpRequest, Responder};
+use api_models::webhook_events::{EventId, EventListConstraints};
 use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
@@ -6,9 +7,9 @@ use crate::{
     core::{
         api_locking,
         payment_methods::Oss,
-        webhooks::{self, types},
+        webhooks::{self, types, webhook_events},
     },
-    services::{api, authentication as auth},
+    services::{api, authentication as auth, authorization::permissions::Permission},
 };
 
 #[instrument(skip_all, fields(flow = ?Flow::IncomingWebhookReceive))]
@@ -43,6 +44,95 @@ pub async fn receive_incoming_webhook<W: types::OutgoingWebhookType>(
     .await
 }
 
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEventsList))]
+pub async fn list_webhook_events(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<EventListConstraints>,
+) -> impl Responder {
+    let flow = Flow::WebhookEventsList;
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        query.into_inner(),
+        |state, auth, constraints| {
+            webhook_events::list_events(state, auth.merchant_account, constraints)
+        },
+        auth::auth_type(
+            &auth::ApiKeyAuth,
+            &auth::JWTAuth(Permission::MerchantAccountRead),
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEventDeliveryAttemptList))]
+pub async fn list_webhook_event_delivery_attempts(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::WebhookEventDeliveryAttemptList;
+    let event_id = EventId {
+        event_id: path.into_inner(),
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        event_id,
+        |state, auth, req| {
+            webhook_events::list_delivery_attempts(state, auth.merchant_account, req.event_id)
+        },
+        auth::auth_type(
+            &auth::ApiKeyAuth,
+            &auth::JWTAuth(Permission::MerchantAccountRead),
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::WebhookEventDeliveryRetry))]
+pub async fn retry_webhook_event_delivery(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::WebhookEventDeliveryRetry;
+    let event_id = EventId {
+        event_id: path.into_inner(),
+    };
+
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        event_id,
+        |state, auth, req| {
+            webhook_events::retry_delivery(
+                state,
+                auth.merchant_account,
+                auth.key_store,
+                req.event_id,
+            )
+        },
+        auth::auth_type(
+            &auth::ApiKeyAuth,
+            &auth::JWTAuth(Permission::MerchantAccountWrite),
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
 #[derive(Debug)]
 struct WebhookBytes(web::Bytes);
 
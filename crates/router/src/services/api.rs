@@ -32,9 +32,10 @@ use crate::{
     configs::settings::{Connectors, Settings},
     consts,
     core::{
-        api_locking,
+        api_locking, audit_log,
         errors::{self, CustomResult},
-        payments,
+        idempotency,
+        payments::{self, routing::circuit_breaker},
     },
     events::{
         api_logs::{ApiEvent, ApiEventMetric, ApiEventsType},
@@ -372,6 +373,11 @@ where
                     let response = call_connector_api(state, request).await;
                     let external_latency = current_time.elapsed().as_millis();
                     logger::debug!(connector_response=?response);
+                    circuit_breaker::record_connector_response(
+                        state,
+                        req.merchant_connector_id.as_ref(),
+                        &response,
+                    );
 
                     let connector_event = ConnectorEvent::new(
                         req.connector.clone(),
@@ -726,6 +732,8 @@ pub enum ApplicationResponse<R> {
     PaymenkLinkForm(Box<PaymentLinkFormData>),
     FileData((Vec<u8>, mime::Mime)),
     JsonWithHeaders((R, Vec<(String, String)>)),
+    /// The response stored for an idempotent request, replayed to its retry
+    Replay(idempotency::StoredResponse),
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
@@ -815,6 +823,7 @@ pub async fn server_wrap_util<'a, 'b, A, U, T, Q, F, Fut, E, OErr>(
     func: F,
     api_auth: &dyn AuthenticateAndFetch<U, A>,
     lock_action: api_locking::LockAction,
+    idempotency_guard: &mut Option<idempotency::IdempotencyGuard>,
 ) -> CustomResult<ApplicationResponse<Q>, OErr>
 where
     F: Fn(A, U, T) -> Fut,
@@ -861,21 +870,73 @@ where
 
     tracing::Span::current().record("merchant_id", &merchant_id);
 
-    let output = {
+    let idempotency_input =
+        idempotency::IdempotencyInput::from_request(flow, request, &merchant_id, &payload)
+            .switch()?;
+
+    let audit_log_input = match audit_log::AuditLogInput::from_request(
+        flow,
+        request,
+        &auth_type,
+        &payload,
+        &serialized_request,
+    ) {
+        Some(audit_log_input) => Some(audit_log_input.with_snapshot_before(&request_state).await),
+        None => None,
+    };
+
+    let idempotency_status = match idempotency_input {
+        Some(idempotency_input) => Some(idempotency_input.acquire(&request_state).await.switch()?),
+        None => None,
+    };
+
+    let lock = || async {
         lock_action
             .clone()
             .perform_locking_action(&request_state, merchant_id.to_owned())
             .await
-            .switch()?;
+            .switch()
+    };
+    let api_call = || async {
         let res = func(request_state.clone(), auth_out, payload)
             .await
-            .switch();
+            .switch()
+            .map_err(|error| {
+                if is_kv_writes_paused(&error) {
+                    error.change_context(errors::ApiErrorResponse::ServiceUnavailable.switch())
+                } else {
+                    error
+                }
+            });
         lock_action
             .free_lock_action(&request_state, merchant_id.to_owned())
             .await
             .switch()?;
         res
     };
+    let output = match idempotency_status {
+        Some(idempotency::IdempotencyStatus::Replay(response)) => {
+            logger::info!("Replaying the stored response of the idempotent request");
+            Ok(ApplicationResponse::Replay(response))
+        }
+        Some(idempotency::IdempotencyStatus::Acquired(guard)) => match lock().await {
+            Ok(()) => {
+                // The response is stored once rendered by the caller
+                *idempotency_guard = Some(guard);
+                api_call().await
+            }
+            // The request has not been processed and can be retried
+            Err(error) => {
+                guard.release(&request_state).await;
+                Err(error)
+            }
+        },
+        None => match lock().await {
+            Ok(()) => api_call().await,
+            Err(error) => Err(error),
+        },
+    };
+    let is_replay = matches!(output, Ok(ApplicationResponse::Replay(_)));
     let request_duration = Instant::now()
         .saturating_duration_since(start_instant)
         .as_millis();
@@ -924,6 +985,12 @@ where
         }
     };
 
+    if let (Some(audit_log_input), Ok(_), false) = (audit_log_input, output.as_ref(), is_replay) {
+        audit_log_input
+            .record(&request_state, serialized_response.clone())
+            .await;
+    }
+
     let api_event = ApiEvent::new(
         Some(merchant_id.clone()),
         flow,
@@ -953,6 +1020,17 @@ where
     output
 }
 
+/// Whether the request failed on a write rejected while the KV writes of the merchant are paused
+/// by a storage scheme migration, in which case it can be retried once the writes are resumed
+fn is_kv_writes_paused<E>(error: &Report<E>) -> bool {
+    error.frames().any(|frame| {
+        matches!(
+            frame.downcast_ref::<errors::RedisError>(),
+            Some(errors::RedisError::KvWritesPaused)
+        )
+    })
+}
+
 #[instrument(
     skip(request, state, func, api_auth, payload),
     fields(request_method, request_url_path)
@@ -983,6 +1061,7 @@ where
     let start_instant = Instant::now();
     logger::info!(tag = ?Tag::BeginRequest, payload = ?payload);
 
+    let mut idempotency_guard = None;
     let res = match metrics::request::record_request_time_metric(
         server_wrap_util(
             &flow,
@@ -992,6 +1071,7 @@ where
             func,
             api_auth,
             lock_action,
+            &mut idempotency_guard,
         ),
         &flow,
     )
@@ -1015,6 +1095,7 @@ where
         Ok(ApplicationResponse::FileData((file_data, content_type))) => {
             http_response_file_data(file_data, content_type)
         }
+        Ok(ApplicationResponse::Replay(response)) => response.into_http_response(),
         Ok(ApplicationResponse::JsonForRedirection(response)) => {
             match serde_json::to_string(&response) {
                 Ok(res) => http_redirect_response(res, response),
@@ -1074,6 +1155,10 @@ where
         }
         Err(error) => log_and_return_error_response(error),
     };
+    let res = match idempotency_guard {
+        Some(idempotency_guard) => idempotency_guard.record(state.get_ref(), res).await,
+        None => res,
+    };
 
     let response_code = res.status().as_u16();
     let end_instant = Instant::now();
@@ -1610,8 +1695,23 @@ fn get_hyper_loader_sdk(sdk_url: &str) -> String {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_mime_essence() {
         assert_eq!(mime::APPLICATION_JSON.essence_str(), "application/json");
     }
+
+    #[test]
+    fn test_is_kv_writes_paused() {
+        let paused = report!(errors::RedisError::KvWritesPaused)
+            .change_context(errors::StorageError::KVError)
+            .change_context(errors::ApiErrorResponse::InternalServerError);
+        assert!(is_kv_writes_paused(&paused));
+
+        let failed = report!(errors::RedisError::SetNxFailed)
+            .change_context(errors::StorageError::KVError)
+            .change_context(errors::ApiErrorResponse::InternalServerError);
+        assert!(!is_kv_writes_paused(&failed));
+    }
 }
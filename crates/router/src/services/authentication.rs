@@ -89,6 +89,10 @@ pub struct AuthToken {
     pub role_id: String,
     pub exp: u64,
     pub org_id: String,
+    /// Set on the tokens issued through the single sign-on of the organization, which cannot be
+    /// used to switch to the merchants of other organizations
+    #[serde(default)]
+    pub org_scoped: bool,
 }
 
 #[cfg(feature = "olap")]
@@ -99,6 +103,7 @@ impl AuthToken {
         role_id: String,
         settings: &settings::Settings,
         org_id: String,
+        org_scoped: bool,
     ) -> UserResult<String> {
         let exp_duration = std::time::Duration::from_secs(consts::JWT_TOKEN_TIME_IN_SECS);
         let exp = jwt::generate_exp(exp_duration)?.as_secs();
@@ -108,6 +113,54 @@ impl AuthToken {
             role_id,
             exp,
             org_id,
+            org_scoped,
+        };
+        jwt::generate_jwt(&token_payload, settings).await
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenPurpose {
+    TwoFactorAuth,
+}
+
+/// Issued once the password of a user having two factor authentication has been verified, it
+/// cannot be used as an [`AuthToken`]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct TwoFactorAuthToken {
+    pub user_id: String,
+    pub purpose: TokenPurpose,
+    pub exp: u64,
+    /// Set when the user signs in through the single sign-on of an organization, the token
+    /// issued once two factor authentication succeeds being scoped to it
+    #[serde(default)]
+    pub sso_role: Option<SsoRole>,
+}
+
+/// The role given to a user signing in through the single sign-on of an organization
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct SsoRole {
+    pub org_id: String,
+    pub merchant_id: String,
+    pub role_id: String,
+}
+
+#[cfg(feature = "olap")]
+impl TwoFactorAuthToken {
+    pub async fn new_token(
+        user_id: String,
+        sso_role: Option<SsoRole>,
+        settings: &settings::Settings,
+    ) -> UserResult<String> {
+        let exp_duration =
+            std::time::Duration::from_secs(consts::user::TWO_FACTOR_AUTH_TOKEN_TIME_IN_SECS);
+        let exp = jwt::generate_exp(exp_duration)?.as_secs();
+        let token_payload = Self {
+            user_id,
+            purpose: TokenPurpose::TwoFactorAuth,
+            exp,
+            sso_role,
         };
         jwt::generate_jwt(&token_payload, settings).await
     }
@@ -119,6 +172,7 @@ pub struct UserFromToken {
     pub merchant_id: String,
     pub role_id: String,
     pub org_id: String,
+    pub org_scoped: bool,
 }
 
 pub trait AuthInfo {
@@ -418,8 +472,9 @@ where
     ) -> RouterResult<((), AuthenticationType)> {
         let payload = parse_jwt_payload::<A, AuthToken>(request_headers, state).await?;
 
-        let permissions = authorization::get_permissions(&payload.role_id)?;
-        authorization::check_authorization(&self.0, permissions)?;
+        let permissions =
+            authorization::get_permissions(state, &payload.role_id, &payload.merchant_id).await?;
+        authorization::check_authorization(&self.0, &permissions)?;
 
         Ok((
             (),
@@ -444,8 +499,9 @@ where
     ) -> RouterResult<(UserFromToken, AuthenticationType)> {
         let payload = parse_jwt_payload::<A, AuthToken>(request_headers, state).await?;
 
-        let permissions = authorization::get_permissions(&payload.role_id)?;
-        authorization::check_authorization(&self.0, permissions)?;
+        let permissions =
+            authorization::get_permissions(state, &payload.role_id, &payload.merchant_id).await?;
+        authorization::check_authorization(&self.0, &permissions)?;
 
         Ok((
             UserFromToken {
@@ -453,6 +509,7 @@ where
                 merchant_id: payload.merchant_id.clone(),
                 org_id: payload.org_id,
                 role_id: payload.role_id,
+                org_scoped: payload.org_scoped,
             },
             AuthenticationType::MerchantJwt {
                 merchant_id: payload.merchant_id,
@@ -479,8 +536,9 @@ where
     ) -> RouterResult<((), AuthenticationType)> {
         let payload = parse_jwt_payload::<A, AuthToken>(request_headers, state).await?;
 
-        let permissions = authorization::get_permissions(&payload.role_id)?;
-        authorization::check_authorization(&self.required_permission, permissions)?;
+        let permissions =
+            authorization::get_permissions(state, &payload.role_id, &payload.merchant_id).await?;
+        authorization::check_authorization(&self.required_permission, &permissions)?;
 
         // Check if token has access to MerchantId that has been requested through query param
         if payload.merchant_id != self.merchant_id {
@@ -509,6 +567,7 @@ where
 
 #[derive(serde::Deserialize)]
 struct JwtAuthPayloadFetchMerchantAccount {
+    user_id: String,
     merchant_id: String,
     role_id: String,
 }
@@ -527,8 +586,9 @@ where
             parse_jwt_payload::<A, JwtAuthPayloadFetchMerchantAccount>(request_headers, state)
                 .await?;
 
-        let permissions = authorization::get_permissions(&payload.role_id)?;
-        authorization::check_authorization(&self.0, permissions)?;
+        let permissions =
+            authorization::get_permissions(state, &payload.role_id, &payload.merchant_id).await?;
+        authorization::check_authorization(&self.0, &permissions)?;
 
         let key_store = state
             .store()
@@ -554,7 +614,7 @@ where
             auth.clone(),
             AuthenticationType::MerchantJwt {
                 merchant_id: auth.merchant_account.merchant_id.clone(),
-                user_id: None,
+                user_id: Some(payload.user_id),
             },
         ))
     }
@@ -581,6 +641,7 @@ where
                 merchant_id: payload.merchant_id.clone(),
                 org_id: payload.org_id,
                 role_id: payload.role_id,
+                org_scoped: payload.org_scoped,
             },
             AuthenticationType::MerchantJwt {
                 merchant_id: payload.merchant_id,
@@ -1,14 +1,37 @@
-use crate::core::errors::{ApiErrorResponse, RouterResult};
+use error_stack::{IntoReport, ResultExt};
+
+use crate::{
+    core::errors::{ApiErrorResponse, RouterResult, StorageErrorExt},
+    routes::app::AppStateInfo,
+};
 
 pub mod info;
 pub mod permissions;
 pub mod predefined_permissions;
 
-pub fn get_permissions(role: &str) -> RouterResult<&Vec<permissions::Permission>> {
-    predefined_permissions::PREDEFINED_PERMISSIONS
-        .get(role)
-        .map(|role_info| role_info.get_permissions())
-        .ok_or(ApiErrorResponse::InvalidJwtToken.into())
+/// Returns the permissions of a predefined role, or of a custom role of the merchant
+pub async fn get_permissions<A>(
+    state: &A,
+    role_id: &str,
+    merchant_id: &str,
+) -> RouterResult<Vec<permissions::Permission>>
+where
+    A: AppStateInfo + Sync,
+{
+    if let Some(role_info) = predefined_permissions::PREDEFINED_PERMISSIONS.get(role_id) {
+        return Ok(role_info.get_permissions().clone());
+    }
+
+    // Custom roles are cached, and invalidated through pub-sub whenever updated or deleted
+    let role = state
+        .store()
+        .find_role_by_role_id_merchant_id(role_id, merchant_id)
+        .await
+        .to_not_found_response(ApiErrorResponse::InvalidJwtToken)?;
+    permissions::parse_permissions(&role.permissions)
+        .into_report()
+        .change_context(ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to parse the permissions of the role")
 }
 
 pub fn check_authorization(
@@ -1,6 +1,6 @@
-use strum::Display;
+use strum::{Display, EnumString};
 
-#[derive(PartialEq, Display, Clone, Debug)]
+#[derive(PartialEq, Display, EnumString, Clone, Debug)]
 pub enum Permission {
     PaymentRead,
     PaymentWrite,
@@ -72,3 +72,11 @@ impl Permission {
         }
     }
 }
+
+/// Parses the permissions of a custom role, as they are stored against the role
+pub fn parse_permissions(permissions: &[String]) -> Result<Vec<Permission>, strum::ParseError> {
+    permissions
+        .iter()
+        .map(|permission| permission.parse())
+        .collect()
+}
@@ -310,6 +310,8 @@ pub struct RouterData<Flow, Request, Response> {
     pub apple_pay_flow: Option<storage_enums::ApplePayFlow>,
 
     pub frm_metadata: Option<serde_json::Value>,
+    /// The merchant connector account the connector is called with, used to track its health
+    pub merchant_connector_id: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -1299,6 +1301,7 @@ impl<F1, F2, T1, T2> From<(&RouterData<F1, T1, PaymentsResponseData>, T2)>
             external_latency: data.external_latency,
             apple_pay_flow: data.apple_pay_flow.clone(),
             frm_metadata: data.frm_metadata.clone(),
+            merchant_connector_id: data.merchant_connector_id.clone(),
         }
     }
 }
@@ -1355,6 +1358,7 @@ impl<F1, F2>
             external_latency: data.external_latency,
             apple_pay_flow: None,
             frm_metadata: None,
+            merchant_connector_id: data.merchant_connector_id.clone(),
         }
     }
 }
@@ -5,7 +5,7 @@ pub use api_models::{
     routing::{
         ConnectorVolumeSplit, DetailedConnectorChoice, RoutableConnectorChoice, RoutingAlgorithm,
         RoutingAlgorithmKind, RoutingAlgorithmRef, RoutingConfigRequest, RoutingDictionary,
-        RoutingDictionaryRecord, StraightThroughAlgorithm,
+        RoutingDictionaryRecord, StraightThroughAlgorithm, SuccessRateBasedAlgorithm,
     },
 };
 
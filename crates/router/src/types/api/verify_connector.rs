@@ -102,6 +102,7 @@ impl VerifyConnectorData {
             external_latency: None,
             apple_pay_flow: None,
             frm_metadata: None,
+            merchant_connector_id: None,
         }
     }
 }
@@ -7,6 +7,7 @@ mod merchant_key_store;
 pub mod types;
 #[cfg(feature = "olap")]
 pub mod user;
+mod user_key_store;
 
 pub use address::*;
 pub use customer::*;
@@ -15,3 +16,4 @@ pub use merchant_connector_account::*;
 pub use merchant_key_store::*;
 #[cfg(feature = "olap")]
 pub use user::*;
+pub use user_key_store::*;
@@ -5,7 +5,8 @@ use api_models::{
 };
 use common_utils::pii;
 use diesel_models::{
-    enums::UserStatus,
+    enums::{TotpStatus, UserStatus},
+    oidc_config::OidcConfig,
     organization as diesel_org,
     organization::Organization,
     user as storage_user,
@@ -26,10 +27,14 @@ use crate::{
     db::StorageInterface,
     routes::AppState,
     services::{
+        self,
         authentication::UserFromToken,
         authorization::{info, predefined_permissions},
     },
-    types::transformers::ForeignFrom,
+    types::{
+        domain::{types as domain_types, UserKeyStore},
+        transformers::ForeignFrom,
+    },
     utils::user::password,
 };
 
@@ -268,6 +273,17 @@ impl From<InviteeUserRequestWithInvitedUserToken> for NewUserOrganization {
     }
 }
 
+/// A user signing in through the identity provider of their organization for the first time
+pub type NewSsoUser = (UserEmail, UserName, OidcConfig);
+impl From<NewSsoUser> for NewUserOrganization {
+    fn from(value: NewSsoUser) -> Self {
+        Self(diesel_org::OrganizationNew {
+            org_id: value.2.org_id,
+            org_name: None,
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct MerchantId(String);
 
@@ -442,6 +458,19 @@ impl TryFrom<InviteeUserRequestWithInvitedUserToken> for NewUserMerchant {
     }
 }
 
+impl TryFrom<NewSsoUser> for NewUserMerchant {
+    type Error = error_stack::Report<UserErrors>;
+    fn try_from(value: NewSsoUser) -> UserResult<Self> {
+        let merchant_id = MerchantId::new(value.2.merchant_id.clone())?;
+        let new_organization = NewUserOrganization::from(value);
+        Ok(Self {
+            company_name: None,
+            merchant_id,
+            new_organization,
+        })
+    }
+}
+
 type UserMerchantCreateRequestWithToken =
     (UserFromStorage, user_api::UserMerchantCreate, UserFromToken);
 
@@ -699,6 +728,27 @@ impl TryFrom<InviteeUserRequestWithInvitedUserToken> for NewUser {
     }
 }
 
+impl TryFrom<NewSsoUser> for NewUser {
+    type Error = error_stack::Report<UserErrors>;
+    fn try_from(value: NewSsoUser) -> UserResult<Self> {
+        let user_id = uuid::Uuid::new_v4().to_string();
+        let email = value.0.clone();
+        let name = value.1.clone();
+        // The user signs in through the identity provider, the password is never to be used
+        let password = password::generate_password_hash(uuid::Uuid::new_v4().to_string().into())?;
+        let password = UserPassword::new(password)?;
+        let new_merchant = NewUserMerchant::try_from(value)?;
+
+        Ok(Self {
+            user_id,
+            name,
+            email,
+            password,
+            new_merchant,
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct UserFromStorage(pub storage_user::User);
 
@@ -737,6 +787,75 @@ impl UserFromStorage {
             .change_context(UserErrors::InternalServerError)
     }
 
+    pub fn is_two_factor_auth_setup(&self) -> bool {
+        self.0.totp_status == TotpStatus::Set
+    }
+
+    pub fn get_recovery_codes(&self) -> Option<Vec<Secret<String>>> {
+        self.0.totp_recovery_codes.clone()
+    }
+
+    pub fn get_totp_last_used_step(&self) -> Option<i64> {
+        self.0.totp_last_used_step
+    }
+
+    pub async fn get_or_create_key_store(&self, state: &AppState) -> UserResult<UserKeyStore> {
+        let master_key = state.store.get_master_key();
+        let key_store_result = state
+            .store
+            .get_user_key_store_by_user_id(self.get_user_id(), &master_key.to_vec().into())
+            .await;
+
+        match key_store_result {
+            Ok(key_store) => Ok(key_store),
+            Err(e) if e.current_context().is_db_not_found() => {
+                let key = services::generate_aes256_key()
+                    .change_context(UserErrors::InternalServerError)
+                    .attach_printable("Unable to generate aes 256 key")?;
+
+                let key_store = UserKeyStore {
+                    user_id: self.get_user_id().to_string(),
+                    key: domain_types::encrypt(key.to_vec().into(), master_key)
+                        .await
+                        .change_context(UserErrors::InternalServerError)?,
+                    created_at: common_utils::date_time::now(),
+                };
+                state
+                    .store
+                    .insert_user_key_store(key_store, &master_key.to_vec().into())
+                    .await
+                    .change_context(UserErrors::InternalServerError)
+            }
+            Err(e) => Err(e.change_context(UserErrors::InternalServerError)),
+        }
+    }
+
+    pub async fn decrypt_totp_secret(
+        &self,
+        state: &AppState,
+    ) -> UserResult<Option<Secret<Vec<u8>>>> {
+        if self.0.totp_secret.is_none() {
+            return Ok(None);
+        }
+
+        let key_store = state
+            .store
+            .get_user_key_store_by_user_id(
+                self.get_user_id(),
+                &state.store.get_master_key().to_vec().into(),
+            )
+            .await
+            .change_context(UserErrors::InternalServerError)?;
+
+        Ok(domain_types::decrypt::<Vec<u8>, masking::WithType>(
+            self.0.totp_secret.clone(),
+            key_store.key.get_inner().peek(),
+        )
+        .await
+        .change_context(UserErrors::InternalServerError)?
+        .map(common_utils::crypto::Encryptable::into_inner))
+    }
+
     #[cfg(feature = "email")]
     pub fn get_verification_days_left(&self, state: AppState) -> UserResult<Option<i64>> {
         if self.0.is_verified {
@@ -807,7 +926,8 @@ impl TryFrom<info::PermissionInfo> for user_role_api::PermissionInfo {
     }
 }
 
-pub struct UserAndRoleJoined(pub storage_user::User, pub UserRole);
+/// The user and their role, along with the name of the role when it is a custom role
+pub struct UserAndRoleJoined(pub storage_user::User, pub UserRole, pub Option<String>);
 
 impl TryFrom<UserAndRoleJoined> for user_api::UserDetails {
     type Error = ();
@@ -819,8 +939,9 @@ impl TryFrom<UserAndRoleJoined> for user_api::UserDetails {
 
         let role_id = user_and_role.1.role_id;
         let role_name = predefined_permissions::get_role_name_from_id(role_id.as_str())
-            .ok_or(())?
-            .to_string();
+            .map(ToString::to_string)
+            .or(user_and_role.2)
+            .ok_or(())?;
 
         Ok(Self {
             user_id: user_and_role.0.user_id,
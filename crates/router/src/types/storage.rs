@@ -1,5 +1,6 @@
 pub mod address;
 pub mod api_keys;
+pub mod audit_log;
 pub mod authorization;
 pub mod business_profile;
 pub mod capture;
@@ -14,6 +15,7 @@ pub mod events;
 pub mod file;
 pub mod fraud_check;
 pub mod gsm;
+pub mod idempotency_key;
 #[cfg(feature = "kv_store")]
 pub mod kv;
 pub mod locker_mock_up;
@@ -29,8 +31,10 @@ pub mod payouts;
 pub mod refund;
 pub mod reverse_lookup;
 pub mod routing_algorithm;
+pub mod routing_algorithm_activation;
 pub mod user;
 pub mod user_role;
+pub mod webhook_delivery_attempt;
 
 use std::collections::HashMap;
 
@@ -39,15 +43,19 @@ pub use data_models::payments::{
     payment_intent::{PaymentIntentNew, PaymentIntentUpdate},
     PaymentIntent,
 };
-pub use diesel_models::{ProcessTracker, ProcessTrackerNew, ProcessTrackerUpdate};
+pub use diesel_models::{
+    ProcessTracker, ProcessTrackerListConstraints, ProcessTrackerNew, ProcessTrackerUpdate,
+};
 pub use scheduler::db::process_tracker;
 
 pub use self::{
-    address::*, api_keys::*, authorization::*, capture::*, cards_info::*, configs::*, customers::*,
-    dashboard_metadata::*, dispute::*, ephemeral_key::*, events::*, file::*, fraud_check::*,
-    gsm::*, locker_mock_up::*, mandate::*, merchant_account::*, merchant_connector_account::*,
-    merchant_key_store::*, payment_link::*, payment_method::*, payout_attempt::*, payouts::*,
-    process_tracker::*, refund::*, reverse_lookup::*, routing_algorithm::*, user::*, user_role::*,
+    address::*, api_keys::*, audit_log::*, authorization::*, capture::*, cards_info::*, configs::*,
+    customers::*, dashboard_metadata::*, dispute::*, ephemeral_key::*, events::*, file::*,
+    fraud_check::*, gsm::*, idempotency_key::*, locker_mock_up::*, mandate::*, merchant_account::*,
+    merchant_connector_account::*, merchant_key_store::*, payment_link::*, payment_method::*,
+    payout_attempt::*, payouts::*, process_tracker::*, refund::*, reverse_lookup::*,
+    routing_algorithm::*, routing_algorithm_activation::*, user::*, user_role::*,
+    webhook_delivery_attempt::*,
 };
 use crate::types::api::routing;
 
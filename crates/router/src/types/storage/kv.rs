@@ -1,4 +1,5 @@
 pub use diesel_models::kv::{
-    AddressUpdateMems, DBOperation, Insertable, PaymentAttemptUpdateMems, PaymentIntentUpdateMems,
-    RefundUpdateMems, TypedSql, Updateable,
+    AddressUpdateMems, CustomerUpdateMems, DBOperation, Insertable, MandateUpdateMems,
+    PaymentAttemptUpdateMems, PaymentIntentUpdateMems, PaymentMethodUpdateMems, RefundUpdateMems,
+    TypedSql, Updateable,
 };
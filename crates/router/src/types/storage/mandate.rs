@@ -67,3 +67,34 @@ impl MandateDbExt for Mandate {
             .attach_printable("Error filtering mandates by specified constraints")
     }
 }
+
+/// Whether the mandate satisfies the constraints other than their limit, for the mandates which
+/// are not queried through [`MandateDbExt::filter_by_constraints`]
+pub fn satisfies_constraints(
+    mandate: &Mandate,
+    mandate_list_constraints: &api_models::mandates::MandateListConstraints,
+) -> bool {
+    let mut checker = true;
+    if let Some(created_time) = mandate_list_constraints.created_time {
+        checker &= mandate.created_at == created_time;
+    }
+    if let Some(created_time_lt) = mandate_list_constraints.created_time_lt {
+        checker &= mandate.created_at < created_time_lt;
+    }
+    if let Some(created_time_gt) = mandate_list_constraints.created_time_gt {
+        checker &= mandate.created_at > created_time_gt;
+    }
+    if let Some(created_time_lte) = mandate_list_constraints.created_time_lte {
+        checker &= mandate.created_at <= created_time_lte;
+    }
+    if let Some(created_time_gte) = mandate_list_constraints.created_time_gte {
+        checker &= mandate.created_at >= created_time_gte;
+    }
+    if let Some(connector) = &mandate_list_constraints.connector {
+        checker &= mandate.connector == *connector;
+    }
+    if let Some(mandate_status) = mandate_list_constraints.mandate_status {
+        checker &= mandate.mandate_status == mandate_status;
+    }
+    checker
+}
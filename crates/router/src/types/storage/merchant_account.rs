@@ -1,5 +1,6 @@
 pub use diesel_models::merchant_account::{
-    MerchantAccount, MerchantAccountNew, MerchantAccountUpdateInternal,
+    DrainerStreamWatermark, MerchantAccount, MerchantAccountNew, MerchantAccountUpdateInternal,
+    StorageSchemeMigrationTrackingData,
 };
 
 pub use crate::types::domain::MerchantAccountUpdate;
@@ -0,0 +1,3 @@
+pub use diesel_models::routing_algorithm_activation::{
+    RoutingAlgorithmActivation, RoutingAlgorithmActivationNew,
+};
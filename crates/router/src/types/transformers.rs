@@ -692,6 +692,100 @@ impl ForeignFrom<storage::Dispute> for api_models::disputes::DisputeResponse {
     }
 }
 
+impl ForeignFrom<storage::Event> for api_models::webhook_events::EventListItemResponse {
+    fn foreign_from(event: storage::Event) -> Self {
+        Self {
+            event_id: event.event_id,
+            event_type: event.event_type,
+            event_class: event.event_class,
+            object_id: event.primary_object_id,
+            object_type: event.primary_object_type,
+            is_webhook_notified: event.is_webhook_notified,
+            delivery_attempts: event.delivery_attempts,
+            created: event.created_at,
+        }
+    }
+}
+
+impl ForeignFrom<storage::WebhookDeliveryAttempt>
+    for api_models::webhook_events::EventDeliveryAttemptResponse
+{
+    fn foreign_from(delivery_attempt: storage::WebhookDeliveryAttempt) -> Self {
+        Self {
+            attempt_id: delivery_attempt.attempt_id,
+            event_id: delivery_attempt.event_id,
+            attempt_number: delivery_attempt.attempt_number,
+            delivery_trigger: delivery_attempt.delivery_trigger,
+            request_url: delivery_attempt.request_url,
+            request_body: delivery_attempt.request_body,
+            response_status_code: delivery_attempt
+                .response_status_code
+                .and_then(|status_code| u16::try_from(status_code).ok()),
+            response_body: delivery_attempt.response_body,
+            error_message: delivery_attempt.error_message,
+            latency_ms: delivery_attempt.latency_ms,
+            is_delivered: delivery_attempt.is_delivered,
+            created: delivery_attempt.created_at,
+        }
+    }
+}
+
+impl ForeignFrom<api_models::process_tracker::ProcessTrackerListConstraints>
+    for storage::ProcessTrackerListConstraints
+{
+    fn foreign_from(
+        constraints: api_models::process_tracker::ProcessTrackerListConstraints,
+    ) -> Self {
+        Self {
+            runner: constraints.runner,
+            status: constraints.status,
+            merchant_id: constraints.merchant_id,
+            schedule_time_start: constraints.schedule_time_start,
+            schedule_time_end: constraints.schedule_time_end,
+            limit: constraints.limit,
+            offset: constraints.offset,
+        }
+    }
+}
+
+impl ForeignFrom<storage::ProcessTracker> for api_models::process_tracker::ProcessTrackerResponse {
+    fn foreign_from(process: storage::ProcessTracker) -> Self {
+        Self {
+            id: process.id,
+            name: process.name,
+            tag: process.tag,
+            runner: process.runner,
+            retry_count: process.retry_count,
+            crash_count: process.crash_count,
+            schedule_time: process.schedule_time,
+            tracking_data: process.tracking_data,
+            business_status: process.business_status,
+            status: process.status,
+            created_at: process.created_at,
+            updated_at: process.updated_at,
+        }
+    }
+}
+
+impl ForeignFrom<storage::AuditLog> for api_models::audit_log::AuditLogResponse {
+    fn foreign_from(audit_log: storage::AuditLog) -> Self {
+        Self {
+            id: audit_log.id,
+            actor_type: audit_log.actor_type,
+            actor_id: audit_log.actor_id,
+            merchant_id: audit_log.merchant_id,
+            flow: audit_log.flow,
+            resource_type: audit_log.resource_type,
+            resource_id: audit_log.resource_id,
+            request_id: audit_log.request_id,
+            request: audit_log.request,
+            snapshot_before: audit_log.snapshot_before,
+            snapshot_after: audit_log.snapshot_after,
+            created_at: audit_log.created_at,
+        }
+    }
+}
+
 impl ForeignFrom<storage::Authorization> for payments::IncrementalAuthorizationResponse {
     fn foreign_from(authorization: storage::Authorization) -> Self {
         Self {
@@ -6,6 +6,8 @@ pub mod db_utils;
 pub mod ext_traits;
 #[cfg(feature = "kv_store")]
 pub mod storage_partitioning;
+#[cfg(test)]
+pub mod test_utils;
 #[cfg(feature = "olap")]
 pub mod user;
 #[cfg(feature = "olap")]
@@ -302,6 +304,7 @@ pub async fn find_payment_intent_from_mandate_id_type(
             .find_mandate_by_merchant_id_mandate_id(
                 &merchant_account.merchant_id,
                 mandate_id.as_str(),
+                merchant_account.storage_scheme,
             )
             .await
             .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?,
@@ -309,6 +312,7 @@ pub async fn find_payment_intent_from_mandate_id_type(
             .find_mandate_by_merchant_id_connector_mandate_id(
                 &merchant_account.merchant_id,
                 connector_mandate_id.as_str(),
+                merchant_account.storage_scheme,
             )
             .await
             .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?,
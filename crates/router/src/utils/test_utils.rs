@@ -0,0 +1,13 @@
+#![allow(clippy::expect_used)]
+
+use tokio::sync::oneshot;
+
+use crate::{configs::settings::Settings, db::StorageImpl, routes::AppState, services};
+
+/// The state of the application backed by the mock database, for the tests of the core flows
+pub async fn get_mock_state() -> AppState {
+    let conf = Settings::new().expect("invalid settings");
+    let tx: oneshot::Sender<()> = oneshot::channel().0;
+    let api_client = Box::new(services::MockApiClient);
+    AppState::with_storage(conf, StorageImpl::Mock, tx, api_client).await
+}
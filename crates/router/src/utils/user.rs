@@ -6,14 +6,16 @@ use masking::Secret;
 use crate::{
     core::errors::{UserErrors, UserResult},
     routes::AppState,
-    services::authentication::{AuthToken, UserFromToken},
+    services::authentication::{AuthToken, TwoFactorAuthToken, UserFromToken},
     types::domain::{MerchantAccount, UserFromStorage},
 };
 
 pub mod dashboard_metadata;
+pub mod oidc;
 pub mod password;
 #[cfg(feature = "dummy_connector")]
 pub mod sample_data;
+pub mod two_factor_auth;
 
 impl UserFromToken {
     pub async fn get_merchant_account(&self, state: AppState) -> UserResult<MerchantAccount> {
@@ -66,6 +68,26 @@ pub async fn generate_jwt_auth_token(
         user_role.role_id.clone(),
         &state.conf,
         user_role.org_id.clone(),
+        false,
+    )
+    .await?;
+    Ok(Secret::new(token))
+}
+
+/// The token of a user signed in through the single sign-on of the organization, see
+/// [`AuthToken::org_scoped`]
+pub async fn generate_org_scoped_jwt_auth_token(
+    state: AppState,
+    user: &UserFromStorage,
+    user_role: &UserRole,
+) -> UserResult<Secret<String>> {
+    let token = AuthToken::new_token(
+        user.get_user_id().to_string(),
+        user_role.merchant_id.clone(),
+        user_role.role_id.clone(),
+        &state.conf,
+        user_role.org_id.clone(),
+        true,
     )
     .await?;
     Ok(Secret::new(token))
@@ -84,6 +106,7 @@ pub async fn generate_jwt_auth_token_with_custom_role_attributes(
         role_id,
         &state.conf,
         org_id,
+        false,
     )
     .await?;
     Ok(Secret::new(token))
@@ -111,3 +134,29 @@ pub fn get_dashboard_entry_response(
         user_role: user_role.role_id,
     })
 }
+
+/// Users with two factor authentication set up only get a short lived token here, which they
+/// exchange for the dashboard token once they prove the second factor
+pub async fn get_sign_in_response(
+    state: AppState,
+    user: UserFromStorage,
+) -> UserResult<user_api::SignInResponse> {
+    if user.is_two_factor_auth_setup() {
+        let token =
+            TwoFactorAuthToken::new_token(user.get_user_id().to_string(), None, &state.conf)
+                .await?;
+        return Ok(user_api::SignInResponse::TwoFactorAuth(
+            user_api::TwoFactorAuthRequiredResponse {
+                token: token.into(),
+                email: user.get_email(),
+            },
+        ));
+    }
+
+    let user_role = user.get_role_from_db(state.clone()).await?;
+    let token = generate_jwt_auth_token(state.clone(), &user, &user_role).await?;
+
+    Ok(user_api::SignInResponse::DashboardEntry(
+        get_dashboard_entry_response(state, user, user_role, token)?,
+    ))
+}
@@ -0,0 +1,471 @@
+use std::{collections::HashMap, net::IpAddr};
+
+use base64::Engine;
+use common_utils::request::{Method, RequestBuilder, RequestContent};
+use error_stack::{IntoReport, ResultExt};
+use jsonwebtoken::{jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use masking::{PeekInterface, Secret};
+use rand::distributions::{Alphanumeric, DistString};
+use redis_interface::DelReply;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    consts::{self, user as user_consts},
+    core::errors::{UserErrors, UserResult},
+    routes::AppState,
+    services,
+};
+
+/// The part of the discovery document of the identity provider in use, see
+/// [OpenID Connect Discovery](https://openid.net/specs/openid-connect-discovery-1_0.html)
+#[derive(Debug, serde::Deserialize)]
+pub struct ProviderMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+/// Kept between the redirection of the user to the identity provider and the callback
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct AuthorizationState {
+    pub org_id: String,
+    pub code_verifier: Secret<String>,
+    pub nonce: String,
+    /// The signed in user linking the identity to their account, if any
+    #[serde(default)]
+    pub link_user_id: Option<String>,
+}
+
+impl AuthorizationState {
+    pub fn new(org_id: String, link_user_id: Option<String>) -> Self {
+        let mut rng = rand::thread_rng();
+        Self {
+            org_id,
+            link_user_id,
+            code_verifier: Alphanumeric
+                .sample_string(&mut rng, user_consts::OIDC_CODE_VERIFIER_LENGTH)
+                .into(),
+            nonce: Alphanumeric.sample_string(&mut rng, user_consts::OIDC_NONCE_LENGTH),
+        }
+    }
+
+    /// The `S256` challenge derived from the verifier, see [RFC 7636](https://www.rfc-editor.org/rfc/rfc7636)
+    pub fn get_code_challenge(&self) -> String {
+        let digest =
+            ring::digest::digest(&ring::digest::SHA256, self.code_verifier.peek().as_bytes());
+        consts::BASE64_ENGINE_URL_SAFE_NO_PAD.encode(digest)
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+struct TokenRequest {
+    grant_type: &'static str,
+    code: Secret<String>,
+    redirect_uri: String,
+    client_id: String,
+    client_secret: Secret<String>,
+    code_verifier: Secret<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TokenResponse {
+    id_token: Secret<String>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct IdTokenClaims {
+    pub sub: String,
+    pub nonce: Option<String>,
+    pub email: Option<Secret<String, common_utils::pii::EmailStrategy>>,
+    pub email_verified: Option<bool>,
+    pub name: Option<Secret<String>>,
+    #[serde(flatten)]
+    pub additional_claims: HashMap<String, serde_json::Value>,
+}
+
+impl IdTokenClaims {
+    /// Providers send the groups either as a list or, for a single group, as a string
+    pub fn get_groups(&self, groups_claim: &str) -> Vec<String> {
+        match self.additional_claims.get(groups_claim) {
+            Some(serde_json::Value::Array(groups)) => groups
+                .iter()
+                .filter_map(|group| group.as_str().map(ToString::to_string))
+                .collect(),
+            Some(serde_json::Value::String(group)) => vec![group.to_owned()],
+            _ => Vec::new(),
+        }
+    }
+}
+
+pub fn generate_state() -> String {
+    Alphanumeric.sample_string(&mut rand::thread_rng(), user_consts::OIDC_STATE_LENGTH)
+}
+
+pub async fn get_provider_metadata(
+    state: &AppState,
+    issuer_url: &str,
+) -> UserResult<ProviderMetadata> {
+    let discovery_url = format!(
+        "{}/{}",
+        issuer_url.trim_end_matches('/'),
+        user_consts::OIDC_DISCOVERY_PATH
+    );
+    let request = RequestBuilder::new()
+        .method(Method::Get)
+        .url(&discovery_url)
+        .attach_default_headers()
+        .build();
+    let metadata: ProviderMetadata = send_request(state, request).await?;
+
+    // The issuer has to match the one the discovery document was fetched from, see section 4.3
+    // of the discovery specification
+    if metadata.issuer.trim_end_matches('/') != issuer_url.trim_end_matches('/') {
+        return Err(UserErrors::SsoProviderError)
+            .into_report()
+            .attach_printable(format!(
+                "Issuer mismatch in discovery document, expected {issuer_url}, found {}",
+                metadata.issuer
+            ));
+    }
+    Ok(metadata)
+}
+
+pub fn get_authorization_url(
+    metadata: &ProviderMetadata,
+    client_id: &str,
+    redirect_url: &str,
+    csrf_state: &str,
+    authorization_state: &AuthorizationState,
+) -> UserResult<String> {
+    let mut url = url::Url::parse(&metadata.authorization_endpoint)
+        .into_report()
+        .change_context(UserErrors::SsoProviderError)
+        .attach_printable("Invalid authorization endpoint in discovery document")?;
+    url.query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", client_id)
+        .append_pair("redirect_uri", redirect_url)
+        .append_pair("scope", user_consts::OIDC_SCOPES)
+        .append_pair("state", csrf_state)
+        .append_pair("nonce", &authorization_state.nonce)
+        .append_pair("code_challenge", &authorization_state.get_code_challenge())
+        .append_pair("code_challenge_method", "S256");
+    Ok(url.to_string())
+}
+
+pub async fn exchange_code_for_id_token(
+    state: &AppState,
+    metadata: &ProviderMetadata,
+    client_id: String,
+    client_secret: Secret<String>,
+    redirect_url: String,
+    code: Secret<String>,
+    code_verifier: Secret<String>,
+) -> UserResult<Secret<String>> {
+    let request = RequestBuilder::new()
+        .method(Method::Post)
+        .url(&metadata.token_endpoint)
+        .attach_default_headers()
+        .header(
+            http::header::CONTENT_TYPE.as_str(),
+            "application/x-www-form-urlencoded",
+        )
+        .set_body(RequestContent::FormUrlEncoded(Box::new(TokenRequest {
+            grant_type: "authorization_code",
+            code,
+            redirect_uri: redirect_url,
+            client_id,
+            client_secret,
+            code_verifier,
+        })))
+        .build();
+    let token_response: TokenResponse = send_request(state, request).await?;
+    Ok(token_response.id_token)
+}
+
+pub async fn validate_id_token(
+    state: &AppState,
+    metadata: &ProviderMetadata,
+    id_token: &Secret<String>,
+    client_id: &str,
+    nonce: &str,
+) -> UserResult<IdTokenClaims> {
+    let request = RequestBuilder::new()
+        .method(Method::Get)
+        .url(&metadata.jwks_uri)
+        .attach_default_headers()
+        .build();
+    let jwks: JwkSet = send_request(state, request).await?;
+
+    verify_id_token(id_token, &jwks, &metadata.issuer, client_id, nonce)
+}
+
+/// Verifies the signature of the ID token with the keys of the identity provider, along with its
+/// issuer, audience, expiry and nonce
+pub fn verify_id_token(
+    id_token: &Secret<String>,
+    jwks: &JwkSet,
+    issuer: &str,
+    client_id: &str,
+    nonce: &str,
+) -> UserResult<IdTokenClaims> {
+    let header = jsonwebtoken::decode_header(id_token.peek())
+        .into_report()
+        .change_context(UserErrors::SsoIdTokenInvalid)?;
+
+    // Symmetric algorithms would have the token signed with the client secret instead of a key
+    // of the identity provider, and are not supported
+    let algorithm = match header.alg {
+        Algorithm::RS256
+        | Algorithm::RS384
+        | Algorithm::RS512
+        | Algorithm::PS256
+        | Algorithm::PS384
+        | Algorithm::PS512
+        | Algorithm::ES256
+        | Algorithm::ES384
+        | Algorithm::EdDSA => header.alg,
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+            return Err(UserErrors::SsoIdTokenInvalid)
+                .into_report()
+                .attach_printable(format!("Unsupported ID token algorithm {:?}", header.alg));
+        }
+    };
+
+    let jwk = match header.kid {
+        Some(kid) => jwks.find(&kid),
+        // Without a key ID, the identity provider is expected to have a single key
+        None if jwks.keys.len() == 1 => jwks.keys.first(),
+        None => None,
+    }
+    .ok_or(UserErrors::SsoIdTokenInvalid)
+    .into_report()
+    .attach_printable("Signing key of the ID token not found in the JWKS")?;
+    let decoding_key = DecodingKey::from_jwk(jwk)
+        .into_report()
+        .change_context(UserErrors::SsoIdTokenInvalid)
+        .attach_printable("Unsupported key in the JWKS")?;
+
+    let mut validation = Validation::new(algorithm);
+    validation.set_issuer(&[issuer]);
+    validation.set_audience(&[client_id]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+    let claims = jsonwebtoken::decode::<IdTokenClaims>(id_token.peek(), &decoding_key, &validation)
+        .into_report()
+        .change_context(UserErrors::SsoIdTokenInvalid)?
+        .claims;
+
+    if claims.nonce.as_deref() != Some(nonce) {
+        return Err(UserErrors::SsoIdTokenInvalid)
+            .into_report()
+            .attach_printable("Nonce mismatch in ID token");
+    }
+    Ok(claims)
+}
+
+pub async fn store_authorization_state(
+    state: &AppState,
+    csrf_state: &str,
+    authorization_state: &AuthorizationState,
+) -> UserResult<()> {
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .change_context(UserErrors::InternalServerError)
+        .attach_printable("Failed to get redis connection")?;
+    redis_conn
+        .serialize_and_set_key_with_expiry(
+            &get_redis_key_for_state(csrf_state),
+            authorization_state,
+            user_consts::OIDC_AUTHORIZATION_TIME_IN_SECS,
+        )
+        .await
+        .change_context(UserErrors::InternalServerError)
+}
+
+/// The state can be used only once, it is deleted as it is read
+pub async fn take_authorization_state(
+    state: &AppState,
+    csrf_state: &str,
+) -> UserResult<AuthorizationState> {
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .change_context(UserErrors::InternalServerError)
+        .attach_printable("Failed to get redis connection")?;
+    let key = get_redis_key_for_state(csrf_state);
+
+    let authorization_state = redis_conn
+        .get_and_deserialize_key::<AuthorizationState>(&key, "AuthorizationState")
+        .await
+        .map_err(|e| {
+            if matches!(
+                e.current_context(),
+                redis_interface::errors::RedisError::NotFound
+            ) {
+                e.change_context(UserErrors::SsoStateInvalid)
+            } else {
+                e.change_context(UserErrors::InternalServerError)
+            }
+        })?;
+
+    match redis_conn
+        .delete_key(&key)
+        .await
+        .change_context(UserErrors::InternalServerError)?
+    {
+        DelReply::KeyDeleted => Ok(authorization_state),
+        // Another callback with the same state got to it first
+        DelReply::KeyNotDeleted => Err(UserErrors::SsoStateInvalid.into()),
+    }
+}
+
+fn get_redis_key_for_state(csrf_state: &str) -> String {
+    format!("{}{}", user_consts::REDIS_OIDC_STATE_PREFIX, csrf_state)
+}
+
+/// The identity provider is reached from the server, so its URLs are restricted to HTTPS hosts
+/// with public addresses, unless insecure providers are allowed for local development
+pub async fn validate_provider_url(state: &AppState, url: &str) -> UserResult<()> {
+    let url = url::Url::parse(url)
+        .into_report()
+        .change_context(UserErrors::SsoProviderError)
+        .attach_printable_lazy(|| format!("Invalid identity provider URL {url}"))?;
+    if state.conf.sso.allow_insecure_providers {
+        return Ok(());
+    }
+    if url.scheme() != "https" {
+        return Err(UserErrors::SsoProviderError)
+            .into_report()
+            .attach_printable_lazy(|| format!("Identity provider URL {url} is not HTTPS"));
+    }
+
+    let port = url.port_or_known_default().unwrap_or(443);
+    let addresses = match url.host() {
+        Some(url::Host::Ipv4(address)) => vec![IpAddr::V4(address)],
+        Some(url::Host::Ipv6(address)) => vec![IpAddr::V6(address)],
+        Some(url::Host::Domain(domain)) => tokio::net::lookup_host((domain, port))
+            .await
+            .into_report()
+            .change_context(UserErrors::SsoProviderError)
+            .attach_printable_lazy(|| format!("Failed to resolve {domain}"))?
+            .map(|address| address.ip())
+            .collect(),
+        None => Vec::new(),
+    };
+    if addresses.is_empty() || !addresses.into_iter().all(is_public_address) {
+        return Err(UserErrors::SsoProviderError)
+            .into_report()
+            .attach_printable_lazy(|| {
+                format!("Identity provider URL {url} does not resolve to a public address")
+            });
+    }
+    Ok(())
+}
+
+fn is_public_address(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => {
+            let [first, second, ..] = address.octets();
+            !(address.is_private()
+                || address.is_loopback()
+                || address.is_link_local()
+                || address.is_unspecified()
+                || address.is_broadcast()
+                || address.is_documentation()
+                // Shared address space, see RFC 6598
+                || (first == 100 && (second & 0xc0) == 64))
+        }
+        IpAddr::V6(address) => {
+            let first_segment = address.segments()[0];
+            !(address.is_loopback()
+                || address.is_unspecified()
+                // Unique local addresses, see RFC 4193
+                || (first_segment & 0xfe00) == 0xfc00
+                // Link-local unicast addresses
+                || (first_segment & 0xffc0) == 0xfe80)
+                && address
+                    .to_ipv4_mapped()
+                    .map_or(true, |address| is_public_address(IpAddr::V4(address)))
+        }
+    }
+}
+
+async fn send_request<T: DeserializeOwned>(
+    state: &AppState,
+    request: services::Request,
+) -> UserResult<T> {
+    let url = request.url.clone();
+    validate_provider_url(state, &url).await?;
+    services::send_request(state, request, None)
+        .await
+        .change_context(UserErrors::SsoProviderError)
+        .attach_printable_lazy(|| format!("Failed to send request to {url}"))?
+        .error_for_status()
+        .into_report()
+        .change_context(UserErrors::SsoProviderError)
+        .attach_printable_lazy(|| format!("Error response from {url}"))?
+        .json::<T>()
+        .await
+        .into_report()
+        .change_context(UserErrors::SsoProviderError)
+        .attach_printable_lazy(|| format!("Failed to parse response from {url}"))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_is_public_address() {
+        for address in ["93.184.216.34", "2606:2800:220:1:248:1893:25c8:1946"] {
+            assert!(is_public_address(address.parse().unwrap()), "{address}");
+        }
+        for address in [
+            "127.0.0.1",
+            "10.0.0.1",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:169.254.169.254",
+        ] {
+            assert!(!is_public_address(address.parse().unwrap()), "{address}");
+        }
+    }
+
+    #[test]
+    fn test_get_code_challenge() {
+        // Test vector from RFC 7636, Appendix B
+        let authorization_state = AuthorizationState {
+            org_id: "org_test".to_string(),
+            code_verifier: Secret::new("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk".to_string()),
+            nonce: "nonce".to_string(),
+            link_user_id: None,
+        };
+        assert_eq!(
+            authorization_state.get_code_challenge(),
+            "E9Melhoa2OwvFrEMTJguCHaoeKt8zJ7ZnzP2f7HYczY"
+        );
+    }
+
+    #[test]
+    fn test_get_groups() {
+        let claims: IdTokenClaims = serde_json::from_value(serde_json::json!({
+            "sub": "user",
+            "groups": ["admins", "developers"],
+            "role": "operators",
+        }))
+        .unwrap();
+        assert_eq!(claims.get_groups("groups"), vec!["admins", "developers"]);
+        assert_eq!(claims.get_groups("role"), vec!["operators"]);
+        assert!(claims.get_groups("teams").is_empty());
+    }
+}
@@ -0,0 +1,287 @@
+use common_utils::crypto::generate_cryptographically_secure_random_bytes;
+use error_stack::{IntoReport, ResultExt};
+use masking::{ExposeInterface, PeekInterface, Secret};
+use rand::{distributions::Uniform, Rng};
+
+use crate::{
+    consts::user as user_consts,
+    core::errors::{UserErrors, UserResult},
+    routes::AppState,
+    types::domain,
+    utils::user::password,
+};
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const RECOVERY_CODE_ALPHABET: &[u8; 32] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+pub fn generate_totp_secret() -> Secret<Vec<u8>> {
+    Secret::new(
+        generate_cryptographically_secure_random_bytes::<
+            { user_consts::TOTP_SECRET_LENGTH_IN_BYTES },
+        >()
+        .to_vec(),
+    )
+}
+
+/// The `otpauth` URL from which authenticator apps enrol the secret, usually shared as a QR code
+pub fn get_totp_url(email: &str, secret: &Secret<Vec<u8>>) -> UserResult<Secret<String>> {
+    let label = format!("{}:{}", user_consts::TOTP_ISSUER_NAME, email);
+    let mut url = url::Url::parse("otpauth://totp/")
+        .into_report()
+        .change_context(UserErrors::InternalServerError)?;
+    url.path_segments_mut()
+        .map_err(|_| UserErrors::InternalServerError)?
+        .push(&label);
+    url.query_pairs_mut()
+        .append_pair("secret", &base32_encode(secret.peek()))
+        .append_pair("issuer", user_consts::TOTP_ISSUER_NAME)
+        .append_pair("algorithm", "SHA1")
+        .append_pair("digits", &user_consts::TOTP_DIGITS.to_string())
+        .append_pair("period", &user_consts::TOTP_PERIOD_IN_SECS.to_string());
+    Ok(Secret::new(url.to_string()))
+}
+
+/// The secret as shown to users who enter it into their authenticator app by hand
+pub fn encode_totp_secret(secret: &Secret<Vec<u8>>) -> Secret<String> {
+    Secret::new(base32_encode(secret.peek()))
+}
+
+/// Accepts the code if it is valid and of a later time step than the last code accepted for the
+/// user, recording its step so that it cannot be replayed, see
+/// [RFC 6238, section 5.2](https://www.rfc-editor.org/rfc/rfc6238#section-5.2)
+pub async fn verify_totp(
+    state: &AppState,
+    user: &domain::UserFromStorage,
+    secret: &Secret<Vec<u8>>,
+    totp: &Secret<String>,
+) -> UserResult<()> {
+    let now = common_utils::date_time::now_unix_timestamp();
+    let current_step = u64::try_from(now)
+        .into_report()
+        .change_context(UserErrors::InternalServerError)?
+        / user_consts::TOTP_PERIOD_IN_SECS;
+    let last_used_step = user
+        .get_totp_last_used_step()
+        .map(u64::try_from)
+        .transpose()
+        .into_report()
+        .change_context(UserErrors::InternalServerError)?;
+
+    let totp_step =
+        get_totp_step(secret, totp, current_step, last_used_step).ok_or(UserErrors::InvalidTotp)?;
+    let totp_step = i64::try_from(totp_step)
+        .into_report()
+        .change_context(UserErrors::InternalServerError)?;
+
+    // The step is recorded only if still later than the last one, a code submitted concurrently
+    // is accepted once
+    state
+        .store
+        .update_user_totp_last_used_step(user.get_user_id(), totp_step)
+        .await
+        .map_err(|e| {
+            if e.current_context().is_db_not_found() {
+                e.change_context(UserErrors::InvalidTotp)
+                    .attach_printable("TOTP already used")
+            } else {
+                e.change_context(UserErrors::InternalServerError)
+            }
+        })?;
+    Ok(())
+}
+
+/// Returns the time step the code is valid for, among the steps around the current one which are
+/// later than the last step used
+fn get_totp_step(
+    secret: &Secret<Vec<u8>>,
+    totp: &Secret<String>,
+    current_step: u64,
+    last_used_step: Option<u64>,
+) -> Option<u64> {
+    let totp = totp.peek().trim();
+    (current_step.saturating_sub(user_consts::TOTP_TOLERANCE)
+        ..=current_step.saturating_add(user_consts::TOTP_TOLERANCE))
+        .filter(|step| last_used_step.map_or(true, |last_used_step| *step > last_used_step))
+        .find(|step| {
+            ring::constant_time::verify_slices_are_equal(
+                generate_hotp(secret.peek(), *step).as_bytes(),
+                totp.as_bytes(),
+            )
+            .is_ok()
+        })
+}
+
+/// Generates the code for the given counter, see [RFC 4226](https://www.rfc-editor.org/rfc/rfc4226)
+fn generate_hotp(secret: &[u8], counter: u64) -> String {
+    let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, secret);
+    let hmac = ring::hmac::sign(&key, &counter.to_be_bytes());
+    let hmac = hmac.as_ref();
+
+    let offset = usize::from(hmac[hmac.len() - 1] & 0x0f);
+    let binary_code = u32::from_be_bytes([
+        hmac[offset] & 0x7f,
+        hmac[offset + 1],
+        hmac[offset + 2],
+        hmac[offset + 3],
+    ]);
+    format!(
+        "{:0width$}",
+        binary_code % 10_u32.pow(user_consts::TOTP_DIGITS),
+        width = user_consts::TOTP_DIGITS as usize
+    )
+}
+
+fn base32_encode(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity((data.len() * 8 + 4) / 5);
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+    for byte in data {
+        buffer = (buffer << 8) | u16::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(char::from(
+                BASE32_ALPHABET[usize::from((buffer >> bits) & 0x1f)],
+            ));
+        }
+    }
+    if bits > 0 {
+        encoded.push(char::from(
+            BASE32_ALPHABET[usize::from((buffer << (5 - bits)) & 0x1f)],
+        ));
+    }
+    encoded
+}
+
+/// Generates the recovery codes along with their hashes, only the hashes are to be stored
+pub fn generate_recovery_codes() -> UserResult<(Vec<Secret<String>>, Vec<Secret<String>>)> {
+    let mut rng = rand::rngs::OsRng;
+    let alphabet = Uniform::from(0..RECOVERY_CODE_ALPHABET.len());
+
+    let recovery_codes: Vec<Secret<String>> = (0..user_consts::RECOVERY_CODES_COUNT)
+        .map(|_| {
+            let code: String = (0..user_consts::RECOVERY_CODE_LENGTH)
+                .map(|_| char::from(RECOVERY_CODE_ALPHABET[rng.sample(alphabet)]))
+                .collect();
+            let (first_half, second_half) = code.split_at(user_consts::RECOVERY_CODE_LENGTH / 2);
+            Secret::new(format!("{first_half}-{second_half}"))
+        })
+        .collect();
+    let hashed_recovery_codes = recovery_codes
+        .iter()
+        .cloned()
+        .map(password::generate_password_hash)
+        .collect::<UserResult<Vec<_>>>()?;
+
+    Ok((recovery_codes, hashed_recovery_codes))
+}
+
+/// Returns the remaining hashed recovery codes if the recovery code is valid, the code being used
+/// up
+pub fn use_recovery_code(
+    recovery_code: Secret<String>,
+    hashed_recovery_codes: Vec<Secret<String>>,
+) -> UserResult<Option<Vec<Secret<String>>>> {
+    let recovery_code = Secret::new(recovery_code.expose().trim().to_uppercase());
+    for (index, hashed_recovery_code) in hashed_recovery_codes.iter().enumerate() {
+        if password::is_correct_password(recovery_code.clone(), hashed_recovery_code.clone())? {
+            let mut remaining_recovery_codes = hashed_recovery_codes;
+            remaining_recovery_codes.remove(index);
+            return Ok(Some(remaining_recovery_codes));
+        }
+    }
+    Ok(None)
+}
+
+/// Counts the attempts made at the second factor, which are limited as the codes are short
+pub async fn check_two_factor_auth_attempts(state: &AppState, user_id: &str) -> UserResult<()> {
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .change_context(UserErrors::InternalServerError)
+        .attach_printable("Failed to get redis connection")?;
+    let key = format!(
+        "{}{}",
+        user_consts::REDIS_TWO_FACTOR_AUTH_ATTEMPTS_PREFIX,
+        user_id
+    );
+    let attempts = redis_conn
+        .increment_field_in_hash(
+            &key,
+            "attempts",
+            1,
+            Some(
+                i64::try_from(user_consts::TWO_FACTOR_AUTH_TOKEN_TIME_IN_SECS)
+                    .into_report()
+                    .change_context(UserErrors::InternalServerError)?,
+            ),
+        )
+        .await
+        .change_context(UserErrors::InternalServerError)?;
+
+    if attempts > user_consts::MAX_TWO_FACTOR_AUTH_ATTEMPTS {
+        return Err(UserErrors::TooManyTwoFactorAuthAttempts.into());
+    }
+    Ok(())
+}
+
+pub async fn reset_two_factor_auth_attempts(state: &AppState, user_id: &str) -> UserResult<()> {
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .change_context(UserErrors::InternalServerError)
+        .attach_printable("Failed to get redis connection")?;
+    redis_conn
+        .delete_key(&format!(
+            "{}{}",
+            user_consts::REDIS_TWO_FACTOR_AUTH_ATTEMPTS_PREFIX,
+            user_id
+        ))
+        .await
+        .change_context(UserErrors::InternalServerError)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_generate_hotp() {
+        // Test vectors from RFC 4226, Appendix D
+        let secret = b"12345678901234567890";
+        let expected = ["755224", "287082", "359152", "969429", "338314"];
+        for (counter, code) in expected.iter().enumerate() {
+            assert_eq!(generate_hotp(secret, counter as u64), *code);
+        }
+    }
+
+    #[test]
+    fn test_get_totp_step() {
+        let secret = Secret::new(b"12345678901234567890".to_vec());
+        let totp = Secret::new(generate_hotp(secret.peek(), 100));
+
+        assert_eq!(get_totp_step(&secret, &totp, 100, None), Some(100));
+        assert_eq!(get_totp_step(&secret, &totp, 101, Some(99)), Some(100));
+        // Codes of the previous and next steps are accepted for clock drift
+        assert_eq!(get_totp_step(&secret, &totp, 99, None), Some(100));
+        assert_eq!(get_totp_step(&secret, &totp, 102, None), None);
+        // A code cannot be replayed once accepted, nor after a later code has been accepted
+        assert_eq!(get_totp_step(&secret, &totp, 100, Some(100)), None);
+        assert_eq!(get_totp_step(&secret, &totp, 101, Some(101)), None);
+        assert_eq!(
+            get_totp_step(&secret, &Secret::new("000000".to_string()), 100, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_base32_encode() {
+        // Test vectors from RFC 4648, Section 10, without padding
+        assert_eq!(base32_encode(b""), "");
+        assert_eq!(base32_encode(b"f"), "MY");
+        assert_eq!(base32_encode(b"fo"), "MZXQ");
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+    }
+}
@@ -1,6 +1,6 @@
 use api_models::user_role as user_role_api;
-use diesel_models::enums::UserStatus;
-use error_stack::ResultExt;
+use diesel_models::{enums::UserStatus, role::Role};
+use error_stack::{IntoReport, ResultExt};
 use router_env::logger;
 
 use crate::{
@@ -8,7 +8,7 @@ use crate::{
     core::errors::{UserErrors, UserResult},
     routes::AppState,
     services::authorization::{
-        permissions::Permission,
+        permissions::{self, Permission},
         predefined_permissions::{self, RoleInfo},
     },
 };
@@ -34,11 +34,85 @@ pub async fn get_merchant_ids_for_user(state: &AppState, user_id: &str) -> UserR
         .collect())
 }
 
-pub fn validate_role_id(role_id: &str) -> UserResult<()> {
+/// A role can be assigned if it is an invitable predefined role or a custom role of the merchant
+pub async fn validate_role_id(
+    state: &AppState,
+    role_id: &str,
+    merchant_id: &str,
+) -> UserResult<()> {
     if predefined_permissions::is_role_invitable(role_id) {
         return Ok(());
     }
-    Err(UserErrors::InvalidRoleId.into())
+    state
+        .store
+        .find_role_by_role_id_merchant_id(role_id, merchant_id)
+        .await
+        .map_err(|e| {
+            if e.current_context().is_db_not_found() {
+                e.change_context(UserErrors::InvalidRoleId)
+            } else {
+                e.change_context(UserErrors::InternalServerError)
+            }
+        })?;
+    Ok(())
+}
+
+pub fn validate_role_name(role_name: &str) -> UserResult<String> {
+    let role_name = role_name.trim();
+    if role_name.is_empty() || role_name.len() > consts::user_role::MAX_ROLE_NAME_LENGTH {
+        return Err(UserErrors::RoleNameParsingError.into());
+    }
+    // Custom roles cannot be told apart from the predefined roles having the same name
+    if predefined_permissions::PREDEFINED_PERMISSIONS
+        .values()
+        .filter_map(|role_info| role_info.get_name())
+        .any(|predefined_role_name| predefined_role_name.eq_ignore_ascii_case(role_name))
+    {
+        return Err(UserErrors::RoleNameAlreadyExists.into());
+    }
+    Ok(role_name.to_owned())
+}
+
+/// The permissions of a custom role must be held by the user creating or updating it, so that
+/// users cannot grant permissions they do not have
+pub fn validate_role_permissions(
+    permissions: Vec<user_role_api::Permission>,
+    user_permissions: &[Permission],
+) -> UserResult<Vec<String>> {
+    if permissions.is_empty() {
+        return Err(UserErrors::InvalidRoleOperation.into())
+            .attach_printable("Role has no permissions");
+    }
+
+    let mut role_permissions: Vec<String> = Vec::with_capacity(permissions.len());
+    for permission in permissions.into_iter().map(Permission::from) {
+        if !user_permissions.contains(&permission) {
+            return Err(UserErrors::InvalidRoleOperation.into())
+                .attach_printable(format!("User does not have the permission {permission}"));
+        }
+        let permission = permission.to_string();
+        if !role_permissions.contains(&permission) {
+            role_permissions.push(permission);
+        }
+    }
+    Ok(role_permissions)
+}
+
+pub fn get_custom_role_response(role: Role) -> UserResult<user_role_api::RoleInfoResponse> {
+    let permissions = permissions::parse_permissions(&role.permissions)
+        .into_report()
+        .change_context(UserErrors::InternalServerError)
+        .attach_printable("Failed to parse the permissions of the role")?
+        .iter()
+        .filter_map(|permission| permission.try_into().ok())
+        .collect();
+
+    Ok(user_role_api::RoleInfoResponse {
+        role_id: role.role_id,
+        permissions,
+        role_name: role.role_name,
+        is_custom: true,
+    })
 }
 
 pub fn get_role_name_and_permission_response(
@@ -91,3 +165,96 @@ impl TryFrom<&Permission> for user_role_api::Permission {
         }
     }
 }
+
+impl From<user_role_api::Permission> for Permission {
+    fn from(value: user_role_api::Permission) -> Self {
+        match value {
+            user_role_api::Permission::PaymentRead => Self::PaymentRead,
+            user_role_api::Permission::PaymentWrite => Self::PaymentWrite,
+            user_role_api::Permission::RefundRead => Self::RefundRead,
+            user_role_api::Permission::RefundWrite => Self::RefundWrite,
+            user_role_api::Permission::ApiKeyRead => Self::ApiKeyRead,
+            user_role_api::Permission::ApiKeyWrite => Self::ApiKeyWrite,
+            user_role_api::Permission::MerchantAccountRead => Self::MerchantAccountRead,
+            user_role_api::Permission::MerchantAccountWrite => Self::MerchantAccountWrite,
+            user_role_api::Permission::MerchantConnectorAccountRead => {
+                Self::MerchantConnectorAccountRead
+            }
+            user_role_api::Permission::MerchantConnectorAccountWrite => {
+                Self::MerchantConnectorAccountWrite
+            }
+            user_role_api::Permission::ForexRead => Self::ForexRead,
+            user_role_api::Permission::RoutingRead => Self::RoutingRead,
+            user_role_api::Permission::RoutingWrite => Self::RoutingWrite,
+            user_role_api::Permission::DisputeRead => Self::DisputeRead,
+            user_role_api::Permission::DisputeWrite => Self::DisputeWrite,
+            user_role_api::Permission::MandateRead => Self::MandateRead,
+            user_role_api::Permission::MandateWrite => Self::MandateWrite,
+            user_role_api::Permission::FileRead => Self::FileRead,
+            user_role_api::Permission::FileWrite => Self::FileWrite,
+            user_role_api::Permission::Analytics => Self::Analytics,
+            user_role_api::Permission::ThreeDsDecisionManagerWrite => {
+                Self::ThreeDsDecisionManagerWrite
+            }
+            user_role_api::Permission::ThreeDsDecisionManagerRead => {
+                Self::ThreeDsDecisionManagerRead
+            }
+            user_role_api::Permission::SurchargeDecisionManagerWrite => {
+                Self::SurchargeDecisionManagerWrite
+            }
+            user_role_api::Permission::SurchargeDecisionManagerRead => {
+                Self::SurchargeDecisionManagerRead
+            }
+            user_role_api::Permission::UsersRead => Self::UsersRead,
+            user_role_api::Permission::UsersWrite => Self::UsersWrite,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_validate_role_permissions() {
+        let user_permissions = [Permission::PaymentRead, Permission::RefundRead];
+
+        let role_permissions = validate_role_permissions(
+            vec![
+                user_role_api::Permission::RefundRead,
+                user_role_api::Permission::PaymentRead,
+                user_role_api::Permission::RefundRead,
+            ],
+            &user_permissions,
+        )
+        .unwrap();
+        assert_eq!(
+            role_permissions,
+            vec![
+                Permission::RefundRead.to_string(),
+                Permission::PaymentRead.to_string()
+            ]
+        );
+
+        let error = validate_role_permissions(
+            vec![
+                user_role_api::Permission::PaymentRead,
+                user_role_api::Permission::PaymentWrite,
+            ],
+            &user_permissions,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            UserErrors::InvalidRoleOperation
+        ));
+
+        let error = validate_role_permissions(vec![], &user_permissions).unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            UserErrors::InvalidRoleOperation
+        ));
+    }
+}
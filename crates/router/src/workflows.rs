@@ -1,3 +1,7 @@
+pub mod idempotency_key_purge;
+pub mod outgoing_webhook_retry;
 pub mod payment_sync;
 pub mod refund_router;
+#[cfg(feature = "kv_store")]
+pub mod storage_scheme_migration;
 pub mod tokenized_data;
@@ -0,0 +1,38 @@
+use router_env::logger;
+use scheduler::{
+    consumer::workflows::ProcessTrackerWorkflow, db::process_tracker::ProcessTrackerExt,
+};
+
+use crate::{db::StorageInterface, errors, routes::AppState, types::storage};
+
+/// Deletes the idempotency keys that have expired, run as a recurring job
+pub struct IdempotencyKeyPurgeWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for IdempotencyKeyPurgeWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let purged_count = db
+            .delete_expired_idempotency_keys(common_utils::date_time::now())
+            .await?;
+        logger::info!(purged_count, "Purged the expired idempotency keys");
+
+        process
+            .finish_with_status(db.as_scheduler(), "COMPLETED_BY_PT".to_string())
+            .await
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        _state: &'a AppState,
+        process: storage::ProcessTracker,
+        _error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        logger::error!(%process.id, "Failed while executing idempotency key purge workflow");
+        Ok(())
+    }
+}
@@ -0,0 +1,332 @@
+use api_models::payments::HeaderPayload;
+use common_utils::ext_traits::{StringExt, ValueExt};
+use error_stack::ResultExt;
+use router_env::logger;
+use scheduler::{
+    consumer::{self, types::process_data, workflows::ProcessTrackerWorkflow},
+    db::process_tracker::ProcessTrackerExt,
+    errors as sch_errors, utils as scheduler_utils,
+};
+
+use crate::{
+    core::{
+        errors::{self, StorageErrorExt},
+        payment_methods::Oss,
+        payments, refunds, webhooks as webhooks_core,
+    },
+    db::StorageInterface,
+    routes::AppState,
+    services,
+    types::{
+        api::{self, mandates::MandateResponseExt},
+        domain,
+        storage::{self, enums},
+        transformers::ForeignInto,
+    },
+};
+
+pub struct OutgoingWebhookRetryWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for OutgoingWebhookRetryWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), sch_errors::ProcessTrackerError> {
+        let db: &dyn StorageInterface = &*state.store;
+        let tracking_data: storage::OutgoingWebhookTrackingData = process
+            .tracking_data
+            .clone()
+            .parse_value("OutgoingWebhookTrackingData")?;
+
+        let event = db.find_event_by_event_id(&tracking_data.event_id).await?;
+
+        // The merchant may have been notified through some other delivery in the meantime
+        if event.is_webhook_notified {
+            return process
+                .finish_with_status(db.as_scheduler(), "COMPLETED_BY_PT".to_string())
+                .await;
+        }
+
+        let key_store = db
+            .get_merchant_key_store_by_merchant_id(
+                &tracking_data.merchant_id,
+                &db.get_master_key().to_vec().into(),
+            )
+            .await?;
+
+        let merchant_account = db
+            .find_merchant_account_by_merchant_id(&tracking_data.merchant_id, &key_store)
+            .await?;
+
+        let business_profile = db
+            .find_business_profile_by_profile_id(&tracking_data.business_profile_id)
+            .await?;
+
+        let content =
+            get_outgoing_webhook_content(state, &merchant_account, key_store, &tracking_data)
+                .await?;
+
+        let outgoing_webhook = api::OutgoingWebhook {
+            merchant_id: tracking_data.merchant_id.clone(),
+            event_id: event.event_id,
+            event_type: event.event_type,
+            content,
+            timestamp: event.created_at,
+        };
+
+        let delivery_result = webhooks_core::trigger_appropriate_webhook_to_merchant(
+            state,
+            &merchant_account,
+            business_profile,
+            outgoing_webhook,
+            enums::WebhookDeliveryAttemptTrigger::AutomaticRetry,
+        )
+        .await;
+
+        match delivery_result {
+            Ok(()) => {
+                process
+                    .finish_with_status(db.as_scheduler(), "COMPLETED_BY_PT".to_string())
+                    .await?
+            }
+            Err(error) if error.current_context().is_webhook_delivery_retryable() => {
+                logger::warn!(
+                    ?error,
+                    "Outgoing webhook delivery failed, scheduling a retry"
+                );
+                retry_webhook_delivery_task(db, &tracking_data.merchant_id, process).await?;
+            }
+            Err(error) => {
+                logger::error!(?error, "Outgoing webhook delivery failed");
+                Err(sch_errors::ProcessTrackerError::FlowExecutionError {
+                    flow: "OutgoingWebhookRetryWorkflow",
+                })?
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: sch_errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), sch_errors::ProcessTrackerError> {
+        consumer::consumer_error_handler(state.store.as_scheduler(), process, error).await
+    }
+}
+
+/// Get the next schedule time
+///
+/// The schedule time can be configured in configs by this key `pt_mapping_outgoing_webhooks`
+/// ```json
+/// {
+///     "defaultMapping": {
+///         "start_after": 60,
+///         "frequency": [120, 240, 480],
+///         "count": [1, 1, 1]
+///     },
+///     "customMerchantMapping": {}
+/// }
+/// ```
+///
+/// This config represents
+///
+/// `start_after`: The first retry should happen 60 seconds after the initial delivery failed
+///
+/// `frequency` and `count`: The next 3 retries should happen after 120, 240 and 480 seconds
+/// respectively, after which the merchant is no longer notified about the event
+///
+pub async fn get_outgoing_webhook_retry_schedule_time(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    retry_count: i32,
+) -> Option<time::PrimitiveDateTime> {
+    let mapping: common_utils::errors::CustomResult<
+        process_data::OutgoingWebhookRetryProcessTrackerMapping,
+        errors::StorageError,
+    > = db
+        .find_config_by_key("pt_mapping_outgoing_webhooks")
+        .await
+        .map(|value| value.config)
+        .and_then(|config| {
+            config
+                .parse_struct("OutgoingWebhookRetryProcessTrackerMapping")
+                .change_context(errors::StorageError::DeserializationFailed)
+        });
+    let mapping = match mapping {
+        Ok(x) => x,
+        Err(err) => {
+            logger::info!("Outgoing webhook retry mapping error: {}", err);
+            process_data::OutgoingWebhookRetryProcessTrackerMapping::default()
+        }
+    };
+    let time_delta = scheduler_utils::get_outgoing_webhook_retry_schedule_time(
+        mapping,
+        merchant_id,
+        retry_count,
+    );
+
+    scheduler_utils::get_time_from_delta(time_delta)
+}
+
+/// Schedule the task for retry, or finish it if all retries have been exhausted
+pub async fn retry_webhook_delivery_task(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    process: storage::ProcessTracker,
+) -> Result<(), sch_errors::ProcessTrackerError> {
+    let schedule_time =
+        get_outgoing_webhook_retry_schedule_time(db, merchant_id, process.retry_count + 1).await;
+
+    match schedule_time {
+        Some(schedule_time) => process.retry(db.as_scheduler(), schedule_time).await,
+        None => {
+            process
+                .finish_with_status(db.as_scheduler(), "RETRIES_EXCEEDED".to_string())
+                .await
+        }
+    }
+}
+
+/// Fetch the current state of the primary object of the event, to be sent as the content of the
+/// outgoing webhook.
+pub(crate) async fn get_outgoing_webhook_content(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    tracking_data: &storage::OutgoingWebhookTrackingData,
+) -> Result<api::OutgoingWebhookContent, sch_errors::ProcessTrackerError> {
+    let db = &*state.store;
+    match tracking_data.event_class {
+        enums::EventClass::Payments => {
+            let request = api::PaymentsRetrieveRequest {
+                resource_id: api::PaymentIdType::PaymentIntentId(
+                    tracking_data.primary_object_id.clone(),
+                ),
+                merchant_id: Some(tracking_data.merchant_id.clone()),
+                force_sync: false,
+                ..Default::default()
+            };
+
+            let payments_response = Box::pin(payments::payments_core::<
+                api::PSync,
+                api::PaymentsResponse,
+                _,
+                _,
+                _,
+                Oss,
+            >(
+                state.clone(),
+                merchant_account.clone(),
+                key_store,
+                payments::operations::PaymentStatus,
+                request,
+                services::AuthFlow::Merchant,
+                payments::CallConnectorAction::Avoid,
+                None,
+                HeaderPayload::default(),
+            ))
+            .await?;
+
+            match payments_response {
+                services::ApplicationResponse::JsonWithHeaders((payments_response, _)) => Ok(
+                    api::OutgoingWebhookContent::PaymentDetails(payments_response),
+                ),
+                _ => Err(sch_errors::ProcessTrackerError::ResourceFetchingFailed {
+                    resource_name: "payment",
+                }),
+            }
+        }
+        enums::EventClass::Refunds => {
+            let refund = refunds::refund_retrieve_core(
+                state.clone(),
+                merchant_account.clone(),
+                key_store,
+                api_models::refunds::RefundsRetrieveRequest {
+                    refund_id: tracking_data.primary_object_id.clone(),
+                    force_sync: Some(false),
+                    merchant_connector_details: None,
+                },
+            )
+            .await?;
+
+            Ok(api::OutgoingWebhookContent::RefundDetails(
+                refund.foreign_into(),
+            ))
+        }
+        enums::EventClass::Disputes => {
+            let dispute = db
+                .find_dispute_by_merchant_id_dispute_id(
+                    &tracking_data.merchant_id,
+                    &tracking_data.primary_object_id,
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::DisputeNotFound {
+                    dispute_id: tracking_data.primary_object_id.clone(),
+                })?;
+
+            Ok(api::OutgoingWebhookContent::DisputeDetails(Box::new(
+                dispute.foreign_into(),
+            )))
+        }
+        enums::EventClass::Mandates => {
+            let mandate = db
+                .find_mandate_by_merchant_id_mandate_id(
+                    &tracking_data.merchant_id,
+                    &tracking_data.primary_object_id,
+                    merchant_account.storage_scheme,
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
+
+            Ok(api::OutgoingWebhookContent::MandateDetails(Box::new(
+                api::mandates::MandateResponse::from_db_mandate(
+                    state,
+                    mandate,
+                    merchant_account.storage_scheme,
+                )
+                .await?,
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_default_outgoing_webhook_retry_schedule_time() {
+        let schedule_time_deltas = (0..=10)
+            .map(|retry_count| {
+                scheduler_utils::get_outgoing_webhook_retry_schedule_time(
+                    process_data::OutgoingWebhookRetryProcessTrackerMapping::default(),
+                    "-",
+                    retry_count,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            schedule_time_deltas,
+            vec![
+                Some(60),
+                Some(120),
+                Some(240),
+                Some(480),
+                Some(960),
+                Some(1920),
+                Some(3840),
+                Some(7680),
+                Some(15360),
+                Some(30720),
+                None
+            ]
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use scheduler::consumer::workflows::ProcessTrackerWorkflow;
+
+use crate::{
+    core::storage_scheme_migration, errors, logger::error, routes::AppState, types::storage,
+};
+
+pub struct StorageSchemeMigrationWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for StorageSchemeMigrationWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        Box::pin(storage_scheme_migration::start_storage_scheme_migration_workflow(state, &process))
+            .await
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        _state: &'a AppState,
+        process: storage::ProcessTracker,
+        _error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        error!(%process.id, "Failed while executing storage scheme migration workflow");
+        Ok(())
+    }
+}
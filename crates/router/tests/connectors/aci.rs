@@ -95,6 +95,7 @@ fn construct_payment_router_data() -> types::PaymentsAuthorizeRouterData {
         connector_api_version: None,
         connector_http_status_code: None,
         apple_pay_flow: None,
+        merchant_connector_id: None,
         external_latency: None,
         frm_metadata: None,
     }
@@ -154,6 +155,7 @@ fn construct_refund_router_data<F>() -> types::RefundsRouterData<F> {
         connector_api_version: None,
         connector_http_status_code: None,
         apple_pay_flow: None,
+        merchant_connector_id: None,
         external_latency: None,
         frm_metadata: None,
     }
@@ -522,6 +522,7 @@ pub trait ConnectorActions: Connector {
             connector_api_version: None,
             connector_http_status_code: None,
             apple_pay_flow: None,
+            merchant_connector_id: None,
             external_latency: None,
             frm_metadata: None,
         }
@@ -52,7 +52,7 @@ pub enum Tag {
 }
 
 /// API Flow
-#[derive(Debug, Display, Clone, PartialEq, Eq)]
+#[derive(Debug, Display, Clone, PartialEq, Eq, EnumString)]
 pub enum Flow {
     /// Merchants account create flow.
     MerchantsAccountCreate,
@@ -74,6 +74,10 @@ pub enum Flow {
     MerchantConnectorsDelete,
     /// Merchant Connectors list flow.
     MerchantConnectorsList,
+    /// Merchant Connectors circuit breaker retrieve flow.
+    MerchantConnectorsCircuitBreakerRetrieve,
+    /// Merchant Connectors circuit breaker reset flow.
+    MerchantConnectorsCircuitBreakerReset,
     /// ConfigKey create flow.
     ConfigKeyCreate,
     /// ConfigKey fetch flow.
@@ -185,8 +189,22 @@ pub enum Flow {
     RoutingUpdateDefaultConfig,
     /// Routing delete config
     RoutingDeleteConfig,
+    /// Routing simulate config
+    RoutingSimulateConfig,
+    /// Routing retrieve activation history
+    RoutingRetrieveActivationHistory,
+    /// Routing diff config
+    RoutingDiffConfig,
+    /// Routing rollback config
+    RoutingRollbackConfig,
     /// Incoming Webhook Receive
     IncomingWebhookReceive,
+    /// Webhook events list flow
+    WebhookEventsList,
+    /// Webhook event delivery attempts list flow
+    WebhookEventDeliveryAttemptList,
+    /// Webhook event delivery retry flow
+    WebhookEventDeliveryRetry,
     /// Validate payment method flow
     ValidatePaymentMethod,
     /// API Key create flow
@@ -221,6 +239,10 @@ pub enum Flow {
     RetrieveDisputeEvidence,
     /// Invalidate cache flow
     CacheInvalidate,
+    /// Cache invalidation by key prefix or by merchant flow
+    CacheInvalidateMatching,
+    /// Cache statistics retrieve flow
+    CacheStatisticsRetrieve,
     /// Payment Link Retrieve flow
     PaymentLinkRetrieve,
     /// payment Link Initiate flow
@@ -237,6 +259,8 @@ pub enum Flow {
     BusinessProfileDelete,
     /// List all the business profiles for a merchant
     BusinessProfileList,
+    /// Rotate the key used to sign outgoing webhooks of a business profile
+    WebhookSigningKeyRotate,
     /// Different verification flows
     Verification,
     /// Rust locker migration
@@ -249,6 +273,22 @@ pub enum Flow {
     GsmRuleUpdate,
     /// Gsm Rule Delete flow
     GsmRuleDelete,
+    /// Process tracker tasks list flow
+    ProcessTrackerList,
+    /// Process tracker task retrieve flow
+    ProcessTrackerRetrieve,
+    /// Process tracker task retry flow
+    ProcessTrackerRetry,
+    /// Process tracker task reschedule flow
+    ProcessTrackerReschedule,
+    /// Process tracker task finish flow
+    ProcessTrackerFinish,
+    /// Storage scheme migration start flow
+    StorageSchemeMigrationStart,
+    /// Storage scheme migration retrieve flow
+    StorageSchemeMigrationRetrieve,
+    /// Storage scheme migration abort flow
+    StorageSchemeMigrationAbort,
     /// User Sign Up
     UserSignUp,
     /// User Sign Up
@@ -285,6 +325,12 @@ pub enum Flow {
     GetRole,
     /// Update user role
     UpdateUserRole,
+    /// Create custom role
+    CreateRole,
+    /// Update custom role
+    UpdateRole,
+    /// Delete custom role
+    DeleteRole,
     /// Create merchant account for user in a org
     UserMerchantAccountCreate,
     /// Generate Sample Data
@@ -315,6 +361,30 @@ pub enum Flow {
     VerifyEmail,
     /// Send verify email
     VerifyEmailRequest,
+    /// User Sign In with the second factor
+    TwoFactorAuthSignIn,
+    /// Begin TOTP enrolment
+    BeginTotp,
+    /// Verify TOTP to complete the enrolment
+    VerifyTotp,
+    /// Generate new recovery codes
+    GenerateRecoveryCodes,
+    /// Reset two factor authentication of a user
+    ResetTwoFactorAuth,
+    /// Set OIDC single sign-on config of the organization
+    SetOidcConfig,
+    /// Get OIDC single sign-on config of the organization
+    GetOidcConfig,
+    /// Begin OIDC single sign-on
+    OidcAuthorize,
+    /// Complete OIDC single sign-on with the callback from the identity provider
+    OidcCallback,
+    /// Begin linking an OIDC identity to the signed in user
+    OidcLink,
+    /// Complete linking an OIDC identity with the callback from the identity provider
+    OidcLinkCallback,
+    /// List audit log entries
+    AuditLogList,
 }
 
 ///
@@ -0,0 +1,361 @@
+use std::sync::Arc;
+
+use common_utils::errors::CustomResult;
+use diesel_models::enums::ProcessTrackerStatus;
+use error_stack::{report, IntoReport, ResultExt};
+use router_env::{instrument, tracing};
+use time::Duration;
+use tokio::sync::mpsc;
+
+use super::{
+    env::logger::{self, debug, error, warn},
+    metrics,
+};
+use crate::{
+    configs::settings::SchedulerSettings, errors, flow::SchedulerFlow,
+    scheduler::SchedulerInterface, utils::*, SchedulerAppState,
+};
+
+/// Business status of the tasks finished by the cleaner after crashing too many times
+pub const CRASH_LIMIT_EXCEEDED: &str = "CRASH_LIMIT_EXCEEDED";
+
+#[instrument(skip_all)]
+pub async fn start_cleaner<T>(
+    state: &T,
+    scheduler_settings: Arc<SchedulerSettings>,
+    (tx, mut rx): (mpsc::Sender<()>, mpsc::Receiver<()>),
+) -> CustomResult<(), errors::ProcessTrackerError>
+where
+    T: SchedulerAppState,
+{
+    use std::time::Duration;
+
+    use rand::distributions::{Distribution, Uniform};
+
+    let mut rng = rand::thread_rng();
+
+    // TODO: this can be removed once rand-0.9 is released
+    // reference - https://github.com/rust-random/rand/issues/1326#issuecomment-1635331942
+    #[allow(unknown_lints)]
+    #[allow(clippy::unnecessary_fallible_conversions)]
+    let timeout = Uniform::try_from(0..=scheduler_settings.loop_interval)
+        .into_report()
+        .change_context(errors::ProcessTrackerError::ConfigurationError)?;
+
+    tokio::time::sleep(Duration::from_millis(timeout.sample(&mut rng))).await;
+
+    let mut interval = tokio::time::interval(std::time::Duration::from_millis(
+        scheduler_settings.loop_interval,
+    ));
+
+    let mut shutdown_interval = tokio::time::interval(std::time::Duration::from_millis(
+        scheduler_settings.graceful_shutdown_interval,
+    ));
+
+    let signal = common_utils::signals::get_allowed_signals()
+        .map_err(|error| {
+            logger::error!("Signal Handler Error: {:?}", error);
+            errors::ProcessTrackerError::ConfigurationError
+        })
+        .into_report()
+        .attach_printable("Failed while creating a signals handler")?;
+    let handle = signal.handle();
+    let task_handle = tokio::spawn(common_utils::signals::signal_handler(signal, tx));
+
+    loop {
+        match rx.try_recv() {
+            Err(mpsc::error::TryRecvError::Empty) => {
+                interval.tick().await;
+                match run_cleaner_flow(state, &scheduler_settings).await {
+                    Ok(_) => (),
+                    Err(error) => {
+                        // Intentionally not propagating error to caller.
+                        // Any errors that occur in the cleaner flow must be handled here only, as
+                        // this is the topmost level function which is concerned with the cleaner flow.
+                        error!(%error);
+                    }
+                }
+            }
+            Ok(()) | Err(mpsc::error::TryRecvError::Disconnected) => {
+                logger::debug!("Awaiting shutdown!");
+                rx.close();
+                shutdown_interval.tick().await;
+                logger::info!("Terminating cleaner");
+                break;
+            }
+        }
+    }
+    handle.close();
+    task_handle
+        .await
+        .into_report()
+        .change_context(errors::ProcessTrackerError::UnexpectedFlow)?;
+
+    Ok(())
+}
+
+#[instrument(skip_all)]
+pub async fn run_cleaner_flow<T>(
+    state: &T,
+    settings: &SchedulerSettings,
+) -> CustomResult<(), errors::ProcessTrackerError>
+where
+    T: SchedulerAppState,
+{
+    lock_acquire_release::<_, _, _>(
+        state.get_db().as_scheduler(),
+        "CLEANER_LOCK",
+        &settings.cleaner.lock_key,
+        settings.cleaner.lock_ttl,
+        move || async {
+            let tasks = fetch_expired_tasks(state.get_db().as_scheduler(), settings).await?;
+            debug!("Cleaner count of tasks {}", tasks.len());
+
+            let (abandoned_tasks, recoverable_tasks): (Vec<_>, Vec<_>) = tasks
+                .into_iter()
+                .partition(|task| task.crash_count >= settings.cleaner.max_crash_count);
+
+            abandon_tasks(state.get_db().as_scheduler(), abandoned_tasks).await?;
+
+            // Requeued through `reinitialize_limbo_processes`, which also bumps the crash count
+            divide_and_append_tasks(
+                state.get_db().as_scheduler(),
+                SchedulerFlow::Cleaner,
+                recoverable_tasks,
+                settings,
+            )
+            .await?;
+
+            Ok(())
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip_all)]
+pub async fn fetch_expired_tasks(
+    db: &dyn SchedulerInterface,
+    conf: &SchedulerSettings,
+) -> CustomResult<Vec<storage::ProcessTracker>, errors::ProcessTrackerError> {
+    let lease_expiry = common_utils::date_time::now()
+        .checked_sub(Duration::seconds(conf.cleaner.lease_timeout))
+        .ok_or_else(|| {
+            report!(errors::ProcessTrackerError::ConfigurationError)
+                .attach_printable("Error obtaining lease expiry to fetch cleaner tasks")
+        })?;
+
+    db.find_processes_with_expired_lease(lease_expiry, Some(conf.cleaner.fetch_limit))
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessFetchingFailed)
+}
+
+#[instrument(skip_all)]
+async fn abandon_tasks(
+    db: &dyn SchedulerInterface,
+    tasks: Vec<storage::ProcessTracker>,
+) -> CustomResult<(), errors::ProcessTrackerError> {
+    if tasks.is_empty() {
+        return Ok(());
+    }
+
+    let task_ids = tasks.into_iter().map(|task| task.id).collect::<Vec<_>>();
+    warn!(?task_ids, "Finishing tasks that exceeded the crash limit");
+
+    let count = db
+        .process_tracker_update_process_status_by_ids(
+            task_ids,
+            storage::ProcessTrackerUpdate::StatusUpdate {
+                status: ProcessTrackerStatus::Finish,
+                business_status: Some(CRASH_LIMIT_EXCEEDED.to_string()),
+            },
+        )
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessUpdateFailed)?;
+
+    // Safety: Assuming we won't deal with more than `u64::MAX` tasks at once
+    #[allow(clippy::as_conversions)]
+    metrics::TASKS_ABANDONED_COUNT.add(&metrics::CONTEXT, count as u64, &[]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use storage_impl::mock_db::MockDb;
+
+    use super::*;
+    use crate::{
+        consumer::types::ProcessTrackerBatch, db::process_tracker::ProcessTrackerInterface,
+    };
+
+    async fn insert_task(
+        db: &MockDb,
+        id: &str,
+        status: ProcessTrackerStatus,
+        idle_for: Duration,
+    ) -> storage::ProcessTracker {
+        let updated_at = common_utils::date_time::now() - idle_for;
+        db.insert_process(storage::ProcessTrackerNew {
+            id: id.to_string(),
+            name: Some("TEST_TASK".to_string()),
+            tag: vec![],
+            runner: Some("TEST_WORKFLOW".to_string()),
+            retry_count: 0,
+            schedule_time: Some(updated_at),
+            rule: String::new(),
+            tracking_data: serde_json::json!({}),
+            business_status: "Pending".to_string(),
+            status,
+            event: vec![],
+            created_at: updated_at,
+            updated_at,
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn get_task(db: &MockDb, id: &str) -> storage::ProcessTracker {
+        db.find_process_by_id(id).await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_tasks_waiting_in_the_stream_are_not_expired() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create mock DB");
+        let settings = SchedulerSettings::default();
+        let expired = Duration::seconds(settings.cleaner.lease_timeout + 60);
+
+        insert_task(
+            &db,
+            "started",
+            ProcessTrackerStatus::ProcessStarted,
+            expired,
+        )
+        .await;
+        insert_task(&db, "queued", ProcessTrackerStatus::Processing, expired).await;
+        insert_task(
+            &db,
+            "running",
+            ProcessTrackerStatus::ProcessStarted,
+            Duration::ZERO,
+        )
+        .await;
+        insert_task(&db, "finished", ProcessTrackerStatus::Finish, expired).await;
+
+        let tasks = fetch_expired_tasks(&db, &settings).await.unwrap();
+
+        assert_eq!(
+            tasks.into_iter().map(|task| task.id).collect::<Vec<_>>(),
+            vec!["started".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_tasks_are_requeued_once() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create mock DB");
+        let settings = SchedulerSettings::default();
+        let expired = Duration::seconds(settings.cleaner.lease_timeout + 60);
+
+        insert_task(
+            &db,
+            "crashed",
+            ProcessTrackerStatus::ProcessStarted,
+            expired,
+        )
+        .await;
+        insert_task(
+            &db,
+            "finished",
+            ProcessTrackerStatus::ProcessStarted,
+            expired,
+        )
+        .await;
+        let tasks = fetch_expired_tasks(&db, &settings).await.unwrap();
+        assert_eq!(tasks.len(), 2);
+
+        // The consumer holding the task finishes it before the cleaner gets to requeue it
+        db.process_tracker_update_process_status_by_ids(
+            vec!["finished".to_string()],
+            storage::ProcessTrackerUpdate::StatusUpdate {
+                status: ProcessTrackerStatus::Finish,
+                business_status: Some("COMPLETED_BY_PT".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        divide_and_append_tasks(&db, SchedulerFlow::Cleaner, tasks.clone(), &settings)
+            .await
+            .unwrap();
+
+        let crashed = get_task(&db, "crashed").await;
+        assert_eq!(crashed.status, ProcessTrackerStatus::Processing);
+        assert_eq!(crashed.crash_count, 1);
+        assert_eq!(
+            get_task(&db, "finished").await.status,
+            ProcessTrackerStatus::Finish
+        );
+
+        // Only the requeued task is appended to the stream
+        let stream_entries = db.stream_entries.lock().await.clone();
+        assert_eq!(stream_entries.len(), 1);
+        let (_, fields) = stream_entries.into_iter().next().unwrap();
+        let batch = ProcessTrackerBatch::from_redis_stream_entry(
+            fields
+                .into_iter()
+                .map(|(field, value)| (field, Some(value)))
+                .collect(),
+        )
+        .unwrap();
+        assert_eq!(
+            batch
+                .trackers
+                .into_iter()
+                .map(|task| task.id)
+                .collect::<Vec<_>>(),
+            vec!["crashed".to_string()]
+        );
+
+        // Requeued tasks are waiting in the stream, and are not requeued again
+        divide_and_append_tasks(&db, SchedulerFlow::Cleaner, tasks, &settings)
+            .await
+            .unwrap();
+        assert_eq!(db.stream_entries.lock().await.len(), 1);
+        assert!(fetch_expired_tasks(&db, &settings)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tasks_exceeding_the_crash_limit_are_abandoned() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create mock DB");
+        let settings = SchedulerSettings::default();
+        let expired = Duration::seconds(settings.cleaner.lease_timeout + 60);
+
+        let task = insert_task(
+            &db,
+            "crashing",
+            ProcessTrackerStatus::ProcessStarted,
+            expired,
+        )
+        .await;
+        abandon_tasks(&db, vec![task]).await.unwrap();
+
+        let task = get_task(&db, "crashing").await;
+        assert_eq!(task.status, ProcessTrackerStatus::Finish);
+        assert_eq!(task.business_status, CRASH_LIMIT_EXCEEDED);
+        assert!(fetch_expired_tasks(&db, &settings)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
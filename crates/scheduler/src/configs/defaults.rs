@@ -4,6 +4,10 @@ impl Default for super::settings::SchedulerSettings {
             stream: "SCHEDULER_STREAM".into(),
             producer: super::settings::ProducerSettings::default(),
             consumer: super::settings::ConsumerSettings::default(),
+            cleaner: super::settings::CleanerSettings::default(),
+            recurring_jobs: std::collections::HashMap::new(),
+            lanes: std::collections::HashMap::new(),
+            runners: std::collections::HashMap::new(),
             graceful_shutdown_interval: 60000,
             loop_interval: 5000,
         }
@@ -27,6 +31,19 @@ impl Default for super::settings::ConsumerSettings {
         Self {
             disabled: false,
             consumer_group: "SCHEDULER_GROUP".into(),
+            default_lane_weight: 1,
+        }
+    }
+}
+
+impl Default for super::settings::CleanerSettings {
+    fn default() -> Self {
+        Self {
+            lease_timeout: 1800,
+            max_crash_count: 3,
+            fetch_limit: 1000,
+            lock_key: "CLEANER_LOCKING_KEY".into(),
+            lock_ttl: 160,
         }
     }
 }
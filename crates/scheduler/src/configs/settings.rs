@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 #[cfg(feature = "kms")]
 use external_services::kms;
 pub use router_env::config::{Log, LogConsole, LogFile, LogTelemetry};
@@ -13,6 +15,14 @@ pub struct SchedulerSettings {
     pub stream: String,
     pub producer: ProducerSettings,
     pub consumer: ConsumerSettings,
+    pub cleaner: CleanerSettings,
+    /// Jobs the producer schedules on a cron schedule, keyed by the job name
+    pub recurring_jobs: HashMap<String, RecurringJobSettings>,
+    /// Streams the tasks of the listed runners are routed to instead of `stream`, keyed by the
+    /// lane name
+    pub lanes: HashMap<String, LaneSettings>,
+    /// Limits the consumer applies to the tasks of a runner, keyed by the runner name
+    pub runners: HashMap<String, RunnerSettings>,
     pub loop_interval: u64,
     pub graceful_shutdown_interval: u64,
 }
@@ -33,4 +43,42 @@ pub struct ProducerSettings {
 pub struct ConsumerSettings {
     pub disabled: bool,
     pub consumer_group: String,
+    /// Weight of the default stream, relative to the weights of the lanes
+    pub default_lane_weight: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CleanerSettings {
+    /// Time (in seconds) after which a task held by a consumer is considered abandoned
+    pub lease_timeout: i64,
+    /// Number of lease expiries after which a task is finished instead of being requeued
+    pub max_crash_count: i32,
+    pub fetch_limit: i64,
+
+    pub lock_key: String,
+    pub lock_ttl: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecurringJobSettings {
+    /// Five field cron expression, evaluated in UTC
+    pub cron: crate::cron::CronSchedule,
+    /// Runner the consumer executes every occurrence with
+    pub runner: String,
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LaneSettings {
+    pub runners: Vec<String>,
+    /// Share of the consumer polls the lane gets, relative to the other lanes
+    pub weight: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RunnerSettings {
+    /// Maximum number of tasks of the runner a consumer executes at once
+    pub max_in_flight: usize,
 }
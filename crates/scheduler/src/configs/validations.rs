@@ -19,6 +19,36 @@ impl super::settings::SchedulerSettings {
 
         self.producer.validate()?;
 
+        self.cleaner.validate()?;
+
+        self.recurring_jobs
+            .values()
+            .try_for_each(|recurring_job| recurring_job.validate())?;
+
+        when(self.consumer.default_lane_weight == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "scheduler default lane weight must be greater than zero".into(),
+            ))
+        })?;
+
+        self.lanes.values().try_for_each(|lane| lane.validate())?;
+
+        let mut lane_runners = std::collections::HashSet::new();
+        self.lanes
+            .values()
+            .flat_map(|lane| lane.runners.iter())
+            .try_for_each(|runner| {
+                when(!lane_runners.insert(runner), || {
+                    Err(ApplicationError::InvalidConfigurationValueError(format!(
+                        "scheduler runner {runner} must not be assigned to more than one lane"
+                    )))
+                })
+            })?;
+
+        self.runners
+            .values()
+            .try_for_each(|runner| runner.validate())?;
+
         Ok(())
     }
 }
@@ -32,3 +62,51 @@ impl super::settings::ProducerSettings {
         })
     }
 }
+
+impl super::settings::CleanerSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.lock_key.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "cleaner lock key must not be empty".into(),
+            ))
+        })?;
+
+        when(self.lease_timeout <= 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "cleaner lease timeout must be greater than zero".into(),
+            ))
+        })
+    }
+}
+
+impl super::settings::RecurringJobSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        common_utils::fp_utils::when(self.runner.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "recurring job runner must not be empty".into(),
+            ))
+        })
+    }
+}
+
+impl super::settings::LaneSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        common_utils::fp_utils::when(self.weight == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "scheduler lane weight must be greater than zero".into(),
+            ))
+        })
+    }
+}
+
+impl super::settings::RunnerSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        common_utils::fp_utils::when(self.max_in_flight == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "scheduler runner max in flight must be greater than zero".into(),
+            ))
+        })
+    }
+}
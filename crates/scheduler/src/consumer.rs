@@ -1,18 +1,21 @@
 // TODO: Figure out what to log
 
-use std::sync::{self, atomic};
+use std::{
+    collections::HashMap,
+    sync::{self, atomic},
+};
 pub mod types;
 pub mod workflows;
 
 use common_utils::{errors::CustomResult, signals::get_allowed_signals};
 use diesel_models::enums;
 pub use diesel_models::{self, process_tracker as storage};
-use error_stack::{IntoReport, ResultExt};
+use error_stack::{report, IntoReport, ResultExt};
 use futures::future;
 use redis_interface::{RedisConnectionPool, RedisEntryId};
-use router_env::{instrument, tracing};
+use router_env::{instrument, opentelemetry, tracing};
 use time::PrimitiveDateTime;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, OwnedSemaphorePermit, Semaphore};
 use uuid::Uuid;
 
 use super::env::logger;
@@ -28,6 +31,47 @@ pub fn valid_business_statuses() -> Vec<&'static str> {
     vec!["Pending"]
 }
 
+/// Limits the number of tasks of each runner a consumer executes at once, so that a burst of
+/// tasks of one runner cannot starve the others.
+#[derive(Debug, Default)]
+pub struct RunnerLimits {
+    limits: HashMap<String, sync::Arc<Semaphore>>,
+}
+
+/// Holds the slot of a task under the limit of its runner, freeing it when dropped. The tasks of
+/// runners without a limit get an empty permit.
+#[derive(Debug)]
+pub struct RunnerPermit(Option<OwnedSemaphorePermit>);
+
+impl RunnerLimits {
+    pub fn new(settings: &SchedulerSettings) -> Self {
+        let limits = settings
+            .runners
+            .iter()
+            .map(|(runner, runner_settings)| {
+                (
+                    runner.clone(),
+                    sync::Arc::new(Semaphore::new(runner_settings.max_in_flight)),
+                )
+            })
+            .collect();
+
+        Self { limits }
+    }
+
+    /// Takes a slot under the limit of the runner without waiting for one, returning `None` if
+    /// the runner is at its limit. The returned permit must be held for as long as the task runs.
+    pub fn try_acquire(&self, runner: Option<&str>) -> Option<RunnerPermit> {
+        match runner.and_then(|runner| self.limits.get(runner)) {
+            Some(semaphore) => sync::Arc::clone(semaphore)
+                .try_acquire_owned()
+                .ok()
+                .map(|permit| RunnerPermit(Some(permit))),
+            None => Some(RunnerPermit(None)),
+        }
+    }
+}
+
 #[instrument(skip_all)]
 pub async fn start_consumer<T: SchedulerAppState + 'static>(
     state: &T,
@@ -57,6 +101,7 @@ pub async fn start_consumer<T: SchedulerAppState + 'static>(
         tokio::time::interval(Duration::from_millis(settings.graceful_shutdown_interval));
 
     let consumer_operation_counter = sync::Arc::new(atomic::AtomicU64::new(0));
+    let runner_limits = sync::Arc::new(RunnerLimits::new(&settings));
     let signal = get_allowed_signals()
         .map_err(|error| {
             logger::error!("Signal Handler Error: {:?}", error);
@@ -84,6 +129,7 @@ pub async fn start_consumer<T: SchedulerAppState + 'static>(
                         logger::error!(%err);
                     },
                     sync::Arc::clone(&consumer_operation_counter),
+                    sync::Arc::clone(&runner_limits),
                     workflow_selector,
                 ));
             }
@@ -115,40 +161,107 @@ pub async fn start_consumer<T: SchedulerAppState + 'static>(
 pub async fn consumer_operations<T: SchedulerAppState + 'static>(
     state: &T,
     settings: &SchedulerSettings,
+    runner_limits: sync::Arc<RunnerLimits>,
     workflow_selector: impl workflows::ProcessTrackerWorkflows<T> + 'static + Copy + std::fmt::Debug,
 ) -> CustomResult<(), errors::ProcessTrackerError> {
-    let stream_name = settings.stream.clone();
     let group_name = settings.consumer.consumer_group.clone();
     let consumer_name = format!("consumer_{}", Uuid::new_v4());
+    let lane_order = pt_utils::get_weighted_lane_order(settings, &mut rand::thread_rng());
 
-    let group_created = &mut state
-        .get_db()
-        .consumer_group_create(&stream_name, &group_name, &RedisEntryId::AfterLastID)
-        .await;
-    if group_created.is_err() {
-        logger::info!("Consumer group already exists");
+    let mut picked = None;
+    let mut last_error = None;
+
+    // Lanes are tried in weighted order until one of them yields tasks, so that a lane with
+    // a lower weight still gets picked up whenever the others are empty
+    for stream_name in lane_order {
+        let group_created = &mut state
+            .get_db()
+            .consumer_group_create(&stream_name, &group_name, &RedisEntryId::AfterLastID)
+            .await;
+        if group_created.is_err() {
+            logger::info!("Consumer group already exists");
+        }
+
+        match state
+            .get_db()
+            .as_scheduler()
+            .fetch_consumer_tasks(&stream_name, &group_name, &consumer_name)
+            .await
+        {
+            Ok(tasks) if !tasks.is_empty() => {
+                picked = Some((stream_name, tasks));
+                break;
+            }
+            Ok(_) => (),
+            Err(error) => {
+                logger::debug!(error=%error.current_context(), %stream_name, "No tasks picked from lane");
+                last_error = Some(error);
+            }
+        }
     }
 
-    let mut tasks = state
+    let (stream_name, tasks) = match (picked, last_error) {
+        (Some(picked), _) => picked,
+        (None, Some(error)) => return Err(error),
+        (None, None) => return Ok(()),
+    };
+
+    logger::info!(
+        "{} picked {} tasks from {}",
+        consumer_name,
+        tasks.len(),
+        stream_name
+    );
+
+    // Only the tasks whose runner has room for them are started, the others are handed back to
+    // the producer, so that no task is marked as started while it waits for a slot
+    let mut started_tasks = Vec::new();
+    let mut deferred_tasks = Vec::new();
+    for task in tasks {
+        match runner_limits.try_acquire(task.runner.as_deref()) {
+            Some(permit) => started_tasks.push((task, permit)),
+            None => deferred_tasks.push(task),
+        }
+    }
+
+    if !deferred_tasks.is_empty() {
+        defer_tasks(state.get_db().as_scheduler(), deferred_tasks, settings).await?;
+    }
+
+    if started_tasks.is_empty() {
+        return Ok(());
+    }
+
+    state
         .get_db()
-        .as_scheduler()
-        .fetch_consumer_tasks(&stream_name, &group_name, &consumer_name)
-        .await?;
+        .process_tracker_update_process_status_by_ids(
+            started_tasks
+                .iter()
+                .map(|(task, _)| task.id.clone())
+                .collect(),
+            storage::ProcessTrackerUpdate::StatusUpdate {
+                status: enums::ProcessTrackerStatus::ProcessStarted,
+                business_status: None,
+            },
+        )
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessUpdateFailed)?;
 
-    logger::info!("{} picked {} tasks", consumer_name, tasks.len());
     let mut handler = vec![];
 
-    for task in tasks.iter_mut() {
+    for (mut task, permit) in started_tasks {
+        task.status = enums::ProcessTrackerStatus::ProcessStarted;
         let pickup_time = common_utils::date_time::now();
 
-        pt_utils::add_histogram_metrics(&pickup_time, task, &stream_name);
+        pt_utils::add_histogram_metrics(&pickup_time, &mut task, &stream_name);
 
         metrics::TASK_CONSUMED.add(&metrics::CONTEXT, 1, &[]);
         // let runner = workflow_selector(task)?.ok_or(errors::ProcessTrackerError::UnexpectedFlow)?;
         handler.push(tokio::task::spawn(start_workflow(
             state.clone(),
-            task.clone(),
+            task,
             pickup_time,
+            permit,
             workflow_selector,
         )))
     }
@@ -157,9 +270,52 @@ pub async fn consumer_operations<T: SchedulerAppState + 'static>(
     Ok(())
 }
 
-#[instrument(skip(db, redis_conn))]
+/// Resets the tasks whose runner is at its concurrency limit to `New`, scheduled a loop interval
+/// later, for the producer to queue them again once their runner is likely to have room for them.
+pub async fn defer_tasks(
+    db: &dyn SchedulerInterface,
+    tasks: Vec<storage::ProcessTracker>,
+    settings: &SchedulerSettings,
+) -> CustomResult<(), errors::ProcessTrackerError> {
+    for runner in tasks.iter().filter_map(|task| task.runner.clone()) {
+        metrics::TASKS_DEFERRED_COUNT.add(
+            &metrics::CONTEXT,
+            1,
+            &[opentelemetry::KeyValue::new("runner", runner)],
+        );
+    }
+
+    let schedule_time = i64::try_from(settings.loop_interval)
+        .ok()
+        .and_then(|loop_interval| {
+            common_utils::date_time::now().checked_add(time::Duration::milliseconds(loop_interval))
+        })
+        .ok_or_else(|| {
+            report!(errors::ProcessTrackerError::ConfigurationError)
+                .attach_printable("Error obtaining the schedule time of the deferred tasks")
+        })?;
+    db.process_tracker_update_process_status_by_ids(
+        tasks.into_iter().map(|task| task.id).collect(),
+        storage::ProcessTrackerUpdate::Update {
+            name: None,
+            retry_count: None,
+            schedule_time: Some(schedule_time),
+            tracking_data: None,
+            business_status: None,
+            status: Some(enums::ProcessTrackerStatus::New),
+            updated_at: Some(common_utils::date_time::now()),
+        },
+    )
+    .await
+    .change_context(errors::ProcessTrackerError::ProcessUpdateFailed)?;
+
+    Ok(())
+}
+
+/// Reads the next batch of the stream. The tasks are left `Processing`, the consumer marks the
+/// ones it starts as `ProcessStarted`, from when the cleaner times their lease.
+#[instrument(skip(redis_conn))]
 pub async fn fetch_consumer_tasks(
-    db: &dyn ProcessTrackerInterface,
     redis_conn: &RedisConnectionPool,
     stream_name: &str,
     group_name: &str,
@@ -167,7 +323,7 @@ pub async fn fetch_consumer_tasks(
 ) -> CustomResult<Vec<storage::ProcessTracker>, errors::ProcessTrackerError> {
     let batches = pt_utils::get_batches(redis_conn, stream_name, group_name, consumer_name).await?;
 
-    let mut tasks = batches.into_iter().fold(Vec::new(), |mut acc, batch| {
+    let tasks = batches.into_iter().fold(Vec::new(), |mut acc, batch| {
         acc.extend_from_slice(
             batch
                 .trackers
@@ -178,38 +334,32 @@ pub async fn fetch_consumer_tasks(
         );
         acc
     });
-    let task_ids = tasks
-        .iter()
-        .map(|task| task.id.to_owned())
-        .collect::<Vec<_>>();
-
-    db.process_tracker_update_process_status_by_ids(
-        task_ids,
-        storage::ProcessTrackerUpdate::StatusUpdate {
-            status: enums::ProcessTrackerStatus::ProcessStarted,
-            business_status: None,
-        },
-    )
-    .await
-    .change_context(errors::ProcessTrackerError::ProcessFetchingFailed)?;
-    tasks
-        .iter_mut()
-        .for_each(|x| x.status = enums::ProcessTrackerStatus::ProcessStarted);
     Ok(tasks)
 }
 
 // Accept flow_options if required
-#[instrument(skip(state), fields(workflow_id))]
+#[instrument(skip(state, _permit), fields(workflow_id))]
 pub async fn start_workflow<T>(
     state: T,
     process: storage::ProcessTracker,
     _pickup_time: PrimitiveDateTime,
+    _permit: RunnerPermit,
     workflow_selector: impl workflows::ProcessTrackerWorkflows<T> + 'static + std::fmt::Debug,
 ) -> Result<(), errors::ProcessTrackerError>
 where
     T: SchedulerAppState,
 {
     tracing::Span::current().record("workflow_id", Uuid::new_v4().to_string());
+
+    if let Some((schedule_time, runner)) = process.schedule_time.zip(process.runner.as_ref()) {
+        let wait_time = (common_utils::date_time::now() - schedule_time).as_seconds_f64();
+        metrics::TASK_WAIT_TIME.record(
+            &metrics::CONTEXT,
+            wait_time,
+            &[opentelemetry::KeyValue::new("runner", runner.clone())],
+        );
+    }
+
     let res = workflow_selector
         .trigger_workflow(&state.clone(), process.clone())
         .await;
@@ -245,3 +395,75 @@ pub async fn create_task(
     db.insert_process(process_tracker_entry).await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use storage_impl::mock_db::MockDb;
+
+    use super::*;
+    use crate::configs::settings::RunnerSettings;
+
+    #[test]
+    fn test_runner_limits() {
+        let mut settings = SchedulerSettings::default();
+        settings.runners.insert(
+            "PAYMENTS_SYNC_WORKFLOW".to_string(),
+            RunnerSettings { max_in_flight: 1 },
+        );
+        let runner_limits = RunnerLimits::new(&settings);
+
+        let permit = runner_limits.try_acquire(Some("PAYMENTS_SYNC_WORKFLOW"));
+        assert!(permit.is_some());
+        assert!(runner_limits
+            .try_acquire(Some("PAYMENTS_SYNC_WORKFLOW"))
+            .is_none());
+
+        // Runners without a limit are never held back
+        assert!(runner_limits
+            .try_acquire(Some("REFUND_WORKFLOW_ROUTER"))
+            .is_some());
+        assert!(runner_limits.try_acquire(None).is_some());
+
+        drop(permit);
+        assert!(runner_limits
+            .try_acquire(Some("PAYMENTS_SYNC_WORKFLOW"))
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_deferred_tasks_are_handed_back_to_the_producer() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create mock DB");
+        let now = common_utils::date_time::now();
+        let task = storage::ProcessTracker {
+            id: "sync_1".to_string(),
+            name: None,
+            tag: vec![],
+            runner: Some("PAYMENTS_SYNC_WORKFLOW".to_string()),
+            retry_count: 0,
+            schedule_time: Some(now),
+            rule: String::new(),
+            tracking_data: serde_json::json!({}),
+            business_status: "Pending".to_string(),
+            status: enums::ProcessTrackerStatus::Processing,
+            event: vec![],
+            created_at: now,
+            updated_at: now,
+            crash_count: 0,
+        };
+        db.processes.lock().await.push(task.clone());
+        let settings = SchedulerSettings::default();
+
+        defer_tasks(&db, vec![task], &settings).await.unwrap();
+
+        let processes = db.processes.lock().await;
+        assert_eq!(processes[0].status, enums::ProcessTrackerStatus::New);
+        assert_eq!(processes[0].business_status, "Pending");
+        assert!(processes[0].schedule_time > Some(now));
+        // Nothing is put back in the stream, the producer queues the task again once it is due
+        assert!(db.stream_entries.lock().await.is_empty());
+    }
+}
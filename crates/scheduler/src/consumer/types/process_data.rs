@@ -53,3 +53,35 @@ impl Default for PaymentMethodsPTMapping {
         }
     }
 }
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutgoingWebhookRetryProcessTrackerMapping {
+    pub default_mapping: RetryMapping,
+    pub custom_merchant_mapping: HashMap<String, RetryMapping>,
+}
+
+impl Default for OutgoingWebhookRetryProcessTrackerMapping {
+    fn default() -> Self {
+        Self {
+            custom_merchant_mapping: HashMap::new(),
+            // Back off exponentially, doubling the interval after every attempt, starting at one
+            // minute and giving up roughly 17 hours after the first failed delivery.
+            default_mapping: RetryMapping {
+                start_after: 60,
+                frequency: vec![
+                    60 * 2,
+                    60 * 4,
+                    60 * 8,
+                    60 * 16,
+                    60 * 32,
+                    60 * 64,
+                    60 * 128,
+                    60 * 256,
+                    60 * 512,
+                ],
+                count: vec![1, 1, 1, 1, 1, 1, 1, 1, 1],
+            },
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use std::str::FromStr;
+
+use time::{Duration, PrimitiveDateTime, Time};
+
+use crate::errors::ProcessTrackerError;
+
+/// Occurrences further than this many years away are treated as never happening, which only
+/// affects expressions such as `0 0 30 2 *` that can never match.
+const MAX_LOOKAHEAD_YEARS: i32 = 5;
+
+/// A cron expression in the standard five field format: `minute hour day-of-month month
+/// day-of-week`.
+///
+/// Every field accepts `*`, single values, ranges (`1-5`), lists (`1,15`) and steps (`*/15`,
+/// `0-30/10`). Day of week runs from `0` (Sunday) to `6`, with `7` also accepted for Sunday.
+/// As in Vixie cron, when both day of month and day of week are restricted a day matches if
+/// either of them matches. All times are in UTC.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(try_from = "String")]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    restricted_days_of_month: bool,
+    restricted_days_of_week: bool,
+}
+
+impl CronSchedule {
+    /// Returns the first occurrence strictly after `after`.
+    pub fn next_occurrence(&self, after: PrimitiveDateTime) -> Option<PrimitiveDateTime> {
+        let after = after.replace_time(Time::from_hms(after.hour(), after.minute(), 0).ok()?);
+        let mut candidate = after.checked_add(Duration::minutes(1))?;
+        let max_year = after.year() + MAX_LOOKAHEAD_YEARS;
+
+        while candidate.year() <= max_year {
+            if !is_set(self.months, u8::from(candidate.month())) {
+                let (year, month) = match candidate.month() {
+                    time::Month::December => (candidate.year() + 1, time::Month::January),
+                    month => (candidate.year(), month.next()),
+                };
+                candidate = PrimitiveDateTime::new(
+                    time::Date::from_calendar_date(year, month, 1).ok()?,
+                    Time::MIDNIGHT,
+                );
+                continue;
+            }
+
+            if !self.matches_day(candidate.date()) {
+                candidate = PrimitiveDateTime::new(candidate.date().next_day()?, Time::MIDNIGHT);
+                continue;
+            }
+
+            if !is_set(self.hours, candidate.hour()) {
+                candidate = candidate
+                    .replace_time(Time::from_hms(candidate.hour(), 0, 0).ok()?)
+                    .checked_add(Duration::hours(1))?;
+                continue;
+            }
+
+            if !is_set(self.minutes, candidate.minute()) {
+                candidate = candidate.checked_add(Duration::minutes(1))?;
+                continue;
+            }
+
+            return Some(candidate);
+        }
+
+        None
+    }
+
+    fn matches_day(&self, date: time::Date) -> bool {
+        let day_of_month = is_set(self.days_of_month, date.day());
+        let day_of_week = is_set(self.days_of_week, date.weekday().number_days_from_sunday());
+
+        match (self.restricted_days_of_month, self.restricted_days_of_week) {
+            (true, true) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = error_stack::Report<ProcessTrackerError>;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let fields = expression.split_whitespace().collect::<Vec<_>>();
+        let (minutes, hours, days_of_month, months, days_of_week) = match fields.as_slice() {
+            [minutes, hours, days_of_month, months, days_of_week] => {
+                (*minutes, *hours, *days_of_month, *months, *days_of_week)
+            }
+            _ => {
+                return Err(
+                    error_stack::report!(ProcessTrackerError::ConfigurationError).attach_printable(
+                        format!("cron expression `{expression}` must have exactly five fields"),
+                    ),
+                )
+            }
+        };
+
+        // Sunday can be written as both 0 and 7
+        let days_of_week_bits = parse_field(days_of_week, 0, 7)?;
+
+        Ok(Self {
+            minutes: parse_field(minutes, 0, 59)?,
+            hours: parse_field(hours, 0, 23)?,
+            days_of_month: parse_field(days_of_month, 1, 31)?,
+            months: parse_field(months, 1, 12)?,
+            days_of_week: (days_of_week_bits | (days_of_week_bits >> 7)) & !(1 << 7),
+            restricted_days_of_month: !days_of_month.starts_with('*'),
+            restricted_days_of_week: !days_of_week.starts_with('*'),
+        })
+    }
+}
+
+impl TryFrom<String> for CronSchedule {
+    type Error = String;
+
+    fn try_from(expression: String) -> Result<Self, Self::Error> {
+        Self::from_str(&expression).map_err(|error| format!("{error:?}"))
+    }
+}
+
+fn is_set(field: u64, value: u8) -> bool {
+    field & (1 << value) != 0
+}
+
+fn parse_field(
+    field: &str,
+    min: u8,
+    max: u8,
+) -> Result<u64, error_stack::Report<ProcessTrackerError>> {
+    field.split(',').try_fold(0u64, |bits, part| {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, Some(parse_value(step, 1, max, field)?)),
+            None => (part, None),
+        };
+
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (
+                    parse_value(start, min, max, field)?,
+                    parse_value(end, min, max, field)?,
+                ),
+                // A single value with a step runs until the end of the range, as in `5/15`
+                None => {
+                    let value = parse_value(range, min, max, field)?;
+                    (value, if step.is_some() { max } else { value })
+                }
+            },
+        };
+
+        if start > end {
+            return Err(
+                error_stack::report!(ProcessTrackerError::ConfigurationError)
+                    .attach_printable(format!("invalid range `{range}` in cron field `{field}`")),
+            );
+        }
+
+        let step = usize::from(step.unwrap_or(1));
+        Ok((start..=end)
+            .step_by(step)
+            .fold(bits, |bits, value| bits | (1 << value)))
+    })
+}
+
+fn parse_value(
+    value: &str,
+    min: u8,
+    max: u8,
+    field: &str,
+) -> Result<u8, error_stack::Report<ProcessTrackerError>> {
+    value
+        .parse::<u8>()
+        .ok()
+        .filter(|value| (min..=max).contains(value))
+        .ok_or_else(|| {
+            error_stack::report!(ProcessTrackerError::ConfigurationError).attach_printable(format!(
+                "value `{value}` in cron field `{field}` must be between {min} and {max}"
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    fn at(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> PrimitiveDateTime {
+        PrimitiveDateTime::new(
+            time::Date::from_calendar_date(year, month.try_into().unwrap(), day).unwrap(),
+            Time::from_hms(hour, minute, 0).unwrap(),
+        )
+    }
+
+    fn next(expression: &str, after: PrimitiveDateTime) -> Option<PrimitiveDateTime> {
+        CronSchedule::from_str(expression)
+            .unwrap()
+            .next_occurrence(after)
+    }
+
+    #[test]
+    fn test_every_minute() {
+        assert_eq!(
+            next("* * * * *", at(2023, 12, 29, 10, 15)),
+            Some(at(2023, 12, 29, 10, 16))
+        );
+    }
+
+    #[test]
+    fn test_nightly_job_rolls_over_to_next_day() {
+        assert_eq!(
+            next("30 2 * * *", at(2023, 12, 29, 2, 30)),
+            Some(at(2023, 12, 30, 2, 30))
+        );
+        assert_eq!(
+            next("30 2 * * *", at(2023, 12, 31, 23, 59)),
+            Some(at(2024, 1, 1, 2, 30))
+        );
+    }
+
+    #[test]
+    fn test_steps_ranges_and_lists() {
+        assert_eq!(
+            next("*/15 9-17 * * *", at(2023, 12, 29, 17, 45)),
+            Some(at(2023, 12, 30, 9, 0))
+        );
+        assert_eq!(
+            next("0 0 1,15 * *", at(2023, 12, 2, 0, 0)),
+            Some(at(2023, 12, 15, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_day_of_week() {
+        // 2023-12-29 is a Friday
+        assert_eq!(
+            next("0 6 * * 1-5", at(2023, 12, 29, 7, 0)),
+            Some(at(2024, 1, 1, 6, 0))
+        );
+        assert_eq!(
+            next("0 6 * * 7", at(2023, 12, 29, 7, 0)),
+            Some(at(2023, 12, 31, 6, 0))
+        );
+    }
+
+    #[test]
+    fn test_day_of_month_or_day_of_week() {
+        assert_eq!(
+            next("0 0 1 * 0", at(2023, 12, 29, 0, 0)),
+            Some(at(2023, 12, 31, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_impossible_date_never_occurs() {
+        assert_eq!(next("0 0 30 2 *", at(2023, 12, 29, 0, 0)), None);
+    }
+
+    #[test]
+    fn test_invalid_expressions_are_rejected() {
+        for expression in [
+            "* * * *",
+            "60 * * * *",
+            "* * 0 * *",
+            "5-1 * * * *",
+            "*/0 * * * *",
+        ] {
+            assert!(CronSchedule::from_str(expression).is_err(), "{expression}");
+        }
+    }
+}
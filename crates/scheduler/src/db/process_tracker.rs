@@ -10,11 +10,12 @@ use crate::{errors as sch_errors, metrics, scheduler::Store, SchedulerInterface}
 
 #[async_trait::async_trait]
 pub trait ProcessTrackerInterface: Send + Sync + 'static {
+    /// Returns the IDs of the tasks requeued
     async fn reinitialize_limbo_processes(
         &self,
         ids: Vec<String>,
         schedule_time: PrimitiveDateTime,
-    ) -> CustomResult<usize, errors::StorageError>;
+    ) -> CustomResult<Vec<String>, errors::StorageError>;
 
     async fn find_process_by_id(
         &self,
@@ -27,6 +28,15 @@ pub trait ProcessTrackerInterface: Send + Sync + 'static {
         process: storage::ProcessTrackerUpdate,
     ) -> CustomResult<storage::ProcessTracker, errors::StorageError>;
 
+    /// Updates the task only if its status is still one of `statuses`, returning `None` if it is
+    /// not.
+    async fn update_process_if_status_in(
+        &self,
+        this: storage::ProcessTracker,
+        statuses: Vec<storage_enums::ProcessTrackerStatus>,
+        process: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError>;
+
     async fn process_tracker_update_process_status_by_ids(
         &self,
         task_ids: Vec<String>,
@@ -50,6 +60,21 @@ pub trait ProcessTrackerInterface: Send + Sync + 'static {
         status: storage_enums::ProcessTrackerStatus,
         limit: Option<i64>,
     ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+    async fn find_processes_with_expired_lease(
+        &self,
+        lease_expiry: PrimitiveDateTime,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+    async fn filter_processes_by_constraints(
+        &self,
+        constraints: storage::ProcessTrackerListConstraints,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError>;
+    /// Counts the tasks in `status` for each runner, the tasks without a runner being counted
+    /// under `None`
+    async fn count_processes_by_runner(
+        &self,
+        status: storage_enums::ProcessTrackerStatus,
+    ) -> CustomResult<Vec<(Option<String>, i64)>, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -69,7 +94,7 @@ impl ProcessTrackerInterface for Store {
         &self,
         ids: Vec<String>,
         schedule_time: PrimitiveDateTime,
-    ) -> CustomResult<usize, errors::StorageError> {
+    ) -> CustomResult<Vec<String>, errors::StorageError> {
         let conn = connection::pg_connection_write(self).await?;
         storage::ProcessTracker::reinitialize_limbo_processes(&conn, ids, schedule_time)
             .await
@@ -97,6 +122,40 @@ impl ProcessTrackerInterface for Store {
         .into_report()
     }
 
+    async fn find_processes_with_expired_lease(
+        &self,
+        lease_expiry: PrimitiveDateTime,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ProcessTracker::find_processes_with_expired_lease(&conn, lease_expiry, limit)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn filter_processes_by_constraints(
+        &self,
+        constraints: storage::ProcessTrackerListConstraints,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ProcessTracker::filter_by_constraints(&conn, constraints)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn count_processes_by_runner(
+        &self,
+        status: storage_enums::ProcessTrackerStatus,
+    ) -> CustomResult<Vec<(Option<String>, i64)>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ProcessTracker::count_by_runner(&conn, status)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
     async fn insert_process(
         &self,
         new: storage::ProcessTrackerNew,
@@ -120,6 +179,19 @@ impl ProcessTrackerInterface for Store {
             .into_report()
     }
 
+    async fn update_process_if_status_in(
+        &self,
+        this: storage::ProcessTracker,
+        statuses: Vec<storage_enums::ProcessTrackerStatus>,
+        process: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        this.update_if_status_in(&conn, statuses, process)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
     async fn update_process_tracker(
         &self,
         this: storage::ProcessTracker,
@@ -164,11 +236,23 @@ impl ProcessTrackerInterface for MockDb {
 
     async fn reinitialize_limbo_processes(
         &self,
-        _ids: Vec<String>,
-        _schedule_time: PrimitiveDateTime,
-    ) -> CustomResult<usize, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        ids: Vec<String>,
+        schedule_time: PrimitiveDateTime,
+    ) -> CustomResult<Vec<String>, errors::StorageError> {
+        let mut processes = self.processes.lock().await;
+        let mut reinitialized_ids = Vec::new();
+        for process in processes.iter_mut().filter(|process| {
+            process.status == storage_enums::ProcessTrackerStatus::ProcessStarted
+                && ids.contains(&process.id)
+        }) {
+            process.status = storage_enums::ProcessTrackerStatus::Processing;
+            process.schedule_time = Some(schedule_time);
+            process.crash_count += 1;
+            process.updated_at = common_utils::date_time::now();
+            reinitialized_ids.push(process.id.clone());
+        }
+
+        Ok(reinitialized_ids)
     }
 
     async fn find_processes_by_time_status(
@@ -182,6 +266,102 @@ impl ProcessTrackerInterface for MockDb {
         Err(errors::StorageError::MockDbError)?
     }
 
+    async fn find_processes_with_expired_lease(
+        &self,
+        lease_expiry: PrimitiveDateTime,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let processes = self
+            .processes
+            .lock()
+            .await
+            .iter()
+            .filter(|process| {
+                process.status == storage_enums::ProcessTrackerStatus::ProcessStarted
+                    && process.updated_at < lease_expiry
+            })
+            .take(limit.map_or(usize::MAX, |limit| {
+                usize::try_from(limit).unwrap_or(usize::MAX)
+            }))
+            .cloned()
+            .collect();
+
+        Ok(processes)
+    }
+
+    async fn filter_processes_by_constraints(
+        &self,
+        constraints: storage::ProcessTrackerListConstraints,
+    ) -> CustomResult<Vec<storage::ProcessTracker>, errors::StorageError> {
+        let mut processes = self
+            .processes
+            .lock()
+            .await
+            .iter()
+            .filter(|process| {
+                constraints
+                    .runner
+                    .as_ref()
+                    .map_or(true, |runner| process.runner.as_ref() == Some(runner))
+                    && constraints
+                        .status
+                        .map_or(true, |status| process.status == status)
+                    && constraints
+                        .merchant_id
+                        .as_ref()
+                        .map_or(true, |merchant_id| {
+                            process
+                                .tracking_data
+                                .get("merchant_id")
+                                .and_then(|value| value.as_str())
+                                == Some(merchant_id.as_str())
+                        })
+                    && constraints.schedule_time_start.map_or(true, |start| {
+                        process
+                            .schedule_time
+                            .map_or(false, |schedule_time| schedule_time >= start)
+                    })
+                    && constraints.schedule_time_end.map_or(true, |end| {
+                        process
+                            .schedule_time
+                            .map_or(false, |schedule_time| schedule_time <= end)
+                    })
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        processes.sort_by(|a, b| a.schedule_time.cmp(&b.schedule_time));
+
+        let offset = constraints
+            .offset
+            .and_then(|offset| usize::try_from(offset).ok())
+            .unwrap_or(0);
+        let limit = constraints
+            .limit
+            .and_then(|limit| usize::try_from(limit).ok())
+            .unwrap_or(usize::MAX);
+
+        Ok(processes.into_iter().skip(offset).take(limit).collect())
+    }
+
+    async fn count_processes_by_runner(
+        &self,
+        status: storage_enums::ProcessTrackerStatus,
+    ) -> CustomResult<Vec<(Option<String>, i64)>, errors::StorageError> {
+        let mut counts: std::collections::HashMap<Option<String>, i64> =
+            std::collections::HashMap::new();
+        for process in self
+            .processes
+            .lock()
+            .await
+            .iter()
+            .filter(|process| process.status == status)
+        {
+            *counts.entry(process.runner.clone()).or_default() += 1;
+        }
+
+        Ok(counts.into_iter().collect())
+    }
+
     async fn insert_process(
         &self,
         new: storage::ProcessTrackerNew,
@@ -201,6 +381,7 @@ impl ProcessTrackerInterface for MockDb {
             event: new.event,
             created_at: new.created_at,
             updated_at: new.updated_at,
+            crash_count: 0,
         };
         processes.push(process.clone());
         Ok(process)
@@ -208,29 +389,71 @@ impl ProcessTrackerInterface for MockDb {
 
     async fn update_process(
         &self,
-        _this: storage::ProcessTracker,
-        _process: storage::ProcessTrackerUpdate,
+        this: storage::ProcessTracker,
+        process: storage::ProcessTrackerUpdate,
     ) -> CustomResult<storage::ProcessTracker, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let mut processes = self.processes.lock().await;
+        processes
+            .iter_mut()
+            .find(|existing_process| existing_process.id == this.id)
+            .map(|existing_process| {
+                *existing_process = storage::ProcessTrackerUpdateInternal::from(process)
+                    .apply_changeset(existing_process.clone());
+                existing_process.clone()
+            })
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "Process tracker task {} does not exist",
+                    this.id
+                ))
+                .into(),
+            )
     }
 
     async fn update_process_tracker(
         &self,
-        _this: storage::ProcessTracker,
-        _process: storage::ProcessTrackerUpdate,
+        this: storage::ProcessTracker,
+        process: storage::ProcessTrackerUpdate,
     ) -> CustomResult<storage::ProcessTracker, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        self.update_process(this, process).await
+    }
+
+    async fn update_process_if_status_in(
+        &self,
+        this: storage::ProcessTracker,
+        statuses: Vec<storage_enums::ProcessTrackerStatus>,
+        process: storage::ProcessTrackerUpdate,
+    ) -> CustomResult<Option<storage::ProcessTracker>, errors::StorageError> {
+        let mut processes = self.processes.lock().await;
+        Ok(processes
+            .iter_mut()
+            .find(|existing_process| {
+                existing_process.id == this.id && statuses.contains(&existing_process.status)
+            })
+            .map(|existing_process| {
+                *existing_process = storage::ProcessTrackerUpdateInternal::from(process)
+                    .apply_changeset(existing_process.clone());
+                existing_process.clone()
+            }))
     }
 
     async fn process_tracker_update_process_status_by_ids(
         &self,
-        _task_ids: Vec<String>,
-        _task_update: storage::ProcessTrackerUpdate,
+        task_ids: Vec<String>,
+        task_update: storage::ProcessTrackerUpdate,
     ) -> CustomResult<usize, errors::StorageError> {
-        // [#172]: Implement function for `MockDb`
-        Err(errors::StorageError::MockDbError)?
+        let mut processes = self.processes.lock().await;
+        let mut count = 0;
+        for process in processes
+            .iter_mut()
+            .filter(|process| task_ids.contains(&process.id))
+        {
+            *process = storage::ProcessTrackerUpdateInternal::from(task_update.clone())
+                .apply_changeset(process.clone());
+            count += 1;
+        }
+
+        Ok(count)
     }
 }
 
@@ -51,7 +51,6 @@ impl QueueInterface for Store {
         consumer_name: &str,
     ) -> CustomResult<Vec<storage::ProcessTracker>, ProcessTrackerError> {
         crate::consumer::fetch_consumer_tasks(
-            self,
             &self
                 .get_redis_conn()
                 .map_err(ProcessTrackerError::ERedisError)?
@@ -176,12 +175,18 @@ impl QueueInterface for MockDb {
 
     async fn stream_append_entry(
         &self,
-        _stream: &str,
+        stream: &str,
         _entry_id: &RedisEntryId,
-        _fields: Vec<(&str, String)>,
+        fields: Vec<(&str, String)>,
     ) -> CustomResult<(), RedisError> {
-        // [#172]: Implement function for `MockDb`
-        Err(RedisError::StreamAppendFailed)?
+        self.stream_entries.lock().await.push((
+            stream.to_string(),
+            fields
+                .into_iter()
+                .map(|(field, value)| (field.to_string(), value))
+                .collect(),
+        ));
+        Ok(())
     }
 
     async fn get_key(&self, _key: &str) -> CustomResult<Vec<u8>, RedisError> {
@@ -1,5 +1,7 @@
+pub mod cleaner;
 pub mod configs;
 pub mod consumer;
+pub mod cron;
 pub mod db;
 pub mod env;
 pub mod errors;
@@ -1,9 +1,13 @@
-use router_env::{counter_metric, global_meter, histogram_metric, metrics_context};
+use router_env::{
+    counter_metric, global_meter, histogram_metric, histogram_metric_u64, metrics_context,
+};
 
 metrics_context!(CONTEXT);
 global_meter!(PT_METER, "PROCESS_TRACKER");
 
 histogram_metric!(CONSUMER_STATS, PT_METER, "CONSUMER_OPS");
+histogram_metric!(TASK_WAIT_TIME, PT_METER); // Seconds between the schedule time of a task and the start of its execution
+histogram_metric_u64!(RUNNER_QUEUE_DEPTH, PT_METER); // Tasks of a runner waiting in the streams
 
 counter_metric!(PAYMENT_COUNT, PT_METER); // No. of payments created
 counter_metric!(TASKS_ADDED_COUNT, PT_METER); // Tasks added to process tracker
@@ -15,3 +19,7 @@ counter_metric!(TASK_CONSUMED, PT_METER); // Tasks consumed by consumer
 counter_metric!(TASK_PROCESSED, PT_METER); // Tasks completed processing
 counter_metric!(TASK_FINISHED, PT_METER); // Tasks finished
 counter_metric!(TASK_RETRIED, PT_METER); // Tasks added for retries
+counter_metric!(TASKS_RECOVERED_COUNT, PT_METER); // Tasks requeued by the cleaner after their lease expired
+counter_metric!(TASKS_ABANDONED_COUNT, PT_METER); // Tasks finished by the cleaner after crashing too many times
+counter_metric!(TASKS_DEFERRED_COUNT, PT_METER); // Tasks handed back to the producer as their runner was at its concurrency limit
+counter_metric!(RECURRING_JOBS_SCHEDULED, PT_METER); // Occurrences of recurring jobs added to process tracker
@@ -4,7 +4,7 @@ use common_utils::errors::CustomResult;
 use diesel_models::enums::ProcessTrackerStatus;
 use error_stack::{report, IntoReport, ResultExt};
 use router_env::{instrument, tracing};
-use time::Duration;
+use time::{Duration, PrimitiveDateTime};
 use tokio::sync::mpsc;
 
 use super::{
@@ -12,8 +12,12 @@ use super::{
     metrics,
 };
 use crate::{
-    configs::settings::SchedulerSettings, errors, flow::SchedulerFlow,
-    scheduler::SchedulerInterface, utils::*, SchedulerAppState,
+    configs::settings::{RecurringJobSettings, SchedulerSettings},
+    errors,
+    flow::SchedulerFlow,
+    scheduler::SchedulerInterface,
+    utils::*,
+    SchedulerAppState,
 };
 
 #[instrument(skip_all)]
@@ -99,27 +103,138 @@ pub async fn run_producer_flow<T>(
 where
     T: SchedulerAppState,
 {
-    lock_acquire_release::<_, _, _>(state.get_db().as_scheduler(), settings, move || async {
-        let tasks = fetch_producer_tasks(state.get_db().as_scheduler(), settings).await?;
-        debug!("Producer count of tasks {}", tasks.len());
+    lock_acquire_release::<_, _, _>(
+        state.get_db().as_scheduler(),
+        "PRODUCER_LOCK",
+        &settings.producer.lock_key,
+        settings.producer.lock_ttl,
+        move || async {
+            // Failing to schedule recurring jobs must not hold up the tasks already scheduled
+            if let Err(error) =
+                schedule_recurring_jobs(state.get_db().as_scheduler(), settings).await
+            {
+                error!(%error, "Failed to schedule recurring jobs");
+            }
 
-        // [#268]: Allow task based segregation of tasks
+            let tasks = fetch_producer_tasks(state.get_db().as_scheduler(), settings).await?;
+            debug!("Producer count of tasks {}", tasks.len());
 
-        divide_and_append_tasks(
-            state.get_db().as_scheduler(),
-            SchedulerFlow::Producer,
-            tasks,
-            settings,
-        )
-        .await?;
+            // [#268]: Allow task based segregation of tasks
 
-        Ok(())
-    })
+            divide_and_append_tasks(
+                state.get_db().as_scheduler(),
+                SchedulerFlow::Producer,
+                tasks,
+                settings,
+            )
+            .await?;
+
+            record_runner_queue_depth(state.get_db().as_scheduler()).await;
+
+            Ok(())
+        },
+    )
     .await?;
 
     Ok(())
 }
 
+/// Tracking data of the tasks created for the occurrences of a recurring job
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RecurringJobTrackingData {
+    pub job_name: String,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub occurrence: PrimitiveDateTime,
+}
+
+/// Makes sure the next occurrence of every enabled recurring job has a process tracker task.
+///
+/// Only the next occurrence is created, once the producer has picked it up the following one
+/// is created on a later run. Occurrences that passed while no producer was running are skipped.
+#[instrument(skip_all)]
+pub async fn schedule_recurring_jobs(
+    db: &dyn SchedulerInterface,
+    conf: &SchedulerSettings,
+) -> CustomResult<(), errors::ProcessTrackerError> {
+    let now = common_utils::date_time::now();
+
+    for (job_name, job) in conf.recurring_jobs.iter().filter(|(_, job)| !job.disabled) {
+        // A job failing to be scheduled does not keep the other jobs from being scheduled
+        if let Err(error) = schedule_next_occurrence(db, job_name, job, now).await {
+            error!(?error, %job_name, "Failed to schedule the next occurrence of recurring job");
+        }
+    }
+
+    Ok(())
+}
+
+async fn schedule_next_occurrence(
+    db: &dyn SchedulerInterface,
+    job_name: &str,
+    job: &RecurringJobSettings,
+    now: PrimitiveDateTime,
+) -> CustomResult<(), errors::ProcessTrackerError> {
+    let occurrence = match job.cron.next_occurrence(now) {
+        Some(occurrence) => occurrence,
+        None => {
+            warn!(%job_name, "Recurring job has no upcoming occurrence");
+            return Ok(());
+        }
+    };
+
+    let process_tracker_id = format!(
+        "{}_{job_name}_{}",
+        job.runner,
+        occurrence.assume_utc().unix_timestamp()
+    );
+
+    let existing_process = db
+        .find_process_by_id(&process_tracker_id)
+        .await
+        .change_context(errors::ProcessTrackerError::ProcessFetchingFailed)?;
+    if existing_process.is_some() {
+        return Ok(());
+    }
+
+    let tracking_data = serde_json::to_value(RecurringJobTrackingData {
+        job_name: job_name.to_owned(),
+        occurrence,
+    })
+    .into_report()
+    .change_context(errors::ProcessTrackerError::SerializationFailed)?;
+
+    let insert_result = db
+        .insert_process(storage::ProcessTrackerNew {
+            id: process_tracker_id,
+            name: Some(job_name.to_owned()),
+            tag: vec![String::from("RECURRING")],
+            runner: Some(job.runner.clone()),
+            retry_count: 0,
+            schedule_time: Some(occurrence),
+            rule: String::new(),
+            tracking_data,
+            business_status: String::from("Pending"),
+            status: ProcessTrackerStatus::New,
+            event: vec![],
+            created_at: now,
+            updated_at: now,
+        })
+        .await;
+
+    match insert_result {
+        Ok(_) => {
+            debug!(%job_name, %occurrence, "Scheduled next occurrence of recurring job");
+            metrics::RECURRING_JOBS_SCHEDULED.add(&metrics::CONTEXT, 1, &[]);
+            Ok(())
+        }
+        // Scheduled by another producer in the meantime
+        Err(error) if error.current_context().is_db_unique_violation() => Ok(()),
+        Err(error) => {
+            Err(error.change_context(errors::ProcessTrackerError::ProcessInsertionFailed))
+        }
+    }
+}
+
 #[instrument(skip_all)]
 pub async fn fetch_producer_tasks(
     db: &dyn SchedulerInterface,
@@ -171,3 +286,48 @@ pub async fn fetch_producer_tasks(
     metrics::TASKS_PICKED_COUNT.add(&metrics::CONTEXT, new_tasks.len() as u64, &[]);
     Ok(new_tasks)
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used, clippy::unwrap_used)]
+
+    use storage_impl::mock_db::MockDb;
+
+    use super::*;
+
+    fn get_recurring_job(disabled: bool) -> RecurringJobSettings {
+        RecurringJobSettings {
+            cron: "0 3 * * *".parse().unwrap(),
+            runner: "TEST_WORKFLOW".to_string(),
+            disabled,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_next_occurrence_of_enabled_jobs_is_scheduled_once() {
+        let db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create mock DB");
+        let mut settings = SchedulerSettings::default();
+        settings
+            .recurring_jobs
+            .insert("nightly".to_string(), get_recurring_job(false));
+        settings
+            .recurring_jobs
+            .insert("paused".to_string(), get_recurring_job(true));
+
+        schedule_recurring_jobs(&db, &settings).await.unwrap();
+        schedule_recurring_jobs(&db, &settings).await.unwrap();
+
+        let processes = db.processes.lock().await;
+        assert_eq!(processes.len(), 1);
+
+        let process = &processes[0];
+        let tracking_data: RecurringJobTrackingData =
+            serde_json::from_value(process.tracking_data.clone()).unwrap();
+        assert_eq!(tracking_data.job_name, "nightly");
+        assert_eq!(process.schedule_time, Some(tracking_data.occurrence));
+        assert_eq!(process.runner.as_deref(), Some("TEST_WORKFLOW"));
+        assert_eq!(process.status, ProcessTrackerStatus::New);
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     sync::{self, atomic},
     time as std_time,
 };
@@ -46,7 +47,7 @@ where
 pub async fn update_status_and_append<T>(
     state: &T,
     flow: SchedulerFlow,
-    pt_batch: ProcessTrackerBatch,
+    mut pt_batch: ProcessTrackerBatch,
 ) -> CustomResult<(), errors::ProcessTrackerError>
 where
     T: SchedulerInterface + Send + Sync + ?Sized,
@@ -79,8 +80,20 @@ where
                 .reinitialize_limbo_processes(process_ids, common_utils::date_time::now())
                 .await;
             match res {
-                Ok(count) => {
+                Ok(reinitialized_ids) => {
+                    let count = reinitialized_ids.len();
                     logger::debug!("Reinitialized {count} processes");
+                    // Safety: Assuming we won't deal with more than `u64::MAX` tasks at once
+                    #[allow(clippy::as_conversions)]
+                    metrics::TASKS_RECOVERED_COUNT.add(&metrics::CONTEXT, count as u64, &[]);
+                    // The tasks finished or picked up again since they were fetched are left
+                    // out, so that they are not run twice
+                    pt_batch
+                        .trackers
+                        .retain(|process| reinitialized_ids.contains(&process.id));
+                    if pt_batch.trackers.is_empty() {
+                        return Ok(());
+                    }
                     Ok(())
                 }
                 Err(error) => {
@@ -141,13 +154,28 @@ pub fn divide(
 ) -> Vec<ProcessTrackerBatch> {
     let now = common_utils::date_time::now();
     let batch_size = conf.producer.batch_size;
-    divide_into_batches(batch_size, tasks, now, conf)
+
+    let mut lanes: HashMap<String, Vec<storage::ProcessTracker>> = HashMap::new();
+    for task in tasks {
+        lanes
+            .entry(get_stream_for_runner(conf, task.runner.as_deref()))
+            .or_default()
+            .push(task);
+    }
+
+    lanes
+        .into_iter()
+        .flat_map(|(stream_name, tasks)| {
+            divide_into_batches(batch_size, tasks, now, stream_name, conf)
+        })
+        .collect()
 }
 
 pub fn divide_into_batches(
     batch_size: usize,
     tasks: Vec<storage::ProcessTracker>,
     batch_creation_time: time::PrimitiveDateTime,
+    stream_name: String,
     conf: &SchedulerSettings,
 ) -> Vec<ProcessTrackerBatch> {
     let batch_id = Uuid::new_v4().to_string();
@@ -158,7 +186,7 @@ pub fn divide_into_batches(
             let batch = ProcessTrackerBatch {
                 id: batch_id.clone(),
                 group_name: conf.consumer.consumer_group.clone(),
-                stream_name: conf.stream.clone(),
+                stream_name: stream_name.clone(),
                 connection_name: String::new(),
                 created_time: batch_creation_time,
                 rule: String::new(), // is it required?
@@ -170,6 +198,79 @@ pub fn divide_into_batches(
         })
 }
 
+pub fn get_lane_stream_name(conf: &SchedulerSettings, lane_name: &str) -> String {
+    format!("{}_{lane_name}", conf.stream)
+}
+
+/// Returns the stream of the lane the runner is assigned to, or the default stream for the
+/// runners that are not assigned to any lane.
+pub fn get_stream_for_runner(conf: &SchedulerSettings, runner: Option<&str>) -> String {
+    runner
+        .and_then(|runner| {
+            conf.lanes
+                .iter()
+                .find(|(_, lane)| lane.runners.iter().any(|lane_runner| lane_runner == runner))
+        })
+        .map(|(lane_name, _)| get_lane_stream_name(conf, lane_name))
+        .unwrap_or_else(|| conf.stream.clone())
+}
+
+/// Returns every stream the consumer reads from along with its weight, starting with the
+/// default stream.
+pub fn get_lane_streams(conf: &SchedulerSettings) -> Vec<(String, u32)> {
+    std::iter::once((conf.stream.clone(), conf.consumer.default_lane_weight))
+        .chain(
+            conf.lanes
+                .iter()
+                .map(|(lane_name, lane)| (get_lane_stream_name(conf, lane_name), lane.weight)),
+        )
+        .collect()
+}
+
+/// Orders the lane streams for a single consumer run, so that each lane comes first with a
+/// probability proportional to its weight.
+pub fn get_weighted_lane_order(conf: &SchedulerSettings, rng: &mut impl rand::Rng) -> Vec<String> {
+    // Weighted random sampling without replacement, as described by Efraimidis and Spirakis
+    let mut lanes = get_lane_streams(conf)
+        .into_iter()
+        .map(|(stream_name, weight)| (rng.gen::<f64>().powf(1.0 / f64::from(weight)), stream_name))
+        .collect::<Vec<_>>();
+    lanes.sort_by(|(left, _), (right, _)| right.total_cmp(left));
+
+    lanes
+        .into_iter()
+        .map(|(_, stream_name)| stream_name)
+        .collect()
+}
+
+/// Records the number of tasks of each runner that are waiting in the streams to be picked up by
+/// a consumer.
+pub async fn record_runner_queue_depth<T>(state: &T)
+where
+    T: SchedulerInterface + Send + Sync + ?Sized,
+{
+    match state
+        .count_processes_by_runner(ProcessTrackerStatus::Processing)
+        .await
+    {
+        Ok(counts) => {
+            for (runner, depth) in counts {
+                metrics::RUNNER_QUEUE_DEPTH.record(
+                    &metrics::CONTEXT,
+                    u64::try_from(depth).unwrap_or_default(),
+                    &[opentelemetry::KeyValue::new(
+                        "runner",
+                        runner.unwrap_or_else(|| "unknown".to_string()),
+                    )],
+                )
+            }
+        }
+        Err(error) => {
+            logger::warn!(error=%error.current_context(), "Error while counting the queued tasks of each runner")
+        }
+    }
+}
+
 pub async fn get_batches(
     conn: &RedisConnectionPool,
     stream_name: &str,
@@ -246,6 +347,7 @@ pub async fn consumer_operation_handler<E, T: Send + Sync + 'static>(
     settings: sync::Arc<SchedulerSettings>,
     error_handler_fun: E,
     consumer_operation_counter: sync::Arc<atomic::AtomicU64>,
+    runner_limits: sync::Arc<consumer::RunnerLimits>,
     workflow_selector: impl workflows::ProcessTrackerWorkflows<T> + 'static + Copy + std::fmt::Debug,
 ) where
     // Error handler function
@@ -255,7 +357,7 @@ pub async fn consumer_operation_handler<E, T: Send + Sync + 'static>(
     consumer_operation_counter.fetch_add(1, atomic::Ordering::Release);
     let start_time = std_time::Instant::now();
 
-    match consumer::consumer_operations(&state, &settings, workflow_selector).await {
+    match consumer::consumer_operations(&state, &settings, runner_limits, workflow_selector).await {
         Ok(_) => (),
         Err(err) => error_handler_fun(err),
     }
@@ -329,6 +431,26 @@ pub fn get_pm_schedule_time(
     }
 }
 
+pub fn get_outgoing_webhook_retry_schedule_time(
+    mapping: process_data::OutgoingWebhookRetryProcessTrackerMapping,
+    merchant_name: &str,
+    retry_count: i32,
+) -> Option<i32> {
+    let mapping = match mapping.custom_merchant_mapping.get(merchant_name) {
+        Some(map) => map.clone(),
+        None => mapping.default_mapping,
+    };
+
+    if retry_count == 0 {
+        Some(mapping.start_after)
+    } else {
+        get_delay(
+            retry_count,
+            mapping.count.iter().zip(mapping.frequency.iter()),
+        )
+    }
+}
+
 /// Get the delay based on the retry count
 fn get_delay<'a>(
     retry_count: i32,
@@ -349,7 +471,9 @@ fn get_delay<'a>(
 
 pub(crate) async fn lock_acquire_release<T, F, Fut>(
     state: &T,
-    settings: &SchedulerSettings,
+    tag: &str,
+    lock_key: &str,
+    ttl: i64,
     callback: F,
 ) -> CustomResult<(), errors::ProcessTrackerError>
 where
@@ -357,10 +481,7 @@ where
     T: SchedulerInterface + Send + Sync + ?Sized,
     Fut: futures::Future<Output = CustomResult<(), errors::ProcessTrackerError>>,
 {
-    let tag = "PRODUCER_LOCK";
-    let lock_key = &settings.producer.lock_key;
     let lock_val = "LOCKED";
-    let ttl = settings.producer.lock_ttl;
 
     if state
         .acquire_pt_lock(tag, lock_key, lock_val, ttl)
@@ -379,3 +500,129 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::configs::settings::LaneSettings;
+
+    fn get_settings() -> SchedulerSettings {
+        let mut settings = SchedulerSettings::default();
+        settings.lanes.insert(
+            "maintenance".to_string(),
+            LaneSettings {
+                runners: vec!["API_KEY_EXPIRY_WORKFLOW".to_string()],
+                weight: 9,
+            },
+        );
+        settings
+    }
+
+    fn get_task(id: &str, runner: &str) -> storage::ProcessTracker {
+        let now = common_utils::date_time::now();
+        storage::ProcessTracker {
+            id: id.to_string(),
+            name: None,
+            tag: vec![],
+            runner: Some(runner.to_string()),
+            retry_count: 0,
+            schedule_time: Some(now),
+            rule: String::new(),
+            tracking_data: serde_json::json!({}),
+            business_status: "Pending".to_string(),
+            status: ProcessTrackerStatus::Processing,
+            event: vec![],
+            created_at: now,
+            updated_at: now,
+            crash_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_get_stream_for_runner() {
+        let settings = get_settings();
+
+        assert_eq!(
+            get_stream_for_runner(&settings, Some("API_KEY_EXPIRY_WORKFLOW")),
+            "SCHEDULER_STREAM_maintenance"
+        );
+        assert_eq!(
+            get_stream_for_runner(&settings, Some("PAYMENTS_SYNC_WORKFLOW")),
+            "SCHEDULER_STREAM"
+        );
+        assert_eq!(get_stream_for_runner(&settings, None), "SCHEDULER_STREAM");
+    }
+
+    #[test]
+    fn test_get_weighted_lane_order() {
+        let settings = get_settings();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+
+        let runs = 10000;
+        let mut lane_first = 0;
+        for _ in 0..runs {
+            let order = get_weighted_lane_order(&settings, &mut rng);
+            let mut sorted_order = order.clone();
+            sorted_order.sort();
+            assert_eq!(
+                sorted_order,
+                vec![
+                    "SCHEDULER_STREAM".to_string(),
+                    "SCHEDULER_STREAM_maintenance".to_string()
+                ]
+            );
+            if order[0] == "SCHEDULER_STREAM_maintenance" {
+                lane_first += 1;
+            }
+        }
+
+        // The lane has 9 times the weight of the default stream
+        assert!((8500..9500).contains(&lane_first), "{lane_first}");
+    }
+
+    #[test]
+    fn test_divide_into_lane_batches() {
+        let mut settings = get_settings();
+        settings.producer.batch_size = 2;
+        let tasks = vec![
+            get_task("sync_1", "PAYMENTS_SYNC_WORKFLOW"),
+            get_task("expiry_1", "API_KEY_EXPIRY_WORKFLOW"),
+            get_task("sync_2", "PAYMENTS_SYNC_WORKFLOW"),
+            get_task("sync_3", "REFUND_WORKFLOW_ROUTER"),
+        ];
+
+        let mut batches = divide(tasks, &settings)
+            .into_iter()
+            .map(|batch| {
+                (
+                    batch.stream_name,
+                    batch
+                        .trackers
+                        .into_iter()
+                        .map(|task| task.id)
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        batches.sort();
+
+        assert_eq!(
+            batches,
+            vec![
+                (
+                    "SCHEDULER_STREAM".to_string(),
+                    vec!["sync_1".to_string(), "sync_2".to_string()]
+                ),
+                ("SCHEDULER_STREAM".to_string(), vec!["sync_3".to_string()]),
+                (
+                    "SCHEDULER_STREAM_maintenance".to_string(),
+                    vec!["expiry_1".to_string()]
+                ),
+            ]
+        );
+    }
+}
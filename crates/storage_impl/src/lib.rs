@@ -8,11 +8,14 @@ use redis::{kv_store::RedisConnInterface, RedisStore};
 mod address;
 pub mod config;
 pub mod connection;
+mod customers;
 pub mod database;
 pub mod errors;
 mod lookup;
+mod mandate;
 pub mod metrics;
 pub mod mock_db;
+mod payment_method;
 pub mod payments;
 pub mod redis;
 pub mod refund;
@@ -94,6 +97,7 @@ impl<T: DatabaseStore> RouterStore<T> {
             .change_context(StorageError::InitializationError)
             .attach_printable("Failed to create cache store")?;
         cache_store.set_error_callback(cache_error_signal);
+        cache_store.record_reconnections();
         cache_store
             .subscribe_to_channel(inmemory_cache_stream)
             .await
@@ -201,6 +205,70 @@ impl<T: DatabaseStore> KVRouterStore<T> {
         format!("{{{}}}_{}", shard_key, self.drainer_stream_name)
     }
 
+    /// While a merchant is migrated between storage schemes, rows written under `RedisKv` may
+    /// only be present in Redis until the drainer applies them. Reads of such a merchant that
+    /// miss in Postgres are then retried against Redis.
+    pub async fn is_dual_read_enabled(&self, merchant_id: &str) -> bool {
+        let redis_conn = match self.get_redis_conn() {
+            Ok(redis_conn) => redis_conn,
+            Err(error) => {
+                router_env::logger::error!(?error, "Failed to check whether dual-read is enabled");
+                return false;
+            }
+        };
+        redis_conn
+            .exists::<String>(&redis::kv_store::get_dual_read_key(merchant_id))
+            .await
+            .unwrap_or_else(|error| {
+                router_env::logger::error!(?error, "Failed to check whether dual-read is enabled");
+                false
+            })
+    }
+
+    /// While a merchant is migrated from `RedisKv` to `PostgresOnly`, its KV writes are paused
+    /// until the drainer has applied the entries already queued, so that nothing is left on the
+    /// streams once the merchant is switched. The flag is cached in memory for
+    /// [`redis::cache::KV_WRITES_PAUSED_CACHE_TTL`] seconds, so that writes do not check it in
+    /// Redis each time.
+    pub async fn are_kv_writes_paused(&self, merchant_id: &str) -> bool {
+        let key = redis::kv_store::get_kv_writes_paused_key(merchant_id);
+        if let Some(paused) = redis::cache::KV_WRITES_PAUSED_CACHE
+            .get_val::<bool>(&key)
+            .await
+        {
+            return paused;
+        }
+
+        let redis_conn = match self.get_redis_conn() {
+            Ok(redis_conn) => redis_conn,
+            Err(error) => {
+                router_env::logger::error!(?error, "Failed to check whether KV writes are paused");
+                return false;
+            }
+        };
+        match redis_conn.exists::<String>(&key).await {
+            Ok(paused) => {
+                redis::cache::KV_WRITES_PAUSED_CACHE.push(key, paused).await;
+                paused
+            }
+            Err(error) => {
+                router_env::logger::error!(?error, "Failed to check whether KV writes are paused");
+                false
+            }
+        }
+    }
+
+    /// Whether the KV writes of the merchant owning the key are paused. Keys which could belong
+    /// to several merchants are checked against each of them.
+    pub async fn are_kv_writes_paused_for_key(&self, key: &str) -> bool {
+        for merchant_id in redis::kv_store::get_merchant_ids_from_kv_key(key) {
+            if self.are_kv_writes_paused(merchant_id).await {
+                return true;
+            }
+        }
+        false
+    }
+
     pub async fn push_to_drainer_stream<R>(
         &self,
         redis_entry: diesel_models::kv::TypedSql,
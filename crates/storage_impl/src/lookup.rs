@@ -6,8 +6,8 @@ use diesel_models::{
         ReverseLookup as DieselReverseLookup, ReverseLookupNew as DieselReverseLookupNew,
     },
 };
-use error_stack::{IntoReport, ResultExt};
-use redis_interface::SetnxReply;
+use error_stack::{report, IntoReport, ResultExt};
+use redis_interface::{errors::RedisError, SetnxReply};
 
 use crate::{
     diesel_error_to_data_error,
@@ -79,6 +79,11 @@ impl<T: DatabaseStore> ReverseLookupInterface for KVRouterStore<T> {
                     .await
             }
             storage_enums::MerchantStorageScheme::RedisKv => {
+                // The reverse lookup is written for the merchant of the row it points to
+                if self.are_kv_writes_paused_for_key(&new.pk_id).await {
+                    return Err(report!(RedisError::KvWritesPaused)
+                        .to_redis_failed_response(&new.lookup_id));
+                }
                 let created_rev_lookup = DieselReverseLookup {
                     lookup_id: new.lookup_id.clone(),
                     sk_id: new.sk_id.clone(),
@@ -0,0 +1,5 @@
+use diesel_models::mandate::Mandate;
+
+use crate::redis::kv_store::KvStorePartition;
+
+impl KvStorePartition for Mandate {}
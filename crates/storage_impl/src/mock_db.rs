@@ -37,14 +37,26 @@ pub struct MockDb {
     pub mandates: Arc<Mutex<Vec<store::Mandate>>>,
     pub captures: Arc<Mutex<Vec<crate::store::capture::Capture>>>,
     pub merchant_key_store: Arc<Mutex<Vec<crate::store::merchant_key_store::MerchantKeyStore>>>,
+    pub user_key_store: Arc<Mutex<Vec<crate::store::user_key_store::UserKeyStore>>>,
     pub business_profiles: Arc<Mutex<Vec<crate::store::business_profile::BusinessProfile>>>,
     pub reverse_lookups: Arc<Mutex<Vec<store::ReverseLookup>>>,
     pub payment_link: Arc<Mutex<Vec<store::payment_link::PaymentLink>>>,
     pub organizations: Arc<Mutex<Vec<store::organization::Organization>>>,
     pub users: Arc<Mutex<Vec<store::user::User>>>,
     pub user_roles: Arc<Mutex<Vec<store::user_role::UserRole>>>,
+    pub roles: Arc<Mutex<Vec<store::role::Role>>>,
+    pub oidc_configs: Arc<Mutex<Vec<store::oidc_config::OidcConfig>>>,
+    pub oidc_identities: Arc<Mutex<Vec<store::oidc_config::OidcIdentity>>>,
     pub authorizations: Arc<Mutex<Vec<store::authorization::Authorization>>>,
     pub dashboard_metadata: Arc<Mutex<Vec<store::user::dashboard_metadata::DashboardMetadata>>>,
+    pub webhook_delivery_attempts:
+        Arc<Mutex<Vec<store::webhook_delivery_attempt::WebhookDeliveryAttempt>>>,
+    pub routing_algorithm_activations:
+        Arc<Mutex<Vec<store::routing_algorithm_activation::RoutingAlgorithmActivation>>>,
+    pub idempotency_keys: Arc<Mutex<Vec<store::idempotency_key::IdempotencyKey>>>,
+    pub audit_logs: Arc<Mutex<Vec<store::audit_log::AuditLog>>>,
+    /// The entries appended to each stream, as field value pairs
+    pub stream_entries: Arc<Mutex<Vec<(String, Vec<(String, String)>)>>>,
 }
 
 impl MockDb {
@@ -74,14 +86,23 @@ impl MockDb {
             mandates: Default::default(),
             captures: Default::default(),
             merchant_key_store: Default::default(),
+            user_key_store: Default::default(),
             business_profiles: Default::default(),
             reverse_lookups: Default::default(),
             payment_link: Default::default(),
             organizations: Default::default(),
             users: Default::default(),
             user_roles: Default::default(),
+            roles: Default::default(),
+            oidc_configs: Default::default(),
+            oidc_identities: Default::default(),
             authorizations: Default::default(),
             dashboard_metadata: Default::default(),
+            webhook_delivery_attempts: Default::default(),
+            routing_algorithm_activations: Default::default(),
+            idempotency_keys: Default::default(),
+            audit_logs: Default::default(),
+            stream_entries: Default::default(),
         })
     }
 }
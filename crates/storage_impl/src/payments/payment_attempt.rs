@@ -1,5 +1,7 @@
 use api_models::enums::{AuthenticationType, Connector, PaymentMethod, PaymentMethodType};
-use common_utils::{errors::CustomResult, fallback_reverse_lookup_not_found};
+use common_utils::{
+    errors::CustomResult, fallback_dual_read_not_found, fallback_reverse_lookup_not_found,
+};
 use data_models::{
     errors,
     mandates::{MandateAmountData, MandateDataType},
@@ -561,7 +563,9 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<PaymentAttempt, errors::StorageError> {
         match storage_scheme {
-            MerchantStorageScheme::PostgresOnly => {
+            MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                self,
+                merchant_id,
                 self.router_store
                     .find_payment_attempt_by_connector_transaction_id_payment_id_merchant_id(
                         connector_transaction_id,
@@ -569,8 +573,15 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                         merchant_id,
                         storage_scheme,
                     )
-                    .await
-            }
+                    .await,
+                self.find_payment_attempt_by_connector_transaction_id_payment_id_merchant_id(
+                    connector_transaction_id,
+                    payment_id,
+                    merchant_id,
+                    MerchantStorageScheme::RedisKv,
+                )
+                .await
+            ),
             MerchantStorageScheme::RedisKv => {
                 // We assume that PaymentAttempt <=> PaymentIntent is a one-to-one relation for now
                 let lookup_id = format!("pa_conn_trans_{merchant_id}_{connector_transaction_id}");
@@ -615,7 +626,17 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                 )
         };
         match storage_scheme {
-            MerchantStorageScheme::PostgresOnly => database_call().await,
+            MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                self,
+                merchant_id,
+                database_call().await,
+                self.find_payment_attempt_last_successful_attempt_by_payment_id_merchant_id(
+                    payment_id,
+                    merchant_id,
+                    MerchantStorageScheme::RedisKv,
+                )
+                .await
+            ),
             MerchantStorageScheme::RedisKv => {
                 let key = format!("mid_{merchant_id}_pid_{payment_id}");
                 let pattern = "pa_*";
@@ -663,7 +684,17 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                 )
         };
         match storage_scheme {
-            MerchantStorageScheme::PostgresOnly => database_call().await,
+            MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                self,
+                merchant_id,
+                database_call().await,
+                self.find_payment_attempt_last_successful_or_partially_captured_attempt_by_payment_id_merchant_id(
+                    payment_id,
+                    merchant_id,
+                    MerchantStorageScheme::RedisKv,
+                )
+                .await
+            ),
             MerchantStorageScheme::RedisKv => {
                 let key = format!("mid_{merchant_id}_pid_{payment_id}");
                 let pattern = "pa_*";
@@ -706,15 +737,23 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<PaymentAttempt, errors::StorageError> {
         match storage_scheme {
-            MerchantStorageScheme::PostgresOnly => {
+            MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                self,
+                merchant_id,
                 self.router_store
                     .find_payment_attempt_by_merchant_id_connector_txn_id(
                         merchant_id,
                         connector_txn_id,
                         storage_scheme,
                     )
-                    .await
-            }
+                    .await,
+                self.find_payment_attempt_by_merchant_id_connector_txn_id(
+                    merchant_id,
+                    connector_txn_id,
+                    MerchantStorageScheme::RedisKv,
+                )
+                .await
+            ),
             MerchantStorageScheme::RedisKv => {
                 let lookup_id = format!("pa_conn_trans_{merchant_id}_{connector_txn_id}");
                 let lookup = fallback_reverse_lookup_not_found!(
@@ -764,7 +803,9 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<PaymentAttempt, errors::StorageError> {
         match storage_scheme {
-            MerchantStorageScheme::PostgresOnly => {
+            MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                self,
+                merchant_id,
                 self.router_store
                     .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
                         payment_id,
@@ -772,8 +813,15 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                         attempt_id,
                         storage_scheme,
                     )
-                    .await
-            }
+                    .await,
+                self.find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+                    payment_id,
+                    merchant_id,
+                    attempt_id,
+                    MerchantStorageScheme::RedisKv,
+                )
+                .await
+            ),
             MerchantStorageScheme::RedisKv => {
                 let key = format!("mid_{merchant_id}_pid_{payment_id}");
                 let field = format!("pa_{attempt_id}");
@@ -806,15 +854,23 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<PaymentAttempt, errors::StorageError> {
         match storage_scheme {
-            MerchantStorageScheme::PostgresOnly => {
+            MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                self,
+                merchant_id,
                 self.router_store
                     .find_payment_attempt_by_attempt_id_merchant_id(
                         attempt_id,
                         merchant_id,
                         storage_scheme,
                     )
-                    .await
-            }
+                    .await,
+                self.find_payment_attempt_by_attempt_id_merchant_id(
+                    attempt_id,
+                    merchant_id,
+                    MerchantStorageScheme::RedisKv,
+                )
+                .await
+            ),
             MerchantStorageScheme::RedisKv => {
                 let lookup_id = format!("pa_{merchant_id}_{attempt_id}");
                 let lookup = fallback_reverse_lookup_not_found!(
@@ -862,15 +918,23 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<PaymentAttempt, errors::StorageError> {
         match storage_scheme {
-            MerchantStorageScheme::PostgresOnly => {
+            MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                self,
+                merchant_id,
                 self.router_store
                     .find_payment_attempt_by_preprocessing_id_merchant_id(
                         preprocessing_id,
                         merchant_id,
                         storage_scheme,
                     )
-                    .await
-            }
+                    .await,
+                self.find_payment_attempt_by_preprocessing_id_merchant_id(
+                    preprocessing_id,
+                    merchant_id,
+                    MerchantStorageScheme::RedisKv,
+                )
+                .await
+            ),
             MerchantStorageScheme::RedisKv => {
                 let lookup_id = format!("pa_preprocessing_{merchant_id}_{preprocessing_id}");
                 let lookup = fallback_reverse_lookup_not_found!(
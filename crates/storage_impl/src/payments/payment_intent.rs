@@ -1,10 +1,10 @@
 #[cfg(feature = "olap")]
 use async_bb8_diesel::{AsyncConnection, AsyncRunQueryDsl};
-use common_utils::{date_time, ext_traits::Encode};
+use common_utils::{date_time, ext_traits::Encode, fallback_dual_read_not_found};
 #[cfg(feature = "olap")]
 use data_models::payments::payment_intent::PaymentIntentFetchConstraints;
 use data_models::{
-    errors::StorageError,
+    errors::{self, StorageError},
     payments::{
         payment_attempt::PaymentAttempt,
         payment_intent::{PaymentIntentInterface, PaymentIntentNew, PaymentIntentUpdate},
@@ -205,7 +205,17 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
                 })
         };
         match storage_scheme {
-            MerchantStorageScheme::PostgresOnly => database_call().await,
+            MerchantStorageScheme::PostgresOnly => fallback_dual_read_not_found!(
+                self,
+                merchant_id,
+                database_call().await.map(PaymentIntent::from_storage_model),
+                self.find_payment_intent_by_payment_id_merchant_id(
+                    payment_id,
+                    merchant_id,
+                    MerchantStorageScheme::RedisKv,
+                )
+                .await
+            ),
 
             MerchantStorageScheme::RedisKv => {
                 let key = format!("mid_{merchant_id}_pid_{payment_id}");
@@ -223,9 +233,9 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
                     database_call,
                 ))
                 .await
+                .map(PaymentIntent::from_storage_model)
             }
         }
-        .map(PaymentIntent::from_storage_model)
     }
 
     async fn get_active_payment_attempt(
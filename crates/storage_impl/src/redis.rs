@@ -40,6 +40,13 @@ impl RedisStore {
         });
     }
 
+    pub fn record_reconnections(&self) {
+        let redis_clone = self.redis_conn.clone();
+        tokio::spawn(async move {
+            redis_clone.on_reconnect().await;
+        });
+    }
+
     pub async fn subscribe_to_channel(
         &self,
         channel: &str,
@@ -1,4 +1,11 @@
-use std::{any::Any, borrow::Cow, sync::Arc};
+use std::{
+    any::Any,
+    borrow::Cow,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 
 use common_utils::{
     errors::{self, CustomResult},
@@ -11,7 +18,10 @@ use moka::future::Cache as MokaCache;
 use once_cell::sync::Lazy;
 use redis_interface::{errors::RedisError, RedisValue};
 
-use super::{kv_store::RedisConnInterface, pub_sub::PubSubInterface};
+use super::{
+    kv_store::{escape_glob, is_kv_key, RedisConnInterface},
+    pub_sub::PubSubInterface,
+};
 
 pub(crate) const PUB_SUB_CHANNEL: &str = "hyperswitch_invalidate";
 
@@ -21,9 +31,26 @@ const CONFIG_CACHE_PREFIX: &str = "config";
 /// Prefix for accounts cache key
 const ACCOUNTS_CACHE_PREFIX: &str = "accounts";
 
+/// Prefix for routing cache key
+const ROUTING_CACHE_PREFIX: &str = "routing";
+
+/// Prefix for cgraph cache key
+const CGRAPH_CACHE_PREFIX: &str = "cgraph";
+
+/// Prefix for the invalidation of all the keys starting with a prefix
+const KEY_PREFIX_CACHE_PREFIX: &str = "key_prefix";
+
+/// Prefix for the invalidation of all the keys of a merchant
+const MERCHANT_CACHE_PREFIX: &str = "merchant";
+
 /// Prefix for all kinds of cache key
 const ALL_CACHE_PREFIX: &str = "all_cache_kind";
 
+/// Set of the keys of the values cached in Redis by [`get_or_populate_redis`]. The cached values
+/// share the keyspace with locks, idempotency records and the like, so the invalidations by prefix
+/// or by merchant only delete the Redis keys held in this set.
+pub const REDIS_CACHE_KEYS_SET: &str = "cache_keys";
+
 /// Time to live 30 mins
 const CACHE_TTL: u64 = 30 * 60;
 
@@ -34,11 +61,44 @@ const CACHE_TTI: u64 = 10 * 60;
 const MAX_CAPACITY: u64 = 30;
 
 /// Config Cache with time_to_live as 30 mins and time_to_idle as 10 mins.
-pub static CONFIG_CACHE: Lazy<Cache> = Lazy::new(|| Cache::new(CACHE_TTL, CACHE_TTI, None));
+pub static CONFIG_CACHE: Lazy<Cache> =
+    Lazy::new(|| Cache::new("CONFIG_CACHE", CACHE_TTL, CACHE_TTI, None));
 
 /// Accounts cache with time_to_live as 30 mins and size limit
 pub static ACCOUNTS_CACHE: Lazy<Cache> =
-    Lazy::new(|| Cache::new(CACHE_TTL, CACHE_TTI, Some(MAX_CAPACITY)));
+    Lazy::new(|| Cache::new("ACCOUNTS_CACHE", CACHE_TTL, CACHE_TTI, Some(MAX_CAPACITY)));
+
+/// Routing algorithms cache with time_to_live as 30 mins and size limit
+pub static ROUTING_CACHE: Lazy<Cache> =
+    Lazy::new(|| Cache::new("ROUTING_CACHE", CACHE_TTL, CACHE_TTI, Some(MAX_CAPACITY)));
+
+/// Constraint graphs cache with time_to_live as 30 mins and size limit
+pub static CGRAPH_CACHE: Lazy<Cache> =
+    Lazy::new(|| Cache::new("CGRAPH_CACHE", CACHE_TTL, CACHE_TTI, Some(MAX_CAPACITY)));
+
+/// Time to live of the cached KV writes paused flags. The storage scheme migration waits for
+/// longer than this after pausing the KV writes of a merchant before relying on the pause.
+pub const KV_WRITES_PAUSED_CACHE_TTL: u64 = 2;
+
+/// KV writes paused flags of the merchants, see [`crate::KVRouterStore::are_kv_writes_paused`]
+pub static KV_WRITES_PAUSED_CACHE: Lazy<Cache> = Lazy::new(|| {
+    Cache::new(
+        "KV_WRITES_PAUSED_CACHE",
+        KV_WRITES_PAUSED_CACHE_TTL,
+        KV_WRITES_PAUSED_CACHE_TTL,
+        None,
+    )
+});
+
+/// All the in-memory caches of the application
+pub fn named_caches() -> [&'static Cache; 4] {
+    [
+        &CONFIG_CACHE,
+        &ACCOUNTS_CACHE,
+        &ROUTING_CACHE,
+        &CGRAPH_CACHE,
+    ]
+}
 
 /// Trait which defines the behaviour of types that's gonna be stored in Cache
 pub trait Cacheable: Any + Send + Sync + DynClone {
@@ -48,7 +108,13 @@ pub trait Cacheable: Any + Send + Sync + DynClone {
 pub enum CacheKind<'a> {
     Config(Cow<'a, str>),
     Accounts(Cow<'a, str>),
+    Routing(Cow<'a, str>),
+    CGraph(Cow<'a, str>),
     All(Cow<'a, str>),
+    /// Invalidates the keys starting with the prefix in all the caches
+    KeyPrefix(Cow<'a, str>),
+    /// Invalidates the keys of the merchant in all the caches, see [`is_merchant_key`]
+    Merchant(Cow<'a, str>),
 }
 
 impl<'a> From<CacheKind<'a>> for RedisValue {
@@ -56,7 +122,11 @@ impl<'a> From<CacheKind<'a>> for RedisValue {
         let value = match kind {
             CacheKind::Config(s) => format!("{CONFIG_CACHE_PREFIX},{s}"),
             CacheKind::Accounts(s) => format!("{ACCOUNTS_CACHE_PREFIX},{s}"),
+            CacheKind::Routing(s) => format!("{ROUTING_CACHE_PREFIX},{s}"),
+            CacheKind::CGraph(s) => format!("{CGRAPH_CACHE_PREFIX},{s}"),
             CacheKind::All(s) => format!("{ALL_CACHE_PREFIX},{s}"),
+            CacheKind::KeyPrefix(s) => format!("{KEY_PREFIX_CACHE_PREFIX},{s}"),
+            CacheKind::Merchant(s) => format!("{MERCHANT_CACHE_PREFIX},{s}"),
         };
         Self::from_string(value)
     }
@@ -73,7 +143,11 @@ impl<'a> TryFrom<RedisValue> for CacheKind<'a> {
         match split.0 {
             ACCOUNTS_CACHE_PREFIX => Ok(Self::Accounts(Cow::Owned(split.1.to_string()))),
             CONFIG_CACHE_PREFIX => Ok(Self::Config(Cow::Owned(split.1.to_string()))),
+            ROUTING_CACHE_PREFIX => Ok(Self::Routing(Cow::Owned(split.1.to_string()))),
+            CGRAPH_CACHE_PREFIX => Ok(Self::CGraph(Cow::Owned(split.1.to_string()))),
             ALL_CACHE_PREFIX => Ok(Self::All(Cow::Owned(split.1.to_string()))),
+            KEY_PREFIX_CACHE_PREFIX => Ok(Self::KeyPrefix(Cow::Owned(split.1.to_string()))),
+            MERCHANT_CACHE_PREFIX => Ok(Self::Merchant(Cow::Owned(split.1.to_string()))),
             _ => Err(validation_err.into()),
         }
     }
@@ -91,7 +165,98 @@ where
 dyn_clone::clone_trait_object!(Cacheable);
 
 pub struct Cache {
+    name: &'static str,
     inner: MokaCache<String, Arc<dyn Cacheable>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// Statistics of a cache, local to the running instance
+#[derive(Clone, Debug)]
+pub struct CacheStatistics {
+    pub name: &'static str,
+    /// Lookups which found an entry, since the instance started
+    pub hits: u64,
+    /// Lookups which found no entry, since the instance started
+    pub misses: u64,
+    pub entry_count: u64,
+    /// Size of the keys and of the values held, not counting the memory the values point to
+    pub estimated_size_in_bytes: u64,
+}
+
+/// Keys of a merchant are the merchant ID itself and the keys made of the merchant ID followed
+/// by a suffix, optionally preceded by the prefix of the routing and cgraph keys. Keys of other
+/// merchants whose ID starts with the same characters may match too, which only causes extra
+/// cache misses.
+pub fn is_merchant_key(key: &str, merchant_id: &str) -> bool {
+    let key = ["dsl_", "kgraph_"]
+        .iter()
+        .find_map(|prefix| key.strip_prefix(prefix))
+        .unwrap_or(key);
+    key.strip_prefix(merchant_id)
+        .map_or(false, |suffix| suffix.is_empty() || suffix.starts_with('_'))
+}
+
+/// Invalidates the entries matching the predicate in all the caches, returning their keys
+pub async fn invalidate_matching_keys(predicate: impl Fn(&str) -> bool) -> Vec<String> {
+    let mut keys = Vec::new();
+    for cache in named_caches() {
+        keys.extend(cache.remove_matching(&predicate).await);
+    }
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// Redis patterns matching the keys of a merchant, see [`is_merchant_key`]
+pub fn get_merchant_key_patterns(merchant_id: &str) -> Vec<String> {
+    let merchant_id = escape_glob(merchant_id);
+    ["", "dsl_", "kgraph_"]
+        .iter()
+        .flat_map(|prefix| {
+            [
+                format!("{prefix}{merchant_id}"),
+                format!("{prefix}{merchant_id}_*"),
+            ]
+        })
+        .collect()
+}
+
+/// Redis pattern matching the keys starting with the prefix
+pub fn get_key_prefix_pattern(prefix: &str) -> String {
+    format!("{}*", escape_glob(prefix))
+}
+
+/// Deletes the cached values matching the patterns and the predicate from Redis, returning
+/// their keys. Unlike the in-memory entries, which every instance invalidates on its own, the
+/// values cached in Redis are shared and are deleted once by the instance requesting the
+/// invalidation. Only the keys of [`REDIS_CACHE_KEYS_SET`] are deleted, and never the KV rows of
+/// the merchants or their reverse lookups.
+pub async fn delete_matching_redis_keys(
+    redis_conn: &redis_interface::RedisConnectionPool,
+    patterns: &[String],
+    predicate: impl Fn(&str) -> bool,
+) -> CustomResult<Vec<String>, RedisError> {
+    let mut keys = Vec::new();
+    for pattern in patterns {
+        keys.extend(
+            redis_conn
+                .scan_set_members(REDIS_CACHE_KEYS_SET, pattern, None)
+                .await?,
+        );
+    }
+    keys.retain(|key| predicate(key) && !is_kv_key(key));
+    keys.sort();
+    keys.dedup();
+    for key in &keys {
+        redis_conn.delete_key(key).await?;
+    }
+    if !keys.is_empty() {
+        redis_conn
+            .remove_members_from_set(REDIS_CACHE_KEYS_SET, keys.clone())
+            .await?;
+    }
+    Ok(keys)
 }
 
 impl std::ops::Deref for Cache {
@@ -107,17 +272,29 @@ impl Cache {
     /// `time_to_live`: Time in seconds before an object is stored in a caching system before it’s deleted
     /// `time_to_idle`: Time in seconds before a `get` or `insert` operation an object is stored in a caching system before it's deleted
     /// `max_capacity`: Max size in MB's that the cache can hold
-    pub fn new(time_to_live: u64, time_to_idle: u64, max_capacity: Option<u64>) -> Self {
+    /// `name`: Name of the cache reported in its statistics
+    pub fn new(
+        name: &'static str,
+        time_to_live: u64,
+        time_to_idle: u64,
+        max_capacity: Option<u64>,
+    ) -> Self {
         let mut cache_builder = MokaCache::builder()
             .time_to_live(std::time::Duration::from_secs(time_to_live))
-            .time_to_idle(std::time::Duration::from_secs(time_to_idle));
+            .time_to_idle(std::time::Duration::from_secs(time_to_idle))
+            .weigher(|key: &String, value: &Arc<dyn Cacheable>| {
+                u32::try_from(key.len() + std::mem::size_of_val(&**value)).unwrap_or(u32::MAX)
+            });
 
         if let Some(capacity) = max_capacity {
             cache_builder = cache_builder.max_capacity(capacity * 1024 * 1024);
         }
 
         Self {
+            name,
             inner: cache_builder.build(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
         }
     }
 
@@ -126,13 +303,42 @@ impl Cache {
     }
 
     pub async fn get_val<T: Clone + Cacheable>(&self, key: &str) -> Option<T> {
-        let val = self.get(key).await?;
-        (*val).as_any().downcast_ref::<T>().cloned()
+        let val = self.get(key).await;
+        let counter = if val.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        (*val?).as_any().downcast_ref::<T>().cloned()
     }
 
     pub async fn remove(&self, key: &str) {
         self.invalidate(key).await;
     }
+
+    /// Invalidates the entries whose key matches the predicate, returning their keys
+    pub async fn remove_matching(&self, predicate: impl Fn(&str) -> bool) -> Vec<String> {
+        let keys = self
+            .iter()
+            .filter(|(key, _)| predicate(key.as_str()))
+            .map(|(key, _)| String::clone(&key))
+            .collect::<Vec<_>>();
+        for key in &keys {
+            self.invalidate(key).await;
+        }
+        keys
+    }
+
+    pub fn statistics(&self) -> CacheStatistics {
+        CacheStatistics {
+            name: self.name,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entry_count: self.entry_count(),
+            estimated_size_in_bytes: self.weighted_size(),
+        }
+    }
 }
 
 pub async fn get_or_populate_redis<T, F, Fut>(
@@ -161,6 +367,10 @@ where
             .serialize_and_set_key(key, &data)
             .await
             .change_context(StorageError::KVError)?;
+        redis
+            .add_member_to_set(REDIS_CACHE_KEYS_SET, key)
+            .await
+            .change_context(StorageError::KVError)?;
         Ok::<_, Report<StorageError>>(data)
     };
     match redis_val {
@@ -263,7 +473,7 @@ mod cache_tests {
 
     #[tokio::test]
     async fn construct_and_get_cache() {
-        let cache = Cache::new(1800, 1800, None);
+        let cache = Cache::new("test", 1800, 1800, None);
         cache.push("key".to_string(), "val".to_string()).await;
         assert_eq!(
             cache.get_val::<String>("key").await,
@@ -273,14 +483,14 @@ mod cache_tests {
 
     #[tokio::test]
     async fn eviction_on_size_test() {
-        let cache = Cache::new(2, 2, Some(0));
+        let cache = Cache::new("test", 2, 2, Some(0));
         cache.push("key".to_string(), "val".to_string()).await;
         assert_eq!(cache.get_val::<String>("key").await, None);
     }
 
     #[tokio::test]
     async fn invalidate_cache_for_key() {
-        let cache = Cache::new(1800, 1800, None);
+        let cache = Cache::new("test", 1800, 1800, None);
         cache.push("key".to_string(), "val".to_string()).await;
 
         cache.remove("key").await;
@@ -288,9 +498,76 @@ mod cache_tests {
         assert_eq!(cache.get_val::<String>("key").await, None);
     }
 
+    #[tokio::test]
+    async fn invalidate_cache_for_matching_keys() {
+        let cache = Cache::new("test", 1800, 1800, None);
+        cache
+            .push("merchant_1".to_string(), "val".to_string())
+            .await;
+        cache
+            .push("merchant_1_config".to_string(), "val".to_string())
+            .await;
+        cache
+            .push("merchant_10".to_string(), "val".to_string())
+            .await;
+
+        let removed = cache
+            .remove_matching(|key| is_merchant_key(key, "merchant_1"))
+            .await;
+
+        assert_eq!(removed.len(), 2);
+        assert_eq!(cache.get_val::<String>("merchant_1").await, None);
+        assert_eq!(
+            cache.get_val::<String>("merchant_10").await,
+            Some(String::from("val"))
+        );
+    }
+
+    #[test]
+    fn merchant_key_patterns_escape_the_merchant_id() {
+        assert_eq!(
+            get_merchant_key_patterns("merchant_*"),
+            vec![
+                "merchant_\\*",
+                "merchant_\\*_*",
+                "dsl_merchant_\\*",
+                "dsl_merchant_\\*_*",
+                "kgraph_merchant_\\*",
+                "kgraph_merchant_\\*_*",
+            ]
+        );
+        assert_eq!(get_key_prefix_pattern("pm_filters_"), "pm_filters_*");
+    }
+
+    #[tokio::test]
+    async fn statistics_count_hits_and_misses() {
+        let cache = Cache::new("test", 1800, 1800, None);
+        cache.push("key".to_string(), "val".to_string()).await;
+        cache.get_val::<String>("key").await;
+        cache.get_val::<String>("other_key").await;
+
+        let statistics = cache.statistics();
+        assert_eq!(statistics.hits, 1);
+        assert_eq!(statistics.misses, 1);
+    }
+
+    #[tokio::test]
+    async fn statistics_estimate_the_size_of_the_entries() {
+        let cache = Cache::new("test", 1800, 1800, None);
+        cache.push("key".to_string(), "val".to_string()).await;
+        cache.run_pending_tasks().await;
+
+        let statistics = cache.statistics();
+        assert_eq!(statistics.entry_count, 1);
+        assert_eq!(
+            Some(statistics.estimated_size_in_bytes),
+            u64::try_from("key".len() + std::mem::size_of::<String>()).ok()
+        );
+    }
+
     #[tokio::test]
     async fn eviction_on_time_test() {
-        let cache = Cache::new(2, 2, None);
+        let cache = Cache::new("test", 2, 2, None);
         cache.push("key".to_string(), "val".to_string()).await;
         tokio::time::sleep(std::time::Duration::from_secs(3)).await;
         assert_eq!(cache.get_val::<String>("key").await, None);
@@ -1,8 +1,8 @@
-use std::{fmt::Debug, sync::Arc};
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
 
 use common_utils::errors::CustomResult;
 use error_stack::IntoReport;
-use redis_interface::errors::RedisError;
+use redis_interface::{errors::RedisError, RedisConnectionPool};
 use router_derive::TryGetEnumVariant;
 use router_env::logger;
 use serde::de;
@@ -28,6 +28,14 @@ pub enum PartitionKey<'a> {
     MerchantIdPaymentIdCombination {
         combination: &'a str,
     },
+    MerchantIdCustomerId {
+        merchant_id: &'a str,
+        customer_id: &'a str,
+    },
+    MerchantIdMandateId {
+        merchant_id: &'a str,
+        mandate_id: &'a str,
+    },
 }
 
 impl<'a> std::fmt::Display for PartitionKey<'a> {
@@ -40,8 +48,172 @@ impl<'a> std::fmt::Display for PartitionKey<'a> {
             PartitionKey::MerchantIdPaymentIdCombination { combination } => {
                 f.write_str(combination)
             }
+            PartitionKey::MerchantIdCustomerId {
+                merchant_id,
+                customer_id,
+            } => f.write_str(&format!("mid_{merchant_id}_cust_{customer_id}")),
+            PartitionKey::MerchantIdMandateId {
+                merchant_id,
+                mandate_id,
+            } => f.write_str(&format!("mid_{merchant_id}_mandate_{mandate_id}")),
+        }
+    }
+}
+
+/// Key of the flag enabling dual-read for a merchant, see [`KVRouterStore::is_dual_read_enabled`]
+pub fn get_dual_read_key(merchant_id: &str) -> String {
+    format!("dual_read_{merchant_id}")
+}
+
+/// Key of the flag pausing the KV writes of a merchant, see
+/// [`KVRouterStore::are_kv_writes_paused`]
+pub fn get_kv_writes_paused_key(merchant_id: &str) -> String {
+    format!("kv_writes_paused_{merchant_id}")
+}
+
+/// Kinds of the Redis keys holding the KV rows of a merchant, which are named
+/// `mid_{merchant_id}_{kind}_{id}`: its payments (with their attempts, refunds and addresses),
+/// customers (with their payment methods) and mandates
+pub const MERCHANT_KV_KEY_KINDS: [&str; 3] = ["pid", "cust", "mandate"];
+
+/// Prefix of the keys holding the reverse lookups of the KV rows, whose `pk_id` is the key of
+/// the row they point to
+pub const REVERSE_LOOKUP_KEY_PREFIX: &str = "reverse_lookup_";
+
+/// Returns the merchants whose KV rows may be held by the key, if it is one of the keys named
+/// after [`MERCHANT_KV_KEY_KINDS`]. A merchant ID may itself contain `_{kind}_`, in which case the
+/// key can be read as belonging to several merchants, see [`resolve_merchant_id_from_kv_key`].
+pub fn get_merchant_ids_from_kv_key(key: &str) -> Vec<&str> {
+    let key = match key.strip_prefix("mid_") {
+        Some(key) => key,
+        None => return Vec::new(),
+    };
+    key.match_indices('_')
+        .map(|(merchant_id_end, _)| merchant_id_end)
+        .filter(|&merchant_id_end| {
+            merchant_id_end > 0
+                && MERCHANT_KV_KEY_KINDS.iter().any(|kind| {
+                    key[merchant_id_end + 1..]
+                        .strip_prefix(kind)
+                        .map_or(false, |id| id.starts_with('_'))
+                })
+        })
+        .map(|merchant_id_end| &key[..merchant_id_end])
+        .collect()
+}
+
+/// Whether the key holds KV rows of merchants or their reverse lookups
+pub fn is_kv_key(key: &str) -> bool {
+    key.starts_with(REVERSE_LOOKUP_KEY_PREFIX) || !get_merchant_ids_from_kv_key(key).is_empty()
+}
+
+/// Redis patterns matching the keys of [`is_merchant_kv_key`]. The lookup IDs of the reverse
+/// lookups hold the merchant ID, except for those of the payment methods.
+pub fn get_merchant_kv_key_patterns(merchant_id: &str) -> Vec<String> {
+    let merchant_id = escape_glob(merchant_id);
+    MERCHANT_KV_KEY_KINDS
+        .iter()
+        .map(|kind| format!("mid_{merchant_id}_{kind}_*"))
+        .chain([
+            format!("{REVERSE_LOOKUP_KEY_PREFIX}*{merchant_id}_*"),
+            format!("{REVERSE_LOOKUP_KEY_PREFIX}payment_method_*"),
+        ])
+        .collect()
+}
+
+/// Escapes the characters of the value which `SCAN` patterns would otherwise interpret
+pub(crate) fn escape_glob(value: &str) -> String {
+    value.chars().fold(String::new(), |mut escaped, c| {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        escaped
+    })
+}
+
+/// The merchant of a KV row, as serialized in the hash holding it
+#[derive(serde::Deserialize)]
+struct KvRowMerchant {
+    merchant_id: String,
+}
+
+/// Returns the merchant whose KV rows are held by the key. Keys which could belong to several
+/// merchants are resolved from the merchant ID of the rows they hold.
+pub async fn resolve_merchant_id_from_kv_key(
+    redis_conn: &RedisConnectionPool,
+    key: &str,
+) -> CustomResult<Option<String>, RedisError> {
+    let merchant_ids = get_merchant_ids_from_kv_key(key);
+    if merchant_ids.len() <= 1 {
+        return Ok(merchant_ids
+            .first()
+            .map(|merchant_id| merchant_id.to_string()));
+    }
+
+    let rows = redis_conn
+        .get_hash_fields::<HashMap<String, String>>(key)
+        .await?;
+    Ok(rows
+        .values()
+        .find_map(|row| serde_json::from_str::<KvRowMerchant>(row).ok())
+        .map(|row| row.merchant_id)
+        .filter(|merchant_id| merchant_ids.contains(&merchant_id.as_str())))
+}
+
+/// Whether the key holds KV rows of the merchant, or the reverse lookup of such a row
+pub async fn is_merchant_kv_key(
+    redis_conn: &RedisConnectionPool,
+    key: &str,
+    merchant_id: &str,
+) -> CustomResult<bool, RedisError> {
+    let row_key = if key.starts_with(REVERSE_LOOKUP_KEY_PREFIX) {
+        match redis_conn
+            .get_and_deserialize_key::<diesel_models::reverse_lookup::ReverseLookup>(
+                key,
+                "ReverseLookup",
+            )
+            .await
+        {
+            Ok(lookup) => lookup.pk_id,
+            Err(error) if matches!(error.current_context(), RedisError::NotFound) => {
+                return Ok(false)
+            }
+            Err(error) => return Err(error),
         }
+    } else {
+        key.to_owned()
+    };
+
+    if !get_merchant_ids_from_kv_key(&row_key).contains(&merchant_id) {
+        return Ok(false);
     }
+    Ok(resolve_merchant_id_from_kv_key(redis_conn, &row_key)
+        .await?
+        .map_or(false, |owner| owner == merchant_id))
+}
+
+/// Returns the rows held in the fields matching the pattern of the KV keys of the given kind of
+/// the merchant, which are the ones yet to be drained or evicted. Rows of other merchants whose ID
+/// starts with `{merchant_id}_{kind}_` are also returned and are left to the caller to filter out.
+pub async fn scan_merchant_kv_rows<T>(
+    redis_conn: &RedisConnectionPool,
+    merchant_id: &str,
+    kind: &str,
+    field_pattern: &str,
+) -> CustomResult<Vec<T>, RedisError>
+where
+    T: de::DeserializeOwned,
+{
+    let keys = redis_conn
+        .scan_keys(&format!("mid_{}_{kind}_*", escape_glob(merchant_id)), None)
+        .await?;
+    let rows = futures::future::try_join_all(
+        keys.iter()
+            .map(|key| redis_conn.hscan_and_deserialize::<T>(key, field_pattern, None)),
+    )
+    .await?;
+    Ok(rows.into_iter().flatten().collect())
 }
 
 pub trait RedisConnInterface {
@@ -58,6 +230,9 @@ pub enum KvOperation<'a, S: serde::Serialize + Debug> {
     HGet(&'a str),
     Get,
     Scan(&'a str),
+    /// Scans a replica rather than the primary, for listings which tolerate replication lag
+    ScanFromReplica(&'a str),
+    HDel(&'a str, TypedSql),
 }
 
 #[derive(TryGetEnumVariant)]
@@ -69,6 +244,7 @@ pub enum KvResult<T: de::DeserializeOwned> {
     SetNx(redis_interface::SetnxReply),
     HSetNx(redis_interface::HsetnxReply),
     Scan(Vec<T>),
+    HDel(redis_interface::DelReply),
 }
 
 impl<T> std::fmt::Display for KvOperation<'_, T>
@@ -83,6 +259,8 @@ where
             KvOperation::HGet(_) => f.write_str("Hget"),
             KvOperation::Get => f.write_str("Get"),
             KvOperation::Scan(_) => f.write_str("Scan"),
+            KvOperation::ScanFromReplica(_) => f.write_str("ScanFromReplica"),
+            KvOperation::HDel(_, _) => f.write_str("Hdel"),
         }
     }
 }
@@ -106,8 +284,19 @@ where
     let ttl = store.ttl_for_kv;
 
     let partition_key = PartitionKey::MerchantIdPaymentIdCombination { combination: key };
+    let is_write = matches!(
+        op,
+        KvOperation::Hset(..)
+            | KvOperation::HSetNx(..)
+            | KvOperation::SetNx(..)
+            | KvOperation::HDel(..)
+    );
 
     let result = async {
+        if is_write && store.are_kv_writes_paused_for_key(key).await {
+            return Err(RedisError::KvWritesPaused).into_report();
+        }
+
         match op {
             KvOperation::Hset(value, sql) => {
                 logger::debug!(kv_operation= %operation, value = ?value);
@@ -135,6 +324,13 @@ where
                 Ok(KvResult::Scan(result))
             }
 
+            KvOperation::ScanFromReplica(pattern) => {
+                let result: Vec<T> = redis_conn
+                    .hscan_and_deserialize_from_replica(key, pattern, None)
+                    .await?;
+                Ok(KvResult::Scan(result))
+            }
+
             KvOperation::HSetNx(field, value, sql) => {
                 logger::debug!(kv_operation= %operation, value = ?value);
 
@@ -173,6 +369,19 @@ where
                 let result = redis_conn.get_and_deserialize_key(key, type_name).await?;
                 Ok(KvResult::Get(result))
             }
+
+            KvOperation::HDel(field, sql) => {
+                let result = redis_conn.delete_hash_field(key, field).await?;
+
+                // Rows which are no longer in redis are deleted from the database by the caller,
+                // the drainer only has to delete the ones which may still be inserted by it
+                if matches!(result, redis_interface::DelReply::KeyDeleted) {
+                    store
+                        .push_to_drainer_stream::<S>(sql, partition_key)
+                        .await?;
+                }
+                Ok(KvResult::HDel(result))
+            }
         }
     };
 
@@ -193,3 +402,64 @@ where
             err
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_merchant_ids_from_kv_key() {
+        assert_eq!(
+            get_merchant_ids_from_kv_key("mid_merchant_123_pid_pay_456"),
+            vec!["merchant_123"]
+        );
+        assert_eq!(
+            get_merchant_ids_from_kv_key("mid_merchant_123_cust_cus_456"),
+            vec!["merchant_123"]
+        );
+        assert_eq!(
+            get_merchant_ids_from_kv_key("mid_merchant_123_mandate_man_456"),
+            vec!["merchant_123"]
+        );
+        assert!(get_merchant_ids_from_kv_key("reverse_lookup_payment_method_pm_123").is_empty());
+        assert!(get_merchant_ids_from_kv_key("mid_merchant_123").is_empty());
+        assert!(get_merchant_ids_from_kv_key("mid__pid_pay_456").is_empty());
+    }
+
+    #[test]
+    fn test_get_merchant_ids_from_ambiguous_kv_key() {
+        // Either the payment `pay_cust_456` of `merchant_123` or the customer `456` of
+        // `merchant_123_pid_pay`
+        assert_eq!(
+            get_merchant_ids_from_kv_key("mid_merchant_123_pid_pay_cust_456"),
+            vec!["merchant_123", "merchant_123_pid_pay"]
+        );
+        // Kinds are only matched as a whole
+        assert_eq!(
+            get_merchant_ids_from_kv_key("mid_merchant_123_pid_pay_customer_456"),
+            vec!["merchant_123"]
+        );
+    }
+
+    #[test]
+    fn test_is_kv_key() {
+        assert!(is_kv_key("mid_merchant_123_pid_pay_456"));
+        assert!(is_kv_key("reverse_lookup_pa_merchant_123_pay_456_1"));
+        assert!(!is_kv_key("merchant_123"));
+        assert!(!is_kv_key("dsl_merchant_123"));
+    }
+
+    #[test]
+    fn test_get_merchant_kv_key_patterns() {
+        assert_eq!(
+            get_merchant_kv_key_patterns("merchant_*"),
+            vec![
+                r"mid_merchant_\*_pid_*",
+                r"mid_merchant_\*_cust_*",
+                r"mid_merchant_\*_mandate_*",
+                r"reverse_lookup_*merchant_\*_*",
+                "reverse_lookup_payment_method_*",
+            ]
+        );
+    }
+}
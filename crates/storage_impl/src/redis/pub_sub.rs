@@ -2,7 +2,10 @@ use error_stack::{IntoReport, ResultExt};
 use redis_interface::{errors as redis_errors, PubsubInterface, RedisValue};
 use router_env::logger;
 
-use crate::redis::cache::{CacheKind, ACCOUNTS_CACHE, CONFIG_CACHE};
+use crate::redis::cache::{
+    invalidate_matching_keys, is_merchant_key, named_caches, CacheKind, ACCOUNTS_CACHE,
+    CGRAPH_CACHE, CONFIG_CACHE, ROUTING_CACHE,
+};
 
 #[async_trait::async_trait]
 pub trait PubSubInterface {
@@ -61,28 +64,45 @@ impl PubSubInterface for redis_interface::RedisConnectionPool {
                 }
             };
 
-            let key = match key {
+            let keys = match key {
                 CacheKind::Config(key) => {
                     CONFIG_CACHE.invalidate(key.as_ref()).await;
-                    key
+                    vec![key.into_owned()]
                 }
                 CacheKind::Accounts(key) => {
                     ACCOUNTS_CACHE.invalidate(key.as_ref()).await;
-                    key
+                    vec![key.into_owned()]
+                }
+                CacheKind::Routing(key) => {
+                    ROUTING_CACHE.invalidate(key.as_ref()).await;
+                    vec![key.into_owned()]
+                }
+                CacheKind::CGraph(key) => {
+                    CGRAPH_CACHE.invalidate(key.as_ref()).await;
+                    vec![key.into_owned()]
                 }
                 CacheKind::All(key) => {
-                    CONFIG_CACHE.invalidate(key.as_ref()).await;
-                    ACCOUNTS_CACHE.invalidate(key.as_ref()).await;
-                    key
+                    for cache in named_caches() {
+                        cache.invalidate(key.as_ref()).await;
+                    }
+                    vec![key.into_owned()]
+                }
+                CacheKind::KeyPrefix(prefix) => {
+                    invalidate_matching_keys(|key| key.starts_with(prefix.as_ref())).await
+                }
+                CacheKind::Merchant(merchant_id) => {
+                    invalidate_matching_keys(|key| is_merchant_key(key, &merchant_id)).await
                 }
             };
 
-            self.delete_key(key.as_ref())
-                .await
-                .map_err(|err| logger::error!("Error while deleting redis key: {err:?}"))
-                .ok();
+            for key in &keys {
+                self.delete_key(key)
+                    .await
+                    .map_err(|err| logger::error!("Error while deleting redis key: {err:?}"))
+                    .ok();
+            }
 
-            logger::debug!("Done invalidating {key}");
+            logger::debug!("Done invalidating {keys:?}");
         }
         Ok(())
     }
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS webhook_delivery_attempt;
+
+DROP INDEX IF EXISTS events_merchant_id_primary_object_id_index;
+
+ALTER TABLE events DROP COLUMN IF EXISTS business_profile_id;
+
+ALTER TABLE events DROP COLUMN IF EXISTS merchant_id;
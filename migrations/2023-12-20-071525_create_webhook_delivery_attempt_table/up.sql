@@ -0,0 +1,24 @@
+-- Your SQL goes here
+ALTER TABLE events ADD COLUMN IF NOT EXISTS merchant_id VARCHAR(64);
+
+ALTER TABLE events ADD COLUMN IF NOT EXISTS business_profile_id VARCHAR(64);
+
+CREATE INDEX IF NOT EXISTS events_merchant_id_primary_object_id_index ON events (merchant_id, primary_object_id);
+
+CREATE TABLE IF NOT EXISTS webhook_delivery_attempt (
+    attempt_id VARCHAR(64) PRIMARY KEY,
+    event_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    attempt_number INTEGER NOT NULL,
+    delivery_trigger VARCHAR(64) NOT NULL,
+    request_url TEXT NOT NULL,
+    request_body TEXT NOT NULL,
+    response_status_code INTEGER,
+    response_body TEXT,
+    error_message TEXT,
+    latency_ms BIGINT NOT NULL,
+    is_delivered BOOLEAN NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS webhook_delivery_attempt_merchant_id_event_id_index ON webhook_delivery_attempt (merchant_id, event_id);
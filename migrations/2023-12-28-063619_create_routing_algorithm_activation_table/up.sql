@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS routing_algorithm_activation (
+    activation_id VARCHAR(64) PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    profile_id VARCHAR(64),
+    algorithm_id VARCHAR(64),
+    previous_algorithm_id VARCHAR(64),
+    action VARCHAR(64) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS routing_algorithm_activation_merchant_id_profile_id_index ON routing_algorithm_activation (merchant_id, profile_id, created_at);
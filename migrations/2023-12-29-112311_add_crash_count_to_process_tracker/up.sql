@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE process_tracker ADD COLUMN IF NOT EXISTS crash_count INTEGER NOT NULL DEFAULT 0;
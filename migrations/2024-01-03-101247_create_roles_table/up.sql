@@ -0,0 +1,16 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS roles (
+    id SERIAL PRIMARY KEY,
+    role_id VARCHAR(64) NOT NULL UNIQUE,
+    role_name VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    org_id VARCHAR(64) NOT NULL,
+    permissions TEXT[] NOT NULL,
+    created_by VARCHAR(64) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    last_modified_by VARCHAR(64) NOT NULL,
+    last_modified_at TIMESTAMP NOT NULL DEFAULT now(),
+    CONSTRAINT role_name_merchant_unique UNIQUE (role_name, merchant_id)
+);
+
+CREATE INDEX IF NOT EXISTS roles_merchant_id_index ON roles (merchant_id);
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS user_key_store;
+
+ALTER TABLE users
+DROP COLUMN IF EXISTS totp_status,
+DROP COLUMN IF EXISTS totp_secret,
+DROP COLUMN IF EXISTS totp_recovery_codes;
+
+DROP TYPE IF EXISTS "TotpStatus";
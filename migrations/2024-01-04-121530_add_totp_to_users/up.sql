@@ -0,0 +1,13 @@
+-- Your SQL goes here
+CREATE TYPE "TotpStatus" AS ENUM ('set', 'not_set');
+
+ALTER TABLE users
+ADD COLUMN IF NOT EXISTS totp_status "TotpStatus" NOT NULL DEFAULT 'not_set',
+ADD COLUMN IF NOT EXISTS totp_secret BYTEA DEFAULT NULL,
+ADD COLUMN IF NOT EXISTS totp_recovery_codes TEXT[] DEFAULT NULL;
+
+CREATE TABLE IF NOT EXISTS user_key_store (
+    user_id VARCHAR(64) PRIMARY KEY,
+    key BYTEA NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
@@ -0,0 +1,17 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS oidc_config (
+    org_id VARCHAR(32) PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    issuer_url TEXT NOT NULL,
+    client_id VARCHAR(255) NOT NULL,
+    client_secret BYTEA NOT NULL,
+    redirect_url TEXT NOT NULL,
+    groups_claim VARCHAR(64) NOT NULL DEFAULT 'groups',
+    role_mappings JSONB NOT NULL DEFAULT '[]'::JSONB,
+    default_role_id VARCHAR(64),
+    is_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by VARCHAR(64) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    last_modified_by VARCHAR(64) NOT NULL,
+    last_modified_at TIMESTAMP NOT NULL DEFAULT now()
+);
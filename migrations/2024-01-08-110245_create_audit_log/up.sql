@@ -0,0 +1,31 @@
+-- Your SQL goes here
+CREATE TYPE "AuditActorType" AS ENUM ('user', 'admin_api_key');
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    actor_type "AuditActorType" NOT NULL,
+    actor_id VARCHAR(64),
+    merchant_id VARCHAR(64),
+    flow VARCHAR(64) NOT NULL,
+    resource_type VARCHAR(64) NOT NULL,
+    resource_id VARCHAR(255),
+    request_id VARCHAR(64),
+    request JSONB,
+    snapshot_before JSONB,
+    snapshot_after JSONB,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS audit_log_merchant_id_created_at_index ON audit_log (merchant_id, created_at);
+
+CREATE INDEX IF NOT EXISTS audit_log_actor_id_index ON audit_log (actor_id);
+
+-- The log is append-only, the entries cannot be altered once written
+CREATE OR REPLACE FUNCTION audit_log_reject_modification() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log entries cannot be updated or deleted';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_log_append_only BEFORE UPDATE OR DELETE ON audit_log
+FOR EACH ROW EXECUTE PROCEDURE audit_log_reject_modification();
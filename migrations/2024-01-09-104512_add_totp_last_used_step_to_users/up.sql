@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE users
+ADD COLUMN IF NOT EXISTS totp_last_used_step BIGINT DEFAULT NULL;
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS oidc_identity (
+    id SERIAL PRIMARY KEY,
+    org_id VARCHAR(32) NOT NULL,
+    issuer TEXT NOT NULL,
+    subject VARCHAR(255) NOT NULL,
+    user_id VARCHAR(64) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS oidc_identity_org_id_issuer_subject_index ON oidc_identity (org_id, issuer, subject);
+
+CREATE UNIQUE INDEX IF NOT EXISTS oidc_identity_org_id_user_id_index ON oidc_identity (org_id, user_id);
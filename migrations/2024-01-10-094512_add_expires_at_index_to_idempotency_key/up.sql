@@ -0,0 +1,2 @@
+-- Your SQL goes here
+CREATE INDEX IF NOT EXISTS idempotency_key_expires_at_index ON idempotency_key (expires_at);